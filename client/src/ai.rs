@@ -0,0 +1,324 @@
+//! 부하 테스트와 벤치마크용 자동 대전 클라이언트 (`--bot`)
+//!
+//! 사람 대신 전략이 수를 고르고, 게임이 끝나면 새 게임에 다시 참가해 정한 수만큼 대전한 뒤
+//! 결과를 요약합니다. 연결 여러 개를 동시에 열면(`--games`) 봇끼리 짝이 지어져 여러 게임이 함께
+//! 진행되고, 한 연결이 실패해도 나머지는 끝까지 둡니다.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use rand::seq::SliceRandom;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::transport::Endpoint;
+use tonic::Request;
+
+use crate::tictactoe::play_request::Action;
+use crate::telemetry;
+use crate::tictactoe::{Heartbeat, Join, Move, PlayRequest};
+
+type AiResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+/// 미니맥스가 끝까지 읽는 최대 빈 칸 수 (그보다 많으면 `MINIMAX_DEPTH`수 앞까지만 읽음)
+const MINIMAX_FULL_SEARCH: usize = 9;
+/// 큰 보드에서 미니맥스가 읽는 수
+const MINIMAX_DEPTH: usize = 3;
+
+/// 봇의 수 선택 전략
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Strategy {
+    /// 빈 칸 중 무작위
+    #[value(alias = "easy")]
+    Random,
+    /// 이길 수 있으면 이기고, 상대의 승리를 막고, 그 외에는 무작위
+    Greedy,
+    /// 알파-베타 가지치기 미니맥스 (3x3에서는 지지 않음)
+    #[value(alias = "hard")]
+    Minimax,
+}
+
+impl Strategy {
+    /// `me`가 둘 칸 (빈 칸이 없으면 None)
+    pub fn choose(self, board: &[String], size: usize, win_length: usize, me: &str) -> Option<usize> {
+        let empty: Vec<usize> = (0..board.len()).filter(|&i| board[i].is_empty()).collect();
+        let opponent = opponent_of(me);
+        match self {
+            Strategy::Random => empty.choose(&mut rand::thread_rng()).copied(),
+            Strategy::Greedy => winning_cell(board, size, win_length, me)
+                .or_else(|| winning_cell(board, size, win_length, opponent))
+                .or_else(|| empty.choose(&mut rand::thread_rng()).copied()),
+            Strategy::Minimax => {
+                let depth = if empty.len() <= MINIMAX_FULL_SEARCH { empty.len() } else { MINIMAX_DEPTH };
+                let mut trial = board.to_vec();
+                let mut best = None;
+                let mut alpha = i32::MIN + 1;
+                for &pos in &empty {
+                    trial[pos] = me.to_string();
+                    let score = -negamax(&mut trial, size, win_length, opponent, depth - 1, -i32::MAX, -alpha);
+                    trial[pos].clear();
+                    if best.is_none() || score > alpha {
+                        alpha = score;
+                        best = Some(pos);
+                    }
+                }
+                best
+            }
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Strategy::Random => "random",
+            Strategy::Greedy => "greedy",
+            Strategy::Minimax => "minimax",
+        };
+        f.write_str(name)
+    }
+}
+
+fn opponent_of(symbol: &str) -> &'static str {
+    if symbol == "X" { "O" } else { "X" }
+}
+
+/// 방금 `to_move`의 상대가 둔 보드의 점수 (`to_move` 입장, 빨리 이길수록 높음)
+fn negamax(board: &mut [String], size: usize, win_length: usize, to_move: &str, depth: usize, mut alpha: i32, beta: i32) -> i32 {
+    if winner(board, size, win_length).is_some() {
+        // 직전에 둔 상대가 이김
+        return -(100 + depth as i32);
+    }
+    let empty: Vec<usize> = (0..board.len()).filter(|&i| board[i].is_empty()).collect();
+    if empty.is_empty() || depth == 0 {
+        return 0;
+    }
+    let mut best = i32::MIN + 1;
+    for pos in empty {
+        board[pos] = to_move.to_string();
+        let score = -negamax(board, size, win_length, opponent_of(to_move), depth - 1, -beta, -alpha);
+        board[pos].clear();
+        best = best.max(score);
+        alpha = alpha.max(score);
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+/// `symbol`이 한 수로 줄을 완성할 수 있는 빈 칸
+fn winning_cell(board: &[String], size: usize, win_length: usize, symbol: &str) -> Option<usize> {
+    let mut trial = board.to_vec();
+    (0..board.len()).find(|&i| {
+        if !board[i].is_empty() {
+            return false;
+        }
+        trial[i] = symbol.to_string();
+        let wins = winner(&trial, size, win_length).is_some();
+        trial[i].clear();
+        wins
+    })
+}
+
+/// `win_length`개 이상 연속으로 놓인 심볼 (가로, 세로, 두 대각선)
+pub fn winner(board: &[String], size: usize, win_length: usize) -> Option<&str> {
+    const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+    for start in 0..board.len() {
+        let symbol = board[start].as_str();
+        if symbol.is_empty() {
+            continue;
+        }
+        let (row, col) = ((start / size) as isize, (start % size) as isize);
+        for (dr, dc) in DIRECTIONS {
+            let complete = (1..win_length as isize).all(|step| {
+                let (r, c) = (row + dr * step, col + dc * step);
+                (0..size as isize).contains(&r) && (0..size as isize).contains(&c) && board[r as usize * size + c as usize] == symbol
+            });
+            if complete {
+                return Some(symbol);
+            }
+        }
+    }
+    None
+}
+
+/// 봇이 본 게임 결과
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Draw,
+}
+
+/// 여러 게임의 결과 요약
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Summary {
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    /// 끝까지 두지 못한 게임 (스트림이 끊기거나 서버가 거부함)
+    pub failed: u32,
+    pub elapsed: Duration,
+}
+
+impl Summary {
+    pub fn played(&self) -> u32 {
+        self.wins + self.losses + self.draws
+    }
+
+    /// 다른 연결의 결과를 더함 (걸린 시간은 더 긴 쪽)
+    pub fn merge(&mut self, other: &Summary) {
+        self.wins += other.wins;
+        self.losses += other.losses;
+        self.draws += other.draws;
+        self.failed += other.failed;
+        self.elapsed = self.elapsed.max(other.elapsed);
+    }
+
+    fn record(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Win => self.wins += 1,
+            Outcome::Loss => self.losses += 1,
+            Outcome::Draw => self.draws += 1,
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Played {} games in {:.2}s: {} wins, {} losses, {} draws",
+            self.played(),
+            self.elapsed.as_secs_f64(),
+            self.wins,
+            self.losses,
+            self.draws
+        )?;
+        if self.failed > 0 {
+            write!(f, ", {} failed", self.failed)?;
+        }
+        Ok(())
+    }
+}
+
+/// 자동 대전 클라이언트: 자기 차례인 진행 중 업데이트를 받으면 전략이 고른 수를 보냄
+#[derive(Clone)]
+pub struct AiClient {
+    endpoint: Endpoint,
+    strategy: Strategy,
+    player_id: String,
+    /// 수를 보내기 전에 기다리는 시간 (사람이 두는 것처럼 보이게 하는 데모용)
+    move_delay: Duration,
+}
+
+impl AiClient {
+    pub fn new(endpoint: Endpoint, strategy: Strategy) -> Self {
+        AiClient { endpoint, strategy, player_id: String::new(), move_delay: Duration::ZERO }
+    }
+
+    /// 수마다 `delay`만큼 기다렸다가 보냄
+    pub fn with_move_delay(mut self, delay: Duration) -> Self {
+        self.move_delay = delay;
+        self
+    }
+
+    /// 레이팅을 기록할 플레이어 ID
+    pub fn with_player_id(mut self, player_id: impl Into<String>) -> Self {
+        self.player_id = player_id.into();
+        self
+    }
+
+    /// `num_games`번 연달아 새 게임에 참가해 끝까지 두고 결과를 요약
+    pub async fn run(&self, num_games: u32) -> Summary {
+        let started = Instant::now();
+        let mut summary = Summary::default();
+        for game in 1..=num_games {
+            match self.play_game().await {
+                Ok(outcome) => summary.record(outcome),
+                Err(e) => {
+                    tracing::warn!(game, error = %e, "bot game failed");
+                    summary.failed += 1;
+                }
+            }
+        }
+        summary.elapsed = started.elapsed();
+        summary
+    }
+
+    /// 연결 `connections`개가 동시에 각자 `num_games`번씩 두고 결과를 합쳐 요약
+    /// (한 연결이 실패하거나 멈춰도 다른 연결은 계속 둠)
+    pub async fn run_concurrent(&self, connections: u32, num_games: u32) -> Summary {
+        let started = Instant::now();
+        let tasks: Vec<_> = (0..connections)
+            .map(|_| {
+                let bot = self.clone();
+                tokio::spawn(async move { bot.run(num_games).await })
+            })
+            .collect();
+        let mut summary = Summary::default();
+        for (connection, task) in tasks.into_iter().enumerate() {
+            match task.await {
+                Ok(result) => summary.merge(&result),
+                Err(e) => {
+                    tracing::warn!(connection, error = %e, "bot connection task failed");
+                    summary.failed += num_games;
+                }
+            }
+        }
+        summary.elapsed = started.elapsed();
+        summary
+    }
+
+    /// 빈 자리가 있는 게임에 참가해(없으면 새 게임) 끝날 때까지 둠
+    pub async fn play_game(&self) -> AiResult<Outcome> {
+        let mut client = telemetry::traced(self.endpoint.connect().await?);
+        let (tx, rx) = mpsc::channel(8);
+        let join = Join { player_id: self.player_id.clone(), ..Join::default() };
+        tx.send(PlayRequest { action: Some(Action::Join(join)) }).await?;
+        let mut updates = client.play(Request::new(ReceiverStream::new(rx))).await?.into_inner();
+
+        let mut symbol = String::new();
+        while let Some(state) = updates.message().await? {
+            if state.heartbeat {
+                tx.send(PlayRequest { action: Some(Action::Heartbeat(Heartbeat {})) }).await?;
+                continue;
+            }
+            if !state.your_symbol.is_empty() {
+                symbol = state.your_symbol.clone();
+            }
+            if let Some(outcome) = outcome_of(&state.status, &symbol) {
+                return Ok(outcome);
+            }
+            if state.status == "ongoing" && state.next_player == symbol {
+                let size = match state.board_size {
+                    0 => 3,
+                    size => size as usize,
+                };
+                let win_length = match state.win_length {
+                    0 => 3,
+                    len => len as usize,
+                };
+                let Some(position) = self.strategy.choose(&state.board, size, win_length, &symbol) else {
+                    continue;
+                };
+                if !self.move_delay.is_zero() {
+                    tokio::time::sleep(self.move_delay).await;
+                }
+                let mv = Move { player_id: symbol.clone(), position: position as i32, ..Move::default() };
+                tx.send(PlayRequest { action: Some(Action::Move(mv)) }).await?;
+            }
+        }
+        Err("the server closed the game stream before the game finished".into())
+    }
+}
+
+/// 끝난 게임의 상태를 `symbol` 입장의 결과로 (아직 안 끝났으면 None)
+fn outcome_of(status: &str, symbol: &str) -> Option<Outcome> {
+    if status.starts_with("draw") {
+        return Some(Outcome::Draw);
+    }
+    let winner = status.split_once("_win").map(|(winner, _)| winner)?;
+    Some(if winner == symbol { Outcome::Win } else { Outcome::Loss })
+}
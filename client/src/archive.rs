@@ -0,0 +1,252 @@
+//! 끝난 게임을 로컬에 보관하는 아카이브
+//!
+//! 데이터 디렉터리의 `archive.jsonl`에 게임 하나당 한 줄(JSON)로 저장합니다. 검색용 인덱스는
+//! 필요할 때마다 파일을 읽어 메모리에 다시 만들며, 읽을 수 없는 줄은 건너뜁니다.
+
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::tictactoe::GameState;
+use common::fs::atomic_write;
+
+/// 데이터 디렉터리를 바꾸는 환경 변수
+const DATA_DIR_ENV: &str = "TICTACTOE_DATA_DIR";
+/// 아카이브 파일 이름
+const ARCHIVE_FILE: &str = "archive.jsonl";
+
+/// 아카이브에 저장된 게임 한 판
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    pub id: u64,
+    pub date: String,          // 게임이 끝난 시각 (RFC 3339, UTC)
+    pub game_id: String,       // 서버의 게임 ID
+    pub symbol: String,        // 내 심볼 ("X" 또는 "O")
+    pub opponent: String,      // "human" 또는 "house-bot"
+    pub result: String,        // "win", "loss", "draw"
+    pub status: String,        // 서버가 보낸 최종 상태 (예: "X_win_by_resignation")
+    pub moves: Vec<u8>,        // 둔 순서대로의 칸 번호 (X부터 번갈아 둠)
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default = "default_board_size")]
+    pub board_size: usize,     // 보드 한 변의 칸 수 (크기 지정 전에 저장된 게임은 3)
+}
+
+fn default_board_size() -> usize {
+    3
+}
+
+impl ArchiveEntry {
+    /// 표준 기보 표기 (예: "4 0 8 2")
+    pub fn notation(&self) -> String {
+        self.moves.iter().map(|m| m.to_string()).collect::<Vec<_>>().join(" ")
+    }
+
+    /// 첫 수의 칸 번호
+    pub fn opening(&self) -> Option<u8> {
+        self.moves.first().copied()
+    }
+
+    /// 수마다의 보드 상태 (재생용, 마지막 원소가 최종 보드)
+    pub fn boards(&self) -> Vec<Vec<String>> {
+        let mut board = vec![String::new(); self.board_size * self.board_size];
+        let mut boards = Vec::with_capacity(self.moves.len());
+        for (turn, &pos) in self.moves.iter().enumerate() {
+            if let Some(cell) = board.get_mut(pos as usize) {
+                *cell = if turn % 2 == 0 { "X" } else { "O" }.to_string();
+            }
+            boards.push(board.clone());
+        }
+        boards
+    }
+}
+
+/// 검색 조건 (지정한 조건을 모두 만족하는 게임만 남김)
+#[derive(Debug, Default, Clone)]
+pub struct SearchFilter {
+    pub opponent: Option<String>,
+    pub result: Option<String>,
+    pub opening: Option<u8>,
+    pub tag: Option<String>,
+}
+
+impl SearchFilter {
+    fn matches(&self, entry: &ArchiveEntry) -> bool {
+        self.opponent.as_ref().is_none_or(|o| entry.opponent.eq_ignore_ascii_case(o))
+            && self.result.as_ref().is_none_or(|r| entry.result.eq_ignore_ascii_case(r))
+            && self.opening.is_none_or(|o| entry.opening() == Some(o))
+            && self.tag.as_ref().is_none_or(|t| entry.tags.iter().any(|tag| tag == t))
+    }
+}
+
+/// 아카이브 파일에서 읽어 만든 메모리 인덱스
+#[derive(Debug, Default)]
+pub struct ArchiveIndex {
+    pub entries: Vec<ArchiveEntry>,
+    pub skipped: usize, // 읽을 수 없어 건너뛴 줄 수
+}
+
+impl ArchiveIndex {
+    pub fn get(&self, id: u64) -> Option<&ArchiveEntry> {
+        self.entries.iter().find(|e| e.id == id)
+    }
+
+    pub fn search(&self, filter: &SearchFilter) -> Vec<&ArchiveEntry> {
+        self.entries.iter().filter(|e| filter.matches(e)).collect()
+    }
+
+    fn next_id(&self) -> u64 {
+        self.entries.iter().map(|e| e.id).max().unwrap_or(0) + 1
+    }
+}
+
+/// 아카이브 파일
+pub struct Archive {
+    path: PathBuf,
+}
+
+impl Archive {
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        Archive { path: path.into() }
+    }
+
+    /// 기본 위치의 아카이브 (`TICTACTOE_DATA_DIR`, 없으면 OS 데이터 디렉터리 아래 `tictactoe`)
+    pub fn open_default() -> io::Result<Self> {
+        let dir = match std::env::var_os(DATA_DIR_ENV) {
+            Some(dir) => PathBuf::from(dir),
+            None => dirs::data_dir()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?
+                .join("tictactoe"),
+        };
+        Ok(Archive::open(dir.join(ARCHIVE_FILE)))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 파일 내용 (아직 없으면 빈 문자열)
+    fn read(&self) -> io::Result<String> {
+        match std::fs::read_to_string(&self.path) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+            other => other,
+        }
+    }
+
+    /// 파일을 읽어 인덱스를 만듭니다. 손상된 줄은 건너뛰고 개수만 셉니다.
+    pub fn load(&self) -> io::Result<ArchiveIndex> {
+        let mut index = ArchiveIndex::default();
+        for line in self.read()?.lines().filter(|l| !l.trim().is_empty()) {
+            match serde_json::from_str::<ArchiveEntry>(line) {
+                Ok(entry) => index.entries.push(entry),
+                Err(_) => index.skipped += 1,
+            }
+        }
+        Ok(index)
+    }
+
+    /// 게임을 추가하고 새로 붙인 ID를 반환 (entry.id는 무시됨)
+    pub fn append(&self, mut entry: ArchiveEntry) -> io::Result<u64> {
+        let mut contents = self.read()?;
+        entry.id = self.load()?.next_id();
+        if !contents.is_empty() && !contents.ends_with('\n') {
+            contents.push('\n');
+        }
+        contents.push_str(&serde_json::to_string(&entry).map_err(io::Error::other)?);
+        contents.push('\n');
+        atomic_write(&self.path, contents.as_bytes())?;
+        Ok(entry.id)
+    }
+
+    /// 게임에 태그 추가 (게임이 없으면 false). 손상된 줄은 그대로 보존합니다.
+    pub fn tag(&self, id: u64, tag: &str) -> io::Result<bool> {
+        let mut found = false;
+        let mut contents = String::new();
+        for line in self.read()?.lines() {
+            match serde_json::from_str::<ArchiveEntry>(line) {
+                Ok(mut entry) if entry.id == id => {
+                    found = true;
+                    if !entry.tags.iter().any(|t| t == tag) {
+                        entry.tags.push(tag.to_string());
+                    }
+                    contents.push_str(&serde_json::to_string(&entry).map_err(io::Error::other)?);
+                }
+                _ => contents.push_str(line),
+            }
+            contents.push('\n');
+        }
+        if found {
+            atomic_write(&self.path, contents.as_bytes())?;
+        }
+        Ok(found)
+    }
+}
+
+/// 진행 중인 게임의 업데이트를 받아 수순을 기록합니다.
+#[derive(Debug)]
+pub struct GameRecorder {
+    board: Vec<String>,
+    moves: Vec<u8>,
+    against_bot: bool,
+}
+
+impl Default for GameRecorder {
+    fn default() -> Self {
+        GameRecorder::new()
+    }
+}
+
+impl GameRecorder {
+    pub fn new() -> Self {
+        GameRecorder { board: Vec::new(), moves: Vec::new(), against_bot: false }
+    }
+
+    /// 업데이트의 보드를 이전 보드와 비교해 새로 채워진 칸을 수순에 추가
+    pub fn observe(&mut self, state: &GameState) {
+        if state.board.is_empty() || state.status == "error" {
+            return;
+        }
+        if self.board.len() != state.board.len() {
+            // 첫 업데이트에서 보드 크기가 정해짐
+            self.board = vec![String::new(); state.board.len()];
+        }
+        if state.status == "ongoing" && !state.rated {
+            // 비레이팅 게임은 서버 봇과의 대전
+            self.against_bot = true;
+        }
+        for (pos, (old, new)) in self.board.iter().zip(&state.board).enumerate() {
+            if old.is_empty() && !new.is_empty() {
+                self.moves.push(pos as u8);
+            }
+        }
+        self.board = state.board.clone();
+    }
+
+    /// 최종 상태로 아카이브 항목 생성 (ID는 추가할 때 정해짐).
+    /// 서버가 보낸 기보가 있으면 보드 비교로 추정한 수순 대신 그것을 사용합니다.
+    pub fn finish(&self, state: &GameState, symbol: &str) -> ArchiveEntry {
+        let result = if state.status.starts_with("draw") {
+            "draw"
+        } else if state.status.starts_with(&format!("{}_win", symbol)) {
+            "win"
+        } else {
+            "loss"
+        };
+        ArchiveEntry {
+            id: 0,
+            date: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            game_id: state.game_id.clone(),
+            symbol: symbol.to_string(),
+            opponent: if self.against_bot { "house-bot" } else { "human" }.to_string(),
+            result: result.to_string(),
+            status: state.status.clone(),
+            moves: if state.history.is_empty() {
+                self.moves.clone()
+            } else {
+                state.history.iter().map(|m| m.position as u8).collect()
+            },
+            tags: Vec::new(),
+            board_size: if state.board_size > 0 { state.board_size as usize } else { default_board_size() },
+        }
+    }
+}
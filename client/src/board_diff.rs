@@ -0,0 +1,30 @@
+//! 서버가 보낸 보드 차이 반영 (Join.board_diffs)
+//!
+//! 보드 차이를 요청하면 접속한 뒤 첫 업데이트만 보드 전체(`full_board`)이고, 그다음부터는 서버가 이 연결로
+//! 마지막으로 보낸 보드에서 바뀐 칸만 옵니다. 받은 업데이트를 모두 순서대로 반영하면 서버의 보드와 같아집니다.
+
+use common::state_hash::state_hash;
+
+use crate::tictactoe::GameState;
+
+/// `update`를 지금까지 받은 `board`에 반영하고, `update.board`도 반영한 보드 전체로 채움
+/// (보드가 없는 하트비트 같은 업데이트는 둘 다 그대로 둠)
+pub fn apply(board: &mut Vec<String>, update: &mut GameState) {
+    match &update.diff {
+        Some(diff) if !update.full_board => {
+            for change in &diff.changes {
+                if let Some(cell) = usize::try_from(change.position).ok().and_then(|position| board.get_mut(position)) {
+                    *cell = change.new_value.clone();
+                }
+            }
+            update.board = board.clone();
+        }
+        _ if !update.board.is_empty() => *board = update.board.clone(),
+        _ => {}
+    }
+}
+
+/// 반영한 보드와 상태가 서버가 보낸 해시(`state_hash`)와 맞는지 (해시가 없는 오류 업데이트나 하트비트는 맞는 것으로 봄)
+pub fn in_sync(update: &GameState) -> bool {
+    update.state_hash.is_empty() || update.state_hash == state_hash(&update.board, &update.status, &update.next_player)
+}
@@ -0,0 +1,73 @@
+//! gRPC 메시지 압축 (`--compression`)
+//!
+//! 클라이언트는 빌드에 들어 있는 모든 방식(gzip, `zstd` 기능을 켜고 빌드하면 zstd도)으로 압축한 응답을 받겠다고
+//! 알리고, 요청은 고른 방식으로 압축해 보냅니다. 압축 방식은 시작할 때 [`install`]로 한 번 정하며, 이후
+//! [`crate::telemetry::traced`]로 만드는 모든 클라이언트에 적용됩니다 (정하지 않으면 요청을 압축하지 않음).
+
+use std::sync::OnceLock;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use tonic::codec::CompressionEncoding;
+
+use crate::telemetry::TracedClient;
+
+/// 시작할 때 고른 요청 압축 방식
+static REQUESTS: OnceLock<Option<CompressionEncoding>> = OnceLock::new();
+
+/// 보내는 메시지의 압축 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    #[default]
+    Gzip,
+    /// `zstd` 기능을 켜고 빌드해야 쓸 수 있음
+    Zstd,
+    None,
+}
+
+impl Compression {
+    /// 보낼 때 쓸 tonic 압축 방식 (None이면 압축하지 않음, 빌드에 없는 방식이면 에러)
+    pub fn encoding(self) -> Result<Option<CompressionEncoding>, String> {
+        match self {
+            Compression::Gzip => Ok(Some(CompressionEncoding::Gzip)),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Ok(Some(CompressionEncoding::Zstd)),
+            #[cfg(not(feature = "zstd"))]
+            Compression::Zstd => Err("zstd compression needs a client built with --features zstd".into()),
+            Compression::None => Ok(None),
+        }
+    }
+}
+
+/// 받을 수 있는 압축 방식 (빌드에 들어 있는 방식 전부)
+pub fn accepted() -> Vec<CompressionEncoding> {
+    #[allow(unused_mut)]
+    let mut encodings = vec![CompressionEncoding::Gzip];
+    #[cfg(feature = "zstd")]
+    encodings.push(CompressionEncoding::Zstd);
+    encodings
+}
+
+/// 이후 만드는 클라이언트의 요청 압축 방식을 정함 (두 번째부터는 무시)
+pub fn install(compression: Compression) -> Result<(), String> {
+    let encoding = compression.encoding()?;
+    let _ = REQUESTS.set(encoding);
+    Ok(())
+}
+
+/// [`install`]로 정한 요청 압축 방식 (정하지 않았으면 None)
+pub fn installed() -> Option<CompressionEncoding> {
+    REQUESTS.get().copied().flatten()
+}
+
+/// 압축한 응답은 방식과 관계없이 받고, 요청은 `encoding`으로 압축하는 클라이언트
+pub fn client(mut client: TracedClient, encoding: Option<CompressionEncoding>) -> TracedClient {
+    for accepted in accepted() {
+        client = client.accept_compressed(accepted);
+    }
+    match encoding {
+        Some(encoding) => client.send_compressed(encoding),
+        None => client,
+    }
+}
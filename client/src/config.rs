@@ -0,0 +1,243 @@
+//! 클라이언트 설정 (TOML 파일 + 명령줄 인자)
+//!
+//! `--config`로 준 파일, 없으면 설정 디렉터리의 `tictactoe/client.toml`을 읽고,
+//! 명령줄에서 준 값이 있으면 파일의 값을 덮어씁니다.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint};
+
+use crate::compression::Compression;
+use crate::retry::{RetryPolicy, CONNECT_TIMEOUT};
+
+/// 기본 서버 주소
+pub const DEFAULT_SERVER_ADDR: &str = "http://[::1]:50051";
+/// 기본 하트비트 간격 (초)
+pub const DEFAULT_HEARTBEAT_INTERVAL_SECS: u64 = 15;
+/// 경로를 지정하지 않았을 때 설정 디렉터리에서 찾는 파일
+const CONFIG_FILE: &str = "client.toml";
+
+type ConfigResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+/// 클라이언트 설정 (빠진 항목은 기본값 사용)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClientConfig {
+    /// 접속할 서버 주소 (TLS를 쓰려면 https://)
+    pub server: String,
+    /// 플레이어 이름 (수와 접속 메타데이터에 함께 보냄, 비어 있으면 심볼을 보냄)
+    pub name: String,
+    /// 레이팅을 기록할 플레이어 ID (없으면 결과가 레이팅에 반영되지 않음)
+    pub player_id: String,
+    /// 먼저 앉을 때 원하는 심볼 ("X" 또는 "O", 비어 있으면 상관없음)
+    pub symbol: String,
+    /// 보드에 내 자리 대신 표시할 글자 (이모지 등 그래핌 하나, 비어 있으면 "X"/"O")
+    pub marker: String,
+    /// 서버 인증서를 검증할 CA 인증서 (PEM, 있으면 TLS로 접속, 서버 주소는 https://)
+    pub tls_ca: Option<PathBuf>,
+    /// TLS로 접속할 때 SNI로 보내고 서버 인증서와 맞춰 볼 이름 (없으면 서버 주소의 호스트)
+    pub tls_domain: Option<String>,
+    /// 로비 없이 바로 관전할 게임 ID
+    pub spectate: Option<String>,
+    /// 게임 중 서버에 하트비트를 보내는 간격 (초, 0이면 서버가 보낸 하트비트에만 응답)
+    pub heartbeat_interval_secs: u64,
+    /// 접속 재시도 정책
+    pub reconnect: ReconnectConfig,
+    /// 요청 압축 방식 ("gzip", "zstd", "none", 서버가 받지 못하는 방식이면 그 요청은 실패)
+    pub compression: Compression,
+}
+
+/// 접속 재시도 설정
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReconnectConfig {
+    /// 서버 접속을 시도할 최대 횟수 (1이면 재시도 없음)
+    pub attempts: u32,
+    /// 첫 재시도 전 대기 시간 (밀리초, 실패할 때마다 두 배)
+    pub initial_delay_ms: u64,
+    /// 재시도 간격의 상한 (밀리초)
+    pub max_delay_ms: u64,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        ClientConfig {
+            server: DEFAULT_SERVER_ADDR.into(),
+            name: String::new(),
+            player_id: String::new(),
+            symbol: String::new(),
+            marker: String::new(),
+            tls_ca: None,
+            tls_domain: None,
+            spectate: None,
+            heartbeat_interval_secs: DEFAULT_HEARTBEAT_INTERVAL_SECS,
+            reconnect: ReconnectConfig::default(),
+            compression: Compression::default(),
+        }
+    }
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        let policy = RetryPolicy::default();
+        ReconnectConfig {
+            attempts: policy.max_attempts,
+            initial_delay_ms: policy.initial_delay.as_millis() as u64,
+            max_delay_ms: policy.max_delay.as_millis() as u64,
+        }
+    }
+}
+
+/// 설정 파일보다 우선하는 명령줄 인자
+#[derive(Debug, Clone, Default, Args)]
+pub struct ConfigOverrides {
+    /// 설정 파일 (없으면 설정 디렉터리의 tictactoe/client.toml, 그것도 없으면 기본값)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+    /// 접속할 서버 주소
+    #[arg(long, global = true, value_name = "URL")]
+    pub server: Option<String>,
+    /// 플레이어 이름
+    #[arg(long, global = true)]
+    pub name: Option<String>,
+    /// 레이팅을 기록할 플레이어 ID (없으면 결과가 레이팅에 반영되지 않음)
+    #[arg(long, global = true)]
+    pub player_id: Option<String>,
+    /// 원하는 심볼 (먼저 앉는 플레이어에게만 적용되고, 상대가 먼저 골랐으면 남은 심볼을 받음)
+    #[arg(long, global = true, value_parser = ["X", "O"], ignore_case = true)]
+    pub symbol: Option<String>,
+    /// 보드에 내 자리 대신 표시할 글자 (예: 🦊, 상대와 같으면 서버가 거부)
+    #[arg(long, global = true, value_name = "GLYPH")]
+    pub marker: Option<String>,
+    /// 서버 인증서를 검증할 CA 인증서 (PEM, 주면 TLS로 접속)
+    #[arg(long, global = true, value_name = "PATH", alias = "ca-cert")]
+    pub tls_ca: Option<PathBuf>,
+    /// 서버 인증서에 적힌 이름 (IP 주소로 접속하거나 이름이 다를 때)
+    #[arg(long = "domain", global = true, value_name = "NAME")]
+    pub tls_domain: Option<String>,
+    /// 로비 없이 바로 게임 관전 (GAME_ID를 생략하면 가장 최근에 시작한 진행 중인 게임)
+    #[arg(long, value_name = "GAME_ID", num_args = 0..=1, default_missing_value = "")]
+    pub spectate: Option<String>,
+    /// 서버 접속을 시도할 최대 횟수 (0.5초부터 두 배씩, 최대 15초 간격)
+    #[arg(long, global = true)]
+    pub connect_attempts: Option<u32>,
+    /// 서버에 접속하지 못하면 다시 시도하지 않고 바로 종료 (스크립트용)
+    #[arg(long, global = true)]
+    pub no_retry: bool,
+    /// 게임 중 서버에 하트비트를 보내는 간격 (초, 0이면 보내지 않음)
+    #[arg(long, global = true, value_name = "SECS")]
+    pub heartbeat_secs: Option<u64>,
+    /// 요청 압축 방식 (응답은 서버가 고른 방식으로 압축되어 와도 받음)
+    #[arg(long, global = true, value_enum)]
+    pub compression: Option<Compression>,
+}
+
+impl ClientConfig {
+    /// 설정 로드: `path`가 주어지면 그 파일을, 아니면 기본 위치의 파일을 읽고, 그것도 없으면 기본값
+    pub fn load(path: Option<&Path>) -> ConfigResult<Self> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => match dirs::config_dir().map(|dir| dir.join("tictactoe").join(CONFIG_FILE)) {
+                Some(path) if path.exists() => path,
+                _ => return Ok(ClientConfig::default()),
+            },
+        };
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| format!("could not read config file {}: {}", path.display(), e))?;
+        let config = toml::from_str(&text).map_err(|e| format!("invalid config file {}: {}", path.display(), e))?;
+        Ok(config)
+    }
+
+    /// `--config`로 지정한(또는 기본 위치의) 파일을 읽고 명령줄 값으로 덮어씀
+    pub fn from_overrides(overrides: &ConfigOverrides) -> ConfigResult<Self> {
+        let mut config = ClientConfig::load(overrides.config.as_deref())?;
+        config.apply(overrides);
+        Ok(config)
+    }
+
+    /// 명령줄에서 준 값으로 덮어쓰기
+    pub fn apply(&mut self, overrides: &ConfigOverrides) {
+        if let Some(server) = &overrides.server {
+            self.server = server.clone();
+        }
+        if let Some(name) = &overrides.name {
+            self.name = name.trim().to_string();
+        }
+        if let Some(player_id) = &overrides.player_id {
+            self.player_id = player_id.trim().to_string();
+        }
+        if let Some(symbol) = &overrides.symbol {
+            self.symbol = symbol.to_ascii_uppercase();
+        }
+        if let Some(marker) = &overrides.marker {
+            self.marker = marker.trim().to_string();
+        }
+        if let Some(tls_ca) = &overrides.tls_ca {
+            self.tls_ca = Some(tls_ca.clone());
+        }
+        if let Some(domain) = &overrides.tls_domain {
+            self.tls_domain = Some(domain.trim().to_string());
+        }
+        if let Some(game_id) = &overrides.spectate {
+            self.spectate = Some(game_id.clone());
+        }
+        if let Some(attempts) = overrides.connect_attempts {
+            self.reconnect.attempts = attempts;
+        }
+        if overrides.no_retry {
+            self.reconnect.attempts = 1;
+        }
+        if let Some(secs) = overrides.heartbeat_secs {
+            self.heartbeat_interval_secs = secs;
+        }
+        if let Some(compression) = overrides.compression {
+            self.compression = compression;
+        }
+    }
+
+    /// 게임 중 하트비트를 보내는 간격 (끄면 None)
+    pub fn heartbeat_interval(&self) -> Option<Duration> {
+        (self.heartbeat_interval_secs > 0).then(|| Duration::from_secs(self.heartbeat_interval_secs))
+    }
+
+    /// 접속 재시도 정책
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: self.reconnect.attempts.max(1),
+            initial_delay: Duration::from_millis(self.reconnect.initial_delay_ms),
+            max_delay: Duration::from_millis(self.reconnect.max_delay_ms),
+        }
+    }
+
+    /// 서버 엔드포인트 (tls_ca가 있으면 그 CA로 서버 인증서를 검증하는 TLS, 없으면 평문).
+    /// 설정이 맞지 않으면 접속하기 전에 무엇이 문제인지 알려 줌
+    pub fn endpoint(&self) -> ConfigResult<Endpoint> {
+        let mut endpoint = Endpoint::from_shared(self.server.clone())?.connect_timeout(CONNECT_TIMEOUT);
+        let Some(path) = &self.tls_ca else {
+            if self.tls_domain.is_some() {
+                return Err("--domain only applies to TLS connections; also pass --ca-cert".into());
+            }
+            return Ok(endpoint);
+        };
+        if !self.server.starts_with("https://") {
+            return Err(format!("TLS needs an https:// server address (got {})", self.server).into());
+        }
+        let pem = std::fs::read(path).map_err(|e| format!("could not read CA certificate {}: {}", path.display(), e))?;
+        if !String::from_utf8_lossy(&pem).contains("-----BEGIN CERTIFICATE-----") {
+            return Err(format!("CA certificate {} is not a PEM certificate", path.display()).into());
+        }
+        let mut tls = ClientTlsConfig::new().ca_certificate(Certificate::from_pem(pem));
+        if let Some(domain) = &self.tls_domain {
+            tls = tls.domain_name(domain.clone());
+        }
+        // 인증서 내용은 여기서 파싱되므로, 잘못된 파일이면 "transport error" 대신 원인과 파일을 알려 줌
+        endpoint = endpoint.tls_config(tls).map_err(|e| {
+            let cause = std::error::Error::source(&e).map_or_else(|| e.to_string(), |cause| cause.to_string());
+            format!("invalid CA certificate {}: {}", path.display(), cause)
+        })?;
+        Ok(endpoint)
+    }
+}
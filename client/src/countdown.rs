@@ -0,0 +1,77 @@
+//! 차례 제한 시간 카운트다운 (`GameState.turn_deadline_seconds`)
+//!
+//! 서버가 보낸 남은 시간은 업데이트를 보낸 시점의 값이므로, 받은 시각부터 [`Countdown`]이 직접 줄여 나갑니다.
+//! 남은 시간이 [`WARNING_SECS`]초 이하가 되면 한 번만 터미널 벨을 울리고, 새 업데이트가 오면 그 값으로 다시 시작합니다.
+//! 시간을 넘겨 진 판(`GameState.lost_on_time`)의 결과 문구도 여기서 만듭니다.
+
+use std::time::{Duration, Instant};
+
+use crate::tictactoe::GameState;
+
+/// 벨을 울리는 남은 시간 (초)
+pub const WARNING_SECS: u64 = 10;
+/// 터미널 벨
+pub const BELL: &str = "\x07";
+
+/// 내 차례의 남은 시간
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Countdown {
+    deadline: Instant,
+    move_number: u32, // 카운트다운을 시작한 업데이트의 상태 번호 (채팅처럼 상태가 그대로인 업데이트를 알아봄)
+    warned: bool,     // 이 차례에 이미 벨을 울렸는지
+}
+
+impl Countdown {
+    /// 업데이트가 내 차례이고 제한 시간이 있으면 `now`부터 카운트다운 (관전 중이거나 제한이 없으면 None).
+    /// 상태가 그대로인 업데이트(같은 차례)라면 `current`에서 벨을 울렸는지를 이어받음
+    pub fn update(current: Option<&Countdown>, state: &GameState, spectating: bool, now: Instant) -> Option<Countdown> {
+        let my_turn = !spectating && state.status == "ongoing" && !state.your_symbol.is_empty() && state.next_player == state.your_symbol;
+        if !my_turn || state.turn_deadline_seconds == 0 {
+            return None;
+        }
+        let warned = current.is_some_and(|c| c.warned && c.move_number == state.move_number);
+        let deadline = now + Duration::from_secs(state.turn_deadline_seconds.into());
+        Some(Countdown { deadline, move_number: state.move_number, warned })
+    }
+
+    /// 남은 시간 (초, 올림)
+    pub fn seconds_left(&self, now: Instant) -> u64 {
+        self.deadline.saturating_duration_since(now).as_millis().div_ceil(1000) as u64
+    }
+
+    /// 다음에 남은 초가 바뀔 때까지의 시간 (다 지났으면 0)
+    pub fn until_next_tick(&self, now: Instant) -> Duration {
+        let left = self.deadline.saturating_duration_since(now);
+        match left.as_millis() % 1000 {
+            0 if left.is_zero() => Duration::ZERO,
+            0 => Duration::from_secs(1),
+            millis => Duration::from_millis(millis as u64),
+        }
+    }
+
+    /// 상태 줄 ("Your turn — 37s left")
+    pub fn line(&self, now: Instant) -> String {
+        format!("Your turn \u{2014} {}s left", self.seconds_left(now))
+    }
+
+    /// 벨을 울릴 때인지 (남은 시간이 `WARNING_SECS`초 이하가 된 뒤 처음 한 번만 true)
+    pub fn take_warning(&mut self, now: Instant) -> bool {
+        if self.warned || self.seconds_left(now) > WARNING_SECS {
+            return false;
+        }
+        self.warned = true;
+        true
+    }
+}
+
+/// 시간을 넘겨 끝난 판의 결과 문구 (시간패가 아니면 None)
+pub fn lost_on_time_line(state: &GameState, spectating: bool) -> Option<String> {
+    let loser = state.lost_on_time.as_str();
+    let line = match loser {
+        "" => return None,
+        _ if spectating || state.your_symbol.is_empty() => format!("{} lost on time", loser),
+        _ if loser == state.your_symbol => "You lost on time".to_string(),
+        _ => "You won on time: your opponent ran out of time".to_string(),
+    };
+    Some(line)
+}
@@ -0,0 +1,19 @@
+pub mod tictactoe {
+    tonic::include_proto!("tictactoe");
+}
+
+pub mod ai;
+pub mod archive;
+pub mod board_diff;
+pub mod compression;
+pub mod config;
+pub mod countdown;
+pub mod local;
+pub mod moves;
+pub mod render;
+pub mod retry;
+pub mod rpc;
+pub mod stream;
+pub mod telemetry;
+pub mod tui;
+pub mod ultimate;
@@ -0,0 +1,99 @@
+//! 서버 없이 한 터미널에서 두 사람이 번갈아 두는 로컬 게임 (`--local`)
+//!
+//! 서버의 게임과 같은 판정(`common::board`)으로 3×3 게임을 진행하고, 칸 입력 읽기와 수 검사, 보드와 기보
+//! 출력은 온라인 게임의 줄 단위 모드와 같은 함수를 씁니다. 입력과 출력을 인자로 받으므로 정해 둔 입력으로
+//! 게임 한 판을 끝까지 둘 수 있습니다.
+
+use std::io::{self, Write};
+
+use common::board;
+use tokio::io::{AsyncBufRead, Lines};
+
+use crate::moves::{check_move, parse_move, MoveRejection};
+use crate::render::{self, Style};
+use crate::tictactoe::{GameState, Move};
+
+/// 로컬 게임 보드 한 변의 칸 수
+const SIZE: usize = 3;
+
+/// 로컬 게임 한 판 (상태는 서버가 보내는 것과 같은 모양의 `GameState`로 둠)
+#[derive(Debug, Clone)]
+pub struct LocalGame {
+    state: GameState,
+}
+
+impl Default for LocalGame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LocalGame {
+    /// X가 먼저 두는 빈 3×3 보드
+    pub fn new() -> Self {
+        let state = GameState {
+            board: vec![String::new(); SIZE * SIZE],
+            board_size: SIZE as i32,
+            win_length: SIZE as i32,
+            next_player: "X".into(),
+            status: "ongoing".into(),
+            ..GameState::default()
+        };
+        LocalGame { state }
+    }
+
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    /// 차례인 플레이어가 `position`에 둠 (보드 밖이나 채워진 칸은 거부하고, 끝난 게임이면 NotYourTurn)
+    pub fn play(&mut self, position: usize) -> Result<(), MoveRejection> {
+        if self.state.status != "ongoing" {
+            return Err(MoveRejection::NotYourTurn);
+        }
+        let symbol = self.state.next_player.clone();
+        check_move(&self.state.board, &symbol, &symbol, position)?;
+        self.state.board[position] = symbol.clone();
+        self.state.history.push(Move { player_id: symbol.clone(), position: position as i32, chosen_symbol: String::new() });
+        self.state.next_player = if symbol == "X" { "O".into() } else { "X".into() };
+        if let Some(winner) = board::winner(&self.state.board, SIZE, SIZE) {
+            self.state.status = format!("{}_win", winner);
+        } else if self.state.board.iter().all(|cell| !cell.is_empty()) {
+            self.state.status = "draw".into();
+        }
+        Ok(())
+    }
+}
+
+/// 게임이 끝나거나 'exit'을 입력하거나 입력이 끝날 때까지 번갈아 두고 마지막 상태를 반환
+pub async fn play<R: AsyncBufRead + Unpin>(lines: &mut Lines<R>, out: &mut impl Write, style: &Style) -> io::Result<GameState> {
+    let mut game = LocalGame::new();
+    writeln!(out, "Local game: X and O take turns on this terminal. Enter a cell number shown on the board, row,col counting from 1 like 1,3, or a column letter and row like c1 (exported game notation uses the letter for the row instead), or type 'exit' to quit.")?;
+    while game.state().status == "ongoing" {
+        write!(out, "{}", render::game_board(game.state(), style, true))?;
+        writeln!(out, "{}", style.bold(&format!("Player {}, enter your move:", game.state().next_player)))?;
+        out.flush()?;
+        let Some(line) = lines.next_line().await? else {
+            break;
+        };
+        let trimmed = line.trim();
+        if trimmed.eq_ignore_ascii_case("exit") {
+            break;
+        }
+        let played = parse_move(trimmed, SIZE).map_err(|e| e.to_string()).and_then(|pos| game.play(pos).map_err(|e| e.to_string()));
+        if let Err(message) = played {
+            writeln!(out, "{}", message)?;
+        }
+    }
+
+    let state = game.state().clone();
+    if state.status != "ongoing" {
+        write!(out, "{}", render::game_board(&state, style, false))?;
+        writeln!(out, "Game Over: {}", state.status)?;
+        if let Some(moves) = render::history(&state.history) {
+            writeln!(out, "{}", moves)?;
+        }
+    }
+    out.flush()?;
+    Ok(state)
+}
@@ -1,15 +1,51 @@
-use tokio::io::{self, AsyncBufReadExt, BufReader};
-use tokio::sync::{Mutex, mpsc};
+use tokio::io::{self, AsyncBufReadExt, AsyncWriteExt, BufReader, Lines, Stdin};
+use tokio::sync::{Mutex, mpsc, oneshot};
+use std::io::IsTerminal;
 use std::sync::Arc;
+use tonic::metadata::MetadataValue;
+use tonic::transport::Endpoint;
 use tonic::Request;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::ReceiverStream;
+use common::text;
+use clap::{Parser, Subcommand, ValueEnum};
+use tracing::{error, instrument, warn};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
 
-use tictactoe::tic_tac_toe_client::TicTacToeClient;
-use tictactoe::{GameState, Move};
+use client::ai::{AiClient, Strategy};
+use client::archive::{Archive, GameRecorder, SearchFilter};
+use client::board_diff;
+use client::compression;
+use client::config::{ClientConfig, ConfigOverrides};
+use client::countdown::{self, Countdown};
+use client::local;
+use client::moves::{check_move, parse_move, parse_wild_move};
+use client::render::{self, ColorChoice, Style};
+use client::retry::{connect_endpoint, RetryPolicy};
+use client::rpc;
+use client::stream::{self, Disconnect, Item};
+use client::telemetry;
+use client::tui::{self, Command as TuiCommand, Opponent, TerminalGuard, UiEvent, ViewState};
+use client::tictactoe;
+use client::ultimate::{self, SubBoards};
+use tictactoe::play_request::Action;
+use tictactoe::{BotDifficulty, Chat, DrawOffer, Heartbeat, DrawResponse, GameMode, GameOptions, GameState, GameStatusFilter, Join, MatchmakingRequest, ListGamesResponse, Move, PlayRequest, Rematch, ReplayRequest, Resign, SortField, SpectateRequest, Swap};
 
-pub mod tictactoe {
-    tonic::include_proto!("tictactoe");
-}
+/// board_size를 보내지 않는 서버의 보드 크기
+const DEFAULT_BOARD_SIZE: usize = 3;
+/// 게임 중 스트림이 끊겼을 때의 재접속 정책 (서버의 30초 유예 시간 안에 끝나도록)
+const RECONNECT_POLICY: RetryPolicy = RetryPolicy {
+    max_attempts: 5,
+    initial_delay: Duration::from_millis(500),
+    max_delay: Duration::from_secs(8),
+};
+
+/// 표준 입력 줄 리더 (로비 메뉴와 게임 입력이 함께 사용)
+type InputLines = Lines<BufReader<Stdin>>;
 
 /// 클라이언트의 공유 상태 구조체
 struct ClientState {
@@ -19,134 +55,808 @@ struct ClientState {
     game_status: Mutex<String>,
     // 게임 종료 여부 (true면 더 이상 입력을 받지 않음)
     game_over: Mutex<bool>,
+    // 재접속에 사용할 세션 토큰 (서버가 보낸 GameState에서 받음)
+    session_token: Mutex<Option<String>>,
+    // 서버로 메시지를 보내는 채널 (재접속하면 새 채널로 교체됨)
+    outbound: Mutex<mpsc::Sender<PlayRequest>>,
+    // 참가 중인 게임 ID
+    game_id: Mutex<String>,
+    // 보드 한 변의 칸 수 (입력 검증용, 서버가 보낸 GameState에서 받음)
+    board_size: Mutex<usize>,
+    // 마지막으로 받은 보드와 다음 차례 (보내기 전에 수를 미리 검사하는 용도, 판단은 서버가 함)
+    board: Mutex<Vec<String>>,
+    next_player: Mutex<String>,
+    // 얼티밋 게임의 작은 보드 규칙 (클래식이면 None)
+    sub_boards: Mutex<Option<SubBoards>>,
+    // 와일드 게임인지 (칸 번호 뒤에 놓을 심볼을 입력받음)
+    wild: Mutex<bool>,
+    // 게임이 한 번이라도 시작되었는지 (시작 전 접속이 끊기면 재접속할지 묻기 위함)
+    started: Mutex<bool>,
+    // 게임 시작 전에 접속이 끊김
+    lost_before_start: Mutex<bool>,
+    // 스트림이 끝난 이유 (마지막 안내 문구를 고르는 데 씀, 스트림이 열려 있으면 None)
+    disconnect: Mutex<Option<Disconnect>>,
+    // 상대의 접속 상태 (입장, 끊김, 재접속을 알리기 위함)
+    opponent: Mutex<Opponent>,
+    // 관전 모드 여부 (true면 수를 둘 수 없음)
+    spectating: bool,
+    // 게임 화면 그리기 태스크로 보내는 채널 (None이면 --plain: 줄 단위로 출력)
+    ui: Option<mpsc::UnboundedSender<UiEvent>>,
+    // 차례 제한 시간 카운트다운을 보일지 (--no-timer면 false)
+    timer: bool,
+    // --plain에서 내 차례의 남은 시간 (내 차례가 아니거나 제한이 없으면 None)
+    countdown: Mutex<Option<Countdown>>,
+    // 남은 시간 줄을 갱신하는 태스크와 멈춤 신호 (보내는 쪽을 버리면 줄을 마무리하고 끝남)
+    ticker: Mutex<Option<(oneshot::Sender<()>, JoinHandle<()>)>>,
+    // 재접속에 쓰는 서버 엔드포인트와 플레이어 이름
+    connection: Connection,
+    // 줄 단위 출력의 색
+    style: Style,
+}
+
+/// 게임 화면 방식과 출력 설정 (명령줄 인자로 정해짐)
+#[derive(Clone, Copy)]
+struct Output {
+    // 게임 화면 대신 줄 단위로 출력 (--plain)
+    plain: bool,
+    style: Style,
+    // 차례 제한 시간 카운트다운 (--no-timer면 false)
+    timer: bool,
+}
+
+/// 서버 엔드포인트, 플레이어 이름, 하트비트 간격 (설정 파일과 명령줄 인자로 정해짐)
+#[derive(Clone)]
+struct Connection {
+    endpoint: Endpoint,
+    name: String,
+    heartbeat: Option<Duration>,
+}
+
+impl Connection {
+    /// 수와 함께 보내는 플레이어 이름 (이름이 없으면 심볼)
+    fn move_player_id(&self, symbol: &str) -> String {
+        if self.name.is_empty() { symbol.to_string() } else { self.name.clone() }
+    }
 }
 
 impl ClientState {
-    fn new() -> Self {
+    fn new(
+        outbound: mpsc::Sender<PlayRequest>,
+        spectating: bool,
+        ui: Option<mpsc::UnboundedSender<UiEvent>>,
+        connection: Connection,
+        output: Output,
+    ) -> Self {
         ClientState {
             player_symbol: Mutex::new(None),
             game_status: Mutex::new(String::new()),
             game_over: Mutex::new(false),
+            session_token: Mutex::new(None),
+            outbound: Mutex::new(outbound),
+            game_id: Mutex::new(String::new()),
+            board_size: Mutex::new(DEFAULT_BOARD_SIZE),
+            board: Mutex::new(vec![String::new(); DEFAULT_BOARD_SIZE * DEFAULT_BOARD_SIZE]),
+            next_player: Mutex::new(String::new()),
+            sub_boards: Mutex::new(None),
+            wild: Mutex::new(false),
+            started: Mutex::new(false),
+            lost_before_start: Mutex::new(false),
+            disconnect: Mutex::new(None),
+            opponent: Mutex::new(Opponent::default()),
+            spectating,
+            ui,
+            timer: output.timer,
+            countdown: Mutex::new(None),
+            ticker: Mutex::new(None),
+            connection,
+            style: output.style,
+        }
+    }
+
+    /// 알림 한 줄 표시 (게임 화면이면 메시지 영역에, --plain이면 표준 출력에)
+    /// 입력을 더 받지 않게 되었을 때의 안내 (스트림이 끝난 이유에 따라 다름)
+    async fn closing_message(&self) -> &'static str {
+        self.disconnect.lock().await.as_ref().map_or(Disconnect::Finished.closing_message(), Disconnect::closing_message)
+    }
+
+    fn say(&self, message: impl Into<String>) {
+        match &self.ui {
+            Some(ui) => {
+                let _ = ui.send(UiEvent::Message(message.into().trim_start().to_string()));
+            }
+            None => println!("{}", message.into()),
+        }
+    }
+}
+
+/// 로비 메뉴에서 고른 게임 참가 방식
+enum JoinMode {
+    /// 빠른 대전 (상대가 없으면 지정한 난이도의 서버 봇과 대전)
+    Quick(BotDifficulty),
+    /// ID로 지정한 게임에 플레이어로 참가
+    Game(String),
+    /// ID로 지정한 게임을 관전
+    Spectate(String),
+    /// 매치메이킹이나 초대 코드로 배정된 자리 (게임 ID, 세션 토큰)
+    Matched(String, String),
+    /// 초대 코드로만 참가할 수 있는 비공개 방을 만들고 X로 참가 (몇 판 승부인지(0이면 한 판), 게임 방식,
+    /// 판이 끝나면 바로 다음 판을 시작할지, 스왑 규칙을 쓸지)
+    CreateRoom(u32, GameMode, bool, bool),
+    /// 초대 코드로 비공개 방에 참가
+    Room(String),
+    /// 끝난 게임을 서버에서 받아 한 수씩 다시 보기 (관전처럼 읽기만 함)
+    Replay(String),
+}
+
+impl JoinMode {
+    fn to_join(&self) -> Join {
+        match self {
+            JoinMode::Quick(difficulty) => Join {
+                quick_play: true,
+                bot_difficulty: *difficulty as i32,
+                ..Default::default()
+            },
+            JoinMode::Game(game_id) => Join { game_id: game_id.clone(), ..Default::default() },
+            // 다시 보기는 Play 스트림을 열지 않지만, 보낸다면 관전과 같음
+            JoinMode::Spectate(game_id) | JoinMode::Replay(game_id) => Join {
+                game_id: game_id.clone(),
+                spectate: true,
+                ..Default::default()
+            },
+            JoinMode::Matched(game_id, session_token) => Join {
+                game_id: game_id.clone(),
+                session_token: session_token.clone(),
+                ..Default::default()
+            },
+            JoinMode::CreateRoom(best_of, mode, auto_rematch, swap_rule) => Join {
+                create_room: true,
+                best_of: *best_of,
+                mode: *mode as i32,
+                auto_rematch: *auto_rematch,
+                swap_rule: *swap_rule,
+                ..Default::default()
+            },
+            JoinMode::Room(code) => Join { invite_code: code.clone(), ..Default::default() },
+        }
+    }
+}
+
+/// Play 요청에 플레이어 이름을 싣는 메타데이터 키
+const PLAYER_NAME_METADATA: &str = "x-player-name-bin";
+
+/// 서버에 접속해 Join 메시지로 Play 스트림을 엽니다. (세션 토큰이 있으면 기존 자리로 재접속)
+#[instrument(skip_all)]
+async fn open_session(
+    connection: &Connection,
+    join: Join,
+    policy: &RetryPolicy,
+) -> Result<(mpsc::Sender<PlayRequest>, tonic::Streaming<GameState>), Box<dyn std::error::Error + Send + Sync>> {
+    // Ctrl-C는 기본 동작 그대로 두어 기다리는 중에도 바로 종료됨
+    let mut client = connect_endpoint(&connection.endpoint, *policy).await?;
+
+    let (move_tx, move_rx) = mpsc::channel(32);
+    move_tx.send(PlayRequest { action: Some(Action::Join(join)) }).await?;
+
+    let mut request = Request::new(ReceiverStream::new(move_rx));
+    // 이름은 한글 등 ASCII가 아닐 수 있어 바이너리 메타데이터로 보냄
+    if !connection.name.is_empty() {
+        request.metadata_mut().insert_bin(PLAYER_NAME_METADATA, MetadataValue::from_bytes(connection.name.as_bytes()));
+    }
+    // 참가 자체가 거부되면 코드와 메시지를 그대로 보여 줌
+    let response = client.play(request).await.map_err(|status| stream::describe(&status))?;
+    Ok((move_tx, response.into_inner()))
+}
+
+/// Spectate RPC로 게임을 관전하는 읽기 전용 스트림을 엽니다 (`game_id`가 비어 있으면 가장 최근 게임).
+#[instrument(skip_all)]
+async fn open_spectate(
+    connection: &Connection,
+    game_id: &str,
+    policy: &RetryPolicy,
+) -> Result<tonic::Streaming<GameState>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut client = connect_endpoint(&connection.endpoint, *policy).await?;
+    let response = client.spectate(Request::new(SpectateRequest { game_id: game_id.to_string() })).await?;
+    Ok(response.into_inner())
+}
+
+/// ReplayGame RPC로 끝난 게임의 상태를 처음부터 한 수씩 받는 스트림을 엽니다 (서버 설정만큼 간격을 두고 옴).
+#[instrument(skip_all)]
+async fn open_replay(
+    connection: &Connection,
+    game_id: &str,
+    policy: &RetryPolicy,
+) -> Result<tonic::Streaming<GameState>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut client = connect_endpoint(&connection.endpoint, *policy).await?;
+    let request = ReplayRequest { game_id: game_id.to_string(), paced: Some(true) };
+    Ok(client.replay_game(Request::new(request)).await?.into_inner())
+}
+
+/// 게임 중에 스트림이 끊겼을 때(서버가 잠시 Unavailable인 경우 등) 세션 토큰으로 백오프하며 재접속합니다.
+/// 성공하면 새 스트림을 반환합니다.
+async fn reconnect(state: &ClientState) -> Option<tonic::Streaming<GameState>> {
+    let token = state.session_token.lock().await.clone()?;
+    let game_id = state.game_id.lock().await.clone();
+    for attempt in 1..=RECONNECT_POLICY.max_attempts {
+        state.say(format!("Connection lost. Reconnecting (attempt {}/{})...", attempt, RECONNECT_POLICY.max_attempts));
+        tokio::time::sleep(RECONNECT_POLICY.jittered_delay_after(attempt)).await;
+        let join = Join {
+            session_token: token.clone(),
+            game_id: game_id.clone(),
+            board_diffs: true,
+            ..Default::default()
+        };
+        match open_session(&state.connection, join, &RetryPolicy::no_retry()).await {
+            Ok((move_tx, rx)) => {
+                *state.outbound.lock().await = move_tx;
+                state.say("Reconnected. Resuming game.");
+                return Some(rx);
+            }
+            Err(e) => warn!(attempt, error = %e, "reconnect failed"),
         }
     }
+    None
 }
 
-/// 보드 출력 함수
-fn print_board(board: &Vec<String>) {
-    println!("-------------");
-    for i in 0..3 {
-        println!("| {} | {} | {} |", board[i * 3], board[i * 3 + 1], board[i * 3 + 2]);
-        println!("-------------");
+/// GameState의 보드 한 변 길이 (보내지 않는 서버라면 3)
+fn board_size_of(state: &GameState) -> usize {
+    match state.board_size {
+        size if size > 0 => size as usize,
+        _ => DEFAULT_BOARD_SIZE,
     }
 }
 
-/// 서버 업데이트 처리 함수
+/// 받은 상태의 보드 출력 (얼티밋이면 작은 보드로 나눈 9×9 보드). `indices`면 빈 칸에 칸 번호를 표시
+fn print_game_board(state: &GameState, style: &Style, indices: bool) {
+    match SubBoards::of(state) {
+        Some(sub_boards) => ultimate::board_lines(&tui::marker_cells(state), &sub_boards).iter().for_each(|line| println!("{}", line)),
+        None => print!("{}", render::game_board(state, style, indices)),
+    }
+}
+
+/// 끝난 게임의 상태인지 (이 업데이트 뒤에 서버가 스트림을 닫음)
+fn is_finished_status(status: &str) -> bool {
+    matches!(
+        status,
+        "X_win" | "O_win" | "draw" | "X_win_by_resignation" | "O_win_by_resignation" | "draw_agreed" | "admin_terminated" | "server_shutdown"
+            | "idle_timeout" | "join_timeout" | "abandoned"
+    )
+}
+
+/// 서버 업데이트 처리 함수 (TUI 모드면 화면 상태를 갱신하고, 아니면 줄 단위로 출력)
 async fn process_server_updates(mut rx: tonic::Streaming<GameState>, state: Arc<ClientState>) {
-    while let Some(result) = rx.message().await.unwrap_or(None) {
+    let mut recorder = GameRecorder::new();
+    // 끝난 판의 결과(아카이브, 점수)를 이미 알렸는지 (몇 판 승부에서 다음 판이 시작되면 다시 false)
+    let mut game_reported = false;
+    // 마지막으로 받은 상태 번호 (새 스트림을 열면 서버가 현재 상태부터 다시 보내므로 비움)
+    let mut last_move_number = None;
+    loop {
+        let game_over = *state.game_over.lock().await;
+        let result = match stream::classify(rx.message().await, game_over) {
+            // 하트비트는 연결 확인용이라 화면에 반영하지 않고 응답만 보냄
+            Item::Heartbeat => {
+                send_action(&state, Action::Heartbeat(Heartbeat {})).await;
+                continue;
+            }
+            // 보드 차이는 서버가 이 연결로 보낸 순서대로 모두 반영해야 하므로 다른 처리보다 먼저
+            Item::Update(mut update) => {
+                board_diff::apply(&mut *state.board.lock().await, &mut update);
+                *update
+            }
+            Item::End(disconnect) => {
+                stop_ticker(&state).await;
+                let seated = state.player_symbol.lock().await.is_some();
+                if let Some(message) = disconnect.message(seated) {
+                    state.say(message);
+                }
+                if let Disconnect::Lost { code, message } = &disconnect {
+                    warn!(?code, message, "stream error");
+                }
+                let lost = disconnect.is_recoverable();
+                *state.disconnect.lock().await = Some(disconnect);
+                if !lost {
+                    break;
+                }
+                // 게임 시작 전이면 자동으로 재접속하지 않고 사용자에게 물어봄 (run_game)
+                if !*state.started.lock().await {
+                    *state.lost_before_start.lock().await = true;
+                    break;
+                }
+                match reconnect(&state).await {
+                    Some(new_rx) => {
+                        *state.disconnect.lock().await = None;
+                        rx = new_rx;
+                        last_move_number = None;
+                        continue;
+                    }
+                    None => break,
+                }
+            }
+        };
+
+        // 번호가 줄었으면 이미 받은 업데이트이므로 버리고, 건너뛰었으면 놓친 업데이트가 있으니 다시 맞춤.
+        // 번호는 이어지지만 반영한 보드가 서버의 해시와 다르면 잘못 반영한 것이므로 역시 다시 맞춤
+        let result = match last_move_number {
+            Some(last) if result.move_number < last => {
+                warn!(last, received = result.move_number, "ignoring stale or duplicated update");
+                continue;
+            }
+            Some(last) if result.move_number > last + 1 => {
+                warn!(last, received = result.move_number, "missed game updates, re-syncing");
+                resync(&state, result).await
+            }
+            _ if !board_diff::in_sync(&result) => {
+                warn!(move_number = result.move_number, hash = %result.state_hash, "board does not match the server state hash, re-syncing");
+                resync(&state, result).await
+            }
+            _ => result,
+        };
+        last_move_number = Some(result.move_number);
+
+        // 남은 시간 줄을 마무리한 뒤 출력하고, 이 업데이트가 알려 준 남은 시간으로 다시 셈
+        stop_ticker(&state).await;
+        if state.timer && state.ui.is_none() {
+            let mut countdown = state.countdown.lock().await;
+            *countdown = Countdown::update(countdown.as_ref(), &result, state.spectating, Instant::now());
+        }
+
+        // 채팅은 보드를 다시 그리지 않고 한 줄로만 표시
+        if !result.chat_message.is_empty() {
+            state.say(format!("[{}]: {}", result.chat_sender, text::isolate_bidi(&result.chat_message)));
+            start_ticker(&state).await;
+            continue;
+        }
+
+        if !result.session_token.is_empty() {
+            let mut token = state.session_token.lock().await;
+            *token = Some(result.session_token.clone());
+        }
+
         {
             let mut status = state.game_status.lock().await;
             *status = result.status.clone();
         }
+        *state.board.lock().await = result.board.clone();
+        *state.next_player.lock().await = result.next_player.clone();
+        *state.sub_boards.lock().await = SubBoards::of(&result);
+        *state.wild.lock().await = result.mode == GameMode::Wild as i32;
 
-        println!("\n=== Game Update ===");
+        {
+            let mut game_id = state.game_id.lock().await;
+            if *game_id != result.game_id {
+                *game_id = result.game_id.clone();
+                state.say(format!("\nGame ID: {} (share it so others can join or spectate)", result.game_id));
+            }
+        }
 
-        if !result.error_message.is_empty() {
-            println!("Error: {}", result.error_message);
+        // 몇 판 승부의 다음 판이 시작되면 새 기보로 기록
+        if game_reported && result.status == "ongoing" {
+            game_reported = false;
+            recorder = GameRecorder::new();
         }
+        recorder.observe(&result);
 
-        match result.status.as_str() {
-            "waiting" => {
-                let symbol = state.player_symbol.lock().await.clone();
-                if symbol.is_some() {
-                    println!("Opponent disconnected. Waiting for opponent to join...");
+        // 기본과 다른 크기의 보드라면 처음 한 번 칸 번호 범위를 안내
+        {
+            let size = board_size_of(&result);
+            let mut board_size = state.board_size.lock().await;
+            if *board_size != size {
+                *board_size = size;
+                if SubBoards::of(&result).is_some() {
+                    state.say("\nUltimate board: nine 3x3 sub-boards. Enter a cell as board,cell (e.g. 4,0) or 0-80.");
+                } else if result.mode == GameMode::Wild as i32 {
+                    state.say(format!(
+                        "\nWild board: {}x{}, either player may place X or O; whoever completes {} in a row wins. Enter a cell and a symbol (e.g. 4 O); without one you place your own.",
+                        size, size, result.win_length
+                    ));
+                } else if result.mode == GameMode::Misere as i32 {
+                    state.say(format!(
+                        "\nMisere board: {}x{}, completing {} in a row loses (unless it fills the last cell). Cells are numbered 0-{}.",
+                        size, size, result.win_length, size * size - 1
+                    ));
                 } else {
-                    println!("Waiting for opponent to join...");
+                    state.say(format!("\nBoard: {}x{}, {} in a row wins. Cells are numbered 0-{}.", size, size, result.win_length, size * size - 1));
                 }
-            },
-            "ongoing" => {
-                print_board(&result.board);
-                println!("Next Player: {}", result.next_player);
-                println!("Your Symbol: {}", result.your_symbol);
-            },
-            "X_win" | "O_win" | "draw" => {
-                print_board(&result.board);
-                println!("Game Over: {}", result.status);
-                let mut over = state.game_over.lock().await;
-                *over = true;
-                break;
-            },
-            _ => {
-                println!("Status: {}", result.status);
             }
         }
 
-        {
+        let just_started = result.status == "ongoing" && !std::mem::replace(&mut *state.started.lock().await, true);
+
+        let opponent_notice = if state.spectating {
+            None
+        } else {
+            let mut opponent = state.opponent.lock().await;
+            let (next, notice) = opponent.next(&result);
+            *opponent = next;
+            notice
+        };
+
+        match &state.ui {
+            Some(ui) => {
+                let _ = ui.send(UiEvent::Update(Box::new(result.clone())));
+            }
+            // 상대를 찾는 동안에는 search_indicator가 상태 줄 하나만 갱신함
+            None if result.status == "searching" => {}
+            None => print_update(&state, &result).await,
+        }
+        if let Some(notice) = opponent_notice {
+            state.say(notice);
+        }
+
+        // 누가 먼저 두는지는 서버 설정에 따라 달라지므로 첫 수 전에 알림
+        if just_started && result.board.iter().all(String::is_empty) {
+            state.say(format!("{} moves first this game.", result.next_player));
+        }
+
+        if result.status == "searching" {
+            *state.player_symbol.lock().await = Some(result.your_symbol.clone());
+            continue;
+        }
+
+        if is_finished_status(&result.status) {
+            if !std::mem::replace(&mut game_reported, true) {
+                if !state.spectating && !["admin_terminated", "server_shutdown", "idle_timeout", "join_timeout", "abandoned"].contains(&result.status.as_str()) {
+                    save_to_archive(&state, &recorder, &result);
+                }
+                if let Some(line) = tui::scoreboard_line(&result, state.spectating) {
+                    state.say(line);
+                }
+                if let Some(line) = tui::match_result_line(&result, state.spectating) {
+                    state.say(line);
+                }
+                // 몇 판 승부가 끝나지 않았으면 스트림이 열린 채로 다음 판을 기다림
+                if tui::awaits_rematch(&result) && !state.spectating && !result.auto_rematch {
+                    state.say(match state.ui {
+                        Some(_) => "Press r to play the next game of the match, or q to leave.",
+                        None => "Type 'rematch' to play the next game of the match, or 'exit' to leave.",
+                    });
+                }
+            }
+            if tui::awaits_rematch(&result) {
+                continue;
+            }
+            *state.game_over.lock().await = true;
+            break;
+        }
+
+        if !state.spectating {
             let mut sym_lock = state.player_symbol.lock().await;
             if sym_lock.is_none() || sym_lock.as_ref().unwrap() != &result.your_symbol {
                 *sym_lock = Some(result.your_symbol.clone());
-                println!("Your symbol has been updated to: {}", result.your_symbol);
+                state.say(format!("Your symbol has been updated to: {}", result.your_symbol));
             }
         }
 
-        println!("===================\n");
+        if state.ui.is_none() {
+            println!("===================\n");
+        }
+        start_ticker(&state).await;
     }
-    println!("Disconnected from server.");
+    stop_ticker(&state).await;
+    state.say("Disconnected from server.");
     let mut over = state.game_over.lock().await;
     *over = true;
+    if let Some(ui) = &state.ui {
+        let _ = ui.send(UiEvent::Closed);
+    }
+}
+
+/// 업데이트를 놓쳤을 때 GetGameState로 서버의 현재 보드를 받아 덮어씀
+/// (스냅샷에는 플레이어별 필드가 없으므로 그 부분은 받은 업데이트의 값을 그대로 둠)
+async fn resync(state: &ClientState, mut update: GameState) -> GameState {
+    let token = state.session_token.lock().await.clone().unwrap_or_default();
+    match rpc::get_game_state(&state.connection.endpoint, &update.game_id, &token).await {
+        Ok(snapshot) if snapshot.move_number >= update.move_number => {
+            update.board = snapshot.board;
+            update.next_player = snapshot.next_player;
+            update.status = snapshot.status;
+            update.history = snapshot.history;
+            update.move_number = snapshot.move_number;
+        }
+        Ok(_) => {}
+        Err(e) => warn!(error = %e, "could not re-sync the game state"),
+    }
+    update
+}
+
+/// 줄 단위 모드에서 업데이트 하나를 출력
+async fn print_update(state: &ClientState, result: &GameState) {
+    println!("\n=== Game Update ===");
+
+    if !result.error_message.is_empty() {
+        println!("{}", state.style.error(&format!("Error: {}", result.error_message)));
+    }
+    if !result.info_message.is_empty() {
+        println!("{}", result.info_message);
+    }
+
+    match result.status.as_str() {
+        "waiting" => {
+            println!("Waiting for opponent to join...");
+            if !result.invite_code.is_empty() {
+                println!();
+                println!("    Room code: {}", result.invite_code);
+                println!();
+                println!("Share it with your friend; they can join with `client --join {}`.", result.invite_code);
+            }
+        },
+        "ongoing" => {
+            print_game_board(result, &state.style, !state.spectating);
+            let label = |symbol: &str| match symbol {
+                "X" => tui::seat_label(&result.player_x_name, symbol),
+                "O" => tui::seat_label(&result.player_o_name, symbol),
+                _ => symbol.to_string(),
+            };
+            println!("{} vs {}", label("X"), label("O"));
+            if !state.spectating && result.next_player == result.your_symbol {
+                println!("{}", state.style.bold(&format!("Next Player: {} (your turn)", label(&result.next_player))));
+            } else {
+                println!("Next Player: {}", label(&result.next_player));
+            }
+            if let Some(sub_boards) = SubBoards::of(result) {
+                println!("{}", sub_boards.prompt());
+            }
+            if !state.spectating {
+                println!("Your Symbol: {}", label(&result.your_symbol));
+            }
+            if let Some(level) = tui::difficulty_label(result.bot_difficulty) {
+                println!("(unrated game vs the {} house bot)", level);
+            } else if !result.rated {
+                println!("(unrated game)");
+            } else if result.x_rating > 0 || result.o_rating > 0 {
+                println!("Ratings: X {} / O {}", rating_label(result.x_rating), rating_label(result.o_rating));
+            }
+            if let Some(hint) = tui::swap_hint(result).filter(|_| !state.spectating) {
+                println!("{}", hint);
+            }
+            if result.draw_offer_pending {
+                if state.spectating {
+                    println!("A draw has been offered.");
+                } else {
+                    println!("Opponent offers a draw — type 'accept' or keep playing ('decline' to refuse).");
+                }
+            }
+        },
+        "admin_terminated" => {
+            print_game_board(result, &state.style, false);
+            println!("Game Over: ended by a server administrator");
+        },
+        "server_shutdown" => {
+            print_game_board(result, &state.style, false);
+            println!("Game Over: the server shut down");
+        },
+        "idle_timeout" => {
+            print_game_board(result, &state.style, false);
+            println!("Game Over: a player stopped responding");
+        },
+        "abandoned" => {
+            print_game_board(result, &state.style, false);
+            println!("Game Over: abandoned after a long time without moves");
+        },
+        "join_timeout" => println!("Removed from the game: nothing was sent after joining"),
+        status if is_finished_status(status) => {
+            print_game_board(result, &state.style, false);
+            if let Some(line) = countdown::lost_on_time_line(result, state.spectating) {
+                println!("{}", state.style.bold(&format!("Game Over: {}", line)));
+            } else if let Some(winner) = status.strip_suffix("_win_by_resignation") {
+                println!("Game Over: {} wins by resignation", winner);
+            } else if status == "draw_agreed" {
+                println!("Game Over: draw by agreement");
+            } else {
+                println!("Game Over: {}", status);
+            }
+            print_history(&result.history);
+        },
+        _ => {
+            println!("Status: {}", result.status);
+        }
+    }
+}
+
+/// 게임이 끝났을 때 받은 기보를 번호 목록으로 출력 ("1. X→4, 2. O→0, ...")
+fn print_history(history: &[Move]) {
+    if let Some(moves) = render::history(history) {
+        println!("{}", moves);
+    }
+}
+
+/// 끝난 게임을 로컬 아카이브에 저장
+fn save_to_archive(state: &ClientState, recorder: &GameRecorder, result: &GameState) {
+    let entry = recorder.finish(result, &result.your_symbol);
+    match Archive::open_default().and_then(|archive| archive.append(entry)) {
+        Ok(id) => state.say(format!("Saved to archive as #{} (see `client archive list`).", id)),
+        Err(e) => warn!(error = %e, "failed to save game to archive"),
+    }
+}
+
+/// 서버로 요청 하나 보내기 (재접속했다면 새 스트림으로)
+async fn send_action(state: &ClientState, action: Action) {
+    let tx = state.outbound.lock().await.clone();
+    if let Err(e) = tx.send(PlayRequest { action: Some(action) }).await {
+        error!(error = %e, "failed to send request");
+    }
+}
+
+/// SendChat RPC로 채팅 보내기 (자리를 받기 전이면 보내지 않음)
+async fn send_chat(state: &ClientState, text: &str) {
+    let game_id = state.game_id.lock().await.clone();
+    let symbol = state.player_symbol.lock().await.clone().unwrap_or_default();
+    let token = state.session_token.lock().await.clone().unwrap_or_default();
+    if game_id.is_empty() || token.is_empty() {
+        println!("You can chat once you have joined a game.");
+        return;
+    }
+    if let Err(e) = rpc::send_chat(&state.connection.endpoint, &game_id, &symbol, &token, text).await {
+        println!("Chat not sent: {}", e);
+    }
+}
+
+/// 남은 시간 줄 태스크를 멈추고 줄을 마무리할 때까지 기다림 (다른 출력과 섞이지 않도록 출력하기 전에 부름)
+async fn stop_ticker(state: &ClientState) {
+    let ticker = state.ticker.lock().await.take();
+    if let Some((stop, task)) = ticker {
+        drop(stop);
+        let _ = task.await;
+    }
+}
+
+/// --plain에서 내 차례의 남은 시간이 있으면 남은 시간 줄 태스크 시작 (이미 돌고 있으면 그대로)
+async fn start_ticker(state: &Arc<ClientState>) {
+    let running = state.countdown.lock().await.is_some_and(|c| c.seconds_left(Instant::now()) > 0);
+    let mut ticker = state.ticker.lock().await;
+    if running && ticker.is_none() {
+        let (stop, stopped) = oneshot::channel();
+        *ticker = Some((stop, tokio::spawn(tick_countdown(Arc::clone(state), stopped))));
+    }
+}
+
+/// 남은 초가 바뀔 때마다 "Your turn — 37s left" 한 줄을 덮어씀 (10초 남으면 벨).
+/// 줄 길이를 처음 길이로 맞춰, 그 뒤에 입력 중인 글자는 덮어쓰지 않습니다. 멈추면 줄을 끝내 다음 출력이 새 줄에서 시작됨
+async fn tick_countdown(state: Arc<ClientState>, mut stop: oneshot::Receiver<()>) {
+    let mut stdout = io::stdout();
+    let mut width = 0;
+    loop {
+        let now = Instant::now();
+        let frame = state.countdown.lock().await.as_mut().map(|c| (c.line(now), c.take_warning(now), c.until_next_tick(now)));
+        let Some((line, warn, wait)) = frame else {
+            break;
+        };
+        width = width.max(line.chars().count());
+        let bell = if warn { countdown::BELL } else { "" };
+        let _ = stdout.write_all(format!("\r{:<width$} {}", line, bell).as_bytes()).await;
+        let _ = stdout.flush().await;
+        // 시간이 다 되면 서버가 보낼 시간패 업데이트를 기다림
+        if wait.is_zero() {
+            break;
+        }
+        tokio::select! {
+            _ = &mut stop => break,
+            _ = tokio::time::sleep(wait) => {}
+        }
+    }
+    if width > 0 {
+        let _ = stdout.write_all(b"\n").await;
+        let _ = stdout.flush().await;
+    }
 }
 
-/// 사용자 입력 처리 함수 (자동 종료를 위해 select! 사용)
-async fn process_user_input(move_tx: mpsc::Sender<Move>, state: Arc<ClientState>) {
-    let stdin = io::stdin();
-    let reader = BufReader::new(stdin);
-    let mut lines = reader.lines();
+/// 게임이 끝날 때까지 `interval`마다 하트비트를 보내 서버가 응답 없는 플레이어로 보지 않게 함
+async fn send_heartbeats(state: Arc<ClientState>, interval: Duration) {
+    loop {
+        tokio::time::sleep(interval).await;
+        if *state.game_over.lock().await || state.outbound.lock().await.is_closed() {
+            break;
+        }
+        send_action(&state, Action::Heartbeat(Heartbeat {})).await;
+    }
+}
 
-    println!("Enter your move (0-8), or type 'exit' to quit:");
+/// 사용자 입력 처리 함수 (자동 종료를 위해 select! 사용). 끝나면 입력 리더를 돌려줌
+async fn process_user_input(state: Arc<ClientState>, mut lines: InputLines) -> InputLines {
+    if state.spectating {
+        println!("Spectating. Type 'exit' to leave.");
+    } else {
+        println!("Enter your move (a cell number shown on the board, row,col counting from 1 like 1,3, or a column letter and row like c1; note that exported game notation puts the row letter first, so its a2 is b1 here), 'draw' to offer a draw, 'resign' (or 'ff') to concede, '!<message>' (or '/say <message>') to chat, or type 'exit' to quit:");
+    }
     loop {
+        // 입력 한 줄을 처리하는 동안 멈췄던 남은 시간 줄을 다시 보임
+        start_ticker(&state).await;
         tokio::select! {
             maybe_line = lines.next_line() => {
                 match maybe_line {
                     Ok(Some(line)) => {
+                        stop_ticker(&state).await;
                         let trimmed = line.trim();
                         if trimmed.eq_ignore_ascii_case("exit") {
                             break;
                         }
-                        {
-                            let over = state.game_over.lock().await;
-                            if *over {
-                                println!("Game is over. No more moves accepted.");
-                                break;
-                            }
+                        if *state.game_over.lock().await {
+                            println!("{}", state.closing_message().await);
+                            break;
+                        }
+                        if state.spectating {
+                            println!("You are spectating. Type 'exit' to leave.");
+                            continue;
+                        }
+                        // 채팅은 대기 중에도 보낼 수 있음 ('!'는 SendChat RPC로, '/say'는 게임 스트림으로)
+                        if let Some(text) = trimmed.strip_prefix('!') {
+                            send_chat(&state, text.trim()).await;
+                            continue;
+                        }
+                        if let Some(text) = trimmed.strip_prefix("/say") {
+                            send_action(&state, Action::Chat(Chat { text: text.trim().to_string() })).await;
+                            continue;
+                        }
+                        // 몇 판 승부의 다음 판 요청 (할 수 없는 상황이면 서버가 이유를 알려 줌)
+                        if trimmed.eq_ignore_ascii_case("rematch") {
+                            send_action(&state, Action::Rematch(Rematch {})).await;
+                            continue;
                         }
                         {
                             let status = state.game_status.lock().await;
-                            if *status == "waiting" {
+                            if *status == "waiting" || *status == "searching" {
                                 println!("Game has not started yet. Waiting for opponent...");
                                 continue;
                             }
                         }
-                        if let Ok(pos) = trimmed.parse::<usize>() {
-                            if pos < 9 {
+                        // 스왑 규칙: 첫 수를 가져감 (할 수 없는 때면 서버가 이유를 알려 줌)
+                        if trimmed.eq_ignore_ascii_case("swap") {
+                            send_action(&state, Action::Swap(Swap {})).await;
+                            continue;
+                        }
+                        if trimmed.eq_ignore_ascii_case("resign") || trimmed.eq_ignore_ascii_case("ff") {
+                            send_action(&state, Action::Resign(Resign {})).await;
+                            continue;
+                        }
+                        let draw_action = match trimmed.to_ascii_lowercase().as_str() {
+                            "draw" => Some(Action::OfferDraw(DrawOffer {})),
+                            "accept" => Some(Action::RespondDraw(DrawResponse { accept: true })),
+                            "decline" => Some(Action::RespondDraw(DrawResponse { accept: false })),
+                            _ => None,
+                        };
+                        if let Some(action) = draw_action {
+                            send_action(&state, action).await;
+                            continue;
+                        }
+                        let size = *state.board_size.lock().await;
+                        let sub_boards = state.sub_boards.lock().await.clone();
+                        let wild = *state.wild.lock().await;
+                        let parsed = match &sub_boards {
+                            Some(_) => ultimate::parse_position(trimmed).map(|pos| (pos, None)).ok_or_else(|| {
+                                format!("Invalid input. Please enter board,cell (each 0-8) or a number between 0 and {}, 'resign', or 'exit'.", size * size - 1)
+                            }),
+                            None if wild => parse_wild_move(trimmed, size).map_err(|e| format!("{} Add X or O after the cell to choose the symbol.", e)),
+                            None => parse_move(trimmed, size).map(|pos| (pos, None)).map_err(|e| e.to_string()),
+                        };
+                        match parsed {
+                            Ok((pos, mark)) => {
                                 let symbol_opt = {
                                     let lock = state.player_symbol.lock().await;
                                     lock.clone()
                                 };
                                 if let Some(symbol) = symbol_opt {
-                                    let mv = Move {
-                                        player_id: symbol.clone(),
-                                        position: pos as i32,
+                                    // 마지막으로 받은 보드로 미리 검사 (그 사이 상태가 바뀌었으면 서버의 응답이 우선)
+                                    let checked = {
+                                        let board = state.board.lock().await;
+                                        check_move(&board, &state.next_player.lock().await, &symbol, pos)
+                                            .and_then(|()| sub_boards.as_ref().map_or(Ok(()), |s| s.check(pos)))
                                     };
-                                    if let Err(e) = move_tx.send(mv).await {
-                                        eprintln!("Error sending move: {:?}", e);
+                                    match checked {
+                                        Ok(()) => {
+                                            let chosen_symbol = if wild { mark.unwrap_or_else(|| symbol.clone()) } else { String::new() };
+                                            let mv = Move {
+                                                player_id: state.connection.move_player_id(&symbol),
+                                                position: pos as i32,
+                                                chosen_symbol,
+                                            };
+                                            send_action(&state, Action::Move(mv)).await;
+                                        }
+                                        Err(rejection) => println!("{}", rejection),
                                     }
                                 } else {
                                     println!("You haven't been assigned a symbol yet. Please wait for the server update.");
                                 }
-                            } else {
-                                println!("Invalid move. Please enter a number between 0 and 8.");
                             }
-                        } else {
-                            println!("Invalid input. Please enter a number between 0 and 8, or 'exit'.");
+                            Err(message) => println!("{}", message),
                         }
                     },
                     Ok(None) => {
@@ -154,7 +864,7 @@ async fn process_user_input(move_tx: mpsc::Sender<Move>, state: Arc<ClientState>
                         break;
                     },
                     Err(e) => {
-                        eprintln!("Error reading input: {:?}", e);
+                        error!(error = %e, "failed to read input");
                         break;
                     }
                 }
@@ -170,42 +880,797 @@ async fn process_user_input(move_tx: mpsc::Sender<Move>, state: Arc<ClientState>
                     tokio::time::sleep(Duration::from_millis(100)).await;
                 }
             } => {
-                println!("Game session ended due to disconnection.");
+                println!("{}", state.closing_message().await);
                 break;
             }
         }
     }
+    stop_ticker(&state).await;
     println!("Exiting game session.");
+    lines
+}
+
+/// 게임 화면 그리기 태스크: 서버 업데이트(`ui_rx`)나 키 입력이 올 때마다 다시 그립니다.
+/// 'q'나 Ctrl-C로 나가면 터미널을 복구하고 끝남
+async fn run_tui(state: Arc<ClientState>, mut ui_rx: mpsc::UnboundedReceiver<UiEvent>) -> std::io::Result<()> {
+    let mut view = ViewState::new(state.spectating);
+    view.timer = state.timer;
+    let mut terminal = TerminalGuard::enter()?;
+
+    // crossterm의 키 읽기는 블로킹이므로 별도 스레드에서 읽어 채널로 넘김
+    let (key_tx, mut key_rx) = mpsc::channel(16);
+    std::thread::spawn(move || loop {
+        match crossterm::event::poll(Duration::from_millis(100)) {
+            Ok(true) => match crossterm::event::read() {
+                Ok(crossterm::event::Event::Key(key)) if key_tx.blocking_send(key).is_err() => break,
+                Ok(_) => {}
+                Err(_) => break,
+            },
+            Ok(false) if key_tx.is_closed() => break,
+            Ok(false) => {}
+            Err(_) => break,
+        }
+    });
+
+    loop {
+        terminal.draw(&view)?;
+        let now = Instant::now();
+        if view.countdown.as_mut().is_some_and(|c| c.take_warning(now)) {
+            let _ = std::io::Write::write_all(&mut std::io::stdout(), countdown::BELL.as_bytes());
+        }
+        // 남은 시간이 있으면 초가 바뀔 때마다 다시 그림
+        let tick = view.countdown.map(|c| c.until_next_tick(now)).filter(|wait| !wait.is_zero());
+
+        let key = tokio::select! {
+            Some(event) = ui_rx.recv() => {
+                view.handle(event);
+                continue;
+            }
+            _ = tokio::time::sleep(tick.unwrap_or_default()), if tick.is_some() => continue,
+            key = key_rx.recv() => key,
+            // raw 모드에서는 Ctrl-C가 키 입력으로 오지만, 외부에서 보낸 SIGINT도 처리
+            _ = tokio::signal::ctrl_c() => break,
+        };
+        let Some(key) = key else { continue };
+        if view.chat_draft.is_some() {
+            if let Some(text) = view.edit_chat(key) {
+                view.push_message(format!("[{}]: {}", view.your_symbol, text));
+                send_action(&state, Action::Chat(Chat { text })).await;
+            }
+            continue;
+        }
+        let Some(command) = tui::command_for(key) else {
+            continue;
+        };
+        match command {
+            TuiCommand::Quit => break,
+            TuiCommand::Cursor(direction) => view.move_cursor(direction),
+            TuiCommand::Digit(digit) => view.push_digit(digit),
+            TuiCommand::Backspace => {
+                view.input.pop();
+            }
+            _ if view.closed => view.push_message("Game is over. No more moves accepted."),
+            _ if state.spectating => view.push_message("You are spectating. Press q to leave."),
+            // 채팅은 대기 중에도 보낼 수 있음
+            TuiCommand::StartChat => view.chat_draft = Some(String::new()),
+            TuiCommand::Rematch => send_action(&state, Action::Rematch(Rematch {})).await,
+            TuiCommand::ChooseMark(_) if !view.wild => {}
+            TuiCommand::ChooseMark(mark) => view.mark = mark.to_string(),
+            _ if !matches!(view.status.as_str(), "ongoing") => view.push_message("Game has not started yet. Waiting for opponent..."),
+            TuiCommand::Play => match view.take_target() {
+                Ok(position) => match check_move(&view.board, &view.next_player, &view.your_symbol, position)
+                    .and_then(|()| view.sub_boards.as_ref().map_or(Ok(()), |s| s.check(position)))
+                {
+                    Ok(()) => {
+                        let player_id = state.connection.move_player_id(&view.your_symbol);
+                        let mv = Move { player_id, position: position as i32, chosen_symbol: view.chosen_mark() };
+                        send_action(&state, Action::Move(mv)).await;
+                    }
+                    Err(rejection) => view.push_message(rejection.to_string()),
+                },
+                Err(input) => view.push_message(format!(
+                    "Invalid move '{}'. Please enter a number between 0 and {}.",
+                    input,
+                    view.board.len() - 1
+                )),
+            },
+            TuiCommand::OfferDraw => send_action(&state, Action::OfferDraw(DrawOffer {})).await,
+            TuiCommand::AcceptDraw => send_action(&state, Action::RespondDraw(DrawResponse { accept: true })).await,
+            TuiCommand::DeclineDraw => send_action(&state, Action::RespondDraw(DrawResponse { accept: false })).await,
+            TuiCommand::Resign => send_action(&state, Action::Resign(Resign {})).await,
+            TuiCommand::Swap => send_action(&state, Action::Swap(Swap {})).await,
+        }
+    }
+    Ok(())
+}
+
+/// 빠른 대전 상대를 찾는 동안 경과 시간을 한 줄로 갱신해 출력
+async fn search_indicator(state: Arc<ClientState>) {
+    let started = tokio::time::Instant::now();
+    let mut stdout = io::stdout();
+    loop {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let status = state.game_status.lock().await.clone();
+        if status == "searching" {
+            let line = format!("\rSearching for an opponent... {}s", started.elapsed().as_secs());
+            let _ = stdout.write_all(line.as_bytes()).await;
+            let _ = stdout.flush().await;
+        } else if !status.is_empty() || *state.game_over.lock().await {
+            break;
+        }
+    }
+}
+
+/// 게임 목록을 표로 출력
+fn print_game_table(list: &ListGamesResponse) {
+    if list.games.is_empty() {
+        println!("No games in progress.");
+        return;
+    }
+    let id_width = list
+        .games
+        .iter()
+        .map(|game| text::display_width(&game.game_id))
+        .chain(std::iter::once(2))
+        .max()
+        .unwrap_or(2);
+    let row = |id: &str, status: &str, players: &str, spectators: &str| {
+        format!(
+            "{} | {} | {} | {}",
+            text::pad_to_width(id, id_width),
+            text::pad_to_width(status, 9),
+            text::pad_to_width(players, 7),
+            spectators
+        )
+    };
+    println!("{}", row("ID", "Status", "Players", "Spectators"));
+    println!("{}", "-".repeat(id_width + 35));
+    for game in &list.games {
+        println!(
+            "{}",
+            row(
+                &game.game_id,
+                &game.status,
+                &format!("{}/2", game.player_count),
+                &game.spectator_count.to_string()
+            )
+        );
+    }
+    if list.truncated {
+        println!("(showing the first {} games)", list.games.len());
+    }
+}
+
+/// 게임 ID를 입력받음 (빈 입력이면 None)
+async fn prompt_game_id(lines: &mut InputLines) -> Option<String> {
+    println!("Game id:");
+    let line = lines.next_line().await.ok()??;
+    let game_id = line.trim();
+    (!game_id.is_empty()).then(|| game_id.to_string())
+}
+
+/// 프리셋 옵션 요약 (예: "clock 60+2s, unlimited takebacks, hints")
+fn describe_options(options: &GameOptions) -> String {
+    let mut parts = Vec::new();
+    if options.clock_initial_secs > 0 {
+        parts.push(format!("clock {}+{}s", options.clock_initial_secs, options.clock_increment_secs));
+    }
+    match options.max_takebacks {
+        -1 => parts.push("unlimited takebacks".to_string()),
+        0 => {}
+        n => parts.push(format!("{} takebacks", n)),
+    }
+    if options.hints_allowed {
+        parts.push("hints".to_string());
+    }
+    if options.bot_takeover_on_abandon {
+        parts.push("bot takes over on abandon".to_string());
+    }
+    if options.strict_mode {
+        parts.push("strict".to_string());
+    }
+    if options.spectator_delay_secs > 0 {
+        parts.push(format!("{}s spectator delay", options.spectator_delay_secs));
+    }
+    if parts.is_empty() {
+        "no extras".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+/// 숫자를 입력받음 (빈 입력은 0 = 서버 기본값, 숫자가 아니거나 입력이 끝나면 None)
+async fn prompt_number(lines: &mut InputLines, prompt: &str) -> Option<i32> {
+    println!("{}", prompt);
+    let line = lines.next_line().await.ok()??;
+    match line.trim() {
+        "" => Some(0),
+        input => match input.parse() {
+            Ok(n) => Some(n),
+            Err(_) => {
+                println!("Not a number.");
+                None
+            }
+        },
+    }
+}
+
+/// 프리셋 목록을 보여 주고 고른 프리셋으로 게임 생성 (취소하거나 실패하면 None)
+async fn create_game_from_preset(lines: &mut InputLines, endpoint: &Endpoint) -> Option<String> {
+    let presets = match rpc::list_presets(endpoint).await {
+        Ok(presets) => presets,
+        Err(e) => {
+            error!(error = %e, "failed to list presets");
+            return None;
+        }
+    };
+    for (i, preset) in presets.iter().enumerate() {
+        let options = preset.options.unwrap_or_default();
+        println!("{}) {:<12} {}", i + 1, preset.name, describe_options(&options));
+    }
+    println!("Choose a preset by number or name (empty to cancel):");
+    let line = lines.next_line().await.ok()??;
+    let choice = line.trim();
+    if choice.is_empty() {
+        return None;
+    }
+    let preset = match choice.parse::<usize>() {
+        Ok(n) => presets.get(n.wrapping_sub(1)).map(|p| p.name.clone()),
+        Err(_) => presets.iter().find(|p| p.name.eq_ignore_ascii_case(choice)).map(|p| p.name.clone()),
+    };
+    let Some(preset) = preset else {
+        println!("Unknown preset.");
+        return None;
+    };
+    let board_size = prompt_number(lines, "Board size (3-15, empty for 3):").await?;
+    let win_length = prompt_number(lines, "Marks in a row to win (3 up to the board size, empty for 3):").await?;
+    match rpc::create_game(endpoint, &preset, board_size, win_length).await {
+        Ok(created) => {
+            println!(
+                "Created a {} game on a {}x{} board ({} in a row). Share game id {} with your opponent.",
+                preset, created.board_size, created.board_size, created.win_length, created.game_id
+            );
+            Some(created.game_id)
+        }
+        Err(e) => {
+            error!(error = %e, "failed to create game");
+            None
+        }
+    }
+}
+
+/// 매치메이킹 대기열에서 상대를 기다림 ('leave'로 나가거나 실패하면 None)
+async fn matchmaking(lines: &mut InputLines, connection: &Connection) -> Option<JoinMode> {
+    // 설정이나 --name으로 이름을 정했으면 묻지 않음
+    let player_name = if connection.name.is_empty() {
+        println!("Your name:");
+        let line = lines.next_line().await.ok()??;
+        line.trim().to_string()
+    } else {
+        connection.name.clone()
+    };
+
+    let mut client = match connection.endpoint.connect().await.map(telemetry::traced) {
+        Ok(client) => client,
+        Err(e) => {
+            error!(error = %e, "failed to connect");
+            return None;
+        }
+    };
+    let mut updates = match client.join_matchmaking(Request::new(MatchmakingRequest { player_name })).await {
+        Ok(response) => response.into_inner(),
+        Err(status) => {
+            println!("Could not join the queue: {}", status.message());
+            return None;
+        }
+    };
+
+    let mut ticket = String::new();
+    loop {
+        tokio::select! {
+            update = updates.message() => match update {
+                Ok(Some(update)) => {
+                    ticket = update.ticket;
+                    if let Some(game) = update.game {
+                        println!("Opponent found! Joining game {}.", game.game_id);
+                        return Some(JoinMode::Matched(game.game_id, game.session_token));
+                    }
+                    println!("Waiting in the queue (position {}). Type 'leave' to stop waiting.", update.queue_position);
+                }
+                Ok(None) => {
+                    println!("The server closed the matchmaking queue.");
+                    return None;
+                }
+                Err(status) => {
+                    warn!(code = ?status.code(), message = status.message(), "matchmaking stream error");
+                    return None;
+                }
+            },
+            line = lines.next_line() => match line.ok()? {
+                Some(input) if input.trim().eq_ignore_ascii_case("leave") => {
+                    if let Err(e) = rpc::leave_matchmaking(&connection.endpoint, &ticket).await {
+                        warn!(error = %e, "failed to leave matchmaking");
+                    }
+                    println!("Left the queue.");
+                    return None;
+                }
+                Some(_) => println!("Still waiting. Type 'leave' to stop waiting."),
+                None => return None,
+            },
+        }
+    }
+}
+
+/// 게임 시작 전 로비 메뉴 (종료를 고르면 None)
+async fn lobby_menu(lines: &mut InputLines, connection: &Connection) -> Option<JoinMode> {
+    loop {
+        println!("\n=== Lobby ===");
+        println!("1) quick match");
+        println!("2) join game by id");
+        println!("3) spectate game by id");
+        println!("4) list games");
+        println!("5) list games waiting for an opponent");
+        println!("6) create a game from a preset");
+        println!("7) find an opponent (matchmaking queue)");
+        println!("8) create a private game and get an invite code");
+        println!("9) join a friend's game with an invite code");
+        println!("Choose an option, or type 'exit' to quit:");
+
+        let line = lines.next_line().await.ok()??;
+        match line.trim() {
+            "1" => return Some(JoinMode::Quick(BotDifficulty::Unspecified)),
+            "2" => {
+                if let Some(game_id) = prompt_game_id(lines).await {
+                    return Some(JoinMode::Game(game_id));
+                }
+            }
+            "3" => {
+                if let Some(game_id) = prompt_game_id(lines).await {
+                    return Some(JoinMode::Spectate(game_id));
+                }
+            }
+            "4" | "5" => {
+                let filter = if line.trim() == "5" { GameStatusFilter::Waiting } else { GameStatusFilter::All };
+                match rpc::list_games(&connection.endpoint, filter).await {
+                    Ok(list) => print_game_table(&list),
+                    Err(e) => error!(error = %e, "failed to list games"),
+                }
+            }
+            "6" => {
+                if let Some(game_id) = create_game_from_preset(lines, &connection.endpoint).await {
+                    return Some(JoinMode::Game(game_id));
+                }
+            }
+            "7" => {
+                if let Some(mode) = matchmaking(lines, connection).await {
+                    return Some(mode);
+                }
+            }
+            "8" => match rpc::create_private_game(&connection.endpoint).await {
+                Ok(created) => {
+                    println!("Invite code: {} — share it with your friend (it expires after a while).", created.invite_code);
+                    return Some(JoinMode::Game(created.game_id));
+                }
+                Err(e) => error!(error = %e, "failed to create private game"),
+            },
+            "9" => {
+                println!("Invite code:");
+                let code = lines.next_line().await.ok()??;
+                match rpc::join_private_game(&connection.endpoint, code.trim()).await {
+                    Ok(game) => {
+                        println!("Joining game {}.", game.game_id);
+                        return Some(JoinMode::Matched(game.game_id, game.session_token));
+                    }
+                    Err(e) => println!("Could not join with that code: {}", e),
+                }
+            }
+            input if input.eq_ignore_ascii_case("exit") => return None,
+            _ => println!("Invalid option."),
+        }
+    }
+}
+
+/// 레이팅 표시 (player_id 없이 참가한 플레이어는 0으로 옴)
+fn rating_label(rating: i32) -> String {
+    if rating > 0 { rating.to_string() } else { "unrated".to_string() }
+}
+
+/// 게임 시작 전에 접속이 끊겼을 때 다시 접속할지 묻기
+async fn confirm_reconnect(lines: &mut InputLines) -> bool {
+    println!("Lost the connection before the game started. Reconnect? (y/n)");
+    matches!(lines.next_line().await, Ok(Some(line)) if line.trim().eq_ignore_ascii_case("y"))
 }
 
 /// 메인 게임 실행 함수
-async fn run_game() -> Result<(), Box<dyn std::error::Error>> {
-    println!("Connecting to gRPC server...");
-    let mut client = TicTacToeClient::connect("http://[::1]:50051").await?;
+async fn run_game(
+    mode: JoinMode,
+    mut lines: InputLines,
+    policy: RetryPolicy,
+    connection: Connection,
+    identity: Join,
+    output: Output,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    // 플레이어 ID, 원하는 심볼과 표시 글자, 이름은 어떤 방식으로 참가하든 함께 보냄
+    let mut join = Join {
+        player_id: identity.player_id,
+        preferred_symbol: identity.preferred_symbol,
+        marker: identity.marker,
+        player_name: identity.player_name,
+        board_diffs: true,
+        ..mode.to_join()
+    };
+    loop {
+        println!("Connecting to gRPC server...");
+        let (move_tx, rx) = match &mode {
+            // 관전은 보낼 메시지가 없으므로 Spectate 스트림만 받고, 보내는 채널은 쓰이지 않음
+            JoinMode::Spectate(game_id) => (mpsc::channel(1).0, open_spectate(&connection, game_id, &policy).await?),
+            JoinMode::Replay(game_id) => (mpsc::channel(1).0, open_replay(&connection, game_id, &policy).await?),
+            _ => open_session(&connection, join.clone(), &policy).await.inspect_err(|e| println!("Could not join the game: {}", e))?,
+        };
 
-    let (move_tx, move_rx) = mpsc::channel(32);
-    let outbound = tokio_stream::wrappers::ReceiverStream::new(move_rx);
+        let (ui_tx, ui_rx) = if output.plain {
+            (None, None)
+        } else {
+            let (ui_tx, ui_rx) = mpsc::unbounded_channel();
+            (Some(ui_tx), Some(ui_rx))
+        };
+        let read_only = matches!(mode, JoinMode::Spectate(_) | JoinMode::Replay(_));
+        let client_state = Arc::new(ClientState::new(move_tx, read_only, ui_tx, connection.clone(), output));
 
-    let response = client.play(Request::new(outbound)).await?;
-    let (_metadata, rx, _extensions) = response.into_parts();
+        if matches!(mode, JoinMode::Quick(_)) && output.plain {
+            tokio::spawn(search_indicator(Arc::clone(&client_state)));
+        }
 
-    let client_state = Arc::new(ClientState::new());
+        let state_clone = Arc::clone(&client_state);
+        let updates = tokio::spawn(async move {
+            process_server_updates(rx, state_clone).await;
+        });
+        if let Some(interval) = connection.heartbeat.filter(|_| !client_state.spectating) {
+            tokio::spawn(send_heartbeats(Arc::clone(&client_state), interval));
+        }
 
-    let state_clone = Arc::clone(&client_state);
-    tokio::spawn(async move {
-        process_server_updates(rx, state_clone).await;
-    });
+        match ui_rx {
+            Some(ui_rx) => tokio::spawn(run_tui(Arc::clone(&client_state), ui_rx)).await??,
+            // 관전 중에는 입력을 받지 않고 게임이 끝날 때까지 업데이트만 출력
+            None if client_state.spectating => updates.await?,
+            None => lines = process_user_input(Arc::clone(&client_state), lines).await,
+        }
+
+        // --no-retry면 스크립트가 멈추지 않도록 묻지 않고 종료
+        if !*client_state.lost_before_start.lock().await || policy.max_attempts <= 1 || !confirm_reconnect(&mut lines).await {
+            return Ok(());
+        }
+        // 자리를 받았으면 세션 토큰으로 같은 자리에 돌아감
+        let session_token = client_state.session_token.lock().await.clone();
+        if let Some(session_token) = session_token {
+            let game_id = client_state.game_id.lock().await.clone();
+            join = Join { session_token, game_id, board_diffs: true, ..Default::default() };
+        }
+    }
+}
+
+/// 메인 함수: 게임 종료 후 터미널 종료
+/// 클라이언트 실행 인자
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    overrides: ConfigOverrides,
+    /// 게임 화면 대신 줄 단위로 출력하고 표준 입력에서 수를 읽음 (스크립트용, 터미널이 아니면 자동으로 사용)
+    #[arg(long, global = true)]
+    plain: bool,
+    /// 줄 단위 출력에 색을 쓸지 (auto는 터미널이고 NO_COLOR가 없을 때만)
+    #[arg(long, global = true, value_enum, value_name = "WHEN", default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+    /// 차례 제한 시간 카운트다운(남은 시간 줄, 10초 남았을 때의 벨)을 끔 (줄을 덮어쓸 수 없는 단순한 터미널용)
+    #[arg(long, global = true)]
+    no_timer: bool,
+    /// 스팬을 내보낼 OpenTelemetry 수집기의 OTLP/gRPC 주소 (예: http://localhost:4317, 서버 요청에 추적 문맥을 실음)
+    #[arg(long, global = true, value_name = "URL")]
+    otlp_endpoint: Option<String>,
+    /// 초대 코드로만 참가할 수 있는 비공개 방을 만들고 코드를 출력
+    #[arg(long, conflicts_with = "join")]
+    create_room: bool,
+    /// --create-room으로 만드는 방을 몇 판 승부로 할지 (홀수, 과반을 먼저 이긴 쪽이 승리)
+    #[arg(long, value_name = "N", default_value_t = 0, requires = "create_room")]
+    best_of: u32,
+    /// --best-of 승부에서 판이 끝나면 한 판 더 요청 없이 바로 다음 판을 시작
+    #[arg(long, requires = "best_of")]
+    auto_rematch: bool,
+    /// --create-room으로 만드는 방을 얼티밋 틱택토(작은 보드 아홉 개로 나눈 9×9 보드)로
+    #[arg(long, requires = "create_room")]
+    ultimate: bool,
+    /// --create-room으로 만드는 방을 미제르(줄을 이은 쪽이 지는 방식)로
+    #[arg(long, requires = "create_room", conflicts_with = "ultimate")]
+    misere: bool,
+    /// --create-room으로 만드는 방을 와일드(차례마다 X와 O 중 놓을 심볼을 고르는 방식)로
+    #[arg(long, requires = "create_room", conflicts_with_all = ["ultimate", "misere"])]
+    wild: bool,
+    /// --create-room으로 만드는 방에 스왑 규칙을 씀 (첫 수가 놓인 직후 상대가 'swap'으로 그 수를 가져갈 수 있음)
+    #[arg(long, requires = "create_room", conflicts_with = "wild")]
+    swap_rule: bool,
+    /// 서버 없이 이 터미널에서 두 사람이 번갈아 두는 3×3 게임 (줄 단위로 출력)
+    #[arg(long, conflicts_with_all = ["create_room", "join", "replay", "spectate", "bot"])]
+    local: bool,
+    /// 친구가 알려 준 초대 코드로 비공개 방에 참가
+    #[arg(long, value_name = "CODE")]
+    join: Option<String>,
+    /// 서버에 남아 있는 끝난 게임을 게임 화면에서 한 수씩 다시 보기
+    #[arg(long, value_name = "GAME_ID", conflicts_with_all = ["create_room", "join", "spectate"])]
+    replay: Option<String>,
+    /// 사람 대신 봇이 자동으로 두고 결과 요약을 출력 (부하 테스트, 데모용). 값은 수를 고르는 전략
+    /// (easy는 random, hard는 minimax와 같음, 값 없이 주면 greedy)
+    #[arg(long, value_enum, value_name = "STRATEGY", num_args = 0..=1, default_missing_value = "greedy")]
+    bot: Option<Strategy>,
+    /// 봇이 수를 보내기 전에 기다리는 시간 (밀리초)
+    #[arg(long, value_name = "MS", default_value_t = 0, requires = "bot")]
+    bot_delay_ms: u64,
+    /// 동시에 여는 봇 연결 수 (연결마다 따로 참가하므로 봇끼리 짝이 지어짐)
+    #[arg(long, value_name = "N", default_value_t = 1, requires = "bot")]
+    games: u32,
+    /// 봇 연결 하나가 연달아 둘 게임 수
+    #[arg(long, value_name = "N", default_value_t = 1, requires = "bot")]
+    num_games: u32,
+}
+
+/// `client quick --difficulty`로 고르는 서버 봇 난이도
+#[derive(Clone, Copy, ValueEnum)]
+enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl From<Difficulty> for BotDifficulty {
+    fn from(difficulty: Difficulty) -> Self {
+        match difficulty {
+            Difficulty::Easy => BotDifficulty::Easy,
+            Difficulty::Medium => BotDifficulty::Medium,
+            Difficulty::Hard => BotDifficulty::Hard,
+        }
+    }
+}
 
-    process_user_input(move_tx, client_state).await;
+/// `client leaderboard --sort`로 고르는 순위 기준
+#[derive(Clone, Copy, ValueEnum)]
+enum LeaderboardSort {
+    Wins,
+    GamesPlayed,
+    WinRate,
+    AverageMoveTime,
+    EloRating,
+}
+
+impl From<LeaderboardSort> for SortField {
+    fn from(sort: LeaderboardSort) -> Self {
+        match sort {
+            LeaderboardSort::Wins => SortField::Wins,
+            LeaderboardSort::GamesPlayed => SortField::GamesPlayed,
+            LeaderboardSort::WinRate => SortField::WinRate,
+            LeaderboardSort::AverageMoveTime => SortField::AverageMoveTime,
+            LeaderboardSort::EloRating => SortField::EloRating,
+        }
+    }
+}
 
+#[derive(Subcommand)]
+enum Command {
+    /// 로비 없이 바로 빠른 대전
+    Quick {
+        /// 상대가 없을 때 대신 두는 서버 봇의 난이도
+        #[arg(long, value_enum, default_value_t = Difficulty::Medium)]
+        difficulty: Difficulty,
+    },
+    /// 게임의 현재 보드를 한 번 조회하고 종료
+    State { game_id: String },
+    /// 플레이어의 레이팅과 전적을 조회하고 종료
+    Rating { player_id: String },
+    /// 누적 통계 순위표를 출력하고 종료
+    Leaderboard {
+        /// 출력할 플레이어 수 (서버가 최대 100명까지 줌)
+        #[arg(long, default_value_t = 10)]
+        limit: i32,
+        /// 순위 기준 (같으면 player_id 순)
+        #[arg(long, value_enum, default_value_t = LeaderboardSort::Wins)]
+        sort: LeaderboardSort,
+    },
+    /// 지난 게임 아카이브 보기
+    #[command(subcommand)]
+    Archive(ArchiveCommand),
+}
+
+#[derive(Subcommand)]
+enum ArchiveCommand {
+    /// 저장된 게임 목록
+    List,
+    /// 조건으로 게임 검색
+    Search {
+        /// 상대 ("human" 또는 "house-bot")
+        #[arg(long)]
+        opponent: Option<String>,
+        /// 결과 ("win", "loss", "draw")
+        #[arg(long)]
+        result: Option<String>,
+        /// 첫 수의 칸 번호 (0-8)
+        #[arg(long)]
+        opening: Option<u8>,
+        /// 태그
+        #[arg(long)]
+        tag: Option<String>,
+    },
+    /// 게임을 한 수씩 다시 보기
+    Replay { id: u64 },
+    /// 게임에 태그 붙이기
+    Tag { id: u64, tag: String },
+}
+
+/// 아카이브 항목 한 줄 출력
+fn print_archive_entry(entry: &client::archive::ArchiveEntry) {
+    let date = entry.date.get(..10).unwrap_or(&entry.date);
+    let tags = if entry.tags.is_empty() { String::new() } else { format!(" [{}]", entry.tags.join(", ")) };
+    println!(
+        "#{:<4} {}  {} vs {:<9} {:<4}  {}{}",
+        entry.id, date, entry.symbol, entry.opponent, entry.result, entry.notation(), tags
+    );
+}
+
+/// `client archive ...` 명령 실행
+fn run_archive_command(command: ArchiveCommand, style: &Style) -> Result<(), Box<dyn std::error::Error>> {
+    let archive = Archive::open_default()?;
+    let index = archive.load()?;
+    if index.skipped > 0 {
+        warn!(skipped = index.skipped, path = %archive.path().display(), "skipped corrupt archive entries");
+    }
+
+    match command {
+        ArchiveCommand::List => {
+            if index.entries.is_empty() {
+                println!("The archive is empty.");
+            }
+            index.entries.iter().for_each(print_archive_entry);
+        }
+        ArchiveCommand::Search { opponent, result, opening, tag } => {
+            let matches = index.search(&SearchFilter { opponent, result, opening, tag });
+            if matches.is_empty() {
+                println!("No matching games.");
+            }
+            matches.into_iter().for_each(print_archive_entry);
+        }
+        ArchiveCommand::Replay { id } => {
+            let Some(entry) = index.get(id) else {
+                println!("No game #{} in the archive.", id);
+                return Ok(());
+            };
+            print_archive_entry(entry);
+            for (turn, (board, pos)) in entry.boards().iter().zip(&entry.moves).enumerate() {
+                println!("\nMove {}: {} -> {}", turn + 1, if turn % 2 == 0 { "X" } else { "O" }, pos);
+                print!("{}", render::board(&render::Board::new(board, entry.board_size), style));
+            }
+            println!("\nResult: {}", entry.status);
+        }
+        ArchiveCommand::Tag { id, tag } => {
+            if archive.tag(id, &tag)? {
+                println!("Tagged game #{} with '{}'.", id, tag);
+            } else {
+                println!("No game #{} in the archive.", id);
+            }
+        }
+    }
     Ok(())
 }
 
-/// 메인 함수: 게임 종료 후 터미널 종료
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    if let Err(e) = run_game().await {
-        eprintln!("Error in game session: {:?}", e);
+    let cli = Cli::parse();
+    // 화면 출력은 println!으로, 내부 진단 로그는 tracing으로 stderr에 남김 (RUST_LOG로 조절)
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn"));
+    let log = tracing_subscriber::fmt::layer().with_writer(std::io::stderr).with_filter(filter);
+    // --otlp-endpoint가 있으면 로그 레벨과 따로 info 스팬까지 내보냄
+    let tracer_provider = cli.otlp_endpoint.as_deref().map(telemetry::init).transpose()?;
+    let spans = tracer_provider.as_ref().map(|provider| telemetry::layer(provider).with_filter(LevelFilter::INFO));
+    tracing_subscriber::registry().with(log).with(spans).init();
+
+    let result = run(cli).await;
+    // 모아 둔 스팬을 마저 내보냄
+    if let Some(provider) = tracer_provider {
+        if let Err(e) = provider.shutdown() {
+            warn!(error = %e, "failed to flush OpenTelemetry spans");
+        }
+    }
+    result
+}
+
+async fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    let config = ClientConfig::from_overrides(&cli.overrides)?;
+    compression::install(config.compression)?;
+    let policy = config.retry_policy();
+    let connection = Connection { endpoint: config.endpoint()?, name: config.name.clone(), heartbeat: config.heartbeat_interval() };
+    let mut lines = BufReader::new(io::stdin()).lines();
+    let plain = cli.plain || !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal();
+    let style = Style::detect(cli.color);
+
+    if let Some(strategy) = cli.bot {
+        let bot = AiClient::new(connection.endpoint.clone(), strategy)
+            .with_player_id(config.player_id.clone())
+            .with_move_delay(Duration::from_millis(cli.bot_delay_ms));
+        println!(
+            "Bot ({}) playing {} games on each of {} connections against {}",
+            strategy, cli.num_games, cli.games, config.server
+        );
+        println!("{}", bot.run_concurrent(cli.games, cli.num_games).await);
+        return Ok(());
+    }
+
+    if cli.local {
+        local::play(&mut lines, &mut std::io::stdout(), &style).await?;
+        return Ok(());
+    }
+
+    let mode = match cli.command {
+        Some(Command::Archive(command)) => return run_archive_command(command, &style),
+        Some(Command::State { game_id }) => {
+            match rpc::get_game_state(&connection.endpoint, &game_id, "").await {
+                Ok(state) => {
+                    print!("{}", render::board(&render::Board::of(&state), &style));
+                    println!("Game {}: {} (next: {})", state.game_id, state.status, state.next_player);
+                }
+                Err(e) => println!("Could not fetch game {}: {}", game_id, e),
+            }
+            return Ok(());
+        }
+        Some(Command::Rating { player_id }) => {
+            match rpc::get_player_rating(&connection.endpoint, &player_id).await {
+                Ok(r) => println!("{}: {} ({}W {}L {}D)", r.player_id, r.rating, r.wins, r.losses, r.draws),
+                Err(e) => println!("Could not fetch the rating of {}: {}", player_id, e),
+            }
+            return Ok(());
+        }
+        Some(Command::Leaderboard { limit, sort }) => {
+            match rpc::get_leaderboard(&connection.endpoint, sort.into(), limit).await {
+                Ok(players) => print!("{}", render::leaderboard(&players)),
+                Err(e) => println!("Could not fetch the leaderboard: {}", e),
+            }
+            return Ok(());
+        }
+        // `client quick`: 로비 없이 바로 빠른 대전
+        Some(Command::Quick { difficulty }) => JoinMode::Quick(difficulty.into()),
+        // `--create-room`, `--join <code>`: 로비 없이 비공개 방으로, `--replay <id>`: 끝난 게임 다시 보기,
+        // `--spectate [id]`: 바로 관전 (ID가 없으면 가장 최근 게임)
+        None => match (cli.create_room, cli.join, cli.replay, config.spectate.clone()) {
+            (true, ..) => {
+                let mode = match (cli.ultimate, cli.misere, cli.wild) {
+                    (true, _, _) => GameMode::Ultimate,
+                    (_, true, _) => GameMode::Misere,
+                    (_, _, true) => GameMode::Wild,
+                    _ => GameMode::Classic,
+                };
+                JoinMode::CreateRoom(cli.best_of, mode, cli.auto_rematch, cli.swap_rule)
+            }
+            (_, Some(code), ..) => JoinMode::Room(code.trim().to_string()),
+            (_, _, Some(game_id), _) => JoinMode::Replay(game_id.trim().to_string()),
+            (_, _, _, Some(game_id)) => JoinMode::Spectate(game_id.trim().to_string()),
+            _ => match lobby_menu(&mut lines, &connection).await {
+                Some(mode) => mode,
+                None => return Ok(()),
+            },
+        },
+    };
+
+    // 서버가 받지 않는 이름(ASCII가 아닌 글자)이면 보내지 않고 서버 기본 이름("Player X")을 씀
+    let player_name = text::player_name(&config.name).unwrap_or_else(|_| {
+        println!("Names shown to other players can only use printable ASCII characters; using the default name.");
+        None
+    });
+    let identity = Join {
+        player_id: config.player_id.clone(),
+        preferred_symbol: config.symbol.clone(),
+        marker: config.marker.clone(),
+        player_name: player_name.unwrap_or_default(),
+        ..Join::default()
+    };
+    if let Err(e) = run_game(mode, lines, policy, connection, identity, Output { plain, style, timer: !cli.no_timer }).await {
+        error!(error = %e, "game session failed");
     }
     println!("Game session ended. Exiting.");
     Ok(())
@@ -0,0 +1,150 @@
+//! 서버에 보내기 전에 로컬에 알고 있는 보드로 수를 미리 검사
+//!
+//! 즉시 알려 주고 불필요한 왕복을 줄이기 위한 것일 뿐, 최종 판단은 서버가 합니다. 검사한 뒤 보내기 전에
+//! 새 상태가 와도 서버가 거부하면 그 응답이 그대로 표시됩니다.
+
+use std::fmt;
+
+/// 로컬 검사에서 걸린 수
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveRejection {
+    /// 보드 밖의 칸 (칸 수)
+    OutOfRange(usize),
+    /// 이미 채워진 칸 (칸 번호)
+    Taken(usize),
+    /// 상대 차례
+    NotYourTurn,
+    /// 얼티밋에서 정해진 작은 보드가 아님 (둬야 하는 작은 보드)
+    WrongSubBoard(usize),
+    /// 얼티밋에서 이미 승부가 난 작은 보드 (그 작은 보드)
+    SubBoardDecided(usize),
+}
+
+impl fmt::Display for MoveRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveRejection::OutOfRange(cells) => {
+                write!(f, "Invalid move. Please enter a number between 0 and {}.", cells.saturating_sub(1))
+            }
+            MoveRejection::Taken(position) => write!(f, "Cell {} is taken.", position),
+            MoveRejection::NotYourTurn => write!(f, "Wait for your opponent."),
+            MoveRejection::WrongSubBoard(sub_board) => write!(f, "You must play in sub-board {}.", sub_board),
+            MoveRejection::SubBoardDecided(sub_board) => write!(f, "Sub-board {} is already decided.", sub_board),
+        }
+    }
+}
+
+/// 읽을 수 없는 칸 입력
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// 빈 입력
+    Empty,
+    /// 칸 번호나 좌표가 아님 (보드 한 변의 칸 수)
+    Unrecognized(usize),
+    /// 행이나 열에 0을 씀: 0부터 세는 좌표인지 1부터 세는 좌표인지 알 수 없어 거부
+    ZeroCoordinate,
+    /// 보드 밖의 칸 번호 (칸 수)
+    CellOutOfRange(usize),
+    /// 보드 밖의 행이나 열 (보드 한 변의 칸 수)
+    CoordinateOutOfRange(usize),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "Enter a cell."),
+            ParseError::Unrecognized(size) => write!(
+                f,
+                "Enter a cell number (0-{}), row,col (e.g. 1,{}) or a column letter and row (e.g. {}1).",
+                (size * size).saturating_sub(1),
+                size,
+                column_letter(size.saturating_sub(1))
+            ),
+            ParseError::ZeroCoordinate => {
+                write!(f, "Rows and columns start at 1: the top-left cell is 1,1 or a1 (cell numbers start at 0).")
+            }
+            ParseError::CellOutOfRange(cells) => {
+                write!(f, "Invalid move. Please enter a number between 0 and {}.", cells.saturating_sub(1))
+            }
+            ParseError::CoordinateOutOfRange(size) => write!(
+                f,
+                "That cell is off the board: rows go from 1 to {} and columns from 1 to {} (a-{}).",
+                size,
+                size,
+                column_letter(size.saturating_sub(1))
+            ),
+        }
+    }
+}
+
+/// 열 번호(0부터)의 글자 (0 → 'a')
+fn column_letter(column: usize) -> char {
+    (b'a' + column.min(25) as u8) as char
+}
+
+/// `board_size`×`board_size` 보드의 칸 입력을 칸 번호로 읽음
+///
+/// - `4`: 칸 번호 (0부터, 보드에 표시되는 번호)
+/// - `2,3` 또는 `2 3`: 행과 열 (1부터, 위쪽 행과 왼쪽 열이 1)
+/// - `b2`: 열 글자(a부터)와 행 (1부터, 체스 표기와 달리 위쪽 행이 1)
+///
+/// 칸 번호만 0부터 세므로, 좌표에 0이 있으면 어느 쪽으로 세었는지 알 수 없어 거부합니다.
+/// 열 글자는 a-z까지라 26×26 보드까지 쓸 수 있습니다. 서버의 기보(`ExportGame`)는 3x3 전용이고 글자가 행이라
+/// 같은 좌표가 다른 칸입니다 (기보의 a2=칸 1은 여기서 b1, 여기서의 a2=칸 3은 기보로 b1).
+pub fn parse_move(input: &str, board_size: usize) -> Result<usize, ParseError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    let unrecognized = ParseError::Unrecognized(board_size);
+    if input.chars().all(|c| c.is_ascii_digit()) {
+        let position = input.parse::<usize>().map_err(|_| ParseError::CellOutOfRange(board_size * board_size))?;
+        if position >= board_size * board_size {
+            return Err(ParseError::CellOutOfRange(board_size * board_size));
+        }
+        return Ok(position);
+    }
+    let first = input.chars().next().unwrap_or_default();
+    let (row, column) = if first.is_ascii_alphabetic() {
+        let row = input[1..].trim().parse::<usize>().map_err(|_| unrecognized.clone())?;
+        (row, (first.to_ascii_lowercase() as u8 - b'a') as usize + 1)
+    } else {
+        let (row, column) = input.split_once(',').or_else(|| input.split_once(char::is_whitespace)).ok_or(unrecognized.clone())?;
+        let row = row.trim().parse::<usize>().map_err(|_| unrecognized.clone())?;
+        let column = column.trim().parse::<usize>().map_err(|_| unrecognized.clone())?;
+        (row, column)
+    };
+    if row == 0 || column == 0 {
+        return Err(ParseError::ZeroCoordinate);
+    }
+    if row > board_size || column > board_size {
+        return Err(ParseError::CoordinateOutOfRange(board_size));
+    }
+    Ok((row - 1) * board_size + column - 1)
+}
+
+/// 와일드 게임의 입력: 칸(`parse_move`의 형식) 뒤에 놓을 심볼 ("4 O", "4o", "b2 x"). 심볼을 빼면 None (자기 심볼을 놓음)
+pub fn parse_wild_move(input: &str, board_size: usize) -> Result<(usize, Option<String>), ParseError> {
+    let input = input.trim();
+    let mark = input.chars().last().map(|c| c.to_ascii_uppercase()).filter(|c| matches!(c, 'X' | 'O'));
+    match mark {
+        Some(mark) => {
+            let cell = &input[..input.len() - 1];
+            if cell.trim().is_empty() {
+                return Err(ParseError::Unrecognized(board_size));
+            }
+            Ok((parse_move(cell, board_size)?, Some(mark.to_string())))
+        }
+        None => Ok((parse_move(input, board_size)?, None)),
+    }
+}
+
+/// `symbol`이 `position`에 둘 수 있는지 마지막으로 받은 보드와 차례로 검사
+pub fn check_move(board: &[String], next_player: &str, symbol: &str, position: usize) -> Result<(), MoveRejection> {
+    match board.get(position) {
+        None => Err(MoveRejection::OutOfRange(board.len())),
+        Some(cell) if !cell.is_empty() => Err(MoveRejection::Taken(position)),
+        Some(_) if next_player != symbol => Err(MoveRejection::NotYourTurn),
+        Some(_) => Ok(()),
+    }
+}
@@ -0,0 +1,209 @@
+//! 줄 단위 모드(`--plain`)의 보드와 상태 줄 출력
+//!
+//! 표준 출력에 바로 쓰지 않고 문자열을 돌려주므로 출력을 가로채지 않고 검사할 수 있습니다. 색은 ANSI
+//! 이스케이프로 입히며, X와 O는 각자의 색, 이긴 줄은 반전, 빈 칸의 번호는 흐리게, 내 차례는 굵게, 오류는 빨간색입니다.
+//! `--color auto`(기본)면 `NO_COLOR`가 있거나 표준 출력이 터미널이 아닐 때 색을 쓰지 않습니다.
+
+use std::io::IsTerminal;
+
+use clap::ValueEnum;
+use common::text;
+
+use crate::tictactoe::{GameState, Move, PlayerStats};
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "1";
+const RED: &str = "31";
+const X_COLOR: &str = "36";
+const O_COLOR: &str = "35";
+const REVERSED: &str = "7";
+const DIM: &str = "2";
+
+/// 기본 보드 한 변의 칸 수 (이보다 큰 보드는 줄 앞에 칸 번호를 붙임)
+const DEFAULT_BOARD_SIZE: usize = 3;
+
+/// `--color`로 고르는 색 사용 여부
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    /// 터미널이 아니거나 NO_COLOR가 있으면 끔
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// 출력에 색을 입힐지
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Style {
+    pub color: bool,
+}
+
+impl Style {
+    /// 색을 입히는 스타일
+    pub fn colored() -> Self {
+        Style { color: true }
+    }
+
+    /// `choice`와 실행 환경(NO_COLOR, 표준 출력이 터미널인지)으로 정한 스타일
+    pub fn detect(choice: ColorChoice) -> Self {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Style::for_environment(choice, no_color, std::io::stdout().is_terminal())
+    }
+
+    /// `choice`가 Auto면 NO_COLOR가 없고 터미널일 때만 색을 씀
+    pub fn for_environment(choice: ColorChoice, no_color: bool, terminal: bool) -> Self {
+        let color = match choice {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => !no_color && terminal,
+        };
+        Style { color }
+    }
+
+    /// 굵게 (내 차례 안내)
+    pub fn bold(&self, text: &str) -> String {
+        self.paint(&[BOLD], text)
+    }
+
+    /// 빨간색 (오류)
+    pub fn error(&self, text: &str) -> String {
+        self.paint(&[RED], text)
+    }
+
+    /// 자리("X"/"O") 색으로 (그 밖의 자리는 그대로)
+    pub fn seat(&self, seat: &str, text: &str) -> String {
+        match seat {
+            "X" => self.paint(&[X_COLOR], text),
+            "O" => self.paint(&[O_COLOR], text),
+            _ => text.to_string(),
+        }
+    }
+
+    fn paint(&self, codes: &[&str], text: &str) -> String {
+        if !self.color || codes.is_empty() || text.is_empty() {
+            return text.to_string();
+        }
+        format!("\x1b[{}m{}{}", codes.join(";"), text, RESET)
+    }
+}
+
+/// 그릴 보드: 칸마다 자리("X"/"O"/""), 자리마다 표시할 글자, 강조할 칸
+#[derive(Debug, Clone, Default)]
+pub struct Board<'a> {
+    pub cells: &'a [String],
+    pub size: usize,
+    /// X 자리의 표시 글자 (비어 있으면 "X")
+    pub x_marker: &'a str,
+    /// O 자리의 표시 글자 (비어 있으면 "O")
+    pub o_marker: &'a str,
+    /// 이긴 줄의 칸 번호
+    pub winning_line: &'a [usize],
+    /// 빈 칸에 칸 번호를 표시 (둘 칸을 입력하는 플레이어용)
+    pub indices: bool,
+}
+
+impl<'a> Board<'a> {
+    /// 자리 심볼을 그대로 표시하는 보드
+    pub fn new(cells: &'a [String], size: usize) -> Self {
+        Board { cells, size, ..Board::default() }
+    }
+
+    /// 받은 상태의 보드 (표시 글자 포함, 보드 크기를 보내지 않는 서버면 3×3)
+    pub fn of(state: &'a GameState) -> Self {
+        let size = if state.board_size > 0 { state.board_size as usize } else { DEFAULT_BOARD_SIZE };
+        Board { x_marker: &state.x_symbol, o_marker: &state.o_symbol, ..Board::new(&state.board, size) }
+    }
+
+    /// 칸에 표시할 글자 (자리의 표시 글자, 빈 칸이면 `indices`일 때 칸 번호)
+    fn shown(&self, position: usize) -> String {
+        match self.cells[position].as_str() {
+            "X" if !self.x_marker.is_empty() => self.x_marker.to_string(),
+            "O" if !self.o_marker.is_empty() => self.o_marker.to_string(),
+            "" if self.indices => position.to_string(),
+            other => other.to_string(),
+        }
+    }
+}
+
+/// 격자 보드 (이모지 등 폭이 넓은 글자도 칸이 어긋나지 않도록 표시 폭 기준으로 정렬, 줄마다 개행)
+///
+/// 3×3보다 큰 보드는 칸 번호를 찾기 쉽도록 각 줄 앞에 그 줄 첫 칸의 번호를 붙입니다.
+pub fn board(board: &Board, style: &Style) -> String {
+    let size = board.size.max(1);
+    let cell_width = (0..board.cells.len()).map(|position| text::display_width(&board.shown(position))).max().unwrap_or(0).max(1);
+    let label_width = if size > DEFAULT_BOARD_SIZE { board.cells.len().saturating_sub(1).to_string().len() + 1 } else { 0 };
+    let separator = format!("{}{}\n", " ".repeat(label_width), "-".repeat(size * (cell_width + 3) + 1));
+    let mut out = separator.clone();
+    for (i, row) in board.cells.chunks(size).enumerate() {
+        let cells: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(col, seat)| {
+                let shown = text::center_to_width(&board.shown(i * size + col), cell_width);
+                let color = match seat.as_str() {
+                    "X" => Some(X_COLOR),
+                    "O" => Some(O_COLOR),
+                    "" if board.indices => Some(DIM),
+                    _ => None,
+                };
+                let mut codes: Vec<&str> = color.into_iter().collect();
+                if board.winning_line.contains(&(i * size + col)) {
+                    codes.push(REVERSED);
+                }
+                style.paint(&codes, &shown)
+            })
+            .collect();
+        let label = if label_width > 0 { format!("{:>width$} ", i * size, width = label_width - 1) } else { String::new() };
+        out.push_str(&format!("{}| {} |\n", label, cells.join(" | ")));
+        out.push_str(&separator);
+    }
+    out
+}
+
+/// 받은 상태의 보드 (승부가 난 게임이면 이긴 줄을 강조, 얼티밋 보드는 `ultimate::board_lines`로 그림)
+pub fn game_board(state: &GameState, style: &Style, indices: bool) -> String {
+    let line = winning_line(state);
+    board(&Board { indices, winning_line: &line, ..Board::of(state) }, style)
+}
+
+/// 줄을 이어 끝난 게임에서 이긴 줄의 칸 번호 (진행 중이거나 기권, 무승부면 비어 있음)
+pub fn winning_line(state: &GameState) -> Vec<usize> {
+    if !state.status.ends_with("_win") {
+        return Vec::new();
+    }
+    let size = Board::of(state).size;
+    let win_length = if state.win_length > 0 { state.win_length as usize } else { DEFAULT_BOARD_SIZE };
+    common::board::winning_line(&state.board, size, win_length).unwrap_or_default()
+}
+
+/// 끝난 게임의 기보 한 줄 ("Moves: 1. X→4, 2. O→0, ...", 수가 없으면 None)
+pub fn history(history: &[Move]) -> Option<String> {
+    if history.is_empty() {
+        return None;
+    }
+    let moves: Vec<String> = history.iter().enumerate().map(|(i, m)| format!("{}. {}\u{2192}{}", i + 1, m.player_id, m.position)).collect();
+    Some(format!("Moves: {}", moves.join(", ")))
+}
+
+/// 순위표 (`client leaderboard`): 받은 순서가 곧 순위이며, 이름 칸은 가장 긴 이름의 표시 폭에 맞춤
+pub fn leaderboard(players: &[PlayerStats]) -> String {
+    if players.is_empty() {
+        return "No finished games yet.\n".to_string();
+    }
+    let name_width = players.iter().map(|p| text::display_width(&p.player_id)).max().unwrap_or(0).max("Player".len());
+    let mut out = format!("{:>4}  {}  {:>5} {:>4} {:>4} {:>4} {:>5}\n", "Rank", text::pad_to_width("Player", name_width), "Games", "W", "L", "D", "Win%");
+    for (rank, p) in players.iter().enumerate() {
+        let win_rate = (p.wins * 100).checked_div(p.games_played).unwrap_or(0);
+        out.push_str(&format!(
+            "{:>4}  {}  {:>5} {:>4} {:>4} {:>4} {:>4}%\n",
+            rank + 1,
+            text::pad_to_width(&p.player_id, name_width),
+            p.games_played,
+            p.wins,
+            p.losses,
+            p.draws,
+            win_rate
+        ));
+    }
+    out
+}
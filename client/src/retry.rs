@@ -0,0 +1,112 @@
+//! 서버 접속 재시도 간격 (지수 백오프와 지터)
+
+use std::fmt::Display;
+use std::future::Future;
+use std::time::Duration;
+
+use rand::Rng;
+use tonic::transport::Endpoint;
+use tracing::warn;
+
+use crate::telemetry::{self, TracedClient};
+
+/// 접속 시도 한 번을 기다리는 최대 시간 (응답 없는 주소에서 오래 멈추지 않도록)
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 접속 재시도 정책: 실패할 때마다 대기 시간을 두 배로 늘리되 `max_delay`를 넘지 않음
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// 첫 시도를 포함한 최대 시도 횟수 (1이면 재시도 없음)
+    pub max_attempts: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy { max_attempts: 10, initial_delay: Duration::from_millis(500), max_delay: Duration::from_secs(15) }
+    }
+}
+
+impl RetryPolicy {
+    /// 한 번만 시도하고 실패하면 바로 포기 (`--no-retry`)
+    pub fn no_retry() -> Self {
+        RetryPolicy { max_attempts: 1, ..RetryPolicy::default() }
+    }
+
+    /// `attempt`번째(1부터) 시도가 실패한 뒤 다음 시도까지 기다릴 시간
+    pub fn delay_after(&self, attempt: u32) -> Duration {
+        let doublings = attempt.saturating_sub(1).min(31);
+        self.initial_delay.saturating_mul(1 << doublings).min(self.max_delay)
+    }
+
+    /// `delay_after`에 지터를 더한 대기 시간 (절반에서 전체 사이의 임의 값)
+    ///
+    /// 서버가 재시작될 때 여러 클라이언트가 같은 순간에 몰려 접속하지 않도록 흩어 놓습니다.
+    pub fn jittered_delay_after(&self, attempt: u32) -> Duration {
+        let delay = self.delay_after(attempt);
+        delay / 2 + delay.mul_f64(rand::thread_rng().gen_range(0.0..=0.5))
+    }
+}
+
+/// 서버에 접속합니다. 실패하면 `base_delay`부터 두 배씩(지터 포함) 기다리며 최대 `max_attempts`번 시도합니다.
+///
+/// 실패할 때마다 몇 초 뒤에 다시 시도하는지 카운트다운을 출력합니다.
+pub async fn connect_with_retry(
+    addr: &str,
+    max_attempts: usize,
+    base_delay: Duration,
+) -> Result<TracedClient, tonic::transport::Error> {
+    let policy = RetryPolicy {
+        max_attempts: u32::try_from(max_attempts).unwrap_or(u32::MAX).max(1),
+        initial_delay: base_delay,
+        ..RetryPolicy::default()
+    };
+    let endpoint = Endpoint::try_from(addr.to_string())?.connect_timeout(CONNECT_TIMEOUT);
+    connect_endpoint(&endpoint, policy).await
+}
+
+/// 설정해 둔 엔드포인트(TLS 등)로 접속합니다. 실패하면 정책에 따라 기다리며 다시 시도합니다.
+pub async fn connect_endpoint(endpoint: &Endpoint, policy: RetryPolicy) -> Result<TracedClient, tonic::transport::Error> {
+    let channel = retry_with_backoff(policy, |_| endpoint.connect()).await?;
+    Ok(telemetry::traced(channel))
+}
+
+/// `attempt_fn`(시도 번호를 1부터 받음)이 성공할 때까지 정책에 따라 지터를 넣어 기다리며 다시 호출합니다.
+/// 마지막 시도까지 실패하면 그 오류를 반환합니다.
+pub async fn retry_with_backoff<T, E, F, Fut>(policy: RetryPolicy, mut attempt_fn: F) -> Result<T, E>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: Display,
+{
+    let mut attempt = 1;
+    loop {
+        match attempt_fn(attempt).await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt >= policy.max_attempts => return Err(e),
+            Err(e) => {
+                let delay = policy.jittered_delay_after(attempt);
+                warn!(attempt, max_attempts = policy.max_attempts, retry_in_ms = delay.as_millis() as u64, error = %e, "connect failed");
+                countdown(delay, attempt, policy.max_attempts).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// 다음 시도까지 1초마다 남은 시간을 출력하며 기다림
+async fn countdown(delay: Duration, attempt: u32, max_attempts: u32) {
+    let mut remaining = delay;
+    while !remaining.is_zero() {
+        println!(
+            "Server unavailable. Retrying in {}s (attempt {}/{})...",
+            remaining.as_secs_f32().ceil() as u64,
+            attempt + 1,
+            max_attempts
+        );
+        let step = remaining.min(Duration::from_secs(1));
+        tokio::time::sleep(step).await;
+        remaining -= step;
+    }
+}
@@ -0,0 +1,105 @@
+//! 스트림 없이 한 번 호출하고 끝나는 단항 RPC
+
+use tonic::transport::Endpoint;
+use tonic::Request;
+use tracing::instrument;
+
+use crate::telemetry;
+use crate::tictactoe::{
+    ChatMessage, CreateGameRequest, CreateGameResponse, GameState, GameStateRequest, GameStatusFilter, JoinRequest, LeaderboardRequest,
+    LeaveRequest, ListGamesRequest, ListGamesResponse, ListPresetsRequest, PlayerRating, PlayerRatingRequest, PlayerStats, Preset,
+    SortField,
+};
+
+type RpcResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+/// 서버에서 게임 목록의 첫 페이지를 받아 옵니다.
+#[instrument(skip_all)]
+pub async fn list_games(endpoint: &Endpoint, filter: GameStatusFilter) -> RpcResult<ListGamesResponse> {
+    let mut client = telemetry::traced(endpoint.connect().await?);
+    let request = ListGamesRequest { filter: filter.into(), ..ListGamesRequest::default() };
+    let response = client.list_games(Request::new(request)).await?;
+    Ok(response.into_inner())
+}
+
+/// 게임의 현재 상태를 한 번 조회합니다. 초대 전용 게임은 그 게임 자리의 `session_token`이 있어야 보입니다.
+#[instrument(skip_all)]
+pub async fn get_game_state(endpoint: &Endpoint, game_id: &str, session_token: &str) -> RpcResult<GameState> {
+    let mut client = telemetry::traced(endpoint.connect().await?);
+    let request = GameStateRequest { game_id: game_id.to_string(), session_token: session_token.to_string() };
+    let response = client.get_game_state(Request::new(request)).await?;
+    Ok(response.into_inner())
+}
+
+/// 플레이어의 Elo 레이팅과 전적을 조회합니다.
+#[instrument(skip_all)]
+pub async fn get_player_rating(endpoint: &Endpoint, player_id: &str) -> RpcResult<PlayerRating> {
+    let mut client = telemetry::traced(endpoint.connect().await?);
+    let request = PlayerRatingRequest { player_id: player_id.to_string() };
+    let response = client.get_player_rating(Request::new(request)).await?;
+    Ok(response.into_inner())
+}
+
+/// 누적 통계 기준 상위 플레이어를 받아 옵니다 (`limit`이 0이면 서버 기본값).
+#[instrument(skip_all)]
+pub async fn get_leaderboard(endpoint: &Endpoint, sort_by: SortField, limit: i32) -> RpcResult<Vec<PlayerStats>> {
+    let mut client = telemetry::traced(endpoint.connect().await?);
+    let request = LeaderboardRequest { sort_by: sort_by.into(), limit, ..LeaderboardRequest::default() };
+    let response = client.get_leaderboard(Request::new(request)).await?;
+    Ok(response.into_inner().players)
+}
+
+/// 서버의 게임 옵션 프리셋 목록을 받아 옵니다.
+#[instrument(skip_all)]
+pub async fn list_presets(endpoint: &Endpoint) -> RpcResult<Vec<Preset>> {
+    let mut client = telemetry::traced(endpoint.connect().await?);
+    let response = client.list_presets(Request::new(ListPresetsRequest {})).await?;
+    Ok(response.into_inner().presets)
+}
+
+/// 프리셋과 보드 크기로 새 게임을 만듭니다 (참가는 반환된 game_id로 따로 합니다).
+#[instrument(skip_all)]
+pub async fn create_game(endpoint: &Endpoint, preset: &str, board_size: i32, win_length: i32) -> RpcResult<CreateGameResponse> {
+    let mut client = telemetry::traced(endpoint.connect().await?);
+    let request = CreateGameRequest { preset: preset.to_string(), board_size, win_length, ..CreateGameRequest::default() };
+    let response = client.create_game(Request::new(request)).await?;
+    Ok(response.into_inner())
+}
+
+/// 기본 설정의 비공개 게임을 만들고 초대 코드를 받습니다.
+#[instrument(skip_all)]
+pub async fn create_private_game(endpoint: &Endpoint) -> RpcResult<CreateGameResponse> {
+    let mut client = telemetry::traced(endpoint.connect().await?);
+    let response = client.create_private_game(Request::new(CreateGameRequest::default())).await?;
+    Ok(response.into_inner())
+}
+
+/// 초대 코드로 비공개 게임의 자리를 받습니다 (반환된 세션 토큰으로 Play에 접속합니다).
+#[instrument(skip_all)]
+pub async fn join_private_game(endpoint: &Endpoint, invite_code: &str) -> RpcResult<GameState> {
+    let mut client = telemetry::traced(endpoint.connect().await?);
+    let response = client.join_private_game(Request::new(JoinRequest { invite_code: invite_code.to_string() })).await?;
+    Ok(response.into_inner())
+}
+
+/// 매치메이킹 대기열에서 나갑니다 (대기열에 있었으면 true).
+#[instrument(skip_all)]
+pub async fn leave_matchmaking(endpoint: &Endpoint, ticket: &str) -> RpcResult<bool> {
+    let mut client = telemetry::traced(endpoint.connect().await?);
+    let response = client.leave_matchmaking(Request::new(LeaveRequest { ticket: ticket.to_string() })).await?;
+    Ok(response.into_inner().removed)
+}
+
+/// 게임 스트림 밖에서 채팅을 보냅니다 (세션 토큰으로 보내는 자리를 확인).
+#[instrument(skip_all)]
+pub async fn send_chat(endpoint: &Endpoint, game_id: &str, symbol: &str, session_token: &str, text: &str) -> RpcResult<()> {
+    let mut client = telemetry::traced(endpoint.connect().await?);
+    let request = ChatMessage {
+        game_id: game_id.to_string(),
+        sender_symbol: symbol.to_string(),
+        text: text.to_string(),
+        session_token: session_token.to_string(),
+    };
+    client.send_chat(Request::new(request)).await?;
+    Ok(())
+}
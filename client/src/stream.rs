@@ -0,0 +1,123 @@
+//! Play 스트림에서 받은 항목 분류
+//!
+//! 스트림에서 받은 항목 하나(`Result<Option<GameState>, Status>`)가 업데이트인지, 스트림의 끝인지 나누고,
+//! 끝이라면 왜 끝났는지([`Disconnect`])를 정합니다. 서버가 스스로 닫은 경우(정상 종료, 사유를 밝힌 종료,
+//! 거부)는 재접속해도 소용없고, 연결이 끊긴 경우만 재접속합니다.
+
+use prost::Message;
+use tonic::{Code, Status};
+
+use crate::tictactoe::{EndReason, GameState, StreamEnd};
+
+/// 스트림에서 받은 항목 하나
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+    /// 연결 확인용 하트비트 (화면에 반영하지 않고 응답만 보냄)
+    Heartbeat,
+    /// 게임 상태 업데이트
+    Update(Box<GameState>),
+    /// 스트림이 끝남
+    End(Disconnect),
+}
+
+/// 스트림이 끝난 이유 (마지막 안내 문구와 재접속 여부를 정함)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Disconnect {
+    /// 게임이 끝난 뒤 서버가 스트림을 정상 종료
+    Finished,
+    /// 게임이 끝나기 전에 서버가 오류 없이 스트림을 닫음
+    ServerClosed,
+    /// 서버가 사유를 밝히고 스트림을 끝냄 (Status details의 StreamEnd)
+    Ended { reason: EndReason, message: String },
+    /// 서버가 참가나 재접속을 거부함
+    Refused { code: Code, message: String },
+    /// 전송 오류 등으로 연결이 끊김 (재접속할 수 있음)
+    Lost { code: Code, message: String },
+}
+
+/// 스트림에서 받은 항목 분류 (`game_over`는 끝난 게임의 상태를 이미 받았는지)
+pub fn classify(item: Result<Option<GameState>, Status>, game_over: bool) -> Item {
+    match item {
+        Ok(Some(update)) if update.heartbeat => Item::Heartbeat,
+        Ok(Some(update)) => Item::Update(Box::new(update)),
+        // 서버는 게임이 끝난 뒤에만 정상 종료하므로, 그 전에 닫혔다면 따로 알림
+        Ok(None) if game_over => Item::End(Disconnect::Finished),
+        Ok(None) => Item::End(Disconnect::ServerClosed),
+        Err(status) => Item::End(Disconnect::from(&status)),
+    }
+}
+
+/// 서버가 사유를 밝히고 스트림을 끝낸 경우 그 사유 (Status details의 StreamEnd)
+pub fn end_reason(status: &Status) -> Option<EndReason> {
+    if status.details().is_empty() {
+        return None;
+    }
+    let end = StreamEnd::decode(status.details()).ok()?;
+    EndReason::try_from(end.reason).ok()
+}
+
+/// 서버가 의도적으로 거부한 경우인지 (이 경우 재접속해도 소용없음)
+pub fn is_refusal(code: Code) -> bool {
+    matches!(code, Code::NotFound | Code::ResourceExhausted | Code::InvalidArgument)
+}
+
+/// 사용자에게 보여 줄 Status의 코드와 메시지 (예: "NotFound: no such game")
+pub fn describe(status: &Status) -> String {
+    format!("{:?}: {}", status.code(), status.message())
+}
+
+impl From<&Status> for Disconnect {
+    fn from(status: &Status) -> Self {
+        let (code, message) = (status.code(), status.message().to_string());
+        match end_reason(status) {
+            Some(reason) => Disconnect::Ended { reason, message },
+            None if is_refusal(code) => Disconnect::Refused { code, message },
+            None => Disconnect::Lost { code, message },
+        }
+    }
+}
+
+impl Disconnect {
+    /// 재접속하면 이어서 할 수 있는 끊김인지
+    pub fn is_recoverable(&self) -> bool {
+        matches!(self, Disconnect::Lost { .. })
+    }
+
+    /// 스트림이 끝났을 때 알릴 문구 (정상 종료면 None). `seated`는 자리를 받은 뒤인지
+    pub fn message(&self, seated: bool) -> Option<String> {
+        let message = match self {
+            Disconnect::Finished => return None,
+            Disconnect::ServerClosed => "The server closed the game stream.".to_string(),
+            Disconnect::Ended { reason, message } => match reason {
+                EndReason::SessionTakenOver => "This game was resumed from another connection. Closing this one.".to_string(),
+                EndReason::GameAbandoned => "The game was cancelled: your opponent did not come back.".to_string(),
+                EndReason::ServerShutdown => "The server is shutting down.".to_string(),
+                EndReason::Kicked => "You were removed from the game by a server administrator.".to_string(),
+                EndReason::WaitingTimeout => "Matchmaking timed out, try again later.".to_string(),
+                EndReason::IdleTimeout => "The game was closed because this client stopped sending heartbeats.".to_string(),
+                EndReason::RateLimited => "The server closed the connection because this client sent too many messages.".to_string(),
+                EndReason::SlowConsumer => "Spectating ended: this client fell too far behind the game.".to_string(),
+                EndReason::Inactive => "The game was abandoned: no moves were made for too long.".to_string(),
+                EndReason::InternalError => "The game was cancelled because of a server error.".to_string(),
+                _ => format!("The server ended the game stream: {}", message),
+            },
+            Disconnect::Refused { code: Code::NotFound, .. } if !seated => {
+                "No game matches that game id or room code (room codes expire).".to_string()
+            }
+            Disconnect::Refused { code: Code::NotFound, .. } => "Session expired: the game is no longer available.".to_string(),
+            Disconnect::Refused { code, message } => format!("Server refused the connection ({:?}): {}", code, message),
+            Disconnect::Lost { code, message } => format!("Lost the game stream ({:?}): {}", code, message),
+        };
+        Some(message)
+    }
+
+    /// 입력을 더 받지 않게 되었을 때의 마지막 안내
+    pub fn closing_message(&self) -> &'static str {
+        match self {
+            Disconnect::Finished => "Game is over. No more moves accepted.",
+            Disconnect::ServerClosed | Disconnect::Ended { .. } => "The server ended the game session. No more moves accepted.",
+            Disconnect::Refused { .. } => "The server refused the connection. No more moves accepted.",
+            Disconnect::Lost { .. } => "Connection to the server was lost. No more moves accepted.",
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! OpenTelemetry 분산 추적 (`--otlp-endpoint`)
+//!
+//! 서버에 보내는 모든 요청에 [`TraceContextInterceptor`]가 현재 tracing 스팬의 W3C `traceparent`/`tracestate`를
+//! 메타데이터로 실어, 서버의 RPC 스팬이 클라이언트 스팬의 자식으로 같은 추적에 이어집니다. 스팬은
+//! `--otlp-endpoint`를 줄 때만 OTLP(gRPC)로 내보내며, 주지 않으면 빈 문맥이라 아무 헤더도 싣지 않습니다.
+
+use opentelemetry::propagation::Injector;
+use opentelemetry::trace::{TraceError, TracerProvider as _};
+use opentelemetry::{global, KeyValue};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{Tracer, TracerProvider};
+use opentelemetry_sdk::{runtime, Resource};
+use tonic::metadata::{MetadataKey, MetadataMap, MetadataValue};
+use tonic::service::interceptor::InterceptedService;
+use tonic::service::Interceptor;
+use tonic::transport::Channel;
+use tonic::{Request, Status};
+use tracing::Subscriber;
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::compression;
+use crate::tictactoe::tic_tac_toe_client::TicTacToeClient;
+
+/// 내보내는 스팬의 service.name
+pub const SERVICE_NAME: &str = "tictactoe-client";
+
+/// 요청마다 추적 문맥을 싣는 클라이언트
+pub type TracedClient = TicTacToeClient<InterceptedService<Channel, TraceContextInterceptor>>;
+
+/// 접속한 채널로 요청마다 추적 문맥을 싣는 클라이언트를 만듦 (요청 압축은 [`compression::install`]로 정한 방식)
+pub fn traced(channel: Channel) -> TracedClient {
+    compression::client(TicTacToeClient::with_interceptor(channel, TraceContextInterceptor), compression::installed())
+}
+
+/// W3C Trace Context 전파기를 전역으로 등록
+pub fn install_propagator() {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+}
+
+/// `endpoint`(예: `http://localhost:4317`)의 OTLP 수집기로 스팬을 모아 보내는 트레이서 제공자를 만들어
+/// 전역으로 등록하고 전파기도 등록합니다. 종료할 때 `shutdown`으로 남은 스팬을 내보내야 합니다.
+pub fn init(endpoint: &str) -> Result<TracerProvider, TraceError> {
+    let exporter = SpanExporter::builder().with_tonic().with_endpoint(endpoint).build()?;
+    let provider = TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_resource(Resource::new([KeyValue::new("service.name", SERVICE_NAME)]))
+        .build();
+    global::set_tracer_provider(provider.clone());
+    install_propagator();
+    Ok(provider)
+}
+
+/// tracing 스팬을 `provider`의 스팬으로 내보내는 레이어
+pub fn layer<S>(provider: &TracerProvider) -> OpenTelemetryLayer<S, Tracer>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    tracing_opentelemetry::layer().with_tracer(provider.tracer(SERVICE_NAME))
+}
+
+/// 요청 메타데이터에 추적 문맥을 쓰는 어댑터 (메타데이터에 넣을 수 없는 키나 값은 건너뜀)
+struct MetadataInjector<'a>(&'a mut MetadataMap);
+
+impl Injector for MetadataInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(key), Ok(value)) = (MetadataKey::from_bytes(key.as_bytes()), MetadataValue::try_from(value)) {
+            self.0.insert(key, value);
+        }
+    }
+}
+
+/// 현재 tracing 스팬의 문맥을 요청 메타데이터(`traceparent`/`tracestate`)에 싣는 인터셉터
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TraceContextInterceptor;
+
+impl Interceptor for TraceContextInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let context = tracing::Span::current().context();
+        global::get_text_map_propagator(|propagator| propagator.inject_context(&context, &mut MetadataInjector(request.metadata_mut())));
+        Ok(request)
+    }
+}
@@ -0,0 +1,643 @@
+//! 게임 화면 (ratatui + crossterm)
+//!
+//! 서버 업데이트와 클라이언트 알림은 [`UiEvent`]로 그리기 태스크에 전달되어 [`ViewState`]에 쌓이고,
+//! 그리기 태스크가 격자 보드, 상태 줄, 기보, 메시지, 입력 칸을 그립니다.
+//! 칸 번호를 입력하거나 방향키/hjkl로 커서를 옮긴 뒤 Enter로 둡니다.
+
+use std::collections::VecDeque;
+use std::io;
+use std::time::Instant;
+
+use common::text;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::countdown::{self, Countdown};
+use crate::tictactoe::{BotDifficulty, GameMode, GameState};
+use crate::ultimate::{self, SubBoards};
+
+/// 메시지 영역에 남겨 두는 최대 줄 수
+pub const MAX_MESSAGES: usize = 50;
+/// board_size를 보내지 않는 서버의 보드 크기
+const DEFAULT_BOARD_SIZE: usize = 3;
+
+/// 그리기 태스크로 보내는 이벤트
+#[derive(Debug, Clone)]
+pub enum UiEvent {
+    /// 서버가 보낸 게임 상태
+    Update(Box<GameState>),
+    /// 메시지 영역에 표시할 알림
+    Message(String),
+    /// 게임 스트림이 끝남 (더 이상 업데이트가 오지 않음)
+    Closed,
+}
+
+/// 커서 이동 방향
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// 키 입력을 해석한 명령
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Cursor(Direction),
+    /// 입력 칸에 숫자 추가 (얼티밋의 "작은 보드,칸" 입력을 위한 ','도 포함)
+    Digit(char),
+    /// 입력 칸의 마지막 숫자 지우기
+    Backspace,
+    /// 입력한 칸(없으면 커서 칸)에 두기 (Enter, 스페이스)
+    Play,
+    OfferDraw,
+    AcceptDraw,
+    DeclineDraw,
+    Resign,
+    /// 몇 판 승부에서 다음 판 요청 (r)
+    Rematch,
+    /// 채팅 입력 시작 ('/' 또는 t)
+    StartChat,
+    /// 와일드에서 놓을 심볼 고르기 (x, o)
+    ChooseMark(&'static str),
+    /// 스왑 규칙으로 상대의 첫 수를 가져감 (s)
+    Swap,
+    /// 게임 화면 나가기 (q, Esc, Ctrl-C)
+    Quit,
+}
+
+/// 키 입력을 명령으로 (누를 때만 처리하고 떼는 이벤트는 무시)
+pub fn command_for(key: KeyEvent) -> Option<Command> {
+    if key.kind == KeyEventKind::Release {
+        return None;
+    }
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        return (key.code == KeyCode::Char('c')).then_some(Command::Quit);
+    }
+    let command = match key.code {
+        KeyCode::Char(c) if c.is_ascii_digit() || c == ',' => Command::Digit(c),
+        KeyCode::Backspace => Command::Backspace,
+        KeyCode::Up | KeyCode::Char('k') => Command::Cursor(Direction::Up),
+        KeyCode::Down | KeyCode::Char('j') => Command::Cursor(Direction::Down),
+        KeyCode::Left | KeyCode::Char('h') => Command::Cursor(Direction::Left),
+        KeyCode::Right | KeyCode::Char('l') => Command::Cursor(Direction::Right),
+        KeyCode::Enter | KeyCode::Char(' ') => Command::Play,
+        KeyCode::Char('d') => Command::OfferDraw,
+        KeyCode::Char('a') => Command::AcceptDraw,
+        KeyCode::Char('n') => Command::DeclineDraw,
+        KeyCode::Char('R') => Command::Resign,
+        KeyCode::Char('r') => Command::Rematch,
+        KeyCode::Char('/') | KeyCode::Char('t') => Command::StartChat,
+        KeyCode::Char('x') => Command::ChooseMark("X"),
+        KeyCode::Char('o') => Command::ChooseMark("O"),
+        KeyCode::Char('s') => Command::Swap,
+        KeyCode::Char('q') | KeyCode::Esc => Command::Quit,
+        _ => return None,
+    };
+    Some(command)
+}
+
+/// 화면에 그릴 게임 상태 (서버 업데이트와 클라이언트 알림으로 갱신)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViewState {
+    pub game_id: String,
+    pub board: Vec<String>,
+    pub board_size: usize,
+    /// 커서가 있는 칸 번호
+    pub cursor: usize,
+    pub status: String,
+    pub next_player: String,
+    pub your_symbol: String,
+    /// 보드에서 X, O 자리를 표시할 글자 (플레이어가 고른 그래핌, 기본 "X"/"O")
+    pub x_symbol: String,
+    pub o_symbol: String,
+    /// X, O 자리 플레이어의 표시 이름 (서버가 보내지 않았으면 빈 문자열)
+    pub x_name: String,
+    pub o_name: String,
+    pub draw_offer_pending: bool,
+    /// 스왑 규칙으로 상대의 첫 수를 가져갈 수 있는지 (GameState.swap_available)
+    pub swap_available: bool,
+    /// 상대 자리에 연결된 플레이어가 있는지 (GameState.opponent_connected)
+    pub opponent_connected: bool,
+    /// 차례 제한 시간 카운트다운을 보일지 (--no-timer면 false)
+    pub timer: bool,
+    /// 내 차례의 남은 시간 (제한이 없거나 내 차례가 아니면 None)
+    pub countdown: Option<Countdown>,
+    /// 시간패로 끝난 판의 결과 문구 (GameState.lost_on_time)
+    pub lost_on_time: Option<String>,
+    /// 서버 봇과 두는 게임이면 봇의 난이도 ("easy", "medium", "hard")
+    pub bot_difficulty: Option<&'static str>,
+    /// 관전 모드 여부 (true면 내 심볼과 차례를 표시하지 않음)
+    pub spectating: bool,
+    /// 얼티밋 게임의 작은 보드 규칙 (클래식이면 None)
+    pub sub_boards: Option<SubBoards>,
+    /// 와일드 게임인지 (차례마다 놓을 심볼을 고름)
+    pub wild: bool,
+    /// 와일드에서 고른 심볼 (비어 있으면 자기 심볼)
+    pub mark: String,
+    /// 둔 순서대로의 (심볼, 칸 번호)
+    pub history: Vec<(String, usize)>,
+    /// 입력 칸에 입력 중인 칸 번호
+    pub input: String,
+    /// 입력 중인 채팅 메시지 (Some이면 키 입력이 모두 채팅 입력 칸으로 감)
+    pub chat_draft: Option<String>,
+    /// 게임 스트림이 끝났는지
+    pub closed: bool,
+    /// 오래된 것부터 쌓인 메시지 (`MAX_MESSAGES`줄까지)
+    pub messages: VecDeque<String>,
+}
+
+impl ViewState {
+    pub fn new(spectating: bool) -> Self {
+        ViewState {
+            game_id: String::new(),
+            board: vec![String::new(); DEFAULT_BOARD_SIZE * DEFAULT_BOARD_SIZE],
+            board_size: DEFAULT_BOARD_SIZE,
+            cursor: DEFAULT_BOARD_SIZE * DEFAULT_BOARD_SIZE / 2,
+            status: String::new(),
+            next_player: String::new(),
+            your_symbol: String::new(),
+            x_symbol: "X".into(),
+            o_symbol: "O".into(),
+            x_name: String::new(),
+            o_name: String::new(),
+            draw_offer_pending: false,
+            swap_available: false,
+            opponent_connected: false,
+            timer: true,
+            countdown: None,
+            lost_on_time: None,
+            bot_difficulty: None,
+            spectating,
+            sub_boards: None,
+            wild: false,
+            mark: String::new(),
+            history: Vec::new(),
+            input: String::new(),
+            chat_draft: None,
+            closed: false,
+            messages: VecDeque::new(),
+        }
+    }
+
+    /// 이벤트 하나 반영
+    pub fn handle(&mut self, event: UiEvent) {
+        match event {
+            UiEvent::Update(state) => self.apply(&state),
+            UiEvent::Message(message) => self.push_message(message),
+            UiEvent::Closed => {
+                self.closed = true;
+                self.push_message("The game session has ended. Press q to leave.");
+            }
+        }
+    }
+
+    /// 서버가 보낸 GameState 반영 (오류와 안내 메시지는 메시지 영역에 추가)
+    ///
+    /// 서버는 끝난 게임에만 기보를 보내므로, 진행 중에는 보드에서 새로 채워진 칸으로 기보를 이어 붙입니다.
+    pub fn apply(&mut self, state: &GameState) {
+        let size = if state.board_size > 0 { state.board_size as usize } else { DEFAULT_BOARD_SIZE };
+        if size != self.board_size {
+            // 크기가 바뀌면 커서를 새 보드의 가운데로
+            self.board_size = size;
+            self.cursor = size * size / 2;
+            self.history.clear();
+        } else if state.board.iter().all(String::is_empty) {
+            self.history.clear();
+        } else {
+            for (position, (old, new)) in self.board.iter().zip(&state.board).enumerate() {
+                if old.is_empty() && !new.is_empty() {
+                    self.history.push((new.clone(), position));
+                } else if !old.is_empty() && !new.is_empty() && old != new {
+                    // 스왑으로 첫 수의 돌이 바뀜
+                    if let Some(entry) = self.history.iter_mut().find(|(_, p)| *p == position) {
+                        entry.0 = new.clone();
+                    }
+                }
+            }
+        }
+        if !state.history.is_empty() {
+            self.history = state.history.iter().map(|m| (m.player_id.clone(), m.position as usize)).collect();
+        }
+        self.game_id = state.game_id.clone();
+        self.board = state.board.clone();
+        self.status = state.status.clone();
+        self.next_player = state.next_player.clone();
+        self.draw_offer_pending = state.draw_offer_pending;
+        self.swap_available = state.swap_available;
+        self.opponent_connected = state.opponent_connected;
+        if self.timer {
+            self.countdown = Countdown::update(self.countdown.as_ref(), state, self.spectating, Instant::now());
+        }
+        self.lost_on_time = countdown::lost_on_time_line(state, self.spectating);
+        self.x_symbol = marker_or(&state.x_symbol, "X").to_string();
+        self.o_symbol = marker_or(&state.o_symbol, "O").to_string();
+        self.x_name = state.player_x_name.clone();
+        self.o_name = state.player_o_name.clone();
+        self.bot_difficulty = difficulty_label(state.bot_difficulty);
+        self.sub_boards = SubBoards::of(state);
+        self.wild = state.mode == GameMode::Wild as i32;
+        if !self.spectating {
+            self.your_symbol = state.your_symbol.clone();
+        }
+        if !state.error_message.is_empty() {
+            self.push_message(format!("Error: {}", state.error_message));
+        }
+        if !state.info_message.is_empty() {
+            self.push_message(state.info_message.clone());
+        }
+    }
+
+    /// 메시지 영역에 한 줄 추가 (가득 차면 가장 오래된 줄을 버림)
+    pub fn push_message(&mut self, message: impl Into<String>) {
+        if self.messages.len() == MAX_MESSAGES {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(message.into());
+    }
+
+    /// 커서를 한 칸 옮김 (보드 가장자리에서는 멈춤)
+    pub fn move_cursor(&mut self, direction: Direction) {
+        let size = self.board_size.max(1);
+        let (row, col) = (self.cursor / size, self.cursor % size);
+        let (row, col) = match direction {
+            Direction::Up => (row.saturating_sub(1), col),
+            Direction::Down => ((row + 1).min(size - 1), col),
+            Direction::Left => (row, col.saturating_sub(1)),
+            Direction::Right => (row, (col + 1).min(size - 1)),
+        };
+        self.cursor = row * size + col;
+    }
+
+    /// 입력 칸에 숫자 추가 (가장 큰 칸 번호의 자릿수까지만, 얼티밋이면 "8,8"처럼 세 글자까지)
+    pub fn push_digit(&mut self, digit: char) {
+        let max_len = match self.sub_boards {
+            Some(_) => 3,
+            None if digit == ',' => return,
+            None => (self.board_size * self.board_size).saturating_sub(1).to_string().len(),
+        };
+        if self.input.len() < max_len {
+            self.input.push(digit);
+        }
+    }
+
+    /// 둘 칸: 입력한 번호가 있으면 그 칸, 없으면 커서 칸. 입력 칸은 비워짐 (보드 밖 번호면 Err로 그 번호)
+    pub fn take_target(&mut self) -> Result<usize, String> {
+        let input = std::mem::take(&mut self.input);
+        if input.is_empty() {
+            return Ok(self.cursor);
+        }
+        let position = match self.sub_boards {
+            Some(_) => ultimate::parse_position(&input),
+            None => input.parse::<usize>().ok(),
+        };
+        match position {
+            Some(position) if position < self.board.len() => {
+                self.cursor = position;
+                Ok(position)
+            }
+            _ => Err(input),
+        }
+    }
+
+    /// 와일드에서 이번 수로 놓을 심볼 (고르지 않았으면 자기 심볼, 와일드가 아니면 빈 문자열)
+    pub fn chosen_mark(&self) -> String {
+        match (self.wild, self.mark.is_empty()) {
+            (false, _) => String::new(),
+            (true, true) => self.your_symbol.clone(),
+            (true, false) => self.mark.clone(),
+        }
+    }
+
+    /// 채팅 입력 중의 키 처리: Enter면 입력한 메시지를 돌려주고, Esc면 취소
+    pub fn edit_chat(&mut self, key: KeyEvent) -> Option<String> {
+        let draft = self.chat_draft.as_mut()?;
+        if key.kind == KeyEventKind::Release {
+            return None;
+        }
+        match key.code {
+            KeyCode::Enter => {
+                let text = self.chat_draft.take().unwrap_or_default();
+                let text = text.trim();
+                return (!text.is_empty()).then(|| text.to_string());
+            }
+            KeyCode::Esc => self.chat_draft = None,
+            KeyCode::Backspace => {
+                draft.pop();
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => draft.push(c),
+            _ => {}
+        }
+        None
+    }
+
+    /// 지금 내가 둘 차례인지
+    pub fn is_my_turn(&self) -> bool {
+        !self.spectating && self.status == "ongoing" && !self.your_symbol.is_empty() && self.next_player == self.your_symbol
+    }
+
+    /// 자리를 부르는 이름 ("Alice (X)", 이름을 모르면 심볼)
+    pub fn seat_label(&self, symbol: &str) -> String {
+        let name = match symbol {
+            "X" => &self.x_name,
+            "O" => &self.o_name,
+            _ => return symbol.to_string(),
+        };
+        seat_label(name, symbol)
+    }
+
+    /// 상단 상태 줄 (게임 ID, 상태, 차례, 내 심볼)
+    pub fn status_line(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.game_id.is_empty() {
+            parts.push(format!("Game {}", self.game_id));
+        }
+        parts.push(match self.status.as_str() {
+            "" => "Connecting...".to_string(),
+            "searching" => "Searching for an opponent...".to_string(),
+            "waiting" => "Waiting for opponent...".to_string(),
+            "ongoing" if self.is_my_turn() => match &self.countdown {
+                Some(countdown) => countdown.line(Instant::now()),
+                None => "Your turn".to_string(),
+            },
+            "ongoing" => format!("{} to move", self.seat_label(&self.next_player)),
+            "admin_terminated" => "Game over: ended by a server administrator".to_string(),
+            "server_shutdown" => "Game over: the server shut down".to_string(),
+            "idle_timeout" => "Game over: a player stopped responding".to_string(),
+            "abandoned" => "Game over: abandoned after a long time without moves".to_string(),
+            "join_timeout" => "Removed from the game: nothing was sent after joining".to_string(),
+            "draw_agreed" => "Game over: draw by agreement".to_string(),
+            status => match (&self.lost_on_time, status.strip_suffix("_win_by_resignation")) {
+                (Some(line), _) => format!("Game over: {}", line),
+                (None, Some(winner)) => format!("Game over: {} wins by resignation", winner),
+                (None, None) => format!("Game over: {}", status),
+            },
+        });
+        if self.spectating {
+            parts.push("Spectating".to_string());
+        } else if !self.your_symbol.is_empty() {
+            parts.push(format!("You: {}", self.seat_label(&self.your_symbol)));
+        }
+        if let Some(sub_boards) = self.sub_boards.as_ref().filter(|_| self.status == "ongoing") {
+            parts.push(match sub_boards.forced {
+                Some(forced) => format!("Sub-board {}", forced),
+                None => "Any sub-board".to_string(),
+            });
+        }
+        if self.wild && !self.spectating && self.status == "ongoing" {
+            parts.push(format!("Placing {}", self.chosen_mark()));
+        }
+        if let Some(level) = self.bot_difficulty {
+            parts.push(format!("vs {} bot", level));
+        }
+        if self.draw_offer_pending {
+            parts.push("Draw offered".to_string());
+        }
+        if self.swap_available && self.is_my_turn() {
+            parts.push("s: swap".to_string());
+        }
+        if !self.spectating && self.status == "ongoing" && !self.opponent_connected {
+            parts.push("Opponent disconnected".to_string());
+        }
+        parts.join("  |  ")
+    }
+
+    /// 기보 창의 줄 ("1. X→4")
+    pub fn history_lines(&self) -> Vec<String> {
+        self.history
+            .iter()
+            .enumerate()
+            .map(|(i, (symbol, position))| format!("{}. {}\u{2192}{}", i + 1, symbol, position))
+            .collect()
+    }
+}
+
+/// 화면 하단의 키 안내
+fn key_help(view: &ViewState) -> &'static str {
+    if view.chat_draft.is_some() {
+        "Enter: send  Esc: cancel"
+    } else if view.spectating || view.closed {
+        "q: leave"
+    } else if view.status != "ongoing" && !view.status.is_empty() && !matches!(view.status.as_str(), "waiting" | "searching") {
+        "r: next game of the match  /: chat  q: leave"
+    } else if view.wild {
+        "0-9: cell  arrows/hjkl: move  x/o: symbol to place  Enter: play  /: chat  d: offer draw  a/n: accept/decline  R: resign  q: leave"
+    } else {
+        "0-9: cell  arrows/hjkl: move  Enter: play  /: chat  d: offer draw  a/n: accept/decline  R: resign  q: leave"
+    }
+}
+
+/// 격자 보드 (커서 칸은 반전해서 표시)
+///
+/// 얼티밋이면 작은 보드 사이를 '#'과 '='로 나누고, 이번 차례에 둘 수 있는 작은 보드의 칸에 밑줄을 긋습니다.
+fn board_lines(view: &ViewState) -> Vec<Line<'static>> {
+    let size = view.board_size.max(1);
+    let board = with_markers(&view.board, &view.x_symbol, &view.o_symbol);
+    let cell_width = board.iter().map(|cell| text::display_width(cell)).max().unwrap_or(0).max(1);
+    let sub_board_edge = |index: usize| view.sub_boards.is_some() && index % 3 == 2 && index + 1 < size;
+    let separator = |heavy: bool| {
+        let (line, corner) = if heavy { ("=", "#") } else { ("-", "+") };
+        let cells: String = (0..size)
+            .map(|col| format!("{}{}", line.repeat(cell_width + 2), if sub_board_edge(col) { "#" } else { corner }))
+            .collect();
+        format!("{}{}", corner, cells)
+    };
+    let mut lines = vec![Line::from(separator(false))];
+    for (row_index, row) in board.chunks(size).enumerate() {
+        let mut spans = vec![Span::raw("|")];
+        for (col, cell) in row.iter().enumerate() {
+            let position = row_index * size + col;
+            let content = format!(" {} ", text::center_to_width(cell, cell_width));
+            let active = view.sub_boards.as_ref().is_some_and(|s| view.status == "ongoing" && s.is_active(ultimate::sub_board_of(position)));
+            if position == view.cursor {
+                spans.push(Span::styled(content, Style::default().add_modifier(Modifier::REVERSED)));
+            } else if active {
+                spans.push(Span::styled(content, Style::default().add_modifier(Modifier::UNDERLINED)));
+            } else {
+                spans.push(Span::raw(content));
+            }
+            spans.push(Span::raw(if sub_board_edge(col) { "#" } else { "|" }));
+        }
+        lines.push(Line::from(spans));
+        lines.push(Line::from(separator(sub_board_edge(row_index))));
+    }
+    lines
+}
+
+/// 상대의 접속 상태 (GameState.opponent_connected를 받은 순서대로 반영해 정함)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Opponent {
+    /// 아직 상대가 한 번도 앉지 않음
+    #[default]
+    NotJoined,
+    Connected,
+    /// 앉았던 상대의 접속이 끊김
+    Disconnected,
+}
+
+impl Opponent {
+    /// 업데이트 하나를 반영한 상태와, 바뀌었다면 알릴 문구 (관전자에게는 상대가 없으므로 받지 않음)
+    pub fn next(self, state: &GameState) -> (Opponent, Option<&'static str>) {
+        match (self, state.opponent_connected) {
+            (Opponent::NotJoined, true) => (Opponent::Connected, Some("Opponent joined.")),
+            (Opponent::Disconnected, true) => (Opponent::Connected, Some("Opponent reconnected.")),
+            (Opponent::Connected, false) => (Opponent::Disconnected, Some("Opponent disconnected. Waiting for them to reconnect...")),
+            (current, _) => (current, None),
+        }
+    }
+}
+
+/// 끝난 판의 점수 줄 ("Score — You 2 : 1 Opponent, 1 draw", 관전자는 "Score — X 2 : 1 O, ...").
+/// 점수를 보내지 않는 서버이거나 센 판이 없으면 None
+pub fn scoreboard_line(state: &GameState, spectating: bool) -> Option<String> {
+    if state.games_played == 0 {
+        return None;
+    }
+    let draws = if state.draws == 1 { "1 draw".to_string() } else { format!("{} draws", state.draws) };
+    let line = if spectating || state.your_symbol.is_empty() {
+        format!("X {} : {} O", state.score_x, state.score_o)
+    } else if state.your_symbol == "X" {
+        format!("You {} : {} Opponent", state.score_x, state.score_o)
+    } else {
+        format!("You {} : {} Opponent", state.score_o, state.score_x)
+    };
+    Some(format!("Score \u{2014} {}, {}", line, draws))
+}
+
+/// 몇 판 승부가 끝났을 때의 알림 (승부가 나지 않았으면 None)
+pub fn match_result_line(state: &GameState, spectating: bool) -> Option<String> {
+    let winner = state.match_winner.as_str();
+    if winner.is_empty() {
+        None
+    } else if spectating || state.your_symbol.is_empty() {
+        Some(format!("{} wins the best-of-{} match.", winner, state.best_of))
+    } else if winner == state.your_symbol {
+        Some(format!("You won the best-of-{} match!", state.best_of))
+    } else {
+        Some(format!("Your opponent won the best-of-{} match.", state.best_of))
+    }
+}
+
+/// 몇 판 승부에서 승부가 나지 않은 채 판이 끝나 다음 판을 할 수 있는 상태인지
+/// (서버는 이때 스트림을 닫지 않음)
+pub fn awaits_rematch(state: &GameState) -> bool {
+    state.best_of > 0 && state.match_winner.is_empty() && (state.status.contains("_win") || state.status.starts_with("draw"))
+}
+
+/// 스왑 규칙으로 상대의 첫 수를 가져갈 수 있을 때 보여 줄 안내 (내 차례가 아니면 None)
+pub fn swap_hint(state: &GameState) -> Option<String> {
+    let mine = state.swap_available && !state.your_symbol.is_empty() && state.next_player == state.your_symbol;
+    let first = if state.your_symbol == "X" { "O" } else { "X" };
+    mine.then(|| format!("Type 'swap' to take over {}'s move, or reply with a move as usual.", first))
+}
+
+/// 보드 칸의 "X"/"O"를 각 자리의 표시 글자로 바꾼 칸 목록 (줄 단위 출력에서도 사용)
+pub fn marker_cells(state: &GameState) -> Vec<String> {
+    with_markers(&state.board, marker_or(&state.x_symbol, "X"), marker_or(&state.o_symbol, "O"))
+}
+
+fn with_markers(board: &[String], x_symbol: &str, o_symbol: &str) -> Vec<String> {
+    board
+        .iter()
+        .map(|cell| match cell.as_str() {
+            "X" => x_symbol.to_string(),
+            "O" => o_symbol.to_string(),
+            other => other.to_string(),
+        })
+        .collect()
+}
+
+/// 표시 글자를 보내지 않는 서버면 자리 심볼을 그대로 씀
+fn marker_or<'a>(marker: &'a str, symbol: &'a str) -> &'a str {
+    if marker.is_empty() { symbol } else { marker }
+}
+
+/// 이름과 심볼로 자리를 부름 ("Alice (X)", 이름이 비어 있으면 심볼만)
+pub fn seat_label(name: &str, symbol: &str) -> String {
+    if name.is_empty() { symbol.to_string() } else { format!("{} ({})", name, symbol) }
+}
+
+/// GameState.bot_difficulty 표시 이름 (서버 봇과 두는 게임이 아니면 None)
+pub fn difficulty_label(difficulty: i32) -> Option<&'static str> {
+    match BotDifficulty::try_from(difficulty).unwrap_or_default() {
+        BotDifficulty::Unspecified => None,
+        BotDifficulty::Easy => Some("easy"),
+        BotDifficulty::Medium => Some("medium"),
+        BotDifficulty::Hard => Some("hard"),
+    }
+}
+
+/// 최근 줄이 보이도록 테두리 안 높이만큼만 남김
+fn tail(lines: impl ExactSizeIterator<Item = String>, height: u16) -> Vec<Line<'static>> {
+    let skip = lines.len().saturating_sub(height.saturating_sub(2) as usize);
+    lines.skip(skip).map(Line::from).collect()
+}
+
+/// 한 프레임 그리기: 상태 줄, 보드와 기보, 메시지, 입력 칸, 키 안내
+pub fn draw(frame: &mut Frame, view: &ViewState) {
+    let board = board_lines(view);
+    let [status_area, middle_area, messages_area, input_area, help_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(board.len() as u16 + 1),
+        Constraint::Min(3),
+        Constraint::Length(3),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let board_width = board.first().map_or(0, |line| line.width() as u16) + 2;
+    let [board_area, history_area] = Layout::horizontal([Constraint::Length(board_width), Constraint::Min(12)]).areas(middle_area);
+
+    frame.render_widget(
+        Paragraph::new(view.status_line()).style(Style::default().add_modifier(Modifier::BOLD)),
+        status_area,
+    );
+    frame.render_widget(Paragraph::new(board), board_area);
+    frame.render_widget(
+        Paragraph::new(tail(view.history_lines().into_iter(), history_area.height))
+            .block(Block::default().borders(Borders::ALL).title("Moves")),
+        history_area,
+    );
+    frame.render_widget(
+        Paragraph::new(tail(view.messages.iter().cloned(), messages_area.height))
+            .block(Block::default().borders(Borders::ALL).title("Messages")),
+        messages_area,
+    );
+    let (title, input) = match &view.chat_draft {
+        Some(draft) => ("Chat", draft.as_str()),
+        None => ("Cell", view.input.as_str()),
+    };
+    frame.render_widget(
+        Paragraph::new(format!("> {}", input)).block(Block::default().borders(Borders::ALL).title(title)),
+        input_area,
+    );
+    frame.render_widget(Paragraph::new(key_help(view)), help_area);
+}
+
+/// 게임 화면을 여는 동안 터미널을 잡고 있다가 drop되면 원래대로 되돌림
+///
+/// `ratatui::try_init`이 raw 모드와 대체 화면을 켜고 패닉 훅도 설치하므로,
+/// 패닉이 나도 메시지를 출력하기 전에 터미널이 복구됩니다.
+pub struct TerminalGuard {
+    terminal: DefaultTerminal,
+}
+
+impl TerminalGuard {
+    pub fn enter() -> io::Result<Self> {
+        Ok(TerminalGuard { terminal: ratatui::try_init()? })
+    }
+
+    pub fn draw(&mut self, view: &ViewState) -> io::Result<()> {
+        self.terminal.draw(|frame| draw(frame, view)).map(|_| ())
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        ratatui::restore();
+    }
+}
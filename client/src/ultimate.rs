@@ -0,0 +1,116 @@
+//! 얼티밋 틱택토 보드 (GameState.mode가 ULTIMATE인 게임)
+//!
+//! 서버는 9×9 보드를 행 우선 순서의 81칸으로 보내고, 작은 보드 `b`의 칸 `c`는
+//! (행 (b/3)*3 + c/3, 열 (b%3)*3 + c%3) 칸입니다. 칸은 0-80 번호나 `b,c`로 입력합니다.
+
+use crate::moves::MoveRejection;
+use crate::tictactoe::{GameMode, GameState};
+
+/// 얼티밋 보드 한 변의 칸 수
+pub const BOARD_SIZE: usize = 9;
+
+/// 작은 보드 `sub_board`의 칸 `cell`에 해당하는 보드 칸 번호
+pub fn position(sub_board: usize, cell: usize) -> usize {
+    (sub_board / 3 * 3 + cell / 3) * BOARD_SIZE + sub_board % 3 * 3 + cell % 3
+}
+
+/// 보드 칸 번호가 속한 작은 보드
+pub fn sub_board_of(position: usize) -> usize {
+    position / BOARD_SIZE / 3 * 3 + position % BOARD_SIZE / 3
+}
+
+/// 입력한 칸: "b,c" (작은 보드와 그 안의 칸, 각각 0-8) 또는 0-80 (보드 밖이면 None)
+pub fn parse_position(input: &str) -> Option<usize> {
+    let position = match input.split_once(',') {
+        Some((sub_board, cell)) => {
+            let sub_board = sub_board.trim().parse::<usize>().ok().filter(|&b| b < 9)?;
+            let cell = cell.trim().parse::<usize>().ok().filter(|&c| c < 9)?;
+            position(sub_board, cell)
+        }
+        None => input.trim().parse::<usize>().ok()?,
+    };
+    (position < BOARD_SIZE * BOARD_SIZE).then_some(position)
+}
+
+/// 작은 보드 규칙 (어디에 둬야 하는지와 승부가 난 작은 보드)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubBoards {
+    /// 다음 수를 둬야 하는 작은 보드 (None이면 승부가 나지 않은 아무 작은 보드)
+    pub forced: Option<usize>,
+    /// 작은 보드마다 "" (진행 중), "X", "O", "draw"
+    pub results: Vec<String>,
+}
+
+impl SubBoards {
+    /// 얼티밋 게임의 상태면 Some
+    pub fn of(state: &GameState) -> Option<Self> {
+        (state.mode == GameMode::Ultimate as i32).then(|| SubBoards {
+            forced: state.forced_board.and_then(|b| usize::try_from(b).ok()),
+            results: state.macro_board.clone(),
+        })
+    }
+
+    /// 작은 보드가 이미 승부가 났는지
+    pub fn is_decided(&self, sub_board: usize) -> bool {
+        self.results.get(sub_board).is_some_and(|result| !result.is_empty())
+    }
+
+    /// 이번 차례에 `sub_board`에 둘 수 있는지 (강조해서 표시할 작은 보드)
+    pub fn is_active(&self, sub_board: usize) -> bool {
+        !self.is_decided(sub_board) && self.forced.is_none_or(|forced| forced == sub_board)
+    }
+
+    /// 보내기 전에 작은 보드 규칙으로 미리 검사 (칸과 차례 검사는 `moves::check_move`)
+    pub fn check(&self, position: usize) -> Result<(), MoveRejection> {
+        let sub_board = sub_board_of(position);
+        if self.is_decided(sub_board) {
+            return Err(MoveRejection::SubBoardDecided(sub_board));
+        }
+        match self.forced {
+            Some(forced) if forced != sub_board => Err(MoveRejection::WrongSubBoard(forced)),
+            _ => Ok(()),
+        }
+    }
+
+    /// 어디에 둬야 하는지 안내 ("Play in sub-board 4 (4,0-4,8).")
+    pub fn prompt(&self) -> String {
+        match self.forced {
+            Some(forced) => format!("Play in sub-board {} (enter {},0-{},8 or a cell number 0-80).", forced, forced, forced),
+            None => "Play in any open sub-board (enter board,cell or a cell number 0-80).".to_string(),
+        }
+    }
+}
+
+/// 줄 단위 출력용 9×9 보드: 작은 보드 사이는 굵은 선으로 나누고, 둘 수 있는 작은 보드의 빈 칸은 '.',
+/// 그 밖의 빈 칸은 공백으로 표시합니다. 작은 보드를 가져갔거나 비겼으면 그 줄 오른쪽에 결과를 붙입니다.
+pub fn board_lines(cells: &[String], sub_boards: &SubBoards) -> Vec<String> {
+    let separator = "  ===========#===========#===========".to_string();
+    let mut lines = vec![separator.clone()];
+    for row in 0..BOARD_SIZE {
+        let mut line = String::from("  ");
+        for col in 0..BOARD_SIZE {
+            let position = row * BOARD_SIZE + col;
+            let cell = cells.get(position).map(String::as_str).unwrap_or("");
+            let shown = match cell {
+                "" if sub_boards.is_active(sub_board_of(position)) => ".",
+                "" => " ",
+                cell => cell,
+            };
+            line.push_str(&format!(" {} ", shown));
+            line.push(if col == BOARD_SIZE - 1 { ' ' } else if col % 3 == 2 { '#' } else { '|' });
+        }
+        if row % 3 == 1 {
+            let results: Vec<String> = (row / 3 * 3..row / 3 * 3 + 3)
+                .map(|b| match sub_boards.results.get(b).map(String::as_str) {
+                    Some("draw") => "-".to_string(),
+                    Some(r) if !r.is_empty() => r.to_string(),
+                    _ => "\u{b7}".to_string(),
+                })
+                .collect();
+            line.push_str(&format!("  {}", results.join(" ")));
+        }
+        lines.push(line.trim_end().to_string());
+        lines.push(if row % 3 == 2 { separator.clone() } else { "  -----------#-----------#-----------".to_string() });
+    }
+    lines
+}
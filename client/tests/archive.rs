@@ -0,0 +1,160 @@
+use std::path::PathBuf;
+
+use client::archive::{Archive, ArchiveEntry, GameRecorder, SearchFilter};
+use client::tictactoe::GameState;
+
+/// 테스트마다 겹치지 않는 임시 아카이브 경로
+fn temp_archive(name: &str) -> (Archive, PathBuf) {
+    let dir = std::env::temp_dir().join(format!("tictactoe-archive-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    (Archive::open(dir.join("archive.jsonl")), dir)
+}
+
+fn entry(opponent: &str, result: &str, moves: &[u8]) -> ArchiveEntry {
+    ArchiveEntry {
+        id: 0,
+        date: "2026-01-01T00:00:00Z".into(),
+        game_id: "1".into(),
+        symbol: "X".into(),
+        opponent: opponent.into(),
+        result: result.into(),
+        status: "X_win".into(),
+        moves: moves.to_vec(),
+        tags: Vec::new(),
+        board_size: 3,
+    }
+}
+
+fn state(board: [&str; 9], status: &str, rated: bool) -> GameState {
+    GameState {
+        board: board.iter().map(|c| c.to_string()).collect(),
+        status: status.into(),
+        rated,
+        game_id: "7".into(),
+        your_symbol: "X".into(),
+        ..GameState::default()
+    }
+}
+
+#[test]
+fn append_assigns_sequential_ids() {
+    let (archive, dir) = temp_archive("append");
+    assert_eq!(archive.append(entry("human", "win", &[4, 0, 8])).unwrap(), 1);
+    assert_eq!(archive.append(entry("house-bot", "loss", &[0, 4])).unwrap(), 2);
+
+    let index = archive.load().unwrap();
+    assert_eq!(index.entries.len(), 2);
+    assert_eq!(index.get(2).unwrap().opponent, "house-bot");
+    assert_eq!(index.get(1).unwrap().notation(), "4 0 8");
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn search_filters_combine() {
+    let (archive, dir) = temp_archive("search");
+    archive.append(entry("human", "win", &[0, 4, 8])).unwrap();
+    archive.append(entry("human", "loss", &[0, 4])).unwrap();
+    archive.append(entry("house-bot", "win", &[4, 0])).unwrap();
+    let index = archive.load().unwrap();
+
+    let ids = |filter: SearchFilter| index.search(&filter).iter().map(|e| e.id).collect::<Vec<_>>();
+    assert_eq!(ids(SearchFilter::default()), [1, 2, 3]);
+    assert_eq!(ids(SearchFilter { result: Some("win".into()), ..Default::default() }), [1, 3]);
+    assert_eq!(ids(SearchFilter { opening: Some(0), ..Default::default() }), [1, 2]);
+    assert_eq!(
+        ids(SearchFilter { opponent: Some("human".into()), result: Some("win".into()), opening: Some(0), tag: None }),
+        [1]
+    );
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn tagging_is_persisted_and_searchable() {
+    let (archive, dir) = temp_archive("tag");
+    archive.append(entry("human", "win", &[0, 4, 8])).unwrap();
+    archive.append(entry("human", "win", &[4, 0])).unwrap();
+
+    assert!(archive.tag(1, "corner-trap").unwrap());
+    assert!(archive.tag(1, "corner-trap").unwrap());
+    assert!(!archive.tag(9, "missing").unwrap());
+
+    let index = archive.load().unwrap();
+    assert_eq!(index.get(1).unwrap().tags, ["corner-trap"]);
+    let tagged = index.search(&SearchFilter { tag: Some("corner-trap".into()), ..Default::default() });
+    assert_eq!(tagged.len(), 1);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn recorded_game_replays_to_final_board() {
+    let (archive, dir) = temp_archive("replay");
+    let updates = [
+        state(["", "", "", "", "", "", "", "", ""], "ongoing", true),
+        state(["", "", "", "", "X", "", "", "", ""], "ongoing", true),
+        state(["O", "", "", "", "X", "", "", "", ""], "ongoing", true),
+        state(["O", "", "X", "", "X", "", "", "", ""], "ongoing", true),
+        state(["O", "O", "X", "", "X", "", "", "", ""], "ongoing", true),
+        state(["O", "O", "X", "", "X", "", "X", "", ""], "X_win", true),
+    ];
+    let mut recorder = GameRecorder::new();
+    updates.iter().for_each(|u| recorder.observe(u));
+    let finished = recorder.finish(updates.last().unwrap(), "X");
+    assert_eq!(finished.result, "win");
+    assert_eq!(finished.opponent, "human");
+
+    let id = archive.append(finished).unwrap();
+    let index = archive.load().unwrap();
+    let replayed = index.get(id).unwrap();
+    assert_eq!(replayed.notation(), "4 0 2 1 6");
+    assert_eq!(replayed.boards().last().unwrap(), &updates.last().unwrap().board);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn corrupt_entries_are_skipped_and_preserved() {
+    let (archive, dir) = temp_archive("corrupt");
+    archive.append(entry("human", "win", &[4])).unwrap();
+    let mut contents = std::fs::read_to_string(archive.path()).unwrap();
+    contents.push_str("{not json\n");
+    std::fs::write(archive.path(), contents).unwrap();
+    archive.append(entry("human", "draw", &[0])).unwrap();
+
+    let index = archive.load().unwrap();
+    assert_eq!(index.skipped, 1);
+    assert_eq!(index.entries.iter().map(|e| e.id).collect::<Vec<_>>(), [1, 2]);
+
+    archive.tag(2, "late").unwrap();
+    assert!(std::fs::read_to_string(archive.path()).unwrap().contains("{not json"));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn larger_boards_replay_at_their_own_size() {
+    let mut board = vec![String::new(); 16];
+    let mut updates = Vec::new();
+    for (turn, pos) in [5, 0, 10, 3, 15].into_iter().enumerate() {
+        board[pos] = if turn % 2 == 0 { "X" } else { "O" }.to_string();
+        updates.push(GameState {
+            board: board.clone(),
+            status: if turn == 4 { "X_win" } else { "ongoing" }.into(),
+            rated: true,
+            board_size: 4,
+            win_length: 3,
+            ..GameState::default()
+        });
+    }
+    let mut recorder = GameRecorder::new();
+    updates.iter().for_each(|u| recorder.observe(u));
+    let finished = recorder.finish(updates.last().unwrap(), "X");
+    assert_eq!(finished.board_size, 4);
+    assert_eq!(finished.notation(), "5 0 10 3 15");
+    assert_eq!(finished.boards().last().unwrap(), &board);
+
+    // 크기 정보 없이 저장된 예전 항목은 3×3으로 읽힘
+    let old: ArchiveEntry = serde_json::from_str(
+        r#"{"id":1,"date":"2026-01-01T00:00:00Z","game_id":"1","symbol":"X","opponent":"human","result":"win","status":"X_win","moves":[4]}"#,
+    )
+    .unwrap();
+    assert_eq!(old.board_size, 3);
+    assert_eq!(old.boards()[0].len(), 9);
+}
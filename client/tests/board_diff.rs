@@ -0,0 +1,128 @@
+use client::board_diff;
+use client::rpc;
+use client::tictactoe::play_request::Action;
+use client::tictactoe::tic_tac_toe_client::TicTacToeClient;
+use client::tictactoe::{BoardDiff, CellChange, GameState, Join, Move, PlayRequest};
+use server::config::Config;
+use server::service::TicTacToeService;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tonic::transport::{Endpoint, Server};
+use tonic::Streaming;
+
+fn cells(cells: &str) -> Vec<String> {
+    cells.chars().map(|c| if c == '.' { String::new() } else { c.to_string() }).collect()
+}
+
+fn change(position: i32, new_value: &str) -> CellChange {
+    CellChange { position, new_value: new_value.to_string() }
+}
+
+#[test]
+fn diffs_patch_the_last_full_board() {
+    let mut board = Vec::new();
+    let mut full = GameState { board: cells("X........"), full_board: true, ..GameState::default() };
+    board_diff::apply(&mut board, &mut full);
+    assert_eq!(board, cells("X........"));
+
+    let mut update = GameState { diff: Some(BoardDiff { changes: vec![change(4, "O"), change(9, "X")] }), ..GameState::default() };
+    board_diff::apply(&mut board, &mut update);
+    assert_eq!(board, cells("X...O...."));
+    assert_eq!(update.board, board);
+
+    // 하트비트처럼 보드가 없는 업데이트는 보드를 바꾸지 않음
+    let mut beat = GameState { heartbeat: true, ..GameState::default() };
+    board_diff::apply(&mut board, &mut beat);
+    assert_eq!(board, cells("X...O...."));
+    assert!(beat.board.is_empty());
+
+    // 서버가 보낸 해시와 반영한 보드를 비교 (해시가 없으면 비교하지 않음)
+    update.state_hash = common::state_hash::state_hash(&update.board, "ongoing", "X");
+    update.status = "ongoing".into();
+    update.next_player = "X".into();
+    assert!(board_diff::in_sync(&update));
+    update.board[8] = "X".into();
+    assert!(!board_diff::in_sync(&update));
+    assert!(board_diff::in_sync(&beat));
+
+    // 다음 판이 시작되면 칸이 다시 비워짐
+    let mut rematch = GameState { diff: Some(BoardDiff { changes: vec![change(0, ""), change(4, "")] }), ..GameState::default() };
+    board_diff::apply(&mut board, &mut rematch);
+    assert_eq!(board, cells("........."));
+}
+
+async fn start_server() -> Endpoint {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let service = TicTacToeService::new(Config::default());
+    tokio::spawn(Server::builder().add_service(service.into_server()).serve_with_incoming(TcpListenerStream::new(listener)));
+    Endpoint::from_shared(format!("http://{}", addr)).unwrap()
+}
+
+/// 보드 차이를 받기로 하고 `game_id`에 참가해 첫 업데이트(보드 전체)까지 받음
+async fn join(endpoint: &Endpoint, game_id: &str) -> (mpsc::Sender<PlayRequest>, Streaming<GameState>, GameState) {
+    let (tx, rx) = mpsc::channel(8);
+    let join = Join { game_id: game_id.to_string(), board_diffs: true, ..Join::default() };
+    tx.send(PlayRequest { action: Some(Action::Join(join)) }).await.unwrap();
+    let mut client = TicTacToeClient::new(endpoint.connect().await.unwrap());
+    let mut updates = client.play(ReceiverStream::new(rx)).await.unwrap().into_inner();
+    let first = updates.message().await.unwrap().unwrap();
+    assert!(first.full_board && first.diff.is_none() && !first.board.is_empty(), "{:?}", first);
+    (tx, updates, first)
+}
+
+/// `moves`를 차례대로 두며 받은 업데이트마다 보드를 다시 만들고, 보드가 바뀔 수 없는 내 차례마다
+/// 서버의 보드와 비교함 (끝난 게임은 서버가 치우므로 마지막 보드는 호출한 쪽에서 확인). 마지막 상태와 보드 차이로 받은 업데이트 수를 반환
+async fn play(endpoint: Endpoint, session: (mpsc::Sender<PlayRequest>, Streaming<GameState>, GameState), moves: &[i32]) -> (GameState, usize) {
+    let (tx, mut updates, first) = session;
+    let mut board = Vec::new();
+    let mut diffs = 0;
+    let mut moves = moves.iter();
+    let mut next = Some(first);
+    loop {
+        let mut update = match next.take() {
+            Some(update) => update,
+            None => updates.message().await.unwrap().expect("게임이 끝나기 전에 스트림이 닫힘"),
+        };
+        if update.heartbeat {
+            continue;
+        }
+        if !board.is_empty() {
+            assert!(!update.full_board && update.board.is_empty(), "{:?}", update);
+            diffs += 1;
+        }
+        board_diff::apply(&mut board, &mut update);
+        assert!(board_diff::in_sync(&update), "{:?}", update);
+        let my_turn = update.status == "ongoing" && update.next_player == update.your_symbol;
+        let finished = update.status.ends_with("_win") || update.status == "draw";
+        if my_turn {
+            let canonical = rpc::get_game_state(&endpoint, &update.game_id, "").await.unwrap();
+            assert_eq!(board, canonical.board);
+        }
+        if finished {
+            return (update, diffs);
+        }
+        if my_turn {
+            let position = *moves.next().unwrap();
+            tx.send(PlayRequest { action: Some(Action::Move(Move { position, ..Move::default() })) }).await.unwrap();
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn a_full_game_over_diffs_matches_the_server_board() {
+    let endpoint = start_server().await;
+    let game_id = rpc::create_game(&endpoint, "", 0, 0).await.unwrap().game_id;
+    let x = join(&endpoint, &game_id).await;
+    assert_eq!(x.2.your_symbol, "X");
+    let x = tokio::spawn(play(endpoint.clone(), x, &[0, 1, 2]));
+    let o = join(&endpoint, &game_id).await;
+    let o = tokio::spawn(play(endpoint.clone(), o, &[3, 4]));
+    let ((x_last, x_diffs), (o_last, o_diffs)) = (x.await.unwrap(), o.await.unwrap());
+
+    assert_eq!(x_last.status, "X_win");
+    assert_eq!(x_last.board, cells("XXXOO...."));
+    assert_eq!(o_last.board, x_last.board);
+    assert!(x_diffs >= 5 && o_diffs >= 5, "{} {}", x_diffs, o_diffs);
+}
@@ -0,0 +1,88 @@
+use std::time::{Duration, Instant};
+
+use client::ai::{winner, AiClient, Strategy};
+use server::config::Config;
+use server::service::TicTacToeService;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::{Endpoint, Server};
+
+/// 100판을 끝내야 하는 시간 (로컬 루프백 기준으로 넉넉하게)
+const BUDGET: Duration = Duration::from_secs(60);
+
+fn board(cells: &str) -> Vec<String> {
+    cells.chars().map(|c| if c == '.' { String::new() } else { c.to_string() }).collect()
+}
+
+#[test]
+fn greedy_takes_the_win_and_blocks_the_opponent() {
+    // X가 이길 수 있으면 이김 (막는 것보다 우선)
+    let cells = board("XX.OO....");
+    assert_eq!(Strategy::Greedy.choose(&cells, 3, 3, "X"), Some(2));
+    // 이길 수 없으면 O의 가로 줄을 막음
+    let cells = board("X..OO...X");
+    assert_eq!(Strategy::Greedy.choose(&cells, 3, 3, "X"), Some(5));
+    assert_eq!(winner(&board("XXXOO...."), 3, 3), Some("X"));
+}
+
+#[test]
+fn minimax_never_loses_to_itself_on_the_standard_board() {
+    let mut cells = board(".........");
+    let mut symbol = "X";
+    while winner(&cells, 3, 3).is_none() {
+        let Some(pos) = Strategy::Minimax.choose(&cells, 3, 3, symbol) else { break };
+        cells[pos] = symbol.to_string();
+        symbol = if symbol == "X" { "O" } else { "X" };
+    }
+    assert_eq!(winner(&cells, 3, 3), None);
+    assert!(cells.iter().all(|c| !c.is_empty()));
+}
+
+async fn start_server() -> Endpoint {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let service = TicTacToeService::new(Config::default());
+    tokio::spawn(Server::builder().add_service(service.into_server()).serve_with_incoming(TcpListenerStream::new(listener)));
+    Endpoint::from_shared(format!("http://{}", addr)).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn two_bots_finish_a_hundred_games_within_the_budget() {
+    let endpoint = start_server().await;
+    let first = AiClient::new(endpoint.clone(), Strategy::Minimax);
+    let second = AiClient::new(endpoint, Strategy::Greedy);
+    let (first, second) = tokio::time::timeout(BUDGET, async { tokio::join!(first.run(100), second.run(100)) })
+        .await
+        .expect("bots did not finish 100 games within the time budget");
+
+    assert_eq!((first.played(), first.failed), (100, 0));
+    assert_eq!((second.played(), second.failed), (100, 0));
+    // 같은 게임의 양쪽이므로 한쪽의 승리는 다른 쪽의 패배
+    assert_eq!((first.wins, first.draws), (second.losses, second.draws));
+    // 미니맥스는 지지 않음
+    assert_eq!(first.losses, 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn concurrent_connections_pair_up_and_add_up() {
+    let endpoint = start_server().await;
+    let bot = AiClient::new(endpoint, Strategy::Random).with_move_delay(Duration::from_millis(5));
+
+    let started = Instant::now();
+    let summary = tokio::time::timeout(BUDGET, bot.run_concurrent(8, 2)).await.expect("bots did not finish");
+    assert_eq!((summary.played(), summary.failed), (16, 0), "{}", summary);
+    // 모든 게임의 양쪽이 같은 요약에 들어감
+    assert_eq!(summary.wins, summary.losses, "{}", summary);
+    // 게임마다 적어도 다섯 수를 두고, 수마다 기다림
+    assert!(started.elapsed() >= Duration::from_millis(5 * 5), "{:?}", started.elapsed());
+}
+
+#[tokio::test]
+async fn failed_connections_are_counted_without_stopping_the_rest() {
+    // 아무도 받지 않는 포트
+    let unused = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap();
+    let endpoint = Endpoint::from_shared(format!("http://{}", unused)).unwrap().connect_timeout(Duration::from_secs(1));
+    let summary = AiClient::new(endpoint, Strategy::Greedy).run_concurrent(3, 2).await;
+    assert_eq!((summary.played(), summary.failed), (0, 6));
+    assert!(summary.to_string().ends_with(", 6 failed"), "{}", summary);
+}
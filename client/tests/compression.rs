@@ -0,0 +1,60 @@
+use clap::Parser;
+use client::ai::{AiClient, Strategy};
+use client::compression::{self, Compression};
+use client::config::{ClientConfig, ConfigOverrides};
+use server::config::Config;
+use server::service::TicTacToeService;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::codec::CompressionEncoding;
+use tonic::transport::{Endpoint, Server};
+
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    overrides: ConfigOverrides,
+}
+
+/// 응답을 `encoding`으로 압축하는 서버
+async fn start_server(encoding: Option<CompressionEncoding>) -> Endpoint {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let service = TicTacToeService::new(Config::default());
+    let server = Server::builder().add_service(service.into_compressed_server(encoding));
+    tokio::spawn(server.serve_with_incoming(TcpListenerStream::new(listener)));
+    Endpoint::from_shared(format!("http://{}", addr)).unwrap()
+}
+
+/// 두 봇이 `games`판을 두고 둘 다 실패 없이 끝냈는지 확인
+async fn bots_finish(endpoint: Endpoint, games: u32) {
+    let first = AiClient::new(endpoint.clone(), Strategy::Minimax);
+    let second = AiClient::new(endpoint, Strategy::Greedy);
+    let (first, second) = tokio::join!(first.run(games), second.run(games));
+    assert_eq!((first.played(), first.failed), (games, 0));
+    assert_eq!((second.played(), second.failed), (games, 0));
+}
+
+#[test]
+fn compression_comes_from_the_file_or_the_command_line() {
+    assert_eq!(ClientConfig::default().compression, Compression::Gzip);
+
+    let path = std::env::temp_dir().join(format!("tictactoe-client-compression-{}.toml", std::process::id()));
+    std::fs::write(&path, r#"compression = "none""#).unwrap();
+    let cli = Cli::parse_from(["client", "--config", path.to_str().unwrap()]);
+    assert_eq!(ClientConfig::from_overrides(&cli.overrides).unwrap().compression, Compression::None);
+    let cli = Cli::parse_from(["client", "--config", path.to_str().unwrap(), "--compression", "gzip"]);
+    assert_eq!(ClientConfig::from_overrides(&cli.overrides).unwrap().compression, Compression::Gzip);
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(Cli::try_parse_from(["client", "--compression", "brotli"]).is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn bots_finish_games_whether_or_not_the_server_compresses() {
+    // 이 테스트 실행 파일 안에서 만드는 모든 클라이언트가 요청을 gzip으로 압축
+    compression::install(Compression::Gzip).unwrap();
+    assert_eq!(compression::installed(), Some(CompressionEncoding::Gzip));
+
+    bots_finish(start_server(Some(CompressionEncoding::Gzip)).await, 3).await;
+    bots_finish(start_server(None).await, 3).await;
+}
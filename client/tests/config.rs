@@ -0,0 +1,115 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::Parser;
+use client::config::{ClientConfig, ConfigOverrides, DEFAULT_SERVER_ADDR};
+
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    overrides: ConfigOverrides,
+}
+
+fn write_config(name: &str, text: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("tictactoe-client-{}-{}.toml", name, std::process::id()));
+    std::fs::write(&path, text).unwrap();
+    path
+}
+
+#[test]
+fn command_line_overrides_the_config_file() {
+    let path = write_config(
+        "merge",
+        r#"
+server = "http://file.example:50051"
+name = "alice"
+spectate = "7"
+
+[reconnect]
+attempts = 3
+initial_delay_ms = 200
+"#,
+    );
+    let cli = Cli::parse_from(["client", "--config", path.to_str().unwrap(), "--server", "https://cli.example:443"]);
+    let config = ClientConfig::from_overrides(&cli.overrides).unwrap();
+
+    assert_eq!(config.server, "https://cli.example:443");
+    assert_eq!(config.name, "alice");
+    assert_eq!(config.spectate.as_deref(), Some("7"));
+    let policy = config.retry_policy();
+    assert_eq!((policy.max_attempts, policy.initial_delay), (3, Duration::from_millis(200)));
+    assert_eq!(policy.max_delay, Duration::from_secs(15));
+
+    let cli = Cli::parse_from(["client", "--config", path.to_str().unwrap(), "--no-retry", "--name", " bob "]);
+    let config = ClientConfig::from_overrides(&cli.overrides).unwrap();
+    assert_eq!((config.server.as_str(), config.name.as_str()), ("http://file.example:50051", "bob"));
+    assert_eq!(config.retry_policy().max_attempts, 1);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn missing_fields_use_defaults_and_bad_files_are_reported() {
+    let path = write_config("empty", "");
+    let config = ClientConfig::load(Some(&path)).unwrap();
+    assert_eq!(config, ClientConfig::default());
+    assert_eq!(config.server, DEFAULT_SERVER_ADDR);
+    std::fs::remove_file(&path).unwrap();
+
+    let path = write_config("bad", "server = 5");
+    assert!(ClientConfig::load(Some(&path)).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn heartbeat_interval_comes_from_the_file_or_the_command_line() {
+    assert_eq!(ClientConfig::default().heartbeat_interval(), Some(Duration::from_secs(15)));
+
+    let path = write_config("heartbeat", "heartbeat_interval_secs = 5");
+    let cli = Cli::parse_from(["client", "--config", path.to_str().unwrap()]);
+    assert_eq!(ClientConfig::from_overrides(&cli.overrides).unwrap().heartbeat_interval(), Some(Duration::from_secs(5)));
+
+    let cli = Cli::parse_from(["client", "--config", path.to_str().unwrap(), "--heartbeat-secs", "0"]);
+    assert_eq!(ClientConfig::from_overrides(&cli.overrides).unwrap().heartbeat_interval(), None);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn spectate_without_a_game_id_means_the_newest_game() {
+    let mut config = ClientConfig::default();
+    config.apply(&Cli::parse_from(["client", "--spectate"]).overrides);
+    assert_eq!(config.spectate.as_deref(), Some(""));
+
+    config.apply(&Cli::parse_from(["client", "--spectate", "12"]).overrides);
+    assert_eq!(config.spectate.as_deref(), Some("12"));
+}
+
+#[test]
+fn symbol_flag_accepts_x_or_o_in_any_case() {
+    let mut config = ClientConfig::default();
+    config.apply(&Cli::parse_from(["client", "--symbol", "o"]).overrides);
+    assert_eq!(config.symbol, "O");
+    assert!(Cli::try_parse_from(["client", "--symbol", "Z"]).is_err());
+}
+
+#[test]
+fn tls_options_are_checked_before_connecting() {
+    let ca = write_config("ca", "-----BEGIN CERTIFICATE-----\nMIIB\n-----END CERTIFICATE-----\n");
+    let not_pem = write_config("not-pem", "hello");
+    let error = |args: &[&str]| {
+        let mut config = ClientConfig::default();
+        config.apply(&Cli::parse_from(args).overrides);
+        config.endpoint().err().map(|e| e.to_string())
+    };
+    let ca_path = ca.to_str().unwrap();
+
+    assert_eq!(error(&["client"]), None);
+    assert!(error(&["client", "--domain", "ttt.example"]).unwrap().contains("--ca-cert"));
+    assert!(error(&["client", "--ca-cert", ca_path]).unwrap().contains("https://"));
+    let bad = error(&["client", "--server", "https://localhost:50051", "--ca-cert", not_pem.to_str().unwrap()]).unwrap();
+    assert!(bad.contains("not a PEM certificate"), "{}", bad);
+    // PEM 모양이지만 내용이 인증서가 아님
+    let bad = error(&["client", "--server", "https://localhost:50051", "--ca-cert", ca_path, "--domain", "ttt.example"]).unwrap();
+    assert!(bad.contains("invalid CA certificate") && bad.contains(ca_path), "{}", bad);
+    std::fs::remove_file(&ca).unwrap();
+    std::fs::remove_file(&not_pem).unwrap();
+}
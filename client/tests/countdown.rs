@@ -0,0 +1,69 @@
+use std::time::{Duration, Instant};
+
+use client::countdown::{lost_on_time_line, Countdown};
+use client::tictactoe::GameState;
+
+fn my_turn(seconds: u32) -> GameState {
+    GameState {
+        status: "ongoing".into(),
+        next_player: "X".into(),
+        your_symbol: "X".into(),
+        turn_deadline_seconds: seconds,
+        move_number: 3,
+        ..GameState::default()
+    }
+}
+
+#[test]
+fn counts_down_from_the_seconds_the_server_sent() {
+    let start = Instant::now();
+    let countdown = Countdown::update(None, &my_turn(37), false, start).unwrap();
+    assert_eq!(countdown.line(start), "Your turn \u{2014} 37s left");
+    // 남은 초는 올림이라 0.5초가 지나도 37초, 1초가 지나면 36초
+    assert_eq!(countdown.seconds_left(start + Duration::from_millis(500)), 37);
+    assert_eq!(countdown.line(start + Duration::from_secs(1)), "Your turn \u{2014} 36s left");
+    assert_eq!(countdown.until_next_tick(start + Duration::from_millis(300)), Duration::from_millis(700));
+    assert_eq!(countdown.seconds_left(start + Duration::from_secs(60)), 0);
+    assert_eq!(countdown.until_next_tick(start + Duration::from_secs(60)), Duration::ZERO);
+}
+
+#[test]
+fn only_my_turn_with_a_limit_gets_a_countdown() {
+    let now = Instant::now();
+    assert!(Countdown::update(None, &my_turn(0), false, now).is_none(), "제한 없음");
+    assert!(Countdown::update(None, &my_turn(30), true, now).is_none(), "관전자");
+    let opponent = GameState { next_player: "O".into(), ..my_turn(30) };
+    assert!(Countdown::update(None, &opponent, false, now).is_none());
+    let finished = GameState { status: "X_win".into(), ..my_turn(30) };
+    assert!(Countdown::update(None, &finished, false, now).is_none());
+}
+
+#[test]
+fn the_bell_rings_once_at_ten_seconds_even_across_a_chat_update() {
+    let start = Instant::now();
+    let mut countdown = Countdown::update(None, &my_turn(15), false, start).unwrap();
+    assert!(!countdown.take_warning(start + Duration::from_secs(4)));
+    assert!(countdown.take_warning(start + Duration::from_secs(5)));
+    assert!(!countdown.take_warning(start + Duration::from_secs(6)));
+
+    // 상태 번호가 같은 업데이트(채팅 등)로 다시 시작해도 다시 울리지 않음
+    let later = start + Duration::from_secs(6);
+    let mut same_turn = Countdown::update(Some(&countdown), &my_turn(9), false, later).unwrap();
+    assert!(!same_turn.take_warning(later));
+    // 새 차례라면 다시 울림
+    let next_turn = GameState { move_number: 5, ..my_turn(9) };
+    let mut next = Countdown::update(Some(&countdown), &next_turn, false, later).unwrap();
+    assert!(next.take_warning(later));
+}
+
+#[test]
+fn a_time_forfeit_is_described_from_each_side() {
+    let forfeit = GameState { status: "O_win_by_resignation".into(), your_symbol: "X".into(), lost_on_time: "X".into(), ..GameState::default() };
+    assert_eq!(lost_on_time_line(&forfeit, false).as_deref(), Some("You lost on time"));
+    let winner = GameState { your_symbol: "O".into(), ..forfeit.clone() };
+    assert_eq!(lost_on_time_line(&winner, false).as_deref(), Some("You won on time: your opponent ran out of time"));
+    assert_eq!(lost_on_time_line(&forfeit, true).as_deref(), Some("X lost on time"));
+
+    let resigned = GameState { lost_on_time: String::new(), ..forfeit };
+    assert_eq!(lost_on_time_line(&resigned, false), None);
+}
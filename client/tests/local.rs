@@ -0,0 +1,61 @@
+use client::local::{self, LocalGame};
+use client::moves::MoveRejection;
+use client::render::Style;
+use client::tictactoe::GameState;
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+/// 줄마다 입력 하나를 넣고 로컬 게임을 끝까지 둔 뒤 (마지막 상태, 출력)
+async fn play(input: &str) -> (GameState, String) {
+    let mut lines = BufReader::new(input.as_bytes()).lines();
+    let mut out = Vec::new();
+    let state = local::play(&mut lines, &mut out, &Style::default()).await.unwrap();
+    (state, String::from_utf8(out).unwrap())
+}
+
+#[test]
+fn moves_alternate_and_a_line_wins() {
+    let mut game = LocalGame::new();
+    for position in [0, 3, 1, 4] {
+        game.play(position).unwrap();
+    }
+    assert_eq!((game.state().status.as_str(), game.state().next_player.as_str()), ("ongoing", "X"));
+    assert_eq!(game.play(3), Err(MoveRejection::Taken(3)));
+    assert_eq!(game.play(9), Err(MoveRejection::OutOfRange(9)));
+    game.play(2).unwrap();
+    assert_eq!(game.state().status, "X_win");
+    assert_eq!(game.play(5), Err(MoveRejection::NotYourTurn));
+}
+
+#[tokio::test]
+async fn scripted_game_prompts_each_player_and_prints_the_result() {
+    let (state, out) = play("0\n3\n1\n4\n2\n").await;
+    assert_eq!(state.status, "X_win");
+    assert!(out.contains("Player X, enter your move:") && out.contains("Player O, enter your move:"), "{}", out);
+    // 빈 칸 번호가 보이는 보드와, 끝난 뒤의 결과와 기보
+    assert!(out.contains("| 0 | 1 | 2 |"), "{}", out);
+    assert!(out.ends_with("Game Over: X_win\nMoves: 1. X\u{2192}0, 2. O\u{2192}3, 3. X\u{2192}1, 4. O\u{2192}4, 5. X\u{2192}2\n"), "{}", out);
+}
+
+#[tokio::test]
+async fn bad_input_is_explained_like_the_online_game_and_the_turn_stays() {
+    let (state, out) = play("9\nhello\n1,1\na1\nb2\nexit\n").await;
+    assert!(out.contains("Invalid move. Please enter a number between 0 and 8."), "{}", out);
+    assert!(out.contains("Enter a cell number (0-8)"), "{}", out);
+    // 1,1과 a1은 같은 칸이라 O의 a1은 거부되고 O가 다시 둠
+    assert!(out.contains("Cell 0 is taken."), "{}", out);
+    assert_eq!(state.board[0], "X");
+    assert_eq!(state.board[4], "O");
+    // 'exit'으로 나가면 결과를 출력하지 않음
+    assert_eq!(state.status, "ongoing");
+    assert!(!out.contains("Game Over"), "{}", out);
+}
+
+#[tokio::test]
+async fn a_full_board_without_a_line_is_a_draw_and_input_can_run_out() {
+    let (state, out) = play("4\n0\n2\n6\n3\n5\n1\n7\n8\n").await;
+    assert_eq!(state.status, "draw");
+    assert!(out.contains("Game Over: draw"), "{}", out);
+
+    let (state, _) = play("4\n").await;
+    assert_eq!((state.status.as_str(), state.history.len()), ("ongoing", 1));
+}
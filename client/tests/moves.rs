@@ -0,0 +1,99 @@
+use client::moves::{check_move, parse_move, parse_wild_move, MoveRejection, ParseError};
+
+fn board(cells: &str) -> Vec<String> {
+    cells.chars().map(|c| if c == '.' { String::new() } else { c.to_string() }).collect()
+}
+
+#[test]
+fn empty_cell_on_your_turn_is_allowed() {
+    assert_eq!(check_move(&board("X...O...."), "X", "X", 8), Ok(()));
+}
+
+#[test]
+fn taken_cell_and_opponents_turn_are_caught_locally() {
+    let board = board("X...O....");
+    assert_eq!(check_move(&board, "X", "X", 4), Err(MoveRejection::Taken(4)));
+    assert_eq!(MoveRejection::Taken(4).to_string(), "Cell 4 is taken.");
+    assert_eq!(check_move(&board, "X", "O", 8), Err(MoveRejection::NotYourTurn));
+    // 칸이 찼으면 차례보다 먼저 알려 줌
+    assert_eq!(check_move(&board, "X", "O", 0), Err(MoveRejection::Taken(0)));
+}
+
+#[test]
+fn positions_off_the_board_report_the_range() {
+    let rejection = check_move(&board("........."), "X", "X", 9).unwrap_err();
+    assert_eq!(rejection, MoveRejection::OutOfRange(9));
+    assert_eq!(rejection.to_string(), "Invalid move. Please enter a number between 0 and 8.");
+}
+
+#[test]
+fn wild_input_takes_an_optional_symbol_after_the_cell() {
+    assert_eq!(parse_wild_move("4", 4), Ok((4, None)));
+    assert_eq!(parse_wild_move("4 o", 4), Ok((4, Some("O".into()))));
+    assert_eq!(parse_wild_move(" 12X ", 4), Ok((12, Some("X".into()))));
+    assert_eq!(parse_wild_move("b2 x", 4), Ok((5, Some("X".into()))));
+    assert_eq!(parse_wild_move("1,2o", 4), Ok((1, Some("O".into()))));
+    assert!(parse_wild_move("4 z", 4).is_err());
+    assert!(parse_wild_move("O", 4).is_err());
+}
+
+#[test]
+fn cells_are_numbers_row_col_pairs_or_letter_row() {
+    // 칸 번호는 0부터, 행/열과 열 글자/행은 1부터 (위쪽 행, 왼쪽 열이 1)
+    assert_eq!(parse_move("4", 3), Ok(4));
+    assert_eq!(parse_move("1,3", 3), Ok(2));
+    assert_eq!(parse_move("3,1", 3), Ok(6));
+    assert_eq!(parse_move("c1", 3), Ok(2));
+    assert_eq!(parse_move("A3", 3), Ok(6));
+    assert_eq!(parse_move("b2", 3), Ok(4));
+    // 더 큰 보드에서도 같은 형식
+    assert_eq!(parse_move("2,5", 5), Ok(9));
+    assert_eq!(parse_move("e5", 5), Ok(24));
+    assert_eq!(parse_move("o15", 15), Ok(224));
+}
+
+#[test]
+fn whitespace_around_and_inside_coordinates_is_ignored() {
+    assert_eq!(parse_move("  7 ", 3), Ok(7));
+    assert_eq!(parse_move("2 , 3", 3), Ok(5));
+    assert_eq!(parse_move("2 3", 3), Ok(5));
+    assert_eq!(parse_move(" b 2 ", 3), Ok(4));
+    assert_eq!(parse_move("   ", 3), Err(ParseError::Empty));
+}
+
+#[test]
+fn zero_in_a_coordinate_is_rejected_as_ambiguous() {
+    for input in ["0,0", "0,2", "2,0", "a0"] {
+        assert_eq!(parse_move(input, 3), Err(ParseError::ZeroCoordinate), "{}", input);
+    }
+    let message = ParseError::ZeroCoordinate.to_string();
+    assert!(message.contains("start at 1") && message.contains("1,1"), "{}", message);
+}
+
+#[test]
+fn coordinates_off_the_board_are_rejected() {
+    assert_eq!(parse_move("9", 3), Err(ParseError::CellOutOfRange(9)));
+    assert_eq!(
+        ParseError::CellOutOfRange(9).to_string(),
+        "Invalid move. Please enter a number between 0 and 8."
+    );
+    assert_eq!(parse_move("99999999999999999999999", 3), Err(ParseError::CellOutOfRange(9)));
+    assert_eq!(parse_move("4,1", 3), Err(ParseError::CoordinateOutOfRange(3)));
+    assert_eq!(parse_move("1,4", 3), Err(ParseError::CoordinateOutOfRange(3)));
+    assert_eq!(parse_move("d1", 3), Err(ParseError::CoordinateOutOfRange(3)));
+    assert_eq!(
+        ParseError::CoordinateOutOfRange(3).to_string(),
+        "That cell is off the board: rows go from 1 to 3 and columns from 1 to 3 (a-c)."
+    );
+}
+
+#[test]
+fn anything_else_explains_the_accepted_formats() {
+    for input in ["-1", "x", "1,", ",2", "b", "2b", "1,2,3", "resgin"] {
+        assert_eq!(parse_move(input, 3), Err(ParseError::Unrecognized(3)), "{}", input);
+    }
+    assert_eq!(
+        ParseError::Unrecognized(3).to_string(),
+        "Enter a cell number (0-8), row,col (e.g. 1,3) or a column letter and row (e.g. c1)."
+    );
+}
@@ -0,0 +1,127 @@
+use client::render::{board, game_board, history, leaderboard, winning_line, Board, ColorChoice, Style};
+use client::tictactoe::{GameState, Move, PlayerStats};
+
+/// 보드 문자열("X.O......")을 칸 목록으로
+fn cells(board: &str) -> Vec<String> {
+    board.chars().map(|c| if c == '.' { String::new() } else { c.to_string() }).collect()
+}
+
+#[test]
+fn plain_board_snapshot() {
+    let cells = cells("X.O.X...O");
+    let expected = "\
+-------------
+| X |   | O |
+-------------
+|   | X |   |
+-------------
+|   |   | O |
+-------------
+";
+    assert_eq!(board(&Board::new(&cells, 3), &Style::default()), expected);
+}
+
+#[test]
+fn colored_board_paints_each_seat_and_reverses_the_winning_line() {
+    let cells = cells("XXXOO....");
+    let winning_line = [0, 1, 2];
+    let view = Board { winning_line: &winning_line, ..Board::new(&cells, 3) };
+    let rendered = board(&view, &Style::colored());
+    let rows: Vec<&str> = rendered.lines().collect();
+    assert_eq!(rows[1], "| \x1b[36;7mX\x1b[0m | \x1b[36;7mX\x1b[0m | \x1b[36;7mX\x1b[0m |");
+    assert_eq!(rows[3], "| \x1b[35mO\x1b[0m | \x1b[35mO\x1b[0m |   |");
+    assert_eq!(rows[5], "|   |   |   |");
+}
+
+#[test]
+fn finished_games_highlight_their_winning_line_and_list_the_moves() {
+    let won = GameState { board: cells("OXXOX.O.."), board_size: 3, win_length: 3, status: "O_win".into(), ..GameState::default() };
+    assert_eq!(winning_line(&won), [0, 3, 6]);
+    assert!(game_board(&won, &Style::colored(), false).contains("\x1b[35;7mO\x1b[0m"));
+    // 진행 중이거나 기권으로 끝난 게임은 강조하지 않음
+    let resigned = GameState { status: "O_win_by_resignation".into(), ..won.clone() };
+    assert!(winning_line(&resigned).is_empty());
+    assert_eq!(game_board(&resigned, &Style::default(), false), board(&Board::of(&resigned), &Style::default()));
+
+    let moves = [Move { player_id: "X".into(), position: 4, ..Move::default() }, Move { player_id: "O".into(), position: 0, ..Move::default() }];
+    assert_eq!(history(&moves).as_deref(), Some("Moves: 1. X\u{2192}4, 2. O\u{2192}0"));
+    assert_eq!(history(&[]), None);
+}
+
+#[test]
+fn markers_keep_the_seat_color_and_wide_cells_stay_aligned() {
+    let state = GameState {
+        board: cells("XO......."),
+        board_size: 3,
+        x_symbol: "\u{1f436}".into(),
+        ..GameState::default()
+    };
+    let plain = board(&Board::of(&state), &Style::default());
+    assert_eq!(plain.lines().nth(1), Some("| \u{1f436} | O  |    |"));
+    let colored = board(&Board::of(&state), &Style::colored());
+    assert_eq!(colored.lines().nth(1), Some("| \x1b[36m\u{1f436}\x1b[0m | \x1b[35mO \x1b[0m |    |"));
+}
+
+#[test]
+fn larger_boards_number_each_row() {
+    let cells = cells("X...............");
+    let rendered = board(&Board::new(&cells, 4), &Style::default());
+    let rows: Vec<&str> = rendered.lines().collect();
+    assert_eq!(rows[0], "   -----------------");
+    assert_eq!(rows[1], " 0 | X |   |   |   |");
+    assert_eq!(rows[7], "12 |   |   |   |   |");
+}
+
+#[test]
+fn status_lines_are_bold_or_red_only_with_color() {
+    assert_eq!(Style::colored().bold("Your turn"), "\x1b[1mYour turn\x1b[0m");
+    assert_eq!(Style::colored().error("Error: taken"), "\x1b[31mError: taken\x1b[0m");
+    assert_eq!(Style::default().bold("Your turn"), "Your turn");
+    assert_eq!(Style::default().error("Error: taken"), "Error: taken");
+}
+
+#[test]
+fn auto_color_respects_no_color_and_non_terminals() {
+    assert!(Style::for_environment(ColorChoice::Auto, false, true).color);
+    assert!(!Style::for_environment(ColorChoice::Auto, true, true).color);
+    assert!(!Style::for_environment(ColorChoice::Auto, false, false).color);
+    assert!(Style::for_environment(ColorChoice::Always, true, false).color);
+    assert!(!Style::for_environment(ColorChoice::Never, false, true).color);
+}
+
+#[test]
+fn empty_cells_show_their_index_dimmed_for_the_player() {
+    let played = cells("X...O....");
+    let view = Board { indices: true, ..Board::new(&played, 3) };
+    let plain = board(&view, &Style::default());
+    assert_eq!(plain.lines().nth(1), Some("| X | 1 | 2 |"));
+    assert_eq!(plain.lines().nth(3), Some("| 3 | O | 5 |"));
+    let colored = board(&view, &Style::colored());
+    assert_eq!(colored.lines().nth(1), Some("| \x1b[36mX\x1b[0m | \x1b[2m1\x1b[0m | \x1b[2m2\x1b[0m |"));
+
+    // 두 자리 번호가 생기면 모든 칸을 같은 폭으로 맞춤
+    let empty = cells("................");
+    let wide = board(&Board { indices: true, ..Board::new(&empty, 4) }, &Style::default());
+    assert_eq!(wide.lines().nth(1), Some(" 0 | 0  | 1  | 2  | 3  |"));
+}
+
+#[test]
+fn leaderboard_ranks_players_in_the_order_received() {
+    let stats = |player_id: &str, games_played, wins, losses, draws| PlayerStats {
+        player_id: player_id.into(),
+        games_played,
+        wins,
+        losses,
+        draws,
+        ..PlayerStats::default()
+    };
+    let table = leaderboard(&[stats("alexandria", 4, 3, 1, 0), stats("bob", 3, 1, 1, 1), stats("dave", 0, 0, 0, 0)]);
+    let expected = "\
+Rank  Player      Games    W    L    D  Win%
+   1  alexandria      4    3    1    0   75%
+   2  bob             3    1    1    1   33%
+   3  dave            0    0    0    0    0%
+";
+    assert_eq!(table, expected);
+    assert_eq!(leaderboard(&[]), "No finished games yet.\n");
+}
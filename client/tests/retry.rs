@@ -0,0 +1,87 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use client::retry::{connect_with_retry, retry_with_backoff, RetryPolicy};
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::service::Routes;
+use tonic::transport::{Endpoint, Server};
+
+#[test]
+fn delays_double_up_to_the_cap() {
+    let policy = RetryPolicy::default();
+    let delays: Vec<Duration> = (1..=8).map(|attempt| policy.delay_after(attempt)).collect();
+    let millis = [500, 1000, 2000, 4000, 8000, 15000, 15000, 15000];
+    assert_eq!(delays, millis.map(Duration::from_millis));
+
+    // 아주 큰 시도 횟수에서도 넘치지 않음
+    assert_eq!(policy.delay_after(u32::MAX), Duration::from_secs(15));
+}
+
+#[test]
+fn no_retry_allows_a_single_attempt() {
+    assert_eq!(RetryPolicy::no_retry().max_attempts, 1);
+    assert_eq!(RetryPolicy::default().max_attempts, 10);
+}
+
+#[test]
+fn jitter_stays_between_half_and_the_full_delay() {
+    let policy = RetryPolicy::default();
+    for attempt in 1..=8 {
+        let full = policy.delay_after(attempt);
+        let delay = policy.jittered_delay_after(attempt);
+        assert!(delay >= full / 2 && delay <= full, "attempt {attempt}: {delay:?} not within {full:?}");
+    }
+}
+
+/// 빈 gRPC 서버를 주소에 띄움
+fn serve(listener: TcpListener) {
+    tokio::spawn(async move {
+        Server::builder()
+            .add_routes(Routes::default())
+            .serve_with_incoming(TcpListenerStream::new(listener))
+            .await
+            .unwrap();
+    });
+}
+
+/// 아직 아무도 듣고 있지 않은 로컬 주소
+async fn unused_addr() -> SocketAddr {
+    TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap()
+}
+
+#[tokio::test]
+async fn mock_server_becomes_available_after_three_attempts() {
+    let addr = unused_addr().await;
+    let endpoint = Endpoint::from_shared(format!("http://{addr}")).unwrap();
+    let policy = RetryPolicy { max_attempts: 5, initial_delay: Duration::from_millis(10), ..RetryPolicy::default() };
+
+    let attempts = retry_with_backoff(policy, |attempt| {
+        let endpoint = endpoint.clone();
+        async move {
+            // 세 번 거절된 뒤에야 서버가 뜸
+            if attempt == 4 {
+                serve(TcpListener::bind(addr).await.unwrap());
+            }
+            endpoint.connect().await.map(|_| attempt)
+        }
+    })
+    .await;
+    assert_eq!(attempts.unwrap(), 4);
+}
+
+#[tokio::test]
+async fn connect_with_retry_waits_for_a_late_server() {
+    let addr = unused_addr().await;
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        serve(TcpListener::bind(addr).await.unwrap());
+    });
+    assert!(connect_with_retry(&format!("http://{addr}"), 10, Duration::from_millis(20)).await.is_ok());
+}
+
+#[tokio::test]
+async fn gives_up_after_max_attempts() {
+    let addr = unused_addr().await;
+    assert!(connect_with_retry(&format!("http://{addr}"), 2, Duration::from_millis(10)).await.is_err());
+}
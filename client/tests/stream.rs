@@ -0,0 +1,53 @@
+use client::stream::{self, Disconnect, Item};
+use client::tictactoe::{EndReason, GameState, StreamEnd};
+use prost::Message;
+use tonic::{Code, Status};
+
+fn ended(code: Code, reason: EndReason) -> Status {
+    let details = StreamEnd { reason: reason as i32, ..StreamEnd::default() }.encode_to_vec();
+    Status::with_details(code, "closing", details.into())
+}
+
+#[test]
+fn updates_and_heartbeats_are_not_the_end_of_the_stream() {
+    let update = GameState { status: "ongoing".into(), ..GameState::default() };
+    assert_eq!(stream::classify(Ok(Some(update.clone())), false), Item::Update(Box::new(update)));
+    let heartbeat = GameState { heartbeat: true, ..GameState::default() };
+    assert_eq!(stream::classify(Ok(Some(heartbeat)), false), Item::Heartbeat);
+}
+
+#[test]
+fn a_clean_close_is_only_normal_after_the_game_finished() {
+    assert_eq!(stream::classify(Ok(None), true), Item::End(Disconnect::Finished));
+    assert_eq!(stream::classify(Ok(None), false), Item::End(Disconnect::ServerClosed));
+    assert_eq!(Disconnect::Finished.message(true), None);
+    assert_eq!(Disconnect::ServerClosed.message(true).as_deref(), Some("The server closed the game stream."));
+}
+
+#[test]
+fn errors_split_into_server_endings_refusals_and_lost_connections() {
+    let Item::End(end) = stream::classify(Err(ended(Code::Aborted, EndReason::GameAbandoned)), false) else {
+        panic!("스트림 종료가 아님");
+    };
+    assert_eq!(end, Disconnect::Ended { reason: EndReason::GameAbandoned, message: "closing".into() });
+    assert!(!end.is_recoverable());
+
+    let refused = stream::classify(Err(Status::resource_exhausted("game is full")), false);
+    assert_eq!(refused, Item::End(Disconnect::Refused { code: Code::ResourceExhausted, message: "game is full".into() }));
+
+    // 사유가 없는 Unavailable은 연결이 끊긴 것이라 재접속함
+    let Item::End(lost) = stream::classify(Err(Status::unavailable("connection reset")), true) else {
+        panic!("스트림 종료가 아님");
+    };
+    assert!(lost.is_recoverable());
+    assert_eq!(lost.message(true).as_deref(), Some("Lost the game stream (Unavailable): connection reset"));
+    assert_eq!(lost.closing_message(), "Connection to the server was lost. No more moves accepted.");
+}
+
+#[test]
+fn not_found_means_a_bad_code_before_a_seat_and_an_expired_session_after() {
+    let end = Disconnect::from(&Status::not_found("no such game"));
+    assert_eq!(end.message(false).as_deref(), Some("No game matches that game id or room code (room codes expire)."));
+    assert_eq!(end.message(true).as_deref(), Some("Session expired: the game is no longer available."));
+    assert_eq!(stream::describe(&Status::not_found("no such game")), "NotFound: no such game");
+}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use client::rpc;
+use client::telemetry;
+use client::tictactoe::ReplayRequest;
+use opentelemetry::trace::{SpanId, TracerProvider as _};
+use opentelemetry_sdk::export::trace::SpanData;
+use opentelemetry_sdk::testing::trace::InMemorySpanExporter;
+use opentelemetry_sdk::trace::TracerProvider;
+use server::config::Config;
+use server::event_log::GameEventLogger;
+use server::service::TicTacToeService;
+use server::telemetry::TraceContextLayer;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::{Endpoint, Server};
+use tracing::{info_span, Instrument};
+use tracing_subscriber::layer::SubscriberExt;
+
+/// main과 같이 추적 문맥을 이어받는 서버 (다시 보기는 이벤트 로그에서 읽음)
+async fn start_server() -> Endpoint {
+    let path = std::env::temp_dir().join(format!("tictactoe-telemetry-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let log = GameEventLogger::open(&path, u64::MAX).unwrap();
+    let service = TicTacToeService::new(Config::default()).with_event_log(Arc::new(log));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = Server::builder().layer(TraceContextLayer).add_service(service.into_server());
+    tokio::spawn(server.serve_with_incoming(TcpListenerStream::new(listener)));
+    Endpoint::from_shared(format!("http://{}", addr)).unwrap()
+}
+
+/// 이름이 `name`인 스팬이 끝나 내보내질 때까지 기다림
+async fn finished(exporter: &InMemorySpanExporter, name: &str) -> SpanData {
+    for _ in 0..100 {
+        if let Some(span) = exporter.get_finished_spans().unwrap().into_iter().find(|span| span.name == name) {
+            return span;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("span {} was not exported", name);
+}
+
+#[tokio::test]
+async fn server_and_storage_spans_continue_the_client_trace() {
+    let exporter = InMemorySpanExporter::default();
+    let provider = TracerProvider::builder().with_simple_exporter(exporter.clone()).build();
+    telemetry::install_propagator();
+    // 클라이언트와 서버가 같은 스레드에서 돌므로 한 구독자가 양쪽 스팬을 모두 받음
+    let subscriber = tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+    let _guard = tracing::subscriber::set_default(subscriber);
+    let endpoint = start_server().await;
+
+    // 클라이언트 → 서버 RPC → 핸들러 → 이벤트 로그 읽기
+    let mut client = telemetry::traced(endpoint.connect().await.unwrap());
+    let request = ReplayRequest { game_id: "404".into(), paced: Some(false) };
+    let missing = client.replay_game(request).instrument(info_span!("watch_replay")).await.unwrap_err();
+    assert_eq!(missing.code(), tonic::Code::NotFound);
+
+    let root = finished(&exporter, "watch_replay").await;
+    let rpc = finished(&exporter, "/tictactoe.TicTacToe/ReplayGame").await;
+    let handler = finished(&exporter, "replay_game").await;
+    let storage = finished(&exporter, "replay").await;
+    assert_eq!(root.parent_span_id, SpanId::INVALID);
+    assert_eq!(rpc.parent_span_id, root.span_context.span_id());
+    assert_eq!(handler.parent_span_id, rpc.span_context.span_id());
+    assert_eq!(storage.parent_span_id, handler.span_context.span_id());
+    for span in [&rpc, &handler, &storage] {
+        assert_eq!(span.span_context.trace_id(), root.span_context.trace_id(), "{}", span.name);
+    }
+
+    // 단항 RPC 도우미도 자기 스팬의 문맥을 실어 보냄
+    rpc::get_game_state(&endpoint, "404", "").await.unwrap_err();
+    let call = finished(&exporter, "get_game_state").await;
+    let rpc = finished(&exporter, "/tictactoe.TicTacToe/GetGameState").await;
+    assert_eq!(rpc.span_context.trace_id(), call.span_context.trace_id());
+    assert_eq!(rpc.parent_span_id, call.span_context.span_id());
+}
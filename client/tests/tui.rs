@@ -0,0 +1,275 @@
+use client::tictactoe::{GameState, Move};
+use client::tui::{awaits_rematch, command_for, match_result_line, scoreboard_line, seat_label, swap_hint, Command, Direction, Opponent, UiEvent, ViewState, MAX_MESSAGES};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+fn ongoing(board_size: i32, next_player: &str) -> GameState {
+    GameState {
+        game_id: "1".into(),
+        board: vec![String::new(); (board_size * board_size) as usize],
+        board_size,
+        status: "ongoing".into(),
+        next_player: next_player.into(),
+        your_symbol: "X".into(),
+        opponent_connected: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn cursor_moves_within_the_board_edges() {
+    let mut view = ViewState::new(false);
+    assert_eq!(view.cursor, 4);
+    view.move_cursor(Direction::Up);
+    view.move_cursor(Direction::Up);
+    assert_eq!(view.cursor, 1);
+    view.move_cursor(Direction::Left);
+    view.move_cursor(Direction::Left);
+    assert_eq!(view.cursor, 0);
+    for _ in 0..5 {
+        view.move_cursor(Direction::Down);
+        view.move_cursor(Direction::Right);
+    }
+    assert_eq!(view.cursor, 8);
+}
+
+#[test]
+fn applying_a_larger_board_recenters_the_cursor() {
+    let mut view = ViewState::new(false);
+    view.move_cursor(Direction::Up);
+    view.apply(&ongoing(5, "X"));
+    assert_eq!((view.board_size, view.cursor), (5, 12));
+    view.move_cursor(Direction::Right);
+    view.apply(&ongoing(5, "O"));
+    assert_eq!(view.cursor, 13);
+}
+
+#[test]
+fn status_line_shows_turn_and_symbol() {
+    let mut view = ViewState::new(false);
+    assert_eq!(view.status_line(), "Connecting...");
+    view.apply(&ongoing(3, "X"));
+    assert!(view.is_my_turn());
+    assert_eq!(view.status_line(), "Game 1  |  Your turn  |  You: X");
+    view.apply(&ongoing(3, "O"));
+    assert_eq!(view.status_line(), "Game 1  |  O to move  |  You: X");
+
+    let mut spectator = ViewState::new(true);
+    spectator.apply(&ongoing(3, "X"));
+    assert!(!spectator.is_my_turn());
+    assert_eq!(spectator.status_line(), "Game 1  |  X to move  |  Spectating");
+}
+
+#[test]
+fn status_line_counts_down_my_turn_and_names_a_time_forfeit() {
+    let mut view = ViewState::new(false);
+    view.apply(&GameState { turn_deadline_seconds: 37, ..ongoing(3, "X") });
+    assert!(view.countdown.is_some());
+    assert_eq!(view.status_line(), "Game 1  |  Your turn \u{2014} 37s left  |  You: X");
+    // 수를 두어 상대 차례가 되면 카운트다운이 바로 멈춤
+    view.apply(&GameState { turn_deadline_seconds: 30, ..ongoing(3, "O") });
+    assert!(view.countdown.is_none());
+
+    view.apply(&GameState { status: "O_win_by_resignation".into(), lost_on_time: "X".into(), ..ongoing(3, "X") });
+    assert_eq!(view.status_line(), "Game 1  |  Game over: You lost on time  |  You: X");
+
+    // --no-timer면 남은 시간을 보이지 않음
+    let mut plain = ViewState::new(false);
+    plain.timer = false;
+    plain.apply(&GameState { turn_deadline_seconds: 37, ..ongoing(3, "X") });
+    assert_eq!(plain.status_line(), "Game 1  |  Your turn  |  You: X");
+}
+
+#[test]
+fn status_line_names_the_players_next_to_their_symbols() {
+    let named = |next_player| GameState { player_x_name: "Alice".into(), player_o_name: "Bob".into(), ..ongoing(3, next_player) };
+    let mut view = ViewState::new(false);
+    view.apply(&named("O"));
+    assert_eq!(view.status_line(), "Game 1  |  Bob (O) to move  |  You: Alice (X)");
+    assert_eq!(seat_label("", "X"), "X");
+}
+
+#[test]
+fn opponent_arrivals_and_departures_are_told_apart() {
+    let waiting = GameState { status: "waiting".into(), ..Default::default() };
+    let joined = ongoing(3, "X");
+    let left = GameState { opponent_connected: false, ..ongoing(3, "X") };
+
+    // 처음 상대를 기다리는 동안에는 알릴 것이 없음
+    let (opponent, notice) = Opponent::default().next(&waiting);
+    assert_eq!((opponent, notice), (Opponent::NotJoined, None));
+    let (opponent, notice) = opponent.next(&joined);
+    assert_eq!((opponent, notice), (Opponent::Connected, Some("Opponent joined.")));
+    assert_eq!(opponent.next(&joined), (Opponent::Connected, None));
+    let (opponent, notice) = opponent.next(&left);
+    assert_eq!(opponent, Opponent::Disconnected);
+    assert!(notice.unwrap().starts_with("Opponent disconnected."));
+    assert_eq!(opponent.next(&left), (Opponent::Disconnected, None));
+    assert_eq!(opponent.next(&joined), (Opponent::Connected, Some("Opponent reconnected.")));
+
+    let mut view = ViewState::new(false);
+    view.apply(&left);
+    assert_eq!(view.status_line(), "Game 1  |  Your turn  |  You: X  |  Opponent disconnected");
+    view.apply(&waiting);
+    assert!(!view.status_line().contains("disconnected"));
+}
+
+#[test]
+fn server_messages_go_to_the_message_area() {
+    let mut view = ViewState::new(false);
+    view.apply(&GameState { error_message: "Not your turn".into(), info_message: "Draw offered".into(), ..ongoing(3, "O") });
+    assert_eq!(view.messages, ["Error: Not your turn", "Draw offered"]);
+
+    for i in 0..MAX_MESSAGES {
+        view.push_message(i.to_string());
+    }
+    assert_eq!(view.messages.len(), MAX_MESSAGES);
+    assert_eq!(view.messages.front().map(String::as_str), Some("0"));
+}
+
+#[test]
+fn keys_map_to_commands() {
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    assert_eq!(command_for(key(KeyCode::Left)), Some(Command::Cursor(Direction::Left)));
+    assert_eq!(command_for(key(KeyCode::Char('j'))), Some(Command::Cursor(Direction::Down)));
+    assert_eq!(command_for(key(KeyCode::Enter)), Some(Command::Play));
+    assert_eq!(command_for(key(KeyCode::Char('7'))), Some(Command::Digit('7')));
+    assert_eq!(command_for(key(KeyCode::Char('q'))), Some(Command::Quit));
+    assert_eq!(command_for(key(KeyCode::Char('R'))), Some(Command::Resign));
+    assert_eq!(command_for(key(KeyCode::Char('r'))), Some(Command::Rematch));
+    assert_eq!(command_for(key(KeyCode::Char('x'))), Some(Command::ChooseMark("X")));
+    assert_eq!(command_for(key(KeyCode::Char('s'))), Some(Command::Swap));
+    assert_eq!(command_for(key(KeyCode::Char('z'))), None);
+    assert_eq!(command_for(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)), Some(Command::Quit));
+}
+
+#[test]
+fn history_follows_newly_filled_cells() {
+    let mut view = ViewState::new(false);
+    let mut state = ongoing(3, "O");
+    view.apply(&state);
+    state.board[4] = "X".into();
+    view.apply(&state);
+    state.board[0] = "O".into();
+    view.apply(&state);
+    assert_eq!(view.history_lines(), ["1. X\u{2192}4", "2. O\u{2192}0"]);
+
+    // 끝난 게임은 서버가 보낸 기보를 그대로 사용
+    state.status = "X_win".into();
+    state.history = vec![Move { player_id: "X".into(), position: 4, ..Move::default() }, Move { player_id: "O".into(), position: 0, ..Move::default() }, Move { player_id: "X".into(), position: 8, ..Move::default() }];
+    view.apply(&state);
+    assert_eq!(view.history.len(), 3);
+    assert_eq!(view.status_line(), "Game 1  |  Game over: X_win  |  You: X");
+}
+
+#[test]
+fn a_swap_is_offered_on_my_turn_and_recolours_the_first_move() {
+    // O 자리에서 X의 첫 수를 받음
+    let mut state = ongoing(3, "O");
+    state.your_symbol = "O".into();
+    state.board[4] = "X".into();
+    state.swap_available = true;
+    let mut view = ViewState::new(false);
+    view.apply(&state);
+    assert_eq!(swap_hint(&state).as_deref(), Some("Type 'swap' to take over X's move, or reply with a move as usual."));
+    assert!(view.status_line().ends_with("s: swap"));
+
+    // X 자리는 스왑할 수 없음
+    let x_view = GameState { your_symbol: "X".into(), ..state.clone() };
+    assert_eq!(swap_hint(&x_view), None);
+
+    // 스왑하면 돌과 기보의 심볼이 바뀌고 X의 차례
+    state.board[4] = "O".into();
+    state.next_player = "X".into();
+    state.swap_available = false;
+    view.apply(&state);
+    assert_eq!(view.history_lines(), ["1. O\u{2192}4"]);
+    assert_eq!(swap_hint(&state), None);
+}
+
+#[test]
+fn typed_cell_takes_precedence_over_the_cursor() {
+    let mut view = ViewState::new(false);
+    assert_eq!(view.take_target(), Ok(4));
+    view.push_digit('7');
+    view.push_digit('1');
+    assert_eq!(view.input, "7");
+    assert_eq!(view.take_target(), Ok(7));
+    assert_eq!((view.cursor, view.input.as_str()), (7, ""));
+
+    view.apply(&ongoing(5, "X"));
+    view.push_digit('3');
+    view.push_digit('0');
+    assert_eq!(view.take_target(), Err("30".to_string()));
+}
+
+#[test]
+fn closed_event_marks_the_session_over() {
+    let mut view = ViewState::new(false);
+    view.handle(UiEvent::Update(Box::new(ongoing(3, "X"))));
+    view.handle(UiEvent::Message("Opponent left.".into()));
+    view.handle(UiEvent::Closed);
+    assert!(view.closed);
+    assert_eq!(view.messages.front().map(String::as_str), Some("Opponent left."));
+}
+
+#[test]
+fn chat_draft_collects_keys_until_enter() {
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    let mut view = ViewState::new(false);
+    assert_eq!(command_for(key(KeyCode::Char('/'))), Some(Command::StartChat));
+    assert_eq!(view.edit_chat(key(KeyCode::Char('h'))), None);
+    assert!(view.chat_draft.is_none());
+
+    view.chat_draft = Some(String::new());
+    for c in "hi 1q".chars() {
+        assert_eq!(view.edit_chat(key(KeyCode::Char(c))), None);
+    }
+    view.edit_chat(key(KeyCode::Backspace));
+    assert_eq!(view.edit_chat(key(KeyCode::Enter)), Some("hi 1".to_string()));
+    assert!(view.chat_draft.is_none());
+
+    view.chat_draft = Some("draft".into());
+    assert_eq!(view.edit_chat(key(KeyCode::Esc)), None);
+    assert!(view.chat_draft.is_none());
+}
+
+#[test]
+fn board_shows_the_chosen_markers() {
+    let mut state = ongoing(3, "O");
+    state.board[0] = "X".into();
+    state.board[4] = "O".into();
+    state.x_symbol = "🦊".into();
+    assert_eq!(client::tui::marker_cells(&state)[..5], ["🦊", "", "", "", "O"]);
+
+    let mut view = ViewState::new(false);
+    view.apply(&state);
+    assert_eq!((view.x_symbol.as_str(), view.o_symbol.as_str()), ("🦊", "O"));
+    // 칸 값은 자리 심볼 그대로 둬서 검사와 기보에는 영향이 없음
+    assert_eq!(view.board[0], "X");
+}
+
+#[test]
+fn scoreboard_is_shown_from_the_players_side() {
+    let finished = GameState {
+        status: "O_win".into(),
+        your_symbol: "O".into(),
+        score_x: 1,
+        score_o: 2,
+        draws: 1,
+        games_played: 4,
+        best_of: 5,
+        ..Default::default()
+    };
+    assert_eq!(scoreboard_line(&finished, false).unwrap(), "Score \u{2014} You 2 : 1 Opponent, 1 draw");
+    assert_eq!(scoreboard_line(&finished, true).unwrap(), "Score \u{2014} X 1 : 2 O, 1 draw");
+    assert!(awaits_rematch(&finished));
+    assert_eq!(match_result_line(&finished, false), None);
+
+    let decided = GameState { score_o: 3, draws: 0, games_played: 4, match_winner: "O".into(), ..finished };
+    assert_eq!(scoreboard_line(&decided, false).unwrap(), "Score \u{2014} You 3 : 1 Opponent, 0 draws");
+    assert_eq!(match_result_line(&decided, false).unwrap(), "You won the best-of-5 match!");
+    assert_eq!(match_result_line(&decided, true).unwrap(), "O wins the best-of-5 match.");
+    assert!(!awaits_rematch(&decided));
+
+    assert_eq!(scoreboard_line(&GameState::default(), false), None);
+}
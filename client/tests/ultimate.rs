@@ -0,0 +1,91 @@
+use client::moves::MoveRejection;
+use client::tictactoe::{GameMode, GameState};
+use client::tui::ViewState;
+use client::ultimate::{board_lines, parse_position, position, sub_board_of, SubBoards};
+
+fn ultimate_state(forced_board: Option<i32>, macro_board: &[&str]) -> GameState {
+    GameState {
+        game_id: "1".into(),
+        board: vec![String::new(); 81],
+        board_size: 9,
+        win_length: 3,
+        status: "ongoing".into(),
+        next_player: "X".into(),
+        your_symbol: "X".into(),
+        mode: GameMode::Ultimate as i32,
+        forced_board,
+        macro_board: macro_board.iter().map(|s| s.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn cells_are_entered_as_board_comma_cell_or_a_number() {
+    assert_eq!(parse_position("0,0"), Some(0));
+    assert_eq!(parse_position("4, 4"), Some(40));
+    assert_eq!(parse_position("8,8"), Some(80));
+    assert_eq!(parse_position("1,0"), Some(3));
+    assert_eq!(parse_position("27"), Some(27));
+    assert_eq!(parse_position("81"), None);
+    assert_eq!(parse_position("9,0"), None);
+    assert_eq!(parse_position("0,"), None);
+    for pos in 0..81 {
+        assert_eq!(position(sub_board_of(pos), pos % 9 % 3 + pos / 9 % 3 * 3), pos);
+    }
+}
+
+#[test]
+fn sub_board_rules_are_checked_before_sending() {
+    let macro_board = ["X", "", "", "", "", "", "", "", "draw"];
+    let forced = SubBoards::of(&ultimate_state(Some(4), &macro_board)).unwrap();
+    assert_eq!(forced.check(position(4, 0)), Ok(()));
+    assert_eq!(forced.check(position(3, 0)), Err(MoveRejection::WrongSubBoard(4)));
+    assert_eq!(MoveRejection::WrongSubBoard(4).to_string(), "You must play in sub-board 4.");
+
+    let free = SubBoards::of(&ultimate_state(None, &macro_board)).unwrap();
+    assert_eq!(free.check(position(3, 0)), Ok(()));
+    assert_eq!(free.check(position(0, 8)), Err(MoveRejection::SubBoardDecided(0)));
+    assert_eq!(free.check(position(8, 0)), Err(MoveRejection::SubBoardDecided(8)));
+    assert!(free.is_active(5) && !free.is_active(0));
+
+    let classic = GameState { mode: GameMode::Classic as i32, ..ultimate_state(None, &[]) };
+    assert_eq!(SubBoards::of(&classic), None);
+}
+
+#[test]
+fn plain_board_marks_open_cells_only_in_the_active_sub_board() {
+    let mut state = ultimate_state(Some(4), &["X", "", "", "", "", "", "", "", ""]);
+    for cell in 0..3 {
+        state.board[position(0, cell)] = "X".into();
+    }
+    let sub_boards = SubBoards::of(&state).unwrap();
+    let lines = board_lines(&state.board, &sub_boards);
+    // 구분선 10줄과 칸 9줄
+    assert_eq!(lines.len(), 19);
+    assert_eq!(lines[0].chars().count(), lines[2].chars().count());
+    // 가운데 줄(행 4)에서는 작은 보드 4의 빈 칸만 '.'
+    assert_eq!(lines[9].matches('.').count(), 3, "{}", lines[9]);
+    assert_eq!(lines[1].matches('.').count(), 0, "{}", lines[1]);
+    assert!(lines[1].starts_with("   X | X | X #"), "{}", lines[1]);
+    // 작은 보드 결과는 각 줄 묶음의 가운데 줄 오른쪽에
+    assert!(lines[3].ends_with("X \u{b7} \u{b7}"), "{}", lines[3]);
+}
+
+#[test]
+fn tui_accepts_board_comma_cell_input() {
+    let mut view = ViewState::new(false);
+    view.apply(&ultimate_state(Some(4), &[""; 9]));
+    assert_eq!(view.sub_boards.as_ref().and_then(|s| s.forced), Some(4));
+    for c in ['4', ',', '2', '7'] {
+        view.push_digit(c);
+    }
+    assert_eq!(view.input, "4,2");
+    assert_eq!(view.take_target(), Ok(position(4, 2)));
+    assert_eq!(view.cursor, position(4, 2));
+    assert!(view.status_line().contains("Sub-board 4"), "{}", view.status_line());
+
+    // 클래식 보드에서는 ','를 받지 않음
+    let mut classic = ViewState::new(false);
+    classic.push_digit(',');
+    assert!(classic.input.is_empty());
+}
@@ -0,0 +1,38 @@
+//! N×N 보드에서 K개 연속 줄(가로/세로/대각선) 판정
+//!
+//! 보드는 행 우선 순서의 칸 목록이며 각 칸은 "", "X", "O" 중 하나입니다. 서버의 게임과 클라이언트의
+//! 로컬 게임(`--local`)이 같은 규칙으로 승패를 가리도록 여기에 둡니다.
+
+/// 줄을 찾는 방향 (행, 열 증가량): 가로, 세로, ↘ 대각선, ↙ 대각선
+const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+/// `win_length`개 이상 연속으로 놓인 심볼이 있으면 그 심볼을 반환
+pub fn winner(board: &[String], size: usize, win_length: usize) -> Option<String> {
+    winning_line(board, size, win_length).map(|line| board[line[0]].clone())
+}
+
+/// 완성된 줄의 칸 번호 (처음 찾은 줄 하나)
+pub fn winning_line(board: &[String], size: usize, win_length: usize) -> Option<Vec<usize>> {
+    for row in 0..size {
+        for col in 0..size {
+            let symbol = &board[row * size + col];
+            if symbol.is_empty() {
+                continue;
+            }
+            for (dr, dc) in DIRECTIONS {
+                let line: Vec<usize> = (0..win_length as isize)
+                    .map_while(|step| {
+                        let (r, c) = (row as isize + dr * step, col as isize + dc * step);
+                        let inside = (0..size as isize).contains(&r) && (0..size as isize).contains(&c);
+                        inside.then(|| r as usize * size + c as usize)
+                    })
+                    .take_while(|&i| board[i] == *symbol)
+                    .collect();
+                if line.len() == win_length {
+                    return Some(line);
+                }
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,33 @@
+//! 파일 저장 유틸리티
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// 파일을 원자적으로 씁니다.
+///
+/// 같은 디렉터리의 임시 파일에 먼저 쓰고 디스크에 반영한 뒤 이름을 바꾸므로, 쓰는 도중
+/// 프로그램이 죽어도 기존 파일이 반쯤 쓰인 상태로 남지 않습니다. 상위 디렉터리가 없으면 만듭니다.
+pub fn atomic_write(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(dir)?;
+
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+    let tmp_path = dir.join(format!(".{}.tmp", file_name.to_string_lossy()));
+
+    let result = (|| {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
@@ -0,0 +1,6 @@
+//! 서버와 클라이언트가 함께 사용하는 코드
+
+pub mod board;
+pub mod fs;
+pub mod state_hash;
+pub mod text;
@@ -0,0 +1,23 @@
+//! 게임 상태 해시 (GameState.state_hash)
+//!
+//! 서버는 보내는 업데이트마다 보드, 상태, 차례의 해시를 담고, 클라이언트는 보드 차이까지 반영한 자기 보드로
+//! 같은 해시를 계산해 비교합니다. 서로 다르면 업데이트를 잘못 반영한 것이므로 서버의 스냅샷으로 다시 맞춥니다.
+//! 빌드마다 달라질 수 있는 표준 라이브러리 해셔 대신 FNV-1a(64비트)를 써서 서버와 클라이언트의 값이 같습니다.
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 보드 칸, 상태, 다음 차례의 해시 (16자리 소문자 16진수)
+///
+/// 칸마다 구분 바이트를 넣어 `["XO", ""]`와 `["X", "O"]`처럼 이어 붙이면 같은 보드도 구분합니다.
+pub fn state_hash(board: &[String], status: &str, next_player: &str) -> String {
+    let mut hash = FNV_OFFSET;
+    let fields = board.iter().map(String::as_str).chain([status, next_player]);
+    for field in fields {
+        for byte in field.bytes().chain([0xff]) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+    format!("{:016x}", hash)
+}
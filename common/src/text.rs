@@ -0,0 +1,85 @@
+//! 사용자 문자열(이름, 채팅, 보드 글리프)을 안전하게 다루는 유틸리티.
+//!
+//! 바이트 길이나 `char` 개수 대신 그래핌 클러스터와 터미널 표시 폭을 기준으로 하므로
+//! 이모지, 전각 문자, 결합 문자가 섞여 있어도 문자열을 쪼개거나 정렬이 깨지지 않습니다.
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// 양방향 텍스트 격리 시작 (FIRST STRONG ISOLATE)
+const FSI: char = '\u{2068}';
+/// 양방향 텍스트 격리 끝 (POP DIRECTIONAL ISOLATE)
+const PDI: char = '\u{2069}';
+
+/// 플레이어 표시 이름의 최대 글자 수
+pub const MAX_PLAYER_NAME_LEN: usize = 20;
+
+/// 플레이어 표시 이름 정리: 앞뒤 공백을 떼고 [`MAX_PLAYER_NAME_LEN`]자까지 자릅니다.
+/// 비어 있으면 None이고, ASCII 출력 가능 문자(공백 포함)가 아닌 글자가 있으면 그 글자를 Err로 반환합니다.
+pub fn player_name(name: &str) -> Result<Option<String>, char> {
+    let name = name.trim();
+    if let Some(invalid) = name.chars().find(|c| !(' '..='~').contains(c)) {
+        return Err(invalid);
+    }
+    let name = name[..name.len().min(MAX_PLAYER_NAME_LEN)].trim_end();
+    Ok((!name.is_empty()).then(|| name.to_string()))
+}
+
+/// 그래핌 하나가 터미널에서 차지하는 칸 수
+fn grapheme_width(grapheme: &str) -> usize {
+    // ZWJ 이모지 시퀀스는 unicode-width가 한 글자(2칸)로 계산함. 2칸으로 잘라 내면 앞 글자에 붙은
+    // 결합 문자(예: 공백 뒤의 피부색 수식자)의 폭을 잃으므로 그대로 씀
+    grapheme.width()
+}
+
+/// 문자열이 터미널에서 차지하는 칸 수
+pub fn display_width(s: &str) -> usize {
+    s.graphemes(true).map(grapheme_width).sum()
+}
+
+/// 그래핌 클러스터 개수
+pub fn grapheme_count(s: &str) -> usize {
+    s.graphemes(true).count()
+}
+
+/// 최대 `max_graphemes`개의 그래핌까지만 남기고 자릅니다. 그래핌 중간에서 자르지 않습니다.
+pub fn truncate_graphemes(s: &str, max_graphemes: usize) -> &str {
+    match s.grapheme_indices(true).nth(max_graphemes) {
+        Some((end, _)) => &s[..end],
+        None => s,
+    }
+}
+
+/// 표시 폭이 `max_width`칸을 넘지 않도록 그래핌 단위로 자릅니다.
+pub fn truncate_width(s: &str, max_width: usize) -> &str {
+    let mut width = 0;
+    for (idx, grapheme) in s.grapheme_indices(true) {
+        width += grapheme_width(grapheme);
+        if width > max_width {
+            return &s[..idx];
+        }
+    }
+    s
+}
+
+/// 표시 폭이 정확히 `width`칸이 되도록 자르거나 오른쪽을 공백으로 채웁니다.
+pub fn pad_to_width(s: &str, width: usize) -> String {
+    let truncated = truncate_width(s, width);
+    let padding = width - display_width(truncated);
+    format!("{}{}", truncated, " ".repeat(padding))
+}
+
+/// 표시 폭이 정확히 `width`칸이 되도록 가운데 정렬합니다.
+pub fn center_to_width(s: &str, width: usize) -> String {
+    let truncated = truncate_width(s, width);
+    let padding = width - display_width(truncated);
+    let left = padding / 2;
+    format!("{}{}{}", " ".repeat(left), truncated, " ".repeat(padding - left))
+}
+
+/// 로그나 채팅 줄에 사용자 문자열을 넣을 때 양방향 텍스트(RTL)가 주변 텍스트를
+/// 뒤집지 않도록 격리합니다. 입력에 섞인 격리 문자는 먼저 제거합니다.
+pub fn isolate_bidi(s: &str) -> String {
+    let inner: String = s.chars().filter(|&c| c != FSI && c != PDI).collect();
+    format!("{}{}{}", FSI, inner, PDI)
+}
@@ -0,0 +1,126 @@
+//! 임의의 유니코드 문자열을 자르기/정렬 함수에 넣어 보는 속성 테스트
+//!
+//! 시드를 고정한 난수로 문자열을 만들므로 실패하면 같은 입력으로 다시 재현됩니다.
+
+use common::text::{self, MAX_PLAYER_NAME_LEN};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// 속성마다 시험할 문자열 수
+const CASES: usize = 2000;
+
+/// 그래핌 경계와 표시 폭을 틀리기 쉬운 문자들
+const TRICKY: &[char] = &[
+    'a', 'Z', ' ', '~', '\n', '\t', '\r', '\u{0}', '\u{7f}',
+    '\u{301}', '\u{308}', '\u{200d}', '\u{fe0f}', '\u{200b}', '\u{ad}',
+    '👨', '👩', '👧', '🏳', '⚧', '\u{1f3fd}', '🇰', '🇷',
+    '가', '漢', 'Ａ', 'ᄀ', 'ᅡ', 'ᆨ', 'क', '\u{94d}', 'ष',
+    'א', 'ب', '\u{202e}', '\u{2068}', '\u{2069}', '\u{200f}',
+];
+
+/// 까다로운 문자와 아무 유니코드 스칼라 값을 섞은 0~24자 문자열
+fn arbitrary(rng: &mut StdRng) -> String {
+    let len = rng.gen_range(0..=24);
+    (0..len)
+        .map(|_| if rng.gen_bool(0.7) { TRICKY[rng.gen_range(0..TRICKY.len())] } else { rng.gen::<char>() })
+        .collect()
+}
+
+/// 시드를 고정한 난수로 만든 문자열마다 `check`를 실행
+fn for_all(check: impl Fn(&str, &mut StdRng)) {
+    let mut rng = StdRng::seed_from_u64(0x7e77);
+    for _ in 0..CASES {
+        let s = arbitrary(&mut rng);
+        check(&s, &mut rng);
+    }
+}
+
+/// `prefix`가 `s`의 앞쪽 그래핌들을 그대로 이어 붙인 것인지 (그래핌 중간에서 잘리지 않았는지)
+fn is_grapheme_prefix(prefix: &str, s: &str) -> bool {
+    let kept: Vec<&str> = prefix.graphemes(true).collect();
+    s.starts_with(prefix) && s.graphemes(true).take(kept.len()).eq(kept)
+}
+
+#[test]
+fn display_width_is_the_sum_of_grapheme_widths() {
+    for_all(|s, _| {
+        let width = text::display_width(s);
+        let split: usize = s.graphemes(true).map(text::display_width).sum();
+        assert_eq!(width, split, "{:?}", s);
+    });
+}
+
+#[test]
+fn display_width_counts_terminal_columns() {
+    assert_eq!(text::display_width("abc"), 3);
+    assert_eq!(text::display_width("가漢Ａ"), 6);
+    assert_eq!(text::display_width("e\u{301}"), 1);
+    assert_eq!(text::display_width("👨\u{200d}👩\u{200d}👧"), 2);
+    assert_eq!(text::display_width("🇰🇷"), 2);
+    // 공백에 붙은 피부색 수식자도 따로 표시되는 폭만큼 셈
+    assert_eq!(text::display_width(" 🏽"), 3);
+}
+
+#[test]
+fn truncate_graphemes_keeps_whole_graphemes() {
+    for_all(|s, rng| {
+        let max = rng.gen_range(0..=30);
+        let truncated = text::truncate_graphemes(s, max);
+        assert!(is_grapheme_prefix(truncated, s), "{:?} -> {:?}", s, truncated);
+        assert_eq!(text::grapheme_count(truncated), text::grapheme_count(s).min(max), "{:?}", s);
+    });
+}
+
+#[test]
+fn truncate_width_keeps_as_many_whole_graphemes_as_fit() {
+    for_all(|s, rng| {
+        let max = rng.gen_range(0..=30);
+        let truncated = text::truncate_width(s, max);
+        assert!(is_grapheme_prefix(truncated, s), "{:?} -> {:?}", s, truncated);
+        assert!(text::display_width(truncated) <= max, "{:?} -> {:?}", s, truncated);
+        // 다음 그래핌을 붙이면 폭을 넘어야 함
+        if let Some(next) = s[truncated.len()..].graphemes(true).next() {
+            assert!(text::display_width(truncated) + text::display_width(next) > max, "{:?} -> {:?}", s, truncated);
+        }
+    });
+}
+
+#[test]
+fn padded_and_centered_cells_line_up() {
+    for_all(|s, rng| {
+        let width = rng.gen_range(0..=12);
+        let padded = text::pad_to_width(s, width);
+        assert_eq!(text::display_width(&padded), width, "{:?}", s);
+        assert!(padded.starts_with(text::truncate_width(s, width)), "{:?}", s);
+        let centered = text::center_to_width(s, width);
+        assert_eq!(text::display_width(&centered), width, "{:?}", s);
+        assert!(centered.contains(text::truncate_width(s, width)), "{:?}", s);
+    });
+}
+
+#[test]
+fn isolate_bidi_wraps_exactly_one_isolate() {
+    for_all(|s, _| {
+        let isolated = text::isolate_bidi(s);
+        let inner = isolated.strip_prefix('\u{2068}').and_then(|rest| rest.strip_suffix('\u{2069}'));
+        let inner = inner.unwrap_or_else(|| panic!("{:?} -> {:?}", s, isolated));
+        assert!(!inner.contains(['\u{2068}', '\u{2069}']), "{:?}", s);
+        let expected: String = s.chars().filter(|c| !['\u{2068}', '\u{2069}'].contains(c)).collect();
+        assert_eq!(inner, expected);
+    });
+}
+
+#[test]
+fn player_name_is_trimmed_printable_ascii_within_the_limit() {
+    for_all(|s, _| match text::player_name(s) {
+        Ok(Some(name)) => {
+            assert!(!name.is_empty() && name.len() <= MAX_PLAYER_NAME_LEN, "{:?} -> {:?}", s, name);
+            assert_eq!(name.trim(), name);
+            assert!(name.chars().all(|c| (' '..='~').contains(&c)), "{:?} -> {:?}", s, name);
+            assert!(s.trim().starts_with(name.as_str()), "{:?} -> {:?}", s, name);
+        }
+        Ok(None) => assert!(s.trim().is_empty(), "{:?}", s),
+        Err(invalid) => assert!(s.trim().contains(invalid) && !(' '..='~').contains(&invalid), "{:?}", s),
+    });
+}
@@ -0,0 +1,66 @@
+//! 보드 차이 전송으로 줄어드는 바이트 수와 인코딩 비용
+//!
+//! 같은 수순의 3x3 게임을 되풀이하며 X가 받는 업데이트 1000개를 모아, 보드 전체로 보낼 때와 보드 차이로
+//! 보낼 때를 비교합니다. 실행: `cargo bench -p server --bench board_diff`
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use prost::Message;
+use server::game::SharedGame;
+use server::tictactoe::{GameState, Join};
+use tokio::sync::mpsc;
+
+/// 모을 업데이트 수
+const UPDATES: usize = 1000;
+/// 한 판의 수순 (X가 2, 4, 6 대각선으로 이김)
+const MOVES: [usize; 7] = [0, 1, 2, 3, 4, 5, 6];
+
+/// 게임을 되풀이하며 X가 받은 업데이트 `UPDATES`개
+fn updates(board_diffs: bool) -> Vec<GameState> {
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    runtime.block_on(async {
+        let mut updates = Vec::with_capacity(UPDATES);
+        let mut game_id = 0;
+        while updates.len() < UPDATES {
+            game_id += 1;
+            let mut game = SharedGame::new(game_id.to_string(), 3, 3);
+            let (x_tx, mut x_rx) = mpsc::channel(64);
+            let (o_tx, _o_rx) = mpsc::channel(64);
+            game.join_player(&Join { board_diffs, ..Join::default() }, x_tx, None).await.unwrap();
+            game.join_player(&Join::default(), o_tx, None).await.unwrap();
+            for (i, position) in MOVES.into_iter().enumerate() {
+                game.apply_move(if i % 2 == 0 { "X" } else { "O" }, position).unwrap();
+                game.broadcast_update().await;
+            }
+            while let Ok(Ok(update)) = x_rx.try_recv() {
+                updates.push(update);
+            }
+        }
+        updates.truncate(UPDATES);
+        updates
+    })
+}
+
+fn encoded_bytes(updates: &[GameState]) -> usize {
+    updates.iter().map(|update| update.encode_to_vec().len()).sum()
+}
+
+fn bench(c: &mut Criterion) {
+    let full = updates(false);
+    let diffs = updates(true);
+    let (full_bytes, diff_bytes) = (encoded_bytes(&full), encoded_bytes(&diffs));
+    println!(
+        "{} updates: {} bytes with full boards, {} bytes with diffs ({:.1}% smaller)",
+        UPDATES,
+        full_bytes,
+        diff_bytes,
+        100.0 * (1.0 - diff_bytes as f64 / full_bytes as f64)
+    );
+
+    let mut group = c.benchmark_group("encode 1000 game states");
+    group.bench_function("full board", |b| b.iter(|| encoded_bytes(black_box(&full))));
+    group.bench_function("board diff", |b| b.iter(|| encoded_bytes(black_box(&diffs))));
+    group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
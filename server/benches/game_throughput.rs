@@ -0,0 +1,174 @@
+//! 게임 처리량 기준선 (초당 게임 수)
+//!
+//! 보드를 가득 채워 무승부로 끝나는 한 판을 세 단계로 잽니다.
+//! 1. `SharedGame::apply_move`와 `check_winner`만 (비동기 없이)
+//! 2. 한 판 전체: 수마다 프로세스 안의 채널로 두 플레이어에게 업데이트 전송 (네트워크 없음)
+//! 3. 여러 판을 Tokio 태스크로 동시에: 서버처럼 게임 매니저 잠금으로 게임을 만들고 게임 액터에 수를 보냄
+//!
+//! 보드 크기(3, 5, 9)와 동시에 두는 게임 수(1, 10, 100)마다 재며, criterion의 처리량(elem/s)이 초당 게임 수입니다.
+//! `bench` 기능을 켜야 빌드됩니다: `cargo bench -p server --features bench --bench game_throughput`
+
+use std::sync::Arc;
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use server::game::{SharedGame, UpdateSender};
+use server::manager::GameManager;
+use server::metrics::Metrics;
+use server::presets::{GameOptions, DEFAULT_PRESET};
+use server::tictactoe::{GameState, Join};
+use tokio::sync::{mpsc, Mutex};
+use tonic::Status;
+
+/// 잴 보드 크기 (승리 줄 길이는 모두 3)
+const BOARD_SIZES: [usize; 3] = [3, 5, 9];
+/// 동시에 두는 게임 수
+const CONCURRENT_GAMES: [usize; 3] = [1, 10, 100];
+const WIN_LENGTH: usize = 3;
+
+/// 플레이어가 업데이트를 받는 쪽 채널
+type UpdateReceiver = mpsc::Receiver<Result<GameState, Status>>;
+
+/// 가득 채워도 어느 쪽도 세 칸을 잇지 못하는 보드를 만드는 수순 (X부터 번갈아 둠)
+///
+/// 짝수 행은 두 칸씩 X X O O ..., 홀수 행은 O O X X ... 로 채우면 가로, 세로, 대각선 어디에도 같은 심볼이
+/// 세 칸 이어지지 않습니다. 마지막 보드에 줄이 없으므로 중간에도 승부가 나지 않습니다.
+fn draw_moves(size: usize) -> Vec<usize> {
+    let (x, o): (Vec<usize>, Vec<usize>) = (0..size * size).partition(|pos| (pos / size).is_multiple_of(2) == (pos % size / 2).is_multiple_of(2));
+    // X가 먼저 두므로 홀수 크기에서는 X 칸이 하나 더 많음
+    assert_eq!(x.len(), o.len() + size * size % 2);
+    let mut moves = Vec::with_capacity(size * size);
+    let mut o = o.into_iter();
+    for pos in x {
+        moves.push(pos);
+        moves.extend(o.next());
+    }
+    moves
+}
+
+/// 업데이트를 받을 채널 (한 판의 업데이트가 모두 들어가도록 넉넉하게)
+fn channel(size: usize) -> (UpdateSender, UpdateReceiver) {
+    mpsc::channel(size * size + 8)
+}
+
+/// 두 자리가 모두 찬 게임 (플레이어의 받는 쪽 채널도 함께 돌려줌)
+async fn seated_game(game_id: &str, size: usize) -> (SharedGame, [UpdateReceiver; 2]) {
+    let mut game = SharedGame::new(game_id.to_string(), size, WIN_LENGTH);
+    let ((x_tx, x_rx), (o_tx, o_rx)) = (channel(size), channel(size));
+    game.join_player(&Join::default(), x_tx, None).await.unwrap();
+    game.join_player(&Join::default(), o_tx, None).await.unwrap();
+    (game, [x_rx, o_rx])
+}
+
+/// 수순을 모두 두고 수마다 두 플레이어에게 업데이트를 보냄
+async fn play_with_broadcast(game: &mut SharedGame, moves: &[usize]) {
+    for &pos in moves {
+        let symbol = game.next_player.clone();
+        game.apply_move(&symbol, pos).unwrap();
+        game.broadcast_update().await;
+    }
+    assert_eq!(game.status, "draw");
+}
+
+/// 서버처럼 매니저 잠금으로 게임을 만들고, 플레이어를 앉힌 뒤 수마다 게임 액터에 작업을 보내 한 판을 둠
+async fn play_through_actor(manager: Arc<Mutex<GameManager>>, size: usize, moves: Arc<Vec<usize>>) {
+    let handle = manager
+        .lock()
+        .await
+        .create_preset_game(DEFAULT_PRESET, GameOptions::default(), size, WIN_LENGTH)
+        .expect("최대 게임 수 초과");
+    let (x_rx, o_rx) = handle
+        .run(move |game| {
+            Box::pin(async move {
+                let ((x_tx, x_rx), (o_tx, o_rx)) = (channel(size), channel(size));
+                game.join_player(&Join::default(), x_tx, None).await.unwrap();
+                game.join_player(&Join::default(), o_tx, None).await.unwrap();
+                (x_rx, o_rx)
+            })
+        })
+        .await;
+    for &pos in moves.iter() {
+        handle
+            .run(move |game| {
+                Box::pin(async move {
+                    let symbol = game.next_player.clone();
+                    game.apply_move(&symbol, pos).unwrap();
+                    game.broadcast_update().await;
+                })
+            })
+            .await;
+    }
+    manager.lock().await.remove(handle.game_id());
+    drop((x_rx, o_rx));
+}
+
+fn apply_and_check(c: &mut Criterion) {
+    let setup_runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let mut group = c.benchmark_group("apply_move + check_winner");
+    group.throughput(Throughput::Elements(1));
+    for size in BOARD_SIZES {
+        let moves = draw_moves(size);
+        group.bench_with_input(BenchmarkId::new("board", size), &moves, |b, moves| {
+            b.iter_batched(
+                || setup_runtime.block_on(seated_game("1", size)),
+                |(mut game, receivers)| {
+                    for &pos in moves {
+                        let symbol = game.next_player.clone();
+                        black_box(game.apply_move(&symbol, pos).unwrap());
+                        black_box(game.check_winner());
+                    }
+                    (game, receivers)
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn full_game(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let mut group = c.benchmark_group("full game with broadcast");
+    group.throughput(Throughput::Elements(1));
+    for size in BOARD_SIZES {
+        let moves = draw_moves(size);
+        group.bench_with_input(BenchmarkId::new("board", size), &moves, |b, moves| {
+            b.iter(|| {
+                runtime.block_on(async {
+                    let (mut game, receivers) = seated_game("1", size).await;
+                    play_with_broadcast(&mut game, moves).await;
+                    (game, receivers)
+                })
+            })
+        });
+    }
+    group.finish();
+}
+
+fn concurrent_games(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap();
+    let mut group = c.benchmark_group("concurrent games");
+    for size in BOARD_SIZES {
+        let moves = Arc::new(draw_moves(size));
+        for games in CONCURRENT_GAMES {
+            group.throughput(Throughput::Elements(games as u64));
+            let id = BenchmarkId::new(format!("board {}", size), format!("{} games", games));
+            group.bench_with_input(id, &games, |b, &games| {
+                b.iter(|| {
+                    runtime.block_on(async {
+                        let manager = Arc::new(Mutex::new(GameManager::new(games, Arc::new(Metrics::default()))));
+                        let tasks: Vec<_> = (0..games)
+                            .map(|_| tokio::spawn(play_through_actor(manager.clone(), size, moves.clone())))
+                            .collect();
+                        for task in tasks {
+                            task.await.unwrap();
+                        }
+                    })
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, apply_and_check, full_game, concurrent_games);
+criterion_main!(benches);
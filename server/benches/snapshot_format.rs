@@ -0,0 +1,96 @@
+//! 스냅샷 형식 비교 (JSON과 bincode)
+//!
+//! 끝난 게임 1000판의 스냅샷을 형식마다 모두 직렬화하고, 직렬화한 바이트를 다시 모두 역직렬화하는 시간을 잽니다.
+//! 시작할 때 형식마다 1000판의 전체 크기도 출력합니다.
+//! `bench` 기능을 켜야 빌드됩니다: `cargo bench -p server --features bench --bench snapshot_format`
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use server::serialization::{BincodeSerializer, JsonSerializer, Serializer};
+use server::snapshot::{GameSnapshot, SnapshotMove, SnapshotPlayer, SnapshotScore};
+
+/// 잴 게임 수
+const GAMES: usize = 1000;
+
+/// 게임마다 보드 크기와 수 수가 다른 끝난 게임 스냅샷
+fn snapshots() -> Vec<GameSnapshot> {
+    (0..GAMES)
+        .map(|i| {
+            let board_size = [3, 5, 9][i % 3];
+            let cells = board_size * board_size;
+            let moves: Vec<SnapshotMove> = (0..5 + i % (cells - 4))
+                .map(|n| {
+                    let symbol = if n % 2 == 0 { "X" } else { "O" };
+                    SnapshotMove { symbol: symbol.into(), position: ((n * 7 + i) % cells) as u8, played_at_unix: 1_700_000_000 + n as i64, mark: symbol.into() }
+                })
+                .collect();
+            let mut board = vec![String::new(); cells];
+            for m in &moves {
+                board[m.position as usize] = m.mark.clone();
+            }
+            let player = |symbol: &str, player_id: String, bot: bool| SnapshotPlayer {
+                symbol: symbol.into(),
+                player_id,
+                bot,
+                marker: symbol.into(),
+                rating: (!bot).then_some(1200 + i as i32),
+            };
+            GameSnapshot {
+                game_id: i.to_string(),
+                created_at_unix: 1_700_000_000,
+                ended_at_unix: 1_700_000_000 + moves.len() as i64,
+                board_size,
+                win_length: 3,
+                first_player: "X".into(),
+                preset: "classic".into(),
+                rated: i % 2 == 0,
+                board,
+                players: vec![player("X", format!("player-{}", i), false), player("O", String::new(), i % 4 == 0)],
+                moves,
+                outcome: if i % 5 == 0 { "draw".into() } else { "X_win".into() },
+                best_of: 1,
+                score: SnapshotScore { x_wins: 1, o_wins: 0, draws: 0 },
+            }
+        })
+        .collect()
+}
+
+fn formats() -> [(&'static str, &'static dyn Serializer); 2] {
+    [("json", &JsonSerializer), ("bincode", &BincodeSerializer)]
+}
+
+fn serialize(c: &mut Criterion) {
+    let games = snapshots();
+    for (name, serializer) in formats() {
+        let bytes: usize = games.iter().map(|game| serializer.serialize(game).len()).sum();
+        println!("{}: {}판 {} bytes (한 판 평균 {} bytes)", name, GAMES, bytes, bytes / GAMES);
+    }
+
+    let mut group = c.benchmark_group("snapshot serialize");
+    group.throughput(Throughput::Elements(GAMES as u64));
+    for (name, serializer) in formats() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &games, |b, games| {
+            b.iter(|| games.iter().map(|game| black_box(serializer.serialize(game)).len()).sum::<usize>())
+        });
+    }
+    group.finish();
+}
+
+fn deserialize(c: &mut Criterion) {
+    let games = snapshots();
+    let mut group = c.benchmark_group("snapshot deserialize");
+    group.throughput(Throughput::Elements(GAMES as u64));
+    for (name, serializer) in formats() {
+        let encoded: Vec<Vec<u8>> = games.iter().map(|game| serializer.serialize(game)).collect();
+        group.bench_with_input(BenchmarkId::from_parameter(name), &encoded, |b, encoded| {
+            b.iter(|| {
+                for bytes in encoded {
+                    black_box(serializer.deserialize(bytes).unwrap());
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, serialize, deserialize);
+criterion_main!(benches);
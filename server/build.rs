@@ -1,4 +1,35 @@
+use std::path::PathBuf;
+use std::process::Command;
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    tonic_build::compile_protos("../proto/tictactoe.proto")?;
+    // 서버 리플렉션이 돌려줄 파일 디스크립터 세트도 함께 생성
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR")?);
+    tonic_build::configure()
+        // HTTP JSON API(api_http)가 proto 메시지를 그대로 JSON으로 주고받음 (빠진 필드는 기본값)
+        .type_attribute(".tictactoe", "#[derive(serde::Serialize, serde::Deserialize)]")
+        .message_attribute(".tictactoe", "#[serde(default)]")
+        .file_descriptor_set_path(out_dir.join("tictactoe_descriptor.bin"))
+        .compile_protos(&["../proto/tictactoe.proto"], &["../proto"])?;
+    generate_typescript()?;
     Ok(())
-}
\ No newline at end of file
+}
+
+/// TICTACTOE_TS_OUT에 디렉터리를 주면 브라우저 gRPC-Web 클라이언트용 TypeScript 정의도 생성
+/// (protoc-gen-ts 플러그인이 PATH에 있어야 함, 예: `npm install -g protoc-gen-ts`)
+fn generate_typescript() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-env-changed=TICTACTOE_TS_OUT");
+    let Some(ts_out) = std::env::var_os("TICTACTOE_TS_OUT") else {
+        return Ok(());
+    };
+    std::fs::create_dir_all(&ts_out)?;
+    let protoc = std::env::var_os("PROTOC").unwrap_or_else(|| "protoc".into());
+    let status = Command::new(protoc)
+        .arg("--proto_path=../proto")
+        .arg(format!("--ts_out={}", PathBuf::from(ts_out).display()))
+        .arg("../proto/tictactoe.proto")
+        .status()?;
+    if !status.success() {
+        return Err(format!("protoc --ts_out 실패: {}", status).into());
+    }
+    Ok(())
+}
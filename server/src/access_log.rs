@@ -0,0 +1,228 @@
+//! gRPC 접근 로그 (`--no-access-log`로 끔)
+//!
+//! 서버 전체를 감싸는 tower 레이어라 RPC를 새로 추가해도 핸들러마다 코드를 넣을 필요가 없습니다. RPC마다 응답이
+//! 끝날 때 상대 주소, 메서드, gRPC 상태 코드, 걸린 시간을 한 줄 남기고, 스트리밍 RPC는 스트림이 열릴 때도 한 줄
+//! 남기며 끝날 때 보낸 데이터 프레임 수를 함께 남깁니다. 클라이언트가 끝까지 받지 않고 끊으면 상태는 "cancelled"입니다.
+//! 이벤트는 `access_log` 타깃으로 내보내므로 `RUST_LOG=info,access_log=off`처럼 따로 거를 수 있습니다.
+
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use http_body::Frame;
+use prost::Message;
+use prost_types::FileDescriptorSet;
+use tonic::codegen::http::{HeaderMap, Request, Response};
+use tonic::codegen::{Body, BoxFuture, Service};
+use tonic::transport::server::{TcpConnectInfo, TlsConnectInfo};
+use tonic::Code;
+use tower_layer::Layer;
+use tracing::info;
+
+/// 접근 로그 이벤트의 tracing 타깃
+pub const ACCESS_LOG_TARGET: &str = "access_log";
+
+/// 접근 로그 레이어 (`Server::builder().layer(...)`)
+#[derive(Clone)]
+pub struct AccessLogLayer {
+    /// 스트리밍 RPC 경로 ("/tictactoe.TicTacToe/Play" 등, None이면 로그를 남기지 않음)
+    streaming: Option<Arc<HashSet<String>>>,
+}
+
+impl AccessLogLayer {
+    /// tictactoe.proto의 스트리밍 RPC를 구분하는 접근 로그 레이어
+    pub fn new() -> Self {
+        Self::with_descriptors(&[crate::tictactoe::FILE_DESCRIPTOR_SET])
+    }
+
+    /// 파일 디스크립터 세트에서 클라이언트나 서버 스트리밍 메서드를 찾아 둠 (읽지 못한 세트는 건너뜀)
+    pub fn with_descriptors(sets: &[&[u8]]) -> Self {
+        let mut streaming = HashSet::new();
+        for set in sets.iter().filter_map(|bytes| FileDescriptorSet::decode(*bytes).ok()) {
+            for file in &set.file {
+                for service in &file.service {
+                    for method in service.method.iter().filter(|m| m.client_streaming() || m.server_streaming()) {
+                        streaming.insert(format!("/{}.{}/{}", file.package(), service.name(), method.name()));
+                    }
+                }
+            }
+        }
+        AccessLogLayer { streaming: Some(Arc::new(streaming)) }
+    }
+
+    /// 아무 로그도 남기지 않는 레이어 (`--no-access-log`)
+    pub fn disabled() -> Self {
+        AccessLogLayer { streaming: None }
+    }
+}
+
+impl Default for AccessLogLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for AccessLogLayer {
+    type Service = AccessLog<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AccessLog { inner, streaming: self.streaming.clone() }
+    }
+}
+
+/// 요청마다 접근 로그를 남기는 서비스
+#[derive(Clone)]
+pub struct AccessLog<S> {
+    inner: S,
+    streaming: Option<Arc<HashSet<String>>>,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for AccessLog<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<LoggedBody<ResBody>>;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let call = self.streaming.as_ref().map(|streaming| {
+            let method = request.uri().path().to_string();
+            CallLog {
+                peer: peer_addr(&request),
+                streaming: streaming.contains(&method),
+                method,
+                started: Instant::now(),
+                frames: 0,
+                status: None,
+            }
+        });
+        let future = self.inner.call(request);
+        Box::pin(async move {
+            let response = match future.await {
+                Ok(response) => response,
+                Err(error) => {
+                    if let Some(call) = call {
+                        call.finish("transport_error");
+                    }
+                    return Err(error);
+                }
+            };
+            let log = call.map(|mut call| {
+                if call.streaming {
+                    info!(target: ACCESS_LOG_TARGET, peer = %call.peer_label(), method = %call.method, "스트림 시작");
+                }
+                // 본문 없이 헤더에 상태를 담은 응답 (핸들러가 바로 거부한 경우 등)
+                call.status = grpc_status(response.headers());
+                call
+            });
+            Ok(response.map(|inner| LoggedBody { inner, log }))
+        })
+    }
+}
+
+/// 요청한 쪽 주소 (평문이면 TcpConnectInfo, TLS면 그 안의 TcpConnectInfo)
+fn peer_addr<B>(request: &Request<B>) -> Option<SocketAddr> {
+    let extensions = request.extensions();
+    extensions
+        .get::<TcpConnectInfo>()
+        .and_then(TcpConnectInfo::remote_addr)
+        .or_else(|| extensions.get::<TlsConnectInfo<TcpConnectInfo>>().and_then(|info| info.get_ref().remote_addr()))
+}
+
+/// 헤더나 트레일러의 grpc-status
+fn grpc_status(headers: &HeaderMap) -> Option<Code> {
+    headers.get("grpc-status").map(|value| Code::from_bytes(value.as_bytes()))
+}
+
+/// 진행 중인 RPC 하나의 기록 (본문이 끝나거나 버려질 때 한 줄 남김)
+struct CallLog {
+    peer: Option<SocketAddr>,
+    method: String,
+    streaming: bool,
+    started: Instant,
+    frames: u64,
+    status: Option<Code>,
+}
+
+impl CallLog {
+    fn peer_label(&self) -> String {
+        self.peer.map_or_else(|| "-".to_string(), |peer| peer.to_string())
+    }
+
+    fn finish(self, status: &str) {
+        let duration_ms = self.started.elapsed().as_secs_f64() * 1000.0;
+        if self.streaming {
+            info!(
+                target: ACCESS_LOG_TARGET,
+                peer = %self.peer_label(), method = %self.method, status = %status, duration_ms, frames = self.frames, "스트림 끝"
+            );
+        } else {
+            info!(target: ACCESS_LOG_TARGET, peer = %self.peer_label(), method = %self.method, status = %status, duration_ms, "RPC");
+        }
+    }
+}
+
+/// 응답 본문을 그대로 전달하며 데이터 프레임 수와 트레일러의 상태를 세는 본문
+pub struct LoggedBody<B> {
+    inner: B,
+    log: Option<CallLog>,
+}
+
+impl<B> LoggedBody<B> {
+    fn finish(&mut self, fallback: &str) {
+        if let Some(call) = self.log.take() {
+            let status = call.status.map(|code| format!("{:?}", code));
+            call.finish(status.as_deref().unwrap_or(fallback));
+        }
+    }
+}
+
+impl<B: Body + Unpin> Body for LoggedBody<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let polled = Pin::new(&mut self.inner).poll_frame(cx);
+        match &polled {
+            Poll::Ready(Some(Ok(frame))) => {
+                if let Some(call) = self.log.as_mut() {
+                    if frame.is_data() {
+                        call.frames += 1;
+                    }
+                    if let Some(status) = frame.trailers_ref().and_then(grpc_status) {
+                        call.status = Some(status);
+                    }
+                }
+            }
+            Poll::Ready(Some(Err(_))) => self.finish("body_error"),
+            // 트레일러가 없으면 정상 종료로 봄
+            Poll::Ready(None) => self.finish("Ok"),
+            Poll::Pending => {}
+        }
+        polled
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl<B> Drop for LoggedBody<B> {
+    fn drop(&mut self) {
+        // 상태를 받기 전에 버려짐: 클라이언트가 먼저 끊음
+        self.finish("cancelled");
+    }
+}
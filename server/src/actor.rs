@@ -0,0 +1,291 @@
+//! 게임마다 하나씩 도는 액터 태스크
+//!
+//! 게임 상태([`SharedGame`])는 그 게임의 액터 태스크만 가지고 있습니다. 플레이어 스트림, 타이머, 매니저,
+//! 관리자 RPC는 [`GameHandle`]로 [`GameCommand`]를 보내고, 액터는 받은 순서대로 하나씩 끝까지 처리합니다.
+//! 게임 잠금이 없으므로 잠금을 쥔 채 기다리거나 잠금 순서를 따질 일이 없고, 명령 순서가 곧 처리 순서라
+//! 명령 열을 넣어 결정적으로 검사할 수 있습니다.
+//!
+//! 참가, 수, 기권, 차례 시간 초과, 접속 끊김, 상태 조회는 정해진 명령이고, 게임 밖의 일(통계, 기록, 타이머)은
+//! 명령과 함께 보낸 [`GameHooks`]로 알립니다. 그 밖의 드문 처리(채팅, 관리자 명령, 무승부 제안 등)만
+//! [`GameCommand::Run`]으로 작업을 보냅니다.
+//!
+//! 명령 처리 중 패닉이 나면 게임 상태가 반쯤 바뀌었을 수 있으므로 모든 스트림을 INTERNAL_ERROR로 끝내고
+//! 게임을 초기화한 뒤 다음 명령을 받습니다.
+//!
+//! 액터는 게임 밖의 상태(매니저, 토너먼트, 통계)를 잠그지 않습니다. 그래서 매니저를 잠근 채 게임에 명령을
+//! 보내고 기다려도 되지만, 명령 안에서 같은 게임에 다시 명령을 보내고 기다리면 안 됩니다 (끝나지 않음).
+
+use std::future;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use tokio::sync::{mpsc, oneshot};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, Instrument, Span};
+
+use crate::error::{GameError, MoveError};
+use crate::game::{Departure, MoveOutcome, SharedGame, UpdateSender};
+use crate::tictactoe::{EndReason, GameState, Join, Move};
+
+/// 액터의 명령 대기열 크기 (보내는 쪽은 대부분 응답을 기다리므로 기다리는 호출 수만큼만 쌓임)
+const COMMAND_QUEUE: usize = 64;
+
+/// 게임 상태를 받아 처리하는 작업 ([`GameCommand::Run`])
+pub type Job = Box<dyn for<'a> FnOnce(&'a mut SharedGame) -> BoxFuture<'a, ()> + Send>;
+
+/// 명령을 처리하며 게임 밖에 알릴 일. 서비스는 통계, 기록, 타이머를 여기서 처리하고, 테스트는 `()`를 씀
+pub trait GameHooks: Send + Sync + 'static {
+    /// 수를 검사하기 전에 받을지 (서비스는 자리의 수 빈도 제한)
+    fn admit_move(&self, _game: &mut SharedGame, _symbol: &str) -> Result<(), MoveError> {
+        Ok(())
+    }
+
+    /// 받은 수를 검사한 결과
+    fn move_checked(&self, _result: &Result<MoveOutcome, MoveError>) {}
+
+    /// 판이 끝났을 수 있는 처리마다 (끝나지 않았으면 아무것도 하지 않아야 함)
+    fn finished(&self, _game: &SharedGame) {}
+
+    /// 명령 처리를 마친 뒤 (새 차례나 이어지는 판이 시작됐으면 타이머를 켬)
+    fn settled(&self, _game: &mut SharedGame) {}
+}
+
+impl GameHooks for () {}
+
+/// 액터가 처리하는 명령
+pub enum GameCommand {
+    /// 자리 배정이나 재접속 (심볼, 연결 번호)
+    Join {
+        join: Join,
+        tx: UpdateSender,
+        rating: Option<i32>,
+        reply: oneshot::Sender<Result<(String, u64), GameError>>,
+    },
+    /// `symbol` 자리의 수: 검사해 적용하고 업데이트를 보낸 뒤 봇의 응수와 판 종료까지 처리
+    Move {
+        symbol: String,
+        mv: Move,
+        hooks: Box<dyn GameHooks>,
+        reply: oneshot::Sender<Result<(), MoveError>>,
+    },
+    /// `symbol` 자리의 기권 (진행 중인 게임이 아니면 GameNotOngoing)
+    Resign {
+        symbol: String,
+        hooks: Box<dyn GameHooks>,
+        reply: oneshot::Sender<Result<(), MoveError>>,
+    },
+    /// 차례 제한 시간이 지남: `turn`이 아직 취소되지 않았으면 `symbol`의 기권패 (처리했으면 true)
+    Tick {
+        symbol: String,
+        turn: CancellationToken,
+        hooks: Box<dyn GameHooks>,
+        reply: oneshot::Sender<bool>,
+    },
+    /// `symbol` 자리의 `connection_id` 연결이 끊김 (이미 다른 연결로 바뀌었거나 게임이 초기화됐으면 None)
+    Disconnect {
+        symbol: String,
+        connection_id: u64,
+        reply: oneshot::Sender<Option<Departure>>,
+    },
+    /// 현재 게임 상태 (관전자 시점)
+    Snapshot(oneshot::Sender<GameState>),
+    /// 게임 상태로 작업 실행 (끝날 때까지 다음 명령을 처리하지 않음)
+    Run(Job),
+}
+
+/// 게임 액터에 명령을 보내는 핸들 (복제해서 여러 태스크에서 씀, 모든 핸들이 사라지면 액터도 끝남)
+#[derive(Clone)]
+pub struct GameHandle {
+    game_id: Arc<str>,
+    commands: mpsc::Sender<(GameCommand, Span)>,
+}
+
+impl GameHandle {
+    /// `game`을 가진 액터 태스크를 시작
+    pub fn spawn(game: SharedGame) -> Self {
+        let (commands, inbox) = mpsc::channel(COMMAND_QUEUE);
+        let handle = GameHandle { game_id: game.game_id.as_str().into(), commands };
+        tokio::spawn(run_actor(game, inbox));
+        handle
+    }
+
+    /// 게임 ID (만든 뒤로 바뀌지 않으므로 액터에 묻지 않음)
+    pub fn game_id(&self) -> &str {
+        &self.game_id
+    }
+
+    /// 자리를 받거나 세션 토큰으로 재접속하고 (심볼, 연결 번호)를 받음
+    pub async fn join(&self, join: Join, tx: UpdateSender, rating: Option<i32>) -> Result<(String, u64), GameError> {
+        self.request(|reply| GameCommand::Join { join, tx, rating, reply }).await
+    }
+
+    /// `symbol` 자리의 수를 둠
+    pub async fn play_move(&self, symbol: &str, mv: Move, hooks: impl GameHooks) -> Result<(), MoveError> {
+        let (symbol, hooks) = (symbol.to_string(), Box::new(hooks));
+        self.request(|reply| GameCommand::Move { symbol, mv, hooks, reply }).await
+    }
+
+    /// `symbol` 자리가 기권
+    pub async fn resign(&self, symbol: &str, hooks: impl GameHooks) -> Result<(), MoveError> {
+        let (symbol, hooks) = (symbol.to_string(), Box::new(hooks));
+        self.request(|reply| GameCommand::Resign { symbol, hooks, reply }).await
+    }
+
+    /// `turn` 차례의 제한 시간이 지났음을 알림
+    pub async fn tick(&self, symbol: &str, turn: CancellationToken, hooks: impl GameHooks) -> bool {
+        let (symbol, hooks) = (symbol.to_string(), Box::new(hooks));
+        self.request(|reply| GameCommand::Tick { symbol, turn, hooks, reply }).await
+    }
+
+    /// 플레이어 연결이 끊겼음을 알림
+    pub async fn disconnect(&self, symbol: &str, connection_id: u64) -> Option<Departure> {
+        let symbol = symbol.to_string();
+        self.request(|reply| GameCommand::Disconnect { symbol, connection_id, reply }).await
+    }
+
+    /// 현재 게임 상태 (관전자 시점)
+    pub async fn snapshot(&self) -> GameState {
+        self.request(GameCommand::Snapshot).await
+    }
+
+    /// 액터에서 `job`을 실행하고 결과를 받음
+    pub async fn run<R, F>(&self, job: F) -> R
+    where
+        R: Send + 'static,
+        F: for<'a> FnOnce(&'a mut SharedGame) -> BoxFuture<'a, R> + Send + 'static,
+    {
+        self.request(|reply| {
+            GameCommand::Run(Box::new(move |game| {
+                Box::pin(async move {
+                    let _ = reply.send(job(game).await);
+                })
+            }))
+        })
+        .await
+    }
+
+    /// 기다릴 일이 없는 작업을 액터에서 실행하고 결과를 받음
+    pub async fn with<R, F>(&self, job: F) -> R
+    where
+        R: Send + 'static,
+        F: FnOnce(&mut SharedGame) -> R + Send + 'static,
+    {
+        self.run(move |game| ready(job(game))).await
+    }
+
+    /// 응답 채널을 담은 명령을 보내고 응답을 기다림 (보낸 쪽의 tracing 스팬 안에서 처리, 처리 중 패닉이 나면
+    /// 보낸 쪽도 패닉)
+    async fn request<R>(&self, command: impl FnOnce(oneshot::Sender<R>) -> GameCommand) -> R {
+        let (reply, result) = oneshot::channel();
+        if self.commands.send((command(reply), Span::current())).await.is_err() {
+            panic!("게임 {} 액터가 멈춤", self.game_id);
+        }
+        result.await.expect("게임 액터가 명령을 끝내지 못함")
+    }
+}
+
+/// 이미 계산한 값을 작업의 결과로 돌려줌
+fn ready<'a, R: Send + 'a>(value: R) -> BoxFuture<'a, R> {
+    Box::pin(future::ready(value))
+}
+
+/// 명령을 받은 순서대로 처리하다가 모든 핸들이 사라지면 끝남
+async fn run_actor(mut game: SharedGame, mut inbox: mpsc::Receiver<(GameCommand, Span)>) {
+    while let Some((command, span)) = inbox.recv().await {
+        if AssertUnwindSafe(handle(&mut game, command).instrument(span)).catch_unwind().await.is_err() {
+            // 반쯤 바뀐 상태로 계속 두지 않도록 모든 스트림을 끝내고 빈 게임으로 되돌림 (명령을 보낸 쪽은 응답을 못 받아 실패)
+            error!(game_id = %game.game_id, "게임 명령 처리 중 패닉, 게임 초기화");
+            game.end_streams(EndReason::InternalError).await;
+            game.reset();
+        }
+    }
+}
+
+/// 명령 하나를 끝까지 처리
+async fn handle(game: &mut SharedGame, command: GameCommand) {
+    match command {
+        GameCommand::Join { join, tx, rating, reply } => {
+            let _ = reply.send(game.join_player(&join, tx, rating).await);
+        }
+        GameCommand::Move { symbol, mv, hooks, reply } => {
+            let played = play_move(game, &symbol, &mv, hooks.as_ref()).await;
+            hooks.settled(game);
+            let _ = reply.send(played);
+        }
+        GameCommand::Resign { symbol, hooks, reply } => {
+            // 차례와 상관없이 진행 중인 게임에서만 기권 가능
+            if game.status != "ongoing" {
+                debug!(status = %game.status, "거부: 진행 중이 아닌 게임에서 기권");
+                let _ = reply.send(Err(MoveError::GameNotOngoing));
+                return;
+            }
+            game.resign(&symbol);
+            info!(status = %game.status, "플레이어 기권");
+            game.broadcast_update().await;
+            finish_and_continue(game, hooks.as_ref()).await;
+            hooks.settled(game);
+            let _ = reply.send(Ok(()));
+        }
+        GameCommand::Tick { symbol, turn, hooks, reply } => {
+            // 제한 시간이 지난 뒤 이 명령보다 먼저 처리된 수가 있으면 토큰이 이미 취소됨
+            if turn.is_cancelled() {
+                let _ = reply.send(false);
+                return;
+            }
+            game.current_turn_cancel = None;
+            game.forfeit_on_time(&symbol).await;
+            finish_and_continue(game, hooks.as_ref()).await;
+            // 몇 판 승부에서 다음 판이 이어졌으면 그 판의 첫 차례
+            hooks.settled(game);
+            let _ = reply.send(true);
+        }
+        GameCommand::Disconnect { symbol, connection_id, reply } => {
+            let departure = game.mark_disconnected(&symbol, connection_id);
+            if let Some(departure) = departure {
+                game.log_disconnect(&symbol);
+                // 남은 상대에게 접속이 끊겼음을 알림 (끝난 게임은 곧 정리됨)
+                if !departure.finished {
+                    game.broadcast_update().await;
+                }
+            }
+            let _ = reply.send(departure);
+        }
+        GameCommand::Snapshot(reply) => {
+            let _ = reply.send(game.create_update());
+        }
+        GameCommand::Run(job) => job(game).await,
+    }
+}
+
+/// `symbol` 자리의 수를 검사해 적용하고 업데이트를 보낸 뒤 봇의 응수와 판 종료까지 처리
+async fn play_move(game: &mut SharedGame, symbol: &str, mv: &Move, hooks: &dyn GameHooks) -> Result<(), MoveError> {
+    let pos = mv.position as usize;
+    // 빈도 제한을 넘지 않았는지, 진행 중인 게임인지, 자기 차례인지, 보드 안의 빈 칸인지(와일드면 고른 심볼도) 검사한 뒤 적용
+    let checked = hooks.admit_move(game, symbol).and_then(|()| game.apply_move_as(symbol, pos, &mv.chosen_symbol));
+    hooks.move_checked(&checked);
+    let outcome = match checked {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            debug!(position = pos, status = %game.status, reason = %e, "거부: 잘못된 수");
+            return Err(e);
+        }
+    };
+    info!(position = pos, status = %game.status, ?outcome, "수 적용");
+    game.broadcast_update().await;
+    // 봇 대전이라면 봇의 응수
+    game.play_bot_turns().await;
+    finish_and_continue(game, hooks).await;
+    Ok(())
+}
+
+/// 끝난 판을 알리고, 자동으로 이어 두는 몇 판 승부면 다음 판을 시작 (봇이 먼저 두면 봇의 수까지 두고,
+/// 그 수로 판이 끝나면 다시 알림)
+pub async fn finish_and_continue(game: &mut SharedGame, hooks: &dyn GameHooks) {
+    hooks.finished(game);
+    while game.start_next_series_game().await {
+        game.play_bot_turns().await;
+        hooks.finished(game);
+    }
+}
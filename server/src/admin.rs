@@ -0,0 +1,157 @@
+//! 서버 운영자용 게임 관리 서비스 (`tictactoe.AdminService`)
+//!
+//! 멈추거나 방치된 게임을 살펴보고 정리하는 RPC입니다. 모든 요청은 [`AdminInterceptor`]가
+//! 메타데이터 `x-admin-token`을 설정의 `admin_token`(환경 변수 `TICTACTOE_ADMIN_TOKEN`이 있으면 그 값)과
+//! 비교해 통과시킵니다. 토큰은 설정 다시 읽기로 바뀔 수 있으므로 요청마다 현재 설정을 봅니다.
+//! `admin_token`이 없으면 모든 관리자 RPC를 거부합니다. `ttt-admin` 도구로 호출할 수 있습니다.
+
+use std::sync::{Arc, RwLock};
+use tokio::sync::Mutex;
+use tonic::codegen::InterceptedService;
+use tonic::metadata::MetadataMap;
+use tonic::service::Interceptor;
+use tonic::{Request, Response, Status};
+use tracing::warn;
+
+use crate::config::Config;
+use crate::error::GameError;
+use crate::manager::GameManager;
+use crate::service::TicTacToeService;
+use crate::tictactoe::admin_service_server::{AdminService, AdminServiceServer};
+use crate::tictactoe::{
+    ForceEndGameRequest, GameDetail, GameDetailRequest, KickPlayerRequest, ListAllGamesRequest, ListAllGamesResponse,
+    ResetGameRequest, ServerStats, ServerStatsRequest,
+};
+
+/// 관리자 토큰 검사: 메타데이터의 x-admin-token이 설정의 관리자 토큰과 다르면 거부 이유 반환
+pub fn admin_denied(config: &Config, metadata: &MetadataMap) -> Option<GameError> {
+    let Some(expected) = config.admin_token.as_deref() else {
+        return Some(GameError::AdminDisabled);
+    };
+    let given = metadata.get("x-admin-token").and_then(|v| v.to_str().ok());
+    if given != Some(expected) {
+        warn!("관리자 토큰 불일치");
+        return Some(GameError::AuthError("관리자 토큰이 올바르지 않습니다.".into()));
+    }
+    None
+}
+
+/// 관리자 서비스의 모든 요청에서 관리자 토큰을 검사하는 인터셉터
+#[derive(Clone)]
+pub struct AdminInterceptor {
+    config: Arc<RwLock<Arc<Config>>>,
+}
+
+impl Interceptor for AdminInterceptor {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        let config = self.config.read().unwrap().clone();
+        match admin_denied(&config, request.metadata()) {
+            Some(denied) => Err(denied.into()),
+            None => Ok(request),
+        }
+    }
+}
+
+/// 관리자 gRPC 서비스 (게임 서비스와 같은 매니저와 설정을 공유)
+#[derive(Clone)]
+pub struct TicTacToeAdmin {
+    manager: Arc<Mutex<GameManager>>,
+    config: Arc<RwLock<Arc<Config>>>,
+    service: TicTacToeService, // 내보내기로 끝난 게임을 기록 (통계, 레이팅 포함)
+}
+
+impl TicTacToeAdmin {
+    pub(crate) fn new(manager: Arc<Mutex<GameManager>>, config: Arc<RwLock<Arc<Config>>>, service: TicTacToeService) -> Self {
+        TicTacToeAdmin { manager, config, service }
+    }
+
+    /// 관리자 토큰 인터셉터를 거치는 tonic 서버로 변환
+    pub fn into_server(self) -> InterceptedService<AdminServiceServer<Self>, AdminInterceptor> {
+        let interceptor = AdminInterceptor { config: self.config.clone() };
+        AdminServiceServer::with_interceptor(self, interceptor)
+    }
+}
+
+#[tonic::async_trait]
+impl AdminService for TicTacToeAdmin {
+    async fn list_all_games(
+        &self,
+        _request: Request<ListAllGamesRequest>,
+    ) -> Result<Response<ListAllGamesResponse>, Status> {
+        let games = self.manager.lock().await.all_games().await;
+        Ok(Response::new(ListAllGamesResponse { games }))
+    }
+
+    async fn get_game_detail(
+        &self,
+        request: Request<GameDetailRequest>,
+    ) -> Result<Response<GameDetail>, Status> {
+        let game = self.manager.lock().await.get(&request.into_inner().game_id).ok_or(GameError::GameNotFound)?;
+        let detail = game.with(|game| game.detail()).await;
+        Ok(Response::new(detail))
+    }
+
+    async fn force_end_game(
+        &self,
+        request: Request<ForceEndGameRequest>,
+    ) -> Result<Response<GameDetail>, Status> {
+        let request = request.into_inner();
+        let detail = self.manager.lock().await.force_end(&request.game_id, request.reason.trim()).await;
+        detail.map(Response::new).ok_or_else(|| GameError::GameNotFound.into())
+    }
+
+    async fn reset_game(
+        &self,
+        request: Request<ResetGameRequest>,
+    ) -> Result<Response<GameDetail>, Status> {
+        let request = request.into_inner();
+        let game = self.manager.lock().await.get(&request.game_id).ok_or(GameError::GameNotFound)?;
+        let (service, handle) = (self.service.clone(), game.clone());
+        let detail = game
+            .run(move |game| {
+                Box::pin(async move {
+                    game.admin_reset(request.reason.trim()).await;
+                    game.play_bot_turns().await;
+                    service.start_game_timers(&handle, game);
+                    game.detail()
+                })
+            })
+            .await;
+        Ok(Response::new(detail))
+    }
+
+    async fn kick_player(
+        &self,
+        request: Request<KickPlayerRequest>,
+    ) -> Result<Response<GameDetail>, Status> {
+        let request = request.into_inner();
+        if request.symbol != "X" && request.symbol != "O" {
+            return Err(GameError::InvalidArgument("symbol은 \"X\" 또는 \"O\"여야 합니다.".into()).into());
+        }
+        let game = self.manager.lock().await.get(&request.game_id).ok_or(GameError::GameNotFound)?;
+        let service = self.service.clone();
+        let detail = game
+            .run(move |game| {
+                Box::pin(async move {
+                    let ongoing = game.status == "ongoing";
+                    if !game.kick(&request.symbol).await {
+                        return None;
+                    }
+                    if ongoing {
+                        service.record_finish(game);
+                    }
+                    Some(game.detail())
+                })
+            })
+            .await;
+        Ok(Response::new(detail.ok_or(GameError::NoPlayerInSeat)?))
+    }
+
+    async fn get_server_stats(
+        &self,
+        _request: Request<ServerStatsRequest>,
+    ) -> Result<Response<ServerStats>, Status> {
+        let stats = self.manager.lock().await.stats();
+        Ok(Response::new(stats))
+    }
+}
@@ -0,0 +1,150 @@
+//! gRPC를 쓸 수 없는 웹 클라이언트를 위한 HTTP JSON API
+//!
+//! - `GET /games` — 진행 중인 게임 목록 (`ListGames`, 쿼리 `filter`, `page_token`, `page_size`)
+//! - `GET /games/{id}` — 현재 상태 (`GetGameState`, 초대 전용 게임은 쿼리 `session_token` 필요)
+//! - `POST /games/{id}/moves` — 수 두기 (`SubmitMove`, 본문 `{"session_token": ..., "move": {"position": 4}}`)
+//! - `GET /games/{id}/history` — 게임 기록 (`GetHistory`, 초대 전용 게임은 쿼리 `session_token` 필요)
+//!
+//! 모든 경로는 같은 `TicTacToeService`의 RPC를 그대로 부르고, 요청과 응답 JSON은 proto 메시지의 필드 이름을
+//! 그대로 씁니다 (enum은 숫자). `Authorization` 헤더는 gRPC와 같은 인증 인터셉터로 검사합니다. 실패하면
+//! gRPC 상태 코드에 맞는 HTTP 상태와 `{"code": <gRPC 코드 번호>, "message": ...}`를 돌려줍니다.
+
+use axum::extract::{Path, Query, State};
+use axum::http::header::{AUTHORIZATION, CONTENT_TYPE};
+use axum::http::{HeaderMap, Method, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Serialize;
+use std::net::SocketAddr;
+use tonic::metadata::MetadataValue;
+use tonic::service::Interceptor;
+use tonic::{Code, Request, Status};
+use tower_http::cors::{Any, CorsLayer};
+use tracing::info;
+
+use crate::auth::AuthInterceptor;
+use crate::service::TicTacToeService;
+use crate::tictactoe::tic_tac_toe_server::TicTacToe;
+use crate::tictactoe::{GameHistoryRequest, GameHistoryResponse, GameState, GameStateRequest, ListGamesRequest, ListGamesResponse, SubmitMoveRequest};
+
+#[derive(Clone)]
+struct Api {
+    service: TicTacToeService,
+    auth: AuthInterceptor,
+}
+
+/// HTTP JSON API 라우터 (브라우저에서 다른 출처로도 부를 수 있도록 CORS 허용)
+pub fn router(service: TicTacToeService, auth: AuthInterceptor) -> Router {
+    let cors = CorsLayer::new()
+        .allow_origin(Any)
+        .allow_methods([Method::GET, Method::POST])
+        .allow_headers([AUTHORIZATION, CONTENT_TYPE]);
+    Router::new()
+        .route("/games", get(list_games))
+        .route("/games/:id", get(get_game))
+        .route("/games/:id/moves", post(submit_move))
+        .route("/games/:id/history", get(get_history))
+        .layer(cors)
+        .with_state(Api { service, auth })
+}
+
+/// 주소에 바인드해 HTTP JSON API 서버 실행
+pub async fn serve(addr: SocketAddr, router: Router) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    info!(%addr, "HTTP JSON API 서버 실행 중");
+    axum::serve(listener, router).await
+}
+
+/// RPC가 거부한 요청 (gRPC 상태를 HTTP 상태와 JSON 본문으로 바꿈)
+pub struct ApiError(Box<Status>);
+
+impl From<Status> for ApiError {
+    fn from(status: Status) -> Self {
+        ApiError(Box::new(status))
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorBody<'a> {
+    code: i32,
+    message: &'a str,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = ErrorBody { code: self.0.code() as i32, message: self.0.message() };
+        (http_status(self.0.code()), Json(body)).into_response()
+    }
+}
+
+/// gRPC 상태 코드에 해당하는 HTTP 상태
+pub fn http_status(code: Code) -> StatusCode {
+    match code {
+        Code::Ok => StatusCode::OK,
+        Code::InvalidArgument | Code::OutOfRange => StatusCode::BAD_REQUEST,
+        Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+        Code::PermissionDenied => StatusCode::FORBIDDEN,
+        Code::NotFound => StatusCode::NOT_FOUND,
+        Code::AlreadyExists | Code::Aborted | Code::FailedPrecondition => StatusCode::CONFLICT,
+        Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+        Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+        Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+        Code::Cancelled | Code::Unknown | Code::Internal | Code::DataLoss => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// HTTP 요청의 `Authorization` 헤더를 메타데이터로 옮기고 gRPC와 같은 인증 인터셉터를 거친 요청
+fn rpc_request<T>(api: &Api, headers: &HeaderMap, message: T) -> Result<Request<T>, ApiError> {
+    let mut request = Request::new(());
+    if let Some(value) = headers.get(AUTHORIZATION).and_then(|value| value.to_str().ok()) {
+        let value = MetadataValue::try_from(value).map_err(|_| Status::unauthenticated("Authorization 헤더 형식이 올바르지 않습니다."))?;
+        request.metadata_mut().insert("authorization", value);
+    }
+    let (metadata, extensions, ()) = api.auth.clone().call(request)?.into_parts();
+    Ok(Request::from_parts(metadata, extensions, message))
+}
+
+async fn list_games(
+    State(api): State<Api>,
+    headers: HeaderMap,
+    Query(request): Query<ListGamesRequest>,
+) -> Result<Json<ListGamesResponse>, ApiError> {
+    let response = api.service.list_games(rpc_request(&api, &headers, request)?).await?;
+    Ok(Json(response.into_inner()))
+}
+
+async fn get_game(
+    State(api): State<Api>,
+    headers: HeaderMap,
+    Path(game_id): Path<String>,
+    Query(request): Query<GameStateRequest>,
+) -> Result<Json<GameState>, ApiError> {
+    let request = GameStateRequest { game_id, ..request };
+    let response = api.service.get_game_state(rpc_request(&api, &headers, request)?).await?;
+    Ok(Json(response.into_inner()))
+}
+
+/// 본문의 game_id는 무시하고 경로의 게임에 둠
+async fn submit_move(
+    State(api): State<Api>,
+    headers: HeaderMap,
+    Path(game_id): Path<String>,
+    Json(request): Json<SubmitMoveRequest>,
+) -> Result<Json<GameState>, ApiError> {
+    let request = SubmitMoveRequest { game_id, ..request };
+    let response = api.service.submit_move(rpc_request(&api, &headers, request)?).await?;
+    Ok(Json(response.into_inner()))
+}
+
+async fn get_history(
+    State(api): State<Api>,
+    headers: HeaderMap,
+    Path(game_id): Path<String>,
+    Query(request): Query<GameHistoryRequest>,
+) -> Result<Json<GameHistoryResponse>, ApiError> {
+    let request = GameHistoryRequest { game_id, ..request };
+    let response = api.service.get_history(rpc_request(&api, &headers, request)?).await?;
+    Ok(Json(response.into_inner()))
+}
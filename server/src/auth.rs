@@ -0,0 +1,205 @@
+//! JWT 인증
+//!
+//! `POST /auth/token`([`crate::auth_http`])이 사용자 이름과 비밀번호를 확인해 HS256으로 서명한 JWT를
+//! 발급하고, gRPC 요청은 [`AuthInterceptor`]가 `authorization: Bearer <token>` 메타데이터로 검사합니다.
+//! 검증된 토큰의 플레이어 ID는 요청 확장([`AuthenticatedPlayer`])에 담겨 서비스로 전달됩니다.
+//! 설정에 `auth_secret`이 없으면 인증을 하지 않습니다 (모든 요청 통과).
+
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::SaltString;
+use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
+use jsonwebtoken::errors::ErrorKind;
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tonic::metadata::MetadataMap;
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
+use tracing::{debug, info};
+
+use crate::error::GameError;
+
+/// JWT 본문
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String, // 플레이어 ID (로그인한 사용자 이름)
+    pub iat: u64,    // 발급 시각 (유닉스 초)
+    pub exp: u64,    // 만료 시각 (유닉스 초)
+}
+
+/// 토큰 검사 실패 사유
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthError {
+    /// authorization 메타데이터 없음
+    Missing,
+    /// `Bearer <token>` 형식이 아님
+    Malformed,
+    /// 만료된 토큰
+    Expired,
+    /// 서명이나 내용이 올바르지 않은 토큰
+    Invalid,
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            AuthError::Missing => "인증 토큰이 필요합니다 (authorization: Bearer <token>).",
+            AuthError::Malformed => "authorization 메타데이터는 'Bearer <token>' 형식이어야 합니다.",
+            AuthError::Expired => "인증 토큰이 만료되었습니다.",
+            AuthError::Invalid => "유효하지 않은 인증 토큰입니다.",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+/// 현재 유닉스 시각 (초)
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// 토큰 서명과 검증에 쓰는 키 (HS256)
+pub struct TokenKeys {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    ttl: Duration, // 발급한 토큰의 유효 시간
+}
+
+impl TokenKeys {
+    pub fn new(secret: &[u8], ttl: Duration) -> Self {
+        TokenKeys { encoding: EncodingKey::from_secret(secret), decoding: DecodingKey::from_secret(secret), ttl }
+    }
+
+    /// 발급한 토큰의 유효 시간
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// 플레이어 ID로 지금 발급하는 토큰
+    pub fn issue(&self, player_id: &str) -> Result<String, jsonwebtoken::errors::Error> {
+        self.issue_at(player_id, unix_now())
+    }
+
+    /// `issued_at`(유닉스 초)에 발급한 것으로 서명한 토큰
+    pub fn issue_at(&self, player_id: &str, issued_at: u64) -> Result<String, jsonwebtoken::errors::Error> {
+        let claims = Claims { sub: player_id.to_string(), iat: issued_at, exp: issued_at + self.ttl.as_secs() };
+        jsonwebtoken::encode(&Header::new(Algorithm::HS256), &claims, &self.encoding)
+    }
+
+    /// 서명과 만료 시각 검사
+    pub fn verify(&self, token: &str) -> Result<Claims, AuthError> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.set_required_spec_claims(&["exp", "sub"]);
+        match jsonwebtoken::decode::<Claims>(token, &self.decoding, &validation) {
+            Ok(data) if !data.claims.sub.is_empty() => Ok(data.claims),
+            Ok(_) => Err(AuthError::Invalid),
+            Err(e) if matches!(e.kind(), ErrorKind::ExpiredSignature) => Err(AuthError::Expired),
+            Err(e) => {
+                debug!(error = %e, "토큰 검증 실패");
+                Err(AuthError::Invalid)
+            }
+        }
+    }
+}
+
+/// 요청 메타데이터의 `authorization: Bearer <token>`에서 토큰 부분
+pub fn bearer_token(metadata: &MetadataMap) -> Result<&str, AuthError> {
+    let value = metadata.get("authorization").ok_or(AuthError::Missing)?;
+    let value = value.to_str().map_err(|_| AuthError::Malformed)?;
+    match value.split_once(' ') {
+        Some((scheme, token)) if scheme.eq_ignore_ascii_case("bearer") && !token.trim().is_empty() => Ok(token.trim()),
+        _ => Err(AuthError::Malformed),
+    }
+}
+
+/// 토큰 검사를 통과한 요청의 플레이어 (요청 확장에 담김)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatedPlayer {
+    pub player_id: String,
+}
+
+/// gRPC 요청마다 Bearer 토큰을 검사하는 인터셉터 (키가 없으면 검사하지 않음)
+#[derive(Clone, Default)]
+pub struct AuthInterceptor {
+    keys: Option<Arc<TokenKeys>>,
+}
+
+impl AuthInterceptor {
+    pub fn new(keys: Arc<TokenKeys>) -> Self {
+        AuthInterceptor { keys: Some(keys) }
+    }
+
+    /// 인증을 하지 않는 인터셉터 (`auth_secret`이 없을 때)
+    pub fn disabled() -> Self {
+        AuthInterceptor { keys: None }
+    }
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let Some(keys) = &self.keys else {
+            return Ok(request);
+        };
+        let claims = bearer_token(request.metadata())
+            .and_then(|token| keys.verify(token))
+            .map_err(GameError::from)?;
+        request.extensions_mut().insert(AuthenticatedPlayer { player_id: claims.sub });
+        Ok(request)
+    }
+}
+
+/// 비밀번호를 argon2id PHC 문자열로 해시 (사용자 파일에 넣을 값)
+pub fn hash_password(password: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    Ok(Argon2::default().hash_password(password.as_bytes(), &salt)?.to_string())
+}
+
+/// 사용자 파일 형식 (`[users]` 아래에 사용자 이름 = 비밀번호 해시)
+#[derive(Debug, Default, Deserialize)]
+struct UserFile {
+    #[serde(default)]
+    users: HashMap<String, String>,
+}
+
+/// 로그인할 수 있는 사용자 목록 (사용자 이름 → 비밀번호 해시)
+#[derive(Debug, Default)]
+pub struct UserStore {
+    users: HashMap<String, String>,
+}
+
+impl UserStore {
+    /// 사용자 파일을 읽어 목록 생성 (경로가 없으면 아무도 로그인할 수 없는 빈 목록)
+    pub fn load(path: Option<&Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let Some(path) = path else {
+            return Ok(UserStore::default());
+        };
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("사용자 파일을 읽을 수 없습니다 ({}): {}", path.display(), e))?;
+        let file: UserFile =
+            toml::from_str(&text).map_err(|e| format!("사용자 파일 형식 오류 ({}): {}", path.display(), e))?;
+        if let Some((name, _)) = file.users.iter().find(|(_, hash)| PasswordHash::new(hash).is_err()) {
+            return Err(format!("사용자 파일 형식 오류 ({}): {}의 비밀번호 해시를 해석할 수 없습니다.", path.display(), name).into());
+        }
+        info!(path = %path.display(), count = file.users.len(), "사용자 파일 로드");
+        Ok(UserStore { users: file.users })
+    }
+
+    /// 사용자 추가 (비밀번호는 해시해서 보관)
+    pub fn insert(&mut self, username: &str, password: &str) -> Result<(), argon2::password_hash::Error> {
+        self.users.insert(username.to_string(), hash_password(password)?);
+        Ok(())
+    }
+
+    /// 사용자 이름과 비밀번호가 맞는지 검사
+    pub fn verify(&self, username: &str, password: &str) -> bool {
+        let Some(hash) = self.users.get(username) else {
+            return false;
+        };
+        PasswordHash::new(hash).is_ok_and(|hash| Argon2::default().verify_password(password.as_bytes(), &hash).is_ok())
+    }
+}
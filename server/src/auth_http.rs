@@ -0,0 +1,72 @@
+//! 토큰 발급 HTTP 엔드포인트 (`POST /auth/token`)
+//!
+//! 요청 본문 `{"username": ..., "password": ...}`이 사용자 파일과 맞으면
+//! `{"access_token": ..., "token_type": "Bearer", "expires_in": ...}`를 돌려줍니다. 틀리면 401입니다.
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::auth::{TokenKeys, UserStore};
+
+#[derive(Debug, Deserialize)]
+pub struct TokenRequest {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: u64, // 초
+}
+
+#[derive(Clone)]
+struct TokenEndpoint {
+    keys: Arc<TokenKeys>,
+    users: Arc<UserStore>,
+}
+
+/// `POST /auth/token` 라우터
+pub fn router(keys: Arc<TokenKeys>, users: Arc<UserStore>) -> Router {
+    Router::new().route("/auth/token", post(issue_token)).with_state(TokenEndpoint { keys, users })
+}
+
+/// 주소에 바인드해 토큰 발급 서버 실행
+pub async fn serve(addr: SocketAddr, router: Router) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    info!(%addr, "토큰 발급 서버 실행 중");
+    axum::serve(listener, router).await
+}
+
+async fn issue_token(
+    State(endpoint): State<TokenEndpoint>,
+    Json(request): Json<TokenRequest>,
+) -> Result<Json<TokenResponse>, (StatusCode, &'static str)> {
+    // 비밀번호 해시 비교는 CPU를 오래 쓰므로 런타임 스레드를 막지 않도록 따로 실행
+    let users = endpoint.users.clone();
+    let username = request.username.clone();
+    let valid = tokio::task::spawn_blocking(move || users.verify(&username, &request.password))
+        .await
+        .unwrap_or(false);
+    if !valid {
+        warn!(username = %request.username, "로그인 실패");
+        return Err((StatusCode::UNAUTHORIZED, "사용자 이름이나 비밀번호가 올바르지 않습니다."));
+    }
+    let access_token = endpoint.keys.issue(&request.username).map_err(|e| {
+        warn!(error = %e, "토큰 서명 실패");
+        (StatusCode::INTERNAL_SERVER_ERROR, "토큰을 발급하지 못했습니다.")
+    })?;
+    info!(username = %request.username, "토큰 발급");
+    Ok(Json(TokenResponse {
+        access_token,
+        token_type: "Bearer".to_string(),
+        expires_in: endpoint.keys.ttl().as_secs(),
+    }))
+}
@@ -0,0 +1,43 @@
+//! 게임 스냅샷 다시 보기 (`replay <snapshot_file>`)
+//!
+//! 서버가 `--snapshot-dir`에 쓴 스냅샷을 읽어 빈 보드에 수를 차례대로 다시 두며 수마다 보드를 출력합니다.
+
+use clap::Parser;
+use std::path::PathBuf;
+
+use server::snapshot::GameSnapshot;
+
+/// 다시 보기 실행 인자
+#[derive(Parser)]
+struct Args {
+    /// 스냅샷 파일 (<game_id>.json 또는 <game_id>.bin)
+    snapshot_file: PathBuf,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let snapshot = GameSnapshot::load(&args.snapshot_file)
+        .map_err(|e| format!("스냅샷을 읽을 수 없습니다 ({}): {}", args.snapshot_file.display(), e))?;
+
+    println!("게임 {} ({}×{}, {}개 연속)", snapshot.game_id, snapshot.board_size, snapshot.board_size, snapshot.win_length);
+    for player in &snapshot.players {
+        let name = if player.bot { "봇" } else if player.player_id.is_empty() { "(익명)" } else { &player.player_id };
+        println!("  {} [{}]: {}", player.symbol, player.marker, name);
+    }
+    println!();
+    for (number, (mv, board)) in snapshot.moves.iter().zip(snapshot.boards()).enumerate() {
+        println!("{}. {} → {}", number + 1, mv.symbol, mv.position);
+        print_board(&board, snapshot.board_size);
+        println!();
+    }
+    println!("결과: {}", snapshot.outcome);
+    Ok(())
+}
+
+/// 보드를 줄마다 출력 (빈 칸은 '.')
+fn print_board(board: &[String], size: usize) {
+    for row in board.chunks(size.max(1)) {
+        let cells: Vec<&str> = row.iter().map(|cell| if cell.is_empty() { "." } else { cell.as_str() }).collect();
+        println!("  {}", cells.join(" "));
+    }
+}
@@ -0,0 +1,141 @@
+//! 관리자 RPC 도구 (`ttt-admin <명령>`)
+//!
+//! 서버의 `AdminService`를 호출해 게임을 살펴보고 정리합니다. 토큰은 `--token`이나
+//! 서버와 같은 환경 변수 `TICTACTOE_ADMIN_TOKEN`으로 넘깁니다.
+
+use clap::{Parser, Subcommand};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tonic::metadata::{Ascii, MetadataValue};
+use tonic::Request;
+
+use server::config::ADMIN_TOKEN_ENV;
+use server::tictactoe::admin_service_client::AdminServiceClient;
+use server::tictactoe::{
+    ForceEndGameRequest, GameDetail, GameDetailRequest, KickPlayerRequest, ListAllGamesRequest, ResetGameRequest,
+    ServerStatsRequest,
+};
+
+/// 관리자 도구 실행 인자
+#[derive(Parser)]
+struct Args {
+    /// 서버 주소
+    #[arg(long, default_value = "http://[::1]:50051")]
+    server: String,
+    /// 관리자 토큰 (없으면 환경 변수 TICTACTOE_ADMIN_TOKEN)
+    #[arg(long, env = ADMIN_TOKEN_ENV, hide_env_values = true)]
+    token: String,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// 모든 게임 목록
+    List,
+    /// 게임 하나의 상세 정보와 보드
+    Show { game_id: String },
+    /// 자리는 그대로 두고 보드를 비워 새로 시작
+    Reset {
+        game_id: String,
+        /// 플레이어에게 보낼 사유
+        #[arg(long, default_value = "")]
+        reason: String,
+    },
+    /// 플레이어를 내보냄 (진행 중이면 그 플레이어의 기권)
+    Kick { game_id: String, symbol: String },
+    /// 게임을 강제로 끝내고 제거
+    End {
+        game_id: String,
+        /// 플레이어에게 보낼 사유
+        #[arg(long, default_value = "")]
+        reason: String,
+    },
+    /// 서버 게임 수 통계
+    Stats,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let token: MetadataValue<Ascii> = args.token.parse().map_err(|_| "관리자 토큰에 쓸 수 없는 문자가 있습니다.")?;
+    let mut admin = AdminServiceClient::connect(args.server.clone())
+        .await
+        .map_err(|e| format!("서버에 연결할 수 없습니다 ({}): {}", args.server, e))?;
+
+    match args.command {
+        Command::List => {
+            let games = admin.list_all_games(with_token(ListAllGamesRequest {}, &token)).await?.into_inner().games;
+            if games.is_empty() {
+                println!("게임 없음");
+            }
+            for game in &games {
+                print_summary(game);
+            }
+        }
+        Command::Show { game_id } => {
+            let game = admin.get_game_detail(with_token(GameDetailRequest { game_id }, &token)).await?.into_inner();
+            print_detail(&game);
+        }
+        Command::Reset { game_id, reason } => {
+            let game = admin.reset_game(with_token(ResetGameRequest { game_id, reason }, &token)).await?.into_inner();
+            print_detail(&game);
+        }
+        Command::Kick { game_id, symbol } => {
+            let game = admin.kick_player(with_token(KickPlayerRequest { game_id, symbol }, &token)).await?.into_inner();
+            print_detail(&game);
+        }
+        Command::End { game_id, reason } => {
+            let game = admin.force_end_game(with_token(ForceEndGameRequest { game_id, reason }, &token)).await?.into_inner();
+            print_detail(&game);
+        }
+        Command::Stats => {
+            let stats = admin.get_server_stats(with_token(ServerStatsRequest {}, &token)).await?.into_inner();
+            println!("전체 {} / 지금 {} / 최대 {}", stats.total_games, stats.active_games, stats.peak_games);
+        }
+    }
+    Ok(())
+}
+
+/// 요청에 관리자 토큰 메타데이터를 붙임
+fn with_token<T>(message: T, token: &MetadataValue<Ascii>) -> Request<T> {
+    let mut request = Request::new(message);
+    request.metadata_mut().insert("x-admin-token", token.clone());
+    request
+}
+
+/// 게임 한 줄 요약 (ID, 상태, 자리, 마지막 활동 뒤 지난 시간)
+fn print_summary(game: &GameDetail) {
+    let seats: Vec<String> = game
+        .seats
+        .iter()
+        .map(|seat| format!("{}{}", seat.symbol, if seat.connected { "" } else { "(끊김)" }))
+        .collect();
+    println!(
+        "{:>6}  {:<28}  {}×{}  자리 [{}]  관전 {}  {}초 전 활동",
+        game.game_id,
+        game.status,
+        game.board_size,
+        game.board_size,
+        seats.join(" "),
+        game.spectator_count,
+        seconds_since(game.last_activity_unix)
+    );
+}
+
+/// 게임 상세 정보와 보드 (빈 칸은 '.')
+fn print_detail(game: &GameDetail) {
+    print_summary(game);
+    for seat in &game.seats {
+        let name = if seat.bot { "봇" } else if seat.player_id.is_empty() { "(익명)" } else { &seat.player_id };
+        println!("  {}: {}{}", seat.symbol, name, if seat.connected { "" } else { " (접속 끊김)" });
+    }
+    for row in game.board.chunks((game.board_size as usize).max(1)) {
+        let cells: Vec<&str> = row.iter().map(|cell| if cell.is_empty() { "." } else { cell.as_str() }).collect();
+        println!("  {}", cells.join(" "));
+    }
+}
+
+fn seconds_since(unix: i64) -> i64 {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64);
+    (now - unix).max(0)
+}
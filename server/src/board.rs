@@ -0,0 +1,24 @@
+//! N×N 보드 규칙 (크기 검사, K개 연속 줄 판정)
+//!
+//! 보드는 행 우선 순서의 칸 목록이며 각 칸은 "", "X", "O" 중 하나입니다. 줄 판정은 클라이언트의 로컬 게임과
+//! 함께 쓰도록 `common::board`에 있고, 여기서는 보드 크기 규칙을 더합니다.
+
+pub use common::board::{winner, winning_line};
+
+/// 크기를 지정하지 않은 게임의 보드 한 변 길이
+pub const DEFAULT_BOARD_SIZE: usize = 3;
+/// 크기를 지정하지 않은 게임의 승리 줄 길이
+pub const DEFAULT_WIN_LENGTH: usize = 3;
+/// 보드 한 변의 최대 길이 (오목 규격)
+pub const MAX_BOARD_SIZE: usize = 15;
+
+/// 보드 크기와 승리 줄 길이 검사 (3 ≤ win_length ≤ size ≤ 15)
+pub fn validate_dimensions(size: usize, win_length: usize) -> Result<(), String> {
+    if !(DEFAULT_BOARD_SIZE..=MAX_BOARD_SIZE).contains(&size) {
+        return Err(format!("보드 크기는 {}~{} 사이여야 합니다.", DEFAULT_BOARD_SIZE, MAX_BOARD_SIZE));
+    }
+    if !(DEFAULT_WIN_LENGTH..=size).contains(&win_length) {
+        return Err(format!("승리 줄 길이는 {}~{} 사이여야 합니다.", DEFAULT_WIN_LENGTH, size));
+    }
+    Ok(())
+}
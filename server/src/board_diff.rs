@@ -0,0 +1,65 @@
+//! 보드 차이 전송 (`Join.board_diffs`)
+//!
+//! 보드 차이를 요청한 플레이어에게는 연결마다 마지막으로 보낸 보드를 기억해 두었다가, 다음 업데이트부터
+//! 바뀐 칸만 `GameState.diff`로 보냅니다. 전송 버퍼가 가득 차 건너뛴 업데이트는 기억하지 않으므로,
+//! 다음 차이는 클라이언트가 실제로 받은 보드를 기준으로 계산됩니다.
+
+use std::sync::Mutex;
+
+use crate::tictactoe::{BoardDiff, CellChange, GameState};
+
+/// `old`에서 `new`로 바뀐 칸 (두 보드의 칸 수가 같아야 함)
+pub fn diff(old: &[String], new: &[String]) -> BoardDiff {
+    let changes = old
+        .iter()
+        .zip(new)
+        .enumerate()
+        .filter(|(_, (old, new))| old != new)
+        .map(|(position, (_, new))| CellChange { position: position as i32, new_value: new.clone() })
+        .collect();
+    BoardDiff { changes }
+}
+
+/// 한 연결에 마지막으로 보낸 보드 (보드 차이를 요청하지 않은 연결은 항상 보드 전체를 보냄)
+///
+/// 업데이트는 `&SharedGame`으로도 보내므로 보낸 보드는 안쪽에서 바꿉니다.
+#[derive(Debug, Default)]
+pub struct DiffEncoder {
+    enabled: bool,
+    sent: Mutex<Option<Vec<String>>>,
+}
+
+impl DiffEncoder {
+    pub fn new(enabled: bool) -> Self {
+        DiffEncoder { enabled, sent: Mutex::new(None) }
+    }
+
+    /// 보드 차이를 요청한 연결이면 `update`의 보드를 마지막으로 보낸 보드와의 차이로 바꿈
+    /// (처음 보내거나 칸 수가 달라졌으면 보드 전체). 보내는 데 성공하면 `sent`에 넘길 보드를 반환합니다.
+    pub fn encode(&self, update: &mut GameState) -> Option<Vec<String>> {
+        if !self.enabled {
+            return None;
+        }
+        let sent = self.sent.lock().unwrap();
+        match sent.as_deref() {
+            Some(sent) if sent.len() == update.board.len() => {
+                let board = std::mem::take(&mut update.board);
+                update.diff = Some(diff(sent, &board));
+                update.full_board = false;
+                Some(board)
+            }
+            _ => Some(update.board.clone()),
+        }
+    }
+
+    /// 클라이언트 채널에 넣은 업데이트의 보드를 다음 차이의 기준으로 기억
+    pub fn sent(&self, board: Vec<String>) {
+        *self.sent.lock().unwrap() = Some(board);
+    }
+}
+
+impl Clone for DiffEncoder {
+    fn clone(&self) -> Self {
+        DiffEncoder { enabled: self.enabled, sent: Mutex::new(self.sent.lock().unwrap().clone()) }
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use rand::seq::SliceRandom;
+
+use crate::board;
+use crate::tictactoe::BotDifficulty;
+
+/// 완전 탐색(Hard)을 하는 최대 빈 칸 수 (3x3 보드 전체). 그보다 많으면 Medium처럼 둡니다.
+const HARD_FULL_SEARCH: usize = 9;
+
+/// (보드, 둘 차례)별 미니맥스 점수
+type Memo = HashMap<(Vec<String>, String), i32>;
+
+/// 표준 3x3 보드의 점수표 (상태가 수천 개뿐이라 게임 사이에 계속 재사용, 클래식과 미제르는 따로)
+fn standard_memo(misere: bool) -> &'static Mutex<Memo> {
+    static CLASSIC: OnceLock<Mutex<Memo>> = OnceLock::new();
+    static MISERE: OnceLock<Mutex<Memo>> = OnceLock::new();
+    let memo = if misere { &MISERE } else { &CLASSIC };
+    memo.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 서버 봇의 수 선택 전략
+pub trait BotStrategy: Send + Sync {
+    /// `me`("X" 또는 "O")가 둘 칸 (빈 칸이 없으면 None). `misere`면 줄을 이은 쪽이 지는 미제르 규칙으로 둡니다.
+    fn choose_move(&self, board: &[String], size: usize, win_length: usize, me: &str, misere: bool) -> Option<usize>;
+}
+
+/// 쉬움: 빈 칸 중 무작위
+pub struct Easy;
+
+/// 보통: 이길 수 있으면 이기고, 상대의 승리를 막고, 그 외에는 중앙 → 모서리 → 나머지 칸 순으로 무작위 선택
+/// (미제르에서는 줄을 잇지 않는 칸 중 무작위)
+pub struct Medium;
+
+/// 어려움: 메모이제이션한 미니맥스로 끝까지 읽음 (3x3에서는 지지 않음, 미제르에서는 끝 점수를 뒤집음)
+pub struct Hard;
+
+/// 난이도에 맞는 전략 (UNSPECIFIED는 보통)
+pub fn strategy(difficulty: BotDifficulty) -> &'static dyn BotStrategy {
+    match difficulty {
+        BotDifficulty::Easy => &Easy,
+        BotDifficulty::Unspecified | BotDifficulty::Medium => &Medium,
+        BotDifficulty::Hard => &Hard,
+    }
+}
+
+impl BotStrategy for Easy {
+    fn choose_move(&self, board: &[String], _size: usize, _win_length: usize, _me: &str, _misere: bool) -> Option<usize> {
+        let empty: Vec<usize> = (0..board.len()).filter(|&i| board[i].is_empty()).collect();
+        empty.choose(&mut rand::thread_rng()).copied()
+    }
+}
+
+impl BotStrategy for Medium {
+    fn choose_move(&self, board: &[String], size: usize, win_length: usize, me: &str, misere: bool) -> Option<usize> {
+        if misere {
+            return safe_cell(board, size, win_length, me);
+        }
+        if let Some(pos) = winning_cell(board, size, win_length, me).or_else(|| winning_cell(board, size, win_length, opponent_of(me))) {
+            return Some(pos);
+        }
+        let center = (size / 2) * size + size / 2;
+        if size % 2 == 1 && board[center].is_empty() {
+            return Some(center);
+        }
+
+        let last = size - 1;
+        let corners = [0, last, last * size, last * size + last];
+        let mut rng = rand::thread_rng();
+        for cells in [corners.to_vec(), (0..board.len()).collect()] {
+            let empty: Vec<usize> = cells.into_iter().filter(|&i| board[i].is_empty()).collect();
+            if let Some(&pos) = empty.choose(&mut rng) {
+                return Some(pos);
+            }
+        }
+        None
+    }
+}
+
+impl BotStrategy for Hard {
+    fn choose_move(&self, board: &[String], size: usize, win_length: usize, me: &str, misere: bool) -> Option<usize> {
+        let empty: Vec<usize> = (0..board.len()).filter(|&i| board[i].is_empty()).collect();
+        if empty.len() > HARD_FULL_SEARCH {
+            return Medium.choose_move(board, size, win_length, me, misere);
+        }
+        let mut local = Memo::new();
+        let mut shared = (size == 3 && win_length == 3).then(|| standard_memo(misere).lock().unwrap_or_else(|e| e.into_inner()));
+        let memo = match shared.as_deref_mut() {
+            Some(memo) => memo,
+            None => &mut local,
+        };
+        let mut search = Minimax { size, win_length, misere, memo };
+        let mut trial = board.to_vec();
+        empty.into_iter().max_by_key(|&pos| {
+            trial[pos] = me.to_string();
+            let score = -search.score(&mut trial, opponent_of(me));
+            trial[pos].clear();
+            // 점수가 같으면 앞쪽 칸 (max_by_key는 마지막 최댓값을 고르므로 위치를 뒤집어 비교)
+            (score, std::cmp::Reverse(pos))
+        })
+    }
+}
+
+/// 보드와 둘 차례별 점수를 기억하는 미니맥스
+struct Minimax<'a> {
+    size: usize,
+    win_length: usize,
+    misere: bool,
+    memo: &'a mut Memo,
+}
+
+impl Minimax<'_> {
+    /// `to_move`가 둘 차례인 보드의 점수 (`to_move` 입장: 이기면 양수, 지면 음수, 빨리 끝날수록 큼)
+    fn score(&mut self, board: &mut [String], to_move: &str) -> i32 {
+        let remaining = board.iter().filter(|cell| cell.is_empty()).count() as i32;
+        if board::winner(board, self.size, self.win_length).is_some() {
+            // 직전에 둔 상대가 줄을 완성함 (미제르에서는 상대가 진 것이지만, 마지막 빈 칸을 채운 수였다면 이긴 것)
+            return if self.misere && remaining > 0 { 1 + remaining } else { -(1 + remaining) };
+        }
+        if remaining == 0 {
+            return 0;
+        }
+        let key = (board.to_vec(), to_move.to_string());
+        if let Some(&score) = self.memo.get(&key) {
+            return score;
+        }
+        let mut best = i32::MIN;
+        for pos in 0..board.len() {
+            if !board[pos].is_empty() {
+                continue;
+            }
+            board[pos] = to_move.to_string();
+            best = best.max(-self.score(board, opponent_of(to_move)));
+            board[pos].clear();
+        }
+        self.memo.insert(key, best);
+        best
+    }
+}
+
+/// 상대 심볼
+pub(crate) fn opponent_of(symbol: &str) -> &'static str {
+    if symbol == "X" { "O" } else { "X" }
+}
+
+/// `symbol`이 한 수로 줄을 완성할 수 있는 빈 칸
+fn winning_cell(board: &[String], size: usize, win_length: usize, symbol: &str) -> Option<usize> {
+    let mut trial = board.to_vec();
+    (0..board.len()).find(|&i| {
+        if !board[i].is_empty() {
+            return false;
+        }
+        trial[i] = symbol.to_string();
+        let wins = board::winner(&trial, size, win_length).is_some();
+        trial[i].clear();
+        wins
+    })
+}
+
+/// 미제르에서 `symbol`이 줄을 잇지 않고 둘 수 있는 빈 칸 중 무작위 (그런 칸이 없으면 아무 빈 칸)
+fn safe_cell(board: &[String], size: usize, win_length: usize, symbol: &str) -> Option<usize> {
+    let empty: Vec<usize> = (0..board.len()).filter(|&i| board[i].is_empty()).collect();
+    let mut trial = board.to_vec();
+    let safe: Vec<usize> = empty
+        .iter()
+        .copied()
+        .filter(|&i| {
+            trial[i] = symbol.to_string();
+            let completes = board::winner(&trial, size, win_length).is_some();
+            trial[i].clear();
+            !completes
+        })
+        .collect();
+    let mut rng = rand::thread_rng();
+    safe.choose(&mut rng).or_else(|| empty.choose(&mut rng)).copied()
+}
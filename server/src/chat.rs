@@ -0,0 +1,49 @@
+//! 게임 중 채팅
+//!
+//! 플레이어가 Play 스트림의 Chat이나 `SendChat` RPC로 보낸 메시지는 제어 문자를 지우고 길이를 검사한 뒤
+//! 상대 플레이어와 관전자에게 전달됩니다. 도배를 막기 위해 자리마다 보낼 수 있는 빈도를 제한하며,
+//! 두 경로가 같은 제한을 나눠 씁니다. 최대 길이와 빈도는 설정(`chat_max_chars`, `chat_rate_limit`,
+//! `chat_rate_window_secs`)으로 정합니다.
+
+use std::collections::VecDeque;
+use common::text;
+use std::time::Duration;
+use tokio::time::Instant;
+
+use crate::error::ChatError;
+
+/// 채팅 문자열 정리: 제어 문자(줄바꿈 포함)를 지우고 앞뒤 공백을 자름. 비었거나 `max_chars`자보다 길면 거부
+/// (글자 수는 그래핌 단위라 결합 문자나 ZWJ 이모지도 한 글자로 셈)
+pub fn sanitize(text: &str, max_chars: usize) -> Result<String, ChatError> {
+    let cleaned: String = text.chars().filter(|c| !c.is_control()).collect();
+    let cleaned = cleaned.trim();
+    if cleaned.is_empty() {
+        return Err(ChatError::Empty);
+    }
+    if text::grapheme_count(cleaned) > max_chars {
+        return Err(ChatError::TooLong(max_chars));
+    }
+    Ok(cleaned.to_string())
+}
+
+/// 자리 하나의 채팅 빈도 제한 (지난 `window` 동안 보낸 메시지가 `limit`개 미만일 때만 허용)
+#[derive(Debug, Clone, Default)]
+pub struct ChatLimiter {
+    /// 창 안에서 보낸 메시지의 시각 (오래된 것부터)
+    sent: VecDeque<Instant>,
+}
+
+impl ChatLimiter {
+    /// 지금 메시지를 보낼 수 있으면 보낸 것으로 기록하고 true
+    pub fn allow(&mut self, limit: u32, window: Duration) -> bool {
+        let now = Instant::now();
+        while self.sent.front().is_some_and(|sent| now.duration_since(*sent) >= window) {
+            self.sent.pop_front();
+        }
+        if self.sent.len() >= limit as usize {
+            return false;
+        }
+        self.sent.push_back(now);
+        true
+    }
+}
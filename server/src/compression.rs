@@ -0,0 +1,41 @@
+//! gRPC 메시지 압축 (`--compression`)
+//!
+//! 서버는 빌드에 들어 있는 모든 방식(gzip, `zstd` 기능을 켜고 빌드하면 zstd도)으로 압축한 요청을 항상 받고,
+//! 응답만 `--compression`으로 고른 방식으로 압축합니다. tonic은 클라이언트가 `grpc-accept-encoding`으로 받겠다고
+//! 알린 방식일 때만 응답을 압축하므로, 압축을 끈 클라이언트와도 압축하지 않은 메시지로 주고받습니다.
+
+use clap::ValueEnum;
+use tonic::codec::CompressionEncoding;
+
+/// 보내는 메시지의 압축 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Compression {
+    #[default]
+    Gzip,
+    /// `zstd` 기능을 켜고 빌드해야 쓸 수 있음
+    Zstd,
+    None,
+}
+
+impl Compression {
+    /// 보낼 때 쓸 tonic 압축 방식 (None이면 압축하지 않음, 빌드에 없는 방식이면 에러)
+    pub fn encoding(self) -> Result<Option<CompressionEncoding>, String> {
+        match self {
+            Compression::Gzip => Ok(Some(CompressionEncoding::Gzip)),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Ok(Some(CompressionEncoding::Zstd)),
+            #[cfg(not(feature = "zstd"))]
+            Compression::Zstd => Err("zstd 압축은 zstd 기능을 켜고 빌드해야 쓸 수 있습니다 (--features zstd).".into()),
+            Compression::None => Ok(None),
+        }
+    }
+}
+
+/// 받을 수 있는 압축 방식 (빌드에 들어 있는 방식 전부)
+pub fn accepted() -> Vec<CompressionEncoding> {
+    #[allow(unused_mut)]
+    let mut encodings = vec![CompressionEncoding::Gzip];
+    #[cfg(feature = "zstd")]
+    encodings.push(CompressionEncoding::Zstd);
+    encodings
+}
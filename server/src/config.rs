@@ -0,0 +1,378 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::info;
+
+use crate::grpc_web;
+
+/// 경로를 지정하지 않았을 때 작업 디렉터리에서 찾는 설정 파일
+const DEFAULT_CONFIG_FILE: &str = "server.toml";
+
+/// 설정 파일의 `admin_token` 대신 쓰는 환경 변수 (토큰을 파일에 적지 않을 때)
+pub const ADMIN_TOKEN_ENV: &str = "TICTACTOE_ADMIN_TOKEN";
+
+/// 서버 설정 (TOML 파일에서 읽음, 빠진 항목은 기본값 사용)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// 서버 바인드 주소
+    pub listen_addr: String,
+    /// 클라이언트별 업데이트 채널 버퍼 크기
+    pub channel_buffer: usize,
+    /// 접속이 끊긴 플레이어의 자리를 유지하는 시간 (초)
+    pub reconnect_grace_secs: u64,
+    /// 빠른 대전에서 상대를 기다리는 시간 (초, 지나면 서버 봇과 대전)
+    pub quick_play_timeout_secs: u64,
+    /// 혼자 앉은 플레이어가 상대를 기다리는 시간 (밀리초, 지나면 게임을 닫아 자리를 비움)
+    pub waiting_timeout_ms: u64,
+    /// 동시에 진행할 수 있는 최대 게임 수
+    pub max_games: usize,
+    /// 수 처리 지연(p95) 예산 (밀리초, 넘으면 부가 작업을 단계적으로 중단)
+    pub latency_budget_ms: u64,
+    /// 부하 평가 주기 (밀리초)
+    pub load_check_interval_ms: u64,
+    /// 관리자가 정의한 프리셋을 저장할 TOML 파일 (없으면 서버를 다시 시작할 때 사라짐)
+    pub presets_file: Option<PathBuf>,
+    /// 관리자 RPC에 필요한 토큰 (메타데이터 x-admin-token, 없으면 관리자 RPC 비활성화).
+    /// 환경 변수 TICTACTOE_ADMIN_TOKEN이 있으면 그 값을 씀
+    pub admin_token: Option<String>,
+    /// 비공개 게임 초대 코드의 유효 시간 (초)
+    pub invite_ttl_secs: u64,
+    /// Elo 레이팅 K-factor (한 게임으로 움직일 수 있는 최대 레이팅 폭)
+    pub elo_k_factor: f64,
+    /// 플레이어 레이팅을 저장할 TOML 파일 (없으면 서버를 다시 시작할 때 사라짐)
+    pub ratings_file: Option<PathBuf>,
+    /// 플레이어별 누적 통계를 저장할 TOML 파일 (없으면 서버를 다시 시작할 때 사라짐)
+    pub stats_file: Option<PathBuf>,
+    /// JWT 서명 키 (32바이트 이상, 없으면 인증 없이 누구나 접속)
+    pub auth_secret: Option<String>,
+    /// 토큰 발급 HTTP 서버(`POST /auth/token`) 바인드 주소 (auth_secret이 있을 때만 실행)
+    pub auth_listen_addr: String,
+    /// 발급한 토큰의 유효 시간 (초)
+    pub auth_token_ttl_secs: u64,
+    /// 로그인할 수 있는 사용자와 비밀번호 해시 파일 (`server --hash-password`로 해시 생성)
+    pub users_file: Option<PathBuf>,
+    /// Prometheus 지표 HTTP 서버(`GET /metrics`) 바인드 주소 (없으면 지표 서버를 띄우지 않음)
+    pub metrics_listen_addr: Option<String>,
+    /// HTTP JSON API 서버(`/games` 등) 바인드 주소 (빈 문자열이면 띄우지 않음)
+    pub http_listen_addr: String,
+    /// 새 게임에서 먼저 두는 쪽 ("X", "O", "random", "alternate")
+    pub first_player: FirstPlayer,
+    /// HTTP/2 keepalive PING 간격 (초)
+    pub http2_keepalive_interval_secs: u64,
+    /// HTTP/2 keepalive PING 응답을 기다리는 시간 (초, 지나면 연결을 닫음)
+    pub http2_keepalive_timeout_secs: u64,
+    /// 플레이어 스트림에 하트비트를 보내는 간격 (초)
+    pub heartbeat_interval_secs: u64,
+    /// 하트비트를 보내고 응답을 기다리는 시간이자 응답 스트림이 업데이트를 받아 가기를 기다리는 시간
+    /// (초, 보내지 못하거나 응답이 없으면 접속이 끊긴 것으로 처리하고, 받아 가지 않으면 진행 중인 게임은 기권패)
+    pub heartbeat_timeout_secs: u64,
+    /// 하트비트를 포함해 아무 메시지도 보내지 않은 플레이어의 게임을 "idle_timeout"으로 끝내기까지의 시간
+    /// (초, 0이면 끄기, 켜면 heartbeat_interval_secs보다 길어야 함)
+    pub idle_timeout_secs: u64,
+    /// 종료 신호를 받은 뒤 진행 중인 게임이 끝나기를 기다리는 시간 (초, 0이면 바로 강제 종료)
+    pub shutdown_drain_timeout_secs: u64,
+    /// `ReplayGame`으로 다시 볼 수 있게 메모리에 남겨 두는 최근 끝난 게임 수 (0이면 남기지 않음)
+    pub replay_buffer_games: usize,
+    /// `ReplayGame`이 상태 하나를 보내고 다음 상태를 보내기까지 기다리는 시간 (밀리초, `paced: false`면 기다리지 않음)
+    pub replay_delay_ms: u64,
+    /// 채팅 메시지 최대 글자 수 (그래핌 단위)
+    pub chat_max_chars: usize,
+    /// 한 자리가 `chat_rate_window_secs` 동안 보낼 수 있는 채팅 메시지 수
+    pub chat_rate_limit: u32,
+    /// 채팅 빈도 제한의 시간 창 (초)
+    pub chat_rate_window_secs: u64,
+    /// Play 스트림 하나가 초당 보낼 수 있는 게임 요청(수, 기권 등) 수. 채팅은 같은 크기의 버킷을 따로 씀
+    pub message_rate_per_sec: u32,
+    /// 한꺼번에 보낼 수 있는 게임 요청 수 (토큰 버킷 크기, 채팅도 같음)
+    pub message_burst: u32,
+    /// 빈도 제한으로 버린 메시지가 1분 안에 이만큼 쌓이면 스트림을 RESOURCE_EXHAUSTED로 끊음
+    pub dropped_message_limit: u32,
+    /// 한 자리가 한꺼번에 둘 수 있는 수 (연결이 아니라 자리마다라 재접속하거나 SubmitMove로 둬도 늘지 않음)
+    pub rate_limit_capacity: u32,
+    /// 자리의 수 토큰이 초당 채워지는 수 (넘은 수는 "rate limit exceeded" 오류로 거부)
+    pub rate_limit_refill_per_sec: f64,
+    /// 차례인 플레이어가 이 시간 안에 수를 두지 않으면 기권패로 끝냄 (초, 0이면 끄기)
+    pub turn_timeout_secs: u64,
+    /// 관전자의 버퍼가 가득 차 건너뛴 업데이트가 이만큼 쌓이면 그 관전 스트림을 끊음 (0이면 끊지 않음)
+    pub spectator_drop_limit: u32,
+    /// 자리를 받은 연결이 이 시간 안에 아무 메시지(하트비트 응답 포함)도 보내지 않으면 자리를 비우고 연결을 끊음
+    /// (초, 0이면 끄기, 켜면 heartbeat_interval_secs보다 길어야 함)
+    pub join_timeout_secs: u64,
+    /// `--web`으로 gRPC-Web을 켰을 때 부를 수 있는 페이지 출처 (예: "https://play.example.com", 비우면 모든 출처)
+    pub web_allowed_origins: Vec<String>,
+    /// `--snapshot-dir`에 쓰는 끝난 게임 스냅샷 형식 ("json" 또는 "bincode", 읽을 때는 파일 머리로 형식을 알아냄)
+    pub snapshot_format: SnapshotFormat,
+    /// GetLeaderboard 응답을 다시 쓰는 시간 (초, 0이면 매번 새로 정렬)
+    pub leaderboard_cache_secs: u64,
+    /// 진행 중인 게임에서 마지막 수나 참가 뒤 이만큼 아무 수도 참가도 없으면 "abandoned"로 끝내고 자리를 비움
+    /// (밀리초, 0이면 끄기, 접속이 살아 있어도 적용)
+    pub abandon_timeout_ms: u64,
+}
+
+/// 먼저 두는 쪽을 정하는 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FirstPlayer {
+    /// 항상 X가 먼저 (먼저 접속한 플레이어)
+    #[default]
+    X,
+    /// 항상 O가 먼저
+    O,
+    /// 게임마다 무작위로
+    #[serde(rename = "random")]
+    Random,
+    /// 새 게임마다 X와 O를 번갈아
+    #[serde(rename = "alternate")]
+    Alternate,
+}
+
+/// 끝난 게임 스냅샷 파일 형식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SnapshotFormat {
+    /// 사람이 읽을 수 있는 JSON (`<game_id>.json`)
+    #[default]
+    #[serde(rename = "json")]
+    Json,
+    /// 매직 바이트로 시작하는 bincode (`<game_id>.bin`, 더 작고 빠름)
+    #[serde(rename = "bincode")]
+    Bincode,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            listen_addr: "[::1]:50051".into(),
+            channel_buffer: 32,
+            reconnect_grace_secs: 30,
+            quick_play_timeout_secs: 10,
+            waiting_timeout_ms: 5 * 60 * 1000,
+            max_games: 1000,
+            latency_budget_ms: 50,
+            load_check_interval_ms: 1000,
+            presets_file: None,
+            admin_token: None,
+            invite_ttl_secs: 600,
+            elo_k_factor: 32.0,
+            ratings_file: None,
+            stats_file: None,
+            auth_secret: None,
+            auth_listen_addr: "[::1]:50052".into(),
+            auth_token_ttl_secs: 3600,
+            users_file: None,
+            metrics_listen_addr: None,
+            http_listen_addr: "[::1]:8080".into(),
+            first_player: FirstPlayer::X,
+            http2_keepalive_interval_secs: 30,
+            http2_keepalive_timeout_secs: 10,
+            heartbeat_interval_secs: 15,
+            heartbeat_timeout_secs: 10,
+            idle_timeout_secs: 20,
+            shutdown_drain_timeout_secs: 30,
+            replay_buffer_games: 50,
+            replay_delay_ms: 500,
+            chat_max_chars: 256,
+            chat_rate_limit: 5,
+            chat_rate_window_secs: 10,
+            message_rate_per_sec: 5,
+            message_burst: 10,
+            dropped_message_limit: 100,
+            rate_limit_capacity: 10,
+            rate_limit_refill_per_sec: 5.0,
+            turn_timeout_secs: 0,
+            spectator_drop_limit: 50,
+            join_timeout_secs: 0,
+            web_allowed_origins: Vec::new(),
+            snapshot_format: SnapshotFormat::Json,
+            leaderboard_cache_secs: 30,
+            abandon_timeout_ms: 30 * 60 * 1000,
+        }
+    }
+}
+
+impl Config {
+    /// 설정 로드: `path`가 주어지면 그 파일을, 아니면 `server.toml`을 읽고,
+    /// 그것도 없으면 기본값을 사용합니다.
+    pub fn load(path: Option<&Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => {
+                let fallback = PathBuf::from(DEFAULT_CONFIG_FILE);
+                if !fallback.exists() {
+                    info!("설정 파일 없음, 기본값 사용");
+                    return Ok(Config::default().with_env());
+                }
+                fallback
+            }
+        };
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| format!("설정 파일을 읽을 수 없습니다 ({}): {}", path.display(), e))?;
+        let config: Config = toml::from_str(&text)
+            .map_err(|e| format!("설정 파일 형식 오류 ({}): {}", path.display(), e))?;
+        info!(path = %path.display(), "설정 파일 로드");
+        Ok(config.with_env())
+    }
+
+    /// 환경 변수로 지정한 값을 덮어씀 (설정 다시 읽기에도 적용되도록 `load`에서 호출)
+    fn with_env(mut self) -> Self {
+        if let Ok(token) = std::env::var(ADMIN_TOKEN_ENV) {
+            info!("환경 변수의 관리자 토큰 사용");
+            self.admin_token = Some(token);
+        }
+        self
+    }
+
+    /// 값 검사: 잘못된 항목마다 (항목 이름, 이유)를 돌려줌 (비어 있으면 정상)
+    pub fn validate(&self) -> Vec<(&'static str, String)> {
+        let mut errors = Vec::new();
+        if self.listen_addr.parse::<std::net::SocketAddr>().is_err() {
+            errors.push(("listen_addr", format!("올바른 주소가 아닙니다: {}", self.listen_addr)));
+        }
+        if let Some(origin) = self.web_allowed_origins.iter().find(|origin| !grpc_web::is_origin(origin)) {
+            errors.push(("web_allowed_origins", format!("\"scheme://host[:port]\" 형태의 출처가 아닙니다: {}", origin)));
+        }
+        if self.channel_buffer == 0 {
+            errors.push(("channel_buffer", "1 이상이어야 합니다.".to_string()));
+        }
+        if self.waiting_timeout_ms == 0 {
+            errors.push(("waiting_timeout_ms", "1 이상이어야 합니다.".to_string()));
+        }
+        if self.latency_budget_ms == 0 {
+            errors.push(("latency_budget_ms", "1 이상이어야 합니다.".to_string()));
+        }
+        if self.load_check_interval_ms == 0 {
+            errors.push(("load_check_interval_ms", "1 이상이어야 합니다.".to_string()));
+        }
+        if self.invite_ttl_secs == 0 {
+            errors.push(("invite_ttl_secs", "1 이상이어야 합니다.".to_string()));
+        }
+        if !(self.elo_k_factor.is_finite() && self.elo_k_factor > 0.0) {
+            errors.push(("elo_k_factor", "0보다 커야 합니다.".to_string()));
+        }
+        if !(self.rate_limit_refill_per_sec.is_finite() && self.rate_limit_refill_per_sec > 0.0) {
+            errors.push(("rate_limit_refill_per_sec", "0보다 커야 합니다.".to_string()));
+        }
+        if self.auth_secret.as_deref().is_some_and(|secret| secret.len() < 32) {
+            errors.push(("auth_secret", "32바이트 이상이어야 합니다 (비활성화하려면 항목을 지우세요).".to_string()));
+        }
+        if self.auth_listen_addr.parse::<std::net::SocketAddr>().is_err() {
+            errors.push(("auth_listen_addr", format!("올바른 주소가 아닙니다: {}", self.auth_listen_addr)));
+        }
+        if let Some(addr) = self.metrics_listen_addr.as_deref().filter(|addr| addr.parse::<std::net::SocketAddr>().is_err()) {
+            errors.push(("metrics_listen_addr", format!("올바른 주소가 아닙니다: {}", addr)));
+        }
+        if !self.http_listen_addr.is_empty() && self.http_listen_addr.parse::<std::net::SocketAddr>().is_err() {
+            errors.push(("http_listen_addr", format!("올바른 주소가 아닙니다: {}", self.http_listen_addr)));
+        }
+        if self.auth_token_ttl_secs == 0 {
+            errors.push(("auth_token_ttl_secs", "1 이상이어야 합니다.".to_string()));
+        }
+        for (field, value) in [
+            ("http2_keepalive_interval_secs", self.http2_keepalive_interval_secs),
+            ("http2_keepalive_timeout_secs", self.http2_keepalive_timeout_secs),
+            ("heartbeat_interval_secs", self.heartbeat_interval_secs),
+            ("heartbeat_timeout_secs", self.heartbeat_timeout_secs),
+            ("chat_max_chars", self.chat_max_chars as u64),
+            ("chat_rate_limit", u64::from(self.chat_rate_limit)),
+            ("chat_rate_window_secs", self.chat_rate_window_secs),
+            ("message_rate_per_sec", u64::from(self.message_rate_per_sec)),
+            ("message_burst", u64::from(self.message_burst)),
+            ("dropped_message_limit", u64::from(self.dropped_message_limit)),
+            ("rate_limit_capacity", u64::from(self.rate_limit_capacity)),
+        ] {
+            if value == 0 {
+                errors.push((field, "1 이상이어야 합니다.".to_string()));
+            }
+        }
+        if self.idle_timeout_secs != 0 && self.idle_timeout_secs <= self.heartbeat_interval_secs {
+            errors.push(("idle_timeout_secs", "0(끄기)이거나 heartbeat_interval_secs보다 커야 합니다.".to_string()));
+        }
+        if self.join_timeout_secs != 0 && self.join_timeout_secs <= self.heartbeat_interval_secs {
+            errors.push(("join_timeout_secs", "0(끄기)이거나 heartbeat_interval_secs보다 커야 합니다.".to_string()));
+        }
+        if self.admin_token.as_deref().is_some_and(|token| token.trim().is_empty()) {
+            errors.push(("admin_token", "빈 토큰은 쓸 수 없습니다 (비활성화하려면 항목을 지우세요).".to_string()));
+        }
+        errors
+    }
+
+    pub fn reconnect_grace(&self) -> Duration {
+        Duration::from_secs(self.reconnect_grace_secs)
+    }
+
+    pub fn quick_play_timeout(&self) -> Duration {
+        Duration::from_secs(self.quick_play_timeout_secs)
+    }
+
+    pub fn waiting_timeout(&self) -> Duration {
+        Duration::from_millis(self.waiting_timeout_ms)
+    }
+
+    pub fn invite_ttl(&self) -> Duration {
+        Duration::from_secs(self.invite_ttl_secs)
+    }
+
+    pub fn auth_token_ttl(&self) -> Duration {
+        Duration::from_secs(self.auth_token_ttl_secs)
+    }
+
+    pub fn http2_keepalive_interval(&self) -> Duration {
+        Duration::from_secs(self.http2_keepalive_interval_secs)
+    }
+
+    pub fn http2_keepalive_timeout(&self) -> Duration {
+        Duration::from_secs(self.http2_keepalive_timeout_secs)
+    }
+
+    pub fn heartbeat_interval(&self) -> Duration {
+        Duration::from_secs(self.heartbeat_interval_secs)
+    }
+
+    pub fn heartbeat_timeout(&self) -> Duration {
+        Duration::from_secs(self.heartbeat_timeout_secs)
+    }
+
+    /// 응답 없는 플레이어의 게임을 끝내기까지의 시간 (꺼져 있으면 None)
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        (self.idle_timeout_secs > 0).then(|| Duration::from_secs(self.idle_timeout_secs))
+    }
+
+    /// 차례 제한 시간 (꺼져 있으면 None)
+    pub fn turn_timeout(&self) -> Option<Duration> {
+        (self.turn_timeout_secs > 0).then(|| Duration::from_secs(self.turn_timeout_secs))
+    }
+
+    /// 순위표 응답을 캐시에 두는 시간 (0이면 캐시하지 않음)
+    pub fn leaderboard_cache(&self) -> Duration {
+        Duration::from_secs(self.leaderboard_cache_secs)
+    }
+
+    /// 방치된 게임을 끝내기까지의 시간 (꺼져 있으면 None)
+    pub fn abandon_timeout(&self) -> Option<Duration> {
+        (self.abandon_timeout_ms > 0).then(|| Duration::from_millis(self.abandon_timeout_ms))
+    }
+
+    /// 참가한 뒤 첫 메시지를 기다리는 시간 (꺼져 있으면 None)
+    pub fn join_timeout(&self) -> Option<Duration> {
+        (self.join_timeout_secs > 0).then(|| Duration::from_secs(self.join_timeout_secs))
+    }
+
+    pub fn shutdown_drain_timeout(&self) -> Duration {
+        Duration::from_secs(self.shutdown_drain_timeout_secs)
+    }
+
+    pub fn latency_budget(&self) -> Duration {
+        Duration::from_millis(self.latency_budget_ms)
+    }
+
+    pub fn load_check_interval(&self) -> Duration {
+        Duration::from_millis(self.load_check_interval_ms)
+    }
+
+    pub fn replay_delay(&self) -> Duration {
+        Duration::from_millis(self.replay_delay_ms)
+    }
+
+    pub fn chat_rate_window(&self) -> Duration {
+        Duration::from_secs(self.chat_rate_window_secs)
+    }
+}
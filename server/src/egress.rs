@@ -0,0 +1,94 @@
+//! Play 응답 스트림의 끝맺음
+//!
+//! 응답 스트림은 송신 측이 모두 사라지기를 기다리지 않고 여기서 정한 규칙대로 끝납니다.
+//! 끝난 게임의 마지막 상태나 자리를 잃은 연결의 "join_timeout" 상태를 보내면 정상(OK) 종료하고, 오류(참가 거부나 [`stream_end`]로 만든
+//! 종료 사유)를 보내면 그 Status로 끝냅니다. 둘 다 없이 채널이 닫히면 사유 없는 ABORTED로 끝냅니다.
+//! 몇 판 승부에서 승부가 나지 않은 판이 끝났을 때는 한 판 더 할 수 있도록 스트림을 열어 둡니다.
+//!
+//! 게임 액터는 보낼 때 기다리지 않으므로(`try_send`), 느린 클라이언트를 기다리는 일은 게임 쪽 채널과
+//! 응답 스트림 사이의 [`forward`] 태스크가 맡습니다. 받아 가지 않는 클라이언트가 게임 전체를 멈추지 못합니다.
+
+use std::time::Duration;
+
+use futures::Stream;
+use prost::Message;
+use tokio::sync::mpsc::error::SendTimeoutError;
+use tokio::sync::{mpsc, oneshot};
+use tonic::codegen::Bytes;
+use tonic::{Code, Status};
+
+use crate::game::{is_finished_status, ABANDONED, JOIN_TIMEOUT};
+use crate::tictactoe::{EndReason, GameState, StreamEnd};
+
+/// 서버가 스트림을 끝내는 사유를 details에 담은 Status
+pub fn stream_end(reason: EndReason, game_id: &str) -> Status {
+    let (code, message) = match reason {
+        EndReason::SessionTakenOver => (Code::Aborted, "다른 연결이 이 자리를 이어받았습니다."),
+        EndReason::GameAbandoned => (Code::Aborted, "상대가 돌아오지 않아 게임이 취소되었습니다."),
+        EndReason::ServerShutdown => (Code::Unavailable, "서버가 종료됩니다."),
+        EndReason::Kicked => (Code::Aborted, "관리자가 게임에서 내보냈습니다."),
+        EndReason::WaitingTimeout => (Code::DeadlineExceeded, "제한 시간 안에 상대가 참가하지 않았습니다."),
+        EndReason::IdleTimeout => (Code::DeadlineExceeded, "하트비트가 오지 않아 게임을 끝냈습니다."),
+        EndReason::RateLimited => (Code::ResourceExhausted, "메시지를 너무 많이 보내 연결을 끊었습니다."),
+        EndReason::SlowConsumer => (Code::ResourceExhausted, "업데이트를 받아 가지 못해 관전을 끝냈습니다."),
+        EndReason::Inactive => (Code::DeadlineExceeded, "오랫동안 수가 없어 게임을 끝냈습니다."),
+        EndReason::InternalError => (Code::Internal, "서버 오류로 게임을 초기화했습니다."),
+        EndReason::Unspecified => (Code::Aborted, "서버가 스트림을 종료했습니다."),
+    };
+    let details = StreamEnd { reason: reason.into(), game_id: game_id.to_string() };
+    Status::with_details(code, message, Bytes::from(details.encode_to_vec()))
+}
+
+/// Status details의 종료 사유 (참가 거부처럼 details가 없거나 StreamEnd가 아니면 None)
+pub fn end_reason(status: &Status) -> Option<EndReason> {
+    if status.details().is_empty() {
+        return None;
+    }
+    let details = StreamEnd::decode(status.details()).ok()?;
+    EndReason::try_from(details.reason).ok()
+}
+
+/// 게임 쪽 채널(`rx`)의 항목을 응답 스트림이 읽을 채널로 옮기는 태스크를 띄우고, 그 채널과 함께
+/// 클라이언트가 멈췄을 때 완료되는 수신 측을 반환합니다.
+///
+/// 응답 스트림이 `timeout` 안에 다음 항목을 받아 가지 않으면 옮기기를 멈추고(게임 쪽 채널이 닫힘) 알립니다.
+/// 스트림이 끝나 응답 채널이 닫혔거나 게임 쪽 송신 측이 모두 사라졌을 때는 알리지 않고 멈춥니다.
+pub fn forward(mut rx: mpsc::Receiver<Result<GameState, Status>>, timeout: Duration) -> (mpsc::Receiver<Result<GameState, Status>>, oneshot::Receiver<()>) {
+    let (out_tx, out_rx) = mpsc::channel(1);
+    let (stalled_tx, stalled_rx) = oneshot::channel();
+    tokio::spawn(async move {
+        while let Some(item) = rx.recv().await {
+            match out_tx.send_timeout(item, timeout).await {
+                Ok(()) => {}
+                Err(SendTimeoutError::Timeout(_)) => {
+                    let _ = stalled_tx.send(());
+                    return;
+                }
+                Err(SendTimeoutError::Closed(_)) => return,
+            }
+        }
+    });
+    (out_rx, stalled_rx)
+}
+
+/// 채널 수신 측을 종료 규칙이 적용된 응답 스트림으로 변환
+pub fn response_stream(rx: mpsc::Receiver<Result<GameState, Status>>) -> impl Stream<Item = Result<GameState, Status>> {
+    futures::stream::unfold(Some(rx), |rx| async move {
+        let mut rx = rx?;
+        let Some(item) = rx.recv().await else {
+            return Some((Err(Status::aborted("응답 스트림이 예기치 않게 닫혔습니다.")), None));
+        };
+        // 방치로 끝난 게임은 뒤이어 INACTIVE 종료 사유를 보내므로 그 Status로 스트림을 닫음
+        let last = match &item {
+            Ok(state) if state.status == ABANDONED => false,
+            Ok(state) => (is_finished_status(&state.status) && !awaits_rematch(state)) || state.status == JOIN_TIMEOUT,
+            Err(_) => true,
+        };
+        Some((item, (!last).then_some(rx)))
+    })
+}
+
+/// 몇 판 승부 중 승부가 아직 나지 않았고 승패나 무승부로 끝난 판의 상태인지 (다음 판을 기다림)
+fn awaits_rematch(state: &GameState) -> bool {
+    state.best_of > 0 && state.match_winner.is_empty() && (state.status.contains("_win") || state.status.starts_with("draw"))
+}
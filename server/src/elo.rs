@@ -0,0 +1,174 @@
+//! 플레이어 Elo 레이팅
+//!
+//! 레이팅 게임이 끝나면 표준 Elo 공식으로 두 플레이어의 레이팅을 고칩니다. 플레이어는 Join의
+//! `player_id`로 구분하며, 레이팅과 전적은 설정의 `ratings_file`에 저장합니다 (없으면 메모리에만 보관).
+
+use common::fs::atomic_write;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::{info, instrument, warn};
+
+use crate::tictactoe;
+
+/// 처음 레이팅 게임을 하는 플레이어의 레이팅
+pub const INITIAL_RATING: i32 = 1200;
+/// 레이팅 하한 (아무리 져도 이 아래로 내려가지 않음)
+pub const RATING_FLOOR: i32 = 100;
+/// player_id 최대 길이 (문자 수)
+pub const MAX_PLAYER_ID_LEN: usize = 32;
+
+/// 플레이어 한 명의 레이팅과 전적
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EloRecord {
+    pub rating: i32,
+    #[serde(default)]
+    pub wins: u32,
+    #[serde(default)]
+    pub losses: u32,
+    #[serde(default)]
+    pub draws: u32,
+}
+
+impl Default for EloRecord {
+    fn default() -> Self {
+        EloRecord { rating: INITIAL_RATING, wins: 0, losses: 0, draws: 0 }
+    }
+}
+
+impl EloRecord {
+    pub fn to_proto(&self, player_id: &str) -> tictactoe::PlayerRating {
+        tictactoe::PlayerRating {
+            player_id: player_id.to_string(),
+            rating: self.rating,
+            wins: self.wins as i32,
+            losses: self.losses as i32,
+            draws: self.draws as i32,
+        }
+    }
+}
+
+/// 표준 Elo 공식 (K-factor는 한 게임으로 움직일 수 있는 최대 레이팅 폭)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EloRating {
+    pub k_factor: f64,
+}
+
+impl EloRating {
+    pub fn new(k_factor: f64) -> Self {
+        EloRating { k_factor }
+    }
+
+    /// `rating`인 플레이어가 `opponent`를 상대로 얻을 것으로 기대되는 점수 (0 ~ 1)
+    pub fn expected_score(rating: i32, opponent: i32) -> f64 {
+        1.0 / (1.0 + 10f64.powf(f64::from(opponent - rating) / 400.0))
+    }
+
+    /// 실제 점수(승 1, 무 0.5, 패 0)를 반영한 새 레이팅 (반올림, 하한 적용)
+    pub fn rate(&self, rating: i32, opponent: i32, score: f64) -> i32 {
+        let change = self.k_factor * (score - Self::expected_score(rating, opponent));
+        (f64::from(rating) + change).round().max(f64::from(RATING_FLOOR)) as i32
+    }
+
+    /// 승패가 난 게임 반영
+    pub fn update(&self, winner: &mut EloRecord, loser: &mut EloRecord) {
+        let (w, l) = (winner.rating, loser.rating);
+        winner.rating = self.rate(w, l, 1.0);
+        loser.rating = self.rate(l, w, 0.0);
+        winner.wins += 1;
+        loser.losses += 1;
+    }
+
+    /// 무승부 반영
+    pub fn update_draw(&self, a: &mut EloRecord, b: &mut EloRecord) {
+        let (ra, rb) = (a.rating, b.rating);
+        a.rating = self.rate(ra, rb, 0.5);
+        b.rating = self.rate(rb, ra, 0.5);
+        a.draws += 1;
+        b.draws += 1;
+    }
+}
+
+/// 레이팅에 반영할 게임 결과 (두 플레이어의 player_id)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RatedResult {
+    Decisive { winner: String, loser: String },
+    Draw { x: String, o: String },
+}
+
+/// 레이팅 파일 형식
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RatingFile {
+    #[serde(default)]
+    players: BTreeMap<String, EloRecord>,
+}
+
+/// player_id → 레이팅 기록 저장소
+#[derive(Debug, Default)]
+pub struct RatingBook {
+    players: BTreeMap<String, EloRecord>,
+    path: Option<PathBuf>, // 레이팅을 저장할 파일 (없으면 메모리에만 보관)
+}
+
+impl RatingBook {
+    /// 레이팅 파일을 읽어 저장소 생성 (경로가 없거나 파일이 아직 없으면 비어 있는 상태로 시작)
+    pub fn load(path: Option<&Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut book = RatingBook { players: BTreeMap::new(), path: path.map(Path::to_path_buf) };
+        let Some(path) = path else {
+            return Ok(book);
+        };
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(book),
+            Err(e) => return Err(format!("레이팅 파일을 읽을 수 없습니다 ({}): {}", path.display(), e).into()),
+        };
+        let file: RatingFile =
+            toml::from_str(&text).map_err(|e| format!("레이팅 파일 형식 오류 ({}): {}", path.display(), e))?;
+        book.players = file.players;
+        info!(path = %path.display(), count = book.players.len(), "레이팅 파일 로드");
+        Ok(book)
+    }
+
+    /// 기록된 플레이어의 레이팅과 전적
+    pub fn get(&self, player_id: &str) -> Option<EloRecord> {
+        self.players.get(player_id).copied()
+    }
+
+    /// 게임에 표시할 현재 레이팅 (ID가 없으면 None, 기록이 없으면 초기 레이팅)
+    pub fn current_rating(&self, player_id: &str) -> Option<i32> {
+        if player_id.is_empty() {
+            return None;
+        }
+        Some(self.get(player_id).unwrap_or_default().rating)
+    }
+
+    /// 게임 결과를 반영하고 파일에 저장 (저장에 실패해도 메모리의 기록은 유지)
+    pub fn record(&mut self, result: &RatedResult, elo: &EloRating) {
+        let (a, b) = match result {
+            RatedResult::Decisive { winner, loser } => (winner, loser),
+            RatedResult::Draw { x, o } => (x, o),
+        };
+        let mut first = self.get(a).unwrap_or_default();
+        let mut second = self.get(b).unwrap_or_default();
+        match result {
+            RatedResult::Decisive { .. } => elo.update(&mut first, &mut second),
+            RatedResult::Draw { .. } => elo.update_draw(&mut first, &mut second),
+        }
+        info!(player_a = %a, rating_a = first.rating, player_b = %b, rating_b = second.rating, "레이팅 갱신");
+        self.players.insert(a.clone(), first);
+        self.players.insert(b.clone(), second);
+        if let Err(e) = self.save() {
+            warn!(error = %e, "레이팅 파일 저장 실패");
+        }
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let file = RatingFile { players: self.players.clone() };
+        atomic_write(path, toml::to_string(&file)?.as_bytes())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,261 @@
+//! 서버의 요청 처리 오류
+//!
+//! 게임, 매니저, 관리자 코드는 `GameError`를 반환하고, RPC 경계에서 `From<GameError> for Status`로
+//! gRPC 상태 코드와 메시지로 바뀝니다. 수를 거부한 이유는 `MoveError`로 따로 두어 게임 스트림에서는
+//! 플레이어에게 보여 줄 문구로, RPC에서는 `GameError`를 거쳐 상태 코드로 바꿉니다. 채팅을 거부한 이유(`ChatError`)도 같습니다.
+
+use std::fmt;
+
+use tonic::Status;
+
+use crate::auth::AuthError as TokenError;
+
+/// 요청을 처리하지 못한 이유
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameError {
+    /// 두 자리가 모두 찬 게임
+    GameFull,
+    /// 상대가 이미 O 자리에 앉은 게임 (초대 코드로 참가하려 했거나 지정한 게임에 늦게 옴)
+    OpponentAlreadyJoined,
+    /// 초대 코드로만 참가할 수 있는 게임
+    InviteOnly,
+    /// 세션 토큰이 다른 플레이어의 자리를 가리킴
+    NotYourSeat,
+    /// 없거나 만료된 세션 토큰
+    SessionNotFound,
+    /// 없거나 만료된 초대 코드
+    InvalidInviteCode,
+    /// 서버가 수용할 수 있는 게임 수 초과
+    TooManyGames,
+    /// 상대 차례에 둔 수
+    NotYourTurn,
+    /// 이미 채워진 칸
+    CellOccupied,
+    /// 보드 밖의 위치
+    InvalidPosition,
+    /// 진행 중이 아닌 게임에 둔 수
+    GameNotOngoing,
+    /// 얼티밋에서 정해진 작은 보드 밖에 둔 수
+    WrongSubBoard,
+    /// 얼티밋에서 승부가 난 작은 보드에 둔 수
+    SubBoardClosed,
+    /// 와일드에서 놓을 심볼로 "X"나 "O"가 아닌 값을 고른 수
+    InvalidChosenSymbol,
+    /// 레이팅 기록이 없는 플레이어
+    PlayerNotFound,
+    /// 내보낼 플레이어가 앉아 있지 않은 자리
+    NoPlayerInSeat,
+    /// 없는 게임
+    GameNotFound,
+    /// 없는 퍼즐
+    PuzzleNotFound,
+    /// 없는 토너먼트
+    TournamentNotFound,
+    /// 채팅 빈도 제한을 넘음
+    ChatRateLimited,
+    /// 수 빈도 제한을 넘음
+    MoveRateLimited,
+    /// 요청 값이 잘못됨 (이유)
+    InvalidArgument(String),
+    /// 서버 부하로 부가 작업(조회, 다시 보기, 퍼즐, 새 관전) 요청을 잠시 받지 않음
+    Overloaded,
+    /// 서버가 종료 중이라 새 게임 참가를 받지 않음
+    ShuttingDown,
+    /// 관리자 토큰이 설정되지 않아 관리자 RPC를 쓸 수 없음
+    AdminDisabled,
+    /// 인증 실패 (이유)
+    AuthError(String),
+    /// 서버 내부 오류 (이유)
+    Internal(String),
+}
+
+/// 수를 둘 수 없는 이유
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveError {
+    /// 상대 차례에 둔 수
+    NotYourTurn,
+    /// 이미 채워진 칸
+    CellOccupied,
+    /// 보드 밖의 위치
+    OutOfRange,
+    /// 진행 중이 아닌 게임에 둔 수
+    GameNotOngoing,
+    /// 이 게임에 앉아 있지 않은 심볼
+    NotAPlayer,
+    /// 얼티밋에서 정해진 작은 보드 밖에 둔 수
+    WrongSubBoard,
+    /// 얼티밋에서 승부가 난 작은 보드에 둔 수
+    SubBoardClosed,
+    /// 와일드에서 놓을 심볼로 "X"나 "O"가 아닌 값을 고른 수
+    InvalidChosenSymbol,
+    /// 자리의 수 빈도 제한을 넘음
+    RateLimited,
+}
+
+impl MoveError {
+    /// 게임 스트림 안에서 플레이어에게 보여 줄 오류 메시지 (GameState.error_message)
+    pub fn player_message(&self) -> &'static str {
+        match self {
+            MoveError::NotYourTurn => "It's not your turn.",
+            MoveError::CellOccupied => "Cell already occupied.",
+            MoveError::OutOfRange => "Invalid position.",
+            MoveError::GameNotOngoing => "Game is not ongoing.",
+            MoveError::NotAPlayer => "You are not a player in this game.",
+            MoveError::WrongSubBoard => "You must play in the highlighted sub-board.",
+            MoveError::SubBoardClosed => "That sub-board is already decided.",
+            MoveError::InvalidChosenSymbol => "Choose X or O to place in a wild game.",
+            MoveError::RateLimited => "rate limit exceeded",
+        }
+    }
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        GameError::from(*self).fmt(f)
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+impl From<MoveError> for GameError {
+    fn from(error: MoveError) -> Self {
+        match error {
+            MoveError::NotYourTurn => GameError::NotYourTurn,
+            MoveError::CellOccupied => GameError::CellOccupied,
+            MoveError::OutOfRange => GameError::InvalidPosition,
+            MoveError::GameNotOngoing => GameError::GameNotOngoing,
+            MoveError::NotAPlayer => GameError::NotYourSeat,
+            MoveError::WrongSubBoard => GameError::WrongSubBoard,
+            MoveError::SubBoardClosed => GameError::SubBoardClosed,
+            MoveError::InvalidChosenSymbol => GameError::InvalidChosenSymbol,
+            MoveError::RateLimited => GameError::MoveRateLimited,
+        }
+    }
+}
+
+impl From<MoveError> for Status {
+    fn from(error: MoveError) -> Self {
+        GameError::from(error).into()
+    }
+}
+
+/// 채팅을 전달하지 않은 이유
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatError {
+    /// 제어 문자와 공백을 지우고 나니 빈 메시지
+    Empty,
+    /// 설정한 최대 글자 수(값)를 넘는 메시지
+    TooLong(usize),
+    /// 빈도 제한을 넘음
+    TooFast,
+    /// 이 게임에 앉아 있지 않은 심볼
+    NotAPlayer,
+}
+
+impl ChatError {
+    /// 게임 스트림 안에서 플레이어에게 보여 줄 오류 메시지 (GameState.error_message)
+    pub fn player_message(&self) -> String {
+        match self {
+            ChatError::Empty => "Chat message is empty.".to_string(),
+            ChatError::TooLong(max_chars) => format!("Chat messages can be at most {} characters.", max_chars),
+            ChatError::TooFast => "You are sending chat messages too quickly.".to_string(),
+            ChatError::NotAPlayer => MoveError::NotAPlayer.player_message().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ChatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        GameError::from(*self).fmt(f)
+    }
+}
+
+impl std::error::Error for ChatError {}
+
+impl From<ChatError> for GameError {
+    fn from(error: ChatError) -> Self {
+        match error {
+            ChatError::Empty => GameError::InvalidArgument("빈 채팅 메시지입니다.".into()),
+            ChatError::TooLong(max_chars) => GameError::InvalidArgument(format!("채팅 메시지는 {}자까지 보낼 수 있습니다.", max_chars)),
+            ChatError::TooFast => GameError::ChatRateLimited,
+            ChatError::NotAPlayer => GameError::NotYourSeat,
+        }
+    }
+}
+
+impl From<ChatError> for Status {
+    fn from(error: ChatError) -> Self {
+        GameError::from(error).into()
+    }
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::GameFull => write!(f, "이미 두 명의 플레이어가 접속되어 있습니다."),
+            GameError::OpponentAlreadyJoined => write!(f, "이미 상대가 참가한 게임입니다."),
+            GameError::InviteOnly => write!(f, "초대 코드로만 참가할 수 있는 게임입니다."),
+            GameError::NotYourSeat => write!(f, "다른 플레이어의 자리입니다."),
+            GameError::SessionNotFound => write!(f, "유효하지 않거나 만료된 세션 토큰입니다."),
+            GameError::InvalidInviteCode => write!(f, "유효하지 않거나 만료된 초대 코드입니다."),
+            GameError::TooManyGames => write!(f, "서버가 수용할 수 있는 게임 수를 초과했습니다."),
+            GameError::NotYourTurn => write!(f, "현재 차례가 아닙니다."),
+            GameError::CellOccupied => write!(f, "이미 채워진 칸입니다."),
+            GameError::InvalidPosition => write!(f, "보드 밖의 위치입니다."),
+            GameError::GameNotOngoing => write!(f, "진행 중인 게임이 아닙니다."),
+            GameError::WrongSubBoard => write!(f, "정해진 작은 보드에 두어야 합니다."),
+            GameError::SubBoardClosed => write!(f, "승부가 난 작은 보드에는 둘 수 없습니다."),
+            GameError::InvalidChosenSymbol => write!(f, "와일드 게임에서는 놓을 심볼로 \"X\" 또는 \"O\"를 골라야 합니다."),
+            GameError::PlayerNotFound => write!(f, "레이팅 기록이 없는 플레이어입니다."),
+            GameError::NoPlayerInSeat => write!(f, "해당 자리에 내보낼 플레이어가 없습니다."),
+            GameError::GameNotFound => write!(f, "게임을 찾을 수 없습니다."),
+            GameError::PuzzleNotFound => write!(f, "퍼즐을 찾을 수 없습니다."),
+            GameError::TournamentNotFound => write!(f, "토너먼트를 찾을 수 없습니다."),
+            GameError::ChatRateLimited => write!(f, "채팅을 너무 자주 보내고 있습니다. 잠시 뒤에 다시 보내세요."),
+            GameError::MoveRateLimited => write!(f, "수를 너무 자주 두고 있습니다. 잠시 뒤에 다시 두세요."),
+            GameError::InvalidArgument(reason) => write!(f, "{}", reason),
+            GameError::Overloaded => write!(f, "서버 부하로 이 요청을 잠시 처리할 수 없습니다."),
+            GameError::ShuttingDown => write!(f, "서버가 종료 중이라 새 게임에 참가할 수 없습니다."),
+            GameError::AdminDisabled => write!(f, "관리자 RPC가 비활성화되어 있습니다."),
+            GameError::AuthError(reason) => write!(f, "{}", reason),
+            GameError::Internal(reason) => write!(f, "서버 내부 오류: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+impl From<TokenError> for GameError {
+    fn from(error: TokenError) -> Self {
+        GameError::AuthError(error.to_string())
+    }
+}
+
+impl From<GameError> for Status {
+    fn from(error: GameError) -> Self {
+        let message = error.to_string();
+        match error {
+            GameError::GameFull | GameError::TooManyGames | GameError::ChatRateLimited | GameError::MoveRateLimited => {
+                Status::resource_exhausted(message)
+            }
+            GameError::OpponentAlreadyJoined | GameError::CellOccupied => Status::already_exists(message),
+            GameError::InviteOnly | GameError::NotYourSeat | GameError::AdminDisabled => Status::permission_denied(message),
+            GameError::SessionNotFound
+            | GameError::InvalidInviteCode
+            | GameError::PlayerNotFound
+            | GameError::GameNotFound
+            | GameError::PuzzleNotFound
+            | GameError::TournamentNotFound => Status::not_found(message),
+            GameError::NotYourTurn
+            | GameError::GameNotOngoing
+            | GameError::WrongSubBoard
+            | GameError::SubBoardClosed
+            | GameError::NoPlayerInSeat => Status::failed_precondition(message),
+            GameError::InvalidPosition => Status::out_of_range(message),
+            GameError::InvalidArgument(_) | GameError::InvalidChosenSymbol => Status::invalid_argument(message),
+            GameError::Overloaded | GameError::ShuttingDown => Status::unavailable(message),
+            GameError::AuthError(_) => Status::unauthenticated(message),
+            GameError::Internal(_) => Status::internal(message),
+        }
+    }
+}
@@ -0,0 +1,275 @@
+//! 게임 이벤트 로그 (`--event-log`)
+//!
+//! 게임 시작, 수, 스왑, 게임 종료, 플레이어 접속 끊김을 일어날 때마다 JSON 한 줄로 파일 끝에 덧붙여 서버를 다시
+//! 시작한 뒤에도 게임을 되짚을 수 있게 합니다. 끝난 게임 기록(`--record`)처럼 채널로 보내 전용 스레드가 쓰며,
+//! 파일이 설정한 크기를 넘으면 `PATH.1`, `PATH.2`, ... 로 밀어내고 새 파일에 씁니다.
+//! `ReplayGame` RPC는 이 로그로 게임의 상태를 차례대로 다시 만들어 보냅니다.
+
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{instrument, warn};
+
+use crate::tictactoe::{GameState, Move};
+
+/// 쓰기를 기다릴 수 있는 이벤트 수 (넘치면 버리고 경고)
+const EVENT_QUEUE: usize = 4096;
+/// 밀어낸 뒤 남겨 두는 이전 로그 파일 수 (`PATH.1` ~ `PATH.N`, 숫자가 클수록 오래됨)
+pub const EVENT_LOG_BACKUPS: usize = 3;
+/// 로그 파일 하나의 기본 최대 크기 (바이트)
+pub const DEFAULT_EVENT_LOG_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// 게임 이벤트 (JSON의 "event" 필드로 종류를 구분)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum GameEvent {
+    /// 두 자리가 모두 차 게임이 시작됨
+    GameStarted {
+        board_size: usize,
+        win_length: usize,
+        first_player: String,
+        players: Vec<EventPlayer>,
+    },
+    /// 받아들인 수 (`mark`는 보드에 놓은 심볼, 이 항목이 없던 이전 로그에서는 빈 문자열이며 `symbol`을 놓은 수)
+    MoveMade {
+        symbol: String,
+        position: u8,
+        #[serde(default)]
+        mark: String,
+    },
+    /// 스왑 규칙으로 `symbol`이 첫 수를 가져감 (`position`의 돌이 `symbol`로 바뀌고 차례가 상대에게 넘어감)
+    Swapped { symbol: String, position: u8 },
+    /// 게임이 끝남 (최종 상태: "X_win", "draw_agreed", "idle_timeout" 등)
+    GameEnded { status: String },
+    /// 플레이어의 스트림이 끊김 (재접속 유예가 시작됨)
+    PlayerDisconnected { symbol: String },
+    /// 플레이어가 보낸 채팅 (정리를 거친 문자열, 게임 상태는 바뀌지 않음)
+    Chat { sender: String, text: String },
+}
+
+/// 게임 시작 이벤트에 남기는 플레이어 (player_id가 없으면 빈 문자열)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventPlayer {
+    pub symbol: String,
+    pub player_id: String,
+    pub bot: bool,
+}
+
+/// 로그 한 줄
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventRecord {
+    pub timestamp_unix_ms: i64,
+    pub game_id: String,
+    #[serde(flatten)]
+    pub event: GameEvent,
+}
+
+impl EventRecord {
+    /// 지금 일어난 이벤트
+    pub fn now(game_id: &str, event: GameEvent) -> Self {
+        let timestamp_unix_ms = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis() as i64);
+        EventRecord { timestamp_unix_ms, game_id: game_id.to_string(), event }
+    }
+}
+
+/// 로그 쓰기 스레드에 보내는 요청
+enum Command {
+    Write(Box<EventRecord>),
+    /// 앞서 보낸 이벤트를 모두 쓰고 디스크로 내보낸 뒤 응답
+    Flush(oneshot::Sender<()>),
+}
+
+/// 이벤트 로그 파일 쓰기 스레드로 이벤트를 보내는 핸들 (`Arc`로 서비스와 게임이 함께 씀)
+pub struct GameEventLogger {
+    tx: mpsc::Sender<Command>,
+    path: PathBuf,
+}
+
+impl GameEventLogger {
+    /// 로그 파일을 덧붙이기 모드로 열고 (없으면 만듦) 쓰기 스레드를 시작. 파일이 `max_bytes`를 넘으면 밀어냅니다.
+    pub fn open(path: &Path, max_bytes: u64) -> io::Result<Self> {
+        let file = open_append(path)?;
+        let written = file.metadata()?.len();
+        let (tx, rx) = mpsc::channel(EVENT_QUEUE);
+        let writer = LogWriter { out: BufWriter::new(file), path: path.to_path_buf(), written, max_bytes };
+        std::thread::Builder::new().name("game-event-log".into()).spawn(move || writer.run(rx))?;
+        Ok(GameEventLogger { tx, path: path.to_path_buf() })
+    }
+
+    /// 이벤트를 기록하도록 보냄 (기다리지 않음)
+    pub fn log(&self, game_id: &str, event: GameEvent) {
+        if let Err(e) = self.tx.try_send(Command::Write(Box::new(EventRecord::now(game_id, event)))) {
+            warn!(%game_id, error = %e, "게임 이벤트 전송 실패, 이벤트를 버림");
+        }
+    }
+
+    /// 지금까지 보낸 이벤트가 모두 파일에 쓰일 때까지 대기
+    pub async fn flush(&self) {
+        let (ack, done) = oneshot::channel();
+        if self.tx.send(Command::Flush(ack)).await.is_ok() {
+            let _ = done.await;
+        }
+    }
+
+    /// 지금까지 보낸 이벤트를 파일에 쓴 뒤 `game_id`의 마지막 게임을 다시 만듦
+    /// (서버를 다시 시작하면 게임 ID가 다시 쓰이므로 마지막 GameStarted부터 봄, 없으면 None)
+    #[instrument(level = "debug", skip(self))]
+    pub async fn replay(&self, game_id: &str) -> io::Result<Option<Vec<GameState>>> {
+        self.flush().await;
+        let path = self.path.clone();
+        let game_id = game_id.to_string();
+        tokio::task::spawn_blocking(move || read_game(&path, &game_id).map(|events| replay(&game_id, &events)))
+            .await
+            .map_err(io::Error::other)?
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| io::Error::new(e.kind(), format!("게임 이벤트 로그 파일을 열 수 없습니다 ({}): {}", path.display(), e)))
+}
+
+/// 밀어낸 `n`번째 이전 로그 파일 (`PATH.n`)
+pub fn backup_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+/// 로그 쓰기 스레드의 상태
+struct LogWriter {
+    out: BufWriter<File>,
+    path: PathBuf,
+    written: u64, // 현재 파일 크기
+    max_bytes: u64,
+}
+
+impl LogWriter {
+    /// 이벤트마다 한 줄씩 쓰고 바로 디스크로 내보냄
+    fn run(mut self, mut rx: mpsc::Receiver<Command>) {
+        while let Some(command) = rx.blocking_recv() {
+            let result = match command {
+                Command::Write(record) => self.write(&record),
+                Command::Flush(ack) => {
+                    let result = self.out.flush();
+                    let _ = ack.send(());
+                    result
+                }
+            };
+            if let Err(e) = result {
+                warn!(path = %self.path.display(), error = %e, "게임 이벤트 로그 쓰기 실패");
+            }
+        }
+    }
+
+    fn write(&mut self, record: &EventRecord) -> io::Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        if self.written > 0 && self.written + line.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        self.out.write_all(&line)?;
+        self.out.flush()?;
+        self.written += line.len() as u64;
+        Ok(())
+    }
+
+    /// 현재 파일을 `PATH.1`로 밀어내고 (가장 오래된 파일은 지움) 새 파일을 엶
+    fn rotate(&mut self) -> io::Result<()> {
+        self.out.flush()?;
+        for n in (1..EVENT_LOG_BACKUPS).rev() {
+            let from = backup_path(&self.path, n);
+            if from.exists() {
+                std::fs::rename(&from, backup_path(&self.path, n + 1))?;
+            }
+        }
+        std::fs::rename(&self.path, backup_path(&self.path, 1))?;
+        self.out = BufWriter::new(open_append(&self.path)?);
+        self.written = 0;
+        Ok(())
+    }
+}
+
+/// 이전 로그 파일부터 현재 파일까지 읽어 `game_id`의 마지막 게임 이벤트를 모음 (읽을 수 없는 줄은 건너뜀)
+pub fn read_game(path: &Path, game_id: &str) -> io::Result<Vec<GameEvent>> {
+    let files = (1..=EVENT_LOG_BACKUPS).rev().map(|n| backup_path(path, n)).chain([path.to_path_buf()]);
+    let mut events = Vec::new();
+    for file in files {
+        let file = match File::open(&file) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        for line in BufReader::new(file).lines() {
+            let Ok(record) = serde_json::from_str::<EventRecord>(&line?) else {
+                continue;
+            };
+            if record.game_id != game_id {
+                continue;
+            }
+            if matches!(record.event, GameEvent::GameStarted { .. }) {
+                events.clear();
+            }
+            events.push(record.event);
+        }
+    }
+    Ok(events)
+}
+
+/// 이벤트마다 그 시점의 게임 상태를 만듦 (GameStarted로 시작하지 않으면 None)
+pub fn replay(game_id: &str, events: &[GameEvent]) -> Option<Vec<GameState>> {
+    let Some(GameEvent::GameStarted { board_size, win_length, first_player, .. }) = events.first() else {
+        return None;
+    };
+    let mut state = GameState {
+        board: vec![String::new(); board_size * board_size],
+        next_player: first_player.clone(),
+        status: "ongoing".into(),
+        game_id: game_id.to_string(),
+        board_size: *board_size as i32,
+        win_length: *win_length as i32,
+        x_symbol: "X".into(),
+        o_symbol: "O".into(),
+        full_board: true,
+        ..GameState::default()
+    };
+    let mut snapshots = Vec::with_capacity(events.len());
+    // 채팅은 게임 상태를 바꾸지 않으므로 다시 보기에서 뺌
+    let events: Vec<&GameEvent> = events.iter().filter(|event| !matches!(event, GameEvent::Chat { .. })).collect();
+    for (number, event) in events.into_iter().enumerate() {
+        state.move_number = number as u32 + 1;
+        state.info_message.clear();
+        match event {
+            GameEvent::GameStarted { .. } => {}
+            GameEvent::MoveMade { symbol, position, mark } => {
+                let mark = if mark.is_empty() { symbol } else { mark };
+                if let Some(cell) = state.board.get_mut(*position as usize) {
+                    *cell = mark.clone();
+                }
+                state.history.push(Move { player_id: symbol.clone(), position: i32::from(*position), chosen_symbol: mark.clone() });
+                state.next_player = if symbol == "X" { "O".into() } else { "X".into() };
+            }
+            GameEvent::Swapped { symbol, position } => {
+                if let Some(cell) = state.board.get_mut(*position as usize) {
+                    *cell = symbol.clone();
+                }
+                if let Some(first) = state.history.iter_mut().find(|m| m.position == i32::from(*position)) {
+                    first.player_id = symbol.clone();
+                    first.chosen_symbol = symbol.clone();
+                }
+                state.next_player = if symbol == "X" { "O".into() } else { "X".into() };
+            }
+            GameEvent::GameEnded { status } => state.status = status.clone(),
+            GameEvent::PlayerDisconnected { symbol } => state.info_message = format!("Player {} disconnected.", symbol),
+            GameEvent::Chat { .. } => {}
+        }
+        snapshots.push(state.clone());
+    }
+    Some(snapshots)
+}
@@ -0,0 +1,100 @@
+//! 게임 한 판의 이벤트 모델
+//!
+//! `SharedGame`은 상태를 바꿀 때마다 `GameEvent`를 순서대로 쌓습니다. 쌓인 이벤트를 처음부터
+//! `GameState::apply`로 접으면 보드, 차례, 상태를 언제든 다시 만들 수 있어 다시 보기나 감사, 무르기의
+//! 바탕이 되며, `GetHistory` RPC가 이벤트 목록을 그대로 보냅니다.
+//! (서버 밖 파일에 남기는 감사용 기록은 `event_log`가 따로 맡습니다.)
+
+use crate::tictactoe::{history_event, GameState, HistoryEvent, Move};
+
+/// 게임 상태를 바꾼 일 하나
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameEvent {
+    /// 빈 보드로 게임이 만들어짐 (초기화해도 이 이벤트부터 다시 쌓임)
+    GameCreated { size: usize, first_player: String },
+    /// 자리에 플레이어(또는 봇)가 앉음 (재접속은 포함하지 않음)
+    PlayerJoined { symbol: String, marker: String },
+    /// 받아들인 수 (`mark`는 보드에 놓은 심볼, 와일드가 아니면 `symbol`과 같음)
+    MoveMade { symbol: String, pos: usize, mark: String },
+    /// 스왑 규칙으로 `symbol`이 첫 수(`pos`)를 가져감 (돌이 `symbol`로 바뀌고 차례가 상대에게 넘어감)
+    Swapped { symbol: String, pos: usize },
+    /// 게임이 끝남 (최종 상태: "X_win", "draw_agreed", "idle_timeout" 등)
+    GameEnded { outcome: String },
+}
+
+impl GameEvent {
+    pub fn to_proto(&self) -> HistoryEvent {
+        let event = match self {
+            GameEvent::GameCreated { size, first_player } => history_event::Event::GameCreated(history_event::GameCreated {
+                size: *size as i32,
+                first_player: first_player.clone(),
+            }),
+            GameEvent::PlayerJoined { symbol, marker } => history_event::Event::PlayerJoined(history_event::PlayerJoined {
+                symbol: symbol.clone(),
+                marker: marker.clone(),
+            }),
+            GameEvent::MoveMade { symbol, pos, mark } => history_event::Event::MoveMade(Move {
+                player_id: symbol.clone(),
+                position: *pos as i32,
+                chosen_symbol: mark.clone(),
+            }),
+            GameEvent::Swapped { symbol, pos } => history_event::Event::Swapped(history_event::Swapped {
+                symbol: symbol.clone(),
+                position: *pos as i32,
+            }),
+            GameEvent::GameEnded { outcome } => history_event::Event::GameEnded(history_event::GameEnded { outcome: outcome.clone() }),
+        };
+        HistoryEvent { event: Some(event) }
+    }
+}
+
+impl GameState {
+    /// 이벤트 하나를 반영한 새 상태 (입력은 바꾸지 않음). 두 자리가 모두 차면 "ongoing"이 되고,
+    /// 게임 결과는 GameEnded가 정하므로 이 함수는 승패를 판정하지 않습니다.
+    pub fn apply(&self, event: &GameEvent) -> GameState {
+        let mut next = self.clone();
+        match event {
+            GameEvent::GameCreated { size, first_player } => {
+                next = GameState {
+                    game_id: self.game_id.clone(),
+                    board: vec![String::new(); size * size],
+                    board_size: *size as i32,
+                    next_player: first_player.clone(),
+                    status: "waiting".into(),
+                    ..GameState::default()
+                };
+            }
+            GameEvent::PlayerJoined { symbol, marker } => {
+                let seat = if symbol == "X" { &mut next.x_symbol } else { &mut next.o_symbol };
+                *seat = marker.clone();
+                if next.status == "waiting" && !next.x_symbol.is_empty() && !next.o_symbol.is_empty() {
+                    next.status = "ongoing".into();
+                }
+            }
+            GameEvent::MoveMade { symbol, pos, mark } => {
+                if let Some(cell) = next.board.get_mut(*pos) {
+                    *cell = mark.clone();
+                }
+                next.history.push(Move { player_id: symbol.clone(), position: *pos as i32, chosen_symbol: mark.clone() });
+                next.next_player = if symbol == "X" { "O".into() } else { "X".into() };
+            }
+            GameEvent::Swapped { symbol, pos } => {
+                if let Some(cell) = next.board.get_mut(*pos) {
+                    *cell = symbol.clone();
+                }
+                if let Some(first) = next.history.iter_mut().find(|m| m.position == *pos as i32) {
+                    first.player_id = symbol.clone();
+                    first.chosen_symbol = symbol.clone();
+                }
+                next.next_player = if symbol == "X" { "O".into() } else { "X".into() };
+            }
+            GameEvent::GameEnded { outcome } => next.status = outcome.clone(),
+        }
+        next
+    }
+}
+
+/// 이벤트를 처음부터 접어 만든 상태
+pub fn fold(events: &[GameEvent]) -> GameState {
+    events.iter().fold(GameState::default(), |state, event| state.apply(event))
+}
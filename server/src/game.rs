@@ -0,0 +1,1680 @@
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::task::AbortHandle;
+use tokio::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+use tonic::Status;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use rand::Rng;
+use tracing::{debug, info, instrument, warn};
+
+use crate::board;
+use crate::board_diff::DiffEncoder;
+use crate::bot::{self, opponent_of};
+use crate::chat::{self, ChatLimiter};
+use crate::config::Config;
+use crate::egress::stream_end;
+use crate::elo::RatedResult;
+use crate::error::{ChatError, GameError, MoveError};
+use crate::event_log::{EventPlayer, GameEvent, GameEventLogger};
+use crate::events;
+use crate::metrics::Metrics;
+use crate::presets::{GameOptions, DEFAULT_PRESET};
+use crate::rate_limit::TokenBucket;
+use crate::record::unix_secs;
+use crate::tictactoe::{BotDifficulty, EndReason, GameDetail, GameMode, GameState, Join, Move, SeatDetail};
+use crate::ultimate;
+use common::state_hash::state_hash;
+use common::text;
+
+/// 클라이언트 스트림으로 업데이트(또는 스트림을 끝내는 오류)를 보내는 채널
+pub type UpdateSender = mpsc::Sender<Result<GameState, Status>>;
+
+/// 봇 대전이 시작될 때 플레이어에게 보내는 안내 문구
+const HOUSE_BOT_MESSAGE: &str = "no opponent found — playing the house bot";
+/// 대기 제한 시간이 지나 게임을 닫을 때 혼자 남은 플레이어에게 보내는 오류 문구
+const WAITING_TIMEOUT_MESSAGE: &str = "No opponent joined in time. The game was closed.";
+
+/// 각 플레이어의 연결 정보를 저장합니다.
+#[derive(Clone)]
+pub struct PlayerConnection {
+    pub symbol: String,              // "X" 또는 "O"
+    pub tx: UpdateSender,            // 업데이트 전송 채널
+    pub session_token: String,       // 재접속용 세션 토큰
+    pub connection_id: u64,          // 현재 연결 번호 (재접속할 때마다 바뀜)
+    pub connected: bool,             // 스트림이 살아 있는지 여부
+    pub is_bot: bool,                // 서버 봇이 차지한 자리인지 여부
+    pub player_id: String,           // 레이팅을 기록할 플레이어 ID (비어 있으면 반영하지 않음)
+    pub rating: Option<i32>,         // 참가할 때의 레이팅 (player_id가 없으면 None)
+    pub last_heartbeat: Instant,     // 클라이언트에게서 마지막으로 메시지(하트비트 포함)를 받은 시각
+    pub chat: ChatLimiter,           // 채팅 빈도 제한 (스트림과 SendChat이 같이 쓰고, 재접속해도 이어짐)
+    pub moves: Option<TokenBucket>,  // 수 빈도 제한 (첫 수에 설정값으로 만들고, 스트림과 SubmitMove가 같이 씀)
+    pub board_diff: DiffEncoder,     // 보드 차이 전송 (Join.board_diffs, 연결할 때마다 새로 시작)
+}
+
+impl PlayerConnection {
+    fn new(symbol: &str, tx: UpdateSender, connection_id: u64) -> Self {
+        PlayerConnection {
+            symbol: symbol.to_string(),
+            tx,
+            session_token: generate_session_token(),
+            connection_id,
+            connected: true,
+            is_bot: false,
+            player_id: String::new(),
+            rating: None,
+            last_heartbeat: Instant::now(),
+            chat: ChatLimiter::default(),
+            moves: None,
+            board_diff: DiffEncoder::default(),
+        }
+    }
+
+    /// 매치메이킹으로 배정되어 아직 Play로 접속하지 않은 자리 (세션 토큰으로 재접속하면 연결됨)
+    fn awaiting(symbol: &str, connection_id: u64) -> Self {
+        let (tx, _) = mpsc::channel(1);
+        PlayerConnection { connected: false, ..PlayerConnection::new(symbol, tx, connection_id) }
+    }
+
+    /// 서버 봇 자리 (실제 클라이언트가 없으므로 전송 채널은 닫혀 있음)
+    fn house_bot(symbol: &str) -> Self {
+        let (tx, _) = mpsc::channel(1);
+        PlayerConnection {
+            symbol: symbol.to_string(),
+            tx,
+            session_token: String::new(),
+            connection_id: 0,
+            connected: true,
+            is_bot: true,
+            player_id: String::new(),
+            rating: None,
+            last_heartbeat: Instant::now(),
+            chat: ChatLimiter::default(),
+            moves: None,
+            board_diff: DiffEncoder::default(),
+        }
+    }
+
+    /// Join에 담긴 플레이어 ID와 그 레이팅 기록 (재접속하며 ID를 비워 보내면 이전 값 유지)
+    fn identify(&mut self, player_id: &str, rating: Option<i32>) {
+        if !player_id.is_empty() {
+            self.player_id = player_id.to_string();
+            self.rating = rating;
+        }
+    }
+}
+
+/// 관리자가 강제로 끝낸 게임의 상태
+pub const ADMIN_TERMINATED: &str = "admin_terminated";
+
+/// 관리자가 사유 없이 게임을 끝냈을 때 플레이어에게 보내는 안내 문구
+const ADMIN_TERMINATED_MESSAGE: &str = "This game was ended by a server administrator.";
+
+/// 관리자가 사유 없이 게임을 초기화했을 때 플레이어에게 보내는 안내 문구
+const ADMIN_RESET_MESSAGE: &str = "This game was reset by a server administrator.";
+
+/// 서버 종료 대기 시간 안에 끝나지 않아 서버가 끝낸 게임의 상태
+pub const SERVER_SHUTDOWN: &str = "server_shutdown";
+
+/// 서버 종료로 게임을 끝낼 때 플레이어에게 보내는 안내 문구
+const SERVER_SHUTDOWN_MESSAGE: &str = "The server shut down before this game finished.";
+
+/// 플레이어가 하트비트를 보내지 않아 서버가 끝낸 게임의 상태
+pub const IDLE_TIMEOUT: &str = "idle_timeout";
+
+/// 진행 중인 게임에서 수도 참가도 없이 abandon_timeout_ms가 지나 서버가 끝낸 게임의 상태
+pub const ABANDONED: &str = "abandoned";
+
+/// 방치된 게임을 끝낼 때 플레이어에게 보내는 안내 문구
+const ABANDONED_MESSAGE: &str = "No moves were made for too long. The game was abandoned.";
+
+/// 자리를 받고 제한 시간 안에 아무 메시지도 보내지 않아 자리를 잃은 연결에 보내는 상태
+pub const JOIN_TIMEOUT: &str = "join_timeout";
+
+/// 자리를 잃은 연결에 보내는 안내 문구
+const JOIN_TIMEOUT_MESSAGE: &str = "No message was received after joining. The connection was closed.";
+
+/// 승패(기권 포함)나 무승부(합의 포함)로 끝났거나 관리자, 서버 종료, 응답 없는 플레이어, 방치 때문에 끝난
+/// 게임의 상태 문자열인지 검사
+pub fn is_finished_status(status: &str) -> bool {
+    status.contains("_win")
+        || status.starts_with("draw")
+        || status == ADMIN_TERMINATED
+        || status == SERVER_SHUTDOWN
+        || status == IDLE_TIMEOUT
+        || status == ABANDONED
+}
+
+/// 추측하기 어려운 128비트 무작위 세션 토큰 생성
+pub(crate) fn generate_session_token() -> String {
+    format!("{:032x}", rand::thread_rng().gen::<u128>())
+}
+
+/// 연결이 끊긴 순간의 자리 정보 (재접속 유예 뒤 정리할 때 같은 게임, 같은 연결인지 확인하는 데 씀)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Departure {
+    pub connection_id: u64,
+    pub generation: u64,
+    /// 연결이 끊긴 순간 게임이 이미 끝나 있었는지 (끝난 게임은 재접속을 기다리지 않음, 몇 판 승부의 판 사이는 제외)
+    pub finished: bool,
+}
+
+/// 이벤트 로그에 이미 남긴 진행 상황
+#[derive(Debug, Default)]
+struct LoggedEvents {
+    started: bool,
+    moves: usize, // 남긴 수의 개수 (history 앞부분)
+    ended: bool,
+}
+
+/// 받아들인 수의 결과
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveOutcome {
+    /// 게임이 계속됨 (차례가 상대에게 넘어감)
+    Continue,
+    /// 게임이 끝나 `winner`가 이김 (완성된 줄의 칸 번호, 얼티밋은 큰 보드에서 이은 작은 보드 번호).
+    /// 미제르에서는 줄을 이은 쪽의 상대가 승자입니다.
+    Won { winner: String, line: Vec<usize> },
+    /// 보드가 가득 차 (얼티밋은 모든 작은 보드의 승부가 나) 무승부
+    Draw,
+}
+
+/// 몇 판 승부로 할 수 있는 최대 판 수 (`best_of`)
+pub const MAX_BEST_OF: u32 = 99;
+
+/// `best_of` 검사: 0(제한 없음)이거나 `MAX_BEST_OF` 이하의 홀수
+pub fn validate_best_of(best_of: u32) -> Result<(), String> {
+    if best_of == 0 || (best_of % 2 == 1 && best_of <= MAX_BEST_OF) {
+        Ok(())
+    } else {
+        Err(format!("best_of는 0(제한 없음)이거나 {} 이하의 홀수여야 합니다: {}", MAX_BEST_OF, best_of))
+    }
+}
+
+/// 이름을 정하지 않은 자리의 표시 이름 ("Player X", "Player O")
+pub fn default_player_name(symbol: &str) -> String {
+    format!("Player {}", symbol)
+}
+
+/// Join.player_name 검사: 앞뒤 공백을 떼고 20자까지 자른 이름 (비어 있으면 None)
+pub fn check_player_name(name: &str) -> Result<Option<String>, GameError> {
+    text::player_name(name).map_err(|invalid| {
+        GameError::InvalidArgument(format!(
+            "player_name에는 ASCII 출력 가능 문자만 쓸 수 있습니다 ({}자까지): {:?}",
+            text::MAX_PLAYER_NAME_LEN,
+            invalid
+        ))
+    })
+}
+
+/// 같은 두 플레이어가 한 자리에서 이어 둔 판들의 점수 (한 판 더 해도 이어지고, 자리 주인이 바뀌면 0부터)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MatchScore {
+    pub x_wins: u32,
+    pub o_wins: u32,
+    pub draws: u32,
+}
+
+impl MatchScore {
+    /// 끝난 판 하나 반영: 기권과 응답 없음(`timed_out`이 응답하지 않은 쪽)은 상대의 승리로 세고,
+    /// 관리자나 서버 종료로 끝난 판은 세지 않습니다.
+    pub fn record(&mut self, status: &str, timed_out: Option<&str>) {
+        let winner = match status {
+            IDLE_TIMEOUT => timed_out.map(opponent_of),
+            status if status.starts_with("draw") => {
+                self.draws += 1;
+                return;
+            }
+            status => status.strip_suffix("_win").or_else(|| status.strip_suffix("_win_by_resignation")),
+        };
+        match winner {
+            Some("X") => self.x_wins += 1,
+            Some("O") => self.o_wins += 1,
+            _ => {}
+        }
+    }
+
+    /// 센 판 수
+    pub fn games_played(&self) -> u32 {
+        self.x_wins + self.o_wins + self.draws
+    }
+
+    /// `best_of`판 승부에서 과반을 이긴 쪽 (제한이 없거나 아직 정해지지 않았으면 None)
+    pub fn winner(&self, best_of: u32) -> Option<&'static str> {
+        if best_of == 0 {
+            None
+        } else if self.x_wins * 2 > best_of {
+            Some("X")
+        } else if self.o_wins * 2 > best_of {
+            Some("O")
+        } else {
+            None
+        }
+    }
+}
+
+/// 서버가 받아들인 수 하나 (사후 분석용 기보)
+#[derive(Debug, Clone)]
+pub struct RecordedMove {
+    pub symbol: String,
+    pub position: u8,
+    pub played_at: SystemTime,
+    pub mark: String, // 보드에 놓은 심볼 (와일드가 아니면 `symbol`과 같음)
+}
+
+/// 게임의 전체 상태를 저장하는 구조체입니다.
+pub struct SharedGame {
+    pub game_id: String,          // 게임 ID
+    pub created_at: SystemTime,   // 게임 생성 시각
+    pub board: Vec<String>,       // 행 우선 순서의 board_size² 칸 보드 (각 칸: "", "X", "O")
+    pub board_size: usize,        // 보드 한 변의 칸 수
+    pub win_length: usize,        // 이기기 위해 연속으로 놓아야 하는 수
+    pub next_player: String,      // 다음 차례 ("X" 또는 "O")
+    pub first_player: String,     // 이 게임에서 먼저 두는 쪽 ("X" 또는 "O", 초기화하면 다시 이 차례부터)
+    pub status: String,           // "waiting", "searching", "ongoing", "X_win", "O_win", "draw", "{X,O}_win_by_resignation", "draw_agreed", "admin_terminated", "server_shutdown", "idle_timeout", "abandoned"
+    pub rated: bool,              // 레이팅 반영 여부 (봇 대전은 비레이팅)
+    pub player_x: Option<PlayerConnection>,
+    pub player_o: Option<PlayerConnection>,
+    pub pending_draw_offer: Option<String>, // 무승부를 제안하고 응답을 기다리는 플레이어의 심볼
+    pub history: Vec<RecordedMove>, // 받아들인 수 (둔 순서대로)
+    pub preset: String,           // 게임을 만들 때 사용한 프리셋 이름
+    pub options: GameOptions,     // 프리셋과 덮어쓰기를 적용한 최종 옵션
+    pub private: bool,            // CreateGame으로 만든 게임 (자동 매칭에서 제외, ID로만 참가)
+    pub invite_only: bool,        // CreatePrivateGame으로 만든 게임 (O 자리는 초대 코드로만 참가)
+    pub invite_code: String,      // 비공개 게임의 초대 코드 (만든 플레이어에게 표시)
+    pub reserved: Option<[String; 2]>, // 토너먼트 경기처럼 X, O 자리에 앉을 player_id가 정해진 게임
+    pub bot_difficulty: BotDifficulty, // 빠른 대전에서 상대가 없을 때 앉힐 서버 봇의 난이도
+    pub move_number: u32,         // 상태가 바뀌어 업데이트를 보낼 때마다 늘어나는 번호 (처음 상태는 0)
+    pub player_x_symbol: String,  // 보드에서 X 자리를 표시할 글자 (그래핌 하나, 기본 "X")
+    pub player_o_symbol: String,  // 보드에서 O 자리를 표시할 글자 (그래핌 하나, 기본 "O")
+    pub player_x_name: String,    // X 자리 플레이어의 표시 이름 (Join.player_name, 기본 "Player X")
+    pub player_o_name: String,    // O 자리 플레이어의 표시 이름 (Join.player_name, 기본 "Player O")
+    pub timed_out: Option<String>, // "idle_timeout"으로 끝났을 때 응답하지 않은 플레이어의 심볼
+    pub best_of: u32,             // 몇 판 승부인지 (0이면 제한 없음, 방을 만든 사람이 정함)
+    pub auto_rematch: bool,       // 몇 판 승부에서 판이 끝나면 한 판 더 요청 없이 다음 판을 시작하는지
+    pub swap_rule: bool,          // 첫 수가 놓인 직후 상대가 Swap으로 그 수를 가져갈 수 있는지 (만들 때 정함, 와일드 제외)
+    pub mode: GameMode,           // 게임 방식 (얼티밋, 미제르, 와일드 규칙은 proto의 GameMode 참고, 만들 때 정함)
+    pub score: MatchScore,        // 지금 두 자리의 플레이어가 이어 둔 판들의 점수
+    pub rematch_requested: Option<String>, // 한 판 더 하자고 하고 상대를 기다리는 플레이어의 심볼
+    pub event_log: Option<Arc<GameEventLogger>>, // 게임 이벤트를 남길 로그 (--event-log가 없으면 None)
+    pub metrics: Arc<Metrics>,    // 건너뛴 업데이트를 셀 서버 지표 (매니저가 만든 게임이 아니면 이 게임만의 지표)
+    logged: LoggedEvents,         // 이벤트 로그에 이미 남긴 진행 상황
+    events: Vec<events::GameEvent>, // 게임을 만든(초기화한) 뒤 상태를 바꾼 이벤트 (접으면 보드와 상태가 됨)
+    spectators: Vec<Spectator>,   // 관전자 연결
+    next_connection_id: u64,      // 연결 번호 발급용 카운터 (초기화해도 되돌리지 않아 번호가 겹치지 않음)
+    generation: u64,              // 게임을 초기화할 때마다 늘어나는 세대 (정리 태스크가 같은 게임인지 확인)
+    waiting_timer: Option<AbortHandle>, // 상대를 기다리는 제한 시간 타이머 (상대가 앉으면 취소)
+    pub current_turn_cancel: Option<CancellationToken>, // 지금 차례의 제한 시간 타이머 (올바른 수가 오거나 게임이 끝나면 취소)
+    pub turn_timeout: Option<Duration>, // 차례 제한 시간 (업데이트의 남은 시간 계산용, 타이머를 켤 때 설정값으로 맞춤)
+    turn_started: Option<Instant>, // 지금 차례의 타이머를 켠 시각
+    pub lost_on_time: Option<String>, // 차례 제한 시간을 넘겨 기권패한 플레이어의 심볼
+    last_progress: Instant,       // 마지막으로 수를 받아들이거나 플레이어가 참가(재접속 포함)한 시각
+    abandon_cancel: Option<CancellationToken>, // 방치된 게임을 끝내는 타이머 (게임이 끝나면 취소)
+}
+
+impl SharedGame {
+    /// 초기 게임 상태 생성 (size×size 보드, win_length개 연속이면 승리)
+    pub fn new(game_id: String, size: usize, win_length: usize) -> Self {
+        SharedGame {
+            game_id,
+            created_at: SystemTime::now(),
+            board: vec!["".into(); size * size],
+            board_size: size,
+            win_length,
+            next_player: "X".into(),
+            first_player: "X".into(),
+            status: "waiting".into(),
+            rated: false,
+            player_x: None,
+            player_o: None,
+            pending_draw_offer: None,
+            history: Vec::new(),
+            preset: DEFAULT_PRESET.to_string(),
+            options: GameOptions::default(),
+            private: false,
+            invite_only: false,
+            invite_code: String::new(),
+            reserved: None,
+            bot_difficulty: BotDifficulty::Medium,
+            move_number: 0,
+            player_x_symbol: "X".into(),
+            player_o_symbol: "O".into(),
+            player_x_name: default_player_name("X"),
+            player_o_name: default_player_name("O"),
+            timed_out: None,
+            best_of: 0,
+            auto_rematch: false,
+            swap_rule: false,
+            mode: GameMode::Classic,
+            score: MatchScore::default(),
+            rematch_requested: None,
+            event_log: None,
+            metrics: Arc::default(),
+            logged: LoggedEvents::default(),
+            events: vec![events::GameEvent::GameCreated { size, first_player: "X".into() }],
+            spectators: Vec::new(),
+            next_connection_id: 0,
+            generation: 0,
+            waiting_timer: None,
+            current_turn_cancel: None,
+            turn_timeout: None,
+            turn_started: None,
+            lost_on_time: None,
+            last_progress: Instant::now(),
+            abandon_cancel: None,
+        }
+    }
+
+    /// 먼저 두는 쪽 지정 (첫 수를 두기 전에만 의미가 있음)
+    pub fn set_first_player(&mut self, symbol: &str) {
+        self.first_player = symbol.to_string();
+        self.next_player = symbol.to_string();
+        if let Some(events::GameEvent::GameCreated { first_player, .. }) = self.events.first_mut() {
+            *first_player = symbol.to_string();
+        }
+    }
+
+    /// 게임을 만든(초기화한) 뒤 상태를 바꾼 이벤트 (순서대로)
+    pub fn events(&self) -> &[events::GameEvent] {
+        &self.events
+    }
+
+    /// 자리에 플레이어를 앉히고 이벤트로 남김 (재접속은 `resume`이 기존 자리를 이어받음).
+    /// 자리 주인이 바뀌므로 점수는 0부터 다시 셉니다.
+    fn seat(&mut self, player: PlayerConnection) {
+        self.score = MatchScore::default();
+        self.rematch_requested = None;
+        let symbol = player.symbol.clone();
+        *self.seat_mut(&symbol) = Some(player);
+        let marker = if symbol == "X" { self.player_x_symbol.clone() } else { self.player_o_symbol.clone() };
+        self.events.push(events::GameEvent::PlayerJoined { symbol, marker });
+    }
+
+    /// 게임을 `outcome` 상태로 끝내고 점수에 반영한 뒤 이벤트로 남김
+    fn finish(&mut self, outcome: String) {
+        self.cancel_turn();
+        self.stop_abandon_watch();
+        self.score.record(&outcome, self.timed_out.as_deref());
+        self.status = outcome;
+        self.events.push(events::GameEvent::GameEnded { outcome: self.status.clone() });
+    }
+
+    /// 새 연결 번호 발급
+    fn issue_connection_id(&mut self) -> u64 {
+        self.next_connection_id += 1;
+        self.next_connection_id
+    }
+
+    /// 심볼에 해당하는 플레이어 연결 정보
+    pub fn player(&self, symbol: &str) -> Option<&PlayerConnection> {
+        match symbol {
+            "X" => self.player_x.as_ref(),
+            "O" => self.player_o.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// 심볼에 해당하는 플레이어 연결 정보 (수정 가능)
+    fn player_mut(&mut self, symbol: &str) -> Option<&mut PlayerConnection> {
+        match symbol {
+            "X" => self.player_x.as_mut(),
+            "O" => self.player_o.as_mut(),
+            _ => None,
+        }
+    }
+
+    /// 세션 토큰에 해당하는 사람 플레이어의 자리 (빈 토큰은 어느 자리와도 맞지 않음)
+    pub fn seat_with_token(&self, token: &str) -> Option<&PlayerConnection> {
+        [&self.player_x, &self.player_o]
+            .into_iter()
+            .flatten()
+            .find(|p| !token.is_empty() && !p.is_bot && p.session_token == token)
+    }
+
+    /// 이 게임에 해당 세션 토큰을 가진 플레이어가 있는지 검사
+    pub fn has_session(&self, token: &str) -> bool {
+        [&self.player_x, &self.player_o]
+            .into_iter()
+            .flatten()
+            .any(|p| p.session_token == token)
+    }
+
+    /// 게임 ID만으로 관전하거나 상태를 조회할 수 있는지. 초대 전용 게임은 ID가 순서대로 매겨져 짐작할 수 있으므로
+    /// 그 게임에 앉은 플레이어의 세션 토큰을 함께 보낸 경우에만 보여 줌
+    pub fn readable_by(&self, session_token: &str) -> bool {
+        !self.invite_only || (!session_token.is_empty() && self.has_session(session_token))
+    }
+
+    /// 세션 토큰으로 기존 자리에 다시 연결합니다. 새 연결 번호를 반환합니다.
+    /// 이전 연결이 아직 살아 있으면 자리를 넘겨받았다는 사유로 그 스트림을 끝냅니다.
+    fn resume(&mut self, token: &str, tx: UpdateSender, player_id: &str, rating: Option<i32>) -> Option<(String, u64)> {
+        let connection_id = self.issue_connection_id();
+        let game_id = self.game_id.clone();
+        let player = [self.player_x.as_mut(), self.player_o.as_mut()]
+            .into_iter()
+            .flatten()
+            .find(|p| p.session_token == token)?;
+        if player.connected && !player.tx.is_closed() {
+            if let Err(e) = player.tx.try_send(Err(stream_end(EndReason::SessionTakenOver, &game_id))) {
+                warn!(%game_id, player_symbol = %player.symbol, error = %e, "이전 연결에 종료 사유 전송 실패");
+            }
+        }
+        player.tx = tx;
+        player.connection_id = connection_id;
+        player.connected = true;
+        player.last_heartbeat = Instant::now();
+        player.identify(player_id, rating);
+        Some((player.symbol.clone(), connection_id))
+    }
+
+    /// Join 메시지를 처리해 자리를 배정(또는 재접속)하고 초기 상태를 전송합니다.
+    /// `rating`은 Join의 player_id에 해당하는 현재 레이팅입니다. 배정된 심볼과 연결 번호를 반환합니다.
+    pub async fn join_player(&mut self, join: &Join, tx: UpdateSender, rating: Option<i32>) -> Result<(String, u64), GameError> {
+        if !join.session_token.is_empty() {
+            let claimed_by_other = [&self.player_x, &self.player_o].into_iter().flatten().any(|p| {
+                p.session_token == join.session_token && !p.player_id.is_empty() && !join.player_id.is_empty() && p.player_id != join.player_id
+            });
+            if claimed_by_other {
+                return Err(GameError::NotYourSeat);
+            }
+            let name = check_player_name(&join.player_name)?;
+            let Some((symbol, connection_id)) = self.resume(&join.session_token, tx.clone(), &join.player_id, rating) else {
+                return Err(GameError::SessionNotFound);
+            };
+            info!(game_id = %self.game_id, player_symbol = %symbol, "플레이어 재접속");
+            if let Some(name) = name {
+                *self.name_mut(&symbol) = name;
+            }
+            // 재접속한 플레이어에게 전체 상태 스냅샷 전송
+            if let Some(player) = self.player_mut(&symbol) {
+                player.board_diff = DiffEncoder::new(join.board_diffs);
+            }
+            if let Some(player) = self.player(&symbol) {
+                self.send_to(player, self.update_for(player));
+            }
+            // 상대에게 다시 연결되었음을 알림
+            self.broadcast_update().await;
+            self.last_progress = Instant::now();
+            return Ok((symbol, connection_id));
+        }
+
+        if !join.invite_code.is_empty() {
+            // 매니저가 코드가 이 게임의 것인지 확인한 뒤에만 여기까지 옴
+            let name = check_player_name(&join.player_name)?;
+            if self.player_o.is_some() {
+                return Err(GameError::OpponentAlreadyJoined);
+            }
+            self.player_o_name = name.unwrap_or_else(|| default_player_name("O"));
+            let connection_id = self.issue_connection_id();
+            let mut player = PlayerConnection::new("O", tx, connection_id);
+            player.identify(&join.player_id, rating);
+            player.board_diff = DiffEncoder::new(join.board_diffs);
+            if !self.seat_invited(player).await {
+                return Err(GameError::OpponentAlreadyJoined);
+            }
+            self.last_progress = Instant::now();
+            return Ok(("O".to_string(), connection_id));
+        }
+
+        let preferred = match join.preferred_symbol.trim().to_ascii_uppercase().as_str() {
+            "" => None,
+            "X" => Some("X"),
+            "O" => Some("O"),
+            _ => return Err(GameError::InvalidArgument("preferred_symbol은 \"X\", \"O\" 또는 빈 값이어야 합니다.".into())),
+        };
+        let symbol = match &self.reserved {
+            // 정해진 자리에만 앉을 수 있음 (이미 앉아 있으면 재접속은 세션 토큰으로)
+            Some(reserved) => {
+                let Some(seat) = reserved.iter().position(|player_id| *player_id == join.player_id) else {
+                    return Err(GameError::NotYourSeat);
+                };
+                let symbol = if seat == 0 { "X" } else { "O" };
+                if self.player(symbol).is_some() {
+                    return Err(GameError::GameFull);
+                }
+                symbol
+            }
+            None => self.free_symbol(preferred).ok_or(GameError::GameFull)?,
+        };
+        let opponent_seated = self.player(opponent_of(symbol)).is_some();
+        if symbol == "O" && opponent_seated && self.invite_only {
+            return Err(GameError::InviteOnly);
+        }
+        let marker = self.check_marker(symbol, &join.marker)?;
+        let name = check_player_name(&join.player_name)?.unwrap_or_else(|| default_player_name(symbol));
+        let connection_id = self.issue_connection_id();
+        let mut player = PlayerConnection::new(symbol, tx, connection_id);
+        player.identify(&join.player_id, rating);
+        player.board_diff = DiffEncoder::new(join.board_diffs);
+        *self.marker_mut(symbol) = marker;
+        *self.name_mut(symbol) = name;
+        // 원하는 심볼이 이미 찼으면 남은 자리에 앉히고 그 사실을 알림
+        let note = match preferred {
+            Some(preferred) if preferred != symbol => format!("{} was taken, so you play {}.", preferred, symbol),
+            _ => String::new(),
+        };
+
+        if opponent_seated {
+            self.seat(player);
+            self.status = "ongoing".to_string();
+            self.rated = true;
+            self.cancel_waiting_timer();
+            info!(game_id = %self.game_id, player_symbol = %symbol, status = %self.status, "플레이어 할당, 게임 시작");
+            self.broadcast_update().await;
+            if !note.is_empty() {
+                if let Some(player) = self.player(symbol) {
+                    let mut update = self.update_for(player);
+                    update.info_message = note;
+                    self.send_to(player, update);
+                }
+            }
+            self.last_progress = Instant::now();
+            return Ok((symbol.to_string(), connection_id));
+        }
+
+        if join.quick_play {
+            self.status = "searching".to_string();
+            self.bot_difficulty = match join.bot_difficulty() {
+                BotDifficulty::Unspecified => BotDifficulty::Medium,
+                difficulty => difficulty,
+            };
+            info!(game_id = %self.game_id, player_symbol = %symbol, status = %self.status, "플레이어 할당, 빠른 대전 상대 찾는 중");
+        } else {
+            info!(game_id = %self.game_id, player_symbol = %symbol, "플레이어 할당");
+        }
+        let mut update = self.update_for(&player);
+        update.info_message = note;
+        self.send_to(&player, update);
+        self.seat(player);
+        self.last_progress = Instant::now();
+        Ok((symbol.to_string(), connection_id))
+    }
+
+    /// 새 플레이어에게 줄 빈 자리의 심볼. 먼저 앉는 플레이어만 원하는 심볼을 고를 수 있고
+    /// (초대 전용 게임의 O 자리는 초대받은 상대 몫), 두 번째 플레이어는 남은 자리에 앉습니다.
+    fn free_symbol(&self, preferred: Option<&str>) -> Option<&'static str> {
+        match (&self.player_x, &self.player_o) {
+            (None, None) if preferred == Some("O") && !self.invite_only => Some("O"),
+            (None, _) => Some("X"),
+            (_, None) => Some("O"),
+            _ => None,
+        }
+    }
+
+    /// Join.marker 검사: 비어 있으면 자리 심볼을 쓰고, 아니면 표시할 수 있는 그래핌 하나이면서
+    /// 상대 자리의 글자와 달라야 합니다. 자리에 쓸 글자를 반환합니다.
+    fn check_marker(&self, symbol: &str, marker: &str) -> Result<String, GameError> {
+        let marker = marker.trim();
+        if marker.is_empty() {
+            return Ok(symbol.to_string());
+        }
+        if text::grapheme_count(marker) != 1 || marker.chars().any(char::is_control) || text::display_width(marker) == 0 {
+            return Err(GameError::InvalidArgument("marker는 화면에 표시되는 한 글자(그래핌)여야 합니다.".into()));
+        }
+        let opponent_marker = if symbol == "X" { &self.player_o_symbol } else { &self.player_x_symbol };
+        if marker == opponent_marker {
+            return Err(GameError::InvalidArgument("상대와 같은 marker는 쓸 수 없습니다.".into()));
+        }
+        Ok(marker.to_string())
+    }
+
+    /// 심볼에 해당하는 자리의 표시 글자
+    fn marker_mut(&mut self, symbol: &str) -> &mut String {
+        if symbol == "X" { &mut self.player_x_symbol } else { &mut self.player_o_symbol }
+    }
+
+    /// 심볼에 해당하는 자리의 표시 이름
+    fn name_mut(&mut self, symbol: &str) -> &mut String {
+        if symbol == "X" { &mut self.player_x_name } else { &mut self.player_o_name }
+    }
+
+    /// 심볼에 해당하는 자리
+    fn seat_mut(&mut self, symbol: &str) -> &mut Option<PlayerConnection> {
+        if symbol == "X" { &mut self.player_x } else { &mut self.player_o }
+    }
+
+    /// 혼자 앉아 상대를 기다리는 플레이어 (두 자리가 모두 비었거나 찼으면 None)
+    pub fn lone_player(&self) -> Option<&PlayerConnection> {
+        match (&self.player_x, &self.player_o) {
+            (Some(player), None) | (None, Some(player)) => Some(player),
+            _ => None,
+        }
+    }
+
+    /// 매치메이킹으로 짝지어진 두 플레이어를 X, O에 앉히고 레이팅 게임을 시작합니다.
+    /// 각 자리는 세션 토큰으로 Play에 접속할 때까지 끊긴 상태이며, (심볼, 연결 번호, 초기 상태)를 반환합니다.
+    pub fn seat_matched_players(&mut self) -> [(String, u64, GameState); 2] {
+        let seats = ["X", "O"].map(|symbol| {
+            let connection_id = self.issue_connection_id();
+            let player = PlayerConnection::awaiting(symbol, connection_id);
+            (symbol.to_string(), connection_id, player)
+        });
+        let [(_, _, x), (_, _, o)] = &seats;
+        self.seat(x.clone());
+        self.seat(o.clone());
+        self.status = "ongoing".to_string();
+        self.rated = true;
+        info!(game_id = %self.game_id, status = %self.status, "매치메이킹 게임 시작");
+        seats.map(|(symbol, connection_id, player)| (symbol, connection_id, self.update_for(&player)))
+    }
+
+    /// 초대 코드로 참가한 상대를 O 자리에 앉힙니다. 자리는 세션 토큰으로 Play에 접속할 때까지 끊긴 상태이며,
+    /// X가 이미 앉아 있으면 게임을 시작합니다. (연결 번호, 초기 상태)를 반환하고, O 자리가 차 있으면 None입니다.
+    pub async fn seat_invitee(&mut self) -> Option<(u64, GameState)> {
+        let connection_id = self.issue_connection_id();
+        if !self.seat_invited(PlayerConnection::awaiting("O", connection_id)).await {
+            return None;
+        }
+        let update = self.player_o.as_ref().map(|player| self.update_for(player))?;
+        Some((connection_id, update))
+    }
+
+    /// 초대받은 플레이어를 O 자리에 앉히고 X가 이미 앉아 있으면 게임을 시작 (O 자리가 차 있으면 false)
+    async fn seat_invited(&mut self, player: PlayerConnection) -> bool {
+        if self.player_o.is_some() {
+            return false;
+        }
+        self.seat(player);
+        self.cancel_waiting_timer();
+        if self.player_x.is_some() {
+            self.status = "ongoing".to_string();
+            self.rated = true;
+            info!(game_id = %self.game_id, player_symbol = "O", status = %self.status, "초대받은 플레이어 할당, 게임 시작");
+        } else {
+            info!(game_id = %self.game_id, player_symbol = "O", "초대받은 플레이어 할당, 만든 사람 대기");
+        }
+        self.broadcast_update().await;
+        true
+    }
+
+    /// 빠른 대전 상대가 없을 때 빈 자리에 서버 봇을 앉히고 비레이팅 게임을 시작합니다.
+    pub async fn seat_house_bot(&mut self) {
+        let symbol = if self.player_x.is_none() { "X" } else { "O" };
+        self.seat(PlayerConnection::house_bot(symbol));
+        self.status = "ongoing".to_string();
+        self.rated = false;
+        info!(game_id = %self.game_id, status = %self.status, difficulty = ?self.bot_difficulty, "빠른 대전 상대 없음, 서버 봇과 게임 시작");
+        self.broadcast_message(HOUSE_BOT_MESSAGE).await;
+        // 봇이 먼저 두는 게임이면 바로 첫 수를 둠
+        self.play_bot_turns().await;
+    }
+
+    /// 봇 차례라면 봇이 수를 두고 결과를 전송합니다.
+    pub async fn play_bot_turns(&mut self) {
+        while self.status == "ongoing" {
+            let next = self.next_player.clone();
+            if !self.player(&next).is_some_and(|p| p.is_bot) {
+                break;
+            }
+            let strategy = bot::strategy(self.bot_difficulty);
+            let Some(pos) = strategy.choose_move(&self.board, self.board_size, self.win_length, &next, self.mode == GameMode::Misere) else {
+                break;
+            };
+            info!(game_id = %self.game_id, player_symbol = %next, position = pos, "봇이 수를 둠");
+            if let Err(e) = self.apply_move(&next, pos) {
+                warn!(game_id = %self.game_id, position = pos, reason = %e, "봇이 둘 수 없는 수를 고름");
+                break;
+            }
+            self.broadcast_update().await;
+        }
+    }
+
+    /// 연결이 끊긴 플레이어를 표시하고, 그 순간의 세대와 게임 상태를 반환합니다.
+    /// 이미 다른 연결로 교체되었거나 게임이 초기화되었다면 None을 반환합니다.
+    pub fn mark_disconnected(&mut self, symbol: &str, connection_id: u64) -> Option<Departure> {
+        let generation = self.generation;
+        // 몇 판 승부의 판 사이에는 다음 판을 위해 재접속을 기다림
+        let finished = self.is_finished() && !self.awaits_rematch();
+        let player = self.player_mut(symbol).filter(|p| p.connection_id == connection_id)?;
+        player.connected = false;
+        Some(Departure { connection_id, generation, finished })
+    }
+
+    /// 유예 시간이 지난 뒤에도 같은 연결이 끊긴 상태로 남아 있는지 검사
+    pub fn still_disconnected(&self, symbol: &str, connection_id: u64) -> bool {
+        self.player(symbol)
+            .is_some_and(|p| p.connection_id == connection_id && !p.connected)
+    }
+
+    /// 끊긴 연결의 자리를 정리해도 되는지 검사: 그 사이 게임이 초기화되지 않았고 같은 연결이 끊긴 채로 남아 있어야 함
+    pub fn abandoned(&self, symbol: &str, departure: &Departure) -> bool {
+        self.generation == departure.generation && self.still_disconnected(symbol, departure.connection_id)
+    }
+
+    /// 상대를 기다리는 제한 시간 타이머 등록 (이전 타이머가 있으면 취소)
+    pub fn set_waiting_timer(&mut self, timer: AbortHandle) {
+        self.cancel_waiting_timer();
+        self.waiting_timer = Some(timer);
+    }
+
+    /// 상대를 기다리는 제한 시간 타이머 취소
+    fn cancel_waiting_timer(&mut self) {
+        if let Some(timer) = self.waiting_timer.take() {
+            timer.abort();
+        }
+    }
+
+    /// 진행 중인 게임에서 사람의 차례인데 그 차례의 제한 시간 타이머가 아직 없으면 새 취소 토큰을 만들어 두고
+    /// 차례인 플레이어의 심볼과 함께 반환 (이미 타이머가 있거나 봇 차례면 None)
+    pub fn start_turn(&mut self) -> Option<(String, CancellationToken)> {
+        if self.status != "ongoing" || self.current_turn_cancel.is_some() {
+            return None;
+        }
+        if self.player(&self.next_player).is_none_or(|p| p.is_bot) {
+            return None;
+        }
+        let token = CancellationToken::new();
+        self.current_turn_cancel = Some(token.clone());
+        self.turn_started = Some(Instant::now());
+        Some((self.next_player.clone(), token))
+    }
+
+    /// 지금 차례의 제한 시간 타이머를 멈춤 (다음 차례는 `start_turn`이 새 토큰으로 시작)
+    pub fn cancel_turn(&mut self) {
+        if let Some(token) = self.current_turn_cancel.take() {
+            token.cancel();
+        }
+    }
+
+    /// 지금 차례에 남은 시간 (초, 올림). 제한이 없거나 진행 중이 아니거나 봇의 차례면 0.
+    /// 수를 받아 업데이트를 보낼 때는 다음 차례의 타이머가 아직 켜지지 않았으므로 제한 시간 전체
+    pub fn turn_seconds_left(&self) -> u32 {
+        let Some(limit) = self.turn_timeout.filter(|_| self.status == "ongoing") else {
+            return 0;
+        };
+        if self.player(&self.next_player).is_none_or(|p| p.is_bot) {
+            return 0;
+        }
+        let left = match (&self.current_turn_cancel, self.turn_started) {
+            (Some(_), Some(started)) => limit.saturating_sub(started.elapsed()),
+            _ => limit,
+        };
+        left.as_millis().div_ceil(1000) as u32
+    }
+
+    /// 진행 중인 게임에 방치 타이머가 아직 없으면 새 취소 토큰을 만들어 둠 (이미 있거나 진행 중이 아니면 None)
+    pub fn start_abandon_watch(&mut self) -> Option<CancellationToken> {
+        if self.status != "ongoing" || self.abandon_cancel.is_some() {
+            return None;
+        }
+        let token = CancellationToken::new();
+        self.abandon_cancel = Some(token.clone());
+        Some(token)
+    }
+
+    /// 방치 타이머 취소 (설정에서 꺼졌을 때도 호출해 다시 켜면 새 타이머가 시작되게 함)
+    pub fn stop_abandon_watch(&mut self) {
+        if let Some(token) = self.abandon_cancel.take() {
+            token.cancel();
+        }
+    }
+
+    /// 마지막으로 수를 받아들이거나 플레이어가 참가한 뒤 지난 시간
+    pub fn inactive_for(&self) -> Duration {
+        self.last_progress.elapsed()
+    }
+
+    /// 방치된 게임을 "abandoned"로 끝냄: 마지막 상태를 보낸 뒤 두 플레이어와 관전자의 스트림을 INACTIVE 사유로
+    /// 닫습니다. 목록에서 빼는 것은 호출한 쪽이 합니다.
+    pub async fn abandon(&mut self) {
+        let inactive_ms = self.inactive_for().as_millis() as u64;
+        self.finish(ABANDONED.to_string());
+        self.pending_draw_offer = None;
+        info!(game_id = %self.game_id, inactive_ms, "수도 참가도 없이 방치된 게임 종료");
+        self.broadcast_message(ABANDONED_MESSAGE).await;
+        self.end_streams(EndReason::Inactive).await;
+        for player in [&mut self.player_x, &mut self.player_o].into_iter().flatten() {
+            player.connected = false;
+        }
+    }
+
+    /// 차례 제한 시간 안에 수를 두지 않은 `symbol`의 기권패로 게임을 끝내고 알림
+    pub async fn forfeit_on_time(&mut self, symbol: &str) {
+        self.lost_on_time = Some(symbol.to_string());
+        self.resign(symbol);
+        info!(game_id = %self.game_id, player_symbol = %symbol, status = %self.status, "차례 제한 시간 초과, 기권패 처리");
+        self.broadcast_message(&format!("Player {} ran out of time. The game is forfeited.", symbol)).await;
+    }
+
+    /// 대기 제한 시간이 지났을 때 호출: 플레이어가 아직 접속한 채 혼자 기다리고 있으면 오류 문구를 담은
+    /// 마지막 상태를 보내고 WAITING_TIMEOUT 사유로 스트림을 끝냅니다. 게임을 닫았으면 true를 반환합니다.
+    pub async fn expire_waiting(&mut self) -> bool {
+        self.waiting_timer = None;
+        let lonely = self.status == "waiting";
+        let Some(player) = self.lone_player().filter(|p| lonely && p.connected) else {
+            return false;
+        };
+        let mut update = self.update_for(player);
+        update.error_message = WAITING_TIMEOUT_MESSAGE.to_string();
+        self.send_to(player, update);
+        self.end_streams(EndReason::WaitingTimeout).await;
+        true
+    }
+
+    /// 게임을 초기 상태로 되돌리고 두 자리를 모두 비움
+    pub fn reset(&mut self) {
+        self.cancel_waiting_timer();
+        self.cancel_turn();
+        self.stop_abandon_watch();
+        self.generation += 1;
+        self.logged = LoggedEvents::default();
+        self.player_x = None;
+        self.player_o = None;
+        self.board = vec!["".into(); self.board_size * self.board_size];
+        self.next_player = self.first_player.clone();
+        self.status = "waiting".to_string();
+        self.rated = false;
+        self.pending_draw_offer = None;
+        self.history.clear();
+        self.bot_difficulty = BotDifficulty::Medium;
+        self.player_x_symbol = "X".into();
+        self.player_o_symbol = "O".into();
+        self.player_x_name = default_player_name("X");
+        self.player_o_name = default_player_name("O");
+        self.timed_out = None;
+        self.lost_on_time = None;
+        self.score = MatchScore::default();
+        self.rematch_requested = None;
+        self.events = vec![events::GameEvent::GameCreated { size: self.board_size, first_player: self.first_player.clone() }];
+    }
+
+    /// 과반을 먼저 이겨 정해진 몇 판 승부의 승자
+    pub fn match_winner(&self) -> Option<&'static str> {
+        self.score.winner(self.best_of)
+    }
+
+    /// 몇 판 승부에서 승부가 나지 않은 채 승패나 무승부로 판이 끝나 다음 판을 기다리는 중인지
+    pub fn awaits_rematch(&self) -> bool {
+        self.best_of > 0
+            && self.match_winner().is_none()
+            && self.is_finished()
+            && ![ADMIN_TERMINATED, SERVER_SHUTDOWN, IDLE_TIMEOUT, ABANDONED].contains(&self.status.as_str())
+    }
+
+    /// 한 판 더 하자는 요청 (몇 판 승부로 만든 게임에서만). 상대도 이미 요청했거나 상대가 봇이면 같은 자리로 새 게임을 시작하고 true를
+    /// 반환합니다. 할 수 없는 상황이면 클라이언트에 보낼 오류 문구를 반환합니다.
+    pub fn request_rematch(&mut self, symbol: &str) -> Result<bool, &'static str> {
+        if self.best_of == 0 {
+            return Err("Rematches are only available in a best-of match.");
+        }
+        if self.match_winner().is_some() {
+            return Err("The match is over.");
+        }
+        if !self.awaits_rematch() {
+            return Err("You can only ask for a rematch after a finished game.");
+        }
+        let opponent = opponent_of(symbol);
+        let Some(other) = self.player(opponent).filter(|p| p.connected) else {
+            return Err("Your opponent has left.");
+        };
+        let accepted = other.is_bot || self.rematch_requested.as_deref() == Some(opponent);
+        if !accepted && self.rematch_requested.as_deref() == Some(symbol) {
+            return Err("You have already asked for a rematch.");
+        }
+        if !accepted {
+            self.rematch_requested = Some(symbol.to_string());
+            return Ok(false);
+        }
+        self.start_rematch();
+        Ok(true)
+    }
+
+    /// `auto_rematch`로 만든 몇 판 승부에서 판이 끝났으면 한 판 더 요청 없이 다음 판을 시작하고 알림 (시작했으면 true).
+    /// 두 자리 중 접속이 끊긴 쪽이 있으면 시작하지 않으며, 그때는 돌아온 뒤 `request_rematch`로 이어 둡니다.
+    pub async fn start_next_series_game(&mut self) -> bool {
+        if !self.auto_rematch || !self.awaits_rematch() {
+            return false;
+        }
+        if [&self.player_x, &self.player_o].into_iter().any(|seat| !seat.as_ref().is_some_and(|p| p.connected)) {
+            return false;
+        }
+        self.start_rematch();
+        let notice = format!("Next game of the best-of-{} match: {} moves first.", self.best_of, self.first_player);
+        self.broadcast_message(&notice).await;
+        true
+    }
+
+    /// 같은 자리와 점수로 보드를 비우고 새 게임 시작 (먼저 두는 쪽은 지난 게임과 반대)
+    fn start_rematch(&mut self) {
+        self.first_player = opponent_of(&self.first_player).to_string();
+        self.next_player = self.first_player.clone();
+        self.board = vec!["".into(); self.board_size * self.board_size];
+        self.history.clear();
+        self.pending_draw_offer = None;
+        self.rematch_requested = None;
+        self.timed_out = None;
+        self.lost_on_time = None;
+        self.logged = LoggedEvents::default();
+        self.status = "ongoing".to_string();
+        self.events = vec![events::GameEvent::GameCreated { size: self.board_size, first_player: self.first_player.clone() }];
+        for symbol in ["X", "O"] {
+            let marker = if symbol == "X" { self.player_x_symbol.clone() } else { self.player_o_symbol.clone() };
+            self.events.push(events::GameEvent::PlayerJoined { symbol: symbol.to_string(), marker });
+        }
+        info!(game_id = %self.game_id, first_player = %self.first_player, games_played = self.score.games_played(), "한 판 더, 새 게임 시작");
+    }
+
+    /// `symbol`이 `pos`에 둘 수 있는지 검사합니다. (앉아 있는 플레이어, 진행 중인 게임, 자기 차례, 보드 안의 빈 칸, 얼티밋이면 작은 보드 규칙)
+    pub fn check_move(&self, symbol: &str, pos: usize) -> Result<(), MoveError> {
+        if self.player(symbol).is_none() {
+            return Err(MoveError::NotAPlayer);
+        }
+        if self.status != "ongoing" {
+            return Err(MoveError::GameNotOngoing);
+        }
+        if self.next_player != symbol {
+            return Err(MoveError::NotYourTurn);
+        }
+        match self.board.get(pos) {
+            None => Err(MoveError::OutOfRange),
+            Some(cell) if !cell.is_empty() => Err(MoveError::CellOccupied),
+            Some(_) if self.mode == GameMode::Ultimate => ultimate::check_move(&self.board, self.last_position(), pos),
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// 마지막으로 받아들인 수의 칸
+    fn last_position(&self) -> Option<usize> {
+        self.history.last().map(|m| m.position as usize)
+    }
+
+    /// 얼티밋에서 다음 수를 둬야 하는 작은 보드 (아무 데나 둘 수 있거나, 진행 중이 아니거나, 클래식이면 None)
+    pub fn forced_board(&self) -> Option<usize> {
+        if self.mode != GameMode::Ultimate || self.status != "ongoing" {
+            return None;
+        }
+        ultimate::forced_board(&self.board, self.last_position())
+    }
+
+    /// `symbol`이 자기 심볼을 놓는 수 (`apply_move_as` 참고)
+    pub fn apply_move(&mut self, symbol: &str, pos: usize) -> Result<MoveOutcome, MoveError> {
+        self.apply_move_as(symbol, pos, symbol)
+    }
+
+    /// 수를 검증해 보드에 적용하고 승리/무승부/차례를 갱신합니다. 와일드에서는 `chosen_symbol`("X" 또는 "O")을
+    /// 놓고, 다른 방식에서는 무시하고 `symbol`을 놓습니다. 거부한 수는 아무것도 바꾸지 않으며,
+    /// 업데이트 전송은 호출하는 쪽이 합니다.
+    #[instrument(name = "apply_move", level = "debug", skip(self), fields(game_id = %self.game_id))]
+    pub fn apply_move_as(&mut self, symbol: &str, pos: usize, chosen_symbol: &str) -> Result<MoveOutcome, MoveError> {
+        self.check_move(symbol, pos)?;
+        let mark = match self.mode {
+            GameMode::Wild if chosen_symbol != "X" && chosen_symbol != "O" => return Err(MoveError::InvalidChosenSymbol),
+            GameMode::Wild => chosen_symbol,
+            _ => symbol,
+        };
+        // 보드를 바꾸기 전에 이 차례의 제한 시간 타이머부터 멈춤
+        self.cancel_turn();
+        self.last_progress = Instant::now();
+        self.board[pos] = mark.to_string();
+        self.history.push(RecordedMove {
+            symbol: symbol.to_string(),
+            position: pos as u8,
+            played_at: SystemTime::now(),
+            mark: mark.to_string(),
+        });
+        self.events.push(events::GameEvent::MoveMade { symbol: symbol.to_string(), pos, mark: mark.to_string() });
+        // 수가 놓이면 무승부 제안은 사라짐 (제안한 쪽이 두면 철회, 제안받은 쪽이 두면 거절)
+        self.pending_draw_offer = None;
+        let outcome = self.check_outcome(symbol);
+        match &outcome {
+            MoveOutcome::Won { winner, .. } => self.finish(format!("{}_win", winner)),
+            MoveOutcome::Draw => self.finish("draw".to_string()),
+            MoveOutcome::Continue => {
+                self.next_player = opponent_of(symbol).to_string();
+                return Ok(outcome);
+            }
+        }
+        info!(game_id = %self.game_id, status = %self.status, "게임 종료");
+        Ok(outcome)
+    }
+
+    /// 방금 `symbol`이 둔 수의 결과를 게임 방식에 따라 판정 (상태는 바꾸지 않음)
+    #[instrument(level = "debug", skip(self), fields(game_id = %self.game_id))]
+    fn check_outcome(&self, symbol: &str) -> MoveOutcome {
+        let won = |winner: &str, line| MoveOutcome::Won { winner: winner.to_string(), line };
+        match self.mode {
+            // 얼티밋은 큰 보드의 줄로 이기고, 모든 작은 보드의 승부가 나면 무승부
+            GameMode::Ultimate => match ultimate::winning_line(&self.board) {
+                Some(line) => won(symbol, line),
+                None if ultimate::all_decided(&self.board) => MoveOutcome::Draw,
+                None => MoveOutcome::Continue,
+            },
+            // 미제르는 줄을 이은 쪽이 지지만, 마지막 빈 칸을 채운 수였다면 클래식처럼 이김
+            GameMode::Misere => match board::winning_line(&self.board, self.board_size, self.win_length) {
+                Some(line) if self.is_full() => won(symbol, line),
+                Some(line) => won(opponent_of(symbol), line),
+                None if self.is_full() => MoveOutcome::Draw,
+                None => MoveOutcome::Continue,
+            },
+            // 와일드는 어느 심볼로든 줄을 이은 쪽이 이김
+            GameMode::Classic | GameMode::Wild => match board::winning_line(&self.board, self.board_size, self.win_length) {
+                Some(line) => won(symbol, line),
+                None if self.is_full() => MoveOutcome::Draw,
+                None => MoveOutcome::Continue,
+            },
+        }
+    }
+
+    /// 기권한 플레이어의 상대를 승자로 하여 게임을 끝냅니다. (보드는 그대로 유지)
+    pub fn resign(&mut self, symbol: &str) {
+        let winner = if symbol == "X" { "O" } else { "X" };
+        self.finish(format!("{}_win_by_resignation", winner));
+        self.pending_draw_offer = None;
+    }
+
+    /// 무승부를 제안합니다. 제안할 수 없는 상황이면 클라이언트에 보낼 오류 문구를 반환합니다.
+    pub fn offer_draw(&mut self, symbol: &str) -> Result<(), &'static str> {
+        if self.status != "ongoing" {
+            return Err("You can only offer a draw during an ongoing game.");
+        }
+        let opponent = if symbol == "X" { "O" } else { "X" };
+        if self.player(opponent).is_some_and(|p| p.is_bot) {
+            return Err("The house bot does not accept draw offers.");
+        }
+        match self.pending_draw_offer.as_deref() {
+            Some(offerer) if offerer == symbol => Err("You have already offered a draw."),
+            Some(_) => Err("Your opponent has already offered a draw. Accept or decline it instead."),
+            None => {
+                self.pending_draw_offer = Some(symbol.to_string());
+                Ok(())
+            }
+        }
+    }
+
+    /// 상대의 무승부 제안에 응답합니다. 수락하면 "draw_agreed"로 게임이 끝납니다.
+    pub fn respond_draw(&mut self, symbol: &str, accept: bool) -> Result<(), &'static str> {
+        match self.pending_draw_offer.as_deref() {
+            Some(offerer) if offerer != symbol && self.status == "ongoing" => {}
+            _ => return Err("There is no draw offer to respond to."),
+        }
+        self.pending_draw_offer = None;
+        if accept {
+            self.finish("draw_agreed".to_string());
+        }
+        Ok(())
+    }
+
+    /// 스왑 규칙에서 지금 `next_player`가 Swap을 보낼 수 있는지 (진행 중인 게임에 먼저 두는 쪽의 첫 수만 놓였을 때,
+    /// 스왑한 뒤에는 첫 수가 상대의 것이 되므로 false)
+    pub fn swap_available(&self) -> bool {
+        self.swap_rule && self.status == "ongoing" && self.history.len() == 1 && self.history[0].symbol == self.first_player
+    }
+
+    /// 스왑: 첫 수를 `symbol`의 수로 바꾸고 (보드의 돌도 `symbol`로) 차례를 첫 수를 둔 상대에게 넘깁니다.
+    /// 자리와 심볼은 그대로입니다. 가져간 칸을 반환하며, 할 수 없는 상황이면 클라이언트에 보낼 오류 문구를 반환합니다.
+    pub fn swap(&mut self, symbol: &str) -> Result<usize, &'static str> {
+        if !self.swap_rule {
+            return Err("The swap rule is not enabled in this game.");
+        }
+        if !self.swap_available() || self.next_player != symbol {
+            return Err("You can only swap as your reply to the first move.");
+        }
+        // 첫 수는 이미 이벤트 로그에 남았으므로 그 뒤에 스왑을 남김
+        self.log_events();
+        self.cancel_turn();
+        let first = &mut self.history[0];
+        first.symbol = symbol.to_string();
+        first.mark = symbol.to_string();
+        let pos = first.position as usize;
+        self.board[pos] = symbol.to_string();
+        self.next_player = opponent_of(symbol).to_string();
+        self.pending_draw_offer = None;
+        self.events.push(events::GameEvent::Swapped { symbol: symbol.to_string(), pos });
+        if let Some(log) = &self.event_log {
+            log.log(&self.game_id, GameEvent::Swapped { symbol: symbol.to_string(), position: pos as u8 });
+        }
+        Ok(pos)
+    }
+
+    /// 관리자 강제 종료: "admin_terminated" 상태로 마지막 상태를 보냄 (받은 스트림은 정상 종료됨)
+    pub async fn terminate(&mut self, reason: &str) {
+        self.finish(ADMIN_TERMINATED.to_string());
+        self.pending_draw_offer = None;
+        info!(game_id = %self.game_id, reason, "관리자가 게임 강제 종료");
+        let message = if reason.is_empty() { ADMIN_TERMINATED_MESSAGE } else { reason };
+        self.broadcast_message(message).await;
+    }
+
+    /// 관리자 초기화: 자리, 점수, 먼저 두는 쪽은 그대로 두고 보드를 비워 새로 시작한 뒤 사유를 담은 상태를 보냄
+    /// (두 자리가 모두 찼으면 "ongoing", 아니면 "waiting")
+    pub async fn admin_reset(&mut self, reason: &str) {
+        self.cancel_turn();
+        self.board = vec!["".into(); self.board_size * self.board_size];
+        self.history.clear();
+        self.next_player = self.first_player.clone();
+        self.pending_draw_offer = None;
+        self.rematch_requested = None;
+        self.timed_out = None;
+        self.lost_on_time = None;
+        self.logged = LoggedEvents::default();
+        self.events = vec![events::GameEvent::GameCreated { size: self.board_size, first_player: self.first_player.clone() }];
+        for player in [&self.player_x, &self.player_o].into_iter().flatten() {
+            let marker = if player.symbol == "X" { self.player_x_symbol.clone() } else { self.player_o_symbol.clone() };
+            self.events.push(events::GameEvent::PlayerJoined { symbol: player.symbol.clone(), marker });
+        }
+        let seated = self.player_x.is_some() && self.player_o.is_some();
+        self.status = if seated { "ongoing" } else { "waiting" }.to_string();
+        info!(game_id = %self.game_id, reason, "관리자가 게임 초기화");
+        let message = if reason.is_empty() { ADMIN_RESET_MESSAGE } else { reason };
+        self.broadcast_message(message).await;
+    }
+
+    /// 서버 종료: 끝나지 않은 게임을 "server_shutdown" 상태로 끝내고 마지막 상태를 보냄
+    pub async fn end_for_shutdown(&mut self) {
+        self.cancel_waiting_timer();
+        self.finish(SERVER_SHUTDOWN.to_string());
+        self.pending_draw_offer = None;
+        info!(game_id = %self.game_id, "서버 종료로 게임 강제 종료");
+        self.broadcast_message(SERVER_SHUTDOWN_MESSAGE).await;
+    }
+
+    /// 클라이언트가 살아 있다는 신호(하트비트나 다른 메시지)를 받은 시각 갱신
+    pub fn touch(&mut self, symbol: &str, connection_id: u64) {
+        if let Some(player) = self.player_mut(symbol).filter(|p| p.connection_id == connection_id) {
+            player.last_heartbeat = Instant::now();
+        }
+    }
+
+    /// 진행 중인 게임에서 `timeout` 넘게 아무 메시지도 보내지 않은 접속 중인 플레이어의 심볼
+    pub fn idle_player(&self, timeout: Duration) -> Option<String> {
+        if self.status != "ongoing" {
+            return None;
+        }
+        [&self.player_x, &self.player_o]
+            .into_iter()
+            .flatten()
+            .find(|p| p.connected && !p.is_bot && p.last_heartbeat.elapsed() >= timeout)
+            .map(|p| p.symbol.clone())
+    }
+
+    /// 응답 없는 플레이어 때문에 게임을 "idle_timeout"으로 끝냄: 그 플레이어의 스트림은 IDLE_TIMEOUT 사유로
+    /// 끝내고, 상대와 관전자에게는 마지막 상태를 보냅니다. 게임 정리는 접속이 끊길 때 평소처럼 이루어집니다.
+    pub async fn expire_idle(&mut self, symbol: &str) {
+        let game_id = self.game_id.clone();
+        self.timed_out = Some(symbol.to_string());
+        self.finish(IDLE_TIMEOUT.to_string());
+        self.pending_draw_offer = None;
+        if let Some(player) = self.player_mut(symbol) {
+            deliver(&game_id, &player.tx, Err(stream_end(EndReason::IdleTimeout, &game_id)), symbol);
+            player.connected = false;
+        }
+        info!(%game_id, player_symbol = %symbol, "하트비트 없는 플레이어, 게임 종료");
+        self.broadcast_message(&format!("Player {} stopped responding. The game was closed.", symbol)).await;
+    }
+
+    /// 자리를 받은 뒤 제한 시간 안에 아무 메시지도 보내지 않은 연결의 자리를 비웁니다. 그 연결에는 "join_timeout"
+    /// 상태를 보내고, 사람이 한 명 남았으면 보드를 비워 새 상대를 기다리는 "waiting"으로 되돌립니다.
+    /// 이미 다른 연결로 바뀌었거나 끝난 게임이면 false를 반환합니다.
+    pub async fn expire_join(&mut self, symbol: &str, connection_id: u64) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+        let Some(player) = self.player(symbol).filter(|p| p.connection_id == connection_id && p.connected) else {
+            return false;
+        };
+        let mut update = self.update_for(player);
+        update.status = JOIN_TIMEOUT.to_string();
+        update.state_hash.clear();
+        update.error_message = JOIN_TIMEOUT_MESSAGE.to_string();
+        self.send_to(player, update);
+        *self.seat_mut(symbol) = None;
+        info!(game_id = %self.game_id, player_symbol = %symbol, "참가한 뒤 메시지가 없는 연결, 자리 비움");
+        if let Some(remaining) = self.lone_player().filter(|p| !p.is_bot).map(|p| p.symbol.clone()) {
+            self.restart_waiting(symbol, &remaining);
+            self.broadcast_message(&format!("Player {} never responded and was removed. Waiting for a new opponent.", symbol)).await;
+        }
+        true
+    }
+
+    /// 비운 자리(`vacated`)의 표시 정보를 지우고 보드를 비워 `remaining`만 앉은 처음 상태로 되돌림
+    fn restart_waiting(&mut self, vacated: &str, remaining: &str) {
+        self.cancel_turn();
+        if vacated == "X" {
+            self.player_x_symbol = "X".into();
+            self.player_x_name = default_player_name("X");
+        } else {
+            self.player_o_symbol = "O".into();
+            self.player_o_name = default_player_name("O");
+        }
+        self.board = vec!["".into(); self.board_size * self.board_size];
+        self.next_player = self.first_player.clone();
+        self.status = "waiting".to_string();
+        self.rated = false;
+        self.pending_draw_offer = None;
+        self.rematch_requested = None;
+        self.history.clear();
+        self.score = MatchScore::default();
+        self.logged = LoggedEvents::default();
+        let marker = if remaining == "X" { self.player_x_symbol.clone() } else { self.player_o_symbol.clone() };
+        self.events = vec![
+            events::GameEvent::GameCreated { size: self.board_size, first_player: self.first_player.clone() },
+            events::GameEvent::PlayerJoined { symbol: remaining.to_string(), marker },
+        ];
+    }
+
+    /// 응답 스트림이 업데이트를 받아 가지 않는 연결(`egress::forward`가 알림)을 접속이 끊긴 것으로 표시하고,
+    /// 진행 중인 게임이면 그 플레이어의 기권으로 끝내 상대와 관전자에게 알립니다. 게임을 끝냈으면 true를 반환합니다.
+    /// 이미 다른 연결로 바뀌었거나 끊긴 자리는 그대로 둡니다.
+    pub async fn forfeit_stalled(&mut self, symbol: &str, connection_id: u64) -> bool {
+        let Some(player) = self.player_mut(symbol).filter(|p| p.connection_id == connection_id && p.connected) else {
+            return false;
+        };
+        player.connected = false;
+        warn!(game_id = %self.game_id, player_symbol = %symbol, "업데이트를 받지 않는 플레이어, 접속 끊김으로 처리");
+        if self.status != "ongoing" {
+            self.broadcast_update().await;
+            return false;
+        }
+        self.resign(symbol);
+        self.broadcast_message(&format!("Player {} stopped receiving updates. The game is forfeited.", symbol)).await;
+        true
+    }
+
+    /// 관리자가 플레이어를 내보냄: 스트림을 KICKED 사유로 끝내고 세션 토큰을 바꿔 재접속을 막습니다.
+    /// 진행 중인 게임이면 내보낸 플레이어의 기권으로 끝내고, 자리는 접속이 끊긴 상태로 남아
+    /// 재접속 유예 시간이 지나면 게임이 정리됩니다. 사람이 앉은 자리가 아니면 false를 반환합니다.
+    pub async fn kick(&mut self, symbol: &str) -> bool {
+        let game_id = self.game_id.clone();
+        let Some(player) = self.player_mut(symbol).filter(|p| !p.is_bot) else {
+            return false;
+        };
+        if player.connected {
+            deliver(&game_id, &player.tx, Err(stream_end(EndReason::Kicked, &game_id)), symbol);
+        }
+        player.connected = false;
+        player.session_token = generate_session_token();
+        info!(%game_id, player_symbol = %symbol, "관리자가 플레이어를 내보냄");
+        let mut message = format!("Player {} was removed by a server administrator.", symbol);
+        if self.status == "ongoing" {
+            self.resign(symbol);
+            message.push_str(" The game is forfeited.");
+        }
+        self.broadcast_message(&message).await;
+        true
+    }
+
+    /// 승패(기권 포함)나 무승부(합의 포함)로 게임이 끝났는지 검사
+    pub fn is_finished(&self) -> bool {
+        is_finished_status(&self.status)
+    }
+
+    /// 레이팅에 반영할 결과: 끝난 레이팅 게임이고 두 플레이어 모두 서로 다른 player_id로 참가했을 때만 Some
+    /// (관리자가 끝낸 게임은 승패가 없으므로 반영하지 않음)
+    pub fn rated_result(&self) -> Option<RatedResult> {
+        if !self.rated || !self.is_finished() || [ADMIN_TERMINATED, SERVER_SHUTDOWN, IDLE_TIMEOUT, ABANDONED].contains(&self.status.as_str()) {
+            return None;
+        }
+        let x = self.player_x.as_ref().map(|p| p.player_id.clone()).filter(|id| !id.is_empty())?;
+        let o = self.player_o.as_ref().map(|p| p.player_id.clone()).filter(|id| !id.is_empty())?;
+        if x == o {
+            return None;
+        }
+        Some(if self.status.starts_with("draw") {
+            RatedResult::Draw { x, o }
+        } else if self.status.starts_with('X') {
+            RatedResult::Decisive { winner: x, loser: o }
+        } else {
+            RatedResult::Decisive { winner: o, loser: x }
+        })
+    }
+
+    /// 보드가 가득 찼는지 검사
+    fn is_full(&self) -> bool {
+        self.board.iter().all(|cell| !cell.is_empty())
+    }
+
+    /// 승리 조건 검사
+    #[instrument(level = "debug", skip(self), fields(game_id = %self.game_id))]
+    pub fn check_winner(&self) -> Option<String> {
+        board::winner(&self.board, self.board_size, self.win_length)
+    }
+
+    /// 현재 게임 상태를 기반으로 기본 업데이트 메시지를 생성 (관전자에게는 그대로 전송)
+    pub fn create_update(&self) -> GameState {
+        GameState {
+            board: self.board.clone(),
+            next_player: self.next_player.clone(),
+            status: self.status.clone(),
+            your_symbol: String::new(), // 각 클라이언트마다 개별 설정 예정
+            error_message: "".into(),    // 기본적으로 오류 메시지는 비어 있음
+            session_token: String::new(),
+            rated: self.rated,
+            info_message: String::new(),
+            game_id: self.game_id.clone(),
+            draw_offer_pending: self.pending_draw_offer.is_some(),
+            history: if self.is_finished() { self.history_messages() } else { Vec::new() },
+            preset: self.preset.clone(),
+            options: Some(self.options.to_proto()),
+            board_size: self.board_size as i32,
+            win_length: self.win_length as i32,
+            x_rating: self.player_x.as_ref().and_then(|p| p.rating).unwrap_or(0),
+            o_rating: self.player_o.as_ref().and_then(|p| p.rating).unwrap_or(0),
+            invite_code: String::new(),
+            chat_message: String::new(),
+            chat_sender: String::new(),
+            heartbeat: false,
+            bot_difficulty: if self.has_bot() { self.bot_difficulty as i32 } else { BotDifficulty::Unspecified as i32 },
+            move_number: self.move_number,
+            your_move_count: 0,
+            x_symbol: self.player_x_symbol.clone(),
+            o_symbol: self.player_o_symbol.clone(),
+            player_x_name: self.player_x_name.clone(),
+            player_o_name: self.player_o_name.clone(),
+            score_x: self.score.x_wins,
+            score_o: self.score.o_wins,
+            draws: self.score.draws,
+            games_played: self.score.games_played(),
+            best_of: self.best_of,
+            auto_rematch: self.auto_rematch,
+            swap_available: self.swap_available(),
+            state_hash: state_hash(&self.board, &self.status, &self.next_player),
+            turn_deadline_seconds: self.turn_seconds_left(),
+            lost_on_time: self.lost_on_time.clone().unwrap_or_default(),
+            match_winner: self.match_winner().unwrap_or_default().to_string(),
+            mode: self.mode as i32,
+            forced_board: self.forced_board().map(|index| index as i32),
+            macro_board: if self.mode == GameMode::Ultimate { ultimate::macro_board(&self.board) } else { Vec::new() },
+            opponent_connected: false, // 플레이어마다 개별 설정
+            full_board: true,           // 보드 차이는 보낼 때 연결마다 계산
+            diff: None,
+        }
+    }
+
+    /// 관리자용 상세 정보 (세션 토큰은 포함하지 않음)
+    pub fn detail(&self) -> GameDetail {
+        let seats = [&self.player_x, &self.player_o]
+            .into_iter()
+            .flatten()
+            .map(|p| SeatDetail {
+                symbol: p.symbol.clone(),
+                player_id: p.player_id.clone(),
+                connected: p.connected,
+                bot: p.is_bot,
+                rating: p.rating.unwrap_or(0),
+            })
+            .collect();
+        GameDetail {
+            game_id: self.game_id.clone(),
+            status: self.status.clone(),
+            board: self.board.clone(),
+            next_player: self.next_player.clone(),
+            board_size: self.board_size as i32,
+            win_length: self.win_length as i32,
+            preset: self.preset.clone(),
+            rated: self.rated,
+            private: self.private,
+            invite_only: self.invite_only,
+            invite_code: self.invite_code.clone(),
+            created_at_unix: self.created_at.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64),
+            seats,
+            spectator_count: self.spectator_count() as i32,
+            move_count: self.history.len() as i32,
+            last_activity_unix: unix_secs(self.last_activity()),
+        }
+    }
+
+    /// 마지막으로 수를 두거나 플레이어에게서 메시지를 받은 시각 (아무 일도 없었으면 만든 시각)
+    pub fn last_activity(&self) -> SystemTime {
+        let now = SystemTime::now();
+        let heartbeats = [&self.player_x, &self.player_o]
+            .into_iter()
+            .flatten()
+            .filter(|p| !p.is_bot)
+            .map(|p| now.checked_sub(p.last_heartbeat.elapsed()).unwrap_or(UNIX_EPOCH));
+        let last_move = self.history.last().map(|m| m.played_at);
+        heartbeats.chain(last_move).fold(self.created_at, SystemTime::max)
+    }
+
+    /// 기보를 GameState에 담을 Move 목록으로 변환
+    fn history_messages(&self) -> Vec<Move> {
+        self.history
+            .iter()
+            .map(|m| Move { player_id: m.symbol.clone(), position: m.position as i32, chosen_symbol: m.mark.clone() })
+            .collect()
+    }
+
+    /// 특정 플레이어에게 보낼 업데이트 메시지 (심볼과 세션 토큰 포함)
+    pub fn update_for(&self, player: &PlayerConnection) -> GameState {
+        let mut update = self.create_update();
+        update.your_symbol = player.symbol.clone();
+        update.session_token = player.session_token.clone();
+        update.draw_offer_pending = self.pending_draw_offer.as_ref().is_some_and(|offerer| *offerer != player.symbol);
+        update.your_move_count = self.history.iter().filter(|m| m.symbol == player.symbol).count() as u32;
+        update.opponent_connected = self.player(opponent_of(&player.symbol)).is_some_and(|p| p.connected || p.is_bot);
+        if player.symbol == "X" && self.player_o.is_none() {
+            update.invite_code = self.invite_code.clone();
+        }
+        update
+    }
+
+    /// 모든 연결된 플레이어에게 업데이트 메시지 전송
+    pub async fn broadcast_update(&mut self) {
+        self.broadcast_message("").await;
+    }
+
+    /// 안내 메시지를 담아 모든 연결된 플레이어와 관전자에게 업데이트 전송 (상태 번호를 하나 올림)
+    pub async fn broadcast_message(&mut self, info: &str) {
+        self.log_events();
+        self.move_number += 1;
+        for player in [&self.player_x, &self.player_o].into_iter().flatten() {
+            if player.connected && !player.is_bot {
+                let mut update = self.update_for(player);
+                update.info_message = info.to_string();
+                self.send_to(player, update);
+            }
+        }
+        let mut update = self.create_update();
+        update.info_message = info.to_string();
+        self.send_to_spectators(&update);
+    }
+
+    /// 마지막으로 남긴 뒤 바뀐 진행 상황을 이벤트 로그에 남김 (상태가 바뀌면 항상 업데이트를 보내므로 전송할 때 호출)
+    fn log_events(&mut self) {
+        let Some(log) = self.event_log.clone() else {
+            return;
+        };
+        if !self.logged.started && (self.status == "ongoing" || self.logged.moves < self.history.len()) {
+            let players = [&self.player_x, &self.player_o]
+                .into_iter()
+                .flatten()
+                .map(|p| EventPlayer { symbol: p.symbol.clone(), player_id: p.player_id.clone(), bot: p.is_bot })
+                .collect();
+            let first_player = self.history.first().map_or(&self.next_player, |m| &m.symbol).clone();
+            log.log(&self.game_id, GameEvent::GameStarted { board_size: self.board_size, win_length: self.win_length, first_player, players });
+            self.logged.started = true;
+        }
+        if !self.logged.started {
+            return;
+        }
+        for m in &self.history[self.logged.moves..] {
+            log.log(&self.game_id, GameEvent::MoveMade { symbol: m.symbol.clone(), position: m.position, mark: m.mark.clone() });
+        }
+        self.logged.moves = self.history.len();
+        if !self.logged.ended && self.is_finished() {
+            log.log(&self.game_id, GameEvent::GameEnded { status: self.status.clone() });
+            self.logged.ended = true;
+        }
+    }
+
+    /// 플레이어의 스트림이 끊긴 것을 이벤트 로그에 남김
+    pub fn log_disconnect(&self, symbol: &str) {
+        if let Some(log) = &self.event_log {
+            log.log(&self.game_id, GameEvent::PlayerDisconnected { symbol: symbol.to_string() });
+        }
+    }
+
+    /// `symbol` 자리의 수 빈도 제한에서 토큰 하나를 씀 (둘 수 있는지와 상관없이 수를 받을 때마다)
+    pub fn take_move_token(&mut self, symbol: &str, config: &Config) -> Result<(), MoveError> {
+        let player = self.player_mut(symbol).ok_or(MoveError::NotAPlayer)?;
+        let now = Instant::now();
+        let limiter = player
+            .moves
+            .get_or_insert_with(|| TokenBucket::new(config.rate_limit_refill_per_sec, f64::from(config.rate_limit_capacity), now));
+        if limiter.take(now) {
+            Ok(())
+        } else {
+            Err(MoveError::RateLimited)
+        }
+    }
+
+    /// 플레이어의 채팅을 빈도 제한과 정리를 거쳐 전달 (대기 중에도 늦게 온 상대에게 인사할 수 있도록 게임 상태와 상관없이)
+    pub async fn chat(&mut self, sender: &str, text: &str, config: &Config) -> Result<(), ChatError> {
+        let player = self.player_mut(sender).filter(|p| !p.is_bot).ok_or(ChatError::NotAPlayer)?;
+        if !player.chat.allow(config.chat_rate_limit, config.chat_rate_window()) {
+            return Err(ChatError::TooFast);
+        }
+        let text = chat::sanitize(text, config.chat_max_chars)?;
+        self.broadcast_chat(sender, &text).await;
+        Ok(())
+    }
+
+    /// 채팅을 보낸 플레이어를 뺀 상대 플레이어와 관전자에게 전달하고 이벤트 로그에 남김 (정리와 빈도 제한은 호출 전에 끝남)
+    pub async fn broadcast_chat(&mut self, sender: &str, text: &str) {
+        if let Some(log) = &self.event_log {
+            log.log(&self.game_id, GameEvent::Chat { sender: sender.to_string(), text: text.to_string() });
+        }
+        let with_chat = |mut update: GameState| {
+            update.chat_message = text.to_string();
+            update.chat_sender = sender.to_string();
+            update
+        };
+        for player in [&self.player_x, &self.player_o].into_iter().flatten() {
+            if player.symbol != sender && player.connected && !player.is_bot {
+                self.send_to(player, with_chat(self.update_for(player)));
+            }
+        }
+        let update = with_chat(self.create_update());
+        self.send_to_spectators(&update);
+    }
+
+    /// 관전자를 추가하고 현재 상태를 바로 전송합니다. 버퍼가 가득 차 건너뛴 업데이트가 `drop_limit`만큼 쌓이면
+    /// 그 관전 스트림을 끊습니다 (0이면 끊지 않음).
+    pub fn add_spectator(&mut self, tx: UpdateSender, drop_limit: u32) {
+        self.remove_closed_spectators();
+        if let Err(e) = tx.try_send(Ok(self.create_update())) {
+            warn!(game_id = %self.game_id, error = %e, "관전자 스냅샷 전송 실패");
+        }
+        self.spectators.push(Spectator { tx, dropped: 0, drop_limit });
+    }
+
+    /// 관전자들에게 업데이트 전송. 느린 관전자는 기다리지 않고 건너뛰며, 너무 많이 건너뛴 관전자는 SLOW_CONSUMER
+    /// 사유로 끊습니다 (게임 진행은 관전자 때문에 멈추지 않음).
+    fn send_to_spectators(&mut self, update: &GameState) {
+        let (game_id, metrics) = (&self.game_id, &self.metrics);
+        self.spectators.retain_mut(|spectator| {
+            if deliver(game_id, &spectator.tx, Ok(update.clone()), "관전자") != Delivery::Full {
+                return true;
+            }
+            metrics.spectator_update_dropped();
+            spectator.dropped += 1;
+            if spectator.drop_limit == 0 || spectator.dropped < spectator.drop_limit {
+                return true;
+            }
+            warn!(%game_id, dropped = spectator.dropped, "건너뛴 업데이트가 너무 많아 관전 스트림을 끊음");
+            metrics.slow_spectator_disconnected();
+            // 버퍼가 가득 찼으므로 종료 사유는 따로 기다려 보냄 (관전자가 연결을 끊으면 보내지 못하고 끝남)
+            let (tx, status) = (spectator.tx.clone(), stream_end(EndReason::SlowConsumer, game_id));
+            tokio::spawn(async move {
+                let _ = tx.send(Err(status)).await;
+            });
+            false
+        });
+    }
+
+    /// 접속 중인 플레이어와 관전자의 스트림을 사유를 담은 Status로 끝냄 (관전자 목록은 비움)
+    pub async fn end_streams(&mut self, reason: EndReason) {
+        let status = stream_end(reason, &self.game_id);
+        let players = [&self.player_x, &self.player_o].into_iter().flatten();
+        let senders = players.filter(|p| p.connected && !p.is_bot).map(|p| &p.tx).chain(self.spectators.iter().map(|s| &s.tx));
+        for tx in senders {
+            deliver(&self.game_id, tx, Err(status.clone()), "종료 사유");
+        }
+        self.spectators.clear();
+    }
+
+    /// 수신 측이 닫힌 관전자 채널 정리
+    pub fn remove_closed_spectators(&mut self) {
+        self.spectators.retain(|s| !s.tx.is_closed());
+    }
+
+    /// 현재 관전자 수
+    pub fn spectator_count(&self) -> usize {
+        self.spectators.iter().filter(|s| !s.tx.is_closed()).count()
+    }
+
+    /// 자리에 앉은 플레이어 수 (봇 포함)
+    pub fn player_count(&self) -> usize {
+        [&self.player_x, &self.player_o].into_iter().flatten().count()
+    }
+
+    /// 서버 봇이 앉아 있는 게임인지 검사
+    pub fn has_bot(&self) -> bool {
+        [&self.player_x, &self.player_o].into_iter().flatten().any(|p| p.is_bot)
+    }
+
+    /// 새 플레이어가 앉을 수 있는 대기 중인 게임인지 검사
+    pub fn has_open_seat(&self) -> bool {
+        (self.status == "waiting" || self.status == "searching")
+            && self.lone_player().is_some_and(|p| p.connected)
+    }
+
+    /// 지정된 플레이어에게 오류 메시지를 전송합니다.
+    pub async fn send_error(&self, symbol: &str, error_msg: &str) {
+        if let Some(player) = self.player(symbol) {
+            let mut update = self.update_for(player);
+            update.status = "error".into(); // 오류 상태로 설정
+            update.state_hash.clear();
+            update.error_message = error_msg.to_string();
+            self.send_to(player, update);
+        }
+    }
+
+    /// 플레이어에게 업데이트 전송 (보드 차이를 요청했으면 바뀐 칸만)
+    fn send_to(&self, player: &PlayerConnection, mut update: GameState) {
+        let board = player.board_diff.encode(&mut update);
+        match deliver(&self.game_id, &player.tx, Ok(update), &player.symbol) {
+            Delivery::Sent => {
+                if let Some(board) = board {
+                    player.board_diff.sent(board);
+                }
+            }
+            Delivery::Full => self.metrics.player_update_dropped(),
+            Delivery::Closed => {}
+        }
+    }
+}
+
+/// 관전자 연결
+struct Spectator {
+    tx: UpdateSender,
+    dropped: u32,    // 버퍼가 가득 차 건너뛴 업데이트 수
+    drop_limit: u32, // 이만큼 건너뛰면 관전 스트림을 끊음 (0이면 끊지 않음)
+}
+
+/// 기다리지 않고 보낸 결과
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Delivery {
+    Sent,
+    Full,   // 버퍼가 가득 차 건너뜀
+    Closed, // 이미 닫힌 스트림
+}
+
+/// 게임 액터를 멈추지 않도록 기다리지 않고 보내고 그 결과를 반환 (`recipient`는 로그에 남길 받는 쪽)
+///
+/// 버퍼가 가득 찬 느린 연결에는 이번 업데이트를 건너뜁니다 (상태 번호가 건너뛰므로 클라이언트가 다시 맞춤).
+/// 받아 가지 않는 플레이어 연결은 `egress::forward`가 찾아 끊고, 너무 많이 건너뛴 관전자는
+/// `send_to_spectators`가 끊으며, 닫힌 연결은 접속 끊김 처리가 정리합니다.
+fn deliver(game_id: &str, tx: &UpdateSender, item: Result<GameState, Status>, recipient: &str) -> Delivery {
+    match tx.try_send(item) {
+        Ok(()) => Delivery::Sent,
+        Err(TrySendError::Full(_)) => {
+            warn!(%game_id, recipient, "전송 버퍼가 가득 차 업데이트를 건너뜀");
+            Delivery::Full
+        }
+        Err(TrySendError::Closed(_)) => {
+            debug!(%game_id, recipient, "전송 실패: 이미 닫힌 스트림");
+            Delivery::Closed
+        }
+    }
+}
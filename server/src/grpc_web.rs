@@ -0,0 +1,57 @@
+//! 브라우저의 gRPC-Web 클라이언트 지원
+//!
+//! 브라우저는 HTTP/2 트레일러를 읽을 수 없어 gRPC 대신 gRPC-Web(HTTP/1.1도 가능, 상태는 본문 끝에 실음)으로
+//! 부릅니다. 서버는 gRPC 포트에서 HTTP/1.1도 받고, `tonic_web::GrpcWebLayer`가 gRPC-Web 요청을 gRPC로
+//! 바꿔 모든 서비스에 넘깁니다. 설정의 `web_allowed_origins` 페이지에서 부를 수 있도록 이 모듈의 CORS 설정을 그 바깥에
+//! 씌웁니다. 모두 `--web`을 줄 때만 켜지며, 없으면 HTTP/2 gRPC만 받습니다.
+//! gRPC-Web은 단항 호출과 서버 스트리밍만 지원하므로 브라우저에서는 Play 대신 SubmitMove와 Spectate를 씁니다.
+
+use std::time::Duration;
+
+use axum::http::header::{AUTHORIZATION, CONTENT_TYPE};
+use axum::http::{HeaderName, HeaderValue, Method, Uri};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+/// 브라우저가 CORS 사전 요청 결과를 캐시하는 시간
+const PREFLIGHT_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// gRPC-Web 클라이언트가 보내는 헤더
+const ALLOWED_HEADERS: [HeaderName; 5] = [
+    AUTHORIZATION,
+    CONTENT_TYPE,
+    HeaderName::from_static("x-grpc-web"),
+    HeaderName::from_static("x-user-agent"),
+    HeaderName::from_static("grpc-timeout"),
+];
+
+/// 브라우저 스크립트가 읽을 수 있어야 하는 응답 헤더 (트레일러 없이 바로 끝난 호출의 상태)
+const EXPOSED_HEADERS: [HeaderName; 3] = [
+    HeaderName::from_static("grpc-status"),
+    HeaderName::from_static("grpc-message"),
+    HeaderName::from_static("grpc-status-details-bin"),
+];
+
+/// `allowed_origins`의 페이지에서 gRPC-Web으로 부를 수 있게 하는 CORS 설정 (비어 있으면 모든 출처)
+/// (쿠키 대신 Authorization 헤더로 인증하므로 자격 증명은 허용하지 않음)
+pub fn cors(allowed_origins: &[String]) -> CorsLayer {
+    let allow_origin = if allowed_origins.is_empty() {
+        AllowOrigin::from(Any)
+    } else {
+        // 설정 검사(`is_origin`)를 통과한 값만 들어옴
+        AllowOrigin::list(allowed_origins.iter().filter_map(|origin| HeaderValue::from_str(origin).ok()))
+    };
+    CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods([Method::POST])
+        .allow_headers(ALLOWED_HEADERS)
+        .expose_headers(EXPOSED_HEADERS)
+        .max_age(PREFLIGHT_MAX_AGE)
+}
+
+/// 브라우저가 Origin 헤더로 보내는 형태("scheme://host[:port]", 경로 없음)인지
+pub fn is_origin(origin: &str) -> bool {
+    let Ok(uri) = origin.parse::<Uri>() else {
+        return false;
+    };
+    uri.scheme().is_some() && uri.host().is_some() && !origin.ends_with('/') && uri.path_and_query().is_none_or(|p| p.as_str() == "/")
+}
@@ -0,0 +1,29 @@
+//! gRPC 헬스 체크 (`grpc.health.v1.Health`)
+//!
+//! 배포 환경의 준비 상태 검사용입니다. 전체 서버("")와 `tictactoe.TicTacToe`의 상태를 함께 알리며,
+//! 리스너를 바인드하기 전과 종료를 시작한 뒤에는 NOT_SERVING입니다.
+
+use tonic::server::NamedService;
+use tonic_health::pb::health_server::{Health, HealthServer};
+use tonic_health::server::HealthReporter;
+use tonic_health::ServingStatus;
+
+use crate::service::TicTacToeService;
+use crate::tictactoe::tic_tac_toe_server::TicTacToeServer;
+
+/// 상태를 알리는 서비스 이름 (빈 이름은 서버 전체)
+const SERVICE_NAMES: [&str; 2] = ["", <TicTacToeServer<TicTacToeService> as NamedService>::NAME];
+
+/// 모든 상태를 NOT_SERVING으로 둔 헬스 체크 서비스와 상태를 바꿀 때 쓰는 리포터
+pub async fn health_service() -> (HealthReporter, HealthServer<impl Health>) {
+    let (mut reporter, server) = tonic_health::server::health_reporter();
+    set_status(&mut reporter, ServingStatus::NotServing).await;
+    (reporter, server)
+}
+
+/// 서버 전체와 게임 서비스의 상태를 함께 변경
+pub async fn set_status(reporter: &mut HealthReporter, status: ServingStatus) {
+    for name in SERVICE_NAMES {
+        reporter.set_service_status(name, status).await;
+    }
+}
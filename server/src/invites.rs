@@ -0,0 +1,72 @@
+//! 비공개 게임 초대 코드
+//!
+//! CreatePrivateGame으로 만든 게임에는 짧은 초대 코드가 발급됩니다. 친구는 JoinPrivateGame에 코드를
+//! 보내 O 자리를 받고, 받은 세션 토큰으로 Play에 접속합니다. 코드는 발급 후 정해진 시간이 지나면
+//! 더 이상 쓸 수 없습니다.
+
+use std::collections::HashMap;
+use std::time::Duration;
+use rand::Rng;
+use tokio::time::Instant;
+
+/// 초대 코드 길이
+pub const INVITE_CODE_LEN: usize = 6;
+/// 초대 코드에 쓰는 base62 문자
+const BASE62: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// 무작위 base62 초대 코드 생성
+pub fn generate_invite_code() -> String {
+    let mut rng = rand::thread_rng();
+    (0..INVITE_CODE_LEN).map(|_| BASE62[rng.gen_range(0..BASE62.len())] as char).collect()
+}
+
+/// 발급된 초대 코드 하나
+struct Invite {
+    game_id: String,
+    expires_at: Instant,
+}
+
+/// 초대 코드 → 게임 ID 대응표
+#[derive(Default)]
+pub struct InviteBook {
+    invites: HashMap<String, Invite>,
+}
+
+impl InviteBook {
+    /// 게임에 새 초대 코드를 발급 (만료된 코드는 이때 정리)
+    pub fn issue(&mut self, game_id: &str, ttl: Duration) -> String {
+        self.issue_with(game_id, ttl, generate_invite_code)
+    }
+
+    /// `generate`로 만든 코드를 발급. 사용 중인 코드와 겹치면 겹치지 않을 때까지 다시 만듦
+    pub fn issue_with(&mut self, game_id: &str, ttl: Duration, mut generate: impl FnMut() -> String) -> String {
+        self.prune_expired();
+        let code = loop {
+            let code = generate();
+            if !self.invites.contains_key(&code) {
+                break code;
+            }
+        };
+        let invite = Invite { game_id: game_id.to_string(), expires_at: Instant::now() + ttl };
+        self.invites.insert(code.clone(), invite);
+        code
+    }
+
+    /// 만료되지 않은 코드의 게임 ID
+    pub fn resolve(&self, code: &str) -> Option<String> {
+        self.invites
+            .get(code)
+            .filter(|invite| invite.expires_at > Instant::now())
+            .map(|invite| invite.game_id.clone())
+    }
+
+    /// 게임이 사라지면 그 게임의 코드도 제거
+    pub fn remove_game(&mut self, game_id: &str) {
+        self.invites.retain(|_, invite| invite.game_id != game_id);
+    }
+
+    fn prune_expired(&mut self) {
+        let now = Instant::now();
+        self.invites.retain(|_, invite| invite.expires_at > now);
+    }
+}
@@ -0,0 +1,49 @@
+pub mod tictactoe {
+    tonic::include_proto!("tictactoe");
+
+    /// 서버 리플렉션에 쓰는 파일 디스크립터 세트 (build.rs에서 생성)
+    pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("tictactoe_descriptor");
+}
+
+pub mod access_log;
+pub mod actor;
+pub mod admin;
+pub mod api_http;
+pub mod auth;
+pub mod auth_http;
+pub mod board;
+pub mod board_diff;
+pub mod bot;
+pub mod chat;
+pub mod compression;
+pub mod config;
+pub mod egress;
+pub mod elo;
+pub mod error;
+pub mod event_log;
+pub mod events;
+pub mod game;
+pub mod grpc_web;
+pub mod health;
+pub mod invites;
+pub mod load_shed;
+pub mod manager;
+pub mod matchmaking;
+pub mod metrics;
+pub mod metrics_http;
+pub mod notation;
+pub mod presets;
+pub mod puzzles;
+pub mod rate_limit;
+pub mod record;
+pub mod reflection;
+pub mod reload;
+pub mod replays;
+pub mod serialization;
+pub mod service;
+pub mod snapshot;
+pub mod stats;
+pub mod telemetry;
+pub mod tls;
+pub mod tournament;
+pub mod ultimate;
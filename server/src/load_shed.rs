@@ -0,0 +1,201 @@
+//! 부하 제어: 수 처리 지연이 예산을 넘으면 부가 작업을 정해진 순서대로 끄고,
+//! 부하가 줄어들면 역순으로 다시 켭니다.
+//!
+//! 지연 측정값은 주기적으로 `LoadShedder::tick`에서 평가합니다. 판단 로직(`ShedController`)은
+//! 측정값만 입력으로 받는 순수한 상태 기계라 시간이나 서버 상태 없이 검사할 수 있습니다.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// 부하가 높을 때 끌 수 있는 부가 작업 (끄는 순서대로 나열, 꺼진 작업의 요청은 UNAVAILABLE로 거부)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionalWork {
+    /// 끝난 게임 다시 보기 (ReplayGame)
+    Replays,
+    /// 퍼즐 (GetPuzzle, SolvePuzzle)
+    Puzzles,
+    /// 레이팅과 통계 조회 (GetPlayerRating, GetPlayerStats, GetLeaderboard)
+    StatsReads,
+    /// 스트림 밖의 게임 조회 (ListGames, GetGameState, GetHistory, ExportGame, 같은 RPC를 부르는 HTTP API 조회)
+    PollingReads,
+    /// 새 관전 스트림 (Spectate, 이미 관전 중인 스트림은 계속 받음)
+    NewSpectators,
+}
+
+/// 끄는 순서 (다시 켤 때는 역순)
+pub const SHED_ORDER: [OptionalWork; 5] = [
+    OptionalWork::Replays,
+    OptionalWork::Puzzles,
+    OptionalWork::StatsReads,
+    OptionalWork::PollingReads,
+    OptionalWork::NewSpectators,
+];
+
+impl OptionalWork {
+    /// 몇 번째 단계에서 꺼지는지 (1부터)
+    fn shed_at_level(self) -> usize {
+        SHED_ORDER.iter().position(|&w| w == self).unwrap_or(0) + 1
+    }
+}
+
+/// 부가 작업별 기능 게이트. 각 사용 지점에서 `is_enabled`로 확인합니다.
+#[derive(Debug, Default)]
+pub struct FeatureGates {
+    level: AtomicUsize, // SHED_ORDER 앞에서부터 꺼진 작업 수
+}
+
+impl FeatureGates {
+    pub fn is_enabled(&self, work: OptionalWork) -> bool {
+        self.level.load(Ordering::Relaxed) < work.shed_at_level()
+    }
+
+    pub fn level(&self) -> usize {
+        self.level.load(Ordering::Relaxed)
+    }
+
+    fn set_level(&self, level: usize) {
+        self.level.store(level.min(SHED_ORDER.len()), Ordering::Relaxed);
+    }
+}
+
+/// 최근 수 처리 지연 측정값 (평가할 때마다 비움)
+#[derive(Debug)]
+pub struct LatencyWindow {
+    samples: VecDeque<Duration>,
+    capacity: usize,
+}
+
+impl LatencyWindow {
+    pub fn new(capacity: usize) -> Self {
+        LatencyWindow { samples: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    pub fn record(&mut self, latency: Duration) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(latency);
+    }
+
+    /// 95 백분위 지연 (측정값이 없으면 None)
+    pub fn p95(&self) -> Option<Duration> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<Duration> = self.samples.iter().copied().collect();
+        sorted.sort();
+        let rank = (sorted.len() * 95).div_ceil(100).max(1);
+        Some(sorted[rank - 1])
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+}
+
+/// 지연 예산을 기준으로 몇 단계까지 끌지 정하는 제어기
+///
+/// 히스테리시스: 예산을 넘으면 평가마다 한 단계씩 더 끄고, 예산의 절반 아래(또는 측정값 없음)가
+/// `calm_ticks_required`번 연속되어야 한 단계 다시 켭니다. 그 사이 구간에서는 현재 단계를 유지합니다.
+#[derive(Debug)]
+pub struct ShedController {
+    budget: Duration,
+    recover_below: Duration,
+    calm_ticks_required: u32,
+    calm_ticks: u32,
+    level: usize,
+}
+
+impl ShedController {
+    pub fn new(budget: Duration) -> Self {
+        ShedController { budget, recover_below: budget / 2, calm_ticks_required: 3, calm_ticks: 0, level: 0 }
+    }
+
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// 지연 예산 변경 (현재 단계는 유지하고 다음 평가부터 새 예산으로 판단)
+    pub fn set_budget(&mut self, budget: Duration) {
+        self.budget = budget;
+        self.recover_below = budget / 2;
+    }
+
+    /// 한 번의 평가: 이번 구간의 p95를 받아 새 단계를 반환
+    pub fn observe(&mut self, p95: Option<Duration>) -> usize {
+        match p95 {
+            Some(p95) if p95 > self.budget => {
+                self.level = (self.level + 1).min(SHED_ORDER.len());
+                self.calm_ticks = 0;
+            }
+            Some(p95) if p95 >= self.recover_below => self.calm_ticks = 0,
+            _ => {
+                self.calm_ticks += 1;
+                if self.calm_ticks >= self.calm_ticks_required && self.level > 0 {
+                    self.level -= 1;
+                    self.calm_ticks = 0;
+                }
+            }
+        }
+        self.level
+    }
+}
+
+/// 지연 측정, 제어기, 기능 게이트를 묶은 부하 제어기 (서비스 전체에서 공유)
+#[derive(Debug)]
+pub struct LoadShedder {
+    gates: FeatureGates,
+    window: Mutex<LatencyWindow>,
+    controller: Mutex<ShedController>,
+}
+
+impl LoadShedder {
+    pub fn new(budget: Duration) -> Self {
+        LoadShedder {
+            gates: FeatureGates::default(),
+            window: Mutex::new(LatencyWindow::new(1024)),
+            controller: Mutex::new(ShedController::new(budget)),
+        }
+    }
+
+    /// 수 하나를 처리하는 데 걸린 시간 기록
+    pub fn record_move_latency(&self, latency: Duration) {
+        self.window.lock().unwrap().record(latency);
+    }
+
+    pub fn is_enabled(&self, work: OptionalWork) -> bool {
+        self.gates.is_enabled(work)
+    }
+
+    pub fn level(&self) -> usize {
+        self.gates.level()
+    }
+
+    /// 설정 다시 읽기로 지연 예산 변경
+    pub fn set_budget(&self, budget: Duration) {
+        self.controller.lock().unwrap().set_budget(budget);
+    }
+
+    /// 지난 평가 이후의 측정값으로 단계를 갱신하고 게이트에 반영
+    pub fn tick(&self) -> usize {
+        let p95 = {
+            let mut window = self.window.lock().unwrap();
+            let p95 = window.p95();
+            window.clear();
+            p95
+        };
+        let previous = self.gates.level();
+        let level = self.controller.lock().unwrap().observe(p95);
+        self.gates.set_level(level);
+
+        if level > previous {
+            warn!(level, p95_ms = p95.map(|d| d.as_millis() as u64), shed = ?SHED_ORDER[level - 1], "부하 증가, 부가 작업 중단");
+        } else if level < previous {
+            info!(level, restored = ?SHED_ORDER[previous - 1], "부하 감소, 부가 작업 재개");
+        }
+        level
+    }
+}
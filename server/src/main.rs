@@ -1,259 +1,296 @@
-use tonic::{transport::Server, Request, Response, Status};
-use tokio::sync::{Mutex, mpsc};
-use futures::Stream;
-use std::{pin::Pin, sync::Arc};
-use tokio_stream::{wrappers::ReceiverStream, StreamExt};
+use clap::Parser;
+use std::io::BufRead;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::Server;
+use tonic_health::ServingStatus;
+use tonic_web::GrpcWebLayer;
+use tower::util::option_layer;
+use tracing::{info, warn};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
 
-pub mod tictactoe {
-    tonic::include_proto!("tictactoe");
-}
-
-use tictactoe::tic_tac_toe_server::{TicTacToe, TicTacToeServer};
-use tictactoe::{GameState, Move};
+use server::access_log::AccessLogLayer;
+use server::auth::{hash_password, AuthInterceptor, TokenKeys, UserStore};
+use server::api_http;
+use server::auth_http;
+use server::compression::Compression;
+use server::config::Config;
+use server::grpc_web;
+use server::health;
+use server::metrics_http;
+use server::elo::RatingBook;
+use server::event_log::{GameEventLogger, DEFAULT_EVENT_LOG_MAX_BYTES};
+use server::presets::PresetStore;
+use server::record::GameRecorder;
+use server::reflection;
+use server::service::TicTacToeService;
+use server::snapshot::SnapshotWriter;
+use server::stats::StatsBook;
+use server::telemetry::{self, TraceContextLayer};
+use server::tls::TlsFiles;
 
-/// 서버에서 클라이언트로 전송할 스트림 타입
-type ResponseStream = Pin<Box<dyn Stream<Item = Result<GameState, Status>> + Send>>;
+/// 서버 실행 인자
+#[derive(Parser)]
+struct Args {
+    /// 설정 파일 경로 (없으면 작업 디렉터리의 server.toml, 그것도 없으면 기본값)
+    #[arg(long)]
+    config: Option<PathBuf>,
+    /// 로그를 JSON 한 줄씩 출력 (로그 수집기용)
+    #[arg(long)]
+    log_json: bool,
+    /// 플레이어별 누적 통계(전적, 마지막으로 게임을 끝낸 시각)를 저장할 파일 (설정의 stats_file보다 우선)
+    #[arg(long, value_name = "PATH")]
+    db: Option<PathBuf>,
+    /// 끝난 게임을 JSON 한 줄씩 덧붙일 파일 (없으면 기록하지 않음)
+    #[arg(long, value_name = "PATH")]
+    record: Option<PathBuf>,
+    /// 게임 시작, 수, 종료, 접속 끊김을 JSON 한 줄씩 덧붙일 이벤트 로그 (ReplayGame RPC가 읽음, 없으면 남기지 않음)
+    #[arg(long, value_name = "PATH")]
+    event_log: Option<PathBuf>,
+    /// 이벤트 로그 파일 하나의 최대 크기 (바이트, 넘으면 PATH.1, PATH.2, ...로 밀어냄)
+    #[arg(long, value_name = "BYTES", default_value_t = DEFAULT_EVENT_LOG_MAX_BYTES)]
+    event_log_max_bytes: u64,
+    /// 끝난 게임마다 보드, 수, 플레이어, 결과를 <game_id>.json으로 쓸 디렉터리 (형식은 설정의 snapshot_format,
+    /// replay 도구가 읽음, 없으면 쓰지 않음)
+    #[arg(long, value_name = "DIR")]
+    snapshot_dir: Option<PathBuf>,
+    /// gRPC 서버 인증서 체인 (PEM, --tls-key와 함께 주면 TLS로만 받음)
+    #[arg(long, value_name = "PATH", requires = "tls_key")]
+    tls_cert: Option<PathBuf>,
+    /// gRPC 서버 개인 키 (PEM)
+    #[arg(long, value_name = "PATH", requires = "tls_cert")]
+    tls_key: Option<PathBuf>,
+    /// 클라이언트 인증서를 검증할 CA (PEM, 주면 이 CA가 서명한 클라이언트 인증서가 있어야 접속 가능)
+    #[arg(long, value_name = "CA", requires = "tls_cert")]
+    tls_client_auth: Option<PathBuf>,
+    /// 브라우저의 gRPC-Web 요청(HTTP/1.1 포함)도 같은 포트에서 받음 (부를 수 있는 출처는 설정의 web_allowed_origins)
+    #[arg(long)]
+    web: bool,
+    /// RPC마다 남기는 접근 로그(상대 주소, 메서드, 상태, 걸린 시간)를 끔
+    #[arg(long)]
+    no_access_log: bool,
+    /// 스팬을 내보낼 OpenTelemetry 수집기의 OTLP/gRPC 주소 (예: http://localhost:4317, 없으면 내보내지 않음)
+    #[arg(long, value_name = "URL")]
+    otlp_endpoint: Option<String>,
+    /// 응답 압축 방식 (압축한 요청은 이 값과 관계없이 받고, 응답은 클라이언트가 받을 수 있을 때만 압축)
+    #[arg(long, value_enum, default_value_t = Compression::Gzip)]
+    compression: Compression,
+    /// 표준 입력의 비밀번호를 사용자 파일에 넣을 해시로 바꿔 출력하고 종료
+    #[arg(long)]
+    hash_password: bool,
+}
 
 ////////////////////////////
-// 1. 데이터 구조체 및 헬퍼 //
+// 서버 실행              //
 ////////////////////////////
 
-/// 각 플레이어의 연결 정보를 저장합니다.
-#[derive(Clone)]
-struct PlayerConnection {
-    symbol: String,              // "X" 또는 "O"
-    tx: mpsc::Sender<GameState>, // 업데이트 전송 채널
-}
-
-/// 게임의 전체 상태를 저장하는 구조체입니다.
-#[derive(Default)]
-struct SharedGame {
-    board: Vec<String>,       // 9칸 보드 (각 칸: "", "X", "O")
-    next_player: String,      // 다음 차례 ("X" 또는 "O")
-    status: String,           // "waiting", "ongoing", "X_win", "O_win", "draw"
-    player_x: Option<PlayerConnection>,
-    player_o: Option<PlayerConnection>,
-}
-
-impl SharedGame {
-    /// 초기 게임 상태 생성
-    fn new() -> Self {
-        SharedGame {
-            board: vec!["".into(); 9],
-            next_player: "X".into(),
-            status: "waiting".into(),
-            player_x: None,
-            player_o: None,
-        }
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    if args.hash_password {
+        let mut password = String::new();
+        std::io::stdin().lock().read_line(&mut password)?;
+        let hash = hash_password(password.trim_end_matches(['\r', '\n'])).map_err(|e| e.to_string())?;
+        println!("{}", hash);
+        return Ok(());
     }
 
-    /// 보드가 가득 찼는지 검사
-    fn is_full(&self) -> bool {
-        self.board.iter().all(|cell| !cell.is_empty())
+    // 기본은 사람이 읽기 쉬운 형식, --log-json이면 JSON (RUST_LOG로 레벨 조절)
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let log = if args.log_json {
+        tracing_subscriber::fmt::layer().json().boxed()
+    } else {
+        tracing_subscriber::fmt::layer().compact().boxed()
+    };
+    // --otlp-endpoint가 있으면 로그 레벨과 따로 게임 로직과 저장소 스팬까지 내보냄
+    let tracer_provider = args.otlp_endpoint.as_deref().map(telemetry::init).transpose()?;
+    let spans = tracer_provider.as_ref().map(|provider| telemetry::layer(provider).with_filter(telemetry::span_filter()));
+    tracing_subscriber::registry().with(log.with_filter(filter)).with(spans).init();
+    if let Some(endpoint) = &args.otlp_endpoint {
+        info!(%endpoint, "OpenTelemetry 스팬 내보내기");
     }
-
-    /// 승리 조건 검사
-    fn check_winner(&self) -> Option<String> {
-        let b = &self.board;
-        let lines = [
-            (0, 1, 2),
-            (3, 4, 5),
-            (6, 7, 8),
-            (0, 3, 6),
-            (1, 4, 7),
-            (2, 5, 8),
-            (0, 4, 8),
-            (2, 4, 6),
-        ];
-        for &(a, b_idx, c) in &lines {
-            if !b[a].is_empty() && b[a] == b[b_idx] && b[b_idx] == b[c] {
-                return Some(b[a].clone());
-            }
-        }
-        None
+    let config = Config::load(args.config.as_deref())?;
+    if let Some((field, reason)) = config.validate().into_iter().next() {
+        return Err(format!("설정 값 오류 ({}): {}", field, reason).into());
     }
 
-    /// 현재 게임 상태를 기반으로 기본 업데이트 메시지를 생성
-    fn create_update(&self) -> GameState {
-        GameState {
-            board: self.board.clone(),
-            next_player: self.next_player.clone(),
-            status: self.status.clone(),
-            your_symbol: String::new(), // 각 클라이언트마다 개별 설정 예정
-            error_message: "".into(),    // 기본적으로 오류 메시지는 비어 있음
+    let addr: std::net::SocketAddr = config.listen_addr.parse()?;
+    let compression = args.compression.encoding()?;
+    // 잘못된 인증서나 키는 연결을 받기 전에 알림
+    let tls = match (&args.tls_cert, &args.tls_key) {
+        (Some(cert), Some(key)) => {
+            let files = TlsFiles { cert: cert.clone(), key: key.clone(), client_ca: args.tls_client_auth.clone() };
+            Some(files.load()?)
         }
+        _ => None,
+    };
+
+    let presets = PresetStore::load(config.presets_file.as_deref())?;
+    let ratings = RatingBook::load(config.ratings_file.as_deref())?;
+    let stats = StatsBook::load(args.db.as_deref().or(config.stats_file.as_deref()))?;
+    let (health_reporter, health_server) = health::health_service().await;
+    let snapshot_format = config.snapshot_format;
+    let mut service = TicTacToeService::new(config)
+        .with_presets(presets)
+        .with_ratings(ratings)
+        .with_stats(stats)
+        .with_health(health_reporter);
+    if let Some(path) = &args.record {
+        service = service.with_recorder(GameRecorder::open(path)?);
+        info!(path = %path.display(), "끝난 게임을 파일에 기록");
+    }
+    if let Some(path) = &args.event_log {
+        service = service.with_event_log(Arc::new(GameEventLogger::open(path, args.event_log_max_bytes)?));
+        info!(path = %path.display(), max_bytes = args.event_log_max_bytes, "게임 이벤트를 로그에 기록");
+    }
+    if let Some(dir) = &args.snapshot_dir {
+        service = service.with_snapshots(SnapshotWriter::open(dir, snapshot_format)?);
+        info!(dir = %dir.display(), format = ?snapshot_format, "끝난 게임 스냅샷을 디렉터리에 기록");
     }
+    service.spawn_load_controller();
+    service.spawn_idle_sweeper();
+    #[cfg(unix)]
+    spawn_reload_on_sighup(service.clone(), args.config.clone())?;
+    let auth = start_auth(&service.config())?;
+    start_metrics(&service)?;
+    start_http_api(&service, auth.clone())?;
 
-    /// 모든 연결된 플레이어에게 업데이트 메시지 전송
-    async fn broadcast_update(&self) {
-        let mut update = self.create_update();
-        if let Some(ref player_x) = self.player_x {
-            update.your_symbol = player_x.symbol.clone();
-            let _ = player_x.tx.send(update.clone()).await;
-        }
-        if let Some(ref player_o) = self.player_o {
-            update.your_symbol = player_o.symbol.clone();
-            let _ = player_o.tx.send(update.clone()).await;
-        }
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    info!(%addr, tls = tls.is_some(), client_auth = args.tls_client_auth.is_some(), compression = ?args.compression, "TicTacToeServer 실행 중");
+    service.set_health(ServingStatus::Serving).await;
+
+    // 종료 신호를 받으면 새 참가를 막고 헬스 체크를 NOT_SERVING으로 바꾼 뒤, 진행 중인 게임이 끝나기를
+    // shutdown_drain_timeout_secs까지 기다렸다가 남은 게임을 끝내고 서버를 멈춤
+    let shutdown = service.clone();
+    let config = service.config();
+    let mut builder = Server::builder();
+    if let Some(tls) = tls {
+        builder = builder.tls_config(tls)?;
     }
+    // 접근 로그는 access_log 타깃이라 RUST_LOG로도 따로 거를 수 있음
+    let access_log = if args.no_access_log { AccessLogLayer::disabled() } else { AccessLogLayer::new() };
+    if args.web {
+        info!(origins = ?config.web_allowed_origins, "gRPC-Web 요청 받음");
+    }
+    builder
+        // --web이면 브라우저의 gRPC-Web 요청(HTTP/1.1)도 같은 포트에서 받아 gRPC로 바꿈
+        .accept_http1(args.web)
+        .layer(access_log)
+        // 클라이언트가 보낸 traceparent를 이어받아 RPC마다 스팬을 엶
+        .layer(TraceContextLayer)
+        .layer(option_layer(args.web.then(|| grpc_web::cors(&config.web_allowed_origins))))
+        .layer(option_layer(args.web.then(GrpcWebLayer::new)))
+        // 잠든 노트북처럼 응답 없는 연결은 PING 응답이 없으면 닫음
+        .http2_keepalive_interval(Some(config.http2_keepalive_interval()))
+        .http2_keepalive_timeout(Some(config.http2_keepalive_timeout()))
+        .add_service(health_server)
+        .add_service(reflection::reflection_service()?)
+        .add_service(reflection::reflection_service_v1alpha()?)
+        .add_service(service.admin().into_server())
+        .add_service(service.tournaments().into_server())
+        .add_service(service.into_authenticated_server(auth, compression))
+        .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async move {
+            shutdown_signal().await;
+            shutdown.shutdown().await;
+        })
+        .await?;
 
-    /// 지정된 플레이어에게 오류 메시지를 전송합니다.
-    async fn send_error(&self, symbol: &str, error_msg: &str) {
-        let mut update = self.create_update();
-        update.status = "error".into(); // 오류 상태로 설정
-        update.error_message = error_msg.to_string();
-        update.your_symbol = symbol.to_string();
-        if symbol == "X" {
-            if let Some(ref player_x) = self.player_x {
-                let _ = player_x.tx.send(update).await;
-            }
-        } else if symbol == "O" {
-            if let Some(ref player_o) = self.player_o {
-                let _ = player_o.tx.send(update).await;
-            }
+    // 모아 둔 스팬을 마저 내보냄
+    if let Some(provider) = tracer_provider {
+        if let Err(e) = provider.shutdown() {
+            warn!(error = %e, "OpenTelemetry 스팬 내보내기 종료 실패");
         }
     }
+    Ok(())
 }
 
-////////////////////////////
-// 2. gRPC 서비스 구현     //
-////////////////////////////
-
-/// gRPC 서비스 구조체 (게임 상태 공유)
-#[derive(Clone)]
-struct TicTacToeService {
-    game: Arc<Mutex<SharedGame>>,
+/// auth_secret이 있으면 토큰 발급 HTTP 서버를 띄우고 토큰을 검사하는 인터셉터를, 없으면 검사하지 않는 인터셉터를 반환
+fn start_auth(config: &Config) -> Result<AuthInterceptor, Box<dyn std::error::Error>> {
+    let Some(secret) = &config.auth_secret else {
+        info!("auth_secret 없음, 인증 없이 실행");
+        return Ok(AuthInterceptor::disabled());
+    };
+    let users = UserStore::load(config.users_file.as_deref())?;
+    if config.users_file.is_none() {
+        warn!("users_file 없음, 아무도 토큰을 발급받을 수 없습니다");
+    }
+    let keys = Arc::new(TokenKeys::new(secret.as_bytes(), config.auth_token_ttl()));
+    let addr = config.auth_listen_addr.parse()?;
+    let router = auth_http::router(keys.clone(), Arc::new(users));
+    tokio::spawn(async move {
+        if let Err(e) = auth_http::serve(addr, router).await {
+            warn!(%addr, error = %e, "토큰 발급 서버 종료");
+        }
+    });
+    Ok(AuthInterceptor::new(keys))
 }
 
-#[tonic::async_trait]
-impl TicTacToe for TicTacToeService {
-    type PlayStream = ResponseStream;
-
-    async fn play(
-        &self,
-        request: Request<tonic::Streaming<Move>>,
-    ) -> Result<Response<Self::PlayStream>, Status> {
-        println!("새 클라이언트 접속: {:?}", request.remote_addr());
-        let (tx, rx) = mpsc::channel(32);
-        let mut assigned_symbol = String::new();
+/// metrics_listen_addr이 있으면 Prometheus 지표 HTTP 서버를 띄움
+fn start_metrics(service: &TicTacToeService) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(addr) = service.config().metrics_listen_addr.clone() else {
+        return Ok(());
+    };
+    let addr = addr.parse()?;
+    let router = metrics_http::router(service.metrics());
+    tokio::spawn(async move {
+        if let Err(e) = metrics_http::serve(addr, router).await {
+            warn!(%addr, error = %e, "지표 서버 종료");
+        }
+    });
+    Ok(())
+}
 
-        // 플레이어 할당 및 초기 상태 전송
-        {
-            let mut game = self.game.lock().await;
-            if game.player_x.is_none() {
-                assigned_symbol = "X".to_string();
-                game.player_x = Some(PlayerConnection {
-                    symbol: assigned_symbol.clone(),
-                    tx: tx.clone(),
-                });
-                println!("플레이어 X 할당");
-                let init_state = GameState {
-                    board: game.board.clone(),
-                    next_player: game.next_player.clone(),
-                    status: game.status.clone(),
-                    your_symbol: assigned_symbol.clone(),
-                    error_message: "".into(),
-                };
-                let _ = tx.clone().try_send(init_state);
-            } else if game.player_o.is_none() {
-                assigned_symbol = "O".to_string();
-                game.player_o = Some(PlayerConnection {
-                    symbol: assigned_symbol.clone(),
-                    tx: tx.clone(),
-                });
-                game.status = "ongoing".to_string();
-                println!("플레이어 O 할당, 게임 시작 (ongoing)");
-                game.broadcast_update().await;
-            } else {
-                return Err(Status::resource_exhausted("이미 두 명의 플레이어가 접속되어 있습니다."));
-            }
+/// http_listen_addr이 비어 있지 않으면 HTTP JSON API 서버를 띄움 (gRPC와 같은 인증 인터셉터 사용)
+fn start_http_api(service: &TicTacToeService, auth: AuthInterceptor) -> Result<(), Box<dyn std::error::Error>> {
+    let addr = service.config().http_listen_addr.clone();
+    if addr.is_empty() {
+        return Ok(());
+    }
+    let addr = addr.parse()?;
+    let router = api_http::router(service.clone(), auth);
+    tokio::spawn(async move {
+        if let Err(e) = api_http::serve(addr, router).await {
+            warn!(%addr, error = %e, "HTTP JSON API 서버 종료");
         }
+    });
+    Ok(())
+}
 
-        // 클라이언트가 보내는 이동(Move) 메시지 처리
-        let game_clone = self.game.clone();
-        let symbol_clone = assigned_symbol.clone();
-        let mut inbound = request.into_inner();
+/// Ctrl-C(또는 유닉스의 SIGTERM)를 받을 때까지 대기
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
 
-        tokio::spawn(async move {
-            while let Some(result) = inbound.message().await.transpose() {
-                match result {
-                    Ok(mv) => {
-                        println!("플레이어 {}가 {}번 칸에 두려 함", symbol_clone, mv.position);
-                        let mut game = game_clone.lock().await;
-                        // 게임이 진행 중인지 검사
-                        if game.status != "ongoing" {
-                            println!("게임이 진행 중이 아님");
-                            game.send_error(&symbol_clone, "Game is not ongoing.").await;
-                            continue;
-                        }
-                        // 차례 확인
-                        if game.next_player != symbol_clone {
-                            println!("현재 차례 아님: {}", symbol_clone);
-                            game.send_error(&symbol_clone, "It's not your turn.").await;
-                            continue;
-                        }
-                        let pos = mv.position as usize;
-                        // 위치 유효성 검사
-                        if pos >= 9 {
-                            println!("잘못된 위치: {}", pos);
-                            game.send_error(&symbol_clone, "Invalid position.").await;
-                            continue;
-                        }
-                        if !game.board[pos].is_empty() {
-                            println!("칸 {}이 이미 채워짐", pos);
-                            game.send_error(&symbol_clone, "Cell already occupied.").await;
-                            continue;
-                        }
-                        // 이동 적용
-                        game.board[pos] = symbol_clone.clone();
-                        // 승리 검사 및 상태 업데이트
-                        if let Some(winner) = game.check_winner() {
-                            game.status = format!("{}_win", winner);
-                        } else if game.is_full() {
-                            game.status = "draw".to_string();
-                        } else {
-                            game.next_player = if symbol_clone == "X" { "O".into() } else { "X".into() };
-                        }
-                        // 모든 플레이어에게 업데이트 전송
-                        game.broadcast_update().await;
-                    }
-                    Err(e) => {
-                        println!("메시지 수신 에러: {:?}", e);
-                        break;
-                    }
-                }
+        if let Ok(mut terminate) = signal(SignalKind::terminate()) {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = terminate.recv() => {}
             }
-            println!("플레이어 {} 접속 종료", symbol_clone);
-            let mut game = game_clone.lock().await;
-            
-            game.player_x = None;
-            game.player_o = None;
-            game.board = vec!["".into(); 9];
-            game.next_player = "X".into();
-            game.status = "waiting".to_string();
-        });
-
-        let output_stream = Box::pin(ReceiverStream::new(rx).map(|state| Ok(state)));
-        Ok(Response::new(output_stream))
+            return;
+        }
+    }
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        warn!(error = %e, "종료 신호 대기 실패");
     }
 }
 
-////////////////////////////
-// 3. 서버 실행           //
-////////////////////////////
-
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let addr = "[::1]:50051".parse()?;
-    println!("TicTacToeServer가 {}에서 실행 중입니다", addr);
-
-    let game = Arc::new(Mutex::new(SharedGame::new()));
-    let service = TicTacToeService { game };
-
-    Server::builder()
-        .add_service(TicTacToeServer::new(service))
-        .serve(addr)
-        .await?;
+/// SIGHUP을 받을 때마다 같은 경로의 설정을 다시 읽어 반영 (결과는 로그와 GetLastReloadReport로 확인)
+#[cfg(unix)]
+fn spawn_reload_on_sighup(service: TicTacToeService, path: Option<PathBuf>) -> std::io::Result<()> {
+    use tokio::signal::unix::{signal, SignalKind};
 
+    let mut hangups = signal(SignalKind::hangup())?;
+    let source = path.as_ref().map_or_else(|| "server.toml".to_string(), |p| p.display().to_string());
+    tokio::spawn(async move {
+        while hangups.recv().await.is_some() {
+            info!(source = %source, "SIGHUP 수신, 설정 다시 읽기");
+            let loaded = Config::load(path.as_deref()).map_err(|e| e.to_string());
+            service.reload_config(loaded, &source).await;
+        }
+    });
     Ok(())
 }
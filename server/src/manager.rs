@@ -0,0 +1,567 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
+use tokio::sync::oneshot;
+use tracing::{info, warn};
+
+use crate::actor::GameHandle;
+use crate::board::{DEFAULT_BOARD_SIZE, DEFAULT_WIN_LENGTH};
+use crate::config::FirstPlayer;
+use crate::egress::stream_end;
+use crate::error::GameError;
+use crate::event_log::GameEventLogger;
+use crate::game::{generate_session_token, SharedGame, UpdateSender};
+use crate::invites::InviteBook;
+use crate::matchmaking::{MatchmakingQueue, MatchmakingSender, QueuedPlayer};
+use crate::metrics::Metrics;
+use crate::record::GameRecorder;
+use crate::snapshot::SnapshotWriter;
+use crate::presets::GameOptions;
+use crate::stats::CompletedGame;
+use crate::tictactoe::{
+    EndReason, GameDetail, GameState, GameStatusFilter, GameSummary, Join, ListGamesRequest, ListGamesResponse, ServerStats,
+};
+
+/// 로비 목록 한 페이지의 최대 게임 수 (요청에 page_size가 없을 때의 기본값)
+const MAX_LISTED_GAMES: usize = 100;
+
+/// 접속한 클라이언트가 게임에서 맡은 역할
+pub enum Seat {
+    /// 플레이어 (심볼, 연결 번호)
+    Player { symbol: String, connection_id: u64 },
+    /// 관전자
+    Spectator,
+}
+
+/// 서버에서 진행 중인 모든 게임을 관리합니다.
+///
+/// 게임마다 상태를 가진 액터가 따로 돌고, 매니저는 그 핸들만 가집니다. 게임 액터는 매니저를 잠그지 않으므로
+/// 매니저를 잠근 채 게임에 명령을 보내고 기다려도 됩니다.
+pub struct GameManager {
+    games: HashMap<String, GameHandle>,
+    next_game_id: u64,
+    max_games: usize, // 동시에 진행할 수 있는 최대 게임 수
+    queue: MatchmakingQueue, // 매치메이킹 대기열
+    invites: InviteBook,     // 비공개 게임 초대 코드
+    peak_games: usize,       // 동시에 있었던 게임 수의 최댓값
+    metrics: Arc<Metrics>,   // 게임 수 게이지와 끝난 게임 결과
+    first_player: FirstPlayer, // 새 게임에서 먼저 두는 쪽을 정하는 방식
+    alternate_o_next: bool,  // "alternate"에서 다음 게임을 O가 먼저 두는지
+    recorder: Option<GameRecorder>, // 끝난 게임을 기록할 파일 (--record가 없으면 None)
+    event_log: Option<Arc<GameEventLogger>>, // 새 게임에 넘겨 줄 이벤트 로그 (--event-log가 없으면 None)
+    snapshots: Option<SnapshotWriter>, // 끝난 게임 스냅샷을 쓸 디렉터리 (--snapshot-dir가 없으면 None)
+    spectator_drop_limit: u32, // 관전 스트림을 끊기까지 건너뛸 수 있는 업데이트 수 (0이면 끊지 않음)
+    turn_timeout: Option<Duration>, // 새 게임의 차례 제한 시간 (첫 업데이트부터 남은 시간을 알리기 위함)
+}
+
+/// 매치메이킹으로 시작된 게임과, 아직 Play로 접속하지 않은 두 자리 (심볼, 연결 번호)
+pub struct MatchedGame {
+    pub game: GameHandle,
+    pub seats: Vec<(String, u64)>,
+}
+
+impl GameManager {
+    pub fn new(max_games: usize, metrics: Arc<Metrics>) -> Self {
+        GameManager {
+            games: HashMap::new(),
+            next_game_id: 0,
+            max_games,
+            queue: MatchmakingQueue::default(),
+            invites: InviteBook::default(),
+            peak_games: 0,
+            metrics,
+            first_player: FirstPlayer::X,
+            alternate_o_next: false,
+            recorder: None,
+            event_log: None,
+            snapshots: None,
+            spectator_drop_limit: 0,
+            turn_timeout: None,
+        }
+    }
+
+    /// 먼저 두는 쪽을 정하는 방식 변경 (새로 만드는 게임부터 적용)
+    pub fn set_first_player(&mut self, first_player: FirstPlayer) {
+        self.first_player = first_player;
+    }
+
+    /// 끝난 게임을 기록할 곳 지정 (관리자 강제 종료와 서버 종료로 끝난 게임)
+    pub fn set_recorder(&mut self, recorder: GameRecorder) {
+        self.recorder = Some(recorder);
+    }
+
+    /// 이후 만드는 게임이 이벤트를 남길 로그 지정
+    pub fn set_event_log(&mut self, event_log: Arc<GameEventLogger>) {
+        self.event_log = Some(event_log);
+    }
+
+    /// 끝난 게임의 스냅샷을 쓸 곳 지정 (관리자 강제 종료, 서버 종료, 응답 없음으로 끝난 게임)
+    pub fn set_snapshots(&mut self, snapshots: SnapshotWriter) {
+        self.snapshots = Some(snapshots);
+    }
+
+    /// 새 게임에서 먼저 둘 심볼 ("alternate"면 부를 때마다 번갈아 바뀜)
+    fn pick_first_player(&mut self) -> &'static str {
+        let o_first = match self.first_player {
+            FirstPlayer::X => false,
+            FirstPlayer::O => true,
+            FirstPlayer::Random => rand::random(),
+            FirstPlayer::Alternate => {
+                self.alternate_o_next = !self.alternate_o_next;
+                !self.alternate_o_next
+            }
+        };
+        if o_first { "O" } else { "X" }
+    }
+
+    /// 관전 스트림을 끊기까지 건너뛸 수 있는 업데이트 수 변경 (새로 관전하는 연결부터 적용)
+    pub fn set_spectator_drop_limit(&mut self, limit: u32) {
+        self.spectator_drop_limit = limit;
+    }
+
+    /// 차례 제한 시간 변경 (새로 만드는 게임부터 적용, 진행 중인 게임은 다음 차례의 타이머를 켤 때 맞춤)
+    pub fn set_turn_timeout(&mut self, timeout: Option<Duration>) {
+        self.turn_timeout = timeout;
+    }
+
+    /// 최대 게임 수 변경 (이미 진행 중인 게임은 그대로 두고 새 게임 생성에만 적용)
+    pub fn set_max_games(&mut self, max_games: usize) {
+        self.max_games = max_games;
+    }
+
+    /// ID로 게임 조회
+    pub fn get(&self, game_id: &str) -> Option<GameHandle> {
+        self.games.get(game_id).cloned()
+    }
+
+    /// 새 게임을 만들어 등록 (최대 게임 수에 도달했으면 None)
+    fn create_game(&mut self, game: impl FnOnce(String) -> SharedGame) -> Option<GameHandle> {
+        if self.games.len() >= self.max_games {
+            return None;
+        }
+        self.next_game_id += 1;
+        let game_id = self.next_game_id.to_string();
+        let mut game = game(game_id.clone());
+        game.set_first_player(self.pick_first_player());
+        game.event_log = self.event_log.clone();
+        game.metrics = self.metrics.clone();
+        game.turn_timeout = self.turn_timeout;
+        let game = GameHandle::spawn(game);
+        self.games.insert(game_id.clone(), game.clone());
+        self.peak_games = self.peak_games.max(self.games.len());
+        self.metrics.game_created();
+        info!(%game_id, "게임 생성");
+        Some(game)
+    }
+
+    /// 프리셋으로 정해진 옵션과 보드 크기의 비공개 게임 생성 (플레이어는 Join.game_id로 참가)
+    /// (최대 게임 수에 도달했으면 None)
+    pub fn create_preset_game(
+        &mut self,
+        preset: &str,
+        options: GameOptions,
+        board_size: usize,
+        win_length: usize,
+    ) -> Option<GameHandle> {
+        self.create_game(|game_id| preset_game(game_id, preset, options, board_size, win_length))
+    }
+
+    /// 초대 코드가 있어야 O 자리에 앉을 수 있는 비공개 게임 생성. 게임과 발급한 초대 코드를 반환합니다.
+    /// (최대 게임 수에 도달했으면 None)
+    pub async fn create_private_game(
+        &mut self,
+        preset: &str,
+        options: GameOptions,
+        board_size: usize,
+        win_length: usize,
+        invite_ttl: Duration,
+    ) -> Option<(GameHandle, String)> {
+        let game = self.create_game(|game_id| {
+            let mut game = preset_game(game_id, preset, options, board_size, win_length);
+            game.invite_only = true;
+            game
+        })?;
+        let code = self.invites.issue(game.game_id(), invite_ttl);
+        let invite_code = code.clone();
+        game.with(move |game| game.invite_code = invite_code).await;
+        Some((game, code))
+    }
+
+    /// 만료되지 않은 초대 코드가 가리키는 게임
+    fn invited_game(&self, code: &str) -> Option<GameHandle> {
+        self.invites.resolve(code).and_then(|game_id| self.get(&game_id))
+    }
+
+    /// 초대 코드로 비공개 게임의 O 자리를 받습니다. (게임, 연결 번호, O 자리의 초기 상태)를 반환합니다.
+    pub async fn join_by_invite(&mut self, code: &str) -> Result<(GameHandle, u64, GameState), GameError> {
+        let game = self.invited_game(code).ok_or(GameError::InvalidInviteCode)?;
+        let seated = game.run(|game| Box::pin(game.seat_invitee())).await;
+        let (connection_id, state) = seated.ok_or(GameError::OpponentAlreadyJoined)?;
+        info!(game_id = %state.game_id, "초대 코드로 참가");
+        Ok((game, connection_id, state))
+    }
+
+    /// 게임 제거 (모든 플레이어가 떠난 뒤 호출)
+    pub fn remove(&mut self, game_id: &str) {
+        if self.games.remove(game_id).is_some() {
+            self.invites.remove_game(game_id);
+            self.metrics.game_removed();
+            info!(%game_id, "게임 제거");
+        }
+    }
+
+    /// Join 메시지에 따라 게임을 찾아(또는 만들어) 플레이어/관전자로 참가시킵니다.
+    /// `rating`은 Join의 player_id에 해당하는 현재 레이팅입니다 (게임 상태에 표시).
+    pub async fn join(&mut self, join: &Join, tx: UpdateSender, rating: Option<i32>) -> Result<(GameHandle, Seat), GameError> {
+        if join.spectate {
+            let game = self.spectate(&join.game_id, tx).await?;
+            return Ok((game, Seat::Spectator));
+        }
+
+        let game = if !join.invite_code.is_empty() {
+            self.invited_game(&join.invite_code).ok_or(GameError::InvalidInviteCode)?
+        } else if !join.game_id.is_empty() {
+            self.get(&join.game_id).ok_or(GameError::GameNotFound)?
+        } else if !join.session_token.is_empty() {
+            self.find_session(&join.session_token)
+                .await
+                .ok_or(GameError::SessionNotFound)?
+        } else {
+            match self.find_open_game().await {
+                Some(game) => game,
+                None => self
+                    .create_game(|game_id| SharedGame::new(game_id, DEFAULT_BOARD_SIZE, DEFAULT_WIN_LENGTH))
+                    .ok_or(GameError::TooManyGames)?,
+            }
+        };
+
+        let (symbol, connection_id) = game.join(join.clone(), tx, rating).await?;
+        Ok((game, Seat::Player { symbol, connection_id }))
+    }
+
+    /// 관전자로 등록하고 현재 상태를 바로 보냅니다. `game_id`가 비어 있으면 가장 최근에 만든 진행 중인 게임을 관전합니다.
+    pub async fn spectate(&mut self, game_id: &str, tx: UpdateSender) -> Result<GameHandle, GameError> {
+        let game = match game_id {
+            "" => self.newest_ongoing_game().await.ok_or(GameError::GameNotFound)?,
+            game_id => self.readable(game_id, "").await?,
+        };
+        let drop_limit = self.spectator_drop_limit;
+        game.with(move |game| game.add_spectator(tx, drop_limit)).await;
+        Ok(game)
+    }
+
+    /// ID로 게임을 찾되, `session_token`으로 볼 수 없는 초대 전용 게임은 없는 게임처럼 다룸
+    /// (GetGameState, GetHistory, ExportGame, Spectate 공통)
+    pub async fn readable(&self, game_id: &str, session_token: &str) -> Result<GameHandle, GameError> {
+        let game = self.get(game_id).ok_or(GameError::GameNotFound)?;
+        let token = session_token.to_string();
+        match game.with(move |game| game.readable_by(&token)).await {
+            true => Ok(game),
+            false => Err(GameError::GameNotFound),
+        }
+    }
+
+    /// 가장 최근에 만든 진행 중인 공개 게임 (CreateGame이나 CreatePrivateGame으로 만든 게임은 고르지 않음)
+    async fn newest_ongoing_game(&self) -> Option<GameHandle> {
+        let mut newest: Option<(u64, &GameHandle)> = None;
+        for (game_id, game) in &self.games {
+            let id = game_id.parse::<u64>().unwrap_or(0);
+            if newest.is_some_and(|(newest_id, _)| newest_id >= id) {
+                continue;
+            }
+            if game.with(|game| game.status == "ongoing" && !game.private).await {
+                newest = Some((id, game));
+            }
+        }
+        newest.map(|(_, game)| game.clone())
+    }
+
+    /// 매치메이킹 대기열에 등록하고, 두 명이 모이면 먼저 온 순서대로 짝지어 새 게임을 시작합니다.
+    /// 시작된 게임 목록과, 등록한 플레이어가 대기열에서 빠지면 완료되는 수신 측을 반환합니다.
+    pub async fn join_matchmaking(&mut self, name: &str, tx: MatchmakingSender) -> (Vec<MatchedGame>, oneshot::Receiver<()>) {
+        self.queue.prune_closed();
+        let (player, dequeued) = QueuedPlayer::new(generate_session_token(), name.to_string(), tx);
+        self.queue.push(player);
+        info!(player_name = %name, queue_len = self.queue.len(), "매치메이킹 대기열 등록");
+
+        let mut matched = Vec::new();
+        while self.queue.len() >= 2 {
+            let Some(game) = self.create_game(|game_id| SharedGame::new(game_id, DEFAULT_BOARD_SIZE, DEFAULT_WIN_LENGTH)) else {
+                warn!(queue_len = self.queue.len(), "최대 게임 수에 도달해 매칭 보류");
+                break;
+            };
+            let Some((first, second)) = self.queue.pop_pair() else {
+                break;
+            };
+            let seats = game.with(|game| game.seat_matched_players()).await;
+            let mut unclaimed = Vec::new();
+            for (player, (symbol, connection_id, update)) in [&first, &second].into_iter().zip(seats) {
+                info!(player_name = %player.name, player_symbol = %symbol, game_id = %update.game_id, "매칭 완료");
+                player.send_match(update).await;
+                unclaimed.push((symbol, connection_id));
+            }
+            matched.push(MatchedGame { game, seats: unclaimed });
+        }
+        self.queue.broadcast_positions().await;
+        (matched, dequeued)
+    }
+
+    /// 대기표로 매치메이킹 대기열에서 나감 (대기열에 없으면 false)
+    pub async fn leave_matchmaking(&mut self, ticket: &str) -> bool {
+        let removed = self.queue.remove(ticket);
+        if removed {
+            info!(queue_len = self.queue.len(), "매치메이킹 대기열에서 나감");
+            self.queue.broadcast_positions().await;
+        }
+        removed
+    }
+
+    /// 스트림을 닫은 대기자를 정리하고 남은 대기자에게 순번을 다시 알림
+    pub async fn prune_matchmaking(&mut self) {
+        if self.queue.prune_closed() {
+            info!(queue_len = self.queue.len(), "연결이 끊긴 대기자 정리");
+            self.queue.broadcast_positions().await;
+        }
+    }
+
+    /// 서버 종료: 모든 게임의 스트림과 매치메이킹 대기자 스트림을 종료 사유와 함께 끝냄
+    pub async fn end_all_streams(&mut self, reason: EndReason) {
+        for game in self.games.values() {
+            game.run(move |game| Box::pin(game.end_streams(reason))).await;
+        }
+        self.queue.close_all(stream_end(reason, "")).await;
+    }
+
+    /// 서버 종료 시작: 진행 중인 게임에는 `drain` 안에 끝내 달라고 알리고, 상대를 기다리는 게임과
+    /// 매치메이킹 대기열은 바로 종료 사유와 함께 닫습니다.
+    pub async fn announce_shutdown(&mut self, drain: Duration) {
+        let notice = format!("The server is shutting down. Finish your game within {}s.", drain.as_secs());
+        let mut closed = Vec::new();
+        for handle in self.games.values() {
+            let notice = notice.clone();
+            let close = handle
+                .run(move |game| {
+                    Box::pin(async move {
+                        if game.status == "ongoing" {
+                            game.broadcast_message(&notice).await;
+                        } else if !game.is_finished() {
+                            game.end_streams(EndReason::ServerShutdown).await;
+                            game.reset();
+                            return true;
+                        }
+                        false
+                    })
+                })
+                .await;
+            if close {
+                closed.push(handle.game_id().to_string());
+            }
+        }
+        for game_id in closed {
+            self.remove(&game_id);
+        }
+        self.queue.close_all(stream_end(EndReason::ServerShutdown, "")).await;
+    }
+
+    /// 진행 중인 게임 수
+    pub async fn ongoing_games(&self) -> usize {
+        let mut count = 0;
+        for game in self.games.values() {
+            if game.with(|game| game.status == "ongoing").await {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// 서버 종료 마무리: 아직 진행 중인 게임을 "server_shutdown"으로 끝낸 뒤 모든 스트림을 닫습니다.
+    pub async fn end_games_for_shutdown(&mut self) {
+        for game in self.games.values() {
+            let log = self.finish_log();
+            game.run(move |game| {
+                Box::pin(async move {
+                    if game.status == "ongoing" {
+                        game.end_for_shutdown().await;
+                        log.record(game);
+                    }
+                })
+            })
+            .await;
+        }
+        self.end_all_streams(EndReason::ServerShutdown).await;
+    }
+
+    /// 진행 중인 게임에서 `timeout` 넘게 아무 메시지도 보내지 않은 플레이어가 있으면 게임을 "idle_timeout"으로
+    /// 끝냅니다. 게임은 목록에 남아 있다가 플레이어들의 접속이 끊기면 평소처럼 정리됩니다.
+    /// 통계에 반영할 끝낸 게임들을 반환합니다.
+    pub async fn expire_idle_players(&mut self, timeout: Duration) -> Vec<CompletedGame> {
+        let mut completed = Vec::new();
+        for game in self.games.values() {
+            let log = self.finish_log();
+            let expired = game
+                .run(move |game| {
+                    Box::pin(async move {
+                        let symbol = game.idle_player(timeout)?;
+                        game.expire_idle(&symbol).await;
+                        log.record(game);
+                        CompletedGame::of(game)
+                    })
+                })
+                .await;
+            completed.extend(expired);
+        }
+        completed
+    }
+
+    /// 관리자 강제 종료: 게임을 "admin_terminated"로 끝내 접속 중인 클라이언트에게 알리고 목록에서 제거합니다.
+    /// 끝낸 게임의 상세 정보를 반환합니다 (없는 게임이면 None).
+    pub async fn force_end(&mut self, game_id: &str, reason: &str) -> Option<GameDetail> {
+        let game = self.get(game_id)?;
+        let (log, reason) = (self.finish_log(), reason.to_string());
+        let detail = game
+            .run(move |game| {
+                Box::pin(async move {
+                    game.terminate(&reason).await;
+                    log.record(game);
+                    game.detail()
+                })
+            })
+            .await;
+        self.remove(game_id);
+        Some(detail)
+    }
+
+    /// 게임 액터 안에서 끝난 게임을 남길 곳 (지표, 게임 기록, 스냅샷)
+    fn finish_log(&self) -> FinishLog {
+        FinishLog { metrics: self.metrics.clone(), recorder: self.recorder.clone(), snapshots: self.snapshots.clone() }
+    }
+
+    /// 관리자용 전체 게임 상세 목록 (생성 순)
+    pub async fn all_games(&self) -> Vec<GameDetail> {
+        let mut games = Vec::new();
+        for game in self.games.values() {
+            games.push(game.with(|game| game.detail()).await);
+        }
+        games.sort_by_key(|detail| detail.game_id.parse::<u64>().unwrap_or(0));
+        games
+    }
+
+    /// 서버 시작 이후의 게임 수 통계
+    pub fn stats(&self) -> ServerStats {
+        ServerStats {
+            total_games: self.next_game_id as i64,
+            active_games: self.games.len() as i32,
+            peak_games: self.peak_games as i32,
+        }
+    }
+
+    /// 세션 토큰을 가진 플레이어가 있는 게임 검색
+    async fn find_session(&self, token: &str) -> Option<GameHandle> {
+        for game in self.games.values() {
+            let token = token.to_string();
+            if game.with(move |game| game.has_session(&token)).await {
+                return Some(game.clone());
+            }
+        }
+        None
+    }
+
+    /// 상대를 기다리는 공개 게임 중 가장 오래된 것
+    async fn find_open_game(&self) -> Option<GameHandle> {
+        let mut oldest = None;
+        for game in self.games.values() {
+            let open = game.with(|game| (game.has_open_seat() && !game.private).then_some(game.created_at)).await;
+            if let Some(created_at) = open.filter(|created_at| oldest.as_ref().is_none_or(|(oldest, _)| created_at < oldest)) {
+                oldest = Some((created_at, game.clone()));
+            }
+        }
+        oldest.map(|(_, game)| game)
+    }
+
+    /// 로비용 게임 목록 (생성 순, 상태 필터와 페이지 적용, 초대 전용 게임은 빠짐)
+    ///
+    /// 페이지 토큰은 이전 페이지의 마지막 게임 ID입니다. 게임 ID는 생성 순으로 증가하므로
+    /// 페이지 사이에 게임이 추가되거나 제거되어도 항목이 중복되거나 빠지지 않습니다.
+    pub async fn list_games(&self, request: &ListGamesRequest) -> Result<ListGamesResponse, GameError> {
+        let after = match request.page_token.as_str() {
+            "" => 0,
+            token => token.parse::<u64>().map_err(|_| GameError::InvalidArgument("잘못된 페이지 토큰입니다.".into()))?,
+        };
+        let page_size = match request.page_size {
+            size if size <= 0 => MAX_LISTED_GAMES,
+            size => (size as usize).min(MAX_LISTED_GAMES),
+        };
+        let filter = request.filter();
+
+        let mut summaries = Vec::new();
+        for game in self.games.values() {
+            let id = game.game_id().parse::<u64>().unwrap_or(0);
+            if id <= after {
+                continue;
+            }
+            let summary = game
+                .with(move |game| {
+                    (status_matches(filter, game) && !game.invite_only).then(|| GameSummary {
+                        game_id: game.game_id.clone(),
+                        status: game.status.clone(),
+                        player_count: game.player_count() as i32,
+                        spectator_count: game.spectator_count() as i32,
+                        created_at_unix: game.created_at.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64),
+                    })
+                })
+                .await;
+            summaries.extend(summary.map(|summary| (id, summary)));
+        }
+        summaries.sort_by_key(|(id, _)| *id);
+
+        let truncated = summaries.len() > page_size;
+        summaries.truncate(page_size);
+        let next_page_token = match summaries.last() {
+            Some((id, _)) if truncated => id.to_string(),
+            _ => String::new(),
+        };
+        Ok(ListGamesResponse {
+            games: summaries.into_iter().map(|(_, summary)| summary).collect(),
+            truncated,
+            next_page_token,
+        })
+    }
+}
+
+/// 매니저가 끝낸 게임(관리자 강제 종료, 서버 종료, 응답 없음)의 결과를 남길 곳
+struct FinishLog {
+    metrics: Arc<Metrics>,
+    recorder: Option<GameRecorder>,
+    snapshots: Option<SnapshotWriter>,
+}
+
+impl FinishLog {
+    fn record(&self, game: &SharedGame) {
+        self.metrics.game_finished(&game.status);
+        if let Some(recorder) = &self.recorder {
+            recorder.record(game);
+        }
+        if let Some(snapshots) = &self.snapshots {
+            snapshots.write(game);
+        }
+    }
+}
+
+/// 게임 상태가 목록 필터에 해당하는지 검사
+fn status_matches(filter: GameStatusFilter, game: &SharedGame) -> bool {
+    match filter {
+        GameStatusFilter::All => true,
+        GameStatusFilter::Waiting => game.status == "waiting" || game.status == "searching",
+        GameStatusFilter::Ongoing => game.status == "ongoing",
+        GameStatusFilter::Finished => game.is_finished(),
+    }
+}
+
+/// 프리셋 옵션과 보드 크기를 적용한 비공개 게임 (CreateGame, CreatePrivateGame 공통)
+fn preset_game(game_id: String, preset: &str, options: GameOptions, board_size: usize, win_length: usize) -> SharedGame {
+    let mut game = SharedGame::new(game_id, board_size, win_length);
+    game.preset = preset.to_string();
+    game.options = options;
+    game.private = true;
+    game
+}
@@ -0,0 +1,101 @@
+//! 매치메이킹 대기열: 먼저 들어온 순서대로 두 명씩 짝지어 새 게임에 앉힙니다.
+//!
+//! 대기 중인 플레이어는 JoinMatchmaking 스트림으로 대기 순번을 받고, 짝이 정해지면 배정된 게임의
+//! 초기 상태(세션 토큰 포함)를 받은 뒤 스트림이 끝납니다. 이후 세션 토큰으로 Play에 접속해 게임을
+//! 이어 갑니다.
+
+use std::collections::VecDeque;
+use tokio::sync::{mpsc, oneshot};
+use tonic::Status;
+use tracing::warn;
+
+use crate::tictactoe::{GameState, MatchmakingUpdate};
+
+/// 대기 중인 클라이언트 스트림으로 업데이트를 보내는 채널
+pub type MatchmakingSender = mpsc::Sender<Result<MatchmakingUpdate, Status>>;
+
+/// 대기열의 플레이어 한 명
+pub struct QueuedPlayer {
+    pub ticket: String, // LeaveMatchmaking에 쓰는 대기표
+    pub name: String,
+    pub tx: MatchmakingSender,
+    _dequeued: oneshot::Sender<()>, // 대기열에서 빠지면 닫혀 연결 감시 태스크를 끝냄
+}
+
+impl QueuedPlayer {
+    /// 대기열 항목과, 항목이 대기열에서 빠지면 완료되는 수신 측을 함께 생성
+    pub fn new(ticket: String, name: String, tx: MatchmakingSender) -> (Self, oneshot::Receiver<()>) {
+        let (dequeued_tx, dequeued_rx) = oneshot::channel();
+        (QueuedPlayer { ticket, name, tx, _dequeued: dequeued_tx }, dequeued_rx)
+    }
+
+    /// 배정된 게임의 초기 상태 전송 (이 뒤 스트림은 닫힘)
+    pub async fn send_match(&self, game: GameState) {
+        let update = MatchmakingUpdate { ticket: self.ticket.clone(), queue_position: 0, game: Some(game) };
+        // 매니저 잠금을 쥔 채 보내므로 기다리지 않음 (받아 가지 않는 대기자가 다른 게임을 멈추지 못하게)
+        if let Err(e) = self.tx.try_send(Ok(update)) {
+            warn!(player_name = %self.name, error = %e, "매칭 결과 전송 실패");
+        }
+    }
+}
+
+/// 선입선출 대기열
+#[derive(Default)]
+pub struct MatchmakingQueue {
+    players: VecDeque<QueuedPlayer>,
+}
+
+impl MatchmakingQueue {
+    pub fn push(&mut self, player: QueuedPlayer) {
+        self.players.push_back(player);
+    }
+
+    /// 대기표로 대기열에서 제거 (없으면 false)
+    pub fn remove(&mut self, ticket: &str) -> bool {
+        let before = self.players.len();
+        self.players.retain(|p| p.ticket != ticket);
+        self.players.len() != before
+    }
+
+    /// 스트림을 닫고 떠난 플레이어 제거 (제거했으면 true)
+    pub fn prune_closed(&mut self) -> bool {
+        let before = self.players.len();
+        self.players.retain(|p| !p.tx.is_closed());
+        self.players.len() != before
+    }
+
+    /// 가장 오래 기다린 두 명을 꺼냄 (두 명이 안 되면 None)
+    pub fn pop_pair(&mut self) -> Option<(QueuedPlayer, QueuedPlayer)> {
+        if self.players.len() < 2 {
+            return None;
+        }
+        Some((self.players.pop_front()?, self.players.pop_front()?))
+    }
+
+    pub fn len(&self) -> usize {
+        self.players.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.players.is_empty()
+    }
+
+    /// 대기 중인 모두의 스트림을 `status`로 끝내고 대기열을 비움
+    pub async fn close_all(&mut self, status: Status) {
+        for player in self.players.drain(..) {
+            if let Err(e) = player.tx.try_send(Err(status.clone())) {
+                warn!(player_name = %player.name, error = %e, "대기자에게 종료 사유 전송 실패");
+            }
+        }
+    }
+
+    /// 대기 중인 모두에게 현재 순번(1부터) 전송
+    pub async fn broadcast_positions(&self) {
+        for (i, player) in self.players.iter().enumerate() {
+            let update = MatchmakingUpdate { ticket: player.ticket.clone(), queue_position: i as i32 + 1, game: None };
+            if let Err(e) = player.tx.try_send(Ok(update)) {
+                warn!(player_name = %player.name, error = %e, "대기 순번 전송 실패");
+            }
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! 운영 지표 (Prometheus 텍스트 형식)
+//!
+//! 게임 수, 접속한 플레이어 수, 수 처리 결과, 끝난 게임 결과, 버퍼가 가득 차 건너뛴 업데이트를 원자적 카운터와
+//! 게이지로 셉니다.
+//! 게임 액터를 거치지 않고 갱신하고 읽을 수 있으며, [`crate::metrics_http`]가 `GET /metrics`로 내보냅니다.
+
+use std::fmt::Write;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::game::{ABANDONED, ADMIN_TERMINATED, IDLE_TIMEOUT, SERVER_SHUTDOWN};
+
+/// `ttt_games_finished_total`의 outcome 값 (끝난 게임의 상태 문자열)
+pub const OUTCOMES: [&str; 10] = [
+    "X_win",
+    "O_win",
+    "draw",
+    "X_win_by_resignation",
+    "O_win_by_resignation",
+    "draw_agreed",
+    ADMIN_TERMINATED,
+    SERVER_SHUTDOWN,
+    IDLE_TIMEOUT,
+    ABANDONED,
+];
+
+/// 서버 전체 지표
+#[derive(Debug, Default)]
+pub struct Metrics {
+    active_games: AtomicI64,
+    connected_players: AtomicI64,
+    moves_accepted: AtomicU64,
+    moves_rejected: AtomicU64,
+    rejected_joins: AtomicU64,
+    games_finished: [AtomicU64; OUTCOMES.len()],
+    player_updates_dropped: AtomicU64,
+    spectator_updates_dropped: AtomicU64,
+    slow_spectators_disconnected: AtomicU64,
+}
+
+impl Metrics {
+    pub fn game_created(&self) {
+        self.active_games.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn game_removed(&self) {
+        self.active_games.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// 플레이어 스트림이 연결되어 있는 동안 접속 수에 포함 (반환된 값을 버리면 빠짐)
+    pub fn player_connected(self: &Arc<Self>) -> ConnectedPlayer {
+        self.connected_players.fetch_add(1, Ordering::Relaxed);
+        ConnectedPlayer { metrics: self.clone() }
+    }
+
+    pub fn move_accepted(&self) {
+        self.moves_accepted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn move_rejected(&self) {
+        self.moves_rejected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 참가(또는 재접속/관전) 요청을 거부함
+    pub fn join_rejected(&self) {
+        self.rejected_joins.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 끝난 게임의 결과 기록 (끝난 상태가 아니면 무시)
+    pub fn game_finished(&self, status: &str) {
+        if let Some(index) = OUTCOMES.iter().position(|outcome| *outcome == status) {
+            self.games_finished[index].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// 플레이어의 전송 버퍼가 가득 차 업데이트를 건너뜀
+    pub fn player_update_dropped(&self) {
+        self.player_updates_dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 관전자의 전송 버퍼가 가득 차 업데이트를 건너뜀
+    pub fn spectator_update_dropped(&self) {
+        self.spectator_updates_dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 건너뛴 업데이트가 너무 많아 관전 스트림을 끊음
+    pub fn slow_spectator_disconnected(&self) {
+        self.slow_spectators_disconnected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn active_games(&self) -> i64 {
+        self.active_games.load(Ordering::Relaxed)
+    }
+
+    pub fn connected_players(&self) -> i64 {
+        self.connected_players.load(Ordering::Relaxed)
+    }
+
+    /// Prometheus 텍스트 형식 (0.0.4)
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let gauge = |out: &mut String, name: &str, help: &str, value: i64| {
+            let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} gauge\n{name} {value}");
+        };
+        gauge(&mut out, "ttt_active_games", "Games currently held by the server.", self.active_games());
+        gauge(&mut out, "ttt_connected_players", "Players with an open Play stream.", self.connected_players());
+
+        let _ = writeln!(out, "# HELP ttt_moves_total Moves received from players.\n# TYPE ttt_moves_total counter");
+        let _ = writeln!(out, "ttt_moves_total{{result=\"accepted\"}} {}", self.moves_accepted.load(Ordering::Relaxed));
+        let _ = writeln!(out, "ttt_moves_total{{result=\"rejected\"}} {}", self.moves_rejected.load(Ordering::Relaxed));
+
+        let _ = writeln!(out, "# HELP ttt_games_finished_total Games that reached a final status.\n# TYPE ttt_games_finished_total counter");
+        for (outcome, count) in OUTCOMES.iter().zip(&self.games_finished) {
+            let _ = writeln!(out, "ttt_games_finished_total{{outcome=\"{}\"}} {}", outcome, count.load(Ordering::Relaxed));
+        }
+
+        let _ = writeln!(out, "# HELP ttt_rejected_joins_total Play streams refused at join.\n# TYPE ttt_rejected_joins_total counter");
+        let _ = writeln!(out, "ttt_rejected_joins_total {}", self.rejected_joins.load(Ordering::Relaxed));
+
+        let _ = writeln!(out, "# HELP ttt_dropped_updates_total Updates skipped because the receiver's buffer was full.\n# TYPE ttt_dropped_updates_total counter");
+        let _ = writeln!(out, "ttt_dropped_updates_total{{recipient=\"player\"}} {}", self.player_updates_dropped.load(Ordering::Relaxed));
+        let _ = writeln!(out, "ttt_dropped_updates_total{{recipient=\"spectator\"}} {}", self.spectator_updates_dropped.load(Ordering::Relaxed));
+
+        let _ = writeln!(out, "# HELP ttt_slow_spectators_disconnected_total Spectator streams closed for falling too far behind.\n# TYPE ttt_slow_spectators_disconnected_total counter");
+        let _ = writeln!(out, "ttt_slow_spectators_disconnected_total {}", self.slow_spectators_disconnected.load(Ordering::Relaxed));
+        out
+    }
+}
+
+/// 접속 중인 플레이어 하나 (drop되면 접속 수에서 빠짐)
+pub struct ConnectedPlayer {
+    metrics: Arc<Metrics>,
+}
+
+impl Drop for ConnectedPlayer {
+    fn drop(&mut self) {
+        self.metrics.connected_players.fetch_sub(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,30 @@
+//! 지표 HTTP 엔드포인트 (`GET /metrics`, Prometheus 텍스트 형식)
+
+use axum::extract::State;
+use axum::http::header;
+use axum::routing::get;
+use axum::Router;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::metrics::Metrics;
+
+/// Prometheus 텍스트 형식의 Content-Type
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// `GET /metrics` 라우터
+pub fn router(metrics: Arc<Metrics>) -> Router {
+    Router::new().route("/metrics", get(scrape)).with_state(metrics)
+}
+
+/// 주소에 바인드해 지표 서버 실행
+pub async fn serve(addr: SocketAddr, router: Router) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    info!(%addr, "지표 서버 실행 중");
+    axum::serve(listener, router).await
+}
+
+async fn scrape(State(metrics): State<Arc<Metrics>>) -> ([(header::HeaderName, &'static str); 1], String) {
+    ([(header::CONTENT_TYPE, CONTENT_TYPE)], metrics.render())
+}
@@ -0,0 +1,193 @@
+//! 3x3 기보 표기 (`ExportGame`, `ImportGame`)
+//!
+//! 수마다 "심볼:칸"을 공백으로 이어 "X:a1 O:b2 X:c3"처럼 적습니다. 칸의 글자는 행(a가 윗줄),
+//! 숫자는 열(1이 왼쪽)이라 a1=0, a2=1, b1=3, c1=6입니다. 읽을 때는 모양만 확인하고, 가져올 때는
+//! [`replay`]로 처음부터 다시 두어 차례와 빈 칸, 승부가 난 뒤의 수까지 규칙에 맞는지 확인합니다.
+//! 3x3 클래식 게임만 적을 수 있습니다.
+//!
+//! 클라이언트의 수 입력("b2" 형식)은 반대로 글자가 열, 숫자가 행이라 같은 글자가 다른 칸을
+//! 가리킵니다 (기보의 a2는 클라이언트 입력으로 b1).
+
+use std::fmt;
+
+use common::state_hash::state_hash;
+
+use crate::board;
+use crate::error::GameError;
+use crate::events::{self, GameEvent};
+use crate::game::SharedGame;
+use crate::tictactoe::{GameMode, GameState};
+
+/// 기보를 적을 수 있는 보드 한 변의 길이
+const SIZE: usize = 3;
+
+/// 수를 둔 심볼
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symbol {
+    X,
+    O,
+}
+
+impl Symbol {
+    /// "X" 또는 "O" (그 밖의 값은 None)
+    pub fn parse(symbol: &str) -> Option<Symbol> {
+        match symbol {
+            "X" => Some(Symbol::X),
+            "O" => Some(Symbol::O),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Symbol::X => "X",
+            Symbol::O => "O",
+        }
+    }
+
+    pub fn opponent(self) -> Symbol {
+        match self {
+            Symbol::X => Symbol::O,
+            Symbol::O => Symbol::X,
+        }
+    }
+}
+
+/// 기보를 읽거나 다시 두지 못한 이유 (`index`는 1부터 세는 수 번호)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotationError {
+    /// "심볼:칸" 모양이 아닌 수
+    Malformed { index: usize, token: String },
+    /// "X", "O"가 아닌 심볼
+    UnknownSymbol { index: usize, symbol: String },
+    /// a1~c3 밖의 칸
+    InvalidSquare { index: usize, square: String },
+    /// 같은 심볼이 연달아 둔 수
+    OutOfTurn { index: usize },
+    /// 이미 채워진 칸에 둔 수
+    CellOccupied { index: usize, square: String },
+    /// 승부가 난 뒤에 둔 수
+    AfterGameOver { index: usize },
+    /// 3x3 클래식 게임이 아니라 기보로 적을 수 없음
+    Unsupported,
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotationError::Malformed { index, token } => write!(f, "{}번째 수 \"{}\"는 \"X:a1\" 모양이 아닙니다.", index, token),
+            NotationError::UnknownSymbol { index, symbol } => write!(f, "{}번째 수의 심볼 \"{}\"는 X나 O가 아닙니다.", index, symbol),
+            NotationError::InvalidSquare { index, square } => write!(f, "{}번째 수의 칸 \"{}\"는 a1~c3 밖입니다.", index, square),
+            NotationError::OutOfTurn { index } => write!(f, "{}번째 수는 상대의 차례에 둔 수입니다.", index),
+            NotationError::CellOccupied { index, square } => write!(f, "{}번째 수의 칸 {}는 이미 채워져 있습니다.", index, square),
+            NotationError::AfterGameOver { index } => write!(f, "{}번째 수는 승부가 난 뒤에 둔 수입니다.", index),
+            NotationError::Unsupported => write!(f, "3x3 클래식 게임만 기보로 적을 수 있습니다."),
+        }
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+impl From<NotationError> for GameError {
+    fn from(error: NotationError) -> Self {
+        GameError::InvalidArgument(error.to_string())
+    }
+}
+
+/// 칸 번호(0~8)의 좌표 ("a1"~"c3")
+pub fn square(position: usize) -> String {
+    let row = (b'a' + (position / SIZE) as u8) as char;
+    format!("{}{}", row, position % SIZE + 1)
+}
+
+/// 좌표("a1"~"c3")의 칸 번호
+pub fn parse_square(square: &str) -> Option<usize> {
+    let mut chars = square.chars();
+    let (Some(row), Some(col), None) = (chars.next(), chars.next(), chars.next()) else {
+        return None;
+    };
+    let row = ('a'..='c').position(|c| c == row)?;
+    let col = ('1'..='3').position(|c| c == col)?;
+    Some(row * SIZE + col)
+}
+
+/// 수 목록을 기보 문자열로 ("X:a1 O:b2 X:c3", 수가 없으면 빈 문자열)
+pub fn export_game_notation(history: &[(Symbol, usize)]) -> String {
+    history.iter().map(|(symbol, position)| format!("{}:{}", symbol.as_str(), square(*position))).collect::<Vec<_>>().join(" ")
+}
+
+/// 기보 문자열을 수 목록으로 (공백은 몇 칸이든 구분자, 차례와 빈 칸은 확인하지 않음)
+pub fn parse_game_notation(s: &str) -> Result<Vec<(Symbol, usize)>, NotationError> {
+    s.split_whitespace()
+        .enumerate()
+        .map(|(i, token)| {
+            let index = i + 1;
+            let (symbol, square) = token.split_once(':').ok_or_else(|| NotationError::Malformed { index, token: token.to_string() })?;
+            let symbol = Symbol::parse(symbol).ok_or_else(|| NotationError::UnknownSymbol { index, symbol: symbol.to_string() })?;
+            let position = parse_square(square).ok_or_else(|| NotationError::InvalidSquare { index, square: square.to_string() })?;
+            Ok((symbol, position))
+        })
+        .collect()
+}
+
+/// 게임에 지금까지 둔 수의 기보 (3x3 클래식 게임이 아니면 Unsupported)
+pub fn of(game: &SharedGame) -> Result<String, NotationError> {
+    if game.mode != GameMode::Classic || game.board_size != SIZE || game.win_length != SIZE {
+        return Err(NotationError::Unsupported);
+    }
+    let history = game
+        .history
+        .iter()
+        .map(|m| Symbol::parse(&m.symbol).map(|symbol| (symbol, m.position as usize)).ok_or(NotationError::Unsupported))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(export_game_notation(&history))
+}
+
+/// 수를 처음부터 다시 두어 만든 게임 이벤트 (두 자리가 찬 게임에서 시작하고, 승부가 나면 GameEnded로 끝남).
+/// 첫 수의 심볼이 먼저 두며(수가 없으면 X), 그 뒤로는 번갈아 두어야 합니다.
+pub fn replay(moves: &[(Symbol, usize)]) -> Result<Vec<GameEvent>, NotationError> {
+    let first_player = moves.first().map_or(Symbol::X, |(symbol, _)| *symbol);
+    let mut events = vec![GameEvent::GameCreated { size: SIZE, first_player: first_player.as_str().to_string() }];
+    for symbol in [Symbol::X, Symbol::O] {
+        events.push(GameEvent::PlayerJoined { symbol: symbol.as_str().to_string(), marker: symbol.as_str().to_string() });
+    }
+
+    let mut board = vec![String::new(); SIZE * SIZE];
+    let mut next = first_player;
+    let mut outcome = None;
+    for (i, &(symbol, position)) in moves.iter().enumerate() {
+        let index = i + 1;
+        if outcome.is_some() {
+            return Err(NotationError::AfterGameOver { index });
+        }
+        if symbol != next {
+            return Err(NotationError::OutOfTurn { index });
+        }
+        let cell = board.get_mut(position).ok_or_else(|| NotationError::InvalidSquare { index, square: square(position) })?;
+        if !cell.is_empty() {
+            return Err(NotationError::CellOccupied { index, square: square(position) });
+        }
+        *cell = symbol.as_str().to_string();
+        events.push(GameEvent::MoveMade { symbol: symbol.as_str().to_string(), pos: position, mark: symbol.as_str().to_string() });
+        next = symbol.opponent();
+
+        outcome = match board::winner(&board, SIZE, SIZE) {
+            Some(winner) => Some(format!("{}_win", winner)),
+            None if board.iter().all(|cell| !cell.is_empty()) => Some("draw".to_string()),
+            None => None,
+        };
+    }
+    if let Some(outcome) = outcome {
+        events.push(GameEvent::GameEnded { outcome });
+    }
+    Ok(events)
+}
+
+/// 기보를 읽고 다시 두어 본 최종 상태 (`ImportGame`)
+pub fn import(notation: &str) -> Result<GameState, NotationError> {
+    let moves = parse_game_notation(notation)?;
+    let mut state = events::fold(&replay(&moves)?);
+    state.win_length = SIZE as i32;
+    state.state_hash = state_hash(&state.board, &state.status, &state.next_player);
+    Ok(state)
+}
@@ -0,0 +1,352 @@
+//! 게임 옵션 프리셋
+//!
+//! 프리셋은 이름 붙은 `GameOptions` 묶음입니다. 게임의 최종 옵션은 기본값 ← 프리셋 ← 덮어쓰기
+//! 순으로 정해지며, 덮어쓰기는 프리셋이 허용한 항목에만 할 수 있습니다. 관리자가 정의한
+//! 프리셋은 설정의 `presets_file`에 TOML로 저장되어 서버를 다시 시작해도 유지됩니다.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use tonic::Status;
+use tracing::{info, warn};
+
+use crate::tictactoe;
+use common::fs::atomic_write;
+
+/// 프리셋을 지정하지 않았을 때 사용하는 프리셋
+pub const DEFAULT_PRESET: &str = "standard";
+
+/// 덮어쓸 수 있는 옵션 이름 (GameOptions 필드 이름과 같음)
+pub const OPTION_NAMES: [&str; 7] = [
+    "max_takebacks",
+    "clock_initial_secs",
+    "clock_increment_secs",
+    "bot_takeover_on_abandon",
+    "hints_allowed",
+    "strict_mode",
+    "spectator_delay_secs",
+];
+
+/// 프리셋 조회, 옵션 계산, 프리셋 정의 실패 이유
+#[derive(Debug, PartialEq)]
+pub enum PresetError {
+    /// 없는 프리셋
+    NotFound(String),
+    /// 프리셋이 덮어쓰기를 허용하지 않는 항목
+    NotOverridable { preset: String, field: &'static str },
+    /// 잘못된 이름이나 옵션 값
+    Invalid(String),
+    /// 내장 프리셋은 수정할 수 없음
+    Builtin(String),
+    /// 프리셋 파일 저장 실패
+    Storage(String),
+}
+
+impl fmt::Display for PresetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PresetError::NotFound(name) => write!(f, "프리셋을 찾을 수 없습니다: {}", name),
+            PresetError::NotOverridable { preset, field } => {
+                write!(f, "'{}' 프리셋은 {} 변경을 허용하지 않습니다.", preset, field)
+            }
+            PresetError::Invalid(reason) => write!(f, "{}", reason),
+            PresetError::Builtin(name) => write!(f, "내장 프리셋은 수정할 수 없습니다: {}", name),
+            PresetError::Storage(reason) => write!(f, "프리셋 파일 저장 실패: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for PresetError {}
+
+impl From<PresetError> for Status {
+    fn from(error: PresetError) -> Self {
+        let message = error.to_string();
+        match error {
+            PresetError::NotFound(_) => Status::not_found(message),
+            PresetError::NotOverridable { .. } | PresetError::Invalid(_) | PresetError::Builtin(_) => {
+                Status::invalid_argument(message)
+            }
+            PresetError::Storage(_) => Status::internal(message),
+        }
+    }
+}
+
+/// 게임 옵션 (파일에 빠진 항목은 기본값 = standard 프리셋의 값)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameOptions {
+    /// 상대 동의로 무를 수 있는 횟수 (-1이면 무제한)
+    pub max_takebacks: i32,
+    /// 플레이어별 시간 (초, 0이면 시계 없음)
+    pub clock_initial_secs: i32,
+    /// 수마다 더해지는 시간 (초)
+    pub clock_increment_secs: i32,
+    /// 플레이어가 나가면 봇이 이어서 둠
+    pub bot_takeover_on_abandon: bool,
+    pub hints_allowed: bool,
+    pub strict_mode: bool,
+    /// 관전자에게 수를 늦게 보여 주는 시간 (초)
+    pub spectator_delay_secs: i32,
+}
+
+impl GameOptions {
+    /// 값 범위 검사
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_takebacks < -1 {
+            return Err("max_takebacks는 -1(무제한) 이상이어야 합니다.".into());
+        }
+        if self.clock_initial_secs < 0 || self.clock_increment_secs < 0 || self.spectator_delay_secs < 0 {
+            return Err("시간 옵션은 0 이상이어야 합니다.".into());
+        }
+        if self.clock_initial_secs == 0 && self.clock_increment_secs > 0 {
+            return Err("시계 없이 clock_increment_secs를 설정할 수 없습니다.".into());
+        }
+        Ok(())
+    }
+
+    /// 지정된 덮어쓰기 항목 이름 목록
+    fn overridden_fields(overrides: &tictactoe::GameOptionsOverrides) -> Vec<&'static str> {
+        let set = [
+            overrides.max_takebacks.is_some(),
+            overrides.clock_initial_secs.is_some(),
+            overrides.clock_increment_secs.is_some(),
+            overrides.bot_takeover_on_abandon.is_some(),
+            overrides.hints_allowed.is_some(),
+            overrides.strict_mode.is_some(),
+            overrides.spectator_delay_secs.is_some(),
+        ];
+        OPTION_NAMES.iter().zip(set).filter(|(_, set)| *set).map(|(name, _)| *name).collect()
+    }
+
+    /// 덮어쓰기 적용 (허용 여부는 호출 전에 검사)
+    fn apply(&mut self, overrides: &tictactoe::GameOptionsOverrides) {
+        if let Some(v) = overrides.max_takebacks {
+            self.max_takebacks = v;
+        }
+        if let Some(v) = overrides.clock_initial_secs {
+            self.clock_initial_secs = v;
+        }
+        if let Some(v) = overrides.clock_increment_secs {
+            self.clock_increment_secs = v;
+        }
+        if let Some(v) = overrides.bot_takeover_on_abandon {
+            self.bot_takeover_on_abandon = v;
+        }
+        if let Some(v) = overrides.hints_allowed {
+            self.hints_allowed = v;
+        }
+        if let Some(v) = overrides.strict_mode {
+            self.strict_mode = v;
+        }
+        if let Some(v) = overrides.spectator_delay_secs {
+            self.spectator_delay_secs = v;
+        }
+    }
+
+    pub fn to_proto(&self) -> tictactoe::GameOptions {
+        tictactoe::GameOptions {
+            max_takebacks: self.max_takebacks,
+            clock_initial_secs: self.clock_initial_secs,
+            clock_increment_secs: self.clock_increment_secs,
+            bot_takeover_on_abandon: self.bot_takeover_on_abandon,
+            hints_allowed: self.hints_allowed,
+            strict_mode: self.strict_mode,
+            spectator_delay_secs: self.spectator_delay_secs,
+        }
+    }
+
+    pub fn from_proto(options: &tictactoe::GameOptions) -> Self {
+        GameOptions {
+            max_takebacks: options.max_takebacks,
+            clock_initial_secs: options.clock_initial_secs,
+            clock_increment_secs: options.clock_increment_secs,
+            bot_takeover_on_abandon: options.bot_takeover_on_abandon,
+            hints_allowed: options.hints_allowed,
+            strict_mode: options.strict_mode,
+            spectator_delay_secs: options.spectator_delay_secs,
+        }
+    }
+}
+
+/// 이름 붙은 옵션 묶음과 덮어쓸 수 있는 항목 목록
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Preset {
+    pub name: String,
+    #[serde(default)]
+    pub options: GameOptions,
+    #[serde(default)]
+    pub overridable: Vec<String>,
+}
+
+impl Preset {
+    fn new(name: &str, options: GameOptions, overridable: &[&str]) -> Self {
+        Preset {
+            name: name.to_string(),
+            options,
+            overridable: overridable.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    /// 이름, 옵션 값, 덮어쓰기 항목 이름 검사
+    fn validate(&self) -> Result<(), String> {
+        let valid_name = !self.name.is_empty()
+            && self.name.len() <= 32
+            && self.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid_name {
+            return Err("프리셋 이름은 1~32자의 영문, 숫자, '-', '_'만 쓸 수 있습니다.".into());
+        }
+        if let Some(unknown) = self.overridable.iter().find(|f| !OPTION_NAMES.contains(&f.as_str())) {
+            return Err(format!("알 수 없는 옵션 이름입니다: {}", unknown));
+        }
+        self.options.validate()
+    }
+
+    pub fn to_proto(&self, builtin: bool) -> tictactoe::Preset {
+        tictactoe::Preset {
+            name: self.name.clone(),
+            options: Some(self.options.to_proto()),
+            overridable: self.overridable.clone(),
+            builtin,
+        }
+    }
+
+    pub fn from_proto(preset: &tictactoe::Preset) -> Self {
+        Preset {
+            name: preset.name.clone(),
+            options: preset.options.as_ref().map(GameOptions::from_proto).unwrap_or_default(),
+            overridable: preset.overridable.clone(),
+        }
+    }
+}
+
+/// 서버 내장 프리셋 (casual, standard, blitz, tournament)
+pub fn builtin_presets() -> Vec<Preset> {
+    vec![
+        Preset::new(
+            "casual",
+            GameOptions { max_takebacks: -1, bot_takeover_on_abandon: true, hints_allowed: true, ..GameOptions::default() },
+            &OPTION_NAMES,
+        ),
+        Preset::new(
+            DEFAULT_PRESET,
+            GameOptions::default(),
+            &["max_takebacks", "clock_initial_secs", "clock_increment_secs", "bot_takeover_on_abandon", "hints_allowed"],
+        ),
+        Preset::new(
+            "blitz",
+            GameOptions { clock_initial_secs: 60, clock_increment_secs: 2, ..GameOptions::default() },
+            &["clock_initial_secs", "clock_increment_secs"],
+        ),
+        Preset::new(
+            "tournament",
+            GameOptions {
+                clock_initial_secs: 300,
+                clock_increment_secs: 5,
+                strict_mode: true,
+                spectator_delay_secs: 30,
+                ..GameOptions::default()
+            },
+            &[],
+        ),
+    ]
+}
+
+/// 프리셋 파일 형식
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PresetFile {
+    #[serde(default)]
+    presets: Vec<Preset>,
+}
+
+/// 내장 프리셋과 관리자가 정의한 프리셋 저장소
+#[derive(Debug, Default)]
+pub struct PresetStore {
+    custom: BTreeMap<String, Preset>,
+    path: Option<PathBuf>, // 사용자 정의 프리셋을 저장할 파일 (없으면 메모리에만 보관)
+}
+
+impl PresetStore {
+    /// 프리셋 파일을 읽어 저장소 생성 (경로가 없거나 파일이 아직 없으면 비어 있는 상태로 시작)
+    pub fn load(path: Option<&Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut store = PresetStore { custom: BTreeMap::new(), path: path.map(Path::to_path_buf) };
+        let Some(path) = path else {
+            return Ok(store);
+        };
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(store),
+            Err(e) => return Err(format!("프리셋 파일을 읽을 수 없습니다 ({}): {}", path.display(), e).into()),
+        };
+        let file: PresetFile =
+            toml::from_str(&text).map_err(|e| format!("프리셋 파일 형식 오류 ({}): {}", path.display(), e))?;
+        for preset in file.presets {
+            store.custom.insert(preset.name.clone(), preset);
+        }
+        info!(path = %path.display(), count = store.custom.len(), "프리셋 파일 로드");
+        Ok(store)
+    }
+
+    /// 전체 프리셋 (내장 프리셋 먼저, 내장 여부와 함께)
+    pub fn list(&self) -> Vec<(Preset, bool)> {
+        builtin_presets()
+            .into_iter()
+            .map(|p| (p, true))
+            .chain(self.custom.values().cloned().map(|p| (p, false)))
+            .collect()
+    }
+
+    /// 이름으로 프리셋 조회 (내장 프리셋 우선)
+    pub fn get(&self, name: &str) -> Option<Preset> {
+        builtin_presets()
+            .into_iter()
+            .find(|p| p.name == name)
+            .or_else(|| self.custom.get(name).cloned())
+    }
+
+    /// 프리셋과 덮어쓰기로 최종 옵션 계산 (빈 이름은 standard)
+    pub fn resolve(&self, name: &str, overrides: &tictactoe::GameOptionsOverrides) -> Result<GameOptions, PresetError> {
+        let name = if name.is_empty() { DEFAULT_PRESET } else { name };
+        let preset = self.get(name).ok_or_else(|| PresetError::NotFound(name.to_string()))?;
+        if let Some(field) = GameOptions::overridden_fields(overrides)
+            .into_iter()
+            .find(|f| !preset.overridable.iter().any(|o| o == f))
+        {
+            return Err(PresetError::NotOverridable { preset: name.to_string(), field });
+        }
+        let mut options = preset.options;
+        options.apply(overrides);
+        options.validate().map_err(PresetError::Invalid)?;
+        Ok(options)
+    }
+
+    /// 사용자 정의 프리셋 추가 또는 수정 후 파일에 저장 (내장 프리셋은 바꿀 수 없음)
+    pub fn define(&mut self, preset: Preset) -> Result<(), PresetError> {
+        preset.validate().map_err(PresetError::Invalid)?;
+        if builtin_presets().iter().any(|p| p.name == preset.name) {
+            return Err(PresetError::Builtin(preset.name));
+        }
+        let previous = self.custom.insert(preset.name.clone(), preset.clone());
+        if let Err(e) = self.save() {
+            // 저장에 실패하면 메모리 상태도 되돌림
+            match previous {
+                Some(previous) => self.custom.insert(preset.name.clone(), previous),
+                None => self.custom.remove(&preset.name),
+            };
+            warn!(name = %preset.name, error = %e, "프리셋 파일 저장 실패");
+            return Err(PresetError::Storage(e.to_string()));
+        }
+        info!(name = %preset.name, "프리셋 정의");
+        Ok(())
+    }
+
+    /// 사용자 정의 프리셋을 파일에 원자적으로 저장
+    fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let file = PresetFile { presets: self.custom.values().cloned().collect() };
+        atomic_write(path, toml::to_string(&file)?.as_bytes())?;
+        Ok(())
+    }
+}
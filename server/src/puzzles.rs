@@ -0,0 +1,166 @@
+//! 3x3 퍼즐 (`GetPuzzle`, `SolvePuzzle`)
+//!
+//! 퍼즐은 정해진 보드에서 이번 차례의 심볼로 둘 정답 칸을 찾는 문제입니다. 내장 퍼즐은 서버 바이너리에
+//! 넣은 `puzzles.toml`에서 처음 쓸 때 한 번 읽고, 읽을 때 보드와 정답이 맞는 모양인지 확인합니다.
+//! 게임과 상관없는 요청이라 매니저를 잠그거나 게임 액터에 명령을 보내지 않습니다.
+
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use rand::seq::SliceRandom;
+use serde::Deserialize;
+
+use crate::board;
+use crate::error::GameError;
+use crate::tictactoe::{Puzzle as PuzzleProto, PuzzleDifficulty, SolveResult};
+
+/// 내장 퍼즐 파일
+const BUILTIN_PUZZLES: &str = include_str!("../puzzles.toml");
+
+/// 퍼즐 보드 크기 (표준 3x3만)
+const SIZE: usize = 3;
+
+/// 퍼즐 한 문제 (`puzzles.toml`의 항목)
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Puzzle {
+    pub id: String,
+    /// "easy", "medium", "hard"
+    pub difficulty: String,
+    /// 윗줄부터 세 칸씩 "/"로 나눈 보드 ("X", "O", 빈 칸은 ".")
+    pub board: String,
+    /// 이번에 둘 심볼
+    pub winning_symbol: String,
+    /// 정답 칸 (0~8)
+    pub winning_positions: Vec<i32>,
+    /// 맞혔을 때 보여 줄 설명 (영어, 플레이어에게 그대로 보임)
+    pub explanation: String,
+}
+
+impl Puzzle {
+    /// 9칸 보드 (빈 칸은 "")
+    pub fn cells(&self) -> Vec<String> {
+        self.board
+            .chars()
+            .filter(|c| *c != '/')
+            .map(|c| if c == '.' { String::new() } else { c.to_string() })
+            .collect()
+    }
+
+    pub fn difficulty(&self) -> PuzzleDifficulty {
+        match self.difficulty.as_str() {
+            "easy" => PuzzleDifficulty::Easy,
+            "medium" => PuzzleDifficulty::Medium,
+            "hard" => PuzzleDifficulty::Hard,
+            _ => PuzzleDifficulty::Unspecified,
+        }
+    }
+
+    pub fn to_proto(&self) -> PuzzleProto {
+        PuzzleProto {
+            puzzle_id: self.id.clone(),
+            board: self.cells(),
+            player_symbol: self.winning_symbol.clone(),
+            hint_count: self.winning_positions.len() as u32,
+            difficulty: self.difficulty() as i32,
+        }
+    }
+
+    /// `position`이 정답인지 확인 (보드 밖이면 InvalidPosition)
+    pub fn check(&self, position: i32) -> Result<SolveResult, GameError> {
+        let cells = self.cells();
+        if position < 0 || position as usize >= cells.len() {
+            return Err(GameError::InvalidPosition);
+        }
+        let (correct, explanation) = if self.winning_positions.contains(&position) {
+            (true, self.explanation.clone())
+        } else if !cells[position as usize].is_empty() {
+            (false, "That square is already taken.".to_string())
+        } else {
+            let miss = match self.difficulty() {
+                PuzzleDifficulty::Medium => "That move leaves your opponent a winning square.",
+                PuzzleDifficulty::Hard => "That move makes at most one threat, so it can be blocked.",
+                _ => "That move does not win right away.",
+            };
+            (false, miss.to_string())
+        };
+        Ok(SolveResult { correct, explanation })
+    }
+
+    /// 보드 모양, 차례, 정답 칸 확인
+    fn validate(&self) -> Result<(), String> {
+        let cells = self.cells();
+        if cells.len() != SIZE * SIZE || cells.iter().any(|c| !matches!(c.as_str(), "" | "X" | "O")) {
+            return Err(format!("{}: board는 \"X\", \"O\", \".\" 9칸이어야 합니다.", self.id));
+        }
+        if self.difficulty() == PuzzleDifficulty::Unspecified {
+            return Err(format!("{}: difficulty는 easy, medium, hard 중 하나여야 합니다.", self.id));
+        }
+        let xs = cells.iter().filter(|c| *c == "X").count();
+        let os = cells.iter().filter(|c| *c == "O").count();
+        let to_move = match xs.checked_sub(os) {
+            Some(0) => "X",
+            Some(1) => "O",
+            _ => return Err(format!("{}: X와 O의 수가 맞지 않습니다.", self.id)),
+        };
+        if self.winning_symbol != to_move {
+            return Err(format!("{}: 이번 차례는 {}입니다 (winning_symbol {}).", self.id, to_move, self.winning_symbol));
+        }
+        if board::winner(&cells, SIZE, SIZE).is_some() {
+            return Err(format!("{}: 이미 승부가 난 보드입니다.", self.id));
+        }
+        if self.winning_positions.is_empty() {
+            return Err(format!("{}: winning_positions가 비어 있습니다.", self.id));
+        }
+        for &position in &self.winning_positions {
+            if !(0..cells.len() as i32).contains(&position) || !cells[position as usize].is_empty() {
+                return Err(format!("{}: 정답 칸 {}이 보드 밖이거나 이미 채워져 있습니다.", self.id, position));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 퍼즐 모음
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct PuzzleBook {
+    puzzles: Vec<Puzzle>,
+}
+
+impl PuzzleBook {
+    /// TOML(`[[puzzles]]` 목록)을 읽고 모든 퍼즐을 확인
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let book: PuzzleBook = toml::from_str(text).map_err(|e| format!("퍼즐 파일 형식 오류: {}", e))?;
+        let mut ids = HashSet::new();
+        for puzzle in &book.puzzles {
+            puzzle.validate()?;
+            if !ids.insert(puzzle.id.as_str()) {
+                return Err(format!("{}: 같은 id의 퍼즐이 여러 개입니다.", puzzle.id));
+            }
+        }
+        Ok(book)
+    }
+
+    pub fn puzzles(&self) -> &[Puzzle] {
+        &self.puzzles
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Puzzle> {
+        self.puzzles.iter().find(|puzzle| puzzle.id == id)
+    }
+
+    /// `difficulty`의 퍼즐 중 무작위 (UNSPECIFIED면 모든 퍼즐 중)
+    pub fn random(&self, difficulty: PuzzleDifficulty) -> Option<&Puzzle> {
+        let candidates: Vec<&Puzzle> = self
+            .puzzles
+            .iter()
+            .filter(|puzzle| difficulty == PuzzleDifficulty::Unspecified || puzzle.difficulty() == difficulty)
+            .collect();
+        candidates.choose(&mut rand::thread_rng()).copied()
+    }
+}
+
+/// 내장 퍼즐 (`puzzles.toml`, 처음 쓸 때 한 번 읽음)
+pub fn builtin() -> &'static PuzzleBook {
+    static BOOK: OnceLock<PuzzleBook> = OnceLock::new();
+    BOOK.get_or_init(|| PuzzleBook::parse(BUILTIN_PUZZLES).expect("내장 퍼즐 파일 오류"))
+}
@@ -0,0 +1,116 @@
+//! 연결마다 받는 메시지 빈도 제한
+//!
+//! Play 스트림으로 들어오는 수(와 기권, 무승부 같은 게임 요청)와 채팅은 각자의 토큰 버킷에서 토큰을 하나씩
+//! 씁니다. 토큰이 없으면 게임 액터에 보내기 전에 메시지를 버리고, 최근 1분 동안 버린 메시지가 정해진 수에
+//! 이르면 스트림을 끊습니다. 시각은 호출하는 쪽에서 받으므로 gRPC나 실제 시간 없이 검사할 수 있습니다.
+//!
+//! 연결과 따로 플레이어 자리마다 수를 세는 [`TokenBucket`]이 있어, 재접속하거나 SubmitMove로 두어도 수를 초당
+//! 정해진 만큼만 받습니다. 넘은 수는 두지 않고 그 플레이어에게만 "rate limit exceeded" 오류를 보냅니다.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+/// 버린 메시지를 세는 구간
+pub const ABUSE_WINDOW: Duration = Duration::from_secs(60);
+/// 메시지를 버린다는 경고를 다시 남기기까지의 간격
+pub const WARNING_INTERVAL: Duration = Duration::from_secs(10);
+
+/// 초당 `rate`개씩 채워지고 `burst`개까지 모이는 토큰 버킷
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    rate: f64,
+    burst: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    /// 가득 찬 버킷
+    pub fn new(rate: f64, burst: f64, now: Instant) -> Self {
+        TokenBucket { rate, burst, tokens: burst, refilled_at: now }
+    }
+
+    /// 지난 시간만큼 채운 뒤 토큰이 있으면 하나 씀
+    pub fn take(&mut self, now: Instant) -> bool {
+        self.take_n(1.0, now)
+    }
+
+    /// 지난 시간만큼 채운 뒤 `amount`개가 있으면 쓰고 true, 모자라면 그대로 두고 false
+    pub fn take_n(&mut self, amount: f64, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+        self.refilled_at = now;
+        if self.tokens < amount {
+            return false;
+        }
+        self.tokens -= amount;
+        true
+    }
+}
+
+/// 메시지 종류 (종류마다 버킷이 따로 있음)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKind {
+    /// 수, 기권, 무승부 제안과 응답, 한 판 더, 중복 Join
+    Action,
+    Chat,
+}
+
+/// 메시지 하나에 대한 판단
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// 처리
+    Allow,
+    /// 버림 (`warn`이면 경고를 남길 차례, `dropped`는 최근 1분 동안 버린 수)
+    Drop { warn: bool, dropped: usize },
+    /// 너무 많이 버려 스트림을 끊어야 함
+    Disconnect,
+}
+
+/// 한 연결의 빈도 제한
+#[derive(Debug, Clone)]
+pub struct ConnectionLimiter {
+    actions: TokenBucket,
+    chat: TokenBucket,
+    dropped: VecDeque<Instant>,
+    drop_limit: usize,
+    warned_at: Option<Instant>,
+}
+
+impl ConnectionLimiter {
+    /// 종류마다 초당 `rate`개, 최대 `burst`개까지 받고, 1분 동안 `drop_limit`개를 버리면 끊음
+    pub fn new(rate: u32, burst: u32, drop_limit: u32, now: Instant) -> Self {
+        ConnectionLimiter {
+            actions: TokenBucket::new(f64::from(rate), f64::from(burst), now),
+            chat: TokenBucket::new(f64::from(rate), f64::from(burst), now),
+            dropped: VecDeque::new(),
+            drop_limit: drop_limit as usize,
+            warned_at: None,
+        }
+    }
+
+    /// `now`에 받은 `kind` 메시지를 처리할지 판단
+    pub fn check(&mut self, kind: MessageKind, now: Instant) -> Verdict {
+        let bucket = match kind {
+            MessageKind::Action => &mut self.actions,
+            MessageKind::Chat => &mut self.chat,
+        };
+        if bucket.take(now) {
+            return Verdict::Allow;
+        }
+        while self.dropped.front().is_some_and(|at| now.saturating_duration_since(*at) >= ABUSE_WINDOW) {
+            self.dropped.pop_front();
+        }
+        self.dropped.push_back(now);
+        if self.dropped.len() >= self.drop_limit {
+            return Verdict::Disconnect;
+        }
+        let warn = self.warned_at.is_none_or(|at| now.saturating_duration_since(at) >= WARNING_INTERVAL);
+        if warn {
+            self.warned_at = Some(now);
+        }
+        Verdict::Drop { warn, dropped: self.dropped.len() }
+    }
+}
@@ -0,0 +1,147 @@
+//! 끝난 게임 기록 (`--record`)
+//!
+//! 게임이 끝날 때마다 게임 ID, 시작/종료 시각, 플레이어, 전체 수, 결과를 JSON 한 줄로 파일 끝에 덧붙입니다.
+//! 디스크가 느려도 게임 액터를 붙잡지 않도록 기록은 채널로 보내고 전용 스레드가 씁니다.
+
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{instrument, warn};
+
+use crate::game::SharedGame;
+
+/// 쓰기를 기다릴 수 있는 기록 수 (넘치면 버리고 경고)
+const RECORD_QUEUE: usize = 1024;
+
+/// 끝난 게임 한 판의 기록 (JSON 한 줄)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GameRecord {
+    pub game_id: String,
+    pub started_at_unix: i64, // 게임 생성 시각
+    pub ended_at_unix: i64,
+    pub board_size: usize,
+    pub win_length: usize,
+    pub players: Vec<RecordedPlayer>,
+    pub moves: Vec<RecordedMove>,
+    pub outcome: String, // 게임의 최종 상태 ("X_win", "draw_agreed", "admin_terminated" 등)
+}
+
+/// 기록에 남기는 플레이어 (player_id가 없으면 빈 문자열)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecordedPlayer {
+    pub symbol: String,
+    pub player_id: String,
+    pub bot: bool,
+}
+
+/// 기록에 남기는 수 (둔 순서대로)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecordedMove {
+    pub symbol: String,
+    pub position: u8,
+    pub played_at_unix: i64,
+    pub mark: String, // 보드에 놓은 심볼 (와일드가 아니면 symbol과 같음)
+}
+
+impl GameRecord {
+    /// 끝난 게임의 현재 상태로 기록을 만듦 (종료 시각은 지금)
+    pub fn of(game: &SharedGame) -> Self {
+        let players = [&game.player_x, &game.player_o]
+            .into_iter()
+            .flatten()
+            .map(|p| RecordedPlayer { symbol: p.symbol.clone(), player_id: p.player_id.clone(), bot: p.is_bot })
+            .collect();
+        let moves = game
+            .history
+            .iter()
+            .map(|m| RecordedMove {
+                symbol: m.symbol.clone(),
+                position: m.position,
+                played_at_unix: unix_secs(m.played_at),
+                mark: m.mark.clone(),
+            })
+            .collect();
+        GameRecord {
+            game_id: game.game_id.clone(),
+            started_at_unix: unix_secs(game.created_at),
+            ended_at_unix: unix_secs(SystemTime::now()),
+            board_size: game.board_size,
+            win_length: game.win_length,
+            players,
+            moves,
+            outcome: game.status.clone(),
+        }
+    }
+}
+
+pub(crate) fn unix_secs(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64)
+}
+
+/// 기록 스레드에 보내는 요청
+enum Command {
+    Write(Box<GameRecord>),
+    /// 앞서 보낸 기록을 모두 쓰고 디스크로 내보낸 뒤 응답
+    Flush(oneshot::Sender<()>),
+}
+
+/// 기록 파일 쓰기 스레드로 기록을 보내는 핸들 (복제해서 여러 곳에서 씀)
+#[derive(Clone)]
+pub struct GameRecorder {
+    tx: mpsc::Sender<Command>,
+}
+
+impl GameRecorder {
+    /// 기록 파일을 덧붙이기 모드로 열고 (없으면 만듦) 쓰기 스레드를 시작
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("게임 기록 파일을 열 수 없습니다 ({}): {}", path.display(), e)))?;
+        let (tx, rx) = mpsc::channel(RECORD_QUEUE);
+        let path = path.to_path_buf();
+        std::thread::Builder::new().name("game-recorder".into()).spawn(move || write_records(file, path, rx))?;
+        Ok(GameRecorder { tx })
+    }
+
+    /// 끝난 게임을 기록하도록 보냄 (기다리지 않음)
+    #[instrument(level = "debug", skip_all, fields(game_id = %game.game_id))]
+    pub fn record(&self, game: &SharedGame) {
+        if let Err(e) = self.tx.try_send(Command::Write(Box::new(GameRecord::of(game)))) {
+            warn!(game_id = %game.game_id, error = %e, "게임 기록 전송 실패, 기록을 버림");
+        }
+    }
+
+    /// 지금까지 보낸 기록이 모두 파일에 쓰일 때까지 대기
+    pub async fn flush(&self) {
+        let (ack, done) = oneshot::channel();
+        if self.tx.send(Command::Flush(ack)).await.is_ok() {
+            let _ = done.await;
+        }
+    }
+}
+
+/// 기록 스레드: 기록마다 한 줄씩 쓰고 바로 디스크로 내보냄
+fn write_records(file: File, path: PathBuf, mut rx: mpsc::Receiver<Command>) {
+    let mut out = BufWriter::new(file);
+    while let Some(command) = rx.blocking_recv() {
+        let result = match command {
+            Command::Write(record) => serde_json::to_writer(&mut out, &record)
+                .map_err(io::Error::from)
+                .and_then(|()| out.write_all(b"\n"))
+                .and_then(|()| out.flush()),
+            Command::Flush(ack) => {
+                let result = out.flush();
+                let _ = ack.send(());
+                result
+            }
+        };
+        if let Err(e) = result {
+            warn!(path = %path.display(), error = %e, "게임 기록 쓰기 실패");
+        }
+    }
+}
@@ -0,0 +1,25 @@
+//! gRPC 서버 리플렉션 (`grpc.reflection.v1`, `grpc.reflection.v1alpha`)
+//!
+//! grpcurl이나 Postman 같은 도구가 proto 파일 없이 서비스를 살펴볼 수 있도록, 빌드할 때 만든
+//! `tictactoe` 패키지의 파일 디스크립터 세트와 헬스 체크(`grpc.health.v1`)의 세트를 함께 알려 줍니다.
+//! 오래된 도구는 v1alpha만 알기 때문에 두 버전을 모두 등록합니다.
+
+use tonic_reflection::server::{v1, v1alpha, Builder, Error};
+
+use crate::tictactoe::FILE_DESCRIPTOR_SET;
+
+fn builder() -> Builder<'static> {
+    Builder::configure()
+        .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+        .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+}
+
+/// 표준(v1) 리플렉션 서비스
+pub fn reflection_service() -> Result<v1::ServerReflectionServer<impl v1::ServerReflection>, Error> {
+    builder().build_v1()
+}
+
+/// 이전 버전(v1alpha) 리플렉션 서비스
+pub fn reflection_service_v1alpha() -> Result<v1alpha::ServerReflectionServer<impl v1alpha::ServerReflection>, Error> {
+    builder().build_v1alpha()
+}
@@ -0,0 +1,244 @@
+//! 실행 중 설정 다시 읽기 (SIGHUP)
+//!
+//! 새 설정 전체를 먼저 읽고 검사한 뒤, 실행 중인 설정과 항목별로 비교해 각 변경을 바로 적용,
+//! 재시작 필요(거부), 잘못된 값(거부)으로 분류합니다. 적용할 수 있는 변경만 한 번에 반영하고,
+//! 결과 보고서는 로그와 감사 로그(`audit` 대상)에 남기며 관리자 RPC `GetLastReloadReport`로 조회할 수 있습니다.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+use toml::{Table, Value};
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::tictactoe;
+
+/// 설정 항목을 실행 중에 바꿀 수 있는지 여부
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reloadability {
+    /// 다시 읽으면 바로 적용 (이후의 연결/게임부터 새 값 사용)
+    Live,
+    /// 서버를 다시 시작해야 적용
+    Restart,
+}
+
+/// 설정 항목 하나의 다시 읽기 규칙
+pub struct FieldRule {
+    pub name: &'static str,
+    pub reload: Reloadability,
+    pub secret: bool, // 보고서와 로그에 값을 남기지 않음
+}
+
+const fn rule(name: &'static str, reload: Reloadability) -> FieldRule {
+    FieldRule { name, reload, secret: false }
+}
+
+/// 설정 항목별 분류표. `Config`에 항목을 추가하면 여기에도 추가해야 합니다 (테스트로 검사).
+pub const FIELD_RULES: &[FieldRule] = &[
+    rule("listen_addr", Reloadability::Restart),
+    rule("channel_buffer", Reloadability::Live),
+    rule("reconnect_grace_secs", Reloadability::Live),
+    rule("quick_play_timeout_secs", Reloadability::Live),
+    rule("waiting_timeout_ms", Reloadability::Live),
+    rule("max_games", Reloadability::Live),
+    rule("latency_budget_ms", Reloadability::Live),
+    rule("load_check_interval_ms", Reloadability::Restart),
+    rule("presets_file", Reloadability::Restart),
+    FieldRule { name: "admin_token", reload: Reloadability::Live, secret: true },
+    rule("invite_ttl_secs", Reloadability::Live),
+    rule("elo_k_factor", Reloadability::Live),
+    rule("ratings_file", Reloadability::Restart),
+    rule("stats_file", Reloadability::Restart),
+    FieldRule { name: "auth_secret", reload: Reloadability::Restart, secret: true },
+    rule("auth_listen_addr", Reloadability::Restart),
+    rule("auth_token_ttl_secs", Reloadability::Restart),
+    rule("users_file", Reloadability::Restart),
+    rule("metrics_listen_addr", Reloadability::Restart),
+    rule("http_listen_addr", Reloadability::Restart),
+    rule("first_player", Reloadability::Live),
+    rule("http2_keepalive_interval_secs", Reloadability::Restart),
+    rule("http2_keepalive_timeout_secs", Reloadability::Restart),
+    rule("heartbeat_interval_secs", Reloadability::Live),
+    rule("heartbeat_timeout_secs", Reloadability::Live),
+    rule("idle_timeout_secs", Reloadability::Live),
+    rule("shutdown_drain_timeout_secs", Reloadability::Live),
+    rule("replay_buffer_games", Reloadability::Live),
+    rule("replay_delay_ms", Reloadability::Live),
+    rule("chat_max_chars", Reloadability::Live),
+    rule("chat_rate_limit", Reloadability::Live),
+    rule("chat_rate_window_secs", Reloadability::Live),
+    rule("message_rate_per_sec", Reloadability::Live),
+    rule("message_burst", Reloadability::Live),
+    rule("dropped_message_limit", Reloadability::Live),
+    rule("rate_limit_capacity", Reloadability::Live),
+    rule("rate_limit_refill_per_sec", Reloadability::Live),
+    rule("turn_timeout_secs", Reloadability::Live),
+    rule("spectator_drop_limit", Reloadability::Live),
+    rule("join_timeout_secs", Reloadability::Live),
+    rule("web_allowed_origins", Reloadability::Restart),
+    rule("snapshot_format", Reloadability::Restart),
+    rule("leaderboard_cache_secs", Reloadability::Live),
+    rule("abandon_timeout_ms", Reloadability::Live),
+];
+
+/// 변경 하나의 처리 결과
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeOutcome {
+    Applied,
+    /// 재시작해야 적용되는 항목이라 거부
+    RequiresRestart(String),
+    /// 값이 잘못되어 거부
+    Invalid(String),
+}
+
+/// 설정 항목 하나의 변경
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigChange {
+    pub field: String,
+    pub old_value: String,
+    pub new_value: String,
+    pub outcome: ChangeOutcome,
+}
+
+/// 설정 다시 읽기 결과 보고서
+#[derive(Debug, Clone)]
+pub struct ReloadReport {
+    pub reloaded_at: SystemTime,
+    pub source: String,        // 읽은 설정 파일 (없으면 기본값)
+    pub error: Option<String>, // 파일을 읽거나 해석하지 못했으면 그 이유 (이때는 아무것도 바뀌지 않음)
+    pub changes: Vec<ConfigChange>,
+}
+
+/// 실행 중 설정과 새 설정을 비교해, 적용 가능한 변경만 반영한 설정과 항목별 변경 목록을 만듭니다.
+pub fn plan_reload(running: &Config, new: &Config) -> (Config, Vec<ConfigChange>) {
+    let old_table = to_table(running);
+    let new_table = to_table(new);
+    let invalid = new.validate();
+
+    let mut merged = old_table.clone();
+    let mut changes = Vec::new();
+    let mut fields: Vec<&String> = old_table.keys().chain(new_table.keys()).collect();
+    fields.sort();
+    fields.dedup();
+    for field in fields {
+        let (old, new_value) = (old_table.get(field), new_table.get(field));
+        if old == new_value {
+            continue;
+        }
+        let rule = FIELD_RULES.iter().find(|r| r.name == field);
+        let outcome = if let Some((_, reason)) = invalid.iter().find(|(name, _)| name == field) {
+            ChangeOutcome::Invalid(reason.clone())
+        } else {
+            match rule.map(|r| r.reload) {
+                Some(Reloadability::Live) => {
+                    match new_value {
+                        Some(value) => merged.insert(field.clone(), value.clone()),
+                        None => merged.remove(field),
+                    };
+                    ChangeOutcome::Applied
+                }
+                Some(Reloadability::Restart) => ChangeOutcome::RequiresRestart("서버를 다시 시작해야 적용되는 항목입니다.".into()),
+                None => ChangeOutcome::RequiresRestart("다시 읽기 분류표에 없는 항목입니다.".into()),
+            }
+        };
+        let secret = rule.is_some_and(|r| r.secret);
+        changes.push(ConfigChange {
+            field: field.clone(),
+            old_value: display_value(old, secret),
+            new_value: display_value(new_value, secret),
+            outcome,
+        });
+    }
+
+    let merged = Value::Table(merged).try_into().unwrap_or_else(|_| running.clone());
+    (merged, changes)
+}
+
+fn to_table(config: &Config) -> Table {
+    match Value::try_from(config) {
+        Ok(Value::Table(table)) => table,
+        _ => Table::new(),
+    }
+}
+
+/// 보고서에 남길 값 표시 (비밀 값은 가림)
+fn display_value(value: Option<&Value>, secret: bool) -> String {
+    match value {
+        None => "(없음)".into(),
+        Some(_) if secret => "(비공개)".into(),
+        Some(Value::String(s)) => s.clone(),
+        Some(value) => value.to_string(),
+    }
+}
+
+impl ReloadReport {
+    pub fn new(source: &str, changes: Vec<ConfigChange>) -> Self {
+        ReloadReport { reloaded_at: SystemTime::now(), source: source.to_string(), error: None, changes }
+    }
+
+    /// 설정 파일을 읽지 못해 아무것도 바꾸지 않은 보고서
+    pub fn failed(source: &str, error: String) -> Self {
+        ReloadReport { error: Some(error), ..ReloadReport::new(source, Vec::new()) }
+    }
+
+    pub fn applied(&self) -> impl Iterator<Item = &ConfigChange> {
+        self.changes.iter().filter(|c| c.outcome == ChangeOutcome::Applied)
+    }
+
+    pub fn rejected(&self) -> impl Iterator<Item = &ConfigChange> {
+        self.changes.iter().filter(|c| c.outcome != ChangeOutcome::Applied)
+    }
+
+    /// 변경마다 로그를 남기고 요약을 감사 로그에 기록
+    pub fn log(&self) {
+        if let Some(error) = &self.error {
+            warn!(source = %self.source, error = %error, "설정 다시 읽기 실패, 변경 없음");
+        }
+        for change in &self.changes {
+            match &change.outcome {
+                ChangeOutcome::Applied => {
+                    info!(field = %change.field, old = %change.old_value, new = %change.new_value, "설정 변경 적용")
+                }
+                ChangeOutcome::RequiresRestart(reason) | ChangeOutcome::Invalid(reason) => {
+                    warn!(field = %change.field, old = %change.old_value, new = %change.new_value, reason = %reason, "설정 변경 거부")
+                }
+            }
+        }
+        let applied: Vec<&str> = self.applied().map(|c| c.field.as_str()).collect();
+        let rejected: Vec<&str> = self.rejected().map(|c| c.field.as_str()).collect();
+        info!(
+            target: "audit",
+            event = "config_reload",
+            source = %self.source,
+            ok = self.error.is_none(),
+            applied = ?applied,
+            rejected = ?rejected,
+            "설정 다시 읽기"
+        );
+    }
+
+    pub fn to_proto(&self) -> tictactoe::ReloadReport {
+        tictactoe::ReloadReport {
+            reloaded: true,
+            reloaded_at_unix: self.reloaded_at.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64),
+            source: self.source.clone(),
+            error: self.error.clone().unwrap_or_default(),
+            changes: self
+                .changes
+                .iter()
+                .map(|change| {
+                    let (outcome, reason) = match &change.outcome {
+                        ChangeOutcome::Applied => (tictactoe::ReloadOutcome::Applied, String::new()),
+                        ChangeOutcome::RequiresRestart(reason) => (tictactoe::ReloadOutcome::RequiresRestart, reason.clone()),
+                        ChangeOutcome::Invalid(reason) => (tictactoe::ReloadOutcome::Invalid, reason.clone()),
+                    };
+                    tictactoe::ConfigChange {
+                        field: change.field.clone(),
+                        old_value: change.old_value.clone(),
+                        new_value: change.new_value.clone(),
+                        outcome: outcome.into(),
+                        reason,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! 최근 끝난 게임 다시 보기 버퍼 (`ReplayGame` RPC)
+//!
+//! 게임이 끝날 때마다 그 판의 이벤트(`events::GameEvent`)와 보드 설정을 메모리에 남기고, 설정한 수
+//! (`replay_buffer_games`)를 넘으면 가장 오래된 게임부터 버립니다. 다시 볼 때는 이벤트를 `GameState::apply`로
+//! 하나씩 접어 상태를 만들므로, 실제 게임이 보낸 상태와 같은 규칙으로 보드가 만들어집니다.
+
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+use crate::events::GameEvent;
+use crate::game::SharedGame;
+use crate::tictactoe::{GameMode, GameState};
+use crate::ultimate;
+
+/// 다시 볼 수 있게 남긴 끝난 게임 한 판
+#[derive(Debug, Clone, PartialEq)]
+pub struct FinishedGame {
+    pub game_id: String,
+    pub win_length: usize,
+    pub mode: GameMode,
+    /// 게임을 만든(초기화한) 뒤의 이벤트 (GameCreated로 시작해 GameEnded로 끝남)
+    pub events: Vec<GameEvent>,
+    pub finished_at: SystemTime,
+}
+
+impl FinishedGame {
+    /// 끝난 게임의 이벤트를 복사 (끝나지 않은 게임은 None)
+    pub fn of(game: &SharedGame) -> Option<Self> {
+        if !game.is_finished() {
+            return None;
+        }
+        Some(FinishedGame {
+            game_id: game.game_id.clone(),
+            win_length: game.win_length,
+            mode: game.mode,
+            events: game.events().to_vec(),
+            finished_at: SystemTime::now(),
+        })
+    }
+
+    /// 이벤트마다 그 시점의 게임 상태 (첫 상태는 빈 보드)
+    pub fn states(&self) -> Vec<GameState> {
+        let mut state = GameState::default();
+        let mut states = Vec::with_capacity(self.events.len());
+        for (number, event) in self.events.iter().enumerate() {
+            state = state.apply(event);
+            state.game_id = self.game_id.clone();
+            state.win_length = self.win_length as i32;
+            state.mode = self.mode as i32;
+            state.move_number = number as u32;
+            if self.mode == GameMode::Ultimate {
+                state.macro_board = ultimate::macro_board(&state.board);
+                let last_move = state.history.last().map(|m| m.position as usize);
+                state.forced_board = if state.status == "ongoing" {
+                    ultimate::forced_board(&state.board, last_move).map(|index| index as i32)
+                } else {
+                    None
+                };
+            }
+            states.push(state.clone());
+        }
+        states
+    }
+}
+
+/// 최근 끝난 게임 (오래된 것부터)
+#[derive(Debug, Default)]
+pub struct ReplayBuffer {
+    games: VecDeque<FinishedGame>,
+}
+
+impl ReplayBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 끝난 게임을 남기고 `capacity`개를 넘는 오래된 게임을 버림 (같은 ID의 이전 판은 새 판으로 바뀜)
+    pub fn push(&mut self, game: FinishedGame, capacity: usize) {
+        self.games.retain(|kept| kept.game_id != game.game_id);
+        self.games.push_back(game);
+        while self.games.len() > capacity {
+            self.games.pop_front();
+        }
+    }
+
+    pub fn get(&self, game_id: &str) -> Option<&FinishedGame> {
+        self.games.iter().find(|game| game.game_id == game_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.games.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.games.is_empty()
+    }
+}
@@ -0,0 +1,96 @@
+//! 끝난 게임 스냅샷 직렬화 (`snapshot_format`)
+//!
+//! 스냅샷을 바이트로 바꾸는 방식을 [`Serializer`] 하나로 묶습니다. [`JsonSerializer`]는 지금까지처럼
+//! 사람이 읽을 수 있는 JSON을, [`BincodeSerializer`]는 매직 바이트([`BINCODE_MAGIC`])로 시작하는 bincode를 씁니다.
+//! JSON 스냅샷은 항상 `{`로 시작하므로 파일 머리만 보고 형식을 알아낼 수 있고([`detect`]), 그래서 설정을 바꾼 뒤에도
+//! 예전 형식으로 쓴 스냅샷을 그대로 읽습니다. 다른 형식의 바이트를 넘기면 패닉 없이 `InvalidData` 오류를 돌려줍니다.
+
+use bincode::Options;
+use std::io;
+
+use crate::config::SnapshotFormat;
+use crate::snapshot::GameSnapshot;
+
+/// bincode 스냅샷 파일 맨 앞의 매직 바이트 (마지막 바이트는 형식 버전)
+pub const BINCODE_MAGIC: &[u8; 4] = b"TTB\x01";
+
+/// bincode 스냅샷 하나의 최대 크기 (깨진 길이 값으로 큰 메모리를 잡지 않도록)
+const MAX_BINCODE_BYTES: u64 = 16 * 1024 * 1024;
+
+/// 스냅샷 직렬화 방식
+pub trait Serializer: Send + Sync {
+    /// 이 형식으로 쓴 스냅샷 파일의 확장자
+    fn extension(&self) -> &'static str;
+    /// 스냅샷을 파일에 쓸 바이트로 바꿈
+    fn serialize(&self, game: &GameSnapshot) -> Vec<u8>;
+    /// 파일에서 읽은 바이트를 스냅샷으로 되돌림 (다른 형식이거나 깨졌으면 `InvalidData`)
+    fn deserialize(&self, bytes: &[u8]) -> io::Result<GameSnapshot>;
+}
+
+/// 들여쓴 JSON (지금까지의 스냅샷 형식)
+pub struct JsonSerializer;
+
+/// 매직 바이트 뒤에 bincode로 인코딩한 스냅샷
+pub struct BincodeSerializer;
+
+/// 설정한 형식의 직렬화 방식
+pub fn serializer(format: SnapshotFormat) -> &'static dyn Serializer {
+    match format {
+        SnapshotFormat::Json => &JsonSerializer,
+        SnapshotFormat::Bincode => &BincodeSerializer,
+    }
+}
+
+/// 파일 머리로 스냅샷 형식을 알아냄 (매직 바이트가 없으면 JSON)
+pub fn detect(bytes: &[u8]) -> SnapshotFormat {
+    if bytes.starts_with(BINCODE_MAGIC) {
+        SnapshotFormat::Bincode
+    } else {
+        SnapshotFormat::Json
+    }
+}
+
+fn invalid(message: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// bincode 인코딩 옵션 (가변 길이 정수, 남는 바이트는 오류)
+fn bincode_options() -> impl Options {
+    bincode::DefaultOptions::new().with_limit(MAX_BINCODE_BYTES)
+}
+
+impl Serializer for JsonSerializer {
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn serialize(&self, game: &GameSnapshot) -> Vec<u8> {
+        // 스냅샷에는 문자열이 아닌 키의 맵이 없어 JSON 직렬화가 실패하지 않음
+        serde_json::to_vec_pretty(game).expect("스냅샷은 항상 JSON으로 직렬화됨")
+    }
+
+    fn deserialize(&self, bytes: &[u8]) -> io::Result<GameSnapshot> {
+        if detect(bytes) != SnapshotFormat::Json {
+            return Err(invalid("JSON 스냅샷이 아닙니다 (bincode 매직 바이트로 시작함)"));
+        }
+        serde_json::from_slice(bytes).map_err(invalid)
+    }
+}
+
+impl Serializer for BincodeSerializer {
+    fn extension(&self) -> &'static str {
+        "bin"
+    }
+
+    fn serialize(&self, game: &GameSnapshot) -> Vec<u8> {
+        let mut bytes = BINCODE_MAGIC.to_vec();
+        // 스냅샷 크기는 보드와 수 수로 정해져 한도를 넘지 않음
+        bincode_options().serialize_into(&mut bytes, game).expect("스냅샷은 항상 bincode로 직렬화됨");
+        bytes
+    }
+
+    fn deserialize(&self, bytes: &[u8]) -> io::Result<GameSnapshot> {
+        let body = bytes.strip_prefix(BINCODE_MAGIC).ok_or_else(|| invalid("bincode 스냅샷이 아닙니다 (파일 머리에 매직 바이트가 없음)"))?;
+        bincode_options().deserialize(body).map_err(invalid)
+    }
+}
@@ -0,0 +1,1380 @@
+use tonic::codec::CompressionEncoding;
+use tonic::codegen::InterceptedService;
+use tonic_health::server::HealthReporter;
+use tonic_health::ServingStatus;
+use tonic::{Request, Response, Status, Streaming};
+use tokio::sync::{Mutex, mpsc, oneshot};
+use tokio::time::MissedTickBehavior;
+use futures::Stream;
+use std::{ops::ControlFlow, pin::Pin, sync::{atomic::{AtomicBool, Ordering}, Arc, RwLock}, time::{Duration, Instant}};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{debug, info, instrument, warn, Instrument};
+
+use crate::actor::{self, GameHandle, GameHooks};
+use crate::admin::{self, TicTacToeAdmin};
+use crate::auth::{AuthInterceptor, AuthenticatedPlayer};
+use crate::board::{self, DEFAULT_BOARD_SIZE, DEFAULT_WIN_LENGTH};
+use crate::compression;
+use crate::config::Config;
+use crate::egress;
+use crate::error::{GameError, MoveError};
+use crate::event_log::GameEventLogger;
+use crate::elo::{EloRating, RatingBook, MAX_PLAYER_ID_LEN};
+use crate::game::{self, MoveOutcome, SharedGame, UpdateSender};
+use crate::health;
+use crate::load_shed::{LoadShedder, OptionalWork};
+use crate::manager::{GameManager, MatchedGame, Seat};
+use crate::metrics::Metrics;
+use crate::notation;
+use crate::presets::{Preset, PresetStore};
+use crate::puzzles;
+use crate::rate_limit::{ConnectionLimiter, MessageKind, Verdict};
+use crate::record::GameRecorder;
+use crate::reload::{plan_reload, ReloadReport};
+use crate::replays::{FinishedGame, ReplayBuffer};
+use crate::snapshot::SnapshotWriter;
+use crate::stats::{self, CompletedGame, LeaderboardCache, StatsBook, DEFAULT_LEADERBOARD_LIMIT, MAX_LEADERBOARD_LIMIT};
+use crate::tournament::{TicTacToeTournaments, TournamentManager};
+use crate::ultimate;
+use crate::tictactoe::tic_tac_toe_server::{TicTacToe, TicTacToeServer};
+use crate::tictactoe::play_request::Action;
+use crate::tictactoe::{
+    CreateGameRequest, CreateGameResponse, DefinePresetRequest, ExportGameRequest, GameHistoryRequest, GameHistoryResponse, GameMode, GameNotation, GameState,
+    GameStateRequest, LeaveRequest,
+    LeaveResponse, ListGamesRequest, ListGamesResponse, ListPresetsRequest, ListPresetsResponse, MatchmakingRequest,
+    EndReason, JoinRequest, LeaderboardRequest, LeaderboardResponse, MatchmakingUpdate, PlayRequest, PlayerRating,
+    PlayerRatingRequest, PlayerStats, PlayerStatsRequest, Puzzle, PuzzleDifficulty, PuzzleRequest, PuzzleSolution, ChatMessage, Empty,
+    ReloadReportRequest, ReplayRequest, SolveResult, SortField, SpectateRequest, SubmitMoveRequest,
+};
+
+/// 서버에서 클라이언트로 전송할 스트림 타입
+type ResponseStream = Pin<Box<dyn Stream<Item = Result<GameState, Status>> + Send>>;
+/// 매치메이킹 대기 순번과 매칭 결과 스트림 타입
+type MatchmakingStream = Pin<Box<dyn Stream<Item = Result<MatchmakingUpdate, Status>> + Send>>;
+
+////////////////////////////
+// gRPC 서비스 구현        //
+////////////////////////////
+
+/// gRPC 서비스 구조체 (모든 게임을 관리하는 매니저 공유)
+#[derive(Clone)]
+pub struct TicTacToeService {
+    manager: Arc<Mutex<GameManager>>,
+    config: Arc<RwLock<Arc<Config>>>, // 설정 다시 읽기로 통째로 교체 (사용하는 쪽은 스냅샷을 받아 씀)
+    load: Arc<LoadShedder>, // 수 처리 지연에 따른 부가 작업 제어
+    presets: Arc<Mutex<PresetStore>>,
+    ratings: Arc<Mutex<RatingBook>>,
+    stats: Arc<Mutex<StatsBook>>,
+    leaderboard_cache: Arc<Mutex<LeaderboardCache>>, // 최근 GetLeaderboard 응답 (leaderboard_cache_secs 동안 다시 씀)
+    health: HealthReporter, // 헬스 체크 상태 (종료를 시작하면 NOT_SERVING)
+    metrics: Arc<Metrics>,
+    last_reload: Arc<RwLock<Option<ReloadReport>>>,
+    draining: Arc<AtomicBool>, // 종료를 시작해 새 게임 참가를 받지 않는 중
+    recorder: Option<GameRecorder>, // 끝난 게임을 기록할 파일 (--record가 없으면 None)
+    event_log: Option<Arc<GameEventLogger>>, // 게임 이벤트 로그 (--event-log가 없으면 None)
+    snapshots: Option<SnapshotWriter>, // 끝난 게임 스냅샷을 쓸 디렉터리 (--snapshot-dir가 없으면 None)
+    replays: Arc<std::sync::Mutex<ReplayBuffer>>, // ReplayGame으로 다시 볼 최근 끝난 게임
+    tournaments: Arc<Mutex<TournamentManager>>, // 진행 중이거나 끝난 토너먼트 (경기 게임이 끝나면 승자를 올림)
+}
+
+/// 종료 대기 중 진행 중인 게임이 모두 끝났는지 확인하는 간격
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 응답 없는 플레이어를 찾는 간격
+const IDLE_SWEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+#[tonic::async_trait]
+impl TicTacToe for TicTacToeService {
+    type PlayStream = ResponseStream;
+    type SpectateStream = ResponseStream;
+    type ReplayGameStream = ResponseStream;
+    type JoinMatchmakingStream = MatchmakingStream;
+
+    #[instrument(skip_all, fields(remote_addr = ?request.remote_addr(), game_id, player_symbol))]
+    async fn play(
+        &self,
+        request: Request<Streaming<PlayRequest>>,
+    ) -> Result<Response<Self::PlayStream>, Status> {
+        info!("새 클라이언트 접속");
+        let (tx, rx) = mpsc::channel(self.config().channel_buffer);
+        let (rx, stalled) = egress::forward(rx, self.config().heartbeat_timeout());
+        let authenticated = request.extensions().get::<AuthenticatedPlayer>().cloned();
+        let mut inbound = request.into_inner();
+        let service = self.clone();
+
+        tokio::spawn(async move {
+            // 첫 메시지는 Join이어야 함 (세션 토큰이 있으면 재접속)
+            let join = match inbound.message().await {
+                Ok(Some(PlayRequest { action: Some(Action::Join(join)) })) => join,
+                Ok(_) => {
+                    let status = GameError::InvalidArgument("첫 메시지는 Join이어야 합니다.".into()).into();
+                    if tx.send(Err(status)).await.is_err() {
+                        warn!("거부 응답 전송 실패: 클라이언트가 이미 연결을 끊음");
+                    }
+                    return;
+                }
+                Err(e) => {
+                    warn!(error = %e, "메시지 수신 에러");
+                    return;
+                }
+            };
+
+            let mut join = join;
+            // 인증된 요청이면 토큰의 플레이어 ID를 쓰고, Join에 담아 보낸 ID는 무시
+            join.player_id = match authenticated {
+                Some(player) => player.player_id,
+                None => join.player_id.trim().to_string(),
+            };
+            if join.player_id.chars().count() > MAX_PLAYER_ID_LEN {
+                let status = GameError::InvalidArgument(format!("player_id는 {}자 이하여야 합니다.", MAX_PLAYER_ID_LEN)).into();
+                service.metrics.join_rejected();
+                if tx.send(Err(status)).await.is_err() {
+                    warn!("거부 응답 전송 실패: 클라이언트가 이미 연결을 끊음");
+                }
+                return;
+            }
+
+            // 종료 중에는 재접속과 관전만 받음
+            if service.is_draining() && join.session_token.is_empty() && !join.spectate {
+                service.metrics.join_rejected();
+                if tx.send(Err(GameError::ShuttingDown.into())).await.is_err() {
+                    warn!("거부 응답 전송 실패: 클라이언트가 이미 연결을 끊음");
+                }
+                return;
+            }
+
+            // 비공개 방 만들기: 초대 코드로만 참가할 수 있는 게임을 만들고 그 게임에 X로 참가
+            if join.create_room {
+                let request = CreateGameRequest {
+                    best_of: join.best_of,
+                    mode: join.mode,
+                    auto_rematch: join.auto_rematch,
+                    swap_rule: join.swap_rule,
+                    ..CreateGameRequest::default()
+                };
+                match service.create_configured_game(request, true).await {
+                    Ok(created) => join.game_id = created.game_id,
+                    Err(status) => {
+                        warn!(code = ?status.code(), message = status.message(), "비공개 방 생성 거부");
+                        service.metrics.join_rejected();
+                        if tx.send(Err(status)).await.is_err() {
+                            warn!("거부 응답 전송 실패: 클라이언트가 이미 연결을 끊음");
+                        }
+                        return;
+                    }
+                }
+            }
+
+            // 게임 배정(또는 재접속/관전) 및 초기 상태 전송
+            let rating = service.ratings.lock().await.current_rating(&join.player_id);
+            let joined = service.manager.lock().await.join(&join, tx.clone(), rating).await;
+            if let Ok((game, seat)) = &joined {
+                let span = tracing::Span::current();
+                span.record("game_id", game.game_id());
+                if let Seat::Player { symbol, .. } = seat {
+                    span.record("player_symbol", tracing::field::display(symbol));
+                }
+            }
+            match joined {
+                Ok((game, Seat::Player { symbol, connection_id })) => {
+                    let _connected = service.metrics.player_connected();
+                    if join.quick_play {
+                        service.start_quick_play_timer(game.clone(), symbol.clone(), connection_id).await;
+                    }
+                    service.start_waiting_timer(game.clone()).await;
+                    let (timers, handle) = (service.clone(), game.clone());
+                    game.with(move |game| timers.start_game_timers(&handle, game)).await;
+                    service.forfeit_when_stalled(stalled, game.clone(), symbol.clone(), connection_id);
+                    service.handle_player(inbound, tx, game, symbol, connection_id).await;
+                }
+                Ok((game, Seat::Spectator)) => handle_spectator(inbound, game, tx).await,
+                Err(error) => {
+                    let status = Status::from(error);
+                    warn!(code = ?status.code(), message = status.message(), "참가 거부");
+                    service.metrics.join_rejected();
+                    if tx.send(Err(status)).await.is_err() {
+                        warn!("거부 응답 전송 실패: 클라이언트가 이미 연결을 끊음");
+                    }
+                }
+            }
+        }.in_current_span());
+
+        let output_stream = Box::pin(egress::response_stream(rx));
+        Ok(Response::new(output_stream))
+    }
+
+    async fn join_matchmaking(
+        &self,
+        request: Request<MatchmakingRequest>,
+    ) -> Result<Response<Self::JoinMatchmakingStream>, Status> {
+        let name = request.into_inner().player_name.trim().to_string();
+        if name.is_empty() {
+            return Err(GameError::InvalidArgument("player_name이 필요합니다.".into()).into());
+        }
+        if self.is_draining() {
+            return Err(GameError::ShuttingDown.into());
+        }
+        let (tx, rx) = mpsc::channel(self.config().channel_buffer);
+        let (matched, dequeued) = self.manager.lock().await.join_matchmaking(&name, tx.clone()).await;
+        for MatchedGame { game, seats } in matched {
+            self.expire_unclaimed_seats(game, seats);
+        }
+
+        // 매칭 전에 클라이언트가 스트림을 닫으면 대기열에서 정리 (대기열에서 빠지면 감시 종료)
+        let manager = self.manager.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = tx.closed() => manager.lock().await.prune_matchmaking().await,
+                _ = dequeued => {}
+            }
+        });
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+
+    async fn leave_matchmaking(
+        &self,
+        request: Request<LeaveRequest>,
+    ) -> Result<Response<LeaveResponse>, Status> {
+        let removed = self.manager.lock().await.leave_matchmaking(&request.into_inner().ticket).await;
+        Ok(Response::new(LeaveResponse { removed }))
+    }
+
+    async fn list_games(
+        &self,
+        request: Request<ListGamesRequest>,
+    ) -> Result<Response<ListGamesResponse>, Status> {
+        self.check_load(OptionalWork::PollingReads)?;
+        let games = self.manager.lock().await.list_games(request.get_ref()).await?;
+        Ok(Response::new(games))
+    }
+
+    #[instrument(skip_all, fields(remote_addr = ?request.remote_addr(), game_id))]
+    async fn spectate(
+        &self,
+        request: Request<SpectateRequest>,
+    ) -> Result<Response<Self::SpectateStream>, Status> {
+        self.check_load(OptionalWork::NewSpectators)?;
+        let game_id = request.into_inner().game_id;
+        let (tx, rx) = mpsc::channel(self.config().channel_buffer);
+        let game = self.manager.lock().await.spectate(game_id.trim(), tx.clone()).await?;
+        tracing::Span::current().record("game_id", game.game_id());
+        info!("관전 스트림 시작");
+        // 관전자가 스트림을 닫으면 게임의 관전자 목록에서 정리
+        tokio::spawn(async move {
+            tx.closed().await;
+            game.with(|game| game.remove_closed_spectators()).await;
+        }.in_current_span());
+        Ok(Response::new(Box::pin(egress::response_stream(rx))))
+    }
+
+    #[instrument(skip_all, fields(game_id))]
+    async fn replay_game(
+        &self,
+        request: Request<ReplayRequest>,
+    ) -> Result<Response<Self::ReplayGameStream>, Status> {
+        self.check_load(OptionalWork::Replays)?;
+        let request = request.into_inner();
+        let game_id = request.game_id.trim().to_string();
+        tracing::Span::current().record("game_id", game_id.as_str());
+        let buffered = self.replays.lock().unwrap().get(&game_id).map(FinishedGame::states);
+        let snapshots = match (buffered, &self.event_log) {
+            (Some(states), _) => states,
+            (None, Some(log)) => {
+                let snapshots = log.replay(&game_id).await.map_err(|e| GameError::Internal(e.to_string()))?;
+                snapshots.ok_or(GameError::GameNotFound)?
+            }
+            (None, None) => return Err(GameError::GameNotFound.into()),
+        };
+        let paced = request.paced.unwrap_or(true);
+        info!(%game_id, events = snapshots.len(), paced, "게임 다시 보기");
+        if !paced {
+            return Ok(Response::new(Box::pin(tokio_stream::iter(snapshots.into_iter().map(Ok)))));
+        }
+        // 상태 사이에 replay_delay만큼 쉬며 보냄 (클라이언트가 스트림을 닫으면 멈춤)
+        let delay = self.config().replay_delay();
+        let (tx, rx) = mpsc::channel(1);
+        tokio::spawn(async move {
+            for (index, state) in snapshots.into_iter().enumerate() {
+                if index > 0 {
+                    tokio::time::sleep(delay).await;
+                }
+                if tx.send(Ok(state)).await.is_err() {
+                    break;
+                }
+            }
+        }.in_current_span());
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+
+    async fn get_history(
+        &self,
+        request: Request<GameHistoryRequest>,
+    ) -> Result<Response<GameHistoryResponse>, Status> {
+        self.check_load(OptionalWork::PollingReads)?;
+        // 복사해 붙여 넣은 ID의 앞뒤 공백은 무시 (Spectate, ReplayGame과 같음)
+        let request = request.into_inner();
+        let game_id = request.game_id.trim().to_string();
+        let game = self.manager.lock().await.readable(&game_id, &request.session_token).await?;
+        let events = game.with(|game| game.events().iter().map(|event| event.to_proto()).collect()).await;
+        Ok(Response::new(GameHistoryResponse { game_id, events }))
+    }
+
+    async fn get_game_state(
+        &self,
+        request: Request<GameStateRequest>,
+    ) -> Result<Response<GameState>, Status> {
+        self.check_load(OptionalWork::PollingReads)?;
+        let request = request.into_inner();
+        let game = self.manager.lock().await.readable(request.game_id.trim(), &request.session_token).await?;
+        let state = game.snapshot().await;
+        Ok(Response::new(state))
+    }
+
+    async fn list_presets(
+        &self,
+        _request: Request<ListPresetsRequest>,
+    ) -> Result<Response<ListPresetsResponse>, Status> {
+        let presets = self.presets.lock().await.list();
+        let presets = presets.iter().map(|(preset, builtin)| preset.to_proto(*builtin)).collect();
+        Ok(Response::new(ListPresetsResponse { presets }))
+    }
+
+    async fn create_game(
+        &self,
+        request: Request<CreateGameRequest>,
+    ) -> Result<Response<CreateGameResponse>, Status> {
+        self.create_configured_game(request.into_inner(), false).await.map(Response::new)
+    }
+
+    async fn create_private_game(
+        &self,
+        request: Request<CreateGameRequest>,
+    ) -> Result<Response<CreateGameResponse>, Status> {
+        self.create_configured_game(request.into_inner(), true).await.map(Response::new)
+    }
+
+    async fn join_private_game(
+        &self,
+        request: Request<JoinRequest>,
+    ) -> Result<Response<GameState>, Status> {
+        if self.is_draining() {
+            return Err(GameError::ShuttingDown.into());
+        }
+        let code = request.into_inner().invite_code;
+        let (game, connection_id, state) = self.manager.lock().await.join_by_invite(code.trim()).await?;
+        self.expire_unclaimed_seats(game, vec![("O".to_string(), connection_id)]);
+        Ok(Response::new(state))
+    }
+
+    async fn define_preset(
+        &self,
+        request: Request<DefinePresetRequest>,
+    ) -> Result<Response<crate::tictactoe::Preset>, Status> {
+        if let Some(denied) = self.admin_denied(&request) {
+            return Err(denied.into());
+        }
+        let preset = request
+            .into_inner()
+            .preset
+            .ok_or_else(|| GameError::InvalidArgument("preset이 필요합니다.".into()))?;
+        let preset = Preset::from_proto(&preset);
+        self.presets.lock().await.define(preset.clone())?;
+        Ok(Response::new(preset.to_proto(false)))
+    }
+
+    async fn get_last_reload_report(
+        &self,
+        request: Request<ReloadReportRequest>,
+    ) -> Result<Response<crate::tictactoe::ReloadReport>, Status> {
+        if let Some(denied) = self.admin_denied(&request) {
+            return Err(denied.into());
+        }
+        let report = self.last_reload.read().unwrap().as_ref().map(ReloadReport::to_proto);
+        Ok(Response::new(report.unwrap_or_default()))
+    }
+
+    async fn get_player_rating(
+        &self,
+        request: Request<PlayerRatingRequest>,
+    ) -> Result<Response<PlayerRating>, Status> {
+        self.check_load(OptionalWork::StatsReads)?;
+        let player_id = request.into_inner().player_id.trim().to_string();
+        if player_id.is_empty() {
+            return Err(GameError::InvalidArgument("player_id가 필요합니다.".into()).into());
+        }
+        let record = self.ratings.lock().await.get(&player_id);
+        let record = record.ok_or(GameError::PlayerNotFound)?;
+        Ok(Response::new(record.to_proto(&player_id)))
+    }
+
+    async fn get_player_stats(
+        &self,
+        request: Request<PlayerStatsRequest>,
+    ) -> Result<Response<PlayerStats>, Status> {
+        self.check_load(OptionalWork::StatsReads)?;
+        let player_id = request.into_inner().player_id.trim().to_string();
+        if player_id.is_empty() {
+            return Err(GameError::InvalidArgument("player_id가 필요합니다.".into()).into());
+        }
+        let stats = self.stats.lock().await.get(&player_id);
+        let stats = stats.ok_or(GameError::PlayerNotFound)?;
+        Ok(Response::new(stats.to_proto(&player_id)))
+    }
+
+    async fn get_leaderboard(
+        &self,
+        request: Request<LeaderboardRequest>,
+    ) -> Result<Response<LeaderboardResponse>, Status> {
+        self.check_load(OptionalWork::StatsReads)?;
+        let request = request.into_inner();
+        let sort_by = SortField::try_from(request.sort_by)
+            .map_err(|_| GameError::InvalidArgument(format!("알 수 없는 정렬 기준입니다: {}", request.sort_by)))?;
+        if request.page < 0 {
+            return Err(GameError::InvalidArgument(format!("page는 0 이상이어야 합니다: {}", request.page)).into());
+        }
+        let page_size = if request.page_size > 0 { request.page_size } else { request.limit };
+        let page_size = match page_size {
+            size if size <= 0 => DEFAULT_LEADERBOARD_LIMIT,
+            size => (size as usize).min(MAX_LEADERBOARD_LIMIT),
+        };
+        let page = request.page as usize;
+        let key = (sort_by, page, page_size);
+        let ttl = self.config().leaderboard_cache();
+        if let Some(cached) = self.leaderboard_cache.lock().await.get(key, ttl) {
+            return Ok(Response::new(cached));
+        }
+        let response = {
+            let stats = self.stats.lock().await;
+            let ratings = self.ratings.lock().await;
+            stats::leaderboard_page(&stats, &ratings, sort_by, page.saturating_mul(page_size), page_size)
+        };
+        self.leaderboard_cache.lock().await.insert(key, response.clone(), ttl);
+        Ok(Response::new(response))
+    }
+
+    async fn get_puzzle(&self, request: Request<PuzzleRequest>) -> Result<Response<Puzzle>, Status> {
+        self.check_load(OptionalWork::Puzzles)?;
+        let request = request.into_inner();
+        let difficulty = PuzzleDifficulty::try_from(request.difficulty)
+            .map_err(|_| GameError::InvalidArgument(format!("알 수 없는 퍼즐 난이도입니다: {}", request.difficulty)))?;
+        let puzzle = puzzles::builtin().random(difficulty).ok_or(GameError::PuzzleNotFound)?;
+        Ok(Response::new(puzzle.to_proto()))
+    }
+
+    async fn solve_puzzle(&self, request: Request<PuzzleSolution>) -> Result<Response<SolveResult>, Status> {
+        self.check_load(OptionalWork::Puzzles)?;
+        let solution = request.into_inner();
+        let puzzle = puzzles::builtin().get(&solution.puzzle_id).ok_or(GameError::PuzzleNotFound)?;
+        Ok(Response::new(puzzle.check(solution.position)?))
+    }
+
+    #[instrument(skip_all, fields(game_id))]
+    async fn send_chat(&self, request: Request<ChatMessage>) -> Result<Response<Empty>, Status> {
+        let message = request.into_inner();
+        let game_id = message.game_id.trim();
+        tracing::Span::current().record("game_id", game_id);
+        let game = self.manager.lock().await.get(game_id).ok_or(GameError::GameNotFound)?;
+        let config = self.config();
+        let sent = game
+            .run(move |game| {
+                Box::pin(async move {
+                    // 세션 토큰으로 보내는 자리를 확인 (봇 자리는 토큰이 비어 있음)
+                    let seated = game
+                        .player(&message.sender_symbol)
+                        .is_some_and(|p| !message.session_token.is_empty() && p.session_token == message.session_token);
+                    if !seated {
+                        return Err(GameError::NotYourSeat);
+                    }
+                    Ok(game.chat(&message.sender_symbol, &message.text, &config).await?)
+                })
+            })
+            .await;
+        sent?;
+        Ok(Response::new(Empty {}))
+    }
+
+    #[instrument(skip_all, fields(game_id, player_symbol))]
+    async fn submit_move(&self, request: Request<SubmitMoveRequest>) -> Result<Response<GameState>, Status> {
+        let request = request.into_inner();
+        tracing::Span::current().record("game_id", request.game_id.as_str());
+        let started = Instant::now();
+        let game = self.manager.lock().await.get(&request.game_id).ok_or(GameError::GameNotFound)?;
+        let token = request.session_token;
+        let symbol = game.with(move |game| game.seat_with_token(&token).map(|p| p.symbol.clone())).await.ok_or(GameError::NotYourSeat)?;
+        tracing::Span::current().record("player_symbol", symbol.as_str());
+        let mv = request.r#move.unwrap_or_default();
+        debug!(position = mv.position, "수 요청 (SubmitMove)");
+        game.play_move(&symbol, mv, self.hooks(&game)).await.map_err(GameError::from)?;
+        self.load.record_move_latency(started.elapsed());
+        let state = game.with(move |game| game.player(&symbol).map(|player| game.update_for(player)).unwrap_or_else(|| game.create_update())).await;
+        Ok(Response::new(state))
+    }
+
+    async fn export_game(&self, request: Request<ExportGameRequest>) -> Result<Response<GameNotation>, Status> {
+        self.check_load(OptionalWork::PollingReads)?;
+        let request = request.into_inner();
+        let game = self.manager.lock().await.readable(request.game_id.trim(), &request.session_token).await?;
+        let notation = game.with(|game| notation::of(game)).await.map_err(GameError::from)?;
+        Ok(Response::new(GameNotation { notation }))
+    }
+
+    async fn import_game(&self, request: Request<GameNotation>) -> Result<Response<GameState>, Status> {
+        let state = notation::import(&request.into_inner().notation).map_err(GameError::from)?;
+        Ok(Response::new(state))
+    }
+}
+
+impl TicTacToeService {
+    /// 설정으로 서비스 생성
+    pub fn new(config: Config) -> Self {
+        let metrics = Arc::new(Metrics::default());
+        let mut manager = GameManager::new(config.max_games, metrics.clone());
+        manager.set_first_player(config.first_player);
+        manager.set_spectator_drop_limit(config.spectator_drop_limit);
+        manager.set_turn_timeout(config.turn_timeout());
+        let manager = Arc::new(Mutex::new(manager));
+        TicTacToeService {
+            tournaments: Arc::new(Mutex::new(TournamentManager::new(manager.clone()))),
+            manager,
+            metrics,
+            load: Arc::new(LoadShedder::new(config.latency_budget())),
+            presets: Arc::new(Mutex::new(PresetStore::default())),
+            ratings: Arc::new(Mutex::new(RatingBook::default())),
+            stats: Arc::new(Mutex::new(StatsBook::default())),
+            leaderboard_cache: Arc::new(Mutex::new(LeaderboardCache::default())),
+            health: tonic_health::server::health_reporter().0,
+            config: Arc::new(RwLock::new(Arc::new(config))),
+            last_reload: Arc::new(RwLock::new(None)),
+            draining: Arc::new(AtomicBool::new(false)),
+            recorder: None,
+            event_log: None,
+            snapshots: None,
+            replays: Arc::new(std::sync::Mutex::new(ReplayBuffer::new())),
+        }
+    }
+
+    /// 현재 설정의 스냅샷 (다시 읽기와 겹쳐도 한 요청 안에서는 같은 설정을 보도록 한 번 받아 씀)
+    pub fn config(&self) -> Arc<Config> {
+        self.config.read().unwrap().clone()
+    }
+
+    /// 새로 읽은 설정 반영: 바로 적용할 수 있는 변경만 한꺼번에 적용하고, 나머지는 거부해 보고서에 남깁니다.
+    /// `loaded`가 에러면(파일을 읽거나 해석하지 못함) 아무것도 바꾸지 않습니다.
+    pub async fn reload_config(&self, loaded: Result<Config, String>, source: &str) -> ReloadReport {
+        let report = match loaded {
+            Err(error) => ReloadReport::failed(source, error),
+            Ok(new) => {
+                // 매니저 잠금을 쥔 채 적용해 새 게임 생성이 반쯤 바뀐 설정을 보지 않게 함
+                let mut manager = self.manager.lock().await;
+                let (merged, changes) = plan_reload(&self.config(), &new);
+                manager.set_max_games(merged.max_games);
+                manager.set_first_player(merged.first_player);
+                manager.set_spectator_drop_limit(merged.spectator_drop_limit);
+                manager.set_turn_timeout(merged.turn_timeout());
+                self.load.set_budget(merged.latency_budget());
+                *self.config.write().unwrap() = Arc::new(merged);
+                ReloadReport::new(source, changes)
+            }
+        };
+        report.log();
+        *self.last_reload.write().unwrap() = Some(report.clone());
+        report
+    }
+
+    /// 프리셋 저장소 지정 (기본은 파일 없이 메모리에만 보관하는 빈 저장소)
+    pub fn with_presets(mut self, presets: PresetStore) -> Self {
+        self.presets = Arc::new(Mutex::new(presets));
+        self
+    }
+
+    /// 레이팅 저장소 지정 (기본은 파일 없이 메모리에만 보관하는 빈 저장소)
+    pub fn with_ratings(mut self, ratings: RatingBook) -> Self {
+        self.ratings = Arc::new(Mutex::new(ratings));
+        self
+    }
+
+    /// 통계 저장소 지정 (기본은 파일 없이 메모리에만 보관하는 빈 저장소)
+    pub fn with_stats(mut self, stats: StatsBook) -> Self {
+        self.stats = Arc::new(Mutex::new(stats));
+        self
+    }
+
+    /// 끝난 게임을 한 줄씩 기록할 곳 지정 (기본은 기록하지 않음)
+    pub fn with_recorder(mut self, recorder: GameRecorder) -> Self {
+        self.manager.try_lock().expect("서비스를 만드는 동안에는 매니저를 잠그지 않음").set_recorder(recorder.clone());
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// 게임 이벤트를 남길 로그 지정 (기본은 남기지 않음, 이후 만드는 게임부터 적용)
+    pub fn with_event_log(mut self, event_log: Arc<GameEventLogger>) -> Self {
+        self.manager.try_lock().expect("서비스를 만드는 동안에는 매니저를 잠그지 않음").set_event_log(event_log.clone());
+        self.event_log = Some(event_log);
+        self
+    }
+
+    /// 끝난 게임마다 스냅샷 파일을 쓸 곳 지정 (기본은 쓰지 않음)
+    pub fn with_snapshots(mut self, snapshots: SnapshotWriter) -> Self {
+        self.manager.try_lock().expect("서비스를 만드는 동안에는 매니저를 잠그지 않음").set_snapshots(snapshots.clone());
+        self.snapshots = Some(snapshots);
+        self
+    }
+
+    /// 헬스 체크 서비스와 연결된 리포터 지정 (기본은 어느 서비스에도 연결되지 않은 리포터)
+    pub fn with_health(mut self, health: HealthReporter) -> Self {
+        self.health = health;
+        self
+    }
+
+    /// 헬스 체크 상태 변경 (리스너를 바인드한 뒤 SERVING)
+    pub async fn set_health(&self, status: ServingStatus) {
+        health::set_status(&mut self.health.clone(), status).await;
+    }
+
+    /// 운영 지표 (지표 HTTP 서버에 넘겨 내보냄)
+    pub fn metrics(&self) -> Arc<Metrics> {
+        self.metrics.clone()
+    }
+
+    /// 부하 제어기 (지연 측정값 주입과 상태 확인용)
+    pub fn load_shedder(&self) -> Arc<LoadShedder> {
+        self.load.clone()
+    }
+
+    /// 주기적으로 수 처리 지연을 평가해 부가 작업을 끄고 켜는 태스크 시작
+    pub fn spawn_load_controller(&self) {
+        let load = self.load.clone();
+        let mut interval = tokio::time::interval(self.config().load_check_interval());
+        tokio::spawn(async move {
+            loop {
+                interval.tick().await;
+                load.tick();
+            }
+        });
+    }
+
+    /// 주기적으로 하트비트가 끊긴 플레이어를 찾아 그 게임을 "idle_timeout"으로 끝내는 태스크 시작
+    /// (idle_timeout_secs는 설정 다시 읽기로 바뀔 수 있으므로 매번 현재 설정을 봄)
+    pub fn spawn_idle_sweeper(&self) {
+        let service = self.clone();
+        let mut interval = tokio::time::interval(IDLE_SWEEP_INTERVAL);
+        tokio::spawn(async move {
+            loop {
+                interval.tick().await;
+                if let Some(timeout) = service.config().idle_timeout() {
+                    let completed = service.manager.lock().await.expire_idle_players(timeout).await;
+                    service.record_stats(completed);
+                }
+            }
+        });
+    }
+
+    /// 프리셋과 보드 크기로 게임을 만듦 (`invite_only`면 초대 코드도 발급)
+    async fn create_configured_game(&self, request: CreateGameRequest, invite_only: bool) -> Result<CreateGameResponse, Status> {
+        if self.is_draining() {
+            return Err(GameError::ShuttingDown.into());
+        }
+        let options = self.presets.lock().await.resolve(&request.preset, &request.overrides.unwrap_or_default())?;
+        let preset = if request.preset.is_empty() { crate::presets::DEFAULT_PRESET } else { &request.preset };
+        let mode = GameMode::try_from(request.mode)
+            .map_err(|_| GameError::InvalidArgument(format!("알 수 없는 게임 방식입니다: {}", request.mode)))?;
+        let (size, win_length) = match mode {
+            // 얼티밋 보드는 항상 9×9 (작은 보드 3×3 아홉 개)
+            GameMode::Ultimate if request.board_size != 0 || request.win_length != 0 => {
+                return Err(GameError::InvalidArgument("얼티밋 게임은 보드 크기와 승리 줄 길이를 지정할 수 없습니다.".into()).into());
+            }
+            GameMode::Ultimate => (ultimate::ULTIMATE_BOARD_SIZE, ultimate::ULTIMATE_WIN_LENGTH),
+            GameMode::Classic | GameMode::Misere | GameMode::Wild => {
+                let size = match request.board_size {
+                    0 => DEFAULT_BOARD_SIZE,
+                    size => size.max(0) as usize,
+                };
+                let win_length = match request.win_length {
+                    0 => DEFAULT_WIN_LENGTH,
+                    len => len.max(0) as usize,
+                };
+                (size, win_length)
+            }
+        };
+        board::validate_dimensions(size, win_length).map_err(GameError::InvalidArgument)?;
+        game::validate_best_of(request.best_of).map_err(GameError::InvalidArgument)?;
+        if request.auto_rematch && request.best_of == 0 {
+            return Err(GameError::InvalidArgument("auto_rematch는 best_of와 함께만 쓸 수 있습니다.".into()).into());
+        }
+        // 와일드의 돌은 주인이 없어 가져갈 수 없음
+        if request.swap_rule && mode == GameMode::Wild {
+            return Err(GameError::InvalidArgument("와일드 게임에는 스왑 규칙을 쓸 수 없습니다.".into()).into());
+        }
+
+        let (game, invite_code) = {
+            let mut manager = self.manager.lock().await;
+            if invite_only {
+                let ttl = self.config().invite_ttl();
+                manager.create_private_game(preset, options.clone(), size, win_length, ttl).await
+            } else {
+                manager.create_preset_game(preset, options.clone(), size, win_length).map(|game| (game, String::new()))
+            }
+        }
+        .ok_or(GameError::TooManyGames)?;
+        let (best_of, auto_rematch, swap_rule) = (request.best_of, request.auto_rematch, request.swap_rule);
+        game.with(move |game| {
+            game.best_of = best_of;
+            game.auto_rematch = auto_rematch;
+            game.swap_rule = swap_rule;
+            game.mode = mode;
+        })
+        .await;
+        let game_id = game.game_id().to_string();
+        info!(%game_id, preset, ?mode, board_size = size, win_length, invite_only, best_of = request.best_of, auto_rematch = request.auto_rematch, swap_rule = request.swap_rule, "프리셋으로 게임 생성");
+        self.expire_unclaimed_game(game_id.clone());
+        Ok(CreateGameResponse {
+            game_id,
+            options: Some(options.to_proto()),
+            board_size: size as i32,
+            win_length: win_length as i32,
+            invite_code,
+        })
+    }
+
+    /// 부하가 높아 `work`를 중단한 상태면 Overloaded
+    fn check_load(&self, work: OptionalWork) -> Result<(), GameError> {
+        if self.load.is_enabled(work) {
+            Ok(())
+        } else {
+            Err(GameError::Overloaded)
+        }
+    }
+
+    /// 관리자 RPC 권한 검사: 요청 메타데이터의 x-admin-token이 설정의 관리자 토큰과 다르면 거부 이유 반환
+    fn admin_denied<T>(&self, request: &Request<T>) -> Option<GameError> {
+        admin::admin_denied(&self.config(), request.metadata())
+    }
+
+    /// CreateGame으로 만든 게임에 재접속 유예 시간 안에 아무도 참가하지 않으면 제거
+    fn expire_unclaimed_game(&self, game_id: String) {
+        let manager = self.manager.clone();
+        let grace = self.config().reconnect_grace();
+        tokio::spawn(async move {
+            tokio::time::sleep(grace).await;
+            let mut manager = manager.lock().await;
+            let Some(game) = manager.get(&game_id) else {
+                return;
+            };
+            if game.with(|game| game.player_count()).await == 0 {
+                info!(%game_id, "참가자 없는 게임 만료");
+                manager.remove(&game_id);
+            }
+        });
+    }
+
+    /// 응답 스트림이 업데이트를 받아 가지 않아 `stalled`가 완료되면 그 연결을 끊긴 것으로 보고 진행 중인 게임은
+    /// 그 플레이어의 기권패로 끝냄 (스트림이 정상적으로 끝나면 아무것도 하지 않음)
+    fn forfeit_when_stalled(&self, stalled: oneshot::Receiver<()>, game: GameHandle, symbol: String, connection_id: u64) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            if stalled.await.is_err() {
+                return;
+            }
+            game.run(move |game| {
+                Box::pin(async move {
+                    if game.forfeit_stalled(&symbol, connection_id).await {
+                        service.record_finish(game);
+                    }
+                })
+            })
+            .await;
+        }.in_current_span());
+    }
+
+    /// 매치메이킹으로 배정된 자리에 재접속 유예 시간 안에 아무도 접속하지 않으면 게임을 정리
+    fn expire_unclaimed_seats(&self, game: GameHandle, seats: Vec<(String, u64)>) {
+        let manager = self.manager.clone();
+        let grace = self.config().reconnect_grace();
+        tokio::spawn(async move {
+            tokio::time::sleep(grace).await;
+            let mut manager = manager.lock().await;
+            let abandoned = game
+                .run(move |game| {
+                    Box::pin(async move {
+                        if !seats.iter().any(|(symbol, connection_id)| game.still_disconnected(symbol, *connection_id)) {
+                            return false;
+                        }
+                        info!(game_id = %game.game_id, "매칭된 플레이어가 접속하지 않음, 게임 정리");
+                        game.end_streams(EndReason::GameAbandoned).await;
+                        game.reset();
+                        true
+                    })
+                })
+                .await;
+            if abandoned {
+                manager.remove(game.game_id());
+            }
+        });
+    }
+
+    /// 끝난 게임들을 통계에 반영 (통계 파일 쓰기가 비동기 작업 스레드나 게임 액터를 막지 않도록 블로킹 스레드에서)
+    fn record_stats(&self, completed: Vec<CompletedGame>) {
+        if completed.is_empty() {
+            return;
+        }
+        let (stats, span) = (self.stats.clone(), tracing::Span::current());
+        tokio::task::spawn_blocking(move || {
+            let _entered = span.enter();
+            let mut stats = stats.blocking_lock();
+            for game in &completed {
+                stats.record(game);
+            }
+        });
+    }
+
+    /// 게임이 막 끝났으면 결과를 지표와 게임 기록, 스냅샷에 남기고, 별도 태스크에서 통계와 (레이팅 게임이면) 레이팅에 반영
+    /// (수 처리를 기다리게 하지 않음)
+    pub(crate) fn record_finish(&self, game: &SharedGame) {
+        if !game.is_finished() {
+            return;
+        }
+        self.metrics.game_finished(&game.status);
+        if let Some(recorder) = &self.recorder {
+            recorder.record(game);
+        }
+        if let Some(snapshots) = &self.snapshots {
+            snapshots.write(game);
+        }
+        if let Some(finished) = FinishedGame::of(game) {
+            self.replays.lock().unwrap().push(finished, self.config().replay_buffer_games);
+        }
+        if game.reserved.is_some() {
+            // 게임 액터 안이므로 토너먼트는 따로 잠금 (토너먼트가 새 경기를 열려면 매니저와 게임 액터가 필요함)
+            let tournaments = self.tournaments.clone();
+            let (game_id, status, timed_out) = (game.game_id.clone(), game.status.clone(), game.timed_out.clone());
+            tokio::spawn(async move {
+                tournaments.lock().await.game_finished(&game_id, &status, timed_out.as_deref()).await;
+            });
+        }
+        if let Some(completed) = CompletedGame::of(game) {
+            self.record_stats(vec![completed]);
+        }
+        let Some(result) = game.rated_result() else {
+            return;
+        };
+        let ratings = self.ratings.clone();
+        let elo = EloRating::new(self.config().elo_k_factor);
+        tokio::spawn(async move {
+            ratings.lock().await.record(&result, &elo);
+        }.in_current_span());
+    }
+
+    /// 종료를 시작해 새 게임 참가를 받지 않는 중인지
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Relaxed)
+    }
+
+    /// 서버 종료: 새 참가를 막고, 진행 중인 게임이 끝나기를 설정한 시간만큼 기다린 뒤
+    /// 남은 게임을 "server_shutdown"으로 끝내고 모든 응답 스트림을 종료 사유와 함께 닫습니다.
+    pub async fn shutdown(&self) {
+        let drain = self.config().shutdown_drain_timeout();
+        info!(drain_secs = drain.as_secs(), "서버 종료 시작, 진행 중인 게임이 끝나기를 기다림");
+        self.draining.store(true, Ordering::Relaxed);
+        // 로드 밸런서가 새 연결을 보내지 않도록 먼저 NOT_SERVING으로 바꿈
+        self.set_health(ServingStatus::NotServing).await;
+        self.manager.lock().await.announce_shutdown(drain).await;
+
+        let drained = tokio::time::timeout(drain, async {
+            while self.manager.lock().await.ongoing_games().await > 0 {
+                tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+            }
+        })
+        .await;
+        if drained.is_err() {
+            warn!("종료 대기 시간 초과, 남은 게임 강제 종료");
+        }
+        info!("서버 종료, 모든 스트림 종료");
+        self.manager.lock().await.end_games_for_shutdown().await;
+        if let Some(recorder) = &self.recorder {
+            recorder.flush().await;
+        }
+        if let Some(event_log) = &self.event_log {
+            event_log.flush().await;
+        }
+        if let Some(snapshots) = &self.snapshots {
+            snapshots.flush().await;
+        }
+    }
+
+    /// tonic 서버에 등록할 수 있는 형태로 변환 (압축한 요청은 받고, 응답은 압축하지 않음)
+    pub fn into_server(self) -> TicTacToeServer<Self> {
+        self.into_compressed_server(None)
+    }
+
+    /// 응답을 `encoding`으로 압축하는 tonic 서버로 변환 (클라이언트가 받겠다고 알린 방식일 때만 압축)
+    pub fn into_compressed_server(self, encoding: Option<CompressionEncoding>) -> TicTacToeServer<Self> {
+        let mut server = TicTacToeServer::new(self);
+        for accepted in compression::accepted() {
+            server = server.accept_compressed(accepted);
+        }
+        match encoding {
+            Some(encoding) => server.send_compressed(encoding),
+            None => server,
+        }
+    }
+
+    /// 같은 게임 목록을 다루는 관리자 서비스
+    pub fn admin(&self) -> TicTacToeAdmin {
+        TicTacToeAdmin::new(self.manager.clone(), self.config.clone(), self.clone())
+    }
+
+    /// 같은 게임 매니저로 경기를 여는 토너먼트 서비스
+    pub fn tournaments(&self) -> TicTacToeTournaments {
+        TicTacToeTournaments::new(self.tournaments.clone())
+    }
+
+    /// 모든 요청이 인증 인터셉터를 거치고 응답을 `encoding`으로 압축하는 tonic 서버로 변환
+    pub fn into_authenticated_server(
+        self,
+        auth: AuthInterceptor,
+        encoding: Option<CompressionEncoding>,
+    ) -> InterceptedService<TicTacToeServer<Self>, AuthInterceptor> {
+        InterceptedService::new(self.into_compressed_server(encoding), auth)
+    }
+
+    /// 빠른 대전: 제한 시간 안에 상대가 오지 않으면 봇과 대전
+    async fn start_quick_play_timer(&self, game: GameHandle, symbol: String, connection_id: u64) {
+        if game.with(|game| game.status != "searching").await {
+            return;
+        }
+        let timeout = self.config().quick_play_timeout();
+        let (service, handle) = (self.clone(), game.clone());
+        tokio::spawn(async move {
+            tokio::time::sleep(timeout).await;
+            game.run(move |game| {
+                Box::pin(async move {
+                    let still_searching = game.status == "searching"
+                        && game.player(&symbol).is_some_and(|p| p.connection_id == connection_id && p.connected);
+                    if still_searching {
+                        game.seat_house_bot().await;
+                        service.start_game_timers(&handle, game);
+                    }
+                })
+            })
+            .await;
+        });
+    }
+
+    /// `handle` 게임에 보내는 명령이 통계, 기록, 타이머를 이 서비스로 처리하도록 함
+    fn hooks(&self, handle: &GameHandle) -> ServiceHooks {
+        ServiceHooks { service: self.clone(), handle: handle.clone() }
+    }
+
+    /// 게임이 시작됐거나 새 차례가 시작됐으면 차례 제한 시간과 방치 타이머를 켬 (게임 액터 안에서 호출)
+    pub(crate) fn start_game_timers(&self, handle: &GameHandle, game: &mut SharedGame) {
+        self.start_turn_timer(handle, game);
+        self.start_abandon_timer(handle, game);
+    }
+
+    /// 진행 중인 게임에 방치 타이머가 없으면 켬 (게임 액터 안에서 호출). 깨어날 때마다 마지막 수나 참가 뒤 지난
+    /// 시간을 보고, 남은 시간이 있으면 그만큼 다시 기다리고, 다 썼으면 게임을 "abandoned"로 끝내고 목록에서 뺍니다.
+    /// 게임이 끝나면 토큰이 취소되어 타이머도 끝납니다. abandon_timeout_ms는 다시 읽기로 바뀔 수 있어 매번 현재 설정을 봄
+    fn start_abandon_timer(&self, handle: &GameHandle, game: &mut SharedGame) {
+        let Some(timeout) = self.config().abandon_timeout() else {
+            return;
+        };
+        let Some(cancel) = game.start_abandon_watch() else {
+            return;
+        };
+        let (service, handle) = (self.clone(), handle.clone());
+        tokio::spawn(async move {
+            let mut wait = timeout;
+            loop {
+                tokio::select! {
+                    _ = cancel.cancelled() => return,
+                    _ = tokio::time::sleep(wait) => {}
+                }
+                let (timer, token) = (service.clone(), cancel.clone());
+                let step = handle
+                    .run(move |game| {
+                        Box::pin(async move {
+                            // 깨어난 뒤 이 작업보다 먼저 게임이 끝났으면 토큰이 이미 취소됨
+                            if token.is_cancelled() {
+                                return ControlFlow::Break(false);
+                            }
+                            let Some(timeout) = timer.config().abandon_timeout() else {
+                                game.stop_abandon_watch();
+                                return ControlFlow::Break(false);
+                            };
+                            let left = timeout.saturating_sub(game.inactive_for());
+                            if !left.is_zero() {
+                                debug!(game_id = %game.game_id, idle_budget_ms = left.as_millis() as u64, "방치 타이머 남은 시간");
+                                return ControlFlow::Continue(left);
+                            }
+                            game.abandon().await;
+                            timer.record_finish(game);
+                            ControlFlow::Break(true)
+                        })
+                    })
+                    .await;
+                match step {
+                    ControlFlow::Continue(left) => wait = left,
+                    ControlFlow::Break(false) => return,
+                    ControlFlow::Break(true) => break,
+                }
+            }
+            service.manager.lock().await.remove(handle.game_id());
+        }.in_current_span());
+    }
+
+    /// 새 차례가 시작됐으면 차례 제한 시간 타이머를 켬 (게임 액터 안에서 호출, 이미 켜져 있으면 그대로 둠).
+    /// 올바른 수가 오거나 게임이 끝나면 토큰이 취소되어 타이머가 바로 끝나고, 제한 시간이 먼저 지나면
+    /// 차례인 플레이어의 기권패로 끝냅니다.
+    fn start_turn_timer(&self, handle: &GameHandle, game: &mut SharedGame) {
+        let Some(timeout) = self.config().turn_timeout() else {
+            game.turn_timeout = None;
+            return;
+        };
+        let Some((symbol, cancel)) = game.start_turn() else {
+            return;
+        };
+        // 설정을 다시 읽어 제한 시간이 바뀌었으면 새 차례부터 알림
+        game.turn_timeout = Some(timeout);
+        let (service, handle) = (self.clone(), handle.clone());
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = cancel.cancelled() => return,
+                _ = tokio::time::sleep(timeout) => {}
+            }
+            handle.tick(&symbol, cancel, service.hooks(&handle)).await;
+        }.in_current_span());
+    }
+
+    /// 자리를 받고 join_timeout 안에 아무 메시지도 보내지 않은 연결의 자리를 비우고, 남은 사람이 없으면 게임을 정리
+    async fn expire_join(&self, game: &GameHandle, symbol: &str, connection_id: u64) {
+        let mut manager = self.manager.lock().await;
+        let symbol = symbol.to_string();
+        let emptied = game
+            .run(move |game| {
+                Box::pin(async move {
+                    if !game.expire_join(&symbol, connection_id).await {
+                        return false;
+                    }
+                    // 봇만 남은 게임은 이어 갈 수 없음
+                    let emptied = game.lone_player().is_none_or(|p| p.is_bot);
+                    if emptied {
+                        game.reset();
+                    }
+                    emptied
+                })
+            })
+            .await;
+        if emptied {
+            manager.remove(game.game_id());
+        }
+    }
+
+    /// 혼자 앉아 상대를 기다리는 게임: 제한 시간 안에 상대가 오지 않으면 게임을 닫아 자리를 비움
+    /// (상대가 앉거나 게임이 초기화되면 타이머가 취소됨)
+    async fn start_waiting_timer(&self, game: GameHandle) {
+        let manager = self.manager.clone();
+        let timeout = self.config().waiting_timeout();
+        let waiting = game.clone();
+        game.with(move |game| {
+            // 초대 전용 게임에서 초대받은 상대(O)가 만든 사람보다 먼저 온 경우에는 타이머 없이 기다림
+            let waiting_alone = game.status == "waiting" && game.lone_player().is_some_and(|p| !(game.invite_only && p.symbol == "O"));
+            if !waiting_alone {
+                return;
+            }
+            let timer = tokio::spawn(async move {
+                tokio::time::sleep(timeout).await;
+                let mut manager = manager.lock().await;
+                // 그 사이 게임이 시작되었거나 플레이어가 스스로 떠났으면 아무것도 하지 않음
+                let expired = waiting
+                    .run(move |game| {
+                        Box::pin(async move {
+                            if !game.expire_waiting().await {
+                                return false;
+                            }
+                            info!(game_id = %game.game_id, timeout_ms = timeout.as_millis() as u64, "상대가 오지 않아 대기 중인 게임 닫음");
+                            game.reset();
+                            true
+                        })
+                    })
+                    .await;
+                if expired {
+                    manager.remove(waiting.game_id());
+                }
+            }.in_current_span());
+            game.set_waiting_timer(timer.abort_handle());
+        })
+        .await;
+    }
+
+    /// 플레이어가 보내는 메시지를 처리하고, 접속이 끊기면 재접속 유예 후 게임을 정리합니다.
+    /// 주기적으로 `tx`에 하트비트를 보내고, 제한 시간 안에 보내지 못하거나 그동안 클라이언트에게서
+    /// 아무 메시지(하트비트 응답 포함)도 오지 않으면 접속이 끊긴 것으로 처리합니다.
+    #[instrument(name = "move_processing", skip_all)]
+    async fn handle_player(
+        &self,
+        mut inbound: Streaming<PlayRequest>,
+        tx: UpdateSender,
+        shared: GameHandle,
+        symbol: String,
+        connection_id: u64,
+    ) {
+        let config = self.config();
+        let (interval, timeout) = (config.heartbeat_interval(), config.heartbeat_timeout());
+        let mut heartbeat = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+        heartbeat.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut unanswered_since = None; // 응답을 받지 못한 첫 하트비트를 보낸 시각
+        let now = tokio::time::Instant::now();
+        let mut limiter = ConnectionLimiter::new(config.message_rate_per_sec, config.message_burst, config.dropped_message_limit, now);
+        // 자리를 받고 아무 메시지도 보내지 않는 연결(스캐너 등)이 자리를 계속 차지하지 않도록 첫 메시지를 기다리는 시간
+        let join_timeout = config.join_timeout();
+        let join_deadline = tokio::time::sleep(join_timeout.unwrap_or_default());
+        tokio::pin!(join_deadline);
+        let mut silent = join_timeout.is_some();
+        loop {
+            let result = tokio::select! {
+                message = inbound.message() => match message.transpose() {
+                    Some(result) => result,
+                    None => break,
+                },
+                _ = &mut join_deadline, if silent => {
+                    warn!(timeout_secs = config.join_timeout_secs, "참가한 뒤 메시지가 없음, 자리 비움");
+                    self.expire_join(&shared, &symbol, connection_id).await;
+                    return;
+                }
+                _ = heartbeat.tick() => {
+                    // 잠든 노트북이나 응답 스트림을 버린 클라이언트는 하트비트에 응답하지 않음
+                    if unanswered_since.is_some_and(|sent: tokio::time::Instant| sent.elapsed() >= timeout) {
+                        warn!(timeout_secs = timeout.as_secs(), "하트비트 응답 없음, 접속 끊김으로 처리");
+                        break;
+                    }
+                    let beat = GameState { heartbeat: true, ..Default::default() };
+                    if let Err(e) = tx.send_timeout(Ok(beat), timeout).await {
+                        warn!(error = %e, "하트비트 전송 실패, 접속 끊김으로 처리");
+                        break;
+                    }
+                    unanswered_since.get_or_insert_with(tokio::time::Instant::now);
+                    continue;
+                }
+            };
+            // 클라이언트가 보낸 메시지는 무엇이든 살아 있다는 응답으로 봄
+            unanswered_since = None;
+            silent = false;
+            // 빈도 제한을 넘은 메시지는 게임 액터에 보내지 않고 버림
+            let kind = match &result {
+                Ok(PlayRequest { action: Some(Action::Chat(_)) }) => Some(MessageKind::Chat),
+                Ok(PlayRequest { action: Some(Action::Heartbeat(_)) | None }) | Err(_) => None,
+                Ok(_) => Some(MessageKind::Action),
+            };
+            match kind.map(|kind| limiter.check(kind, tokio::time::Instant::now())) {
+                None | Some(Verdict::Allow) => {}
+                Some(Verdict::Drop { warn, dropped }) => {
+                    if warn {
+                        warn!(?kind, dropped, "빈도 제한을 넘은 메시지를 버림");
+                    }
+                    continue;
+                }
+                Some(Verdict::Disconnect) => {
+                    warn!(limit = config.dropped_message_limit, "빈도 제한을 넘은 메시지가 너무 많아 연결을 끊음");
+                    let _ = tx.try_send(Err(egress::stream_end(EndReason::RateLimited, shared.game_id())));
+                    break;
+                }
+            }
+            let action = match result {
+                Ok(PlayRequest { action }) => action,
+                Err(e) => {
+                    warn!(error = %e, "메시지 수신 에러");
+                    break;
+                }
+            };
+            let received = Instant::now();
+            let alive = symbol.clone();
+            shared.with(move |game| game.touch(&alive, connection_id)).await;
+            let hooks = self.hooks(&shared);
+            match action {
+                None => {}
+                Some(Action::Move(mv)) => {
+                    debug!(position = mv.position, "수 요청");
+                    match shared.play_move(&symbol, mv, hooks).await {
+                        Ok(()) => self.load.record_move_latency(received.elapsed()),
+                        Err(e) => send_error(&shared, &symbol, e.player_message()).await,
+                    }
+                }
+                Some(Action::Resign(_)) => {
+                    let resigned = shared.resign(&symbol, hooks).await;
+                    if resigned.is_err() {
+                        send_error(&shared, &symbol, "You can only resign during an ongoing game.").await;
+                    }
+                }
+                Some(action) => {
+                    let (service, symbol) = (self.clone(), symbol.clone());
+                    shared
+                        .run(move |game| {
+                            Box::pin(async move {
+                                service.play_action(game, &symbol, action, &hooks).await;
+                                hooks.settled(game);
+                            })
+                        })
+                        .await;
+                }
+            }
+        }
+        // 연결 확인과 게임 상태 확인을 한 명령 안에서 함 (그 사이 상대의 수가 게임을 끝낼 수 있음)
+        let Some(departure) = shared.disconnect(&symbol, connection_id).await else {
+            return;
+        };
+
+        // 끝난 게임은 재접속을 기다릴 필요가 없음
+        if !departure.finished {
+            let grace = self.config().reconnect_grace();
+            info!(grace_secs = grace.as_secs(), "플레이어 접속 끊김, 재접속 대기");
+            tokio::time::sleep(grace).await;
+        }
+
+        // 유예 시간 안에 재접속하지 않았고 그 사이 게임이 초기화되지 않았다면 게임을 정리하고 목록에서 제거
+        let mut manager = self.manager.lock().await;
+        let abandoned = shared
+            .run(move |game| {
+                Box::pin(async move {
+                    if !game.abandoned(&symbol, &departure) {
+                        return false;
+                    }
+                    info!("플레이어 접속 종료, 게임 정리");
+                    game.end_streams(EndReason::GameAbandoned).await;
+                    game.reset();
+                    true
+                })
+            })
+            .await;
+        if abandoned {
+            manager.remove(shared.game_id());
+        }
+    }
+
+    /// 플레이어 스트림으로 받은 수와 기권 밖의 게임 요청 하나를 게임 액터 안에서 처리 (거부하면 그 플레이어에게만
+    /// 오류를 보냄, 수와 기권은 정해진 명령으로 따로 보냄)
+    async fn play_action(&self, game: &mut SharedGame, symbol: &str, action: Action, hooks: &ServiceHooks) {
+        match action {
+            Action::Move(_) | Action::Resign(_) | Action::Heartbeat(_) => {}
+            Action::OfferDraw(_) => {
+                if let Err(reason) = game.offer_draw(symbol) {
+                    debug!(reason, "거부: 무승부 제안");
+                    game.send_error(symbol, reason).await;
+                    return;
+                }
+                info!("무승부 제안");
+                game.broadcast_update().await;
+            }
+            Action::RespondDraw(response) => {
+                if let Err(reason) = game.respond_draw(symbol, response.accept) {
+                    debug!(reason, "거부: 무승부 응답");
+                    game.send_error(symbol, reason).await;
+                    return;
+                }
+                info!(accept = response.accept, status = %game.status, "무승부 제안 응답");
+                if response.accept {
+                    game.broadcast_update().await;
+                    actor::finish_and_continue(game, hooks).await;
+                } else {
+                    game.broadcast_message("Draw offer declined.").await;
+                }
+            }
+            Action::Rematch(_) => {
+                match game.request_rematch(symbol) {
+                    Err(reason) => {
+                        debug!(reason, "거부: 한 판 더");
+                        game.send_error(symbol, reason).await;
+                    }
+                    Ok(false) => {
+                        info!("한 판 더 요청, 상대 응답 대기");
+                        let notice = format!("Player {} wants a rematch.", symbol);
+                        game.broadcast_message(&notice).await;
+                    }
+                    Ok(true) => {
+                        let notice = format!("Rematch! {} moves first this game.", game.first_player);
+                        game.broadcast_message(&notice).await;
+                        game.play_bot_turns().await;
+                        actor::finish_and_continue(game, hooks).await;
+                    }
+                }
+            }
+            Action::Swap(_) => {
+                let pos = match game.swap(symbol) {
+                    Ok(pos) => pos,
+                    Err(reason) => {
+                        debug!(reason, "거부: 스왑");
+                        game.send_error(symbol, reason).await;
+                        return;
+                    }
+                };
+                info!(position = pos, next_player = %game.next_player, "스왑, 첫 수를 가져감");
+                let notice = format!("Player {} swapped: the first move is now theirs.", symbol);
+                game.broadcast_message(&notice).await;
+                // 첫 수를 둔 쪽이 봇이면 이어서 봇의 수
+                game.play_bot_turns().await;
+                actor::finish_and_continue(game, hooks).await;
+            }
+            Action::Chat(message) => {
+                if let Err(e) = game.chat(symbol, &message.text, &self.config()).await {
+                    debug!(reason = %e, "거부: 채팅");
+                    game.send_error(symbol, &e.player_message()).await;
+                }
+            }
+            Action::Join(_) => game.send_error(symbol, "Already joined.").await,
+        }
+    }
+}
+
+/// 서비스가 게임 명령과 함께 보내는 [`GameHooks`]: 수 빈도 제한과 지표, 끝난 판의 기록, 차례와 방치 타이머
+struct ServiceHooks {
+    service: TicTacToeService,
+    handle: GameHandle,
+}
+
+impl GameHooks for ServiceHooks {
+    fn admit_move(&self, game: &mut SharedGame, symbol: &str) -> Result<(), MoveError> {
+        game.take_move_token(symbol, &self.service.config())
+    }
+
+    fn move_checked(&self, result: &Result<MoveOutcome, MoveError>) {
+        match result {
+            Ok(_) => self.service.metrics.move_accepted(),
+            Err(_) => self.service.metrics.move_rejected(),
+        }
+    }
+
+    fn finished(&self, game: &SharedGame) {
+        self.service.record_finish(game);
+    }
+
+    fn settled(&self, game: &mut SharedGame) {
+        self.service.start_game_timers(&self.handle, game);
+    }
+}
+
+/// 게임 스트림으로 `symbol` 자리의 플레이어에게만 오류를 보냄
+async fn send_error(game: &GameHandle, symbol: &str, message: &'static str) {
+    let symbol = symbol.to_string();
+    game.run(move |game| Box::pin(async move { game.send_error(&symbol, message).await })).await;
+}
+
+/// 관전자 스트림 처리: 관전자는 수를 둘 수 없으며, 연결이 끊기면 채널을 정리합니다.
+async fn handle_spectator(mut inbound: Streaming<PlayRequest>, game: GameHandle, tx: UpdateSender) {
+    while let Ok(Some(request)) = inbound.message().await {
+        let error = match request.action {
+            Some(Action::Move(_) | Action::Resign(_) | Action::OfferDraw(_) | Action::RespondDraw(_) | Action::Swap(_)) => {
+                "Spectators cannot make moves."
+            }
+            Some(Action::Chat(_)) => "Spectators cannot chat.",
+            _ => continue,
+        };
+        let mut update = game.snapshot().await;
+        update.status = "error".into();
+        update.state_hash.clear();
+        update.error_message = error.into();
+        if let Err(e) = tx.send(Ok(update)).await {
+            warn!(error = %e, "관전자에게 오류 전송 실패");
+        }
+    }
+    drop(tx);
+    game.with(|game| game.remove_closed_spectators()).await;
+}
@@ -0,0 +1,202 @@
+//! 끝난 게임 스냅샷 (`--snapshot-dir`)
+//!
+//! 게임이 끝날 때마다 보드, 전체 수, 플레이어, 결과, 시각을 `<DIR>/<game_id>.json` 파일 하나로 씁니다
+//! (`snapshot_format = "bincode"`면 `<game_id>.bin`, 형식은 [`crate::serialization`]).
+//! 끝난 게임 기록(`--record`)처럼 채널로 보내 전용 스레드가 쓰므로 업데이트 전송을 기다리게 하지 않고,
+//! 파일은 원자적으로 바꿔 써서 반쯤 쓰인 스냅샷이 남지 않습니다. 게임 ID가 같으면 (재대결이나 서버 재시작)
+//! 마지막 판으로 덮어씁니다. `replay` 도구가 스냅샷을 읽어 수마다 보드를 다시 보여 줍니다.
+
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tokio::sync::{mpsc, oneshot};
+use tracing::{instrument, warn};
+
+use crate::config::SnapshotFormat;
+use crate::game::SharedGame;
+use crate::record::unix_secs;
+use crate::serialization;
+
+/// 쓰기를 기다릴 수 있는 스냅샷 수 (넘치면 버리고 경고)
+const SNAPSHOT_QUEUE: usize = 1024;
+
+/// 끝난 게임 한 판의 스냅샷 (파일 하나)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    pub game_id: String,
+    pub created_at_unix: i64,
+    pub ended_at_unix: i64,
+    pub board_size: usize,
+    pub win_length: usize,
+    pub first_player: String,
+    pub preset: String,
+    pub rated: bool,
+    pub board: Vec<String>, // 끝났을 때의 보드 (행 우선 순서)
+    pub players: Vec<SnapshotPlayer>,
+    pub moves: Vec<SnapshotMove>,
+    pub outcome: String, // 게임의 최종 상태 ("X_win", "draw_agreed", "admin_terminated" 등)
+    pub best_of: u32,
+    pub score: SnapshotScore,
+}
+
+/// 스냅샷에 남기는 플레이어 (player_id가 없으면 빈 문자열)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotPlayer {
+    pub symbol: String,
+    pub player_id: String,
+    pub bot: bool,
+    pub marker: String, // 보드에 표시한 글자
+    pub rating: Option<i32>,
+}
+
+/// 스냅샷에 남기는 수 (둔 순서대로)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotMove {
+    pub symbol: String,
+    pub position: u8,
+    pub played_at_unix: i64,
+    #[serde(default)]
+    pub mark: String, // 보드에 놓은 심볼 (이 항목이 없던 이전 스냅샷에서는 빈 문자열이며 symbol을 놓은 수)
+}
+
+/// 몇 판 승부에서 이 판까지의 점수
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotScore {
+    pub x_wins: u32,
+    pub o_wins: u32,
+    pub draws: u32,
+}
+
+impl GameSnapshot {
+    /// 끝난 게임의 현재 상태로 스냅샷을 만듦 (종료 시각은 지금)
+    pub fn of(game: &SharedGame) -> Self {
+        let players = [&game.player_x, &game.player_o]
+            .into_iter()
+            .flatten()
+            .map(|p| SnapshotPlayer {
+                symbol: p.symbol.clone(),
+                player_id: p.player_id.clone(),
+                bot: p.is_bot,
+                marker: if p.symbol == "X" { game.player_x_symbol.clone() } else { game.player_o_symbol.clone() },
+                rating: p.rating,
+            })
+            .collect();
+        let moves = game
+            .history
+            .iter()
+            .map(|m| SnapshotMove {
+                symbol: m.symbol.clone(),
+                position: m.position,
+                played_at_unix: unix_secs(m.played_at),
+                mark: m.mark.clone(),
+            })
+            .collect();
+        GameSnapshot {
+            game_id: game.game_id.clone(),
+            created_at_unix: unix_secs(game.created_at),
+            ended_at_unix: unix_secs(SystemTime::now()),
+            board_size: game.board_size,
+            win_length: game.win_length,
+            first_player: game.first_player.clone(),
+            preset: game.preset.clone(),
+            rated: game.rated,
+            board: game.board.clone(),
+            players,
+            moves,
+            outcome: game.status.clone(),
+            best_of: game.best_of,
+            score: SnapshotScore { x_wins: game.score.x_wins, o_wins: game.score.o_wins, draws: game.score.draws },
+        }
+    }
+
+    /// 스냅샷 파일을 읽음 (형식은 파일 머리로 알아냄)
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = std::fs::read(path)?;
+        serialization::serializer(serialization::detect(&contents)).deserialize(&contents)
+    }
+
+    /// 빈 보드에 수를 차례대로 다시 두며 수마다의 보드를 만듦 (보드 밖의 수는 건너뜀)
+    pub fn boards(&self) -> Vec<Vec<String>> {
+        let mut board = vec![String::new(); self.board_size * self.board_size];
+        self.moves
+            .iter()
+            .map(|m| {
+                if let Some(cell) = board.get_mut(m.position as usize) {
+                    *cell = if m.mark.is_empty() { m.symbol.clone() } else { m.mark.clone() };
+                }
+                board.clone()
+            })
+            .collect()
+    }
+}
+
+/// 스냅샷 스레드에 보내는 요청
+enum Command {
+    Write(Box<GameSnapshot>),
+    /// 앞서 보낸 스냅샷을 모두 쓴 뒤 응답
+    Flush(oneshot::Sender<()>),
+}
+
+/// 스냅샷 쓰기 스레드로 스냅샷을 보내는 핸들 (복제해서 여러 곳에서 씀)
+#[derive(Clone)]
+pub struct SnapshotWriter {
+    tx: mpsc::Sender<Command>,
+    dir: PathBuf,
+    format: SnapshotFormat,
+}
+
+impl SnapshotWriter {
+    /// 스냅샷 디렉터리를 (없으면) 만들고 `format`으로 쓰는 쓰기 스레드를 시작
+    pub fn open(dir: &Path, format: SnapshotFormat) -> io::Result<Self> {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| io::Error::new(e.kind(), format!("스냅샷 디렉터리를 만들 수 없습니다 ({}): {}", dir.display(), e)))?;
+        let (tx, rx) = mpsc::channel(SNAPSHOT_QUEUE);
+        let writer_dir = dir.to_path_buf();
+        std::thread::Builder::new().name("game-snapshots".into()).spawn(move || write_snapshots(writer_dir, format, rx))?;
+        Ok(SnapshotWriter { tx, dir: dir.to_path_buf(), format })
+    }
+
+    /// `game_id`의 스냅샷 파일 경로
+    pub fn path_for(&self, game_id: &str) -> PathBuf {
+        snapshot_path(&self.dir, game_id, self.format)
+    }
+
+    /// 끝난 게임의 스냅샷을 쓰도록 보냄 (기다리지 않음)
+    #[instrument(level = "debug", skip_all, fields(game_id = %game.game_id))]
+    pub fn write(&self, game: &SharedGame) {
+        if let Err(e) = self.tx.try_send(Command::Write(Box::new(GameSnapshot::of(game)))) {
+            warn!(game_id = %game.game_id, error = %e, "게임 스냅샷 전송 실패, 스냅샷을 버림");
+        }
+    }
+
+    /// 지금까지 보낸 스냅샷이 모두 파일로 쓰일 때까지 대기
+    pub async fn flush(&self) {
+        let (ack, done) = oneshot::channel();
+        if self.tx.send(Command::Flush(ack)).await.is_ok() {
+            let _ = done.await;
+        }
+    }
+}
+
+fn snapshot_path(dir: &Path, game_id: &str, format: SnapshotFormat) -> PathBuf {
+    dir.join(format!("{}.{}", game_id, serialization::serializer(format).extension()))
+}
+
+/// 스냅샷 스레드: 스냅샷마다 파일 하나를 원자적으로 씀
+fn write_snapshots(dir: PathBuf, format: SnapshotFormat, mut rx: mpsc::Receiver<Command>) {
+    let serializer = serialization::serializer(format);
+    while let Some(command) = rx.blocking_recv() {
+        match command {
+            Command::Write(snapshot) => {
+                let path = snapshot_path(&dir, &snapshot.game_id, format);
+                if let Err(e) = common::fs::atomic_write(&path, &serializer.serialize(&snapshot)) {
+                    warn!(path = %path.display(), error = %e, "게임 스냅샷 쓰기 실패");
+                }
+            }
+            Command::Flush(ack) => {
+                let _ = ack.send(());
+            }
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! 플레이어별 누적 통계
+//!
+//! 레이팅과 달리 봇 대전과 비레이팅 게임도 포함해, player_id를 보낸 플레이어가 끝낸 모든 게임의 결과와
+//! 수를 두는 데 걸린 시간을 모읍니다. 통계는 설정의 `stats_file`에 저장합니다 (없으면 메모리에만 보관).
+//! 순위표(`GetLeaderboard`)는 통계와 레이팅을 합쳐 정렬한 뒤 요청한 페이지만 잘라 보내고, 같은 페이지의 응답은
+//! `leaderboard_cache_secs` 동안 [`LeaderboardCache`]에 두고 다시 씁니다.
+
+use common::fs::atomic_write;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tokio::time::Instant;
+use tracing::{info, instrument, warn};
+
+use crate::elo::RatingBook;
+use crate::game::{default_player_name, SharedGame, IDLE_TIMEOUT};
+use crate::record::unix_secs;
+use crate::tictactoe::{self, LeaderboardEntry, LeaderboardResponse, SortField};
+
+/// GetLeaderboard에서 page_size와 limit을 주지 않았을 때 한 페이지의 플레이어 수
+pub const DEFAULT_LEADERBOARD_LIMIT: usize = 10;
+/// GetLeaderboard 한 페이지에 돌려줄 수 있는 최대 플레이어 수
+pub const MAX_LEADERBOARD_LIMIT: usize = 100;
+
+/// 플레이어 한 명의 누적 통계
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayerStats {
+    pub games_played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    /// 기권으로 진 게임 (losses에도 포함)
+    pub forfeits: u32,
+    /// 하트비트를 보내지 않아 끝난 게임
+    pub timeouts: u32,
+    /// 시간을 잰 수들에 걸린 시간의 합 (밀리초)
+    pub total_move_time_ms: u64,
+    /// 시간을 잰 수의 개수 (각 게임의 첫 수는 시작 시각을 알 수 없어 제외)
+    pub timed_moves: u32,
+    /// 마지막으로 게임을 끝낸 시각 (유닉스 초, 기록이 없으면 0)
+    pub last_seen: i64,
+}
+
+impl PlayerStats {
+    /// 수 하나를 두는 데 걸린 평균 시간 (잰 수가 없으면 0)
+    pub fn average_move_time_ms(&self) -> u64 {
+        self.total_move_time_ms.checked_div(u64::from(self.timed_moves)).unwrap_or(0)
+    }
+
+    /// 끝낸 게임 중 이긴 비율 (0 ~ 1)
+    pub fn win_rate(&self) -> f64 {
+        if self.games_played == 0 { 0.0 } else { f64::from(self.wins) / f64::from(self.games_played) }
+    }
+
+    pub fn to_proto(&self, player_id: &str) -> tictactoe::PlayerStats {
+        tictactoe::PlayerStats {
+            player_id: player_id.to_string(),
+            games_played: self.games_played,
+            wins: self.wins,
+            losses: self.losses,
+            draws: self.draws,
+            forfeits: self.forfeits,
+            timeouts: self.timeouts,
+            average_move_time_ms: self.average_move_time_ms(),
+            last_seen_unix: self.last_seen,
+        }
+    }
+
+    fn apply(&mut self, seat: &SeatResult, finished_at: i64) {
+        self.games_played += 1;
+        self.last_seen = self.last_seen.max(finished_at);
+        match seat.outcome {
+            SeatOutcome::Win => self.wins += 1,
+            SeatOutcome::Loss => self.losses += 1,
+            SeatOutcome::Forfeit => {
+                self.losses += 1;
+                self.forfeits += 1;
+            }
+            SeatOutcome::Draw => self.draws += 1,
+            SeatOutcome::TimedOut => self.timeouts += 1,
+            SeatOutcome::OpponentTimedOut => {}
+        }
+        for time in &seat.move_times {
+            self.total_move_time_ms += time.as_millis() as u64;
+            self.timed_moves += 1;
+        }
+    }
+}
+
+/// 한 자리의 게임 결과
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeatOutcome {
+    Win,
+    Loss,
+    /// 기권해서 짐
+    Forfeit,
+    Draw,
+    /// 하트비트를 보내지 않아 게임이 끝남
+    TimedOut,
+    /// 상대가 응답하지 않아 게임이 끝남 (승패 없음)
+    OpponentTimedOut,
+}
+
+/// 통계에 반영할 한 플레이어의 결과
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeatResult {
+    pub player_id: String,
+    /// 이 게임에서 쓴 이름 (기본 이름이면 비어 있음)
+    pub player_name: String,
+    pub outcome: SeatOutcome,
+    /// 이 플레이어가 둔 수마다 직전 수 이후 걸린 시간
+    pub move_times: Vec<Duration>,
+}
+
+/// 통계에 반영할 끝난 게임 (player_id가 있는 사람 자리만)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletedGame {
+    pub seats: Vec<SeatResult>,
+}
+
+impl CompletedGame {
+    /// 끝난 게임의 자리별 결과. 승패나 무승부로 끝났거나 응답 없는 플레이어 때문에 끝난 게임만 반영하고
+    /// (관리자나 서버 종료로 끝난 게임은 제외), player_id가 있는 자리가 없으면 None입니다.
+    pub fn of(game: &SharedGame) -> Option<Self> {
+        if !game.is_finished() {
+            return None;
+        }
+        let status = game.status.as_str();
+        let outcome_of = |symbol: &str| -> Option<SeatOutcome> {
+            if status == IDLE_TIMEOUT {
+                let timed_out = game.timed_out.as_deref() == Some(symbol);
+                return Some(if timed_out { SeatOutcome::TimedOut } else { SeatOutcome::OpponentTimedOut });
+            }
+            if status.starts_with("draw") {
+                return Some(SeatOutcome::Draw);
+            }
+            let winner = status.strip_suffix("_win").or_else(|| status.strip_suffix("_win_by_resignation"))?;
+            Some(match (winner == symbol, status.ends_with("_by_resignation")) {
+                (true, _) => SeatOutcome::Win,
+                (false, true) => SeatOutcome::Forfeit,
+                (false, false) => SeatOutcome::Loss,
+            })
+        };
+
+        let mut seats = Vec::new();
+        for player in [&game.player_x, &game.player_o].into_iter().flatten() {
+            if player.is_bot || player.player_id.is_empty() {
+                continue;
+            }
+            let move_times = game
+                .history
+                .windows(2)
+                .filter(|pair| pair[1].symbol == player.symbol)
+                .map(|pair| pair[1].played_at.duration_since(pair[0].played_at).unwrap_or_default())
+                .collect();
+            let name = if player.symbol == "X" { &game.player_x_name } else { &game.player_o_name };
+            let player_name = if *name == default_player_name(&player.symbol) { String::new() } else { name.clone() };
+            seats.push(SeatResult { player_id: player.player_id.clone(), player_name, outcome: outcome_of(&player.symbol)?, move_times });
+        }
+        // 같은 ID로 양쪽에 앉은 게임은 레이팅처럼 반영하지 않음
+        if seats.is_empty() || seats.len() == 2 && seats[0].player_id == seats[1].player_id {
+            return None;
+        }
+        Some(CompletedGame { seats })
+    }
+}
+
+/// 통계 파일 형식
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StatsFile {
+    #[serde(default)]
+    players: BTreeMap<String, PlayerStats>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    names: BTreeMap<String, String>,
+}
+
+/// player_id → 누적 통계 저장소
+#[derive(Debug, Default)]
+pub struct StatsBook {
+    players: BTreeMap<String, PlayerStats>,
+    names: BTreeMap<String, String>, // 플레이어가 마지막으로 쓴 이름 (기본 이름만 쓴 플레이어는 없음)
+    path: Option<PathBuf>, // 통계를 저장할 파일 (없으면 메모리에만 보관)
+}
+
+impl StatsBook {
+    /// 통계 파일을 읽어 저장소 생성 (경로가 없거나 파일이 아직 없으면 비어 있는 상태로 시작)
+    pub fn load(path: Option<&Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut book = StatsBook { players: BTreeMap::new(), names: BTreeMap::new(), path: path.map(Path::to_path_buf) };
+        let Some(path) = path else {
+            return Ok(book);
+        };
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(book),
+            Err(e) => return Err(format!("통계 파일을 읽을 수 없습니다 ({}): {}", path.display(), e).into()),
+        };
+        let file: StatsFile =
+            toml::from_str(&text).map_err(|e| format!("통계 파일 형식 오류 ({}): {}", path.display(), e))?;
+        book.players = file.players;
+        book.names = file.names;
+        info!(path = %path.display(), count = book.players.len(), "통계 파일 로드");
+        Ok(book)
+    }
+
+    /// 기록된 플레이어의 통계
+    pub fn get(&self, player_id: &str) -> Option<PlayerStats> {
+        self.players.get(player_id).copied()
+    }
+
+    /// 플레이어가 마지막으로 끝낸 게임에서 쓴 이름 (기본 이름만 썼으면 None)
+    pub fn name(&self, player_id: &str) -> Option<&str> {
+        self.names.get(player_id).map(String::as_str)
+    }
+
+    /// 끝난 게임의 모든 자리를 한꺼번에 반영하고 파일에 저장 (저장에 실패해도 메모리의 기록은 유지)
+    pub fn record(&mut self, game: &CompletedGame) {
+        let finished_at = unix_secs(SystemTime::now());
+        for seat in &game.seats {
+            self.players.entry(seat.player_id.clone()).or_default().apply(seat, finished_at);
+            if !seat.player_name.is_empty() {
+                self.names.insert(seat.player_id.clone(), seat.player_name.clone());
+            }
+        }
+        if let Err(e) = self.save() {
+            warn!(error = %e, "통계 파일 저장 실패");
+        }
+    }
+
+    /// `sort_by` 기준으로 정렬한 순위에서 `offset`번째부터 `limit`명과 전체 플레이어 수 (같으면 player_id 순).
+    /// 평균 수 시간은 빠른 순이며 잰 수가 없는 플레이어는 빼고, 레이팅은 `ratings`에서 찾습니다 (없으면 초기 레이팅).
+    pub fn leaderboard(&self, sort_by: SortField, ratings: &RatingBook, offset: usize, limit: usize) -> (Vec<(String, PlayerStats)>, usize) {
+        let mut players: Vec<(String, PlayerStats)> = self
+            .players
+            .iter()
+            .filter(|(_, stats)| sort_by != SortField::AverageMoveTime || stats.timed_moves > 0)
+            .map(|(id, stats)| (id.clone(), *stats))
+            .collect();
+        // 정렬은 안정적이므로 BTreeMap 순서(player_id)가 동점 순서가 됨
+        match sort_by {
+            SortField::Wins => players.sort_by_key(|(_, stats)| Reverse(stats.wins)),
+            SortField::GamesPlayed => players.sort_by_key(|(_, stats)| Reverse(stats.games_played)),
+            SortField::WinRate => players.sort_by(|a, b| b.1.win_rate().total_cmp(&a.1.win_rate())),
+            SortField::AverageMoveTime => players.sort_by_key(|(_, stats)| stats.average_move_time_ms()),
+            SortField::EloRating => players.sort_by_cached_key(|(id, _)| Reverse(ratings.get(id).unwrap_or_default().rating)),
+        }
+        let total = players.len();
+        (players.into_iter().skip(offset).take(limit).collect(), total)
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let file = StatsFile { players: self.players.clone(), names: self.names.clone() };
+        atomic_write(path, toml::to_string(&file)?.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// GetLeaderboard 응답 캐시 ((정렬 기준, 페이지, 페이지 크기)마다 만든 시각과 응답)
+#[derive(Debug, Default)]
+pub struct LeaderboardCache {
+    pages: HashMap<(SortField, usize, usize), (Instant, LeaderboardResponse)>,
+}
+
+impl LeaderboardCache {
+    /// `ttl` 안에 만든 응답 (`ttl`이 0이면 항상 None)
+    pub fn get(&self, key: (SortField, usize, usize), ttl: Duration) -> Option<LeaderboardResponse> {
+        let (made_at, response) = self.pages.get(&key)?;
+        (made_at.elapsed() < ttl).then(|| response.clone())
+    }
+
+    /// 응답을 넣으면서 `ttl`이 지난 응답은 버림 (`ttl`이 0이면 넣지 않음)
+    pub fn insert(&mut self, key: (SortField, usize, usize), response: LeaderboardResponse, ttl: Duration) {
+        self.pages.retain(|_, (made_at, _)| made_at.elapsed() < ttl);
+        if !ttl.is_zero() {
+            self.pages.insert(key, (Instant::now(), response));
+        }
+    }
+}
+
+/// 순위표 한 페이지 (`offset`은 페이지 첫 플레이어의 0부터 센 순위)
+pub fn leaderboard_page(stats: &StatsBook, ratings: &RatingBook, sort_by: SortField, offset: usize, limit: usize) -> LeaderboardResponse {
+    let (leaders, total) = stats.leaderboard(sort_by, ratings, offset, limit);
+    let entries = leaders
+        .iter()
+        .enumerate()
+        .map(|(i, (player_id, player))| LeaderboardEntry {
+            rank: (offset + i + 1) as i32,
+            player_id: player_id.clone(),
+            player_name: stats.name(player_id).unwrap_or_default().to_string(),
+            elo_rating: ratings.get(player_id).unwrap_or_default().rating,
+            wins: player.wins,
+            losses: player.losses,
+            draws: player.draws,
+        })
+        .collect();
+    let players = leaders.iter().map(|(player_id, player)| player.to_proto(player_id)).collect();
+    LeaderboardResponse { players, entries, total_count: total as i32 }
+}
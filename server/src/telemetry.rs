@@ -0,0 +1,114 @@
+//! OpenTelemetry 분산 추적 (`--otlp-endpoint`)
+//!
+//! tracing 스팬을 OpenTelemetry 스팬으로 바꿔 OTLP(gRPC)로 내보냅니다. 클라이언트는 요청 메타데이터에 W3C
+//! `traceparent`/`tracestate`를 실어 보내고, 서버 전체를 감싸는 [`TraceContextLayer`]가 이를 읽어 RPC마다
+//! 클라이언트 스팬의 자식인 `rpc` 스팬을 엽니다. 핸들러(`play`, `submit_move`, ...)와 게임 로직(`apply_move`,
+//! `check_outcome`), 저장소 작업(이벤트 로그, 게임 기록, 스냅샷, 레이팅, 통계) 스팬은 그 아래에 이어 붙습니다.
+//! 게임 로직과 저장소 스팬은 debug 레벨이라 내보내는 쪽에는 [`span_filter`]로 따로 거릅니다.
+
+use std::task::{Context, Poll};
+
+use opentelemetry::propagation::Extractor;
+use opentelemetry::trace::{TraceError, TracerProvider as _};
+use opentelemetry::{global, KeyValue};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{Tracer, TracerProvider};
+use opentelemetry_sdk::{runtime, Resource};
+use tonic::codegen::http::{HeaderMap, Request};
+use tonic::codegen::Service;
+use tower_layer::Layer;
+use tracing::instrument::Instrumented;
+use tracing::{info_span, Instrument, Level, Subscriber};
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::registry::LookupSpan;
+
+/// 내보내는 스팬의 service.name
+pub const SERVICE_NAME: &str = "tictactoe-server";
+
+/// W3C Trace Context 전파기를 전역으로 등록 (`traceparent`/`tracestate` 헤더를 읽고 씀)
+pub fn install_propagator() {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+}
+
+/// `endpoint`(예: `http://localhost:4317`)의 OTLP 수집기로 스팬을 모아 보내는 트레이서 제공자를 만들어
+/// 전역으로 등록하고 전파기도 등록합니다. 종료할 때 `shutdown`으로 남은 스팬을 내보내야 합니다.
+pub fn init(endpoint: &str) -> Result<TracerProvider, TraceError> {
+    let exporter = SpanExporter::builder().with_tonic().with_endpoint(endpoint).build()?;
+    let provider = TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_resource(Resource::new([KeyValue::new("service.name", SERVICE_NAME)]))
+        .build();
+    global::set_tracer_provider(provider.clone());
+    install_propagator();
+    Ok(provider)
+}
+
+/// tracing 스팬을 `provider`의 스팬으로 내보내는 레이어
+pub fn layer<S>(provider: &TracerProvider) -> OpenTelemetryLayer<S, Tracer>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    tracing_opentelemetry::layer().with_tracer(provider.tracer(SERVICE_NAME))
+}
+
+/// 내보낼 스팬: 서버의 스팬은 debug까지(게임 로직과 저장소 작업), 나머지는 info까지
+pub fn span_filter() -> Targets {
+    Targets::new().with_default(Level::INFO).with_target("server", Level::DEBUG)
+}
+
+/// 요청 헤더에서 추적 문맥을 읽는 어댑터
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
+
+/// 요청의 추적 문맥을 이어받는 레이어 (`Server::builder().layer(...)`)
+#[derive(Clone, Default)]
+pub struct TraceContextLayer;
+
+impl<S> Layer<S> for TraceContextLayer {
+    type Service = TraceContext<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TraceContext { inner }
+    }
+}
+
+/// RPC마다 클라이언트 스팬의 자식인 `rpc` 스팬 안에서 핸들러를 실행하는 서비스
+/// (`traceparent`가 없으면 새 추적을 시작)
+#[derive(Clone)]
+pub struct TraceContext<S> {
+    inner: S,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for TraceContext<S>
+where
+    S: Service<Request<ReqBody>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Instrumented<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let parent = global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(request.headers())));
+        let method = request.uri().path();
+        let span = info_span!("rpc", otel.name = %method, otel.kind = "server", rpc.system = "grpc", rpc.method = %method);
+        span.set_parent(parent);
+        // 핸들러의 스팬이 future를 만들 때 생겨도 이 스팬 아래에 붙도록 부르는 동안에도 들어가 있음
+        let future = span.in_scope(|| self.inner.call(request));
+        future.instrument(span)
+    }
+}
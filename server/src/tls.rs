@@ -0,0 +1,61 @@
+//! gRPC 서버 TLS (`--tls-cert`, `--tls-key`, `--tls-client-auth`)
+//!
+//! 인증서와 키는 PEM 파일로 받습니다. 파일이 없거나, PEM이 아니거나, 키가 인증서와 맞지 않으면
+//! 첫 연결의 핸드셰이크가 아니라 서버를 시작할 때 어느 파일이 문제인지 알려 주고 멈춥니다.
+//! `--tls-client-auth`에 CA를 주면 그 CA가 서명한 클라이언트 인증서가 있어야 접속할 수 있습니다 (mTLS).
+//! 토큰 발급 HTTP 서버(`auth_listen_addr`)와 지표 서버에는 적용하지 않습니다.
+
+use std::path::{Path, PathBuf};
+use tonic::transport::{Certificate, Identity, Server, ServerTlsConfig};
+
+/// 서버 TLS에 쓸 파일
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsFiles {
+    /// 서버 인증서 체인 (PEM)
+    pub cert: PathBuf,
+    /// 서버 개인 키 (PEM)
+    pub key: PathBuf,
+    /// 클라이언트 인증서를 검증할 CA (PEM, 있으면 클라이언트 인증서 필수)
+    pub client_ca: Option<PathBuf>,
+}
+
+impl TlsFiles {
+    /// 파일을 읽어 TLS 설정을 만들고, 키와 인증서가 맞는지까지 미리 확인
+    pub fn load(&self) -> Result<ServerTlsConfig, String> {
+        let cert = read_pem(&self.cert, "서버 인증서", "CERTIFICATE")?;
+        let key = read_pem(&self.key, "서버 개인 키", "PRIVATE KEY")?;
+        let mut config = ServerTlsConfig::new().identity(Identity::from_pem(cert, key));
+        if let Some(path) = &self.client_ca {
+            let ca = read_pem(path, "클라이언트 인증 CA", "CERTIFICATE")?;
+            config = config.client_ca_root(Certificate::from_pem(ca));
+        }
+        // rustls 설정을 한 번 만들어 보면 키가 인증서와 맞지 않는 경우도 여기서 걸림
+        Server::builder().tls_config(config.clone()).map_err(|e| {
+            format!("TLS 설정 오류 (인증서 {}, 키 {}): {}", self.cert.display(), self.key.display(), tls_error_detail(&e))
+        })?;
+        Ok(config)
+    }
+}
+
+/// PEM 파일을 읽고 `label` 블록(예: "CERTIFICATE")이 들어 있는지 확인
+fn read_pem(path: &Path, what: &str, label: &str) -> Result<Vec<u8>, String> {
+    let pem = std::fs::read(path).map_err(|e| format!("{} 파일을 읽을 수 없습니다 ({}): {}", what, path.display(), e))?;
+    let text = String::from_utf8_lossy(&pem);
+    let has_block = text.lines().any(|line| line.starts_with("-----BEGIN ") && line.trim_end().ends_with(&format!("{}-----", label)));
+    if !has_block {
+        return Err(format!("{} 파일에 PEM {} 블록이 없습니다 ({})", what, label, path.display()));
+    }
+    Ok(pem)
+}
+
+/// tonic 전송 오류의 원인까지 이어 붙인 설명 (맨 바깥 오류는 "transport error"뿐이라 원인이 필요함)
+fn tls_error_detail(error: &(dyn std::error::Error + 'static)) -> String {
+    let mut detail = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        detail.push_str(": ");
+        detail.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    detail
+}
@@ -0,0 +1,378 @@
+//! 싱글 엘리미네이션 토너먼트 (`tictactoe.TournamentService`)
+//!
+//! 대진표(`Bracket`)는 시드 순 플레이어 목록에서 만듭니다. 참가자가 2의 거듭제곱이 아니면 높은 시드부터
+//! 첫 라운드를 부전승으로 올라가고, 첫 라운드는 i번 시드와 (N-1-i)번 자리를 짝짓습니다. 다음 라운드는
+//! 이웃한 두 경기의 승자끼리 만나며, 앞 라운드의 모든 경기가 끝나야 열립니다.
+//!
+//! `TournamentManager`는 경기마다 `GameManager`에 두 플레이어만 앉을 수 있는 게임을 열고, 게임이 끝나면
+//! (`TicTacToeService::record_finish`가 알려 줌) 승자를 올립니다. 승자가 없는 결과(무승부, 관리자 종료 등)는
+//! 자리를 바꿔 새 게임으로 다시 둡니다. 잠금 순서는 토너먼트 → 게임 매니저이고 경기를 열 때 게임 액터에
+//! 명령을 보내므로, 게임 액터 안에서는 토너먼트를 잠그지 말고 태스크를 따로 띄워 알려야 합니다. 끝난 토너먼트도 대진표 조회를 위해 남겨 둡니다.
+
+use std::collections::{HashMap, HashSet};
+use std::pin::Pin;
+use std::sync::Arc;
+
+use futures::Stream;
+use tokio::sync::{mpsc, Mutex};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+use tracing::{info, warn};
+
+use crate::board::{DEFAULT_BOARD_SIZE, DEFAULT_WIN_LENGTH};
+use crate::elo::MAX_PLAYER_ID_LEN;
+use crate::error::GameError;
+use crate::manager::GameManager;
+use crate::presets::{GameOptions, DEFAULT_PRESET};
+use crate::tictactoe::tournament_service_server::{TournamentService, TournamentServiceServer};
+use crate::tictactoe::{
+    Bracket as BracketProto, BracketMatch as BracketMatchProto, BracketRound, CreateTournamentRequest, TournamentId,
+    TournamentUpdate,
+};
+
+/// 토너먼트 하나의 최대 참가자 수
+pub const MAX_TOURNAMENT_PLAYERS: usize = 64;
+
+/// 토너먼트를 지켜보는 스트림 하나의 버퍼 (대진표 변경은 경기가 끝날 때만 생김)
+const WATCH_BUFFER: usize = 32;
+
+type UpdateSender = mpsc::Sender<Result<TournamentUpdate, Status>>;
+type UpdateStream = Pin<Box<dyn Stream<Item = Result<TournamentUpdate, Status>> + Send>>;
+
+/// 대진표의 경기 하나
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BracketMatch {
+    pub player_x: String,
+    /// 부전승이면 None
+    pub player_o: Option<String>,
+    /// 경기를 두는 게임 (열기 전이나 부전승이면 빈 문자열)
+    pub game_id: String,
+    pub winner: Option<String>,
+    /// 승자 없이 끝나 자리를 바꿔 다시 연 횟수
+    pub replays: u32,
+}
+
+impl BracketMatch {
+    fn new(player_x: String, player_o: Option<String>) -> Self {
+        // 부전승은 만들 때 바로 정해짐
+        let winner = if player_o.is_none() { Some(player_x.clone()) } else { None };
+        BracketMatch { player_x, player_o, game_id: String::new(), winner, replays: 0 }
+    }
+
+    /// 게임을 열어 두어야 하는 경기 (상대가 있고 승자가 아직 없음)
+    pub fn is_pending(&self) -> bool {
+        self.player_o.is_some() && self.winner.is_none()
+    }
+
+    fn to_proto(&self, round: usize, index: usize) -> BracketMatchProto {
+        BracketMatchProto {
+            round: round as u32 + 1,
+            index: index as u32,
+            player_x: self.player_x.clone(),
+            player_o: self.player_o.clone().unwrap_or_default(),
+            game_id: self.game_id.clone(),
+            winner: self.winner.clone().unwrap_or_default(),
+            replays: self.replays,
+        }
+    }
+}
+
+/// 싱글 엘리미네이션 대진표
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bracket {
+    /// 시드 순 참가자
+    pub players: Vec<String>,
+    /// 지금까지 열린 라운드 (마지막이 진행 중인 라운드)
+    pub rounds: Vec<Vec<BracketMatch>>,
+}
+
+impl Bracket {
+    /// 참가자를 확인하고 첫 라운드를 짬 (부전승 포함)
+    pub fn new(players: Vec<String>) -> Result<Self, String> {
+        let players: Vec<String> = players.into_iter().map(|player| player.trim().to_string()).collect();
+        if players.len() < 2 || players.len() > MAX_TOURNAMENT_PLAYERS {
+            return Err(format!("토너먼트 참가자는 2~{}명이어야 합니다 ({}명).", MAX_TOURNAMENT_PLAYERS, players.len()));
+        }
+        let mut seen = HashSet::new();
+        for player in &players {
+            if player.is_empty() || player.chars().count() > MAX_PLAYER_ID_LEN {
+                return Err(format!("참가자 player_id는 1~{}자여야 합니다.", MAX_PLAYER_ID_LEN));
+            }
+            if !seen.insert(player.as_str()) {
+                return Err(format!("같은 참가자가 두 번 있습니다: {}", player));
+            }
+        }
+        let slots = players.len().next_power_of_two();
+        let first = (0..slots / 2)
+            .map(|seed| BracketMatch::new(players[seed].clone(), players.get(slots - 1 - seed).cloned()))
+            .collect();
+        Ok(Bracket { players, rounds: vec![first] })
+    }
+
+    /// 진행 중인 라운드 (0부터)
+    pub fn current_round(&self) -> usize {
+        self.rounds.len() - 1
+    }
+
+    /// 진행 중인 라운드의 모든 경기에 승자가 있는지
+    pub fn round_complete(&self) -> bool {
+        self.rounds.last().is_some_and(|round| round.iter().all(|m| m.winner.is_some()))
+    }
+
+    /// 결승까지 끝났으면 우승자
+    pub fn champion(&self) -> Option<&str> {
+        match self.rounds.last() {
+            Some(round) if round.len() == 1 => round[0].winner.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// 진행 중인 라운드가 끝났으면 이웃한 경기의 승자끼리 다음 라운드를 짬 (새 라운드를 만들었으면 true)
+    pub fn advance(&mut self) -> bool {
+        if !self.round_complete() || self.champion().is_some() {
+            return false;
+        }
+        let winners: Vec<String> = self.rounds[self.current_round()].iter().filter_map(|m| m.winner.clone()).collect();
+        let next = winners.chunks(2).map(|pair| BracketMatch::new(pair[0].clone(), pair.get(1).cloned())).collect();
+        self.rounds.push(next);
+        true
+    }
+
+    pub fn to_proto(&self, tournament_id: &str) -> BracketProto {
+        BracketProto {
+            tournament_id: tournament_id.to_string(),
+            players: self.players.clone(),
+            rounds: self
+                .rounds
+                .iter()
+                .enumerate()
+                .map(|(round, matches)| BracketRound {
+                    matches: matches.iter().enumerate().map(|(index, m)| m.to_proto(round, index)).collect(),
+                })
+                .collect(),
+            champion: self.champion().unwrap_or_default().to_string(),
+        }
+    }
+}
+
+/// 끝난 게임의 승자 심볼 (승자 없이 끝났으면 None: 무승부, 관리자 종료, 서버 종료 등)
+pub fn winning_symbol(status: &str, timed_out: Option<&str>) -> Option<&'static str> {
+    match status {
+        _ if status.starts_with("X_win") => Some("X"),
+        _ if status.starts_with("O_win") => Some("O"),
+        // 응답하지 않은 쪽의 상대가 이김
+        "idle_timeout" => match timed_out {
+            Some("X") => Some("O"),
+            Some("O") => Some("X"),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// 진행 중이거나 끝난 토너먼트 하나
+struct Tournament {
+    bracket: Bracket,
+    watchers: Vec<UpdateSender>,
+}
+
+/// 토너먼트 목록과 경기 게임 관리
+pub struct TournamentManager {
+    games: Arc<Mutex<GameManager>>,
+    tournaments: HashMap<String, Tournament>,
+    /// 경기를 두는 게임 ID → (토너먼트 ID, 라운드, 경기 순서)
+    matches: HashMap<String, (String, usize, usize)>,
+    next_id: u64,
+}
+
+impl TournamentManager {
+    pub fn new(games: Arc<Mutex<GameManager>>) -> Self {
+        TournamentManager { games, tournaments: HashMap::new(), matches: HashMap::new(), next_id: 0 }
+    }
+
+    /// 대진표를 만들고 첫 라운드 게임을 엶 (토너먼트 ID 반환)
+    pub async fn create(&mut self, players: Vec<String>) -> Result<String, GameError> {
+        let bracket = Bracket::new(players).map_err(GameError::InvalidArgument)?;
+        self.next_id += 1;
+        let tournament_id = format!("t{}", self.next_id);
+        info!(%tournament_id, players = bracket.players.len(), "토너먼트 생성");
+        self.tournaments.insert(tournament_id.clone(), Tournament { bracket, watchers: Vec::new() });
+        self.open_round(&tournament_id).await?;
+        Ok(tournament_id)
+    }
+
+    pub fn bracket(&self, tournament_id: &str) -> Option<BracketProto> {
+        self.tournaments.get(tournament_id).map(|t| t.bracket.to_proto(tournament_id))
+    }
+
+    /// 지켜보기 시작: 현재 대진표를 바로 보냄 (끝난 토너먼트면 보낸 뒤 바로 닫음)
+    pub fn watch(&mut self, tournament_id: &str) -> Result<mpsc::Receiver<Result<TournamentUpdate, Status>>, GameError> {
+        let tournament = self.tournaments.get_mut(tournament_id).ok_or(GameError::TournamentNotFound)?;
+        let (tx, rx) = mpsc::channel(WATCH_BUFFER);
+        let snapshot = update(tournament_id, &tournament.bracket, "Current bracket.".to_string());
+        // 새 채널이라 자리가 있음
+        let _ = tx.try_send(Ok(snapshot));
+        if tournament.bracket.champion().is_none() {
+            tournament.watchers.push(tx);
+        }
+        Ok(rx)
+    }
+
+    /// 경기 게임이 끝남: 승자를 올리거나 자리를 바꿔 다시 열고, 라운드가 끝났으면 다음 라운드를 엶
+    /// (토너먼트 경기가 아닌 게임은 무시)
+    pub async fn game_finished(&mut self, game_id: &str, status: &str, timed_out: Option<&str>) {
+        let Some((tournament_id, round, index)) = self.matches.remove(game_id) else {
+            return;
+        };
+        let Some(tournament) = self.tournaments.get_mut(&tournament_id) else {
+            return;
+        };
+        let slot = &mut tournament.bracket.rounds[round][index];
+        let player_o = slot.player_o.clone().unwrap_or_default();
+        let players = (slot.player_x.clone(), player_o);
+        let Some(symbol) = winning_symbol(status, timed_out) else {
+            // 자리를 바꿔 다시 둠
+            slot.player_x = players.1.clone();
+            slot.player_o = Some(players.0.clone());
+            slot.game_id.clear();
+            slot.replays += 1;
+            info!(%tournament_id, round = round + 1, index, %status, "토너먼트 경기 승자 없음, 다시 둠");
+            let message = format!("{} vs {} ended without a winner ({}); replaying with sides swapped.", players.0, players.1, status);
+            self.open_match(&tournament_id, round, index).await;
+            self.broadcast(&tournament_id, message);
+            return;
+        };
+        let (winner, loser) = if symbol == "X" { players } else { (players.1, players.0) };
+        slot.winner = Some(winner.clone());
+        info!(%tournament_id, round = round + 1, index, %winner, "토너먼트 경기 끝");
+        self.broadcast(&tournament_id, format!("{} beat {} in round {}.", winner, loser, round + 1));
+
+        let tournament = self.tournaments.get_mut(&tournament_id).expect("방금 찾은 토너먼트");
+        if let Some(champion) = tournament.bracket.champion().map(str::to_string) {
+            info!(%tournament_id, %champion, "토너먼트 끝");
+            self.broadcast(&tournament_id, format!("{} won the tournament!", champion));
+            // 끝난 토너먼트의 스트림은 닫음
+            if let Some(tournament) = self.tournaments.get_mut(&tournament_id) {
+                tournament.watchers.clear();
+            }
+        } else if tournament.bracket.advance() {
+            if let Err(e) = self.open_round(&tournament_id).await {
+                warn!(%tournament_id, error = %e, "다음 라운드 게임을 열지 못함");
+            }
+        }
+    }
+
+    /// 진행 중인 라운드의 경기 게임을 모두 열고 알림
+    async fn open_round(&mut self, tournament_id: &str) -> Result<(), GameError> {
+        let bracket = &self.tournaments[tournament_id].bracket;
+        let round = bracket.current_round();
+        let pending: Vec<usize> = (0..bracket.rounds[round].len()).filter(|&i| bracket.rounds[round][i].is_pending()).collect();
+        let byes: Vec<String> = bracket.rounds[round].iter().filter(|m| m.player_o.is_none()).map(|m| m.player_x.clone()).collect();
+        let mut failed = None;
+        for index in pending {
+            if !self.open_match(tournament_id, round, index).await {
+                failed = Some(GameError::TooManyGames);
+            }
+        }
+        let mut message = format!("Round {} started.", round + 1);
+        if !byes.is_empty() {
+            message.push_str(&format!(" Byes: {}.", byes.join(", ")));
+        }
+        self.broadcast(tournament_id, message);
+        failed.map_or(Ok(()), Err)
+    }
+
+    /// 경기 하나의 게임을 열어 두 자리를 경기 플레이어로 정함 (최대 게임 수에 도달했으면 false)
+    async fn open_match(&mut self, tournament_id: &str, round: usize, index: usize) -> bool {
+        let slot = &self.tournaments[tournament_id].bracket.rounds[round][index];
+        let seats = [slot.player_x.clone(), slot.player_o.clone().unwrap_or_default()];
+        let game = {
+            let mut manager = self.games.lock().await;
+            manager.create_preset_game(DEFAULT_PRESET, GameOptions::default(), DEFAULT_BOARD_SIZE, DEFAULT_WIN_LENGTH)
+        };
+        let Some(game) = game else {
+            warn!(%tournament_id, round = round + 1, index, "최대 게임 수에 도달해 토너먼트 경기를 열지 못함");
+            return false;
+        };
+        game.with(move |game| game.reserved = Some(seats)).await;
+        let game_id = game.game_id().to_string();
+        info!(%tournament_id, round = round + 1, index, %game_id, "토너먼트 경기 게임 생성");
+        if let Some(tournament) = self.tournaments.get_mut(tournament_id) {
+            tournament.bracket.rounds[round][index].game_id = game_id.clone();
+        }
+        self.matches.insert(game_id, (tournament_id.to_string(), round, index));
+        true
+    }
+
+    /// 지켜보는 모든 스트림에 대진표와 `message`를 보냄 (닫힌 스트림은 정리)
+    fn broadcast(&mut self, tournament_id: &str, message: String) {
+        let Some(tournament) = self.tournaments.get_mut(tournament_id) else {
+            return;
+        };
+        let update = update(tournament_id, &tournament.bracket, message);
+        tournament.watchers.retain(|tx| match tx.try_send(Ok(update.clone())) {
+            Ok(()) => true,
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                warn!(%tournament_id, "토너먼트 스트림 버퍼가 가득 차 업데이트를 건너뜀");
+                true
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => false,
+        });
+    }
+}
+
+/// 대진표 변경 알림
+fn update(tournament_id: &str, bracket: &Bracket, message: String) -> TournamentUpdate {
+    let round = bracket.current_round();
+    let upcoming = bracket.rounds[round]
+        .iter()
+        .enumerate()
+        .filter(|(_, m)| m.is_pending() && !m.game_id.is_empty())
+        .map(|(index, m)| m.to_proto(round, index))
+        .collect();
+    TournamentUpdate {
+        bracket: Some(bracket.to_proto(tournament_id)),
+        message,
+        upcoming,
+        champion: bracket.champion().unwrap_or_default().to_string(),
+    }
+}
+
+/// 토너먼트 gRPC 서비스 (게임 서비스와 같은 게임 매니저를 씀)
+#[derive(Clone)]
+pub struct TicTacToeTournaments {
+    tournaments: Arc<Mutex<TournamentManager>>,
+}
+
+impl TicTacToeTournaments {
+    pub fn new(tournaments: Arc<Mutex<TournamentManager>>) -> Self {
+        TicTacToeTournaments { tournaments }
+    }
+
+    pub fn into_server(self) -> TournamentServiceServer<Self> {
+        TournamentServiceServer::new(self)
+    }
+}
+
+#[tonic::async_trait]
+impl TournamentService for TicTacToeTournaments {
+    type WatchTournamentStream = UpdateStream;
+
+    async fn create_tournament(&self, request: Request<CreateTournamentRequest>) -> Result<Response<TournamentId>, Status> {
+        let players = request.into_inner().players;
+        let tournament_id = self.tournaments.lock().await.create(players).await?;
+        Ok(Response::new(TournamentId { tournament_id }))
+    }
+
+    async fn get_bracket(&self, request: Request<TournamentId>) -> Result<Response<BracketProto>, Status> {
+        let tournament_id = request.into_inner().tournament_id;
+        let bracket = self.tournaments.lock().await.bracket(&tournament_id);
+        Ok(Response::new(bracket.ok_or(GameError::TournamentNotFound)?))
+    }
+
+    async fn watch_tournament(&self, request: Request<TournamentId>) -> Result<Response<Self::WatchTournamentStream>, Status> {
+        let tournament_id = request.into_inner().tournament_id;
+        let rx = self.tournaments.lock().await.watch(&tournament_id)?;
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+}
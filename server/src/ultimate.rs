@@ -0,0 +1,92 @@
+//! 얼티밋 틱택토 규칙 (`GameMode::Ultimate`)
+//!
+//! 보드는 클래식과 같은 행 우선 순서의 9×9 칸 목록이며, 3×3 작은 보드 아홉 개로 나눠 봅니다.
+//! 작은 보드 `b`(0-8, 행 우선)의 칸 `c`(0-8, 행 우선)는 보드의 (행 (b/3)*3 + c/3, 열 (b%3)*3 + c%3) 칸입니다.
+//! 작은 보드와 큰 보드의 줄 판정은 `board`의 판정을 그대로 씁니다.
+
+use crate::board;
+use crate::error::MoveError;
+
+/// 얼티밋 보드 한 변의 칸 수 (작은 보드 3개 × 3칸)
+pub const ULTIMATE_BOARD_SIZE: usize = 9;
+/// 작은 보드와 큰 보드에서 이기기 위해 이어야 하는 수
+pub const ULTIMATE_WIN_LENGTH: usize = 3;
+/// 작은 보드 수 (= 작은 보드 하나의 칸 수)
+pub const SUB_BOARDS: usize = 9;
+/// 가득 찼지만 승부가 나지 않은 작은 보드를 큰 보드에 표시하는 값
+pub const SUB_BOARD_DRAW: &str = "draw";
+
+/// 작은 보드 `sub_board`의 칸 `cell`에 해당하는 보드 칸 번호
+pub fn position(sub_board: usize, cell: usize) -> usize {
+    let row = sub_board / 3 * 3 + cell / 3;
+    let col = sub_board % 3 * 3 + cell % 3;
+    row * ULTIMATE_BOARD_SIZE + col
+}
+
+/// 보드 칸 번호를 (작은 보드, 작은 보드 안의 칸)으로
+pub fn split(pos: usize) -> (usize, usize) {
+    let (row, col) = (pos / ULTIMATE_BOARD_SIZE, pos % ULTIMATE_BOARD_SIZE);
+    (row / 3 * 3 + col / 3, row % 3 * 3 + col % 3)
+}
+
+/// 작은 보드 하나의 칸 (행 우선 순서)
+pub fn sub_board(board: &[String], index: usize) -> Vec<String> {
+    (0..SUB_BOARDS).map(|cell| board[position(index, cell)].clone()).collect()
+}
+
+/// 작은 보드의 결과: 줄을 이은 심볼, 가득 찼으면 `SUB_BOARD_DRAW`, 아직 둘 수 있으면 빈 문자열
+pub fn sub_board_result(board: &[String], index: usize) -> String {
+    let cells = sub_board(board, index);
+    if let Some(winner) = board::winner(&cells, 3, ULTIMATE_WIN_LENGTH) {
+        return winner;
+    }
+    if cells.iter().all(|cell| !cell.is_empty()) {
+        return SUB_BOARD_DRAW.to_string();
+    }
+    String::new()
+}
+
+/// 큰 보드: 작은 보드마다의 결과 (`sub_board_result`)
+pub fn macro_board(board: &[String]) -> Vec<String> {
+    (0..SUB_BOARDS).map(|index| sub_board_result(board, index)).collect()
+}
+
+/// `last_move` 다음에 둬야 하는 작은 보드 (첫 수이거나 가리킨 작은 보드가 끝났으면 None: 아무 데나 둘 수 있음)
+pub fn forced_board(board: &[String], last_move: Option<usize>) -> Option<usize> {
+    let (_, target) = split(last_move?);
+    sub_board_result(board, target).is_empty().then_some(target)
+}
+
+/// 클래식 검사(차례, 보드 안의 빈 칸)를 통과한 수에 얼티밋 규칙 적용: 승부가 난 작은 보드에는 둘 수 없고,
+/// 작은 보드가 정해져 있으면 그 안에만 둘 수 있음
+pub fn check_move(board: &[String], last_move: Option<usize>, pos: usize) -> Result<(), MoveError> {
+    let (target, _) = split(pos);
+    if !sub_board_result(board, target).is_empty() {
+        return Err(MoveError::SubBoardClosed);
+    }
+    match forced_board(board, last_move) {
+        Some(forced) if forced != target => Err(MoveError::WrongSubBoard),
+        _ => Ok(()),
+    }
+}
+
+/// 둘 수 있는 칸 (보드 칸 번호 오름차순)
+pub fn legal_moves(board: &[String], last_move: Option<usize>) -> Vec<usize> {
+    (0..board.len())
+        .filter(|&pos| board[pos].is_empty() && check_move(board, last_move, pos).is_ok())
+        .collect()
+}
+
+/// 큰 보드에서 완성된 줄의 작은 보드 번호 (무승부로 끝난 작은 보드는 누구의 줄에도 들지 않음)
+pub fn winning_line(board: &[String]) -> Option<Vec<usize>> {
+    let owners: Vec<String> = macro_board(board)
+        .into_iter()
+        .map(|result| if result == SUB_BOARD_DRAW { String::new() } else { result })
+        .collect();
+    board::winning_line(&owners, 3, ULTIMATE_WIN_LENGTH)
+}
+
+/// 모든 작은 보드의 승부가 나서 더 둘 곳이 없는지 (큰 보드에 줄이 없으면 무승부)
+pub fn all_decided(board: &[String]) -> bool {
+    (0..SUB_BOARDS).all(|index| !sub_board_result(board, index).is_empty())
+}
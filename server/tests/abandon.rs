@@ -0,0 +1,80 @@
+mod scenario;
+
+use std::time::Duration;
+
+use scenario::{eq, Scenario};
+use server::config::Config;
+use server::tictactoe::EndReason;
+use tonic::Code;
+
+fn config(abandon_timeout_ms: u64) -> Config {
+    Config { abandon_timeout_ms, ..Config::default() }
+}
+
+#[test]
+fn game_stalled_after_a_move_is_abandoned() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .spectator("carol", "alice")
+        .move_("alice", 4)
+        .expect_status("bob", eq("ongoing"))
+        .advance(Duration::from_millis(600))
+        .expect("alice", |s| s.status == "abandoned" && s.info_message == "No moves were made for too long. The game was abandoned.")
+        .expect_status("bob", eq("abandoned"))
+        .expect_status("carol", eq("abandoned"))
+        .expect_end("alice", Code::DeadlineExceeded, EndReason::Inactive)
+        .expect_end("bob", Code::DeadlineExceeded, EndReason::Inactive)
+        .list_games(|r| r.games.is_empty())
+        .metrics(|text| text.contains(r#"ttt_games_finished_total{outcome="abandoned"} 1"#))
+        .run(config(500));
+}
+
+#[test]
+fn each_move_resets_the_abandon_timer() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .expect_status("bob", eq("ongoing"))
+        .advance(Duration::from_millis(400))
+        .move_("alice", 0)
+        .expect("bob", |s| s.status == "ongoing" && s.board[0] == "X")
+        .advance(Duration::from_millis(400))
+        .move_("bob", 4)
+        .expect("alice", |s| s.status == "ongoing" && s.board[4] == "O")
+        .advance(Duration::from_millis(400))
+        .move_("alice", 8)
+        .expect("bob", |s| s.status == "ongoing" && s.board[8] == "X")
+        .run(config(500));
+}
+
+#[test]
+fn finished_game_is_never_abandoned() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .move_("alice", 0)
+        .move_("bob", 3)
+        .move_("alice", 1)
+        .move_("bob", 4)
+        .move_("alice", 2)
+        .expect_status("bob", eq("X_win"))
+        .advance(Duration::from_secs(2))
+        .metrics(|text| {
+            text.contains(r#"ttt_games_finished_total{outcome="X_win"} 1"#)
+                && text.contains(r#"ttt_games_finished_total{outcome="abandoned"} 0"#)
+        })
+        .run(config(500));
+}
+
+#[test]
+fn abandon_timeout_can_be_turned_off() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .expect_status("bob", eq("ongoing"))
+        .advance(Duration::from_secs(5))
+        .move_("alice", 0)
+        .expect("bob", |s| s.status == "ongoing" && s.board[0] == "X")
+        .run(config(0));
+}
@@ -0,0 +1,61 @@
+mod scenario;
+
+use std::time::Duration;
+
+use scenario::Captured;
+use server::access_log::AccessLogLayer;
+use server::config::Config;
+use server::service::TicTacToeService;
+use server::tictactoe::tic_tac_toe_client::TicTacToeClient;
+use server::tictactoe::{CreateGameRequest, GameStateRequest, SpectateRequest};
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::{Channel, Server};
+use tonic::Code;
+
+/// 접속 주소(peer)가 남는지 보려고 시나리오의 메모리 연결 대신 TCP로 띄움
+async fn start(layer: AccessLogLayer) -> TicTacToeClient<Channel> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let service = TicTacToeService::new(Config::default());
+    let server = Server::builder().layer(layer).add_service(service.into_server());
+    tokio::spawn(server.serve_with_incoming(TcpListenerStream::new(listener)));
+    let channel = Channel::from_shared(format!("http://{}", addr)).unwrap().connect().await.unwrap();
+    TicTacToeClient::new(channel)
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn unary_and_streaming_calls_are_logged() {
+    let (captured, _guard) = Captured::install();
+    let mut client = start(AccessLogLayer::new()).await;
+
+    let missing = client.get_game_state(GameStateRequest { game_id: "999".into(), ..GameStateRequest::default() }).await.unwrap_err();
+    assert_eq!(missing.code(), Code::NotFound);
+    let line = captured.wait_for("/tictactoe.TicTacToe/GetGameState").await;
+    assert!(line.contains("access_log") && line.contains("status=NotFound"), "{}", line);
+    assert!(line.contains("peer=127.0.0.1:") && line.contains("duration_ms="), "{}", line);
+    assert!(!line.contains("frames="), "{}", line);
+
+    let game_id = client.create_game(CreateGameRequest::default()).await.unwrap().into_inner().game_id;
+    let mut updates = client.spectate(SpectateRequest { game_id }).await.unwrap().into_inner();
+    updates.message().await.unwrap().unwrap();
+    let started = captured.wait_for("access_log: 스트림 시작").await;
+    assert!(started.contains("/tictactoe.TicTacToe/Spectate"), "{}", started);
+    assert!(!captured.lines().iter().any(|line| line.contains("access_log: 스트림 끝")));
+
+    // 관전자가 끊으면 스트림이 끝난 줄이 보낸 상태 수와 함께 남음
+    drop(updates);
+    let ended = captured.wait_for("access_log: 스트림 끝").await;
+    assert!(ended.contains("/tictactoe.TicTacToe/Spectate") && ended.contains("frames="), "{}", ended);
+    assert!(ended.contains("status=cancelled") && !ended.contains("frames=0"), "{}", ended);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn disabled_layer_logs_nothing() {
+    let (captured, _guard) = Captured::install();
+    let mut client = start(AccessLogLayer::disabled()).await;
+
+    client.create_game(CreateGameRequest::default()).await.unwrap();
+    assert!(client.get_game_state(GameStateRequest { game_id: "999".into(), ..GameStateRequest::default() }).await.is_err());
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!captured.lines().iter().any(|line| line.contains("access_log")), "{:#?}", captured.lines());
+}
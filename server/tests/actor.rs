@@ -0,0 +1,152 @@
+use std::sync::{Arc, Mutex};
+
+use server::actor::{GameHooks, GameHandle};
+use server::error::MoveError;
+use server::game::SharedGame;
+use server::tictactoe::{GameState, Join, Move};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use tonic::{Code, Status};
+
+/// 두 자리가 모두 찬 3×3 게임의 액터, 플레이어 업데이트 수신 측, X와 O의 연결 번호
+async fn seated_game() -> (GameHandle, mpsc::Receiver<Result<GameState, Status>>, u64, u64) {
+    let game = GameHandle::spawn(SharedGame::new("1".into(), 3, 3));
+    let (tx, rx) = mpsc::channel(64);
+    let (_, x) = game.join(Join::default(), tx.clone(), None).await.unwrap();
+    let (_, o) = game.join(Join::default(), tx, None).await.unwrap();
+    (game, rx, x, o)
+}
+
+fn at(position: i32) -> Move {
+    Move { position, ..Move::default() }
+}
+
+/// 끝난 판의 상태를 모아 두는 훅
+#[derive(Clone, Default)]
+struct Finished(Arc<Mutex<Vec<String>>>);
+
+impl GameHooks for Finished {
+    fn finished(&self, game: &SharedGame) {
+        if game.is_finished() {
+            self.0.lock().unwrap().push(game.status.clone());
+        }
+    }
+}
+
+/// 모든 수를 거부하는 훅
+struct Throttled;
+
+impl GameHooks for Throttled {
+    fn admit_move(&self, _game: &mut SharedGame, _symbol: &str) -> Result<(), MoveError> {
+        Err(MoveError::RateLimited)
+    }
+}
+
+#[tokio::test]
+async fn commands_are_applied_in_the_order_they_were_sent() {
+    let (game, _rx, _, _) = seated_game().await;
+    // 같은 칸을 노리는 두 수: 먼저 보낸 X의 수가 두어지고 O의 수는 차 있는 칸이라 거부됨
+    let (x, o) = tokio::join!(game.play_move("X", at(4), ()), game.play_move("O", at(4), ()));
+    assert!(x.is_ok());
+    assert_eq!(o.unwrap_err(), MoveError::CellOccupied);
+
+    // 차례대로 보낸 두 수는 보낸 순서대로 두어짐 (X의 수가 먼저 처리됐다면 자기 차례가 아니라 거부됐을 것)
+    let (o, x) = tokio::join!(game.play_move("O", at(0), ()), game.play_move("X", at(8), ()));
+    assert!(o.is_ok() && x.is_ok());
+    let state = game.snapshot().await;
+    assert_eq!((state.board[0].as_str(), state.board[4].as_str(), state.board[8].as_str()), ("O", "X", "X"));
+    assert_eq!(state.next_player, "O");
+}
+
+#[tokio::test]
+async fn broadcasts_from_the_actor_reach_players_and_spectators() {
+    let (game, mut players, _, _) = seated_game().await;
+    while players.try_recv().is_ok() {}
+    let (tx, mut spectator) = mpsc::channel(64);
+    game.with(move |game| game.add_spectator(tx, 0)).await;
+    while spectator.try_recv().is_ok() {}
+
+    game.play_move("X", at(0), ()).await.unwrap();
+    assert_eq!(spectator.recv().await.unwrap().unwrap().board[0], "X");
+    assert_eq!(players.recv().await.unwrap().unwrap().board[0], "X");
+}
+
+#[tokio::test]
+async fn a_winning_sequence_is_reported_once_and_closes_the_board() {
+    let (game, _rx, _, _) = seated_game().await;
+    let finished = Finished::default();
+    for (symbol, pos) in [("X", 0), ("O", 3), ("X", 1), ("O", 4), ("X", 2)] {
+        game.play_move(symbol, at(pos), finished.clone()).await.unwrap();
+    }
+    assert_eq!(*finished.0.lock().unwrap(), ["X_win"]);
+    assert_eq!(game.play_move("O", at(5), finished.clone()).await, Err(MoveError::GameNotOngoing));
+    assert_eq!(finished.0.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn a_move_the_hooks_refuse_is_not_applied() {
+    let (game, _rx, _, _) = seated_game().await;
+    assert_eq!(game.play_move("X", at(0), Throttled).await, Err(MoveError::RateLimited));
+    let state = game.snapshot().await;
+    assert_eq!((state.board[0].as_str(), state.next_player.as_str()), ("", "X"));
+}
+
+#[tokio::test]
+async fn resigning_ends_only_an_ongoing_game() {
+    let (game, _rx, _, _) = seated_game().await;
+    let finished = Finished::default();
+    game.resign("O", finished.clone()).await.unwrap();
+    assert_eq!(*finished.0.lock().unwrap(), ["X_win_by_resignation"]);
+    assert_eq!(game.resign("X", ()).await, Err(MoveError::GameNotOngoing));
+    assert_eq!(game.snapshot().await.status, "X_win_by_resignation");
+}
+
+#[tokio::test]
+async fn a_tick_forfeits_only_a_turn_that_is_still_running() {
+    let (game, _rx, _, _) = seated_game().await;
+    // 그 사이 수가 와서 취소된 차례의 시간 초과는 무시됨
+    let answered = CancellationToken::new();
+    answered.cancel();
+    assert!(!game.tick("X", answered, ()).await);
+    assert_eq!(game.snapshot().await.status, "ongoing");
+
+    let finished = Finished::default();
+    assert!(game.tick("X", CancellationToken::new(), finished.clone()).await);
+    assert_eq!(*finished.0.lock().unwrap(), ["O_win_by_resignation"]);
+}
+
+#[tokio::test]
+async fn disconnect_applies_only_to_the_current_connection() {
+    let (game, mut rx, x, o) = seated_game().await;
+    assert_eq!(game.disconnect("O", x).await, None);
+    while rx.try_recv().is_ok() {}
+
+    let departure = game.disconnect("O", o).await.unwrap();
+    assert!(!departure.finished);
+    // 남은 X에게 O의 접속이 끊겼음을 알림
+    assert!(rx.recv().await.unwrap().is_ok());
+    assert!(game.with(move |game| game.abandoned("O", &departure)).await);
+}
+
+#[tokio::test]
+async fn a_panicking_command_resets_the_game() {
+    let (game, mut rx, _, _) = seated_game().await;
+    game.play_move("X", at(0), ()).await.unwrap();
+    while rx.try_recv().is_ok() {}
+
+    let doomed = game.clone();
+    let failed = tokio::spawn(async move { doomed.with(|_| panic!("broken job")).await }).await;
+    assert!(failed.is_err());
+
+    // 반쯤 바뀌었을 수 있는 게임은 스트림을 INTERNAL로 끝내고 빈 게임으로 되돌림
+    let ended = rx.recv().await.unwrap().unwrap_err();
+    assert_eq!(ended.code(), Code::Internal);
+    let state = game.snapshot().await;
+    assert_eq!(state.status, "waiting");
+    assert!(state.board.iter().all(|cell| cell.is_empty()));
+
+    // 액터는 계속 명령을 받으므로 새 플레이어가 앉을 수 있음
+    let (tx, _rx2) = mpsc::channel(64);
+    assert!(game.join(Join::default(), tx, None).await.is_ok());
+    assert_eq!(game.game_id(), "1");
+}
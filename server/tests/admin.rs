@@ -0,0 +1,164 @@
+mod scenario;
+
+use scenario::{connect, start_server, Scenario};
+use server::config::{Config, ADMIN_TOKEN_ENV};
+use server::service::TicTacToeService;
+use server::tictactoe::admin_service_client::AdminServiceClient;
+use server::tictactoe::tic_tac_toe_client::TicTacToeClient;
+use server::tictactoe::{
+    CreateGameRequest, EndReason, ForceEndGameRequest, GameDetailRequest, KickPlayerRequest, ListAllGamesRequest,
+    ResetGameRequest, ServerStatsRequest,
+};
+use tonic::transport::Channel;
+use tonic::{Code, Request};
+
+const TOKEN: &str = "operator-token";
+
+fn admin_config() -> Config {
+    Config { admin_token: Some(TOKEN.to_string()), ..Config::default() }
+}
+
+fn with_token<T>(message: T, token: &str) -> Request<T> {
+    let mut request = Request::new(message);
+    request.metadata_mut().insert("x-admin-token", token.parse().unwrap());
+    request
+}
+
+async fn start(config: Config) -> (AdminServiceClient<Channel>, TicTacToeClient<Channel>) {
+    let conn_tx = start_server(TicTacToeService::new(config));
+    let channel = connect(&conn_tx).await;
+    (AdminServiceClient::new(channel.clone()), TicTacToeClient::new(channel))
+}
+
+async fn create_game(game: &mut TicTacToeClient<Channel>) -> String {
+    game.create_game(CreateGameRequest::default()).await.unwrap().into_inner().game_id
+}
+
+#[tokio::test]
+async fn admin_rpcs_require_the_admin_token() {
+    let (mut admin, _) = start(admin_config()).await;
+    let denied = admin.get_server_stats(ServerStatsRequest {}).await.unwrap_err();
+    assert_eq!(denied.code(), Code::Unauthenticated);
+    let denied = admin.list_all_games(with_token(ListAllGamesRequest {}, "wrong")).await.unwrap_err();
+    assert_eq!(denied.code(), Code::Unauthenticated);
+    assert!(admin.get_server_stats(with_token(ServerStatsRequest {}, TOKEN)).await.is_ok());
+
+    let (mut disabled, _) = start(Config::default()).await;
+    let denied = disabled.get_server_stats(with_token(ServerStatsRequest {}, TOKEN)).await.unwrap_err();
+    assert_eq!(denied.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn lists_and_inspects_every_game() {
+    let (mut admin, mut game) = start(admin_config()).await;
+    let first = create_game(&mut game).await;
+    let private = game.create_private_game(CreateGameRequest::default()).await.unwrap().into_inner();
+
+    let games = admin.list_all_games(with_token(ListAllGamesRequest {}, TOKEN)).await.unwrap().into_inner().games;
+    let ids: Vec<&str> = games.iter().map(|g| g.game_id.as_str()).collect();
+    assert_eq!(ids, [first.as_str(), private.game_id.as_str()]);
+
+    let request = with_token(GameDetailRequest { game_id: private.game_id.clone() }, TOKEN);
+    let detail = admin.get_game_detail(request).await.unwrap().into_inner();
+    assert_eq!((detail.status.as_str(), detail.invite_only), ("waiting", true));
+    assert_eq!(detail.invite_code, private.invite_code);
+    assert_eq!(detail.board.len(), 9);
+    assert!(detail.seats.is_empty());
+    assert!(detail.last_activity_unix >= detail.created_at_unix);
+
+    let missing = admin.get_game_detail(with_token(GameDetailRequest { game_id: "99".into() }, TOKEN)).await.unwrap_err();
+    assert_eq!(missing.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn server_stats_track_total_active_and_peak_games() {
+    let (mut admin, mut game) = start(admin_config()).await;
+    let first = create_game(&mut game).await;
+    create_game(&mut game).await;
+    create_game(&mut game).await;
+    let request = with_token(ForceEndGameRequest { game_id: first, reason: String::new() }, TOKEN);
+    assert_eq!(admin.force_end_game(request).await.unwrap().into_inner().status, "admin_terminated");
+    create_game(&mut game).await;
+
+    let stats = admin.get_server_stats(with_token(ServerStatsRequest {}, TOKEN)).await.unwrap().into_inner();
+    assert_eq!((stats.total_games, stats.active_games, stats.peak_games), (4, 3, 3));
+}
+
+#[tokio::test]
+async fn kick_rejects_bad_symbols_and_empty_seats() {
+    let (mut admin, mut game) = start(admin_config()).await;
+    let game_id = create_game(&mut game).await;
+
+    let request = with_token(KickPlayerRequest { game_id: game_id.clone(), symbol: "Z".into() }, TOKEN);
+    assert_eq!(admin.kick_player(request).await.unwrap_err().code(), Code::InvalidArgument);
+    let request = with_token(KickPlayerRequest { game_id, symbol: "X".into() }, TOKEN);
+    assert_eq!(admin.kick_player(request).await.unwrap_err().code(), Code::FailedPrecondition);
+}
+
+#[test]
+fn force_end_notifies_players_and_removes_the_game() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .spectator("carol", "alice")
+        .move_("alice", 4)
+        .force_end("alice", "server maintenance")
+        .expect_state(|s| s.status == "admin_terminated" && s.info_message == "server maintenance" && s.board[4] == "X")
+        .expect_closed("alice")
+        .expect_closed("bob")
+        .expect_closed("carol")
+        .list_games(|r| r.games.is_empty())
+        .run(admin_config());
+}
+
+#[test]
+fn kicked_player_forfeits_and_cannot_resume_the_seat() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .kick("bob")
+        .expect_end("bob", Code::Aborted, EndReason::Kicked)
+        .expect("alice", |s| s.info_message.contains("Player O was removed") && s.status == "X_win_by_resignation")
+        .reconnect("bob")
+        .expect_error("bob", Code::NotFound)
+        .run(admin_config());
+}
+
+#[test]
+fn reset_clears_the_board_and_keeps_the_seats() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .move_("alice", 4)
+        .move_("bob", 0)
+        .reset_game("alice", "")
+        .expect_state(|s| {
+            s.status == "ongoing"
+                && s.board.iter().all(String::is_empty)
+                && s.next_player == "X"
+                && s.info_message == "This game was reset by a server administrator."
+        })
+        .move_("alice", 0)
+        .expect_state(|s| s.board[0] == "X" && s.next_player == "O")
+        .run(admin_config());
+}
+
+#[tokio::test]
+async fn reset_rejects_unknown_games() {
+    let (mut admin, _) = start(admin_config()).await;
+    let request = with_token(ResetGameRequest { game_id: "99".into(), reason: String::new() }, TOKEN);
+    assert_eq!(admin.reset_game(request).await.unwrap_err().code(), Code::NotFound);
+    let denied = admin.reset_game(ResetGameRequest { game_id: "99".into(), reason: String::new() }).await.unwrap_err();
+    assert_eq!(denied.code(), Code::Unauthenticated);
+}
+
+#[test]
+fn admin_token_can_come_from_the_environment() {
+    let path = std::env::temp_dir().join(format!("ttt-admin-env-{}.toml", std::process::id()));
+    std::fs::write(&path, "listen_addr = \"127.0.0.1:50051\"\n").unwrap();
+    std::env::set_var(ADMIN_TOKEN_ENV, "from-env");
+    let config = Config::load(Some(&path)).unwrap();
+    std::env::remove_var(ADMIN_TOKEN_ENV);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(config.admin_token.as_deref(), Some("from-env"));
+}
@@ -0,0 +1,136 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::{Request as HttpRequest, StatusCode};
+use axum::Router;
+use serde_json::{json, Value};
+use server::api_http;
+use server::auth::{AuthInterceptor, TokenKeys};
+use server::config::Config;
+use server::service::TicTacToeService;
+use server::tictactoe::play_request::Action;
+use server::tictactoe::tic_tac_toe_client::TicTacToeClient;
+use server::tictactoe::{GameState, Join, PlayRequest};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tonic::transport::{Channel, Server};
+use tonic::{Code, Streaming};
+use tower::ServiceExt;
+
+/// gRPC 서버를 띄우고 같은 서비스로 만든 HTTP 라우터와 gRPC 채널을 반환
+async fn start() -> (Router, Channel) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let service = TicTacToeService::new(Config::default());
+    let router = api_http::router(service.clone(), AuthInterceptor::disabled());
+    tokio::spawn(Server::builder().add_service(service.into_server()).serve_with_incoming(TcpListenerStream::new(listener)));
+    let channel = Channel::from_shared(format!("http://{}", addr)).unwrap().connect().await.unwrap();
+    (router, channel)
+}
+
+async fn join(channel: Channel, game_id: &str) -> (GameState, Streaming<GameState>, mpsc::Sender<PlayRequest>) {
+    let (tx, rx) = mpsc::channel(8);
+    let join = Join { game_id: game_id.to_string(), ..Join::default() };
+    tx.send(PlayRequest { action: Some(Action::Join(join)) }).await.unwrap();
+    let mut updates = TicTacToeClient::new(channel).play(ReceiverStream::new(rx)).await.unwrap().into_inner();
+    let first = updates.message().await.unwrap().unwrap();
+    (first, updates, tx)
+}
+
+/// 요청을 보내 HTTP 상태와 JSON 본문을 받음
+async fn call(router: &Router, request: HttpRequest<Body>) -> (StatusCode, Value) {
+    let response = router.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+fn get(uri: &str) -> HttpRequest<Body> {
+    HttpRequest::get(uri).body(Body::empty()).unwrap()
+}
+
+fn post_move(game_id: &str, session_token: &str, position: i32) -> HttpRequest<Body> {
+    let body = json!({ "session_token": session_token, "move": { "position": position } });
+    HttpRequest::post(format!("/games/{}/moves", game_id))
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn http_routes_share_games_with_grpc_players() {
+    let (router, channel) = start().await;
+    let (x, mut x_updates, _x_tx) = join(channel.clone(), "").await;
+    let game_id = x.game_id.clone();
+    let (o, _o_updates, _o_tx) = join(channel, &game_id).await;
+
+    let (status, games) = call(&router, get("/games")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(games["games"][0]["game_id"], game_id);
+    assert_eq!(games["games"][0]["player_count"], 2);
+
+    // HTTP로 둔 수는 gRPC 스트림에도 전달됨
+    let (status, state) = call(&router, post_move(&game_id, &x.session_token, 4)).await;
+    assert_eq!(status, StatusCode::OK, "{}", state);
+    assert_eq!((state["board"][4].as_str(), state["your_symbol"].as_str(), state["next_player"].as_str()), (Some("X"), Some("X"), Some("O")));
+    let seen = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let update = x_updates.message().await.unwrap().unwrap();
+            if update.board[4] == "X" {
+                return update;
+            }
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(seen.next_player, "O");
+
+    // 거부된 수는 gRPC 상태 코드에 맞는 HTTP 상태로
+    let (status, error) = call(&router, post_move(&game_id, &o.session_token, 4)).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(error["code"], Code::AlreadyExists as i32);
+    let (status, _) = call(&router, post_move(&game_id, "not-a-token", 0)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, error) = call(&router, post_move("999", &o.session_token, 0)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(error["message"], "게임을 찾을 수 없습니다.");
+    let (status, _) = call(&router, post_move(&game_id, &o.session_token, 0)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, state) = call(&router, get(&format!("/games/{}", game_id))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(state["board"], json!(["O", "", "", "", "X", "", "", "", ""]));
+    assert_eq!(state["full_board"], true);
+
+    let (status, history) = call(&router, get(&format!("/games/{}/history", game_id))).await;
+    assert_eq!(status, StatusCode::OK);
+    let moves: Vec<&Value> = history["events"].as_array().unwrap().iter().filter_map(|e| e["event"].get("MoveMade")).collect();
+    assert_eq!(moves.len(), 2, "{}", history);
+    assert_eq!(call(&router, get("/games/999/history")).await.0, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn http_api_allows_cross_origin_calls_and_checks_tokens() {
+    let (router, _channel) = start().await;
+    let preflight = HttpRequest::options("/games/1/moves")
+        .header("origin", "https://example.com")
+        .header("access-control-request-method", "POST")
+        .header("access-control-request-headers", "content-type,authorization")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.clone().oneshot(preflight).await.unwrap();
+    assert_eq!(response.headers()["access-control-allow-origin"], "*");
+
+    // gRPC와 같은 인증 인터셉터를 씀
+    let keys = Arc::new(TokenKeys::new(b"0123456789abcdef0123456789abcdef", Duration::from_secs(3600)));
+    let router = api_http::router(TicTacToeService::new(Config::default()), AuthInterceptor::new(keys.clone()));
+    assert_eq!(call(&router, get("/games")).await.0, StatusCode::UNAUTHORIZED);
+    let authorized = HttpRequest::get("/games")
+        .header("authorization", format!("Bearer {}", keys.issue("alice").unwrap()))
+        .body(Body::empty())
+        .unwrap();
+    let (status, games) = call(&router, authorized).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(games["games"], json!([]));
+}
@@ -0,0 +1,76 @@
+use server::error::MoveError;
+use server::game::{MoveOutcome, SharedGame};
+use server::tictactoe::{GameState, Join};
+use tokio::sync::mpsc;
+use tonic::Status;
+
+/// 두 자리가 모두 찬 3×3 게임 (업데이트는 반환한 수신기에 쌓임)
+async fn ongoing_game() -> (SharedGame, mpsc::Receiver<Result<GameState, Status>>) {
+    let mut game = SharedGame::new("1".into(), 3, 3);
+    let (tx, rx) = mpsc::channel(64);
+    game.join_player(&Join::default(), tx.clone(), None).await.unwrap();
+    game.join_player(&Join::default(), tx, None).await.unwrap();
+    assert_eq!(game.status, "ongoing");
+    (game, rx)
+}
+
+/// X와 O가 번갈아 `moves`를 두고 마지막 결과를 반환
+fn play(game: &mut SharedGame, moves: &[usize]) -> MoveOutcome {
+    let mut outcome = MoveOutcome::Continue;
+    for (i, &pos) in moves.iter().enumerate() {
+        let symbol = if i % 2 == 0 { "X" } else { "O" };
+        outcome = game.apply_move(symbol, pos).unwrap();
+    }
+    outcome
+}
+
+#[tokio::test]
+async fn accepted_move_passes_the_turn() {
+    let (mut game, _rx) = ongoing_game().await;
+    assert_eq!(game.apply_move("X", 4), Ok(MoveOutcome::Continue));
+    assert_eq!((game.board[4].as_str(), game.next_player.as_str()), ("X", "O"));
+    assert_eq!(game.history.len(), 1);
+}
+
+#[tokio::test]
+async fn completing_a_line_wins_with_that_line() {
+    let (mut game, _rx) = ongoing_game().await;
+    let outcome = play(&mut game, &[0, 3, 1, 4, 2]);
+    assert_eq!(outcome, MoveOutcome::Won { winner: "X".into(), line: vec![0, 1, 2] });
+    assert_eq!(game.status, "X_win");
+}
+
+#[tokio::test]
+async fn filling_the_board_without_a_line_draws() {
+    let (mut game, _rx) = ongoing_game().await;
+    // X O X / X O O / O X X
+    let outcome = play(&mut game, &[0, 1, 2, 4, 3, 5, 7, 6, 8]);
+    assert_eq!(outcome, MoveOutcome::Draw);
+    assert_eq!(game.status, "draw");
+}
+
+#[tokio::test]
+async fn each_rejection_leaves_the_game_unchanged() {
+    let (mut game, _rx) = ongoing_game().await;
+    game.apply_move("X", 4).unwrap();
+    let cases = [
+        ("X", 0, MoveError::NotYourTurn),
+        ("O", 4, MoveError::CellOccupied),
+        ("O", 9, MoveError::OutOfRange),
+        ("Z", 0, MoveError::NotAPlayer),
+    ];
+    for (symbol, pos, error) in cases {
+        assert_eq!(game.apply_move(symbol, pos), Err(error), "{} at {}", symbol, pos);
+        assert_eq!((game.history.len(), game.next_player.as_str()), (1, "O"));
+    }
+
+    game.resign("O");
+    assert_eq!(game.apply_move("X", 0), Err(MoveError::GameNotOngoing));
+    assert_eq!(game.board[0], "");
+}
+
+#[test]
+fn empty_seats_are_not_players() {
+    let mut game = SharedGame::new("1".into(), 3, 3);
+    assert_eq!(game.apply_move("X", 0), Err(MoveError::NotAPlayer));
+}
@@ -0,0 +1,147 @@
+mod scenario;
+
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use axum::body::Body;
+use axum::http::{Request as HttpRequest, StatusCode};
+use server::auth::{AuthError, AuthInterceptor, AuthenticatedPlayer, TokenKeys, UserStore};
+use server::auth_http::{self, TokenResponse};
+use server::config::Config;
+use server::service::TicTacToeService;
+use server::tictactoe::play_request::Action;
+use server::tictactoe::tic_tac_toe_client::TicTacToeClient;
+use server::tictactoe::{Join, ListGamesRequest, PlayRequest, PlayerRatingRequest, Resign};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::metadata::{Ascii, MetadataValue};
+use tonic::service::Interceptor;
+use tonic::{Code, Request, Status};
+use tower::ServiceExt;
+
+const SECRET: &[u8] = b"0123456789abcdef0123456789abcdef";
+
+fn keys() -> Arc<TokenKeys> {
+    Arc::new(TokenKeys::new(SECRET, Duration::from_secs(3600)))
+}
+
+fn with_bearer(token: &str) -> Request<()> {
+    let mut request = Request::new(());
+    request.metadata_mut().insert("authorization", format!("Bearer {}", token).parse().unwrap());
+    request
+}
+
+/// 요청마다 authorization 메타데이터를 붙이는 클라이언트 인터셉터
+struct Bearer(MetadataValue<Ascii>);
+
+impl Interceptor for Bearer {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        request.metadata_mut().insert("authorization", self.0.clone());
+        Ok(request)
+    }
+}
+
+#[test]
+fn missing_or_malformed_token_is_rejected() {
+    let mut auth = AuthInterceptor::new(keys());
+    assert_eq!(auth.call(Request::new(())).unwrap_err().code(), Code::Unauthenticated);
+
+    let mut request = Request::new(());
+    request.metadata_mut().insert("authorization", "Basic YWxpY2U6cHc=".parse().unwrap());
+    assert_eq!(auth.call(request).unwrap_err().code(), Code::Unauthenticated);
+    assert_eq!(auth.call(with_bearer("not-a-jwt")).unwrap_err().code(), Code::Unauthenticated);
+}
+
+#[test]
+fn expired_token_is_rejected() {
+    let keys = keys();
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    // 만료 시각 검사의 기본 여유(60초)보다 오래 전에 만료된 토큰
+    let token = keys.issue_at("alice", now - 3600 - 120).unwrap();
+    assert_eq!(keys.verify(&token), Err(AuthError::Expired));
+    let status = AuthInterceptor::new(keys).call(with_bearer(&token)).unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+    assert!(status.message().contains("만료"), "{}", status.message());
+}
+
+#[test]
+fn token_signed_with_another_secret_is_rejected() {
+    let other = TokenKeys::new(b"another-secret-another-secret-00", Duration::from_secs(3600));
+    let token = other.issue("alice").unwrap();
+    assert_eq!(keys().verify(&token), Err(AuthError::Invalid));
+}
+
+#[test]
+fn valid_token_attaches_the_player() {
+    let keys = keys();
+    let token = keys.issue("alice").unwrap();
+    let request = AuthInterceptor::new(keys).call(with_bearer(&token)).unwrap();
+    let player = request.extensions().get::<AuthenticatedPlayer>();
+    assert_eq!(player, Some(&AuthenticatedPlayer { player_id: "alice".into() }));
+
+    // 인증을 끄면 토큰 없이도 통과
+    assert!(AuthInterceptor::disabled().call(Request::new(())).is_ok());
+}
+
+#[tokio::test]
+async fn token_endpoint_checks_the_password() {
+    let mut users = UserStore::default();
+    users.insert("alice", "hunter2").unwrap();
+    let keys = keys();
+    let router = auth_http::router(keys.clone(), Arc::new(users));
+    let login = |password: &str| {
+        let body = format!(r#"{{"username":"alice","password":"{}"}}"#, password);
+        HttpRequest::post("/auth/token").header("content-type", "application/json").body(Body::from(body)).unwrap()
+    };
+
+    let response = router.clone().oneshot(login("wrong")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = router.oneshot(login("hunter2")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let token: TokenResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!((token.token_type.as_str(), token.expires_in), ("Bearer", 3600));
+    assert_eq!(keys.verify(&token.access_token).unwrap().sub, "alice");
+}
+
+#[tokio::test]
+async fn authenticated_play_uses_the_token_identity() {
+    let keys = keys();
+    let conn_tx = scenario::start_authenticated_server(TicTacToeService::new(Config::default()), AuthInterceptor::new(keys.clone()));
+    let channel = scenario::connect(&conn_tx).await;
+
+    let status = TicTacToeClient::new(channel.clone()).list_games(ListGamesRequest::default()).await.unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    let client_for = |player_id: &str| {
+        let header = format!("Bearer {}", keys.issue(player_id).unwrap()).parse().unwrap();
+        TicTacToeClient::with_interceptor(channel.clone(), Bearer(header))
+    };
+    let mut alice = client_for("alice");
+    assert!(alice.list_games(ListGamesRequest::default()).await.is_ok());
+
+    // alice가 Join에 다른 ID를 적어도 토큰의 ID로 기록됨
+    let mut streams = Vec::new();
+    for (mut client, claimed) in [(client_for("alice"), "bob"), (client_for("bob"), "")] {
+        let (tx, rx) = mpsc::channel(4);
+        tx.send(PlayRequest { action: Some(Action::Join(Join { player_id: claimed.into(), ..Join::default() })) }).await.unwrap();
+        let updates = client.play(ReceiverStream::new(rx)).await.unwrap().into_inner();
+        streams.push((tx, updates));
+    }
+    let (bob_tx, bob_updates) = &mut streams[1];
+    let state = bob_updates.message().await.unwrap().unwrap();
+    assert_eq!(state.status, "ongoing");
+    bob_tx.send(PlayRequest { action: Some(Action::Resign(Resign {})) }).await.unwrap();
+    while bob_updates.message().await.unwrap().is_some() {}
+
+    let request = || PlayerRatingRequest { player_id: "alice".into() };
+    let rating = loop {
+        match alice.get_player_rating(request()).await {
+            Ok(rating) => break rating.into_inner(),
+            Err(status) if status.code() == Code::NotFound => tokio::task::yield_now().await,
+            Err(status) => panic!("{}", status),
+        }
+    };
+    assert_eq!(rating.wins, 1);
+}
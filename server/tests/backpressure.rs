@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+use server::egress;
+use server::game::SharedGame;
+use server::tictactoe::{EndReason, GameState, Join};
+use tokio::sync::mpsc;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+fn state(move_number: u32) -> GameState {
+    GameState { move_number, status: "ongoing".into(), ..GameState::default() }
+}
+
+#[tokio::test]
+async fn a_wedged_receiver_neither_blocks_the_game_nor_keeps_its_seat() {
+    let mut game = SharedGame::new("1".into(), 3, 3);
+    // X는 첫 상태로 버퍼가 가득 찬 뒤 아무것도 읽지 않음
+    let (x_tx, _x_rx) = mpsc::channel(1);
+    let (o_tx, mut o_rx) = mpsc::channel(64);
+    let (_, x) = game.join_player(&Join::default(), x_tx, None).await.unwrap();
+    game.join_player(&Join::default(), o_tx, None).await.unwrap();
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        for (symbol, position) in [("X", 0), ("O", 4), ("X", 1), ("O", 2)] {
+            game.apply_move(symbol, position).unwrap();
+            game.broadcast_update().await;
+            game.broadcast_chat("O", "still here").await;
+        }
+    })
+    .await
+    .expect("broadcasts waited on a receiver that never reads");
+
+    assert!(game.forfeit_stalled("X", x).await);
+    assert_eq!(game.status, "O_win_by_resignation");
+    let mut last = None;
+    while let Ok(Ok(update)) = o_rx.try_recv() {
+        last = Some(update);
+    }
+    let last = last.unwrap();
+    assert_eq!(last.status, "O_win_by_resignation");
+    assert_eq!(last.board[..3], ["X", "X", "O"]);
+    assert!(last.info_message.contains("stopped receiving updates"), "{}", last.info_message);
+    assert!(!last.opponent_connected);
+    // 이미 끊긴 자리는 다시 처리하지 않음
+    assert!(!game.forfeit_stalled("X", x).await);
+}
+
+#[tokio::test]
+async fn a_spectator_that_never_reads_does_not_hold_up_the_players() {
+    let mut game = SharedGame::new("1".into(), 3, 3);
+    let (tx, mut players) = mpsc::channel(64);
+    for _ in 0..2 {
+        game.join_player(&Join::default(), tx.clone(), None).await.unwrap();
+    }
+    // 관전자는 처음 스냅샷으로 버퍼가 가득 찬 뒤 아무것도 읽지 않음 (0이면 끊지 않음)
+    let (spectator, _never_read) = mpsc::channel(1);
+    game.add_spectator(spectator, 0);
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        for (symbol, position) in [("X", 0), ("O", 3), ("X", 1), ("O", 4), ("X", 2)] {
+            game.apply_move(symbol, position).unwrap();
+            game.broadcast_update().await;
+        }
+    })
+    .await
+    .expect("broadcasts waited on a spectator that never reads");
+
+    assert_eq!(game.status, "X_win");
+    let mut last = None;
+    while let Ok(Ok(update)) = players.try_recv() {
+        last = Some(update);
+    }
+    assert_eq!(last.unwrap().status, "X_win");
+    assert_eq!(game.spectator_count(), 1);
+    assert!(game.metrics.render().contains("ttt_dropped_updates_total{recipient=\"spectator\"} 5"));
+}
+
+#[tokio::test]
+async fn a_spectator_past_the_drop_limit_is_disconnected_with_a_reason() {
+    let mut game = SharedGame::new("1".into(), 3, 3);
+    let (tx, _players) = mpsc::channel(64);
+    for _ in 0..2 {
+        game.join_player(&Join::default(), tx.clone(), None).await.unwrap();
+    }
+    let (spectator, mut updates) = mpsc::channel(1);
+    game.add_spectator(spectator, 3);
+
+    for (symbol, position) in [("X", 0), ("O", 4), ("X", 8)] {
+        game.apply_move(symbol, position).unwrap();
+        game.broadcast_update().await;
+    }
+    assert_eq!(game.spectator_count(), 0);
+    let metrics = game.metrics.render();
+    assert!(metrics.contains("ttt_dropped_updates_total{recipient=\"spectator\"} 3"), "{metrics}");
+    assert!(metrics.contains("ttt_slow_spectators_disconnected_total 1"), "{metrics}");
+
+    // 밀린 스냅샷을 읽으면 그다음에 종료 사유가 옴
+    assert!(updates.recv().await.unwrap().unwrap().board.iter().all(|c| c.is_empty()));
+    let status = updates.recv().await.unwrap().unwrap_err();
+    assert_eq!(egress::end_reason(&status), Some(EndReason::SlowConsumer));
+    assert!(updates.recv().await.is_none());
+}
+
+#[tokio::test(start_paused = true)]
+async fn forward_reports_a_stream_that_stops_taking_updates() {
+    let (tx, rx) = mpsc::channel(8);
+    let (mut out, stalled) = egress::forward(rx, TIMEOUT);
+    tx.send(Ok(state(1))).await.unwrap();
+    assert_eq!(out.recv().await.unwrap().unwrap().move_number, 1);
+
+    // 읽지 않으면 하나는 응답 채널에 들어가고, 다음 항목을 넘기려 기다리다 제한 시간이 지나면 알림
+    for n in 2..=4 {
+        tx.send(Ok(state(n))).await.unwrap();
+    }
+    let started = tokio::time::Instant::now();
+    stalled.await.expect("stall was not reported");
+    assert!(started.elapsed() >= TIMEOUT);
+    // 게임 쪽 채널은 닫혀 더 보내도 기다리지 않음
+    assert!(tx.is_closed());
+    assert_eq!(out.recv().await.unwrap().unwrap().move_number, 2);
+    assert!(out.recv().await.is_none());
+}
+
+#[tokio::test(start_paused = true)]
+async fn forward_stops_quietly_when_the_stream_ends() {
+    let (tx, rx) = mpsc::channel(8);
+    let (mut out, stalled) = egress::forward(rx, TIMEOUT);
+    for n in 1..=3 {
+        tx.send(Ok(state(n))).await.unwrap();
+        assert_eq!(out.recv().await.unwrap().unwrap().move_number, n);
+    }
+    drop(out);
+    tx.send(Ok(state(4))).await.unwrap();
+    assert!(stalled.await.is_err());
+    assert!(tx.is_closed());
+}
@@ -0,0 +1,167 @@
+mod scenario;
+
+use scenario::{eq, Scenario};
+use server::board::{validate_dimensions, winner, winning_line};
+use server::config::Config;
+use server::service::TicTacToeService;
+use server::tictactoe::tic_tac_toe_server::TicTacToe;
+use server::tictactoe::CreateGameRequest;
+use tonic::{Code, Request};
+
+/// 빈 size×size 보드에 `cells`만 `symbol`로 채움
+fn board_with(size: usize, cells: &[usize], symbol: &str) -> Vec<String> {
+    let mut board = vec![String::new(); size * size];
+    for &i in cells {
+        board[i] = symbol.to_string();
+    }
+    board
+}
+
+/// 정사각형 보드의 8가지 대칭 변환 (회전 4가지 × 좌우 반전 여부)
+fn symmetries(size: usize) -> Vec<Box<dyn Fn(usize) -> usize>> {
+    let last = size - 1;
+    let mut transforms: Vec<Box<dyn Fn(usize) -> usize>> = Vec::new();
+    for rotations in 0..4 {
+        for mirror in [false, true] {
+            transforms.push(Box::new(move |i| {
+                let (mut r, mut c) = (i / size, i % size);
+                if mirror {
+                    c = last - c;
+                }
+                for _ in 0..rotations {
+                    (r, c) = (c, last - r);
+                }
+                r * size + c
+            }));
+        }
+    }
+    transforms
+}
+
+/// (size, win_length)마다 들어갈 수 있는 모든 위치/방향의 줄
+fn all_lines(size: usize, win_length: usize) -> Vec<Vec<usize>> {
+    let mut lines = Vec::new();
+    for row in 0..size {
+        for col in 0..size {
+            for (dr, dc) in [(0isize, 1isize), (1, 0), (1, 1), (1, -1)] {
+                let cells: Vec<usize> = (0..win_length as isize)
+                    .map(|k| (row as isize + dr * k, col as isize + dc * k))
+                    .take_while(|&(r, c)| r >= 0 && c >= 0 && r < size as isize && c < size as isize)
+                    .map(|(r, c)| r as usize * size + c as usize)
+                    .collect();
+                if cells.len() == win_length {
+                    lines.push(cells);
+                }
+            }
+        }
+    }
+    lines
+}
+
+#[test]
+fn classic_board_has_exactly_the_eight_known_lines() {
+    let mut lines = all_lines(3, 3);
+    lines.iter_mut().for_each(|l| l.sort());
+    lines.sort();
+    assert_eq!(lines, [[0, 1, 2], [0, 3, 6], [0, 4, 8], [1, 4, 7], [2, 4, 6], [2, 5, 8], [3, 4, 5], [6, 7, 8]]);
+    for line in &lines {
+        assert_eq!(winner(&board_with(3, line, "O"), 3, 3).as_deref(), Some("O"));
+    }
+}
+
+#[test]
+fn every_line_wins_under_all_rotations_and_reflections() {
+    for size in 3..=7 {
+        for win_length in 3..=size {
+            for line in all_lines(size, win_length) {
+                for transform in symmetries(size) {
+                    let cells: Vec<usize> = line.iter().map(|&i| transform(i)).collect();
+                    let board = board_with(size, &cells, "X");
+                    assert_eq!(winner(&board, size, win_length).as_deref(), Some("X"), "{}x{} k={} {:?}", size, size, win_length, cells);
+                    let mut found = winning_line(&board, size, win_length).unwrap();
+                    found.sort();
+                    let mut expected = cells.clone();
+                    expected.sort();
+                    assert_eq!(found, expected);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn one_short_or_interrupted_lines_do_not_win() {
+    for size in 3..=7 {
+        for win_length in 3..=size {
+            for line in all_lines(size, win_length) {
+                for transform in symmetries(size) {
+                    let cells: Vec<usize> = line.iter().map(|&i| transform(i)).collect();
+                    // 한 칸 모자란 줄
+                    let short = board_with(size, &cells[1..], "X");
+                    assert_eq!(winner(&short, size, win_length), None, "{}x{} k={} {:?}", size, size, win_length, cells);
+                    // 가운데가 상대 심볼로 끊긴 줄
+                    let mut broken = board_with(size, &cells, "X");
+                    broken[cells[win_length / 2]] = "O".into();
+                    assert_eq!(winner(&broken, size, win_length), None, "{}x{} k={} {:?}", size, size, win_length, cells);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn gomoku_diagonal_wins_on_fifteen_by_fifteen() {
+    let cells: Vec<usize> = (0..5).map(|k| (6 + k) * 15 + (9 - k)).collect();
+    assert_eq!(winner(&board_with(15, &cells, "O"), 15, 5).as_deref(), Some("O"));
+    assert_eq!(winner(&board_with(15, &cells[..4], "O"), 15, 5), None);
+}
+
+#[test]
+fn dimensions_are_validated() {
+    assert!(validate_dimensions(3, 3).is_ok());
+    assert!(validate_dimensions(15, 5).is_ok());
+    assert!(validate_dimensions(2, 2).is_err());
+    assert!(validate_dimensions(16, 5).is_err());
+    assert!(validate_dimensions(4, 5).is_err());
+    assert!(validate_dimensions(4, 2).is_err());
+}
+
+fn sized(board_size: i32, win_length: i32) -> CreateGameRequest {
+    CreateGameRequest { board_size, win_length, ..CreateGameRequest::default() }
+}
+
+#[test]
+fn four_by_four_game_is_won_with_three_in_a_row() {
+    Scenario::new()
+        .create_player("alice", sized(4, 3))
+        .join_game("bob", "alice")
+        .expect_state(|s| s.board.len() == 16 && s.board_size == 4 && s.win_length == 3 && s.status == "ongoing")
+        .move_("alice", 5)
+        .move_("bob", 0)
+        .move_("alice", 10)
+        .move_("bob", 3)
+        .move_("alice", 15)
+        .expect_status("alice", eq("X_win"))
+        .run(Config::default());
+}
+
+#[test]
+fn positions_beyond_the_board_are_rejected() {
+    Scenario::new()
+        .create_player("alice", sized(4, 4))
+        .join_game("bob", "alice")
+        .move_("alice", 15)
+        .expect_state(|s| s.board[15] == "X")
+        .move_("bob", 16)
+        .expect("bob", |s| s.error_message == "Invalid position.")
+        .run(Config::default());
+}
+
+#[tokio::test]
+async fn invalid_dimensions_are_refused() {
+    let service = TicTacToeService::new(Config::default());
+    for request in [sized(4, 5), sized(2, 0), sized(16, 5), sized(-3, 3)] {
+        let status = service.create_game(Request::new(request)).await.unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+}
@@ -0,0 +1,55 @@
+use server::board_diff::{self, DiffEncoder};
+use server::game::SharedGame;
+use server::tictactoe::{CellChange, GameState, Join};
+use tokio::sync::mpsc;
+
+fn cells(cells: &str) -> Vec<String> {
+    cells.chars().map(|c| if c == '.' { String::new() } else { c.to_string() }).collect()
+}
+
+fn changes(update: &GameState) -> Vec<(i32, &str)> {
+    update.diff.as_ref().unwrap().changes.iter().map(|c: &CellChange| (c.position, c.new_value.as_str())).collect()
+}
+
+#[test]
+fn diff_lists_only_changed_cells() {
+    let diff = board_diff::diff(&cells("X...O...."), &cells("X..XO...."));
+    assert_eq!(diff.changes, [CellChange { position: 3, new_value: "X".into() }]);
+    assert!(board_diff::diff(&cells("X........"), &cells("X........")).changes.is_empty());
+    assert_eq!(board_diff::diff(&cells("XO......."), &cells(".........")).changes.len(), 2);
+
+    // 요청하지 않은 연결은 보드를 그대로 둠
+    let mut update = GameState { board: cells("X........"), full_board: true, ..GameState::default() };
+    assert_eq!(DiffEncoder::default().encode(&mut update), None);
+    assert!(update.full_board && update.diff.is_none());
+}
+
+#[tokio::test]
+async fn skipped_updates_are_not_taken_as_sent() {
+    let mut game = SharedGame::new("1".into(), 3, 3);
+    let (x_tx, mut x_rx) = mpsc::channel(1);
+    let (o_tx, _o_rx) = mpsc::channel(64);
+    game.join_player(&Join { board_diffs: true, ..Join::default() }, x_tx, None).await.unwrap();
+    let first = x_rx.recv().await.unwrap().unwrap();
+    assert!(first.full_board && first.board.len() == 9);
+
+    game.join_player(&Join::default(), o_tx, None).await.unwrap();
+    let started = x_rx.recv().await.unwrap().unwrap();
+    assert!(!started.full_board && started.board.is_empty());
+    assert!(changes(&started).is_empty());
+
+    // 버퍼가 찬 동안의 업데이트는 건너뛰므로 다음 차이에 그 수까지 담김
+    game.apply_move("X", 0).unwrap();
+    game.broadcast_update().await;
+    game.apply_move("O", 4).unwrap();
+    game.broadcast_update().await;
+    assert_eq!(changes(&x_rx.recv().await.unwrap().unwrap()), [(0, "X")]);
+    game.broadcast_chat("O", "hi").await;
+    assert_eq!(changes(&x_rx.recv().await.unwrap().unwrap()), [(4, "O")]);
+
+    // 보드 차이를 요청하지 않은 쪽은 계속 보드 전체를 받음
+    let mut snapshot = game.create_update();
+    assert!(snapshot.full_board);
+    assert_eq!(DiffEncoder::new(true).encode(&mut snapshot), Some(cells("X...O....")));
+    assert!(snapshot.full_board && snapshot.board.len() == 9);
+}
@@ -0,0 +1,144 @@
+mod scenario;
+
+use std::time::Duration;
+
+use scenario::{eq, Scenario};
+use server::board;
+use server::bot::{self, BotStrategy, Easy, Hard, Medium};
+use server::config::Config;
+use server::elo::INITIAL_RATING;
+use server::tictactoe::BotDifficulty;
+
+#[test]
+fn quick_play_falls_back_to_house_bot_after_timeout() {
+    let config = Config::default();
+    let timeout = config.quick_play_timeout();
+    Scenario::new()
+        .quick_player("alice")
+        .expect_status("alice", eq("searching"))
+        .advance(timeout)
+        .expect("alice", |s| s.info_message.contains("house bot") && s.status == "ongoing" && !s.rated)
+        .run(config);
+}
+
+#[test]
+fn quick_play_pairs_with_player_who_arrives_in_time() {
+    Scenario::new()
+        .quick_player("alice")
+        .advance(Duration::from_secs(5))
+        .player("bob")
+        .expect_state(|s| s.status == "ongoing" && s.rated)
+        .advance(Duration::from_secs(10))
+        .move_("alice", 4)
+        .expect("bob", |s| s.board[4] == "X" && s.info_message.is_empty())
+        .run(Config::default());
+}
+
+#[test]
+fn two_quick_play_requesters_play_a_rated_game() {
+    Scenario::new()
+        .rated_quick_player("alice", "alice")
+        .expect_status("alice", eq("searching"))
+        .rated_quick_player("bob", "bob")
+        .expect_state(|s| s.status == "ongoing" && s.rated && s.bot_difficulty == BotDifficulty::Unspecified as i32)
+        .resign("bob")
+        .expect("alice", |s| s.status == "X_win_by_resignation")
+        .player_rating("alice", |r| r.rating == 1216 && r.wins == 1)
+        .player_rating("bob", |r| r.rating == 1184 && r.losses == 1)
+        .run(Config::default());
+}
+
+#[test]
+fn house_bot_game_leaves_ratings_untouched() {
+    let config = Config::default();
+    let timeout = config.quick_play_timeout();
+    Scenario::new()
+        .rated_quick_player("alice", "alice")
+        .advance(timeout)
+        .expect("alice", |s| s.status == "ongoing" && !s.rated)
+        .resign("alice")
+        .expect("alice", |s| s.status == "O_win_by_resignation")
+        // 봇에게 진 판은 기록되지 않아 다음 레이팅 게임을 처음 레이팅으로 시작함
+        .rated_player("carol", "alice")
+        .rated_player("dave", "dave")
+        .expect("dave", |s| s.status == "ongoing" && s.x_rating == INITIAL_RATING)
+        .resign("dave")
+        .player_rating("alice", |r| r.rating == 1216 && r.wins == 1 && r.losses == 0)
+        .run(config);
+}
+
+#[test]
+fn house_bot_takes_center_then_blocks() {
+    let config = Config::default();
+    let timeout = config.quick_play_timeout();
+    Scenario::new()
+        .quick_player("alice")
+        .advance(timeout)
+        .expect("alice", |s| s.info_message.contains("house bot"))
+        .move_("alice", 0)
+        .expect("alice", |s| s.board[4] == "O" && s.next_player == "X")
+        .move_("alice", 1)
+        .expect("alice", |s| s.board[2] == "O" && s.status == "ongoing")
+        .run(config);
+}
+
+#[test]
+fn quick_play_bot_uses_the_requested_difficulty() {
+    let config = Config::default();
+    let timeout = config.quick_play_timeout();
+    Scenario::new()
+        .quick_player_against("alice", BotDifficulty::Hard)
+        .expect("alice", |s| s.status == "searching" && s.bot_difficulty == BotDifficulty::Unspecified as i32)
+        .advance(timeout)
+        .expect("alice", |s| s.status == "ongoing" && s.bot_difficulty == BotDifficulty::Hard as i32)
+        .run(config);
+}
+
+#[test]
+fn human_games_report_no_bot_difficulty() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .expect_state(|s| s.status == "ongoing" && s.bot_difficulty == BotDifficulty::Unspecified as i32)
+        .run(Config::default());
+}
+
+/// 보드 문자열("X.O......")을 칸 목록으로
+fn cells(board: &str) -> Vec<String> {
+    board.chars().map(|c| if c == '.' { String::new() } else { c.to_string() }).collect()
+}
+
+#[test]
+fn medium_always_blocks_an_immediate_threat() {
+    // X가 가로, 세로, 대각선으로 한 칸만 남긴 보드마다 O는 그 칸을 막음
+    for (board, block) in [("XX..O....", 2), ("X..X....O", 6), ("X...X..O.", 8), ("..X.X...O", 6)] {
+        for _ in 0..20 {
+            assert_eq!(Medium.choose_move(&cells(board), 3, 3, "O", false), Some(block), "{}", board);
+        }
+    }
+}
+
+#[test]
+fn hard_never_loses_to_random_play() {
+    let hard = bot::strategy(BotDifficulty::Hard);
+    for game in 0..300 {
+        // 절반은 Hard가 먼저, 절반은 무작위가 먼저 둠
+        let hard_symbol = if game % 2 == 0 { "X" } else { "O" };
+        let mut board = cells(".........");
+        let mut to_move = "X";
+        while board::winner(&board, 3, 3).is_none() && board.iter().any(String::is_empty) {
+            let strategy: &dyn BotStrategy = if to_move == hard_symbol { hard } else { &Easy };
+            let pos = strategy.choose_move(&board, 3, 3, to_move, false).unwrap();
+            board[pos] = to_move.to_string();
+            to_move = if to_move == "X" { "O" } else { "X" };
+        }
+        let winner = board::winner(&board, 3, 3);
+        assert!(winner.is_none() || winner.as_deref() == Some(hard_symbol), "game {}: {:?}", game, board);
+    }
+}
+
+#[test]
+fn hard_takes_an_immediate_win_over_a_block() {
+    // O가 2에서 이길 수 있고 X도 5에서 이길 수 있으면 이기는 쪽을 고름
+    assert_eq!(Hard.choose_move(&cells("OO.XX...."), 3, 3, "O", false), Some(2));
+}
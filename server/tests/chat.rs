@@ -0,0 +1,160 @@
+mod scenario;
+
+use std::time::Duration;
+
+use scenario::Scenario;
+use server::chat::{sanitize, ChatLimiter};
+use server::config::Config;
+use server::error::ChatError;
+use server::service::TicTacToeService;
+use server::tictactoe::play_request::Action;
+use server::tictactoe::tic_tac_toe_client::TicTacToeClient;
+use server::tictactoe::{ChatMessage, GameState, Join, PlayRequest};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tonic::transport::{Channel, Server};
+use tonic::{Code, Streaming};
+
+#[test]
+fn chat_reaches_the_opponent_and_spectators() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .spectator("carol", "alice")
+        .chat("alice", "good luck")
+        .expect("bob", |s| s.chat_message == "good luck" && s.chat_sender == "X" && s.status == "ongoing")
+        .expect("carol", |s| s.chat_message == "good luck" && s.chat_sender == "X")
+        .chat("bob", "you too")
+        .expect("alice", |s| s.chat_message == "you too" && s.chat_sender == "O")
+        .run(Config::default());
+}
+
+#[test]
+fn first_player_can_chat_while_waiting() {
+    Scenario::new()
+        .player("alice")
+        .spectator("carol", "alice")
+        .chat("alice", "anyone there?")
+        .expect("carol", |s| s.chat_message == "anyone there?" && s.status == "waiting")
+        .run(Config::default());
+}
+
+#[test]
+fn chat_is_cleaned_and_length_checked() {
+    let config = Config { chat_max_chars: 10, ..Config::default() };
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .chat("alice", "  hi\u{7}\nthere ")
+        .expect("bob", |s| s.chat_message == "hithere")
+        .chat("alice", &"x".repeat(11))
+        .expect("alice", |s| s.error_message == "Chat messages can be at most 10 characters.")
+        .chat("alice", "\n\t")
+        .expect("alice", |s| s.error_message == "Chat message is empty.")
+        .run(config);
+}
+
+#[test]
+fn chat_flooding_is_rate_limited() {
+    let config = Config::default();
+    let mut scenario = Scenario::new().player("alice").player("bob");
+    for i in 0..=config.chat_rate_limit {
+        scenario = scenario.chat("alice", &format!("spam {i}"));
+    }
+    scenario
+        .expect("alice", |s| s.error_message == "You are sending chat messages too quickly.")
+        .advance(config.chat_rate_window())
+        .chat("alice", "sorry")
+        .expect("bob", |s| s.chat_message == "sorry")
+        .run(config);
+}
+
+#[test]
+fn spectators_cannot_chat() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .spectator("carol", "alice")
+        .chat("carol", "hello")
+        .expect("carol", |s| s.error_message == "Spectators cannot chat.")
+        .run(Config::default());
+}
+
+#[test]
+fn sanitize_keeps_unicode_text() {
+    assert_eq!(sanitize("안녕 👋", 256).as_deref(), Ok("안녕 👋"));
+    assert!(sanitize(&"가".repeat(256), 256).is_ok());
+    assert_eq!(sanitize(&"가".repeat(257), 256), Err(ChatError::TooLong(256)));
+    // 결합 문자와 ZWJ 이모지는 여러 char라도 한 글자로 셈
+    assert!(sanitize(&"e\u{301}".repeat(4), 4).is_ok());
+    assert!(sanitize("👨\u{200d}👩\u{200d}👧", 1).is_ok());
+    assert_eq!(sanitize(&"e\u{301}".repeat(5), 4), Err(ChatError::TooLong(4)));
+}
+
+#[tokio::test(start_paused = true)]
+async fn limiter_allows_a_fixed_number_per_window() {
+    let (limit, window) = (5, Duration::from_secs(10));
+    let mut limiter = ChatLimiter::default();
+    assert!((0..limit).all(|_| limiter.allow(limit, window)));
+    assert!(!limiter.allow(limit, window));
+    // 창 안에서는 오래 기다려도 그대로이고, 가장 오래된 메시지가 창을 벗어나면 하나씩 다시 보낼 수 있음
+    tokio::time::advance(window - Duration::from_millis(1)).await;
+    assert!(!limiter.allow(limit, window));
+    tokio::time::advance(Duration::from_millis(1)).await;
+    assert!((0..limit).all(|_| limiter.allow(limit, window)));
+    assert!(!limiter.allow(limit, window));
+}
+
+async fn start(config: Config) -> Channel {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let service = TicTacToeService::new(config);
+    tokio::spawn(Server::builder().add_service(service.into_server()).serve_with_incoming(TcpListenerStream::new(listener)));
+    Channel::from_shared(format!("http://{}", addr)).unwrap().connect().await.unwrap()
+}
+
+/// Play 스트림으로 참가해 첫 업데이트(심볼, 세션 토큰)를 받음. 요청 채널도 돌려줘 스트림을 열어 둠
+async fn join(channel: Channel, game_id: &str) -> (GameState, Streaming<GameState>, mpsc::Sender<PlayRequest>) {
+    let (tx, rx) = mpsc::channel(8);
+    let join = Join { game_id: game_id.into(), ..Join::default() };
+    tx.send(PlayRequest { action: Some(Action::Join(join)) }).await.unwrap();
+    let mut updates = TicTacToeClient::new(channel).play(ReceiverStream::new(rx)).await.unwrap().into_inner();
+    let first = updates.message().await.unwrap().unwrap();
+    (first, updates, tx)
+}
+
+#[tokio::test]
+async fn send_chat_rpc_reaches_the_opponent_and_shares_the_rate_limit() {
+    let channel = start(Config { chat_rate_limit: 2, ..Config::default() }).await;
+    let mut client = TicTacToeClient::new(channel.clone());
+    let (alice, _alice_updates, _alice_tx) = join(channel.clone(), "").await;
+    let (_, mut bob_updates, _bob_tx) = join(channel, &alice.game_id).await;
+    let chat = |text: &str| ChatMessage {
+        game_id: alice.game_id.clone(),
+        sender_symbol: "X".into(),
+        text: text.into(),
+        session_token: alice.session_token.clone(),
+    };
+
+    // 다른 RPC처럼 게임 ID 앞뒤의 공백은 무시
+    let padded = ChatMessage { game_id: format!(" {} ", alice.game_id), ..chat("hello from outside the stream") };
+    client.send_chat(padded).await.unwrap();
+    loop {
+        let update = bob_updates.message().await.unwrap().unwrap();
+        if !update.chat_message.is_empty() {
+            assert_eq!((update.chat_message.as_str(), update.chat_sender.as_str()), ("hello from outside the stream", "X"));
+            break;
+        }
+    }
+
+    assert_eq!(client.send_chat(chat(" \n")).await.unwrap_err().code(), Code::InvalidArgument);
+    // 빈 메시지도 빈도 제한에 셈: 두 번을 다 썼으므로 거부
+    assert_eq!(client.send_chat(chat("again")).await.unwrap_err().code(), Code::ResourceExhausted);
+
+    let forged = ChatMessage { sender_symbol: "O".into(), ..chat("pretending to be bob") };
+    assert_eq!(client.send_chat(forged).await.unwrap_err().code(), Code::PermissionDenied);
+    let tokenless = ChatMessage { session_token: String::new(), ..chat("no token") };
+    assert_eq!(client.send_chat(tokenless).await.unwrap_err().code(), Code::PermissionDenied);
+    let missing = ChatMessage { game_id: "999".into(), ..chat("anyone?") };
+    assert_eq!(client.send_chat(missing).await.unwrap_err().code(), Code::NotFound);
+}
@@ -0,0 +1,101 @@
+use server::compression::{self, Compression};
+use server::config::Config;
+use server::service::TicTacToeService;
+use server::tictactoe::play_request::Action;
+use server::tictactoe::tic_tac_toe_client::TicTacToeClient;
+use server::tictactoe::{GameState, Join, Move, PlayRequest};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tonic::codec::CompressionEncoding;
+use tonic::transport::{Channel, Server};
+use tonic::Streaming;
+
+/// 응답을 `encoding`으로 압축하는 서버를 띄우고 그 주소를 반환
+async fn start(encoding: Option<CompressionEncoding>) -> String {
+    let service = TicTacToeService::new(Config::default());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = Server::builder().add_service(service.into_compressed_server(encoding));
+    tokio::spawn(server.serve_with_incoming(TcpListenerStream::new(listener)));
+    format!("http://{}", addr)
+}
+
+/// 요청을 `send`로 압축하고 `accept` 방식으로 압축한 응답을 받겠다고 알리는 클라이언트
+async fn connect(addr: &str, send: Option<CompressionEncoding>, accept: &[CompressionEncoding]) -> TicTacToeClient<Channel> {
+    let channel = Channel::from_shared(addr.to_string()).unwrap().connect().await.unwrap();
+    let mut client = TicTacToeClient::new(channel);
+    for &encoding in accept {
+        client = client.accept_compressed(encoding);
+    }
+    match send {
+        Some(encoding) => client.send_compressed(encoding),
+        None => client,
+    }
+}
+
+async fn join(client: &mut TicTacToeClient<Channel>) -> (mpsc::Sender<PlayRequest>, Streaming<GameState>) {
+    let (tx, rx) = mpsc::channel(8);
+    tx.send(PlayRequest { action: Some(Action::Join(Join::default())) }).await.unwrap();
+    let updates = client.play(ReceiverStream::new(rx)).await.unwrap().into_inner();
+    (tx, updates)
+}
+
+/// 보드에 `count`개의 수가 놓인 상태가 올 때까지 받음
+async fn wait_for_moves(updates: &mut Streaming<GameState>, count: usize) -> GameState {
+    loop {
+        let state = updates.message().await.unwrap().unwrap();
+        if state.board.iter().filter(|cell| !cell.is_empty()).count() == count {
+            return state;
+        }
+    }
+}
+
+/// 두 클라이언트로 X가 윗줄을 채워 이기는 게임을 끝까지 두고 마지막 상태를 반환
+async fn play_a_game(mut x: TicTacToeClient<Channel>, mut o: TicTacToeClient<Channel>) -> GameState {
+    let (x_tx, mut x_updates) = join(&mut x).await;
+    let (o_tx, mut o_updates) = join(&mut o).await;
+    let mut last = GameState::default();
+    for (count, (tx, symbol, position)) in [(&x_tx, "X", 0), (&o_tx, "O", 3), (&x_tx, "X", 1), (&o_tx, "O", 4), (&x_tx, "X", 2)].into_iter().enumerate() {
+        let mv = Move { player_id: symbol.into(), position, ..Move::default() };
+        tx.send(PlayRequest { action: Some(Action::Move(mv)) }).await.unwrap();
+        last = wait_for_moves(&mut o_updates, count + 1).await;
+    }
+    assert_eq!(wait_for_moves(&mut x_updates, 5).await.status, last.status);
+    last
+}
+
+#[test]
+fn compression_flag_values_map_to_tonic_encodings() {
+    assert_eq!(Compression::default(), Compression::Gzip);
+    assert_eq!(Compression::Gzip.encoding(), Ok(Some(CompressionEncoding::Gzip)));
+    assert_eq!(Compression::None.encoding(), Ok(None));
+    assert!(compression::accepted().contains(&CompressionEncoding::Gzip));
+    #[cfg(not(feature = "zstd"))]
+    assert!(Compression::Zstd.encoding().is_err());
+    #[cfg(feature = "zstd")]
+    assert_eq!(Compression::Zstd.encoding(), Ok(Some(CompressionEncoding::Zstd)));
+}
+
+#[tokio::test]
+async fn a_game_completes_with_compression_on_both_sides() {
+    let addr = start(Some(CompressionEncoding::Gzip)).await;
+    let gzip = [CompressionEncoding::Gzip];
+    let x = connect(&addr, Some(CompressionEncoding::Gzip), &gzip).await;
+    let o = connect(&addr, Some(CompressionEncoding::Gzip), &gzip).await;
+    assert_eq!(play_a_game(x, o).await.status, "X_win");
+}
+
+#[tokio::test]
+async fn a_game_completes_when_only_one_side_compresses() {
+    // 서버는 압축하지 않지만 압축한 요청은 받음
+    let addr = start(None).await;
+    let x = connect(&addr, Some(CompressionEncoding::Gzip), &[CompressionEncoding::Gzip]).await;
+    let o = connect(&addr, None, &[]).await;
+    assert_eq!(play_a_game(x, o).await.status, "X_win");
+
+    // 서버는 gzip으로 압축하려 하지만 받겠다고 알리지 않은 클라이언트에는 압축하지 않은 응답을 보냄
+    let addr = start(Some(CompressionEncoding::Gzip)).await;
+    let x = connect(&addr, None, &[]).await;
+    let o = connect(&addr, Some(CompressionEncoding::Gzip), &[]).await;
+    assert_eq!(play_a_game(x, o).await.status, "X_win");
+}
@@ -0,0 +1,85 @@
+use server::config::{Config, FirstPlayer, SnapshotFormat};
+
+#[test]
+fn non_default_config_round_trips_through_toml_file() {
+    let config = Config {
+        listen_addr: "127.0.0.1:6000".into(),
+        channel_buffer: 8,
+        reconnect_grace_secs: 5,
+        quick_play_timeout_secs: 2,
+        waiting_timeout_ms: 500,
+        max_games: 3,
+        latency_budget_ms: 20,
+        load_check_interval_ms: 250,
+        presets_file: Some("presets.toml".into()),
+        admin_token: Some("secret".into()),
+        invite_ttl_secs: 60,
+        elo_k_factor: 16.0,
+        ratings_file: Some("ratings.toml".into()),
+        stats_file: Some("stats.toml".into()),
+        auth_secret: Some("0123456789abcdef0123456789abcdef".into()),
+        auth_listen_addr: "127.0.0.1:6001".into(),
+        auth_token_ttl_secs: 60,
+        users_file: Some("users.toml".into()),
+        metrics_listen_addr: Some("127.0.0.1:9100".into()),
+        http_listen_addr: "127.0.0.1:8081".into(),
+        first_player: FirstPlayer::Alternate,
+        http2_keepalive_interval_secs: 20,
+        http2_keepalive_timeout_secs: 5,
+        heartbeat_interval_secs: 7,
+        heartbeat_timeout_secs: 3,
+        idle_timeout_secs: 30,
+        shutdown_drain_timeout_secs: 12,
+        replay_buffer_games: 5,
+        replay_delay_ms: 250,
+        chat_max_chars: 100,
+        chat_rate_limit: 2,
+        chat_rate_window_secs: 30,
+        message_rate_per_sec: 2,
+        message_burst: 4,
+        dropped_message_limit: 20,
+        rate_limit_capacity: 3,
+        rate_limit_refill_per_sec: 0.5,
+        turn_timeout_secs: 60,
+        spectator_drop_limit: 5,
+        join_timeout_secs: 45,
+        web_allowed_origins: vec!["https://play.example.com".into()],
+        snapshot_format: SnapshotFormat::Bincode,
+        leaderboard_cache_secs: 5,
+        abandon_timeout_ms: 600_000,
+    };
+    assert_ne!(config, Config::default());
+
+    let path = std::env::temp_dir().join(format!("tictactoe-config-{}.toml", std::process::id()));
+    std::fs::write(&path, toml::to_string(&config).unwrap()).unwrap();
+    let loaded = Config::load(Some(&path));
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.unwrap(), config);
+}
+
+#[test]
+fn missing_fields_use_defaults() {
+    let config: Config = toml::from_str("max_games = 7").unwrap();
+    assert_eq!(config, Config { max_games: 7, ..Config::default() });
+}
+
+#[test]
+fn first_player_policies_parse_from_toml() {
+    for (text, expected) in [("X", FirstPlayer::X), ("O", FirstPlayer::O), ("random", FirstPlayer::Random), ("alternate", FirstPlayer::Alternate)] {
+        let config: Config = toml::from_str(&format!("first_player = \"{text}\"")).unwrap();
+        assert_eq!(config.first_player, expected);
+    }
+    assert!(toml::from_str::<Config>("first_player = \"loser\"").is_err());
+}
+
+#[test]
+fn web_allowed_origins_must_be_bare_origins() {
+    let with = |origin: &str| Config { web_allowed_origins: vec![origin.into()], ..Config::default() };
+    assert!(with("https://play.example.com").validate().is_empty());
+    assert!(with("http://localhost:5173").validate().is_empty());
+    for bad in ["play.example.com", "https://play.example.com/", "https://play.example.com/app", "*"] {
+        let errors = with(bad).validate();
+        assert_eq!(errors.iter().map(|(field, _)| *field).collect::<Vec<_>>(), ["web_allowed_origins"], "{}", bad);
+    }
+}
@@ -0,0 +1,123 @@
+mod scenario;
+
+use scenario::{eq, Scenario};
+use server::config::Config;
+use server::game::SharedGame;
+
+#[test]
+fn accepted_offer_ends_game_as_agreed_draw() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .move_("alice", 4)
+        .offer_draw("bob")
+        .expect("alice", |s| s.draw_offer_pending)
+        .expect("bob", |s| !s.draw_offer_pending && s.status == "ongoing")
+        .respond_draw("alice", true)
+        .expect_state(|s| s.status == "draw_agreed" && !s.draw_offer_pending && s.board[4] == "X")
+        .run(Config::default());
+}
+
+#[test]
+fn declined_offer_is_cleared_and_game_continues() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .offer_draw("alice")
+        .expect("bob", |s| s.draw_offer_pending)
+        .respond_draw("bob", false)
+        .expect_state(|s| s.info_message == "Draw offer declined." && !s.draw_offer_pending && s.status == "ongoing")
+        .move_("alice", 0)
+        .expect_state(|s| s.board[0] == "X")
+        .run(Config::default());
+}
+
+#[test]
+fn double_offer_is_rejected() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .offer_draw("alice")
+        .offer_draw("alice")
+        .expect("alice", |s| s.error_message == "You have already offered a draw.")
+        .offer_draw("bob")
+        .expect("bob", |s| s.error_message.contains("opponent has already offered"))
+        .respond_draw("bob", true)
+        .expect_state(|s| s.status == "draw_agreed")
+        .run(Config::default());
+}
+
+#[test]
+fn offering_player_cannot_answer_own_offer() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .offer_draw("alice")
+        .respond_draw("alice", true)
+        .expect("alice", |s| s.error_message == "There is no draw offer to respond to.")
+        .expect("bob", |s| s.draw_offer_pending && s.status == "ongoing")
+        .run(Config::default());
+}
+
+#[test]
+fn offer_is_withdrawn_when_offering_player_moves() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .offer_draw("alice")
+        .expect("bob", |s| s.draw_offer_pending)
+        .move_("alice", 4)
+        .expect("bob", |s| s.board[4] == "X" && !s.draw_offer_pending)
+        .respond_draw("bob", true)
+        .expect("bob", |s| s.error_message == "There is no draw offer to respond to.")
+        .run(Config::default());
+}
+
+#[test]
+fn offer_after_game_over_is_rejected() {
+    // 끝난 게임의 스트림은 마지막 상태 뒤에 닫히므로 게임 상태로 직접 확인
+    let mut game = SharedGame::new("1".into(), 3, 3);
+    game.resign("O");
+    assert_eq!(game.status, "X_win_by_resignation");
+    assert_eq!(game.offer_draw("X"), Err("You can only offer a draw during an ongoing game."));
+}
+
+#[test]
+fn house_bot_refuses_draw_offers() {
+    let config = Config::default();
+    let timeout = config.quick_play_timeout();
+    Scenario::new()
+        .quick_player("alice")
+        .advance(timeout)
+        .expect_status("alice", eq("ongoing"))
+        .offer_draw("alice")
+        .expect("alice", |s| s.error_message == "The house bot does not accept draw offers.")
+        .run(config);
+}
+
+#[test]
+fn offer_is_voided_when_opponent_moves_instead() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .move_("alice", 4)
+        .offer_draw("alice")
+        .expect("bob", |s| s.draw_offer_pending)
+        .move_("bob", 0)
+        .expect_state(|s| s.board[0] == "O" && !s.draw_offer_pending)
+        .respond_draw("bob", true)
+        .expect("bob", |s| s.error_message == "There is no draw offer to respond to.")
+        .move_("alice", 8)
+        .expect_state(|s| s.board[8] == "X" && s.status == "ongoing")
+        .run(Config::default());
+}
+
+#[test]
+fn offer_while_waiting_is_rejected() {
+    Scenario::new()
+        .player("alice")
+        .expect_status("alice", eq("waiting"))
+        .offer_draw("alice")
+        .expect("alice", |s| s.error_message == "You can only offer a draw during an ongoing game.")
+        .run(Config::default());
+}
@@ -0,0 +1,91 @@
+mod scenario;
+
+use scenario::Scenario;
+use server::config::Config;
+use server::elo::{EloRating, EloRecord, RatedResult, RatingBook, INITIAL_RATING, RATING_FLOOR};
+
+#[test]
+fn first_game_between_new_players_moves_half_the_k_factor() {
+    let elo = EloRating::new(32.0);
+    let (mut winner, mut loser) = (EloRecord::default(), EloRecord::default());
+    elo.update(&mut winner, &mut loser);
+    assert_eq!((winner.rating, loser.rating), (INITIAL_RATING + 16, INITIAL_RATING - 16));
+    assert_eq!((winner.wins, winner.losses, loser.wins, loser.losses), (1, 0, 0, 1));
+}
+
+#[test]
+fn expected_score_follows_the_standard_formula() {
+    assert_eq!(EloRating::expected_score(1500, 1500), 0.5);
+    // 400점 차이면 기대 점수 비율이 10:1
+    let favourite = EloRating::expected_score(1600, 1200);
+    assert!((favourite - 10.0 / 11.0).abs() < 1e-9, "{}", favourite);
+    assert!((favourite + EloRating::expected_score(1200, 1600) - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn upsets_and_draws_move_ratings_toward_each_other() {
+    let elo = EloRating::new(32.0);
+    let mut strong = EloRecord { rating: 1600, ..EloRecord::default() };
+    let mut weak = EloRecord { rating: 1200, ..EloRecord::default() };
+    elo.update_draw(&mut strong, &mut weak);
+    assert_eq!((strong.rating, weak.rating), (1587, 1213));
+    assert_eq!((strong.draws, weak.draws), (1, 1));
+
+    let mut strong = EloRecord { rating: 1600, ..EloRecord::default() };
+    let mut weak = EloRecord { rating: 1200, ..EloRecord::default() };
+    elo.update(&mut weak, &mut strong);
+    assert_eq!((weak.rating, strong.rating), (1229, 1571));
+}
+
+#[test]
+fn ratings_never_drop_below_the_floor() {
+    let elo = EloRating::new(32.0);
+    let mut winner = EloRecord { rating: RATING_FLOOR, ..EloRecord::default() };
+    let mut loser = EloRecord { rating: RATING_FLOOR, ..EloRecord::default() };
+    elo.update(&mut winner, &mut loser);
+    assert_eq!((winner.rating, loser.rating), (RATING_FLOOR + 16, RATING_FLOOR));
+}
+
+#[test]
+fn rating_book_persists_records_to_file() {
+    let path = std::env::temp_dir().join(format!("tictactoe-ratings-{}.toml", std::process::id()));
+    let mut book = RatingBook::load(Some(&path)).unwrap();
+    assert_eq!(book.current_rating("alice"), Some(INITIAL_RATING));
+    assert_eq!(book.current_rating(""), None);
+    book.record(&RatedResult::Decisive { winner: "alice".into(), loser: "bob".into() }, &EloRating::new(32.0));
+
+    let reloaded = RatingBook::load(Some(&path));
+    std::fs::remove_file(&path).unwrap();
+    let reloaded = reloaded.unwrap();
+    assert_eq!(reloaded.get("alice"), Some(EloRecord { rating: 1216, wins: 1, losses: 0, draws: 0 }));
+    assert_eq!(reloaded.get("bob"), Some(EloRecord { rating: 1184, wins: 0, losses: 1, draws: 0 }));
+}
+
+#[test]
+fn finished_rated_game_updates_both_players() {
+    Scenario::new()
+        .rated_player("alice", "alice")
+        .rated_player("bob", "bob")
+        .expect("bob", |s| s.status == "ongoing" && s.x_rating == INITIAL_RATING && s.o_rating == INITIAL_RATING)
+        .resign("bob")
+        .expect("alice", |s| s.status == "X_win_by_resignation")
+        .player_rating("alice", |r| r.rating == 1216 && r.wins == 1)
+        .player_rating("bob", |r| r.rating == 1184 && r.losses == 1)
+        // 다음 게임에는 갱신된 레이팅이 표시됨
+        .rated_player("carol", "alice")
+        .rated_player("dave", "bob")
+        .expect("dave", |s| s.status == "ongoing" && s.x_rating == 1216 && s.o_rating == 1184)
+        .run(Config::default());
+}
+
+#[test]
+fn games_without_player_ids_are_not_recorded() {
+    Scenario::new()
+        .rated_player("alice", "alice")
+        .player("bob")
+        .expect("bob", |s| s.status == "ongoing" && s.x_rating == INITIAL_RATING && s.o_rating == 0)
+        .resign("bob")
+        .expect("alice", |s| s.status == "X_win_by_resignation")
+        .game_state("alice", |s| s.status == "X_win_by_resignation")
+        .run(Config { elo_k_factor: 24.0, ..Config::default() });
+}
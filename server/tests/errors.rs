@@ -0,0 +1,79 @@
+use server::auth::AuthError;
+use server::error::{GameError, MoveError};
+use tonic::{Code, Status};
+
+#[test]
+fn each_game_error_maps_to_its_grpc_code() {
+    let cases = [
+        (GameError::GameFull, Code::ResourceExhausted),
+        (GameError::OpponentAlreadyJoined, Code::AlreadyExists),
+        (GameError::InviteOnly, Code::PermissionDenied),
+        (GameError::NotYourSeat, Code::PermissionDenied),
+        (GameError::SessionNotFound, Code::NotFound),
+        (GameError::InvalidInviteCode, Code::NotFound),
+        (GameError::TooManyGames, Code::ResourceExhausted),
+        (GameError::NotYourTurn, Code::FailedPrecondition),
+        (GameError::CellOccupied, Code::AlreadyExists),
+        (GameError::InvalidPosition, Code::OutOfRange),
+        (GameError::GameNotOngoing, Code::FailedPrecondition),
+        (GameError::WrongSubBoard, Code::FailedPrecondition),
+        (GameError::SubBoardClosed, Code::FailedPrecondition),
+        (GameError::InvalidChosenSymbol, Code::InvalidArgument),
+        (GameError::PlayerNotFound, Code::NotFound),
+        (GameError::NoPlayerInSeat, Code::FailedPrecondition),
+        (GameError::GameNotFound, Code::NotFound),
+        (GameError::PuzzleNotFound, Code::NotFound),
+        (GameError::TournamentNotFound, Code::NotFound),
+        (GameError::ChatRateLimited, Code::ResourceExhausted),
+        (GameError::InvalidArgument("잘못된 값".into()), Code::InvalidArgument),
+        (GameError::Overloaded, Code::Unavailable),
+        (GameError::ShuttingDown, Code::Unavailable),
+        (GameError::AdminDisabled, Code::PermissionDenied),
+        (GameError::AuthError("토큰 없음".into()), Code::Unauthenticated),
+        (GameError::Internal("디스크 오류".into()), Code::Internal),
+    ];
+    for (error, code) in cases {
+        let message = error.to_string();
+        let status = Status::from(error);
+        assert_eq!(status.code(), code, "{}", message);
+        assert_eq!(status.message(), message);
+    }
+}
+
+#[test]
+fn token_errors_become_unauthenticated() {
+    let status = Status::from(GameError::from(AuthError::Expired));
+    assert_eq!(status.code(), Code::Unauthenticated);
+    assert_eq!(status.message(), "인증 토큰이 만료되었습니다.");
+}
+
+#[test]
+fn move_errors_keep_their_in_game_messages() {
+    assert_eq!(MoveError::NotYourTurn.player_message(), "It's not your turn.");
+    assert_eq!(MoveError::CellOccupied.player_message(), "Cell already occupied.");
+    assert_eq!(MoveError::OutOfRange.player_message(), "Invalid position.");
+    assert_eq!(MoveError::GameNotOngoing.player_message(), "Game is not ongoing.");
+    assert_eq!(MoveError::NotAPlayer.player_message(), "You are not a player in this game.");
+    assert_eq!(MoveError::WrongSubBoard.player_message(), "You must play in the highlighted sub-board.");
+    assert_eq!(MoveError::SubBoardClosed.player_message(), "That sub-board is already decided.");
+    assert_eq!(MoveError::InvalidChosenSymbol.player_message(), "Choose X or O to place in a wild game.");
+}
+
+#[test]
+fn move_errors_map_to_grpc_codes_through_game_error() {
+    let cases = [
+        (MoveError::NotYourTurn, Code::FailedPrecondition),
+        (MoveError::CellOccupied, Code::AlreadyExists),
+        (MoveError::OutOfRange, Code::OutOfRange),
+        (MoveError::GameNotOngoing, Code::FailedPrecondition),
+        (MoveError::NotAPlayer, Code::PermissionDenied),
+        (MoveError::WrongSubBoard, Code::FailedPrecondition),
+        (MoveError::SubBoardClosed, Code::FailedPrecondition),
+        (MoveError::InvalidChosenSymbol, Code::InvalidArgument),
+    ];
+    for (error, code) in cases {
+        let status = Status::from(error);
+        assert_eq!(status.code(), code, "{:?}", error);
+        assert_eq!(status.message(), GameError::from(error).to_string());
+    }
+}
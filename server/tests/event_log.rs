@@ -0,0 +1,111 @@
+mod scenario;
+
+use std::sync::Arc;
+
+use scenario::{play_alternately, TestPlayer};
+use server::config::Config;
+use server::event_log::{backup_path, read_game, replay, GameEvent, GameEventLogger, EVENT_LOG_BACKUPS};
+use server::service::TicTacToeService;
+use server::tictactoe::tic_tac_toe_client::TicTacToeClient;
+use server::tictactoe::{Join, ReplayRequest};
+use tonic::Code;
+
+fn moved(symbol: &str, position: u8) -> GameEvent {
+    GameEvent::MoveMade { symbol: symbol.into(), position, mark: symbol.into() }
+}
+
+fn started() -> GameEvent {
+    GameEvent::GameStarted { board_size: 3, win_length: 3, first_player: "X".into(), players: Vec::new() }
+}
+
+#[test]
+fn replay_rebuilds_one_state_per_event() {
+    let events = [
+        started(),
+        moved("X", 4),
+        GameEvent::PlayerDisconnected { symbol: "O".into() },
+        moved("O", 0),
+        GameEvent::GameEnded { status: "X_win_by_resignation".into() },
+    ];
+    let states = replay("7", &events).unwrap();
+    assert_eq!(states.len(), 5);
+    assert!(states[0].board.iter().all(String::is_empty) && states[0].status == "ongoing" && states[0].next_player == "X");
+    assert_eq!((states[1].board[4].as_str(), states[1].next_player.as_str()), ("X", "O"));
+    assert_eq!(states[2].info_message, "Player O disconnected.");
+    assert_eq!((states[3].board[0].as_str(), states[3].history.len()), ("O", 2));
+    assert!(states[3].info_message.is_empty());
+    assert_eq!(states[4].status, "X_win_by_resignation");
+    assert!(states.iter().all(|s| s.game_id == "7"));
+    assert_eq!(states.iter().map(|s| s.move_number).collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+
+    assert_eq!(replay("7", &[moved("X", 4)]), None);
+}
+
+#[tokio::test]
+async fn full_log_is_rotated_and_read_back_in_order() {
+    let dir = scenario::temp_dir("events-rotate");
+    let path = dir.join("events.jsonl");
+    // 한 줄이 100바이트 안팎이므로 파일마다 두세 줄씩 들어감
+    let logger = GameEventLogger::open(&path, 250).unwrap();
+    logger.log("1", started());
+    for position in 0..8 {
+        logger.log("1", moved(if position % 2 == 0 { "X" } else { "O" }, position));
+    }
+    logger.flush().await;
+
+    for n in 1..=EVENT_LOG_BACKUPS {
+        assert!(backup_path(&path, n).exists(), "{} 번째 이전 파일이 없음", n);
+    }
+    assert!(!backup_path(&path, EVENT_LOG_BACKUPS + 1).exists());
+    for file in std::iter::once(path.clone()).chain((1..=EVENT_LOG_BACKUPS).map(|n| backup_path(&path, n))) {
+        assert!(std::fs::metadata(&file).unwrap().len() <= 250, "{}", file.display());
+    }
+    // 가장 오래된 줄(게임 시작과 첫 수들)은 밀려나 지워졌지만 남은 수는 순서대로 읽힘
+    let events = read_game(&path, "1").unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+    let positions: Vec<u8> = events
+        .iter()
+        .map(|event| match event {
+            GameEvent::MoveMade { position, .. } => *position,
+            other => panic!("unexpected event {:?}", other),
+        })
+        .collect();
+    assert!(!positions.is_empty() && positions.len() < 8, "{:?}", positions);
+    assert!(positions.windows(2).all(|pair| pair[1] == pair[0] + 1), "{:?}", positions);
+    assert_eq!(positions.last(), Some(&7));
+}
+
+#[tokio::test]
+async fn replay_game_streams_the_logged_game() {
+    let dir = scenario::temp_dir("events-replay");
+    let path = dir.join("events.jsonl");
+    let logger = Arc::new(GameEventLogger::open(&path, 1 << 20).unwrap());
+    // 메모리에 남긴 게임이 아니라 로그에서 다시 만들도록 버퍼를 끔
+    let config = Config { replay_buffer_games: 0, ..Config::default() };
+    let channel = scenario::connect(&scenario::start_server(TicTacToeService::new(config).with_event_log(logger))).await;
+    let alice = TestPlayer::join(channel.clone(), Join::default()).await;
+    let mut bob = TestPlayer::join(channel.clone(), Join::default()).await;
+
+    // X: 0, 1, 2 / O: 3, 4
+    let last = play_alternately(&alice, &mut bob, &[0, 3, 1, 4, 2]).await;
+    assert_eq!(last.status, "X_win");
+    let game_id = last.game_id;
+
+    let mut client = TicTacToeClient::new(channel);
+    let mut replayed = client.replay_game(ReplayRequest { game_id: game_id.clone(), paced: Some(false) }).await.unwrap().into_inner();
+    let mut states = Vec::new();
+    while let Some(state) = replayed.message().await.unwrap() {
+        states.push(state);
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+    // 게임 시작, 수 다섯 개, 게임 종료
+    assert_eq!(states.len(), 7, "{:#?}", states);
+    assert!(states[0].board.iter().all(String::is_empty));
+    assert_eq!(states[1].board[0], "X");
+    assert_eq!(states[5].board, ["X", "X", "X", "O", "O", "", "", "", ""]);
+    assert_eq!(states[6].status, "X_win");
+    assert!(states.iter().all(|s| s.game_id == game_id));
+
+    let error = client.replay_game(ReplayRequest { game_id: "999".into(), paced: Some(false) }).await.unwrap_err();
+    assert_eq!(error.code(), Code::NotFound);
+}
@@ -0,0 +1,114 @@
+mod scenario;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use scenario::{play_alternately, TestPlayer};
+use server::config::Config;
+use server::events::{fold, GameEvent};
+use server::game::SharedGame;
+use server::service::TicTacToeService;
+use server::tictactoe::history_event::Event;
+use server::tictactoe::tic_tac_toe_client::TicTacToeClient;
+use server::tictactoe::{GameHistoryRequest, GameState, Join};
+use tokio::sync::mpsc;
+use tonic::{Code, Status};
+
+/// 이벤트를 접은 상태가 게임의 보드, 상태, 차례와 같은지 확인
+/// (끝난 게임의 차례는 서버가 넘기지 않으므로 진행 중일 때만 비교)
+fn assert_fold_matches(game: &SharedGame) {
+    let folded = fold(game.events());
+    assert_eq!(folded.board, game.board, "{:?}", game.events());
+    assert_eq!(folded.status, game.status, "{:?}", game.events());
+    if game.status == "ongoing" {
+        assert_eq!(folded.next_player, game.next_player, "{:?}", game.events());
+    }
+}
+
+/// 임의의 빈 칸에 두거나 가끔 기권하며 한 판을 끝까지 둠
+async fn random_game(rng: &mut StdRng, size: usize) -> SharedGame {
+    let mut game = SharedGame::new("1".into(), size, size.min(4));
+    let (tx, _rx) = mpsc::channel::<Result<GameState, Status>>(1024);
+    assert_fold_matches(&game);
+    game.join_player(&Join::default(), tx.clone(), None).await.unwrap();
+    assert_fold_matches(&game);
+    game.join_player(&Join::default(), tx, None).await.unwrap();
+    assert_fold_matches(&game);
+    while game.status == "ongoing" {
+        let symbol = game.next_player.clone();
+        if rng.gen_ratio(1, 30) {
+            game.resign(&symbol);
+        } else {
+            let empty: Vec<usize> = (0..game.board.len()).filter(|&i| game.board[i].is_empty()).collect();
+            game.apply_move(&symbol, empty[rng.gen_range(0..empty.len())]).unwrap();
+        }
+        assert_fold_matches(&game);
+    }
+    game
+}
+
+#[tokio::test]
+async fn folding_events_rebuilds_random_games() {
+    let mut rng = StdRng::seed_from_u64(291);
+    for round in 0..200 {
+        let game = random_game(&mut rng, 3 + round % 3).await;
+        assert!(matches!(game.events().first(), Some(GameEvent::GameCreated { .. })));
+        assert!(matches!(game.events().last(), Some(GameEvent::GameEnded { .. })));
+    }
+}
+
+#[tokio::test]
+async fn apply_is_pure_and_folds_in_any_split() {
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..50 {
+        let game = random_game(&mut rng, 3).await;
+        let events = game.events();
+        let whole = fold(events);
+        for split in 0..=events.len() {
+            let prefix = fold(&events[..split]);
+            let before = prefix.clone();
+            let rest = events[split..].iter().fold(prefix.clone(), |state, event| state.apply(event));
+            assert_eq!(prefix, before);
+            assert_eq!(rest, whole);
+        }
+    }
+}
+
+#[tokio::test]
+async fn reset_starts_a_new_event_list() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut game = random_game(&mut rng, 3).await;
+    game.reset();
+    assert_eq!(game.events().len(), 1);
+    assert_fold_matches(&game);
+}
+
+#[tokio::test]
+async fn get_history_lists_the_game_events() {
+    let channel = scenario::connect(&scenario::start_server(TicTacToeService::new(Config::default()))).await;
+    let alice = TestPlayer::join(channel.clone(), Join::default()).await;
+    let mut bob = TestPlayer::join(channel.clone(), Join::default()).await;
+
+    // X: 0, 1, 2 / O: 3, 4
+    let last = play_alternately(&alice, &mut bob, &[0, 3, 1, 4, 2]).await;
+    assert_eq!(last.status, "X_win");
+
+    let mut client = TicTacToeClient::new(channel);
+    let history = client.get_history(GameHistoryRequest { game_id: last.game_id.clone(), ..GameHistoryRequest::default() }).await.unwrap().into_inner();
+    assert_eq!(history.game_id, last.game_id);
+    let kinds: Vec<&str> = history
+        .events
+        .iter()
+        .map(|event| match event.event.as_ref().unwrap() {
+            Event::GameCreated(_) => "created",
+            Event::PlayerJoined(_) => "joined",
+            Event::MoveMade(_) => "move",
+            Event::GameEnded(_) => "ended",
+            Event::Swapped(_) => "swap",
+        })
+        .collect();
+    assert_eq!(kinds, ["created", "joined", "joined", "move", "move", "move", "move", "move", "ended"]);
+    assert!(matches!(history.events.last().unwrap().event, Some(Event::GameEnded(ref e)) if e.outcome == "X_win"));
+
+    let error = client.get_history(GameHistoryRequest { game_id: "999".into(), ..GameHistoryRequest::default() }).await.unwrap_err();
+    assert_eq!(error.code(), Code::NotFound);
+}
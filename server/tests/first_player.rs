@@ -0,0 +1,50 @@
+mod scenario;
+
+use scenario::Scenario;
+use server::config::{Config, FirstPlayer};
+
+fn config(first_player: FirstPlayer) -> Config {
+    Config { first_player, ..Config::default() }
+}
+
+#[test]
+fn o_moves_first_when_configured() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .expect_state(|s| s.status == "ongoing" && s.next_player == "O")
+        .move_("alice", 4)
+        .expect("alice", |s| s.error_message == "It's not your turn." && s.board[4].is_empty())
+        .move_("bob", 4)
+        .expect_state(|s| s.board[4] == "O" && s.next_player == "X")
+        .run(config(FirstPlayer::O));
+}
+
+#[test]
+fn alternate_flips_the_first_player_every_game() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .expect("alice", |s| s.status == "ongoing" && s.next_player == "X")
+        .player("carol")
+        .player("dave")
+        .expect("carol", |s| s.status == "ongoing" && s.game_id == "2" && s.next_player == "O")
+        .move_("dave", 0)
+        .expect("carol", |s| s.board[0] == "O")
+        .player("erin")
+        .player("frank")
+        .expect("erin", |s| s.status == "ongoing" && s.game_id == "3" && s.next_player == "X")
+        .run(config(FirstPlayer::Alternate));
+}
+
+#[test]
+fn house_bot_opens_when_it_moves_first() {
+    let config = config(FirstPlayer::O);
+    let timeout = config.quick_play_timeout();
+    Scenario::new()
+        .quick_player("alice")
+        .advance(timeout)
+        .expect("alice", |s| s.info_message.contains("house bot"))
+        .expect("alice", |s| s.board.iter().filter(|cell| *cell == "O").count() == 1 && s.next_player == "X")
+        .run(config);
+}
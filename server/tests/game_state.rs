@@ -0,0 +1,67 @@
+mod scenario;
+
+use scenario::{Scenario, TestServer};
+use server::config::Config;
+use server::service::TicTacToeService;
+use server::tictactoe::tic_tac_toe_server::TicTacToe;
+use server::tictactoe::{GameHistoryRequest, GameStateRequest};
+use tonic::{Code, Request};
+
+#[test]
+fn polled_state_matches_streamed_board() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .move_("alice", 4)
+        .move_("bob", 0)
+        .expect_state(|s| s.board[0] == "O")
+        .game_state("alice", |s| {
+            s.board[4] == "X" && s.board[0] == "O" && s.next_player == "X" && s.status == "ongoing" && s.your_symbol.is_empty()
+        })
+        .run(Config::default());
+}
+
+#[tokio::test]
+async fn unknown_game_is_not_found() {
+    let service = TicTacToeService::new(Config::default());
+    let status = service
+        .get_game_state(Request::new(GameStateRequest { game_id: "missing".into(), ..GameStateRequest::default() }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn malformed_page_token_is_rejected() {
+    let service = TicTacToeService::new(Config::default());
+    let request = server::tictactoe::ListGamesRequest { page_token: "not-a-token".into(), ..Default::default() };
+    let status = service.list_games(Request::new(request)).await.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+}
+
+#[tokio::test(start_paused = true)]
+async fn every_update_of_a_game_carries_the_same_game_id() {
+    let server = TestServer::start(Config::default()).await;
+    let mut x = server.join().await;
+    let mut o = server.join().await;
+    let mut seen = vec![x.next_update().await];
+    seen.push(o.update_where(|s| s.status == "ongoing").await);
+    for (turn, position) in [0, 3, 1, 4, 2].into_iter().enumerate() {
+        let mover = if turn % 2 == 0 { &mut x } else { &mut o };
+        mover.send_move(position).await;
+        seen.push(mover.update_where(|s| !s.board[position as usize].is_empty()).await);
+    }
+    while let Some(Ok(state)) = x.next_event().await {
+        seen.push(state);
+    }
+    assert_eq!(seen.last().unwrap().status, "X_win");
+    assert!(!x.game_id.is_empty());
+    assert!(seen.iter().all(|s| s.game_id == x.game_id), "{:?}", seen.iter().map(|s| &s.game_id).collect::<Vec<_>>());
+
+    // 단항 조회는 앞뒤 공백이 붙은 ID도 받음
+    let padded = format!("  {}\n", x.game_id);
+    let polled = server.service.get_game_state(Request::new(GameStateRequest { game_id: padded.clone(), ..GameStateRequest::default() })).await.unwrap().into_inner();
+    assert_eq!(polled.game_id, x.game_id);
+    let history = server.service.get_history(Request::new(GameHistoryRequest { game_id: padded, ..GameHistoryRequest::default() })).await.unwrap().into_inner();
+    assert_eq!(history.game_id, x.game_id);
+}
@@ -0,0 +1,85 @@
+mod scenario;
+
+use scenario::{eq, Scenario};
+use server::config::Config;
+
+#[test]
+fn first_player_waits_until_opponent_joins() {
+    Scenario::new()
+        .player("alice")
+        .expect("alice", |s| s.status == "waiting" && s.your_symbol == "X")
+        .player("bob")
+        .expect("bob", |s| s.status == "ongoing" && s.your_symbol == "O")
+        .expect("alice", |s| s.status == "ongoing" && s.next_player == "X")
+        .run(Config::default());
+}
+
+#[test]
+fn x_wins_with_top_row() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .move_("alice", 0)
+        .expect_state(|s| s.next_player == "O")
+        .move_("bob", 3)
+        .move_("alice", 1)
+        .move_("bob", 4)
+        .move_("alice", 2)
+        .expect_state(|s| s.status == "X_win" && s.board[..3] == ["X", "X", "X"])
+        .run(Config::default());
+}
+
+#[test]
+fn full_board_without_line_is_a_draw() {
+    let mut scenario = Scenario::new().player("alice").player("bob");
+    // X O X / X O O / O X X
+    for (name, pos) in [("alice", 0), ("bob", 1), ("alice", 2), ("bob", 4), ("alice", 3), ("bob", 5), ("alice", 7), ("bob", 6), ("alice", 8)] {
+        scenario = scenario.move_(name, pos);
+    }
+    scenario.expect_state(|s| s.status == "draw").run(Config::default());
+}
+
+#[test]
+fn rejects_move_out_of_turn() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .move_("bob", 4)
+        .expect("bob", |s| s.error_message == "It's not your turn.")
+        .move_("alice", 4)
+        .expect_state(|s| s.board[4] == "X")
+        .run(Config::default());
+}
+
+#[test]
+fn rejects_occupied_cell() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .move_("alice", 4)
+        .move_("bob", 4)
+        .expect("bob", |s| s.error_message == "Cell already occupied.")
+        .run(Config::default());
+}
+
+#[test]
+fn rejects_invalid_position() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .move_("alice", 9)
+        .expect("alice", |s| s.status == "error" && s.error_message == "Invalid position.")
+        .move_("alice", 8)
+        .expect_state(|s| s.status == "ongoing" && s.board[8] == "X")
+        .run(Config::default());
+}
+
+#[test]
+fn rejects_moves_before_game_starts() {
+    Scenario::new()
+        .player("alice")
+        .expect_status("alice", eq("waiting"))
+        .move_("alice", 0)
+        .expect("alice", |s| s.error_message.contains("not ongoing"))
+        .run(Config::default());
+}
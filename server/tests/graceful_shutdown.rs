@@ -0,0 +1,97 @@
+#![cfg(unix)]
+
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+use server::tictactoe::play_request::Action;
+use server::tictactoe::tic_tac_toe_client::TicTacToeClient;
+use server::tictactoe::{GameState, Join, Move, PlayRequest};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::transport::Channel;
+use tonic::Streaming;
+
+/// 테스트가 끝나면 (실패해도) 서버 프로세스를 정리
+struct ServerProcess(Child);
+
+impl Drop for ServerProcess {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// 빈 포트와 짧은 종료 대기 시간으로 실제 서버 바이너리를 띄우고 접속된 클라이언트를 반환
+async fn start_server() -> (ServerProcess, TicTacToeClient<Channel>) {
+    let addr = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+    let config = std::env::temp_dir().join(format!("tictactoe-graceful-shutdown-{}.toml", std::process::id()));
+    std::fs::write(&config, format!("listen_addr = \"{}\"\nshutdown_drain_timeout_secs = 1\n", addr)).unwrap();
+    let child = Command::new(env!("CARGO_BIN_EXE_server"))
+        .arg("--config")
+        .arg(&config)
+        .env("RUST_LOG", "warn")
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap();
+    let server = ServerProcess(child);
+
+    let endpoint = Channel::from_shared(format!("http://{}", addr)).unwrap();
+    for _ in 0..100 {
+        if let Ok(channel) = endpoint.connect().await {
+            let _ = std::fs::remove_file(&config);
+            return (server, TicTacToeClient::new(channel));
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("서버가 {}에서 응답하지 않음", addr);
+}
+
+/// 새 게임에 참가해 (요청 송신 측, 응답 스트림)을 반환
+async fn join(client: &mut TicTacToeClient<Channel>) -> (mpsc::Sender<PlayRequest>, Streaming<GameState>) {
+    let (tx, rx) = mpsc::channel(8);
+    tx.send(PlayRequest { action: Some(Action::Join(Join::default())) }).await.unwrap();
+    let updates = client.play(ReceiverStream::new(rx)).await.unwrap().into_inner();
+    (tx, updates)
+}
+
+/// 조건에 맞는 상태가 올 때까지 받음
+async fn next_matching(updates: &mut Streaming<GameState>, predicate: impl Fn(&GameState) -> bool) -> GameState {
+    let wait = async {
+        loop {
+            let state = updates.message().await.unwrap().expect("기대한 상태 전에 스트림이 끝남");
+            if predicate(&state) {
+                return state;
+            }
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(10), wait).await.expect("기대한 상태가 오지 않음")
+}
+
+#[tokio::test]
+async fn sigterm_ends_an_ongoing_game_with_a_terminal_state() {
+    let (mut server, mut client) = start_server().await;
+    let (alice, mut alice_updates) = join(&mut client).await;
+    let (bob, mut bob_updates) = join(&mut client).await;
+    next_matching(&mut alice_updates, |s| s.status == "ongoing").await;
+    alice.send(PlayRequest { action: Some(Action::Move(Move { player_id: "X".into(), position: 4, ..Move::default() })) }).await.unwrap();
+    next_matching(&mut bob_updates, |s| s.board[4] == "X").await;
+
+    let pid = server.0.id().to_string();
+    assert!(Command::new("kill").args(["-TERM", &pid]).status().unwrap().success());
+
+    let notice = next_matching(&mut alice_updates, |s| !s.info_message.is_empty()).await;
+    assert_eq!(notice.status, "ongoing");
+    assert!(notice.info_message.starts_with("The server is shutting down."), "{}", notice.info_message);
+
+    for updates in [&mut alice_updates, &mut bob_updates] {
+        let last = next_matching(updates, |s| s.status != "ongoing").await;
+        assert_eq!(last.status, "server_shutdown");
+        assert_eq!(last.board[4], "X");
+        assert!(updates.message().await.unwrap().is_none(), "끝난 게임의 스트림은 정상 종료");
+    }
+
+    // 연결이 남아 있으면 서버가 닫히기를 기다리므로 먼저 끊음
+    drop((alice, alice_updates, bob, bob_updates, client));
+    let exit = tokio::task::spawn_blocking(move || server.0.wait()).await.unwrap().unwrap();
+    assert!(exit.success(), "{:?}", exit);
+}
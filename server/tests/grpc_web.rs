@@ -0,0 +1,138 @@
+use axum::body::Body;
+use axum::http::{Method, Request};
+use hyper_util::client::legacy::Client;
+use hyper_util::rt::TokioExecutor;
+use server::config::Config;
+use server::grpc_web;
+use server::service::TicTacToeService;
+use server::tictactoe::tic_tac_toe_client::TicTacToeClient;
+use prost::Message;
+use server::tictactoe::{CreateGameRequest, GameState, GameStateRequest, SpectateRequest};
+use std::net::SocketAddr;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::{Channel, Server, Uri};
+use tonic::Code;
+use tonic_web::{GrpcWebClientLayer, GrpcWebLayer};
+use tower::Layer;
+
+/// main에 --web을 준 것처럼 HTTP/1.1과 gRPC-Web을 받는 서버 (`allowed_origins`가 비면 모든 출처)
+async fn start_with_origins(allowed_origins: &[String]) -> SocketAddr {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let service = TicTacToeService::new(Config::default());
+    let server = Server::builder()
+        .accept_http1(true)
+        .layer(grpc_web::cors(allowed_origins))
+        .layer(GrpcWebLayer::new())
+        .add_service(service.into_server());
+    tokio::spawn(server.serve_with_incoming(TcpListenerStream::new(listener)));
+    addr
+}
+
+async fn start() -> SocketAddr {
+    start_with_origins(&[]).await
+}
+
+/// 브라우저에서 보내는 것과 같은 CORS 사전 요청
+fn preflight(addr: SocketAddr, origin: &str) -> Request<Body> {
+    Request::builder()
+        .method(Method::OPTIONS)
+        .uri(format!("http://{}/tictactoe.TicTacToe/GetGameState", addr))
+        .header("origin", origin)
+        .header("access-control-request-method", "POST")
+        .header("access-control-request-headers", "authorization,content-type,x-grpc-web")
+        .body(Body::empty())
+        .unwrap()
+}
+
+/// gRPC-Web 본문의 프레임들 (플래그 1바이트 + 길이 4바이트 + 내용, 플래그 0x80은 트레일러)
+fn frames(mut body: &[u8]) -> Vec<(u8, Vec<u8>)> {
+    let mut frames = Vec::new();
+    while body.len() >= 5 {
+        let len = u32::from_be_bytes(body[1..5].try_into().unwrap()) as usize;
+        frames.push((body[0], body[5..5 + len].to_vec()));
+        body = &body[5 + len..];
+    }
+    frames
+}
+
+#[tokio::test]
+async fn grpc_web_clients_make_unary_and_streaming_calls_over_http1() {
+    let addr = start().await;
+    let http1 = Client::builder(TokioExecutor::new()).build_http();
+    let origin: Uri = format!("http://{}", addr).parse().unwrap();
+    let mut client = TicTacToeClient::with_origin(GrpcWebClientLayer::new().layer(http1), origin);
+
+    let created = client.create_game(CreateGameRequest::default()).await.unwrap().into_inner();
+    let state = client.get_game_state(GameStateRequest { game_id: created.game_id.clone(), ..GameStateRequest::default() }).await.unwrap().into_inner();
+    assert_eq!((state.game_id.as_str(), state.status.as_str()), (created.game_id.as_str(), "waiting"));
+
+    // 오류 상태는 트레일러 대신 응답 본문 끝에 실려 옴
+    let missing = client.get_game_state(GameStateRequest { game_id: "999".into(), ..GameStateRequest::default() }).await.unwrap_err();
+    assert_eq!(missing.code(), Code::NotFound);
+
+    let mut updates = client.spectate(SpectateRequest { game_id: created.game_id.clone() }).await.unwrap().into_inner();
+    let first = updates.message().await.unwrap().unwrap();
+    assert_eq!(first.game_id, created.game_id);
+
+    // 같은 포트에서 기존 gRPC(HTTP/2) 클라이언트도 그대로 동작
+    let channel = Channel::from_shared(format!("http://{}", addr)).unwrap().connect().await.unwrap();
+    let state = TicTacToeClient::new(channel).get_game_state(GameStateRequest { game_id: created.game_id, ..GameStateRequest::default() }).await.unwrap();
+    assert_eq!(state.into_inner().status, "waiting");
+}
+
+#[tokio::test]
+async fn browsers_pass_the_cors_preflight() {
+    let addr = start().await;
+    let http1 = Client::builder(TokioExecutor::new()).build_http::<Body>();
+    let response = http1.request(preflight(addr, "https://play.example.com")).await.unwrap();
+    assert!(response.status().is_success(), "{}", response.status());
+    let headers = response.headers();
+    assert_eq!(headers["access-control-allow-origin"], "*");
+    assert!(headers["access-control-allow-methods"].to_str().unwrap().contains("POST"));
+    let allowed = headers["access-control-allow-headers"].to_str().unwrap();
+    assert!(allowed.contains("authorization") && allowed.contains("x-grpc-web"), "{}", allowed);
+}
+
+#[tokio::test]
+async fn only_configured_origins_pass_the_cors_preflight() {
+    let addr = start_with_origins(&["https://play.example.com".into()]).await;
+    let http1 = Client::builder(TokioExecutor::new()).build_http::<Body>();
+    let allowed = http1.request(preflight(addr, "https://play.example.com")).await.unwrap();
+    assert_eq!(allowed.headers()["access-control-allow-origin"], "https://play.example.com");
+    let other = http1.request(preflight(addr, "https://evil.example.com")).await.unwrap();
+    assert!(!other.headers().contains_key("access-control-allow-origin"));
+}
+
+#[tokio::test]
+async fn hand_framed_grpc_web_request_gets_a_game_state_back() {
+    let addr = start().await;
+    let channel = Channel::from_shared(format!("http://{}", addr)).unwrap().connect().await.unwrap();
+    let created = TicTacToeClient::new(channel).create_game(CreateGameRequest::default()).await.unwrap().into_inner();
+
+    // 브라우저 라이브러리가 만드는 것과 같은 본문: 압축하지 않은 메시지 프레임 하나
+    let message = GameStateRequest { game_id: created.game_id.clone(), ..GameStateRequest::default() }.encode_to_vec();
+    let mut body = vec![0];
+    body.extend((message.len() as u32).to_be_bytes());
+    body.extend(message);
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(format!("http://{}/tictactoe.TicTacToe/GetGameState", addr))
+        .header("content-type", "application/grpc-web+proto")
+        .header("x-grpc-web", "1")
+        .body(Body::from(body))
+        .unwrap();
+    let http1 = Client::builder(TokioExecutor::new()).build_http::<Body>();
+    let response = http1.request(request).await.unwrap();
+    assert!(response.status().is_success(), "{}", response.status());
+    assert_eq!(response.headers()["content-type"], "application/grpc-web+proto");
+
+    let body = axum::body::to_bytes(Body::new(response.into_body()), usize::MAX).await.unwrap();
+    let frames = frames(&body);
+    assert_eq!(frames.len(), 2, "메시지 프레임 하나와 트레일러 프레임 하나");
+    let state = GameState::decode(frames[0].1.as_slice()).unwrap();
+    assert_eq!((frames[0].0, state.game_id.as_str(), state.status.as_str()), (0, created.game_id.as_str(), "waiting"));
+    let trailers = String::from_utf8(frames[1].1.clone()).unwrap();
+    assert_eq!(frames[1].0, 0x80);
+    assert!(trailers.contains("grpc-status:0"), "{}", trailers);
+}
@@ -0,0 +1,126 @@
+mod scenario;
+
+use scenario::{TestPlayer, TestServer};
+use server::config::Config;
+use server::egress::end_reason;
+use server::tictactoe::{EndReason, GameState};
+use tonic::Code;
+
+/// 두 플레이어를 같은 게임에 앉히고 둘 다 진행 중 상태를 받을 때까지 기다림
+async fn seated(server: &TestServer) -> (TestPlayer, TestPlayer) {
+    let mut x = server.join().await;
+    assert_eq!(x.next_update().await.status, "waiting");
+    let mut o = server.join().await;
+    o.update_where(|s| s.status == "ongoing").await;
+    x.update_where(|s| s.status == "ongoing").await;
+    assert_eq!((x.symbol.as_str(), o.symbol.as_str()), ("X", "O"));
+    (x, o)
+}
+
+/// 번갈아 수를 두고 두 플레이어가 마지막 수를 받은 상태를 반환
+async fn play(x: &mut TestPlayer, o: &mut TestPlayer, moves: &[i32]) -> (GameState, GameState) {
+    let mut last = None;
+    for (i, &position) in moves.iter().enumerate() {
+        let mover = if i % 2 == 0 { &*x } else { &*o };
+        mover.send_move(position).await;
+        let marks = i + 1;
+        let placed = move |s: &GameState| s.board.iter().filter(|c| !c.is_empty()).count() == marks;
+        last = Some((x.update_where(placed).await, o.update_where(placed).await));
+    }
+    last.expect("둘 수가 없음")
+}
+
+#[tokio::test(start_paused = true)]
+async fn x_wins_a_full_game() {
+    let server = TestServer::start(Config::default()).await;
+    let (mut x, mut o) = seated(&server).await;
+    let (seen_by_x, seen_by_o) = play(&mut x, &mut o, &[0, 3, 1, 4, 2]).await;
+    for state in [&seen_by_x, &seen_by_o] {
+        assert_eq!(state.status, "X_win");
+        assert_eq!(state.board[..3], ["X", "X", "X"]);
+    }
+    assert_eq!(x.next_event().await.map(|r| r.is_ok()), None, "끝난 게임의 스트림은 닫힘");
+}
+
+#[tokio::test(start_paused = true)]
+async fn full_board_without_a_line_is_a_draw() {
+    let server = TestServer::start(Config::default()).await;
+    let (mut x, mut o) = seated(&server).await;
+    // X O X / X O O / O X X
+    let (state, _) = play(&mut x, &mut o, &[0, 1, 2, 4, 3, 5, 7, 6, 8]).await;
+    assert_eq!(state.status, "draw");
+    assert!(state.board.iter().all(|c| !c.is_empty()));
+}
+
+#[tokio::test(start_paused = true)]
+async fn illegal_moves_are_rejected_without_changing_the_board() {
+    let server = TestServer::start(Config::default()).await;
+    let (mut x, mut o) = seated(&server).await;
+
+    o.send_move(4).await;
+    let state = o.next_update().await;
+    assert!(!state.error_message.is_empty(), "차례가 아닌 수");
+    assert!(state.board.iter().all(|c| c.is_empty()));
+
+    x.send_move(4).await;
+    x.update_where(|s| s.board[4] == "X").await;
+    o.update_where(|s| s.board[4] == "X").await;
+    o.send_move(4).await;
+    let state = o.next_update().await;
+    assert!(!state.error_message.is_empty(), "이미 찬 칸");
+    assert_eq!(state.next_player, "O");
+
+    o.send_move(9).await;
+    let state = o.next_update().await;
+    assert!(!state.error_message.is_empty(), "보드 밖의 칸");
+    assert_eq!(state.board.iter().filter(|c| !c.is_empty()).count(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn third_client_waits_in_a_new_game_or_is_refused_a_full_one() {
+    let server = TestServer::start(Config::default()).await;
+    let (x, _o) = seated(&server).await;
+
+    let mut third = server.join().await;
+    let state = third.next_update().await;
+    assert_eq!((state.status.as_str(), state.your_symbol.as_str()), ("waiting", "X"));
+    assert_ne!(third.game_id, x.game_id);
+
+    let mut fourth = server.join_game(&x).await;
+    assert_eq!(fourth.next_error().await.code(), Code::ResourceExhausted);
+}
+
+#[tokio::test(start_paused = true)]
+async fn disconnecting_mid_game_is_announced_and_then_abandons_the_game() {
+    let config = Config::default();
+    let grace = config.reconnect_grace();
+    let server = TestServer::start(config).await;
+    let (mut x, mut o) = seated(&server).await;
+    play(&mut x, &mut o, &[0]).await;
+
+    o.disconnect();
+    let state = x.update_where(|s| !s.opponent_connected).await;
+    assert_eq!((state.status.as_str(), state.board[0].as_str()), ("ongoing", "X"));
+
+    tokio::time::advance(grace).await;
+    let status = x.next_error().await;
+    assert_eq!(status.code(), Code::Aborted);
+    assert_eq!(end_reason(&status), Some(EndReason::GameAbandoned));
+}
+
+#[tokio::test(start_paused = true)]
+async fn reconnecting_within_the_grace_period_resumes_the_seat() {
+    let server = TestServer::start(Config::default()).await;
+    let (mut x, mut o) = seated(&server).await;
+    play(&mut x, &mut o, &[0]).await;
+
+    o.disconnect();
+    x.update_where(|s| !s.opponent_connected).await;
+    let mut o = server.reconnect(&o).await;
+    let state = o.next_update().await;
+    assert_eq!((state.your_symbol.as_str(), state.board[0].as_str(), state.next_player.as_str()), ("O", "X", "O"));
+    x.update_where(|s| s.opponent_connected).await;
+
+    o.send_move(4).await;
+    assert_eq!(x.update_where(|s| s.board[4] == "O").await.next_player, "X");
+}
@@ -0,0 +1,49 @@
+use server::config::Config;
+use server::health;
+use server::service::TicTacToeService;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::{Channel, Server};
+use tonic_health::pb::health_check_response::ServingStatus;
+use tonic_health::pb::health_client::HealthClient;
+use tonic_health::pb::HealthCheckRequest;
+
+/// 실제 서버처럼 헬스 체크와 게임 서비스를 함께 띄우고 (서비스, 헬스 체크 클라이언트)를 반환
+async fn start() -> (TicTacToeService, HealthClient<Channel>) {
+    let (reporter, health_server) = health::health_service().await;
+    let service = TicTacToeService::new(Config::default()).with_health(reporter);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        Server::builder()
+            .add_service(health_server)
+            .add_service(service.clone().into_server())
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    let channel = Channel::from_shared(format!("http://{}", addr)).unwrap().connect().await.unwrap();
+    (service, HealthClient::new(channel))
+}
+
+async fn status(client: &mut HealthClient<Channel>, service: &str) -> ServingStatus {
+    let request = HealthCheckRequest { service: service.to_string() };
+    client.check(request).await.unwrap().into_inner().status()
+}
+
+#[tokio::test]
+async fn reports_serving_once_the_listener_is_bound() {
+    let (service, mut client) = start().await;
+    assert_eq!(status(&mut client, "").await, ServingStatus::NotServing);
+
+    service.set_health(tonic_health::ServingStatus::Serving).await;
+    assert_eq!(status(&mut client, "").await, ServingStatus::Serving);
+    assert_eq!(status(&mut client, "tictactoe.TicTacToe").await, ServingStatus::Serving);
+}
+
+#[tokio::test]
+async fn reports_not_serving_after_shutdown_begins() {
+    let (service, mut client) = start().await;
+    service.set_health(tonic_health::ServingStatus::Serving).await;
+
+    service.shutdown().await;
+    assert_eq!(status(&mut client, "").await, ServingStatus::NotServing);
+    assert_eq!(status(&mut client, "tictactoe.TicTacToe").await, ServingStatus::NotServing);
+}
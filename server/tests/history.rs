@@ -0,0 +1,54 @@
+mod scenario;
+
+use scenario::Scenario;
+use server::config::Config;
+
+/// GameState의 기보를 (심볼, 칸) 목록으로
+fn moves(state: &server::tictactoe::GameState) -> Vec<(&str, i32)> {
+    state.history.iter().map(|m| (m.player_id.as_str(), m.position)).collect()
+}
+
+#[test]
+fn history_is_attached_only_to_final_update_in_play_order() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .move_("alice", 0)
+        .expect_state(|s| s.board[0] == "X" && s.history.is_empty())
+        .move_("bob", 3)
+        .move_("alice", 1)
+        .move_("bob", 4)
+        .move_("alice", 2)
+        .expect_state(|s| s.status == "X_win" && moves(s) == [("X", 0), ("O", 3), ("X", 1), ("O", 4), ("X", 2)])
+        .run(Config::default());
+}
+
+#[test]
+fn rejected_moves_never_enter_history() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .move_("bob", 4)
+        .move_("alice", 9)
+        .move_("alice", 4)
+        .move_("bob", 4)
+        .move_("alice", 0)
+        .move_("bob", 8)
+        .resign("alice")
+        .expect_state(|s| s.status == "O_win_by_resignation" && moves(s) == [("X", 4), ("O", 8)])
+        .run(Config::default());
+}
+
+#[test]
+fn spectators_and_late_pollers_see_history_after_game_over() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .spectator("carol", "alice")
+        .move_("alice", 4)
+        .offer_draw("bob")
+        .respond_draw("alice", true)
+        .expect("carol", |s| s.status == "draw_agreed" && moves(s) == [("X", 4)])
+        .game_state("alice", |s| moves(s) == [("X", 4)])
+        .run(Config::default());
+}
@@ -0,0 +1,54 @@
+mod scenario;
+
+use std::time::Duration;
+
+use scenario::{eq, Scenario};
+use server::config::Config;
+
+#[test]
+fn silent_player_ends_the_game_with_idle_timeout() {
+    let config = Config::default();
+    let idle = config.idle_timeout().unwrap();
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .spectator("carol", "alice")
+        .move_("alice", 4)
+        .expect_status("bob", eq("ongoing"))
+        .stop_reading("bob")
+        .advance(idle + Duration::from_secs(5))
+        .expect("alice", |s| s.status == "idle_timeout" && s.info_message == "Player O stopped responding. The game was closed.")
+        .expect_status("carol", eq("idle_timeout"))
+        .expect_closed("alice")
+        .expect_closed("carol")
+        .metrics(|text| text.contains(r#"ttt_games_finished_total{outcome="idle_timeout"} 1"#))
+        .run(config);
+}
+
+#[test]
+fn players_answering_heartbeats_are_never_idle() {
+    let config = Config::default();
+    let idle = config.idle_timeout().unwrap();
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .expect_status("bob", eq("ongoing"))
+        .advance(idle * 3)
+        .move_("alice", 0)
+        .expect("bob", |s| s.status == "ongoing" && s.board[0] == "X")
+        .run(config);
+}
+
+#[test]
+fn idle_timeout_can_be_turned_off() {
+    let config = Config { idle_timeout_secs: 0, heartbeat_interval_secs: 60, ..Config::default() };
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .expect_status("bob", eq("ongoing"))
+        .stop_reading("bob")
+        .advance(Duration::from_secs(45))
+        .move_("alice", 0)
+        .expect("alice", |s| s.status == "ongoing" && s.board[0] == "X")
+        .run(config);
+}
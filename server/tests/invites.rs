@@ -0,0 +1,163 @@
+mod scenario;
+
+use std::time::Duration;
+
+use scenario::{eq, Scenario};
+use server::config::Config;
+use server::invites::{generate_invite_code, InviteBook, INVITE_CODE_LEN};
+use server::service::TicTacToeService;
+use server::tictactoe::admin_service_server::AdminService;
+use server::tictactoe::tic_tac_toe_server::TicTacToe;
+use server::tictactoe::{
+    CreateGameRequest, ExportGameRequest, GameDetailRequest, GameHistoryRequest, GameStateRequest, JoinRequest, ListGamesRequest,
+    SpectateRequest,
+};
+use tonic::{Code, Request};
+
+async fn create_private(service: &TicTacToeService) -> (String, String) {
+    let created = service.create_private_game(Request::new(CreateGameRequest::default())).await.unwrap().into_inner();
+    (created.game_id, created.invite_code)
+}
+
+async fn join_with(service: &TicTacToeService, invite_code: &str) -> Result<server::tictactoe::GameState, Code> {
+    let request = Request::new(JoinRequest { invite_code: invite_code.to_string() });
+    service.join_private_game(request).await.map(|r| r.into_inner()).map_err(|status| status.code())
+}
+
+#[test]
+fn invite_codes_are_six_base62_characters() {
+    for _ in 0..100 {
+        let code = generate_invite_code();
+        assert_eq!(code.len(), INVITE_CODE_LEN);
+        assert!(code.chars().all(|c| c.is_ascii_alphanumeric()), "{}", code);
+    }
+}
+
+#[test]
+fn invitee_takes_seat_o_and_the_game_starts() {
+    Scenario::new()
+        .private_creator("alice")
+        .expect_status("alice", eq("waiting"))
+        .invitee("bob", "alice")
+        .expect("alice", |s| s.status == "ongoing" && s.your_symbol == "X")
+        .expect("bob", |s| s.status == "ongoing" && s.your_symbol == "O")
+        .move_("alice", 4)
+        .expect_state(|s| s.board[4] == "X")
+        .run(Config::default());
+}
+
+#[test]
+fn seat_o_cannot_be_taken_by_game_id() {
+    Scenario::new()
+        .private_creator("alice")
+        .join_game("mallory", "alice")
+        .expect_error("mallory", Code::PermissionDenied)
+        .player("carol")
+        .expect("carol", |s| s.game_id != "1")
+        .run(Config::default());
+}
+
+#[tokio::test]
+async fn second_join_with_the_same_code_is_rejected() {
+    let service = TicTacToeService::new(Config::default());
+    let (game_id, code) = create_private(&service).await;
+
+    let seat = join_with(&service, &code).await.unwrap();
+    assert_eq!((seat.game_id.as_str(), seat.your_symbol.as_str()), (game_id.as_str(), "O"));
+    assert!(!seat.session_token.is_empty());
+    assert_eq!(join_with(&service, &code).await.unwrap_err(), Code::AlreadyExists);
+    assert_eq!(join_with(&service, "nope00").await.unwrap_err(), Code::NotFound);
+}
+
+#[tokio::test(start_paused = true)]
+async fn codes_expire_after_the_ttl() {
+    // 참가자 없는 게임이 먼저 정리되지 않도록 유예 시간을 길게 둠
+    let config = Config { invite_ttl_secs: 60, reconnect_grace_secs: 3600, admin_token: Some("admin".into()), ..Config::default() };
+    let service = TicTacToeService::new(config);
+    let (game_id, code) = create_private(&service).await;
+
+    tokio::time::advance(Duration::from_secs(61)).await;
+    assert_eq!(join_with(&service, &code).await.unwrap_err(), Code::NotFound);
+    // 게임은 남아 있고 코드만 만료됨 (앉은 사람이 없는 초대 전용 게임은 관리자만 ID로 볼 수 있음)
+    let mut request = Request::new(GameDetailRequest { game_id });
+    request.metadata_mut().insert("x-admin-token", "admin".parse().unwrap());
+    let detail = service.admin().get_game_detail(request).await.unwrap().into_inner();
+    assert_eq!(detail.status, "waiting");
+}
+
+#[tokio::test]
+async fn invite_only_games_are_hidden_from_guessed_ids() {
+    let service = TicTacToeService::new(Config::default());
+    let (game_id, code) = create_private(&service).await;
+    let state = |session_token: &str| GameStateRequest { game_id: game_id.clone(), session_token: session_token.into() };
+
+    assert_eq!(service.get_game_state(Request::new(state(""))).await.unwrap_err().code(), Code::NotFound);
+    let history = GameHistoryRequest { game_id: game_id.clone(), ..GameHistoryRequest::default() };
+    assert_eq!(service.get_history(Request::new(history)).await.unwrap_err().code(), Code::NotFound);
+    let export = ExportGameRequest { game_id: game_id.clone(), ..ExportGameRequest::default() };
+    assert_eq!(service.export_game(Request::new(export)).await.unwrap_err().code(), Code::NotFound);
+    let spectate = service.spectate(Request::new(SpectateRequest { game_id: game_id.clone() })).await;
+    assert_eq!(spectate.map(|_| ()).unwrap_err().code(), Code::NotFound);
+    let listed = service.list_games(Request::new(ListGamesRequest::default())).await.unwrap().into_inner();
+    assert!(listed.games.is_empty());
+
+    // 자리에 앉은 플레이어는 자기 세션 토큰으로 조회할 수 있음
+    let seat = join_with(&service, &code).await.unwrap();
+    assert_eq!(service.get_game_state(Request::new(state(&seat.session_token))).await.unwrap().into_inner().game_id, game_id);
+    assert_eq!(service.get_game_state(Request::new(state("not-a-seat"))).await.unwrap_err().code(), Code::NotFound);
+}
+
+#[test]
+fn spectating_without_an_id_skips_private_games() {
+    Scenario::new()
+        .private_creator("alice")
+        .invitee("bob", "alice")
+        .expect_status("alice", eq("ongoing"))
+        .game_state("alice", |s| s.status == "ongoing")
+        .player("carol")
+        .player("dave")
+        .expect_status("carol", eq("ongoing"))
+        .observer("erin", None)
+        .expect("erin", |s| s.game_id == "2" && s.status == "ongoing")
+        .run(Config::default());
+}
+
+#[test]
+fn colliding_codes_are_regenerated() {
+    let mut book = InviteBook::default();
+    let ttl = Duration::from_secs(60);
+    assert_eq!(book.issue_with("1", ttl, || "AAAAAA".to_string()), "AAAAAA");
+
+    let mut candidates = ["AAAAAA", "AAAAAA", "BBBBBB"].into_iter();
+    let code = book.issue_with("2", ttl, || candidates.next().unwrap().to_string());
+    assert_eq!(code, "BBBBBB");
+    assert_eq!(book.resolve("AAAAAA").as_deref(), Some("1"));
+    assert_eq!(book.resolve("BBBBBB").as_deref(), Some("2"));
+}
+
+#[test]
+fn room_created_from_play_is_joined_by_code_in_join() {
+    Scenario::new()
+        .room_creator("alice")
+        .expect("alice", |s| s.status == "waiting" && s.your_symbol == "X" && s.invite_code.len() == INVITE_CODE_LEN)
+        .room_guest("bob", "alice")
+        .expect("bob", |s| s.status == "ongoing" && s.your_symbol == "O" && s.invite_code.is_empty())
+        .expect("alice", |s| s.status == "ongoing" && s.invite_code.is_empty())
+        .room_guest("carol", "alice")
+        .expect_error("carol", Code::AlreadyExists)
+        .room_guest_with_code("dave", "zzzzzz")
+        .expect_error("dave", Code::NotFound)
+        .run(Config::default());
+}
+
+#[test]
+fn room_code_is_dropped_when_the_room_empties() {
+    Scenario::new()
+        .room_creator("alice")
+        .expect_status("alice", eq("waiting"))
+        .disconnect("alice")
+        .advance(Duration::from_secs(31))
+        .room_guest("bob", "alice")
+        .expect_error("bob", Code::NotFound)
+        .run(Config::default());
+}
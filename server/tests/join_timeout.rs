@@ -0,0 +1,68 @@
+mod scenario;
+
+use std::time::Duration;
+
+use scenario::TestServer;
+use server::config::Config;
+
+/// 하트비트(15초)보다 길고, 응답 없는 하트비트로 끊기는 시각(30초)보다 짧은 참가 제한 시간
+fn config() -> Config {
+    Config { join_timeout_secs: 20, ..Config::default() }
+}
+
+#[tokio::test(start_paused = true)]
+async fn a_client_that_never_sends_anything_loses_its_seat() {
+    let server = TestServer::start(config()).await;
+    let mut x = server.join().await;
+    x.update_where(|s| s.status == "waiting").await;
+    let mut scanner = server.join_silent().await;
+    scanner.update_where(|s| s.status == "ongoing").await;
+    x.update_where(|s| s.status == "ongoing").await;
+    x.send_move(4).await;
+    x.update_where(|s| s.board[4] == "X").await;
+
+    tokio::time::advance(Duration::from_secs(20)).await;
+    let state = scanner.update_where(|s| s.status == "join_timeout").await;
+    assert_eq!(state.your_symbol, "O");
+    assert!(!state.error_message.is_empty());
+    assert!(scanner.next_event().await.is_none(), "자리를 잃은 연결은 닫힘");
+
+    // 남은 X는 빈 보드로 새 상대를 기다리고, 새로 온 플레이어가 같은 게임의 O 자리에 앉음
+    let state = x.update_where(|s| s.status == "waiting").await;
+    assert!(state.board.iter().all(|c| c.is_empty()));
+    let mut o = server.join().await;
+    let state = o.update_where(|s| s.status == "ongoing").await;
+    assert_eq!((state.your_symbol.as_str(), state.game_id.as_str()), ("O", x.game_id.as_str()));
+}
+
+#[tokio::test(start_paused = true)]
+async fn answering_heartbeats_keeps_a_player_who_has_not_moved_yet() {
+    let server = TestServer::start(config()).await;
+    let mut x = server.join().await;
+    x.update_where(|s| s.status == "waiting").await;
+    let mut o = server.join().await;
+    o.update_where(|s| s.status == "ongoing").await;
+
+    // 둘 다 수를 두지 않았지만 15초마다 오는 하트비트에 응답했으므로 자리를 지킴
+    // (한 번에 건너뛰면 응답할 틈 없이 하트비트 제한 시간이 지나므로 1초씩 진행)
+    for _ in 0..60 {
+        tokio::time::advance(Duration::from_secs(1)).await;
+    }
+    x.send_move(0).await;
+    let state = o.update_where(|s| s.board[0] == "X").await;
+    assert_eq!((state.status.as_str(), state.next_player.as_str()), ("ongoing", "O"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn a_silent_player_alone_in_a_game_closes_it() {
+    let server = TestServer::start(config()).await;
+    let mut scanner = server.join_silent().await;
+    scanner.update_where(|s| s.status == "waiting").await;
+
+    tokio::time::advance(Duration::from_secs(20)).await;
+    scanner.update_where(|s| s.status == "join_timeout").await;
+    // 빈 게임은 목록에서 빠지므로 다음 플레이어는 새 게임을 받음
+    let mut next = server.join().await;
+    next.update_where(|s| s.status == "waiting").await;
+    assert_ne!(next.game_id, scanner.game_id);
+}
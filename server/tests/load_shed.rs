@@ -0,0 +1,127 @@
+use std::time::Duration;
+
+use server::config::Config;
+use server::load_shed::{LatencyWindow, LoadShedder, OptionalWork, ShedController, SHED_ORDER};
+use server::service::TicTacToeService;
+use server::tictactoe::tic_tac_toe_server::TicTacToe;
+use server::tictactoe::{LeaderboardRequest, ListGamesRequest, PuzzleRequest, ReplayRequest, SpectateRequest};
+use tonic::{Code, Request};
+
+const BUDGET: Duration = Duration::from_millis(50);
+
+fn ms(millis: u64) -> Option<Duration> {
+    Some(Duration::from_millis(millis))
+}
+
+/// 꺼진 작업 목록 (SHED_ORDER 순)
+fn shed(load: &LoadShedder) -> Vec<OptionalWork> {
+    SHED_ORDER.into_iter().filter(|&w| !load.is_enabled(w)).collect()
+}
+
+/// 서비스가 실제로 UNAVAILABLE로 거부하는 작업 목록 (SHED_ORDER 순, 켜져 있으면 없는 게임이라 NOT_FOUND 등으로 끝남)
+async fn refused(service: &TicTacToeService) -> Vec<OptionalWork> {
+    let mut refused = Vec::new();
+    for work in SHED_ORDER {
+        let error = match work {
+            OptionalWork::Replays => service.replay_game(Request::new(ReplayRequest { game_id: "1".into(), ..ReplayRequest::default() })).await.err(),
+            OptionalWork::Puzzles => service.get_puzzle(Request::new(PuzzleRequest::default())).await.err(),
+            OptionalWork::StatsReads => service.get_leaderboard(Request::new(LeaderboardRequest::default())).await.err(),
+            OptionalWork::PollingReads => service.list_games(Request::new(ListGamesRequest::default())).await.err(),
+            OptionalWork::NewSpectators => service.spectate(Request::new(SpectateRequest { game_id: "1".into() })).await.err(),
+        };
+        if error.is_some_and(|status| status.code() == Code::Unavailable) {
+            refused.push(work);
+        }
+    }
+    refused
+}
+
+#[test]
+fn p95_uses_nearest_rank() {
+    let mut window = LatencyWindow::new(100);
+    assert_eq!(window.p95(), None);
+    (1..=100).for_each(|i| window.record(Duration::from_millis(i)));
+    assert_eq!(window.p95(), ms(95));
+    window.record(Duration::from_millis(500));
+    assert_eq!(window.p95(), ms(96));
+}
+
+#[test]
+fn controller_escalates_while_over_budget() {
+    let mut controller = ShedController::new(BUDGET);
+    assert_eq!(controller.observe(ms(80)), 1);
+    assert_eq!(controller.observe(ms(80)), 2);
+    (0..10).for_each(|_| {
+        controller.observe(ms(80));
+    });
+    assert_eq!(controller.level(), SHED_ORDER.len());
+}
+
+#[test]
+fn controller_holds_level_inside_hysteresis_band() {
+    let mut controller = ShedController::new(BUDGET);
+    controller.observe(ms(80));
+    controller.observe(ms(80));
+    // 예산 아래지만 절반 이상이면 유지
+    for _ in 0..10 {
+        assert_eq!(controller.observe(ms(30)), 2);
+    }
+}
+
+#[test]
+fn controller_recovers_one_level_per_calm_streak() {
+    let mut controller = ShedController::new(BUDGET);
+    controller.observe(ms(80));
+    controller.observe(ms(80));
+    assert_eq!(controller.observe(ms(10)), 2);
+    assert_eq!(controller.observe(None), 2);
+    assert_eq!(controller.observe(ms(10)), 1);
+    // 중간에 한 번이라도 높아지면 연속 횟수가 초기화됨
+    controller.observe(ms(10));
+    controller.observe(ms(30));
+    controller.observe(ms(10));
+    controller.observe(ms(10));
+    assert_eq!(controller.level(), 1);
+    assert_eq!(controller.observe(ms(10)), 0);
+}
+
+#[tokio::test]
+async fn injected_latency_sheds_in_documented_order_and_recovers_in_reverse() {
+    let service = TicTacToeService::new(Config { latency_budget_ms: BUDGET.as_millis() as u64, ..Config::default() });
+    let load = service.load_shedder();
+    assert!(shed(&load).is_empty());
+    assert!(refused(&service).await.is_empty());
+
+    for level in 1..=SHED_ORDER.len() {
+        (0..20).for_each(|_| load.record_move_latency(Duration::from_millis(120)));
+        assert_eq!(load.tick(), level);
+        assert_eq!(shed(&load), SHED_ORDER[..level]);
+        assert_eq!(refused(&service).await, SHED_ORDER[..level]);
+    }
+
+    for level in (0..SHED_ORDER.len()).rev() {
+        (0..3).for_each(|_| {
+            load.record_move_latency(Duration::from_millis(5));
+            load.tick();
+        });
+        assert_eq!(shed(&load), SHED_ORDER[..level]);
+        assert_eq!(refused(&service).await, SHED_ORDER[..level]);
+    }
+}
+
+#[tokio::test]
+async fn polling_reads_are_refused_while_shed() {
+    let service = TicTacToeService::new(Config::default());
+    let load = service.load_shedder();
+    while load.is_enabled(OptionalWork::PollingReads) {
+        load.record_move_latency(Duration::from_millis(500));
+        load.tick();
+    }
+    let status = service.list_games(Request::new(ListGamesRequest::default())).await.unwrap_err();
+    assert_eq!(status.code(), Code::Unavailable);
+
+    while !load.is_enabled(OptionalWork::PollingReads) {
+        load.tick();
+    }
+    assert!(service.list_games(Request::new(ListGamesRequest::default())).await.is_ok());
+}
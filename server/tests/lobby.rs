@@ -0,0 +1,107 @@
+mod scenario;
+
+use scenario::{eq, Scenario};
+use server::config::Config;
+use server::tictactoe::{GameStatusFilter, ListGamesRequest, ListGamesResponse};
+use tonic::Code;
+
+#[test]
+fn list_games_reports_waiting_and_ongoing_games() {
+    Scenario::new()
+        .list_games(|r| r.games.is_empty() && !r.truncated)
+        .player("alice")
+        .player("bob")
+        .player("carol")
+        .list_games(|r| {
+            let statuses: Vec<&str> = r.games.iter().map(|g| g.status.as_str()).collect();
+            statuses == ["ongoing", "waiting"] && r.games[0].player_count == 2 && r.games[1].player_count == 1
+        })
+        .run(Config::default());
+}
+
+#[test]
+fn join_by_game_id_and_refuse_full_game() {
+    Scenario::new()
+        .player("alice")
+        .player("carol")
+        .join_game("bob", "alice")
+        .expect_status("alice", eq("ongoing"))
+        .join_game("dave", "alice")
+        .expect_error("dave", Code::ResourceExhausted)
+        .run(Config::default());
+}
+
+#[test]
+fn unknown_game_id_is_not_found() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .disconnect("bob")
+        .disconnect("alice")
+        .advance(Config::default().reconnect_grace())
+        .list_games(|r| r.games.is_empty())
+        .join_game("carol", "alice")
+        .expect_error("carol", Code::NotFound)
+        .run(Config::default());
+}
+
+#[test]
+fn refuses_new_games_beyond_max_games() {
+    let config = Config { max_games: 1, ..Config::default() };
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .player("carol")
+        .expect_error("carol", Code::ResourceExhausted)
+        .run(config);
+}
+
+/// 목록의 게임 ID들
+fn ids(r: &ListGamesResponse) -> Vec<&str> {
+    r.games.iter().map(|g| g.game_id.as_str()).collect()
+}
+
+fn filtered(filter: GameStatusFilter) -> ListGamesRequest {
+    ListGamesRequest { filter: filter.into(), ..ListGamesRequest::default() }
+}
+
+fn page(size: i32, token: &str) -> ListGamesRequest {
+    ListGamesRequest { page_size: size, page_token: token.into(), ..ListGamesRequest::default() }
+}
+
+#[test]
+fn list_games_filters_by_status_and_paginates() {
+    let config = Config::default();
+    let quick_play_timeout = config.quick_play_timeout();
+    Scenario::new()
+        // 1: 진행 중
+        .player("alice")
+        .player("bob")
+        // 2: 기권으로 끝남
+        .player("carol")
+        .player("dave")
+        .resign("dave")
+        // 3: 합의 무승부
+        .player("erin")
+        .player("frank")
+        .offer_draw("erin")
+        .respond_draw("frank", true)
+        // 4: 봇 대전 진행 중
+        .quick_player("gina")
+        .advance(quick_play_timeout)
+        // 5: 상대 대기 중
+        .player("hank")
+        .list_games(|r| {
+            let statuses: Vec<&str> = r.games.iter().map(|g| g.status.as_str()).collect();
+            statuses == ["ongoing", "X_win_by_resignation", "draw_agreed", "ongoing", "waiting"]
+                && r.games.iter().all(|g| g.created_at_unix > 0)
+                && r.next_page_token.is_empty()
+        })
+        .list_games_with(filtered(GameStatusFilter::Waiting), |r| ids(r) == ["5"])
+        .list_games_with(filtered(GameStatusFilter::Ongoing), |r| ids(r) == ["1", "4"])
+        .list_games_with(filtered(GameStatusFilter::Finished), |r| ids(r) == ["2", "3"])
+        .list_games_with(page(2, ""), |r| ids(r) == ["1", "2"] && r.truncated && r.next_page_token == "2")
+        .list_games_with(page(2, "2"), |r| ids(r) == ["3", "4"] && r.next_page_token == "4")
+        .list_games_with(page(2, "4"), |r| ids(r) == ["5"] && !r.truncated && r.next_page_token.is_empty())
+        .run(config);
+}
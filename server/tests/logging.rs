@@ -0,0 +1,24 @@
+mod scenario;
+
+use scenario::{Captured, TestServer};
+use server::config::Config;
+
+#[tokio::test(flavor = "current_thread")]
+async fn joins_and_moves_are_logged_with_structured_fields() {
+    let (captured, _guard) = Captured::install();
+    let server = TestServer::start(Config::default()).await;
+    let mut x = server.join().await;
+    x.next_update().await;
+    let mut o = server.join_game(&x).await;
+    o.update_where(|s| s.status == "ongoing").await;
+
+    let started = captured.wait_for("플레이어 할당, 게임 시작").await;
+    assert!(started.contains("game_id=1") && started.contains("player_symbol=O") && started.contains("status=ongoing"), "{}", started);
+
+    x.send_move(4).await;
+    o.update_where(|s| s.board[4] == "X").await;
+    let moved = captured.wait_for("수 적용").await;
+    assert!(moved.contains("position=4") && moved.contains("status=ongoing"), "{}", moved);
+    // 수 처리는 그 플레이어 스트림의 스팬 안에서 남으므로 어느 게임의 누구인지도 함께 나옴
+    assert!(moved.contains("game_id=\"1\" player_symbol=X}"), "{}", moved);
+}
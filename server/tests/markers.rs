@@ -0,0 +1,65 @@
+mod scenario;
+
+use scenario::Scenario;
+use server::config::Config;
+use tonic::Code;
+
+#[test]
+fn chosen_markers_are_sent_with_every_state() {
+    Scenario::new()
+        .player_with_marker("alice", "🦊")
+        .player_with_marker("bob", "🐻")
+        .expect_state(|s| s.status == "ongoing" && s.x_symbol == "🦊" && s.o_symbol == "🐻")
+        .move_("alice", 0)
+        .move_("bob", 4)
+        // 보드 칸과 차례는 계속 자리 심볼로 표시
+        .expect_state(|s| s.board[0] == "X" && s.board[4] == "O" && s.next_player == "X")
+        .run(Config::default());
+}
+
+#[test]
+fn markers_default_to_the_seat_symbols() {
+    Scenario::new()
+        .player_with_marker("alice", "  ")
+        .player("bob")
+        .expect_state(|s| s.status == "ongoing" && s.x_symbol == "X" && s.o_symbol == "O")
+        .run(Config::default());
+}
+
+#[test]
+fn multi_code_point_graphemes_count_as_one_marker() {
+    // ZWJ 가족 이모지, 국기(지역 표시 문자 두 개), 결합 악센트, 피부색 수식자
+    for marker in ["👨‍👩‍👧", "🇰🇷", "e\u{301}", "👍🏽", "한"] {
+        Scenario::new()
+            .player_with_marker("alice", marker)
+            .expect("alice", move |s| s.status == "waiting" && s.x_symbol == marker)
+            .run(Config::default());
+    }
+}
+
+#[test]
+fn markers_that_are_not_one_visible_grapheme_are_refused() {
+    for marker in ["ab", "🦊🐻", "\u{301}", "\u{7}", "e\u{301}e"] {
+        Scenario::new()
+            .player_with_marker("alice", marker)
+            .expect_error("alice", Code::InvalidArgument)
+            .run(Config::default());
+    }
+}
+
+#[test]
+fn both_players_cannot_share_a_marker() {
+    Scenario::new()
+        .player_with_marker("alice", "🦊")
+        .player_with_marker("bob", "🦊")
+        .expect_error("bob", Code::InvalidArgument)
+        .player("carol")
+        .expect("carol", |s| s.status == "ongoing" && s.x_symbol == "🦊" && s.o_symbol == "O")
+        .run(Config::default());
+
+    // 빈 O 자리의 기본 글자도 상대의 글자로 취급
+    Scenario::new()
+        .player_with_marker("alice", "O")
+        .expect_error("alice", Code::InvalidArgument)
+        .run(Config::default());
+}
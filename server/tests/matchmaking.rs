@@ -0,0 +1,106 @@
+mod scenario;
+
+use std::time::Duration;
+
+use scenario::{eq, Scenario};
+use server::config::Config;
+use server::service::TicTacToeService;
+use server::tictactoe::tic_tac_toe_server::TicTacToe;
+use server::tictactoe::{LeaveRequest, MatchmakingRequest, MatchmakingUpdate};
+use tokio_stream::StreamExt;
+use tonic::{Code, Request, Status};
+
+type UpdateStream = std::pin::Pin<Box<dyn tokio_stream::Stream<Item = Result<MatchmakingUpdate, Status>> + Send>>;
+
+async fn enqueue(service: &TicTacToeService, name: &str) -> UpdateStream {
+    let request = Request::new(MatchmakingRequest { player_name: name.into() });
+    service.join_matchmaking(request).await.unwrap().into_inner()
+}
+
+async fn next(stream: &mut UpdateStream) -> MatchmakingUpdate {
+    tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .expect("업데이트 대기 시간 초과")
+        .expect("스트림이 닫힘")
+        .unwrap()
+}
+
+/// 지금까지 도착한 업데이트를 모두 받음 (스트림이 끝났으면 끝난 여부도 함께)
+async fn drain(stream: &mut UpdateStream) -> (Vec<MatchmakingUpdate>, bool) {
+    let mut updates = Vec::new();
+    loop {
+        match tokio::time::timeout(Duration::from_millis(100), stream.next()).await {
+            Ok(Some(update)) => updates.push(update.unwrap()),
+            Ok(None) => return (updates, true),
+            Err(_) => return (updates, false),
+        }
+    }
+}
+
+/// 가장 최근에 받은 대기 순번
+async fn position(stream: &mut UpdateStream) -> i32 {
+    drain(stream).await.0.last().expect("받은 업데이트 없음").queue_position
+}
+
+#[test]
+fn concurrently_queued_players_are_paired_and_can_play() {
+    Scenario::new()
+        .matched_players("alice", "bob")
+        .expect("alice", |s| s.status == "ongoing" && s.rated && s.your_symbol == "X")
+        .expect("bob", |s| s.game_id == "1" && s.your_symbol == "O")
+        .move_("alice", 4)
+        .move_("bob", 0)
+        .move_("alice", 2)
+        .move_("bob", 1)
+        .move_("alice", 6)
+        .expect_status("bob", eq("X_win"))
+        .run(Config::default());
+}
+
+#[tokio::test]
+async fn queue_positions_follow_arrival_order_and_leaving() {
+    let service = TicTacToeService::new(Config { max_games: 0, ..Config::default() });
+    // 게임을 만들 수 없으면 매칭이 보류되어 대기열에 남음
+    let mut alice = enqueue(&service, "alice").await;
+    let ticket = next(&mut alice).await.ticket;
+    let mut bob = enqueue(&service, "bob").await;
+    let mut carol = enqueue(&service, "carol").await;
+    assert_eq!(position(&mut alice).await, 1);
+    assert_eq!(position(&mut bob).await, 2);
+    assert_eq!(position(&mut carol).await, 3);
+
+    let left = service.leave_matchmaking(Request::new(LeaveRequest { ticket: ticket.clone() })).await.unwrap();
+    assert!(left.into_inner().removed);
+    assert!(drain(&mut alice).await.1, "나간 플레이어의 스트림은 끝나야 함");
+    assert_eq!(position(&mut bob).await, 1);
+    assert_eq!(position(&mut carol).await, 2);
+
+    // 스트림을 닫고 떠나도 남은 대기자의 순번이 당겨짐
+    drop(bob);
+    assert_eq!(position(&mut carol).await, 1);
+
+    let again = service.leave_matchmaking(Request::new(LeaveRequest { ticket })).await.unwrap();
+    assert!(!again.into_inner().removed);
+}
+
+#[tokio::test]
+async fn matched_players_receive_their_seat_and_stream_ends() {
+    let service = TicTacToeService::new(Config::default());
+    let mut alice = enqueue(&service, "alice").await;
+    assert_eq!(next(&mut alice).await.queue_position, 1);
+    let mut bob = enqueue(&service, "bob").await;
+
+    let for_alice = next(&mut alice).await.game.unwrap();
+    let for_bob = next(&mut bob).await.game.unwrap();
+    assert_eq!((for_alice.your_symbol.as_str(), for_bob.your_symbol.as_str()), ("X", "O"));
+    assert_eq!(for_alice.game_id, for_bob.game_id);
+    assert!(!for_alice.session_token.is_empty() && for_alice.session_token != for_bob.session_token);
+    assert!(alice.next().await.is_none() && bob.next().await.is_none());
+}
+
+#[tokio::test]
+async fn nameless_players_are_rejected() {
+    let service = TicTacToeService::new(Config::default());
+    let request = Request::new(MatchmakingRequest { player_name: "  ".into() });
+    assert_eq!(service.join_matchmaking(request).await.err().unwrap().code(), Code::InvalidArgument);
+}
@@ -0,0 +1,85 @@
+mod scenario;
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use scenario::Scenario;
+use server::config::Config;
+use server::metrics::Metrics;
+use server::metrics_http;
+use tonic::Code;
+use tower::ServiceExt;
+
+/// 지표 본문에서 `series`(이름과 라벨) 한 줄의 값
+fn sample(body: &str, series: &str) -> Option<f64> {
+    body.lines()
+        .find_map(|line| line.strip_prefix(series)?.strip_prefix(' '))
+        .and_then(|value| value.parse().ok())
+}
+
+/// 지표 본문의 `series` 값이 `expected`인지 검사
+fn has(series: &'static str, expected: f64) -> impl Fn(&str) -> bool {
+    move |body| sample(body, series) == Some(expected)
+}
+
+#[test]
+fn a_short_game_moves_the_counters() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .metrics(|body| has("ttt_active_games", 1.0)(body) && has("ttt_connected_players", 2.0)(body))
+        .move_("alice", 0)
+        .move_("bob", 0)
+        .expect_status("bob", scenario::eq("error"))
+        .move_("bob", 3)
+        .move_("alice", 1)
+        .move_("bob", 4)
+        .move_("alice", 2)
+        .expect_state(|s| s.status == "X_win")
+        .metrics(has("ttt_moves_total{result=\"accepted\"}", 5.0))
+        .metrics(has("ttt_moves_total{result=\"rejected\"}", 1.0))
+        .metrics(has("ttt_games_finished_total{outcome=\"X_win\"}", 1.0))
+        .metrics(has("ttt_games_finished_total{outcome=\"draw\"}", 0.0))
+        // 끝난 게임은 두 플레이어가 나가면 바로 정리됨
+        .disconnect("alice")
+        .disconnect("bob")
+        .metrics(|body| has("ttt_active_games", 0.0)(body) && has("ttt_connected_players", 0.0)(body))
+        .run(Config::default());
+}
+
+#[test]
+fn refused_joins_are_counted() {
+    Scenario::new()
+        .room_guest_with_code("mallory", "nope00")
+        .expect_error("mallory", Code::NotFound)
+        .metrics(has("ttt_rejected_joins_total", 1.0))
+        .metrics(has("ttt_connected_players", 0.0))
+        .run(Config::default());
+}
+
+#[tokio::test]
+async fn endpoint_serves_prometheus_text() {
+    let metrics = Arc::new(Metrics::default());
+    metrics.game_created();
+    metrics.game_finished("draw_agreed");
+    metrics.game_finished("ongoing"); // 끝난 상태가 아니면 무시
+
+    let request = Request::get("/metrics").body(Body::empty()).unwrap();
+    let response = metrics_http::router(metrics).oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let content_type = response.headers()[header::CONTENT_TYPE].to_str().unwrap().to_string();
+    assert!(content_type.starts_with("text/plain; version=0.0.4"), "{}", content_type);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(body.contains("# TYPE ttt_moves_total counter"));
+    assert_eq!(sample(&body, "ttt_active_games"), Some(1.0));
+    assert_eq!(sample(&body, "ttt_games_finished_total{outcome=\"draw_agreed\"}"), Some(1.0));
+    let finished: f64 = body
+        .lines()
+        .filter(|line| line.starts_with("ttt_games_finished_total{"))
+        .filter_map(|line| line.rsplit(' ').next()?.parse::<f64>().ok())
+        .sum();
+    assert_eq!(finished, 1.0);
+}
@@ -0,0 +1,127 @@
+use server::board;
+use server::bot::{self, BotStrategy, Easy, Hard, Medium};
+use server::config::Config;
+use server::game::{MoveOutcome, SharedGame};
+use server::service::TicTacToeService;
+use server::tictactoe::tic_tac_toe_server::TicTacToe;
+use server::tictactoe::{BotDifficulty, CreateGameRequest, GameMode, GameState, GameStateRequest, Join};
+use tokio::sync::mpsc;
+use tonic::{Request, Status};
+
+/// 3×3 보드의 여덟 줄 (가로 셋, 세로 셋, 대각선 둘)
+const LINES: [[usize; 3]; 8] = [[0, 1, 2], [3, 4, 5], [6, 7, 8], [0, 3, 6], [1, 4, 7], [2, 5, 8], [0, 4, 8], [2, 4, 6]];
+
+/// 두 자리가 모두 찬 3×3 게임
+async fn game(mode: GameMode) -> (SharedGame, mpsc::Receiver<Result<GameState, Status>>) {
+    let mut game = SharedGame::new("1".into(), 3, 3);
+    game.mode = mode;
+    let (tx, rx) = mpsc::channel(64);
+    game.join_player(&Join::default(), tx.clone(), None).await.unwrap();
+    game.join_player(&Join::default(), tx, None).await.unwrap();
+    assert_eq!(game.status, "ongoing");
+    (game, rx)
+}
+
+/// X와 O가 번갈아 `moves`를 둠 (마지막 수의 결과 반환)
+fn play(game: &mut SharedGame, moves: &[usize]) -> MoveOutcome {
+    let mut outcome = MoveOutcome::Continue;
+    for &pos in moves {
+        let symbol = game.next_player.clone();
+        outcome = game.apply_move(&symbol, pos).unwrap();
+    }
+    outcome
+}
+
+#[tokio::test]
+async fn completing_any_line_loses() {
+    for line in LINES {
+        let (mut game, _rx) = game(GameMode::Misere).await;
+        // O는 줄 밖의 두 칸에 둠 (O가 줄을 잇기 전에 X가 세 번째 칸을 채움)
+        let spare: Vec<usize> = (0..9).filter(|pos| !line.contains(pos)).take(2).collect();
+        let outcome = play(&mut game, &[line[0], spare[0], line[1], spare[1], line[2]]);
+        assert_eq!(outcome, MoveOutcome::Won { winner: "O".into(), line: line.to_vec() }, "{:?}", line);
+        assert_eq!(game.status, "O_win", "{:?}", line);
+    }
+}
+
+#[tokio::test]
+async fn the_same_lines_win_in_classic_mode() {
+    for line in LINES {
+        let (mut game, _rx) = game(GameMode::Classic).await;
+        let spare: Vec<usize> = (0..9).filter(|pos| !line.contains(pos)).take(2).collect();
+        play(&mut game, &[line[0], spare[0], line[1], spare[1], line[2]]);
+        assert_eq!(game.status, "X_win", "{:?}", line);
+    }
+}
+
+#[tokio::test]
+async fn a_line_made_by_filling_the_last_cell_wins() {
+    // X O O / O X O / X X X: 마지막 8번 칸이 두 줄(6-7-8, 0-4-8)을 완성하지만 다른 수를 둘 수 없었음
+    let (mut game, _rx) = game(GameMode::Misere).await;
+    assert_eq!(play(&mut game, &[0, 1, 4, 2, 6, 3, 7, 5]), MoveOutcome::Continue);
+    assert!(matches!(play(&mut game, &[8]), MoveOutcome::Won { ref winner, .. } if winner == "X"));
+    assert_eq!(game.status, "X_win");
+}
+
+#[tokio::test]
+async fn a_full_board_without_a_line_is_a_draw() {
+    // X O X / X O O / O X X
+    let (mut game, _rx) = game(GameMode::Misere).await;
+    assert_eq!(play(&mut game, &[0, 1, 2, 4, 3, 5, 7, 6, 8]), MoveOutcome::Draw);
+    assert_eq!(game.status, "draw");
+}
+
+#[tokio::test]
+async fn create_game_accepts_misere_on_any_board_size() {
+    let service = TicTacToeService::new(Config::default());
+    let request = CreateGameRequest { mode: GameMode::Misere as i32, board_size: 4, ..CreateGameRequest::default() };
+    let created = service.create_game(Request::new(request)).await.unwrap().into_inner();
+    assert_eq!((created.board_size, created.win_length), (4, 3));
+
+    let request = GameStateRequest { game_id: created.game_id, ..GameStateRequest::default() };
+    let state = service.get_game_state(Request::new(request)).await.unwrap().into_inner();
+    assert_eq!(state.mode, GameMode::Misere as i32);
+}
+
+/// 보드 문자열("X.O......")을 칸 목록으로
+fn cells(board: &str) -> Vec<String> {
+    board.chars().map(|c| if c == '.' { String::new() } else { c.to_string() }).collect()
+}
+
+#[test]
+fn bots_avoid_completing_a_line_when_another_cell_is_free() {
+    // X X . / X O O / . O . → X는 2(0-1-2)나 6(0-3-6)에 두면 줄을 이으므로 8
+    // O O . / X X O / X X . → O는 2(0-1-2)에 두면 줄을 이으므로 8 (그다음 X가 마지막 칸으로 이기더라도)
+    for (board, me, safe) in [("XX.XOO.O.", "X", 8), ("OO.XXOXX.", "O", 8)] {
+        assert_eq!(Hard.choose_move(&cells(board), 3, 3, me, true), Some(safe), "{}", board);
+        for _ in 0..20 {
+            assert_eq!(Medium.choose_move(&cells(board), 3, 3, me, true), Some(safe), "{}", board);
+        }
+    }
+}
+
+#[test]
+fn hard_never_loses_misere_to_random_play() {
+    let hard = bot::strategy(BotDifficulty::Hard);
+    for game in 0..300 {
+        // 절반은 Hard가 먼저, 절반은 무작위가 먼저 둠
+        let hard_symbol = if game % 2 == 0 { "X" } else { "O" };
+        let mut board = cells(".........");
+        let mut to_move = "X";
+        let mut last = to_move;
+        while board::winner(&board, 3, 3).is_none() && board.iter().any(String::is_empty) {
+            let strategy: &dyn BotStrategy = if to_move == hard_symbol { hard } else { &Easy };
+            let pos = strategy.choose_move(&board, 3, 3, to_move, true).unwrap();
+            board[pos] = to_move.to_string();
+            last = to_move;
+            to_move = if to_move == "X" { "O" } else { "X" };
+        }
+        // 마지막 빈 칸을 채우며 이은 줄이 아니라면 줄을 이은 쪽이 짐
+        let full = board.iter().all(|cell| !cell.is_empty());
+        let loser = board::winner(&board, 3, 3).filter(|_| !full);
+        assert_ne!(loser.as_deref(), Some(hard_symbol), "game {}: {:?}", game, board);
+        if full && board::winner(&board, 3, 3).is_some() {
+            assert_eq!(last, hard_symbol, "game {}: {:?}", game, board);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+mod scenario;
+
+use common::state_hash::state_hash;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use scenario::{TestPlayer, TestServer};
+use server::config::Config;
+use server::game::is_finished_status;
+use server::tictactoe::play_request::Action;
+use server::tictactoe::{GameState, Join, PlayRequest, Resign};
+
+/// 무작위로 둘 게임 수
+const GAMES: u64 = 20;
+
+/// 플레이어 하나와 지금까지 받은 (하트비트가 아닌) 모든 상태
+struct Player {
+    player: TestPlayer,
+    seen: Vec<GameState>,
+}
+
+impl Player {
+    async fn join(server: &TestServer, game_id: &str) -> Self {
+        let player = server.join_with(Join { game_id: game_id.into(), ..Join::default() }).await;
+        let mut player = Player { player, seen: Vec::new() };
+        player.receive_until(|_| true).await;
+        player
+    }
+
+    /// 마지막 상태가 조건을 만족할 때까지 받은 상태를 모두 모아 둠
+    async fn receive_until(&mut self, done: impl Fn(&GameState) -> bool) -> GameState {
+        if let Some(last) = self.seen.last().filter(|s| done(s)) {
+            return last.clone();
+        }
+        loop {
+            let state = self.player.next_update().await;
+            self.seen.push(state.clone());
+            if done(&state) {
+                return state;
+            }
+        }
+    }
+}
+
+/// 수 하나가 보드에 반영되었거나 게임이 끝난 상태인지
+fn reflects(marks: usize) -> impl Fn(&GameState) -> bool {
+    move |s| is_finished_status(&s.status) || s.board.iter().filter(|c| !c.is_empty()).count() == marks
+}
+
+#[tokio::test]
+async fn move_numbers_strictly_increase_over_random_games() {
+    let server = TestServer::start(Config::default()).await;
+    for seed in 0..GAMES {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut x = Player::join(&server, "").await;
+        let game_id = x.seen[0].game_id.clone();
+        assert_eq!(x.seen[0].move_number, 0, "처음 상태는 0번");
+        let mut o = Player::join(&server, &game_id).await;
+        let mut state = x.receive_until(|s| s.status == "ongoing").await;
+        o.receive_until(|s| s.status == "ongoing").await;
+
+        let mut marks = 0;
+        while !is_finished_status(&state.status) {
+            let empty: Vec<usize> = (0..state.board.len()).filter(|&i| state.board[i].is_empty()).collect();
+            let position = *empty.choose(&mut rng).unwrap() as i32;
+            let mover = if state.next_player == "X" { &x } else { &o };
+            mover.player.send_move(position).await;
+            marks += 1;
+            state = x.receive_until(reflects(marks)).await;
+            o.receive_until(reflects(marks)).await;
+        }
+
+        for (symbol, player) in [("X", &x), ("O", &o)] {
+            let numbers: Vec<u32> = player.seen.iter().map(|s| s.move_number).collect();
+            assert!(numbers.windows(2).all(|w| w[0] < w[1]), "seed {}: {} got {:?}", seed, symbol, numbers);
+            let own = state.history.iter().filter(|m| m.player_id == symbol).count() as u32;
+            assert_eq!(player.seen.last().unwrap().your_move_count, own, "seed {}: {}", seed, symbol);
+        }
+    }
+}
+
+#[tokio::test]
+async fn move_numbers_rise_through_joins_and_disconnects_and_restart_only_in_a_new_game() {
+    let server = TestServer::start(Config::default()).await;
+    let mut x = Player::join(&server, "").await;
+    let game_id = x.seen[0].game_id.clone();
+    let o = Player::join(&server, &game_id).await;
+    x.receive_until(|s| s.status == "ongoing" && s.opponent_connected).await;
+    x.player.send_move(4).await;
+    x.receive_until(reflects(1)).await;
+
+    // O의 연결이 끊긴 것을 알리는 업데이트도 번호를 올림
+    drop(o);
+    x.receive_until(|s| s.status == "ongoing" && !s.opponent_connected).await;
+    x.player.send(PlayRequest { action: Some(Action::Resign(Resign {})) }).await;
+    x.receive_until(|s| is_finished_status(&s.status)).await;
+
+    let numbers: Vec<u32> = x.seen.iter().map(|s| s.move_number).collect();
+    assert!(numbers.windows(2).all(|w| w[0] < w[1]), "{:?}", numbers);
+    for state in &x.seen {
+        assert_eq!(state.state_hash, state_hash(&state.board, &state.status, &state.next_player), "{:?}", state);
+    }
+
+    // 새로 만든 게임은 0번부터 다시 셈
+    let fresh = Player::join(&server, "").await;
+    assert_eq!(fresh.seen[0].move_number, 0);
+}
@@ -0,0 +1,209 @@
+mod scenario;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use scenario::TestServer;
+use server::config::Config;
+use server::game::SharedGame;
+use server::notation::{self, export_game_notation, parse_game_notation, NotationError, Symbol};
+use server::tictactoe::tic_tac_toe_server::TicTacToe;
+use server::tictactoe::{ExportGameRequest, GameMode, GameNotation, GameState, Join};
+use tokio::sync::mpsc;
+use tonic::{Code, Request};
+
+/// 무작위 문자열을 읽혀 볼 횟수
+const FUZZ_CASES: usize = 10_000;
+
+#[test]
+fn squares_are_lettered_by_row_and_numbered_by_column() {
+    let squares: Vec<String> = (0..9).map(notation::square).collect();
+    assert_eq!(squares, ["a1", "a2", "a3", "b1", "b2", "b3", "c1", "c2", "c3"]);
+    for (position, square) in squares.iter().enumerate() {
+        assert_eq!(notation::parse_square(square), Some(position));
+    }
+    for bad in ["", "a", "a0", "a4", "d1", "A1", "1a", "a11", "b2 "] {
+        assert_eq!(notation::parse_square(bad), None, "{:?}", bad);
+    }
+}
+
+#[test]
+fn export_and_parse_round_trip() {
+    let moves = vec![(Symbol::X, 0), (Symbol::O, 4), (Symbol::X, 8)];
+    assert_eq!(export_game_notation(&moves), "X:a1 O:b2 X:c3");
+    assert_eq!(parse_game_notation("X:a1 O:b2 X:c3"), Ok(moves.clone()));
+    // 공백은 몇 칸이든, 줄바꿈이어도 구분자
+    assert_eq!(parse_game_notation("  X:a1\tO:b2\n X:c3 "), Ok(moves));
+    assert_eq!(export_game_notation(&[]), "");
+    assert_eq!(parse_game_notation(""), Ok(Vec::new()));
+}
+
+#[test]
+fn malformed_moves_are_reported_with_their_number() {
+    assert_eq!(parse_game_notation("X:a1 Ob2"), Err(NotationError::Malformed { index: 2, token: "Ob2".into() }));
+    assert_eq!(parse_game_notation("x:a1"), Err(NotationError::UnknownSymbol { index: 1, symbol: "x".into() }));
+    assert_eq!(parse_game_notation("X:a1 O:d1"), Err(NotationError::InvalidSquare { index: 2, square: "d1".into() }));
+    assert_eq!(parse_game_notation("X:a1:b2"), Err(NotationError::InvalidSquare { index: 1, square: "a1:b2".into() }));
+    let error = parse_game_notation("X:a1 O:z9").unwrap_err().to_string();
+    assert!(error.contains("2번째") && error.contains("z9"), "{}", error);
+}
+
+#[test]
+fn replay_checks_turns_cells_and_game_over() {
+    let replay = |s: &str| notation::replay(&parse_game_notation(s).unwrap());
+    assert_eq!(replay("X:a1 X:b2").unwrap_err(), NotationError::OutOfTurn { index: 2 });
+    assert_eq!(replay("X:a1 O:a1").unwrap_err(), NotationError::CellOccupied { index: 2, square: "a1".into() });
+    assert_eq!(replay("X:a1 O:b1 X:a2 O:b2 X:a3 O:b3").unwrap_err(), NotationError::AfterGameOver { index: 6 });
+    // 첫 수의 심볼이 먼저 둠
+    assert!(replay("O:b2 X:a1").is_ok());
+}
+
+#[test]
+fn import_builds_the_final_state() {
+    let state = notation::import("X:a1 O:b1 X:a2 O:b2 X:a3").unwrap();
+    assert_eq!(state.status, "X_win");
+    assert_eq!(state.board, ["X", "X", "X", "O", "O", "", "", "", ""]);
+    assert_eq!(state.history.len(), 5);
+    assert_eq!(state.state_hash, common::state_hash::state_hash(&state.board, &state.status, &state.next_player));
+
+    let ongoing = notation::import("O:b2").unwrap();
+    assert_eq!((ongoing.status.as_str(), ongoing.next_player.as_str()), ("ongoing", "X"));
+    let empty = notation::import("").unwrap();
+    assert_eq!((empty.status.as_str(), empty.next_player.as_str(), empty.board.len()), ("ongoing", "X", 9));
+}
+
+#[test]
+fn fuzzed_strings_never_panic() {
+    let mut rng = StdRng::seed_from_u64(312);
+    let alphabet: Vec<char> = "XOxoabcdz0123489: \t\n:é한\u{0}".chars().collect();
+    for _ in 0..FUZZ_CASES {
+        let len = rng.gen_range(0..40);
+        let input: String = (0..len).map(|_| *alphabet.choose(&mut rng).unwrap()).collect();
+        // 읽을 수 있는 기보는 다시 적었을 때 같은 수 목록으로 읽혀야 함
+        if let Ok(moves) = parse_game_notation(&input) {
+            assert_eq!(parse_game_notation(&export_game_notation(&moves)), Ok(moves.clone()), "{:?}", input);
+            let _ = notation::replay(&moves);
+        }
+        let _ = notation::import(&input);
+    }
+    for _ in 0..FUZZ_CASES {
+        let len = rng.gen_range(0..64);
+        let input: String = (0..len).map(|_| rng.gen::<char>()).collect();
+        let _ = notation::import(&input);
+    }
+}
+
+#[test]
+fn random_legal_games_survive_a_round_trip() {
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..200 {
+        let mut cells: Vec<usize> = (0..9).collect();
+        cells.shuffle(&mut rng);
+        let mut symbol = if rng.gen() { Symbol::X } else { Symbol::O };
+        let mut moves = Vec::new();
+        for cell in cells {
+            moves.push((symbol, cell));
+            symbol = symbol.opponent();
+            if notation::replay(&moves).unwrap().len() > moves.len() + 3 {
+                break; // GameEnded까지 쌓임
+            }
+        }
+        let text = export_game_notation(&moves);
+        assert_eq!(parse_game_notation(&text), Ok(moves), "{}", text);
+        assert!(notation::import(&text).is_ok(), "{}", text);
+    }
+}
+
+#[tokio::test]
+async fn only_classic_3x3_games_can_be_exported() {
+    let mut game = SharedGame::new("1".into(), 3, 3);
+    let (tx, _rx) = mpsc::channel(64);
+    game.join_player(&Join::default(), tx.clone(), None).await.unwrap();
+    game.join_player(&Join::default(), tx, None).await.unwrap();
+    for (symbol, position) in [("X", 4), ("O", 0), ("X", 8)] {
+        game.apply_move(symbol, position).unwrap();
+    }
+    assert_eq!(notation::of(&game).as_deref(), Ok("X:b2 O:a1 X:c3"));
+
+    game.mode = GameMode::Ultimate;
+    assert_eq!(notation::of(&game), Err(NotationError::Unsupported));
+    assert_eq!(notation::of(&SharedGame::new("2".into(), 4, 3)), Err(NotationError::Unsupported));
+}
+
+#[tokio::test(start_paused = true)]
+async fn played_games_export_and_notation_imports_over_rpc() {
+    let server = TestServer::start(Config::default()).await;
+    let x = server.join().await;
+    let mut o = server.join().await;
+    o.update_where(|s| s.status == "ongoing").await;
+
+    let mut last = GameState::default();
+    for (turn, position) in [4, 0, 2].into_iter().enumerate() {
+        let mover = if turn % 2 == 0 { &x } else { &o };
+        mover.send_move(position).await;
+        last = o.update_where(|s| s.board.iter().filter(|cell| !cell.is_empty()).count() == turn + 1).await;
+    }
+    let export = ExportGameRequest { game_id: last.game_id.clone(), ..ExportGameRequest::default() };
+    let exported = server.service.export_game(Request::new(export)).await.unwrap().into_inner();
+    assert_eq!(exported.notation, "X:b2 O:a1 X:a3");
+
+    let imported = server.service.import_game(Request::new(exported)).await.unwrap().into_inner();
+    assert_eq!((imported.board, imported.next_player), (last.board, last.next_player));
+
+    let missing = ExportGameRequest { game_id: "nope".into(), ..ExportGameRequest::default() };
+    assert_eq!(server.service.export_game(Request::new(missing)).await.unwrap_err().code(), Code::NotFound);
+    let illegal = GameNotation { notation: "X:a1 O:a1".into() };
+    let error = server.service.import_game(Request::new(illegal)).await.unwrap_err();
+    assert_eq!(error.code(), Code::InvalidArgument);
+    assert!(error.message().contains("a1"), "{}", error.message());
+}
+
+/// 골든 파일: 첫 줄은 기보, 다음 세 줄은 다시 둔 보드(윗줄부터, 빈 칸은 "."), 마지막 줄은 상태
+fn check_golden(contents: &str) {
+    let lines: Vec<&str> = contents.lines().collect();
+    let [text, rows @ .., status] = lines.as_slice() else {
+        panic!("빈 골든 파일");
+    };
+    let moves = parse_game_notation(text).unwrap();
+    assert_eq!(export_game_notation(&moves), *text);
+
+    let state = notation::import(text).unwrap();
+    let board: Vec<String> = rows.iter().flat_map(|row| row.chars()).map(|c| if c == '.' { String::new() } else { c.to_string() }).collect();
+    assert_eq!(state.board, board, "{}", text);
+    assert_eq!(state.status, *status, "{}", text);
+    assert_eq!(state.history.len(), moves.len());
+}
+
+macro_rules! golden {
+    ($($name:ident => $file:literal,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                check_golden(include_str!(concat!("golden/notation/", $file)));
+            }
+        )*
+    };
+}
+
+golden! {
+    golden_x_top_row => "01-x-top-row.txt",
+    golden_x_middle_row => "02-x-middle-row.txt",
+    golden_x_bottom_row => "03-x-bottom-row.txt",
+    golden_x_left_column => "04-x-left-column.txt",
+    golden_x_center_column => "05-x-center-column.txt",
+    golden_x_right_column => "06-x-right-column.txt",
+    golden_x_main_diagonal => "07-x-main-diagonal.txt",
+    golden_x_anti_diagonal => "08-x-anti-diagonal.txt",
+    golden_o_top_row => "09-o-top-row.txt",
+    golden_o_main_diagonal => "10-o-main-diagonal.txt",
+    golden_o_first_top_row => "11-o-first-top-row.txt",
+    golden_draw_center_opening => "12-draw-center-opening.txt",
+    golden_draw_corner_opening => "13-draw-corner-opening.txt",
+    golden_draw_o_first => "14-draw-o-first.txt",
+    golden_empty => "15-empty.txt",
+    golden_center_opening => "16-center-opening.txt",
+    golden_corners_in_progress => "17-corners-in-progress.txt",
+    golden_o_first_in_progress => "18-o-first-in-progress.txt",
+    golden_x_wins_on_last_cell => "19-x-wins-on-last-cell.txt",
+    golden_x_fork => "20-x-fork.txt",
+}
@@ -0,0 +1,66 @@
+mod scenario;
+
+use scenario::Scenario;
+use server::config::Config;
+use server::game::SharedGame;
+use server::tictactoe::Join;
+use tokio::sync::mpsc;
+use tonic::Code;
+
+#[test]
+fn chosen_names_are_sent_with_every_state() {
+    Scenario::new()
+        .player_named("alice", "Alice")
+        .player_named("bob", "  Bob ")
+        .expect_state(|s| s.status == "ongoing" && s.player_x_name == "Alice" && s.player_o_name == "Bob")
+        .move_("alice", 0)
+        .expect_state(|s| s.board[0] == "X" && s.player_x_name == "Alice" && s.player_o_name == "Bob")
+        .run(Config::default());
+}
+
+#[test]
+fn names_default_to_the_seat() {
+    Scenario::new()
+        .player("alice")
+        .player_named("bob", "   ")
+        .expect_state(|s| s.status == "ongoing" && s.player_x_name == "Player X" && s.player_o_name == "Player O")
+        .run(Config::default());
+}
+
+#[test]
+fn long_names_are_truncated_to_twenty_characters() {
+    Scenario::new()
+        .player_named("alice", "Alexandria Ocasio-Cortez")
+        .expect("alice", |s| s.player_x_name == "Alexandria Ocasio-Co")
+        // 자른 끝의 공백은 남기지 않음
+        .player_named("bob", "Nineteen characters and more")
+        .expect("bob", |s| s.player_o_name == "Nineteen characters")
+        .run(Config::default());
+}
+
+#[test]
+fn names_with_non_printable_or_non_ascii_characters_are_refused() {
+    for name in ["Zoë", "한글", "tab\there", "bell\u{7}", "🦊"] {
+        Scenario::new()
+            .player_named("alice", name)
+            .expect_error("alice", Code::InvalidArgument)
+            .run(Config::default());
+    }
+}
+
+#[tokio::test]
+async fn reconnecting_without_a_name_keeps_the_previous_one() {
+    let mut game = SharedGame::new("1".into(), 3, 3);
+    let (tx, _rx) = mpsc::channel(64);
+    let join = Join { player_name: "Alice".into(), ..Join::default() };
+    game.join_player(&join, tx.clone(), None).await.unwrap();
+    let token = game.player("X").unwrap().session_token.clone();
+
+    let resume = Join { session_token: token.clone(), ..Join::default() };
+    game.join_player(&resume, tx.clone(), None).await.unwrap();
+    assert_eq!(game.create_update().player_x_name, "Alice");
+
+    let renamed = Join { session_token: token, player_name: "Ally".into(), ..Join::default() };
+    game.join_player(&renamed, tx, None).await.unwrap();
+    assert_eq!(game.create_update().player_x_name, "Ally");
+}
@@ -0,0 +1,39 @@
+mod scenario;
+
+use scenario::Scenario;
+use server::config::Config;
+use tonic::Code;
+
+#[test]
+fn first_player_gets_their_preference_and_x_still_moves_first() {
+    Scenario::new()
+        .player_preferring("alice", "O")
+        .expect("alice", |s| s.status == "waiting" && s.your_symbol == "O")
+        .player_preferring("bob", "O")
+        .expect("bob", |s| s.your_symbol == "X" && s.next_player == "X" && s.info_message == "O was taken, so you play X.")
+        .expect("alice", |s| s.status == "ongoing" && s.next_player == "X")
+        .move_("alice", 4)
+        .expect("alice", |s| s.error_message == "It's not your turn." && s.board[4].is_empty())
+        .move_("bob", 4)
+        .expect_state(|s| s.board[4] == "X" && s.next_player == "O")
+        .run(Config::default());
+}
+
+#[test]
+fn lone_o_player_is_matched_and_bot_takes_x() {
+    Scenario::new()
+        .player_preferring("alice", "o")
+        .player("bob")
+        .expect("bob", |s| s.status == "ongoing" && s.your_symbol == "X" && s.info_message.is_empty())
+        .quick_player("carol")
+        .expect("carol", |s| s.your_symbol == "X" && s.game_id == "2")
+        .run(Config::default());
+}
+
+#[test]
+fn unknown_symbol_is_refused() {
+    Scenario::new()
+        .player_preferring("alice", "Z")
+        .expect_error("alice", Code::InvalidArgument)
+        .run(Config::default());
+}
@@ -0,0 +1,121 @@
+use server::config::Config;
+use server::presets::{GameOptions, Preset, PresetError, PresetStore};
+use server::service::TicTacToeService;
+use server::tictactoe::tic_tac_toe_server::TicTacToe;
+use server::tictactoe::{self, CreateGameRequest, DefinePresetRequest, GameOptionsOverrides, GameStateRequest, ListPresetsRequest};
+use tonic::{Code, Request};
+
+/// 테스트마다 다른 임시 프리셋 파일 경로
+fn temp_presets_file(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("tictactoe-presets-{}-{}.toml", name, std::process::id()))
+}
+
+#[test]
+fn preset_values_and_overrides_are_layered_over_defaults() {
+    let store = PresetStore::default();
+
+    let standard = store.resolve("", &GameOptionsOverrides::default()).unwrap();
+    assert_eq!(standard, GameOptions::default());
+
+    let overrides = GameOptionsOverrides { clock_initial_secs: Some(30), ..Default::default() };
+    let blitz = store.resolve("blitz", &overrides).unwrap();
+    assert_eq!(blitz, GameOptions { clock_initial_secs: 30, clock_increment_secs: 2, ..GameOptions::default() });
+
+    let casual = store.resolve("casual", &GameOptionsOverrides::default()).unwrap();
+    assert_eq!(casual.max_takebacks, -1);
+    assert!(casual.hints_allowed && casual.bot_takeover_on_abandon);
+}
+
+#[test]
+fn overrides_outside_the_allowlist_are_rejected() {
+    let store = PresetStore::default();
+
+    let hints = GameOptionsOverrides { hints_allowed: Some(true), ..Default::default() };
+    assert_eq!(
+        store.resolve("tournament", &hints),
+        Err(PresetError::NotOverridable { preset: "tournament".into(), field: "hints_allowed" })
+    );
+    let takebacks = GameOptionsOverrides { max_takebacks: Some(3), ..Default::default() };
+    assert!(matches!(store.resolve("blitz", &takebacks), Err(PresetError::NotOverridable { .. })));
+    assert!(matches!(store.resolve("missing", &GameOptionsOverrides::default()), Err(PresetError::NotFound(_))));
+
+    // 허용된 항목이라도 값이 잘못되면 거부
+    let negative = GameOptionsOverrides { clock_initial_secs: Some(-5), ..Default::default() };
+    assert!(matches!(store.resolve("blitz", &negative), Err(PresetError::Invalid(_))));
+}
+
+#[test]
+fn custom_presets_persist_and_missing_options_use_defaults() {
+    let path = temp_presets_file("persist");
+    let _ = std::fs::remove_file(&path);
+
+    let mut store = PresetStore::load(Some(&path)).unwrap();
+    let bullet = Preset {
+        name: "bullet".into(),
+        options: GameOptions { clock_initial_secs: 15, ..GameOptions::default() },
+        overridable: vec!["clock_increment_secs".into()],
+    };
+    store.define(bullet.clone()).unwrap();
+    assert!(matches!(store.define(Preset { name: "blitz".into(), ..bullet.clone() }), Err(PresetError::Builtin(_))));
+    assert!(matches!(store.define(Preset { name: "bad name".into(), ..bullet.clone() }), Err(PresetError::Invalid(_))));
+
+    let reloaded = PresetStore::load(Some(&path)).unwrap();
+    assert_eq!(reloaded.get("bullet"), Some(bullet));
+
+    // 파일에 일부 옵션만 적어도 나머지는 기본값
+    std::fs::write(&path, "[[presets]]\nname = \"relaxed\"\noverridable = []\n[presets.options]\nhints_allowed = true\n").unwrap();
+    let partial = PresetStore::load(Some(&path)).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        partial.resolve("relaxed", &GameOptionsOverrides::default()).unwrap(),
+        GameOptions { hints_allowed: true, ..GameOptions::default() }
+    );
+}
+
+#[tokio::test]
+async fn created_game_carries_resolved_options() {
+    let service = TicTacToeService::new(Config::default());
+    let request = CreateGameRequest {
+        preset: "casual".into(),
+        overrides: Some(GameOptionsOverrides { hints_allowed: Some(false), ..Default::default() }),
+        ..CreateGameRequest::default()
+    };
+    let created = service.create_game(Request::new(request)).await.unwrap().into_inner();
+    let options = created.options.unwrap();
+    assert!(!options.hints_allowed && options.max_takebacks == -1);
+
+    let state = service
+        .get_game_state(Request::new(GameStateRequest { game_id: created.game_id, ..GameStateRequest::default() }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(state.status, "waiting");
+    assert_eq!(state.preset, "casual");
+    assert_eq!(state.options, Some(options));
+}
+
+#[tokio::test]
+async fn define_preset_requires_the_admin_token() {
+    let preset = tictactoe::Preset { name: "house-rules".into(), ..Default::default() };
+    let define = |token: Option<&str>| {
+        let mut request = Request::new(DefinePresetRequest { preset: Some(preset.clone()) });
+        if let Some(token) = token {
+            request.metadata_mut().insert("x-admin-token", token.parse().unwrap());
+        }
+        request
+    };
+
+    let disabled = TicTacToeService::new(Config::default());
+    assert_eq!(disabled.define_preset(define(Some("secret"))).await.unwrap_err().code(), Code::PermissionDenied);
+
+    let service = TicTacToeService::new(Config { admin_token: Some("secret".into()), ..Config::default() });
+    assert_eq!(service.define_preset(define(Some("wrong"))).await.unwrap_err().code(), Code::Unauthenticated);
+    service.define_preset(define(Some("secret"))).await.unwrap();
+
+    let presets = service.list_presets(Request::new(ListPresetsRequest {})).await.unwrap().into_inner().presets;
+    let names: Vec<(&str, bool)> = presets.iter().map(|p| (p.name.as_str(), p.builtin)).collect();
+    assert_eq!(
+        names,
+        [("casual", true), ("standard", true), ("blitz", true), ("tournament", true), ("house-rules", false)]
+    );
+}
@@ -0,0 +1,117 @@
+mod scenario;
+
+use std::collections::HashSet;
+
+use scenario::{connect, start_server};
+use server::board;
+use server::config::Config;
+use server::puzzles::{self, PuzzleBook};
+use server::service::TicTacToeService;
+use server::tictactoe::tic_tac_toe_client::TicTacToeClient;
+use server::tictactoe::{PuzzleDifficulty, PuzzleRequest, PuzzleSolution};
+use tonic::Code;
+
+/// `symbol`이 두면 바로 이기는 빈 칸
+fn winning_moves(cells: &[String], symbol: &str) -> HashSet<i32> {
+    (0..cells.len())
+        .filter(|&i| cells[i].is_empty())
+        .filter(|&i| {
+            let mut next = cells.to_vec();
+            next[i] = symbol.to_string();
+            board::winner(&next, 3, 3).as_deref() == Some(symbol)
+        })
+        .map(|i| i as i32)
+        .collect()
+}
+
+#[test]
+fn builtin_puzzles_have_exactly_the_answers_they_claim() {
+    let book = puzzles::builtin();
+    assert_eq!(book.puzzles().len(), 20);
+    for puzzle in book.puzzles() {
+        let cells = puzzle.cells();
+        let me = puzzle.winning_symbol.as_str();
+        let opponent = if me == "X" { "O" } else { "X" };
+        let answers: HashSet<i32> = puzzle.winning_positions.iter().copied().collect();
+        let expected = match puzzle.difficulty() {
+            PuzzleDifficulty::Easy => winning_moves(&cells, me),
+            PuzzleDifficulty::Medium => {
+                // 바로 이길 수는 없고, 상대가 이길 칸이 하나뿐이라 막을 수 있음
+                assert!(winning_moves(&cells, me).is_empty(), "{}", puzzle.id);
+                let threats = winning_moves(&cells, opponent);
+                assert_eq!(threats.len(), 1, "{}", puzzle.id);
+                threats
+            }
+            PuzzleDifficulty::Hard => {
+                assert!(winning_moves(&cells, me).is_empty() && winning_moves(&cells, opponent).is_empty(), "{}", puzzle.id);
+                (0..9)
+                    .filter(|&i| cells[i as usize].is_empty())
+                    .filter(|&i| {
+                        let mut next = cells.clone();
+                        next[i as usize] = me.to_string();
+                        winning_moves(&next, me).len() >= 2
+                    })
+                    .collect()
+            }
+            PuzzleDifficulty::Unspecified => unreachable!(),
+        };
+        assert_eq!(answers, expected, "{}", puzzle.id);
+    }
+    for difficulty in [PuzzleDifficulty::Easy, PuzzleDifficulty::Medium, PuzzleDifficulty::Hard] {
+        assert!(book.puzzles().iter().filter(|p| p.difficulty() == difficulty).count() >= 5, "{:?}", difficulty);
+    }
+}
+
+#[test]
+fn malformed_puzzles_are_rejected() {
+    let puzzle = |board: &str, symbol: &str, positions: &str| {
+        format!(
+            "[[puzzles]]\nid = \"p\"\ndifficulty = \"easy\"\nboard = \"{}\"\nwinning_symbol = \"{}\"\nwinning_positions = {}\nexplanation = \"\"\n",
+            board, symbol, positions
+        )
+    };
+    assert!(PuzzleBook::parse(&puzzle("XX./OO./...", "X", "[2]")).is_ok());
+
+    let error = PuzzleBook::parse(&puzzle("XX./OO./...", "O", "[2]")).unwrap_err();
+    assert!(error.contains("이번 차례는 X"), "{}", error);
+    let error = PuzzleBook::parse(&puzzle("XX./OO./...", "X", "[0]")).unwrap_err();
+    assert!(error.contains("정답 칸 0"), "{}", error);
+    let error = PuzzleBook::parse(&puzzle("XXX/OO./...", "O", "[5]")).unwrap_err();
+    assert!(error.contains("이미 승부가 난"), "{}", error);
+    let error = PuzzleBook::parse(&puzzle("XX./OO.", "X", "[2]")).unwrap_err();
+    assert!(error.contains("9칸"), "{}", error);
+
+    let twice = format!("{}{}", puzzle("XX./OO./...", "X", "[2]"), puzzle("XX./OO./...", "X", "[2]"));
+    assert!(PuzzleBook::parse(&twice).unwrap_err().contains("같은 id"));
+}
+
+#[tokio::test]
+async fn puzzles_are_served_by_difficulty_and_checked() {
+    let mut client = TicTacToeClient::new(connect(&start_server(TicTacToeService::new(Config::default()))).await);
+
+    for _ in 0..10 {
+        let request = PuzzleRequest { difficulty: PuzzleDifficulty::Hard as i32 };
+        let puzzle = client.get_puzzle(request).await.unwrap().into_inner();
+        assert_eq!(puzzle.difficulty(), PuzzleDifficulty::Hard);
+        assert_eq!(puzzle.board.len(), 9);
+        let expected = puzzles::builtin().get(&puzzle.puzzle_id).unwrap();
+        assert_eq!(puzzle.hint_count as usize, expected.winning_positions.len());
+    }
+    let any = client.get_puzzle(PuzzleRequest::default()).await.unwrap().into_inner();
+    assert!(puzzles::builtin().get(&any.puzzle_id).is_some());
+
+    // X 0, 1 / O 3, 4: X가 2에 두면 이김
+    let solve = |position| PuzzleSolution { puzzle_id: "win-row".into(), position };
+    let right = client.solve_puzzle(solve(2)).await.unwrap().into_inner();
+    assert!(right.correct && right.explanation.contains("top row"), "{:?}", right);
+    let wrong = client.solve_puzzle(solve(5)).await.unwrap().into_inner();
+    assert!(!wrong.correct && !wrong.explanation.is_empty(), "{:?}", wrong);
+    let taken = client.solve_puzzle(solve(0)).await.unwrap().into_inner();
+    assert_eq!((taken.correct, taken.explanation.as_str()), (false, "That square is already taken."));
+
+    assert_eq!(client.solve_puzzle(solve(9)).await.unwrap_err().code(), Code::OutOfRange);
+    let unknown = PuzzleSolution { puzzle_id: "nope".into(), position: 0 };
+    assert_eq!(client.solve_puzzle(unknown).await.unwrap_err().code(), Code::NotFound);
+    let bad = PuzzleRequest { difficulty: 42 };
+    assert_eq!(client.get_puzzle(bad).await.unwrap_err().code(), Code::InvalidArgument);
+}
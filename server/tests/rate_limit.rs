@@ -0,0 +1,175 @@
+mod scenario;
+
+use std::time::Duration;
+
+use scenario::{connect, eq, start_server, Scenario};
+use server::config::Config;
+use server::rate_limit::{ConnectionLimiter, MessageKind, TokenBucket, Verdict, ABUSE_WINDOW, WARNING_INTERVAL};
+use server::service::TicTacToeService;
+use server::tictactoe::play_request::Action;
+use server::tictactoe::tic_tac_toe_client::TicTacToeClient;
+use server::tictactoe::{EndReason, GameState, Join, Move, PlayRequest, SubmitMoveRequest};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::transport::Channel;
+use tonic::{Code, Streaming};
+
+fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+#[test]
+fn buckets_refill_at_the_rate_up_to_the_burst() {
+    let start = Instant::now();
+    let mut bucket = TokenBucket::new(5.0, 10.0, start);
+    assert!((0..10).all(|_| bucket.take(start)));
+    assert!(!bucket.take(start));
+    // 초당 5개: 100ms에는 반 개뿐이고 200ms가 지나야 하나
+    assert!(!bucket.take(start + ms(100)));
+    assert!(bucket.take(start + ms(200)));
+    assert!(!bucket.take(start + ms(200)));
+    // 오래 쉬어도 burst까지만 모임
+    let later = start + Duration::from_secs(60);
+    assert_eq!((0..20).filter(|_| bucket.take(later)).count(), 10);
+}
+
+#[test]
+fn chat_and_actions_have_separate_buckets() {
+    let start = Instant::now();
+    let mut limiter = ConnectionLimiter::new(1, 2, 100, start);
+    assert_eq!(limiter.check(MessageKind::Action, start), Verdict::Allow);
+    assert_eq!(limiter.check(MessageKind::Action, start), Verdict::Allow);
+    assert!(matches!(limiter.check(MessageKind::Action, start), Verdict::Drop { .. }));
+    assert_eq!(limiter.check(MessageKind::Chat, start), Verdict::Allow);
+    assert_eq!(limiter.check(MessageKind::Action, start + Duration::from_secs(1)), Verdict::Allow);
+}
+
+#[test]
+fn sustained_flooding_disconnects_and_warnings_are_throttled() {
+    let start = Instant::now();
+    let mut limiter = ConnectionLimiter::new(1, 1, 5, start);
+    assert_eq!(limiter.check(MessageKind::Action, start), Verdict::Allow);
+    let drop = |limiter: &mut ConnectionLimiter, at| match limiter.check(MessageKind::Action, at) {
+        Verdict::Drop { warn, dropped } => (warn, dropped),
+        verdict => panic!("{:?}", verdict),
+    };
+    // 처음 버릴 때만 경고하고, WARNING_INTERVAL이 지나야 다시 경고 (그 사이 토큰이 찬 만큼은 받아 줌)
+    assert_eq!(drop(&mut limiter, start), (true, 1));
+    assert_eq!(drop(&mut limiter, start + ms(500)), (false, 2));
+    assert_eq!(limiter.check(MessageKind::Action, start + WARNING_INTERVAL), Verdict::Allow);
+    assert_eq!(drop(&mut limiter, start + WARNING_INTERVAL), (true, 3));
+
+    // 1분이 지난 것은 세지 않음
+    let later = start + ABUSE_WINDOW + Duration::from_secs(1);
+    assert_eq!(limiter.check(MessageKind::Action, later), Verdict::Allow);
+    assert_eq!(drop(&mut limiter, later), (true, 2));
+    assert_eq!(drop(&mut limiter, later), (false, 3));
+    assert_eq!(drop(&mut limiter, later), (false, 4));
+    assert_eq!(limiter.check(MessageKind::Action, later), Verdict::Disconnect);
+}
+
+#[test]
+fn flooding_player_is_disconnected_without_blocking_the_game() {
+    let config = Config { message_rate_per_sec: 1, message_burst: 3, dropped_message_limit: 5, ..Config::default() };
+    let mut scenario = Scenario::new()
+        .player("alice")
+        .player("bob")
+        .expect_status("bob", eq("ongoing"))
+        // 처음 세 번만 처리되고 (첫 수만 둬짐) 나머지는 버려짐
+        .move_("alice", 0)
+        .move_("alice", 1)
+        .move_("alice", 2)
+        .move_("alice", 3)
+        // 게임 요청과 따로 세므로 채팅은 전달됨
+        .chat("alice", "sorry")
+        .expect("bob", |s| s.chat_message == "sorry" && s.board[0] == "X" && s.board[1..4].iter().all(String::is_empty))
+        // 시간이 지나면 다시 받음
+        .advance(Duration::from_secs(1))
+        .move_("bob", 4)
+        .expect("alice", |s| s.board[4] == "O");
+    // 남은 두 번은 차례가 아니라 거부되고, 그 뒤로 다섯 번째 버린 메시지에서 끊김
+    for _ in 0..7 {
+        scenario = scenario.move_("bob", 1);
+    }
+    scenario
+        .expect_end("bob", Code::ResourceExhausted, EndReason::RateLimited)
+        .expect("alice", |s| s.status == "ongoing" && !s.opponent_connected)
+        .run(config);
+}
+
+#[test]
+fn fractional_rates_and_multi_token_takes() {
+    let start = Instant::now();
+    let mut bucket = TokenBucket::new(0.5, 2.5, start);
+    // 모자라면 쓰지 않고 그대로 둠
+    assert!(!bucket.take_n(3.0, start));
+    assert!(bucket.take_n(2.0, start));
+    assert!(!bucket.take(start));
+    // 초당 0.5개: 남은 반 개에 1초 동안 반 개가 더 모여야 하나
+    assert!(!bucket.take(start + ms(900)));
+    assert!(bucket.take(start + ms(1000)));
+    // 오래 쉬어도 burst(2.5개)까지만 모임
+    assert!(bucket.take_n(2.5, start + Duration::from_secs(60)));
+}
+
+async fn join(client: &mut TicTacToeClient<Channel>) -> (mpsc::Sender<PlayRequest>, Streaming<GameState>, GameState) {
+    let (tx, rx) = mpsc::channel(128);
+    tx.send(PlayRequest { action: Some(Action::Join(Join::default())) }).await.unwrap();
+    let mut updates = client.play(ReceiverStream::new(rx)).await.unwrap().into_inner();
+    let first = updates.message().await.unwrap().unwrap();
+    (tx, updates, first)
+}
+
+#[tokio::test]
+async fn move_spam_is_answered_with_rate_limit_errors_after_the_capacity() {
+    let capacity = 5;
+    // 연결의 메시지 빈도 제한은 넉넉히 두어 자리의 수 빈도 제한만 보이게 함
+    let config = Config {
+        rate_limit_capacity: capacity,
+        rate_limit_refill_per_sec: 0.001,
+        message_rate_per_sec: 1000,
+        message_burst: 1000,
+        dropped_message_limit: 1000,
+        channel_buffer: 256,
+        ..Config::default()
+    };
+    let conn_tx = start_server(TicTacToeService::new(config));
+    let mut client = TicTacToeClient::new(connect(&conn_tx).await);
+    let (alice, mut alice_updates, first) = join(&mut client).await;
+    let (_bob, mut bob_updates, _) = join(&mut client).await;
+    assert_eq!(first.your_symbol, "X");
+    while alice_updates.message().await.unwrap().unwrap().status != "ongoing" {}
+
+    // 1ms 안에 수 100개를 한꺼번에 보냄
+    for position in 0..100 {
+        alice.try_send(PlayRequest { action: Some(Action::Move(Move { position: position % 9, ..Move::default() })) }).unwrap();
+    }
+    let mut errors = Vec::new();
+    while errors.len() < 99 {
+        let state = alice_updates.message().await.unwrap().unwrap();
+        if !state.error_message.is_empty() {
+            errors.push(state.error_message);
+        }
+    }
+    // 처음 capacity개는 검사까지 가서 첫 수만 둬지고(나머지는 차례가 아님), 그 뒤는 모두 빈도 제한
+    let limited = errors.iter().filter(|message| *message == "rate limit exceeded").count();
+    assert_eq!(limited, 100 - capacity as usize);
+    assert!(errors[..capacity as usize - 1].iter().all(|message| message == "It's not your turn."), "{:?}", errors);
+    let bob_view = loop {
+        let state = bob_updates.message().await.unwrap().unwrap();
+        if state.board[0] == "X" {
+            break state;
+        }
+    };
+    assert!(bob_view.board[1..].iter().all(String::is_empty));
+
+    // 제한은 연결이 아니라 자리에 붙어 있어 SubmitMove로 둬도 같음
+    let request = SubmitMoveRequest {
+        game_id: first.game_id.clone(),
+        session_token: first.session_token.clone(),
+        r#move: Some(Move { position: 4, ..Move::default() }),
+    };
+    let status = client.submit_move(request).await.unwrap_err();
+    assert_eq!(status.code(), Code::ResourceExhausted);
+}
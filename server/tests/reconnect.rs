@@ -0,0 +1,161 @@
+mod scenario;
+
+use std::time::Duration;
+
+use scenario::{eq, Scenario};
+use server::actor::GameHandle;
+use server::config::Config;
+use server::error::GameError;
+use server::game::SharedGame;
+use server::tictactoe::{GameState, Join, Move};
+use tokio::sync::mpsc;
+use tonic::Status;
+
+#[test]
+fn reconnect_with_session_token_restores_seat() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .move_("alice", 0)
+        .disconnect("bob")
+        .advance(Duration::from_secs(10))
+        .reconnect("bob")
+        .expect("bob", |s| s.your_symbol == "O" && s.board[0] == "X" && s.next_player == "O")
+        .move_("bob", 4)
+        .expect_state(|s| s.board[4] == "O")
+        .run(Config::default());
+}
+
+#[test]
+fn opponent_joins_leaves_and_rejoins_are_announced() {
+    Scenario::new()
+        .player("alice")
+        .expect("alice", |s| s.status == "waiting" && !s.opponent_connected)
+        .player("bob")
+        .expect("alice", |s| s.status == "ongoing" && s.opponent_connected)
+        .expect("bob", |s| s.status == "ongoing" && s.opponent_connected)
+        .disconnect("bob")
+        // 상태는 그대로 진행 중이고 상대의 연결만 끊김
+        .expect("alice", |s| s.status == "ongoing" && !s.opponent_connected)
+        .advance(Duration::from_secs(10))
+        .reconnect("bob")
+        .expect("alice", |s| s.status == "ongoing" && s.opponent_connected)
+        .expect("bob", |s| s.your_symbol == "O" && s.opponent_connected)
+        .move_("alice", 0)
+        .expect_state(|s| s.board[0] == "X" && s.opponent_connected)
+        .run(Config::default());
+}
+
+#[test]
+fn abandoned_game_is_removed_after_grace_period() {
+    let config = Config::default();
+    let grace = config.reconnect_grace();
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .expect_status("alice", eq("ongoing"))
+        .disconnect("bob")
+        .advance(grace + Duration::from_secs(1))
+        .list_games(|r| r.games.is_empty())
+        .reconnect("bob")
+        .expect_error("bob", tonic::Code::NotFound)
+        .run(config);
+}
+
+#[test]
+fn stale_token_is_refused_once_a_new_game_has_started() {
+    let grace = Config::default().reconnect_grace();
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .expect_status("alice", eq("ongoing"))
+        .disconnect("bob")
+        .advance(grace + Duration::from_secs(1))
+        .list_games(|r| r.games.is_empty())
+        .player("carol")
+        .player("dave")
+        .expect_status("carol", eq("ongoing"))
+        .reconnect("bob")
+        .expect_error("bob", tonic::Code::NotFound)
+        .move_("carol", 0)
+        .expect("dave", |s| s.board[0] == "X" && s.opponent_connected)
+        .run(Config::default());
+}
+
+#[test]
+fn client_that_stops_reading_is_treated_as_disconnected() {
+    let config = Config { heartbeat_interval_secs: 1, heartbeat_timeout_secs: 1, ..Config::default() };
+    let detect = config.heartbeat_interval() + config.heartbeat_timeout();
+    let grace = config.reconnect_grace();
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .expect_status("alice", eq("ongoing"))
+        .stop_reading("bob")
+        .advance(detect + grace + Duration::from_secs(1))
+        .expect_end("alice", tonic::Code::Aborted, server::tictactoe::EndReason::GameAbandoned)
+        .list_games(|r| r.games.is_empty())
+        .run(config);
+}
+
+/// 두 자리가 모두 찬 3×3 게임과 각 자리의 연결 번호
+async fn seated_game() -> (SharedGame, mpsc::Receiver<Result<GameState, Status>>, u64, u64) {
+    let mut game = SharedGame::new("1".into(), 3, 3);
+    let (tx, rx) = mpsc::channel(64);
+    let (_, x) = game.join_player(&Join::default(), tx.clone(), None).await.unwrap();
+    let (_, o) = game.join_player(&Join::default(), tx, None).await.unwrap();
+    (game, rx, x, o)
+}
+
+#[tokio::test]
+async fn winning_move_between_disconnect_and_cleanup_is_kept() {
+    let (mut game, _rx, _, o) = seated_game().await;
+    for (symbol, pos) in [("X", 0), ("O", 3), ("X", 1), ("O", 4)] {
+        game.apply_move(symbol, pos).unwrap();
+    }
+    let game = GameHandle::spawn(game);
+    // O의 스트림이 끝나 정리 태스크가 자리를 표시한 직후, 바로 다음 명령으로 온 X의 수가 게임을 끝냄
+    let (departure, won) = tokio::join!(game.disconnect("O", o), game.play_move("X", Move { position: 2, ..Move::default() }, ()));
+    let departure = departure.unwrap();
+    assert!(!departure.finished);
+    won.unwrap();
+    let state = game.snapshot().await;
+    assert_eq!(state.status, "X_win");
+    assert_eq!(state.board[..3], ["X", "X", "X"]);
+    // 정리 태스크는 같은 게임, 같은 연결일 때만 정리하며, 그동안 끝난 결과는 그대로 남음
+    assert!(game.with(move |game| game.abandoned("O", &departure)).await);
+    assert_eq!(game.with(move |game| game.mark_disconnected("O", o).map(|d| d.finished)).await, Some(true));
+}
+
+#[tokio::test]
+async fn cleanup_skips_a_reconnected_seat_or_a_reset_game() {
+    let (mut game, _rx, x, o) = seated_game().await;
+    let departure = game.mark_disconnected("O", o).unwrap();
+    // 같은 세션 토큰으로 재접속하면 새 연결 번호를 받으므로 이전 연결의 정리는 건너뜀
+    let token = game.player("O").unwrap().session_token.clone();
+    let (tx, _rx2) = mpsc::channel(64);
+    let (_, reconnected) = game.join_player(&Join { session_token: token, ..Join::default() }, tx, None).await.unwrap();
+    assert_ne!(reconnected, o);
+    assert!(!game.abandoned("O", &departure));
+    assert_eq!(game.mark_disconnected("O", o), None);
+
+    // 게임이 초기화된 뒤 새 플레이어가 앉아도 이전 세대의 정리는 건너뜀
+    let departure = game.mark_disconnected("X", x).unwrap();
+    game.reset();
+    let (tx, _rx3) = mpsc::channel(64);
+    game.join_player(&Join::default(), tx, None).await.unwrap();
+    assert!(!game.abandoned("X", &departure));
+}
+
+#[tokio::test]
+async fn token_from_before_a_reset_no_longer_resumes() {
+    let (mut game, _rx, _, _) = seated_game().await;
+    let stale = game.player("O").unwrap().session_token.clone();
+    game.reset();
+    let (tx, _rx2) = mpsc::channel(64);
+    game.join_player(&Join::default(), tx.clone(), None).await.unwrap();
+    game.join_player(&Join::default(), tx.clone(), None).await.unwrap();
+    let resumed = game.join_player(&Join { session_token: stale.clone(), ..Join::default() }, tx, None).await;
+    assert!(matches!(resumed, Err(GameError::SessionNotFound)));
+    assert_ne!(game.player("O").unwrap().session_token, stale);
+}
@@ -0,0 +1,43 @@
+mod scenario;
+
+use scenario::{play_alternately, TestServer};
+use server::config::Config;
+use server::record::GameRecorder;
+use server::service::TicTacToeService;
+use server::tictactoe::Join;
+
+#[tokio::test]
+async fn finished_game_is_appended_as_one_json_line() {
+    let dir = scenario::temp_dir("record-finished");
+    let path = dir.join("games.jsonl");
+    let server = TestServer::serve(TicTacToeService::new(Config::default()).with_recorder(GameRecorder::open(&path).unwrap())).await;
+    let alice = server.join_with(Join { player_id: "alice".into(), ..Join::default() }).await;
+    let mut bob = server.join_with(Join { player_id: "bob".into(), ..Join::default() }).await;
+
+    // X: 0, 1, 2 / O: 3, 4
+    let last = play_alternately(&alice, &mut bob, &[0, 3, 1, 4, 2]).await;
+    assert_eq!(last.status, "X_win");
+
+    server.service.shutdown().await;
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines.len(), 1, "{}", contents);
+
+    let record: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+    assert_eq!(record["game_id"], last.game_id.as_str());
+    assert_eq!(record["outcome"], "X_win");
+    assert_eq!(record["moves"].as_array().unwrap().len(), 5);
+    assert_eq!(record["moves"][4]["symbol"], "X");
+    assert_eq!(record["moves"][4]["position"], 2);
+    assert_eq!(record["players"][0]["player_id"], "alice");
+    assert_eq!(record["players"][1]["symbol"], "O");
+    assert!(record["ended_at_unix"].as_i64().unwrap() >= record["started_at_unix"].as_i64().unwrap());
+}
+
+#[test]
+fn unwritable_record_path_fails_at_startup() {
+    let path = std::env::temp_dir().join("tictactoe-no-such-dir").join("games.jsonl");
+    let error = GameRecorder::open(&path).err().expect("없는 디렉터리의 파일은 열 수 없음");
+    assert!(error.to_string().contains("tictactoe-no-such-dir"), "{}", error);
+}
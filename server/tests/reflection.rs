@@ -0,0 +1,52 @@
+use server::config::Config;
+use server::reflection;
+use server::service::TicTacToeService;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::{Channel, Server};
+use tonic_reflection::pb::v1::server_reflection_client::ServerReflectionClient;
+use tonic_reflection::pb::v1::server_reflection_request::MessageRequest;
+use tonic_reflection::pb::v1::server_reflection_response::MessageResponse;
+use tonic_reflection::pb::v1::ServerReflectionRequest;
+
+/// 게임 서비스와 리플렉션을 함께 띄우고 리플렉션 클라이언트를 반환
+async fn start() -> ServerReflectionClient<Channel> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        Server::builder()
+            .add_service(reflection::reflection_service().unwrap())
+            .add_service(TicTacToeService::new(Config::default()).into_server())
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    let channel = Channel::from_shared(format!("http://{}", addr)).unwrap().connect().await.unwrap();
+    ServerReflectionClient::new(channel)
+}
+
+/// 요청 하나를 보내고 첫 응답을 받음
+async fn ask(client: &mut ServerReflectionClient<Channel>, request: MessageRequest) -> MessageResponse {
+    let request = ServerReflectionRequest { host: String::new(), message_request: Some(request) };
+    let mut responses = client.server_reflection_info(tokio_stream::once(request)).await.unwrap().into_inner();
+    responses.message().await.unwrap().unwrap().message_response.unwrap()
+}
+
+#[tokio::test]
+async fn lists_the_game_and_health_services() {
+    let mut client = start().await;
+    let MessageResponse::ListServicesResponse(list) = ask(&mut client, MessageRequest::ListServices(String::new())).await else {
+        panic!("서비스 목록 응답이 아님");
+    };
+    let names: Vec<&str> = list.service.iter().map(|s| s.name.as_str()).collect();
+    for expected in ["tictactoe.TicTacToe", "tictactoe.AdminService", "grpc.health.v1.Health"] {
+        assert!(names.contains(&expected), "{} 없음: {:?}", expected, names);
+    }
+}
+
+#[tokio::test]
+async fn describes_message_types() {
+    let mut client = start().await;
+    let response = ask(&mut client, MessageRequest::FileContainingSymbol("tictactoe.GameState".into())).await;
+    let MessageResponse::FileDescriptorResponse(files) = response else {
+        panic!("파일 디스크립터 응답이 아님: {:?}", response);
+    };
+    assert!(!files.file_descriptor_proto.is_empty());
+}
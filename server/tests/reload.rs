@@ -0,0 +1,113 @@
+use std::collections::BTreeSet;
+
+use server::config::Config;
+use server::reload::{plan_reload, ChangeOutcome, FIELD_RULES};
+use server::service::TicTacToeService;
+use server::tictactoe::tic_tac_toe_server::TicTacToe;
+use server::tictactoe::{CreateGameRequest, ReloadOutcome, ReloadReportRequest};
+use tonic::{Code, Request};
+
+fn report_request(token: &str) -> Request<ReloadReportRequest> {
+    let mut request = Request::new(ReloadReportRequest {});
+    request.metadata_mut().insert("x-admin-token", token.parse().unwrap());
+    request
+}
+
+#[test]
+fn every_config_field_is_classified() {
+    // Option 항목도 직렬화되도록 모두 채운 설정
+    let config = Config {
+        presets_file: Some("presets.toml".into()),
+        admin_token: Some("secret".into()),
+        ratings_file: Some("ratings.toml".into()),
+        stats_file: Some("stats.toml".into()),
+        auth_secret: Some("0123456789abcdef0123456789abcdef".into()),
+        users_file: Some("users.toml".into()),
+        metrics_listen_addr: Some("127.0.0.1:9100".into()),
+        ..Config::default()
+    };
+    let toml::Value::Table(table) = toml::Value::try_from(&config).unwrap() else {
+        panic!("설정이 테이블로 직렬화되지 않음");
+    };
+    let fields: BTreeSet<&str> = table.keys().map(String::as_str).collect();
+    let classified: BTreeSet<&str> = FIELD_RULES.iter().map(|rule| rule.name).collect();
+    assert_eq!(fields, classified);
+}
+
+#[test]
+fn only_live_and_valid_changes_are_applied() {
+    let running = Config::default();
+    let new = Config {
+        listen_addr: "0.0.0.0:6000".into(), // 재시작 필요
+        channel_buffer: 0,                  // 잘못된 값
+        reconnect_grace_secs: 5,            // 바로 적용
+        admin_token: Some("secret".into()), // 바로 적용, 값은 가림
+        ..Config::default()
+    };
+    let (merged, changes) = plan_reload(&running, &new);
+
+    assert_eq!(merged, Config { reconnect_grace_secs: 5, admin_token: Some("secret".into()), ..Config::default() });
+    let outcomes: Vec<(&str, &ChangeOutcome)> = changes.iter().map(|c| (c.field.as_str(), &c.outcome)).collect();
+    assert!(matches!(
+        outcomes.as_slice(),
+        [
+            ("admin_token", ChangeOutcome::Applied),
+            ("channel_buffer", ChangeOutcome::Invalid(_)),
+            ("listen_addr", ChangeOutcome::RequiresRestart(_)),
+            ("reconnect_grace_secs", ChangeOutcome::Applied),
+        ]
+    ));
+    assert_eq!((changes[0].old_value.as_str(), changes[0].new_value.as_str()), ("(없음)", "(비공개)"));
+    assert_eq!((changes[3].old_value.as_str(), changes[3].new_value.as_str()), ("30", "5"));
+
+    // 바뀐 것이 없으면 보고할 변경도 없음
+    assert!(plan_reload(&merged, &merged).1.is_empty());
+}
+
+#[tokio::test]
+async fn reload_applies_live_settings_and_reports_to_admins() {
+    let service = TicTacToeService::new(Config { admin_token: Some("old".into()), ..Config::default() });
+    let before = service.get_last_reload_report(report_request("old")).await.unwrap().into_inner();
+    assert!(!before.reloaded);
+
+    let new = Config {
+        max_games: 1,
+        admin_token: Some("new".into()),
+        load_check_interval_ms: 10,
+        ..Config::default()
+    };
+    let report = service.reload_config(Ok(new), "server.toml").await;
+    assert_eq!(report.applied().count(), 2);
+    assert_eq!(service.config().max_games, 1);
+    assert_eq!(service.config().load_check_interval_ms, 1000);
+
+    // 최대 게임 수가 새 게임 생성에 바로 적용됨
+    service.create_game(Request::new(CreateGameRequest::default())).await.unwrap();
+    let full = service.create_game(Request::new(CreateGameRequest::default())).await.unwrap_err();
+    assert_eq!(full.code(), Code::ResourceExhausted);
+
+    // 관리자 토큰도 바로 바뀜
+    let denied = service.get_last_reload_report(report_request("old")).await.unwrap_err();
+    assert_eq!(denied.code(), Code::Unauthenticated);
+    let last = service.get_last_reload_report(report_request("new")).await.unwrap().into_inner();
+    assert!(last.reloaded && last.error.is_empty());
+    assert_eq!(last.source, "server.toml");
+    let outcomes: Vec<(&str, ReloadOutcome)> = last.changes.iter().map(|c| (c.field.as_str(), c.outcome())).collect();
+    assert_eq!(
+        outcomes,
+        [
+            ("admin_token", ReloadOutcome::Applied),
+            ("load_check_interval_ms", ReloadOutcome::RequiresRestart),
+            ("max_games", ReloadOutcome::Applied),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn unreadable_config_changes_nothing() {
+    let service = TicTacToeService::new(Config::default());
+    let report = service.reload_config(Err("설정 파일 형식 오류".into()), "server.toml").await;
+    assert_eq!(report.error.as_deref(), Some("설정 파일 형식 오류"));
+    assert!(report.changes.is_empty());
+    assert_eq!(*service.config(), Config::default());
+}
@@ -0,0 +1,167 @@
+mod scenario;
+
+use std::time::Duration;
+
+use scenario::{eq, Scenario};
+use server::config::Config;
+use server::game::{validate_best_of, MatchScore, SharedGame};
+use server::service::TicTacToeService;
+use server::tictactoe::tic_tac_toe_server::TicTacToe;
+use server::tictactoe::{CreateGameRequest, Join};
+use tokio::sync::mpsc;
+use tonic::{Code, Request};
+
+fn best_of(n: u32) -> CreateGameRequest {
+    CreateGameRequest { best_of: n, ..CreateGameRequest::default() }
+}
+
+#[test]
+fn score_counts_resignations_and_timeouts_as_wins() {
+    let mut score = MatchScore::default();
+    score.record("X_win", None);
+    score.record("O_win_by_resignation", None);
+    score.record("draw_agreed", None);
+    score.record("idle_timeout", Some("X"));
+    score.record("admin_terminated", None);
+    score.record("server_shutdown", None);
+    assert_eq!(score, MatchScore { x_wins: 1, o_wins: 2, draws: 1 });
+    assert_eq!(score.games_played(), 4);
+    assert_eq!(score.winner(0), None);
+    assert_eq!(score.winner(5), None);
+    assert_eq!(score.winner(3), Some("O"));
+
+    assert!(validate_best_of(0).is_ok() && validate_best_of(5).is_ok());
+    assert!(validate_best_of(4).is_err() && validate_best_of(101).is_err());
+}
+
+#[test]
+fn best_of_three_runs_until_one_side_wins_twice() {
+    Scenario::new()
+        .create_player("alice", best_of(3))
+        .join_game("bob", "alice")
+        .expect_state(|s| s.status == "ongoing" && s.best_of == 3 && s.games_played == 0)
+        // 1판: X(alice) 승리
+        .move_("alice", 0)
+        .move_("bob", 3)
+        .move_("alice", 1)
+        .move_("bob", 4)
+        .move_("alice", 2)
+        .expect_state(|s| s.status == "X_win" && (s.score_x, s.score_o, s.games_played) == (1, 0, 1) && s.match_winner.is_empty())
+        .rematch("bob")
+        .expect("alice", |s| s.info_message == "Player O wants a rematch.")
+        .rematch("bob")
+        .expect("bob", |s| s.error_message == "You have already asked for a rematch.")
+        .rematch("alice")
+        // 2판: O가 먼저 두고, 점수는 그대로 이어짐
+        .expect_state(|s| s.status == "ongoing" && s.next_player == "O" && s.board.iter().all(String::is_empty) && s.score_x == 1)
+        .move_("bob", 0)
+        .move_("alice", 3)
+        .move_("bob", 1)
+        .move_("alice", 4)
+        .resign("alice")
+        .expect_state(|s| s.status == "O_win_by_resignation" && (s.score_x, s.score_o) == (1, 1) && s.match_winner.is_empty())
+        .rematch("alice")
+        .rematch("bob")
+        // 한 번에 보낼 수 있는 게임 요청 수(message_burst)를 넘지 않도록 판 사이에 쉼
+        .advance(Duration::from_secs(2))
+        // 3판: 다시 X가 먼저 두고, 이기면 승부가 끝나 스트림이 닫힘
+        .expect_state(|s| s.status == "ongoing" && s.next_player == "X")
+        .move_("alice", 0)
+        .move_("bob", 3)
+        .move_("alice", 1)
+        .move_("bob", 4)
+        .move_("alice", 2)
+        .expect_state(|s| s.status == "X_win" && (s.score_x, s.score_o, s.games_played) == (2, 1, 3) && s.match_winner == "X")
+        .expect_closed("alice")
+        .expect_closed("bob")
+        .run(Config::default());
+}
+
+#[test]
+fn auto_rematch_series_plays_on_without_rematch_requests() {
+    let request = CreateGameRequest { best_of: 3, auto_rematch: true, ..CreateGameRequest::default() };
+    Scenario::new()
+        .create_player("alice", request)
+        .join_game("bob", "alice")
+        .expect_state(|s| s.status == "ongoing" && s.auto_rematch && s.next_player == "X")
+        // 1판: X(alice) 승리, 곧바로 O가 먼저 두는 2판
+        .move_("alice", 0)
+        .move_("bob", 3)
+        .move_("alice", 1)
+        .move_("bob", 4)
+        .move_("alice", 2)
+        .expect_state(|s| s.status == "X_win" && (s.score_x, s.score_o) == (1, 0))
+        .expect_state(|s| s.status == "ongoing" && s.next_player == "O" && s.info_message == "Next game of the best-of-3 match: O moves first.")
+        // 2판: O(bob) 승리
+        .move_("bob", 0)
+        .move_("alice", 3)
+        .move_("bob", 1)
+        .move_("alice", 4)
+        .move_("bob", 2)
+        .expect_state(|s| s.status == "O_win" && (s.score_x, s.score_o, s.games_played) == (1, 1, 2))
+        .expect_state(|s| s.status == "ongoing" && s.next_player == "X" && s.board.iter().all(String::is_empty))
+        // 3판: X가 이기면 과반을 먼저 이겨 승부가 끝나고 스트림이 닫힘
+        .move_("alice", 4)
+        .move_("bob", 0)
+        .move_("alice", 2)
+        .move_("bob", 6)
+        .move_("alice", 3)
+        .move_("bob", 1)
+        .move_("alice", 5)
+        .expect_state(|s| s.status == "X_win" && (s.score_x, s.score_o, s.games_played) == (2, 1, 3) && s.match_winner == "X")
+        .expect_closed("alice")
+        .expect_closed("bob")
+        .run(Config::default());
+}
+
+#[test]
+fn rematch_needs_a_match_and_a_finished_game() {
+    Scenario::new()
+        .create_player("alice", best_of(3))
+        .join_game("bob", "alice")
+        .expect_status("bob", eq("ongoing"))
+        .rematch("alice")
+        .expect("alice", |s| s.error_message == "You can only ask for a rematch after a finished game.")
+        .player("carol")
+        .player("dave")
+        .move_("carol", 0)
+        .move_("dave", 3)
+        .move_("carol", 1)
+        .move_("dave", 4)
+        .move_("carol", 2)
+        .expect_status("dave", eq("X_win"))
+        // 몇 판 승부가 아닌 게임은 끝나면 스트림이 닫힘
+        .expect_closed("carol")
+        .run(Config::default());
+}
+
+#[tokio::test]
+async fn score_survives_rematches_but_not_a_new_seat() {
+    let mut game = SharedGame::new("1".into(), 3, 3);
+    game.best_of = 5;
+    let (tx, _rx) = mpsc::channel(64);
+    game.join_player(&Join::default(), tx.clone(), None).await.unwrap();
+    game.join_player(&Join::default(), tx.clone(), None).await.unwrap();
+    game.resign("O");
+    assert_eq!(game.request_rematch("X"), Ok(false));
+    assert_eq!(game.request_rematch("O"), Ok(true));
+    assert_eq!((game.status.as_str(), game.next_player.as_str(), game.score.x_wins), ("ongoing", "O", 1));
+
+    // 상대가 돌아오지 않아 게임이 초기화되고 새 플레이어가 앉으면 0부터 다시 셈
+    game.reset();
+    game.join_player(&Join::default(), tx.clone(), None).await.unwrap();
+    game.join_player(&Join::default(), tx, None).await.unwrap();
+    assert_eq!(game.score, MatchScore::default());
+}
+
+#[tokio::test]
+async fn best_of_must_be_odd() {
+    let service = TicTacToeService::new(Config::default());
+    let error = service.create_game(Request::new(best_of(4))).await.unwrap_err();
+    assert_eq!(error.code(), Code::InvalidArgument);
+    assert!(service.create_game(Request::new(best_of(5))).await.is_ok());
+
+    let auto_without_match = CreateGameRequest { auto_rematch: true, ..CreateGameRequest::default() };
+    let error = service.create_game(Request::new(auto_without_match)).await.unwrap_err();
+    assert_eq!(error.code(), Code::InvalidArgument);
+}
@@ -0,0 +1,83 @@
+mod scenario;
+
+use std::time::{Duration, Instant};
+
+use scenario::{play_alternately, TestPlayer};
+use server::config::Config;
+use server::service::TicTacToeService;
+use server::tictactoe::tic_tac_toe_client::TicTacToeClient;
+use server::tictactoe::{GameState, Join, ReplayRequest};
+use tonic::transport::Channel;
+use tonic::Code;
+
+/// 새 게임에 두 플레이어가 앉아 X가 윗줄로 이길 때까지 둠 (게임 ID 반환)
+async fn play_x_win(channel: &Channel) -> String {
+    let alice = TestPlayer::join(channel.clone(), Join::default()).await;
+    let mut bob = TestPlayer::join(channel.clone(), Join::default()).await;
+    // X: 0, 1, 2 / O: 3, 4
+    let last = play_alternately(&alice, &mut bob, &[0, 3, 1, 4, 2]).await;
+    assert_eq!(last.status, "X_win");
+    last.game_id
+}
+
+async fn replay(client: &mut TicTacToeClient<Channel>, game_id: &str, paced: bool) -> Result<Vec<GameState>, Code> {
+    let request = ReplayRequest { game_id: game_id.into(), paced: Some(paced) };
+    let mut stream = client.replay_game(request).await.map_err(|status| status.code())?.into_inner();
+    let mut states = Vec::new();
+    while let Some(state) = stream.message().await.unwrap() {
+        states.push(state);
+    }
+    Ok(states)
+}
+
+#[tokio::test]
+async fn finished_games_replay_from_memory_without_an_event_log() {
+    let channel = scenario::connect(&scenario::start_server(TicTacToeService::new(Config::default()))).await;
+    let mut client = TicTacToeClient::new(channel.clone());
+    let game_id = play_x_win(&channel).await;
+
+    let states = replay(&mut client, &game_id, false).await.unwrap();
+    // 게임 생성, 두 자리 참가, 수 다섯 개, 게임 종료
+    assert_eq!(states.len(), 9, "{:#?}", states);
+    assert_eq!((states[0].status.as_str(), states[2].status.as_str()), ("waiting", "ongoing"));
+    assert!(states[..3].iter().all(|s| s.board.iter().all(String::is_empty)));
+    assert_eq!(states[3].board[0], "X");
+    assert_eq!(states[7].board, ["X", "X", "X", "O", "O", "", "", "", ""]);
+    assert_eq!(states[7].history.len(), 5);
+    assert_eq!(states[8].status, "X_win");
+    assert!(states.iter().all(|s| s.game_id == game_id && s.win_length == 3));
+    assert!(states.windows(2).all(|pair| pair[1].move_number == pair[0].move_number + 1));
+}
+
+#[tokio::test]
+async fn paced_replays_wait_between_states() {
+    let channel = scenario::connect(&scenario::start_server(TicTacToeService::new(Config { replay_delay_ms: 20, ..Config::default() }))).await;
+    let mut client = TicTacToeClient::new(channel.clone());
+    let game_id = play_x_win(&channel).await;
+
+    let started = Instant::now();
+    let states = replay(&mut client, &game_id, true).await.unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(20) * (states.len() as u32 - 1), "{:?}", started.elapsed());
+
+    // paced를 주지 않으면 기다림
+    let request = ReplayRequest { game_id, paced: None };
+    let mut stream = client.replay_game(request).await.unwrap().into_inner();
+    let started = Instant::now();
+    stream.message().await.unwrap().unwrap();
+    stream.message().await.unwrap().unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(20));
+}
+
+#[tokio::test]
+async fn evicted_and_unknown_games_are_not_found() {
+    let channel = scenario::connect(&scenario::start_server(TicTacToeService::new(Config { replay_buffer_games: 1, ..Config::default() }))).await;
+    let mut client = TicTacToeClient::new(channel.clone());
+    let first = play_x_win(&channel).await;
+    assert!(replay(&mut client, &first, false).await.is_ok());
+
+    let second = play_x_win(&channel).await;
+    assert_ne!(first, second);
+    assert_eq!(replay(&mut client, &first, false).await.unwrap_err(), Code::NotFound);
+    assert_eq!(replay(&mut client, &second, false).await.unwrap().last().unwrap().status, "X_win");
+    assert_eq!(replay(&mut client, "999", false).await.unwrap_err(), Code::NotFound);
+}
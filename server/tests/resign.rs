@@ -0,0 +1,44 @@
+mod scenario;
+
+use scenario::{eq, Scenario};
+use server::config::Config;
+
+#[test]
+fn resigning_out_of_turn_ends_game_for_opponent() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .move_("alice", 4)
+        .move_("bob", 0)
+        .move_("alice", 8)
+        .expect_state(|s| s.next_player == "O")
+        .resign("alice")
+        .expect_state(|s| s.status == "O_win_by_resignation" && s.board[4] == "X" && s.board[8] == "X")
+        .expect_closed("bob")
+        .run(Config::default());
+}
+
+#[test]
+fn resigning_while_waiting_is_rejected() {
+    Scenario::new()
+        .player("alice")
+        .expect_status("alice", eq("waiting"))
+        .resign("alice")
+        .expect("alice", |s| s.error_message == "You can only resign during an ongoing game.")
+        .player("bob")
+        .expect_state(|s| s.status == "ongoing")
+        .run(Config::default());
+}
+
+#[test]
+fn spectator_cannot_resign() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .spectator("carol", "alice")
+        .resign("carol")
+        .expect("carol", |s| s.error_message == "Spectators cannot make moves.")
+        .move_("alice", 0)
+        .expect_state(|s| s.status == "ongoing" && s.board[0] == "X")
+        .run(Config::default());
+}
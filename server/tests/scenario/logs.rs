@@ -0,0 +1,50 @@
+//! 테스트 안에서 남긴 tracing 로그 받아 보기
+//!
+//! 구독자는 스레드마다 걸리므로 로그를 볼 테스트는 서버와 게임 액터도 같은 스레드에서 돌도록
+//! `current_thread` 런타임을 씁니다.
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tracing::subscriber::DefaultGuard;
+
+/// 테스트 구독자가 쓴 로그 (줄 단위로 읽음)
+#[derive(Clone, Default)]
+pub struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Captured {
+    /// 이 스레드의 기본 구독자를 로그를 모으는 fmt 구독자로 바꿈 (가드가 사라지면 되돌림)
+    pub fn install() -> (Self, DefaultGuard) {
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt().with_ansi(false).with_writer(move || writer.clone()).finish();
+        (captured, tracing::subscriber::set_default(subscriber))
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        String::from_utf8_lossy(&self.0.lock().unwrap()).lines().map(str::to_string).collect()
+    }
+
+    /// `needle`이 든 줄이 나올 때까지 기다림 (응답을 받은 뒤에 남는 줄이 있어 잠깐 기다려야 함)
+    pub async fn wait_for(&self, needle: &str) -> String {
+        for _ in 0..100 {
+            if let Some(line) = self.lines().into_iter().find(|line| line.contains(needle)) {
+                return line;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("{:?} 로그가 없습니다: {:#?}", needle, self.lines());
+    }
+}
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
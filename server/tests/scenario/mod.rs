@@ -0,0 +1,1148 @@
+//! 통합 테스트용 시나리오 DSL
+//!
+//! 서버를 메모리 안(duplex 스트림)에서 띄우고, 이름 붙인 클라이언트들의 동작과 기대 상태를
+//! 순서대로 선언합니다. 런타임은 시계가 멈춘(paused) 상태로 시작하므로 모든 대기는 가상
+//! 시간이며, 할 일이 없을 때만 시간이 흐릅니다. 타이머는 `advance`로 결정적으로 진행합니다.
+//!
+//! 실패하면 실패한 단계, 테스트 코드 위치, 그리고 클라이언트별 전체 업데이트 기록을 출력합니다.
+#![allow(dead_code)]
+
+mod logs;
+mod player;
+
+#[allow(unused_imports)] // 헤드리스 플레이어나 로그를 쓰지 않는 테스트 크레이트도 있음
+pub use logs::Captured;
+#[allow(unused_imports)]
+pub use player::{play_alternately, TestPlayer, TestServer};
+
+use std::fmt;
+use std::panic::Location;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::Request as HttpRequest;
+use hyper_util::rt::TokioIo;
+use server::auth::AuthInterceptor;
+use server::config::Config;
+use server::egress::end_reason;
+use server::metrics_http;
+use server::service::TicTacToeService;
+use server::tictactoe::play_request::Action;
+use server::tictactoe::admin_service_client::AdminServiceClient;
+use server::tictactoe::tic_tac_toe_client::TicTacToeClient;
+use server::tictactoe::{BotDifficulty, Chat, Heartbeat, ForceEndGameRequest, KickPlayerRequest, ResetGameRequest, CreateGameRequest, EndReason, JoinRequest, DrawOffer, DrawResponse, GameState, GameStateRequest, Join, ListGamesRequest, ListGamesResponse, MatchmakingRequest, MatchmakingUpdate, Move, PlayRequest, LeaderboardRequest, LeaderboardResponse, PlayerRating, PlayerRatingRequest, PlayerStats, PlayerStatsRequest, Rematch, Resign, SortField, SpectateRequest, Swap};
+use tokio::io::DuplexStream;
+use tokio::sync::mpsc;
+use tokio::task::AbortHandle;
+use tokio::time::Instant;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::StreamExt;
+use tower::ServiceExt;
+use tonic::transport::{Channel, Endpoint, Server};
+use tonic::{Code, Request, Status, Streaming};
+
+/// 기대 상태를 기다리는 기본 시간 (가상 시간)
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+////////////////////////////
+// 1. 매처                 //
+////////////////////////////
+
+/// 문자열 값(상태 등)에 대한 조건과 그 설명
+pub struct Matcher {
+    description: String,
+    test: Box<dyn Fn(&str) -> bool>,
+}
+
+/// 값이 정확히 `expected`와 같음
+pub fn eq(expected: &str) -> Matcher {
+    let expected = expected.to_string();
+    Matcher {
+        description: format!("== {:?}", expected),
+        test: Box::new(move |value| value == expected),
+    }
+}
+
+/// 값에 `needle`이 포함됨
+pub fn contains(needle: &str) -> Matcher {
+    let needle = needle.to_string();
+    Matcher {
+        description: format!("contains {:?}", needle),
+        test: Box::new(move |value| value.contains(&needle)),
+    }
+}
+
+////////////////////////////
+// 2. 시나리오 빌더         //
+////////////////////////////
+
+type StatePredicate = Box<dyn Fn(&GameState) -> bool>;
+type ListPredicate = Box<dyn Fn(&ListGamesResponse) -> bool>;
+type RatingPredicate = Box<dyn Fn(&PlayerRating) -> bool>;
+type StatsPredicate = Box<dyn Fn(&PlayerStats) -> bool>;
+type LeaderboardPredicate = Box<dyn Fn(&LeaderboardResponse) -> bool>;
+type MetricsPredicate = Box<dyn Fn(&str) -> bool>;
+
+enum StepKind {
+    Join { name: String, join: Join, game_of: Option<String> },
+    Create { name: String, request: CreateGameRequest },
+    Matchmake { names: [String; 2] },
+    CreatePrivate { name: String },
+    Invitee { name: String, creator: String },
+    RoomGuest { name: String, creator: String },
+    Spectate { name: String, target: String },
+    Observe { name: String, target: Option<String> },
+    Resume { name: String, other: String },
+    Move { name: String, position: i32 },
+    Send { name: String, request: PlayRequest },
+    Expect { name: Option<String>, what: String, predicate: StatePredicate },
+    ExpectError { name: String, code: Code },
+    ExpectEnd { name: String, end: Option<(Code, EndReason)> },
+    Shutdown,
+    Disconnect { name: String },
+    StopReading { name: String },
+    Reconnect { name: String },
+    Advance(Duration),
+    ListGames { request: ListGamesRequest, predicate: ListPredicate },
+    GameState { target: String, predicate: StatePredicate },
+    Rating { player_id: String, predicate: RatingPredicate },
+    Stats { player_id: String, predicate: StatsPredicate },
+    Leaderboard { request: LeaderboardRequest, predicate: LeaderboardPredicate },
+    ForceEnd { target: String, reason: String },
+    Kick { target: String },
+    ResetGame { target: String, reason: String },
+    Metrics { predicate: MetricsPredicate },
+}
+
+struct Step {
+    label: String,
+    kind: StepKind,
+    location: &'static Location<'static>,
+}
+
+/// 선언형 테스트 시나리오
+pub struct Scenario {
+    steps: Vec<Step>,
+    timeout: Duration,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Scenario::new()
+    }
+}
+
+impl Scenario {
+    pub fn new() -> Self {
+        Scenario { steps: Vec::new(), timeout: DEFAULT_TIMEOUT }
+    }
+
+    /// 기대 상태를 기다리는 시간 변경 (가상 시간)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    #[track_caller]
+    fn push(mut self, label: String, kind: StepKind) -> Self {
+        self.steps.push(Step { label, kind, location: Location::caller() });
+        self
+    }
+
+    /// 일반 참가 (빈 자리가 있는 게임에 들어가거나 새 게임 생성)
+    #[track_caller]
+    pub fn player(self, name: &str) -> Self {
+        let join = Join::default();
+        self.push(format!("player({})", name), StepKind::Join { name: name.into(), join, game_of: None })
+    }
+
+    /// 레이팅을 기록할 player_id를 담아 일반 참가
+    #[track_caller]
+    pub fn rated_player(self, name: &str, player_id: &str) -> Self {
+        let join = Join { player_id: player_id.into(), ..Join::default() };
+        self.push(format!("rated_player({}, {})", name, player_id), StepKind::Join { name: name.into(), join, game_of: None })
+    }
+
+    /// 원하는 심볼을 담아 일반 참가 (먼저 앉을 때만 반영됨)
+    #[track_caller]
+    pub fn player_preferring(self, name: &str, symbol: &str) -> Self {
+        let join = Join { preferred_symbol: symbol.into(), ..Join::default() };
+        let label = format!("player_preferring({}, {})", name, symbol);
+        self.push(label, StepKind::Join { name: name.into(), join, game_of: None })
+    }
+
+    /// 보드에 표시할 글자(Join.marker)를 골라 일반 참가
+    #[track_caller]
+    pub fn player_with_marker(self, name: &str, marker: &str) -> Self {
+        let join = Join { marker: marker.into(), ..Join::default() };
+        let label = format!("player_with_marker({}, {:?})", name, marker);
+        self.push(label, StepKind::Join { name: name.into(), join, game_of: None })
+    }
+
+    /// 게임 상태에 표시할 이름(Join.player_name)을 정해 일반 참가
+    #[track_caller]
+    pub fn player_named(self, name: &str, player_name: &str) -> Self {
+        let join = Join { player_name: player_name.into(), ..Join::default() };
+        let label = format!("player_named({}, {:?})", name, player_name);
+        self.push(label, StepKind::Join { name: name.into(), join, game_of: None })
+    }
+
+    /// 빠른 대전 참가 (상대가 없으면 서버 봇과 대전)
+    #[track_caller]
+    pub fn quick_player(self, name: &str) -> Self {
+        let join = Join { quick_play: true, ..Join::default() };
+        self.push(format!("quick_player({})", name), StepKind::Join { name: name.into(), join, game_of: None })
+    }
+
+    /// 레이팅을 기록할 player_id를 담아 빠른 대전 참가
+    #[track_caller]
+    pub fn rated_quick_player(self, name: &str, player_id: &str) -> Self {
+        let join = Join { quick_play: true, player_id: player_id.into(), ..Join::default() };
+        let label = format!("rated_quick_player({}, {})", name, player_id);
+        self.push(label, StepKind::Join { name: name.into(), join, game_of: None })
+    }
+
+    /// 상대가 없을 때 앉을 서버 봇의 난이도를 정해 빠른 대전 참가
+    #[track_caller]
+    pub fn quick_player_against(self, name: &str, difficulty: BotDifficulty) -> Self {
+        let join = Join { quick_play: true, bot_difficulty: difficulty as i32, ..Join::default() };
+        let label = format!("quick_player_against({}, {:?})", name, difficulty);
+        self.push(label, StepKind::Join { name: name.into(), join, game_of: None })
+    }
+
+    /// CreateGame으로 게임을 만든 뒤 첫 플레이어로 참가
+    #[track_caller]
+    pub fn create_player(self, name: &str, request: CreateGameRequest) -> Self {
+        let label = format!("create_player({}, {:?})", name, request);
+        self.push(label, StepKind::Create { name: name.into(), request })
+    }
+
+    /// 두 클라이언트가 동시에 매치메이킹 대기열에 들어가, 짝지어진 게임에 받은 세션 토큰으로 접속
+    #[track_caller]
+    pub fn matched_players(self, first: &str, second: &str) -> Self {
+        let label = format!("matched_players({}, {})", first, second);
+        self.push(label, StepKind::Matchmake { names: [first.into(), second.into()] })
+    }
+
+    /// `other`가 있는 게임에 게임 ID로 참가
+    #[track_caller]
+    pub fn join_game(self, name: &str, other: &str) -> Self {
+        let label = format!("join_game({}, {})", name, other);
+        self.push(label, StepKind::Join { name: name.into(), join: Join::default(), game_of: Some(other.into()) })
+    }
+
+    /// CreatePrivateGame(기본 설정)으로 비공개 게임을 만들고 X로 참가 (초대 코드는 기억해 둠)
+    #[track_caller]
+    pub fn private_creator(self, name: &str) -> Self {
+        self.push(format!("private_creator({})", name), StepKind::CreatePrivate { name: name.into() })
+    }
+
+    /// `creator`의 초대 코드로 JoinPrivateGame을 호출하고 받은 세션 토큰으로 Play에 참가
+    #[track_caller]
+    pub fn invitee(self, name: &str, creator: &str) -> Self {
+        let label = format!("invitee({}, {})", name, creator);
+        self.push(label, StepKind::Invitee { name: name.into(), creator: creator.into() })
+    }
+
+    /// Join.create_room으로 비공개 방을 만들고 X로 참가 (초대 코드는 첫 상태에서 받아 둠)
+    #[track_caller]
+    pub fn room_creator(self, name: &str) -> Self {
+        let join = Join { create_room: true, ..Join::default() };
+        self.push(format!("room_creator({})", name), StepKind::Join { name: name.into(), join, game_of: None })
+    }
+
+    /// `creator`가 받은 초대 코드를 Join에 담아 Play로 바로 참가
+    #[track_caller]
+    pub fn room_guest(self, name: &str, creator: &str) -> Self {
+        let label = format!("room_guest({}, {})", name, creator);
+        self.push(label, StepKind::RoomGuest { name: name.into(), creator: creator.into() })
+    }
+
+    /// 지정한 초대 코드를 Join에 담아 참가
+    #[track_caller]
+    pub fn room_guest_with_code(self, name: &str, invite_code: &str) -> Self {
+        let join = Join { invite_code: invite_code.into(), ..Join::default() };
+        let label = format!("room_guest_with_code({}, {})", name, invite_code);
+        self.push(label, StepKind::Join { name: name.into(), join, game_of: None })
+    }
+
+    /// `target`이 있는 게임을 관전
+    #[track_caller]
+    pub fn spectator(self, name: &str, target: &str) -> Self {
+        let label = format!("spectator({}, {})", name, target);
+        self.push(label, StepKind::Spectate { name: name.into(), target: target.into() })
+    }
+
+    /// Spectate RPC로 `target`이 있는 게임을 관전 (`None`이면 가장 최근에 만든 진행 중인 게임)
+    #[track_caller]
+    pub fn observer(self, name: &str, target: Option<&str>) -> Self {
+        let label = format!("observer({}, {:?})", name, target);
+        self.push(label, StepKind::Observe { name: name.into(), target: target.map(Into::into) })
+    }
+
+    /// `other`의 세션 토큰으로 새 클라이언트 `name`을 접속 (같은 자리를 다른 연결이 이어받음)
+    #[track_caller]
+    pub fn resume(self, name: &str, other: &str) -> Self {
+        self.push(format!("resume({}, {})", name, other), StepKind::Resume { name: name.into(), other: other.into() })
+    }
+
+    /// 수 두기
+    #[track_caller]
+    pub fn move_(self, name: &str, position: i32) -> Self {
+        self.push(format!("move_({}, {})", name, position), StepKind::Move { name: name.into(), position })
+    }
+
+    /// 기권
+    #[track_caller]
+    pub fn resign(self, name: &str) -> Self {
+        let request = PlayRequest { action: Some(Action::Resign(Resign {})) };
+        self.push(format!("resign({})", name), StepKind::Send { name: name.into(), request })
+    }
+
+    /// 무승부 제안
+    #[track_caller]
+    pub fn offer_draw(self, name: &str) -> Self {
+        let request = PlayRequest { action: Some(Action::OfferDraw(DrawOffer {})) };
+        self.push(format!("offer_draw({})", name), StepKind::Send { name: name.into(), request })
+    }
+
+    /// 무승부 제안에 응답
+    #[track_caller]
+    pub fn respond_draw(self, name: &str, accept: bool) -> Self {
+        let request = PlayRequest { action: Some(Action::RespondDraw(DrawResponse { accept })) };
+        self.push(format!("respond_draw({}, {})", name, accept), StepKind::Send { name: name.into(), request })
+    }
+
+    /// 한 판 더 요청
+    #[track_caller]
+    pub fn rematch(self, name: &str) -> Self {
+        let request = PlayRequest { action: Some(Action::Rematch(Rematch {})) };
+        self.push(format!("rematch({})", name), StepKind::Send { name: name.into(), request })
+    }
+
+    /// 스왑 규칙으로 상대의 첫 수를 가져감
+    #[track_caller]
+    pub fn swap(self, name: &str) -> Self {
+        let request = PlayRequest { action: Some(Action::Swap(Swap {})) };
+        self.push(format!("swap({})", name), StepKind::Send { name: name.into(), request })
+    }
+
+    /// 채팅 전송
+    #[track_caller]
+    pub fn chat(self, name: &str, text: &str) -> Self {
+        let request = PlayRequest { action: Some(Action::Chat(Chat { text: text.into() })) };
+        self.push(format!("chat({}, {:?})", name, text), StepKind::Send { name: name.into(), request })
+    }
+
+    /// 임의의 요청을 그대로 전송
+    #[track_caller]
+    pub fn send(self, name: &str, request: PlayRequest) -> Self {
+        self.push(format!("send({}, {:?})", name, request), StepKind::Send { name: name.into(), request })
+    }
+
+    /// 접속 중인 모든 클라이언트가 조건을 만족하는 업데이트를 받을 때까지 대기
+    #[track_caller]
+    pub fn expect_state(self, predicate: impl Fn(&GameState) -> bool + 'static) -> Self {
+        let kind = StepKind::Expect { name: None, what: "state predicate".into(), predicate: Box::new(predicate) };
+        self.push("expect_state(..)".into(), kind)
+    }
+
+    /// `name`이 조건을 만족하는 업데이트를 받을 때까지 대기
+    #[track_caller]
+    pub fn expect(self, name: &str, predicate: impl Fn(&GameState) -> bool + 'static) -> Self {
+        let kind = StepKind::Expect { name: Some(name.into()), what: "state predicate".into(), predicate: Box::new(predicate) };
+        self.push(format!("expect({}, ..)", name), kind)
+    }
+
+    /// `name`이 상태 문자열이 일치하는 업데이트를 받을 때까지 대기
+    #[track_caller]
+    pub fn expect_status(self, name: &str, matcher: Matcher) -> Self {
+        let what = format!("status {}", matcher.description);
+        let label = format!("expect_status({}, {})", name, matcher.description);
+        let predicate = Box::new(move |state: &GameState| (matcher.test)(&state.status));
+        self.push(label, StepKind::Expect { name: Some(name.into()), what, predicate })
+    }
+
+    /// `name`의 스트림이 해당 코드의 오류로 끝날 때까지 대기
+    #[track_caller]
+    pub fn expect_error(self, name: &str, code: Code) -> Self {
+        self.push(format!("expect_error({}, {:?})", name, code), StepKind::ExpectError { name: name.into(), code })
+    }
+
+    /// `name`의 스트림이 오류 없이(OK) 끝날 때까지 대기
+    #[track_caller]
+    pub fn expect_closed(self, name: &str) -> Self {
+        self.push(format!("expect_closed({})", name), StepKind::ExpectEnd { name: name.into(), end: None })
+    }
+
+    /// `name`의 스트림이 해당 코드와 종료 사유(StreamEnd details)의 Status로 끝날 때까지 대기
+    #[track_caller]
+    pub fn expect_end(self, name: &str, code: Code, reason: EndReason) -> Self {
+        let label = format!("expect_end({}, {:?}, {:?})", name, code, reason);
+        self.push(label, StepKind::ExpectEnd { name: name.into(), end: Some((code, reason)) })
+    }
+
+    /// 서버 종료 처리를 백그라운드에서 시작 (종료 대기 시간 동안에도 다음 단계를 진행할 수 있음)
+    #[track_caller]
+    pub fn shutdown_server(self) -> Self {
+        self.push("shutdown_server()".into(), StepKind::Shutdown)
+    }
+
+    /// 스트림을 끊음 (업데이트 기록은 유지)
+    #[track_caller]
+    pub fn disconnect(self, name: &str) -> Self {
+        self.push(format!("disconnect({})", name), StepKind::Disconnect { name: name.into() })
+    }
+
+    /// 응답 스트림 수신 측만 버리고 요청 스트림은 열어 둠 (잠든 클라이언트 흉내)
+    #[track_caller]
+    pub fn stop_reading(self, name: &str) -> Self {
+        self.push(format!("stop_reading({})", name), StepKind::StopReading { name: name.into() })
+    }
+
+    /// 받은 세션 토큰으로 재접속
+    #[track_caller]
+    pub fn reconnect(self, name: &str) -> Self {
+        self.push(format!("reconnect({})", name), StepKind::Reconnect { name: name.into() })
+    }
+
+    /// 가상 시계를 진행 (그 사이의 서버 타이머가 순서대로 발동)
+    #[track_caller]
+    pub fn advance(self, duration: Duration) -> Self {
+        self.push(format!("advance({:?})", duration), StepKind::Advance(duration))
+    }
+
+    /// ListGames 단항 RPC(기본 요청) 결과가 조건을 만족할 때까지 재시도
+    #[track_caller]
+    pub fn list_games(self, predicate: impl Fn(&ListGamesResponse) -> bool + 'static) -> Self {
+        self.list_games_with(ListGamesRequest::default(), predicate)
+    }
+
+    /// 필터/페이지를 지정한 ListGames 결과가 조건을 만족할 때까지 재시도
+    #[track_caller]
+    pub fn list_games_with(self, request: ListGamesRequest, predicate: impl Fn(&ListGamesResponse) -> bool + 'static) -> Self {
+        let label = format!("list_games({:?})", request);
+        self.push(label, StepKind::ListGames { request, predicate: Box::new(predicate) })
+    }
+
+    /// `target`이 있는 게임을 별도 연결의 GetGameState 단항 RPC로 조회해, 조건을 만족할 때까지 재시도
+    #[track_caller]
+    pub fn game_state(self, target: &str, predicate: impl Fn(&GameState) -> bool + 'static) -> Self {
+        let kind = StepKind::GameState { target: target.into(), predicate: Box::new(predicate) };
+        self.push(format!("game_state({}, ..)", target), kind)
+    }
+
+    /// GetPlayerRating으로 `player_id`의 레이팅을 조회해, 기록이 생기고 조건을 만족할 때까지 재시도
+    #[track_caller]
+    pub fn player_rating(self, player_id: &str, predicate: impl Fn(&PlayerRating) -> bool + 'static) -> Self {
+        let kind = StepKind::Rating { player_id: player_id.into(), predicate: Box::new(predicate) };
+        self.push(format!("player_rating({}, ..)", player_id), kind)
+    }
+
+    /// GetPlayerStats로 `player_id`의 통계를 조회해, 기록이 생기고 조건을 만족할 때까지 재시도
+    #[track_caller]
+    pub fn player_stats(self, player_id: &str, predicate: impl Fn(&PlayerStats) -> bool + 'static) -> Self {
+        let kind = StepKind::Stats { player_id: player_id.into(), predicate: Box::new(predicate) };
+        self.push(format!("player_stats({}, ..)", player_id), kind)
+    }
+
+    /// GetLeaderboard로 `sort_by` 기준 상위 `limit`명을 조회해, 조건을 만족할 때까지 재시도
+    #[track_caller]
+    pub fn leaderboard(self, sort_by: SortField, limit: i32, predicate: impl Fn(&LeaderboardResponse) -> bool + 'static) -> Self {
+        let request = LeaderboardRequest { sort_by: sort_by as i32, limit, ..LeaderboardRequest::default() };
+        let kind = StepKind::Leaderboard { request, predicate: Box::new(predicate) };
+        self.push(format!("leaderboard({:?}, {})", sort_by, limit), kind)
+    }
+
+    /// 관리자 ForceEndGame으로 `target`이 있는 게임을 강제 종료 (설정의 admin_token 사용)
+    #[track_caller]
+    pub fn force_end(self, target: &str, reason: &str) -> Self {
+        let kind = StepKind::ForceEnd { target: target.into(), reason: reason.into() };
+        self.push(format!("force_end({}, {:?})", target, reason), kind)
+    }
+
+    /// 관리자 KickPlayer로 `target`을 게임에서 내보냄 (설정의 admin_token 사용)
+    #[track_caller]
+    pub fn kick(self, target: &str) -> Self {
+        self.push(format!("kick({})", target), StepKind::Kick { target: target.into() })
+    }
+
+    /// 관리자 ResetGame으로 `target`이 있는 게임의 보드를 비움 (설정의 admin_token 사용)
+    #[track_caller]
+    pub fn reset_game(self, target: &str, reason: &str) -> Self {
+        let kind = StepKind::ResetGame { target: target.into(), reason: reason.into() };
+        self.push(format!("reset_game({}, {:?})", target, reason), kind)
+    }
+
+    /// 지표 엔드포인트(`GET /metrics`)를 긁어 본문이 조건을 만족할 때까지 재시도
+    #[track_caller]
+    pub fn metrics(self, predicate: impl Fn(&str) -> bool + 'static) -> Self {
+        self.push("metrics(..)".into(), StepKind::Metrics { predicate: Box::new(predicate) })
+    }
+
+    /// 시나리오 실행, 실패하면 전체 기록과 함께 panic
+    pub fn run(self, config: Config) {
+        if let Err(failure) = self.try_run(config) {
+            panic!("{}", failure);
+        }
+    }
+
+    /// 시나리오 실행 (멈춘 시계의 단일 스레드 런타임에서 서버와 클라이언트를 함께 구동)
+    pub fn try_run(self, config: Config) -> Result<(), ScenarioFailure> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .start_paused(true)
+            .build()
+            .expect("테스트 런타임 생성 실패");
+        runtime.block_on(self.execute(config))
+    }
+
+    async fn execute(self, config: Config) -> Result<(), ScenarioFailure> {
+        let service = TicTacToeService::new(config);
+        service.spawn_idle_sweeper();
+        let conn_tx = start_server(service.clone());
+        let channel = connect(&conn_tx).await;
+        let mut run = Run { service, conn_tx, channel, clients: Vec::new(), timeout: self.timeout };
+        for (index, step) in self.steps.into_iter().enumerate() {
+            let result = run.apply(step.kind).await;
+            settle().await;
+            if let Err(reason) = result {
+                return Err(ScenarioFailure {
+                    index,
+                    label: step.label,
+                    location: step.location,
+                    reason,
+                    transcript: run.transcript(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 시나리오 실패 정보 (표시하면 클라이언트별 업데이트 기록 전체가 포함됨)
+#[derive(Debug)]
+pub struct ScenarioFailure {
+    pub index: usize,
+    pub label: String,
+    pub location: &'static Location<'static>,
+    pub reason: String,
+    pub transcript: String,
+}
+
+impl fmt::Display for ScenarioFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "scenario failed at step #{} `{}` ({}): {}", self.index, self.label, self.location, self.reason)?;
+        write!(f, "{}", self.transcript)
+    }
+}
+
+////////////////////////////
+// 3. 실행기               //
+////////////////////////////
+
+/// 메모리 안에서 게임, 관리자, 토너먼트 서비스를 띄우고, 새 연결을 만들 때 쓰는 송신 채널을 반환
+pub fn start_server(service: TicTacToeService) -> mpsc::Sender<DuplexStream> {
+    let (conn_tx, conn_rx) = mpsc::channel::<DuplexStream>(16);
+    let incoming = ReceiverStream::new(conn_rx).map(Ok::<_, std::io::Error>);
+    tokio::spawn(
+        Server::builder()
+            .add_service(service.admin().into_server())
+            .add_service(service.tournaments().into_server())
+            .add_service(service.into_server())
+            .serve_with_incoming(incoming),
+    );
+    conn_tx
+}
+
+/// 모든 요청이 인증 인터셉터를 거치는 메모리 서버 시작
+pub fn start_authenticated_server(service: TicTacToeService, auth: AuthInterceptor) -> mpsc::Sender<DuplexStream> {
+    let (conn_tx, conn_rx) = mpsc::channel::<DuplexStream>(16);
+    let incoming = ReceiverStream::new(conn_rx).map(Ok::<_, std::io::Error>);
+    tokio::spawn(
+        Server::builder()
+            .add_service(service.into_authenticated_server(auth, None))
+            .serve_with_incoming(incoming),
+    );
+    conn_tx
+}
+
+/// 메모리 서버로 새 연결(HTTP/2 커넥션)을 엶
+pub async fn connect(conn_tx: &mpsc::Sender<DuplexStream>) -> Channel {
+    let conn_tx = conn_tx.clone();
+    Endpoint::from_static("http://scenario.test")
+        .connect_with_connector(tower::service_fn(move |_| {
+            let conn_tx = conn_tx.clone();
+            async move {
+                let (client, server) = tokio::io::duplex(64 * 1024);
+                conn_tx.send(server).await.map_err(|_| std::io::Error::other("server stopped"))?;
+                Ok::<_, std::io::Error>(TokioIo::new(client))
+            }
+        }))
+        .await
+        .expect("메모리 연결 실패")
+}
+
+/// 테스트마다 다른 빈 임시 디렉터리 (지난 실행에서 남은 내용은 지움)
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("tictactoe-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("임시 디렉터리 생성 실패");
+    dir
+}
+
+/// 서비스의 지표 라우터에 `GET /metrics`를 보내 본문을 받음
+async fn scrape_metrics(service: &TicTacToeService) -> Result<String, String> {
+    let request = HttpRequest::get("/metrics").body(Body::empty()).map_err(|e| e.to_string())?;
+    let response = metrics_http::router(service.metrics()).oneshot(request).await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("metrics scrape failed: {}", response.status()));
+    }
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.map_err(|e| e.to_string())?;
+    String::from_utf8(body.to_vec()).map_err(|e| e.to_string())
+}
+
+/// 런타임이 한가해질 때까지(보낸 요청이 모두 처리될 때까지) 대기
+///
+/// 멈춘 시계는 실행할 작업이 없을 때만 앞으로 당겨지므로, 짧은 sleep이 끝났다는 것은
+/// 서버와 클라이언트의 모든 태스크가 멈췄다는 뜻입니다. 서로 다른 스트림의 요청 순서가
+/// 시나리오에 적힌 순서대로 처리되도록 매 단계 뒤에 호출합니다.
+async fn settle() {
+    tokio::time::sleep(Duration::from_millis(1)).await;
+}
+
+/// 클라이언트가 받은 것 (기록용)
+enum Event {
+    Update(Box<GameState>),
+    Error(Status),
+    Closed,
+    Note(&'static str),
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::Update(state) => {
+                let board: Vec<String> = state
+                    .board
+                    .chunks(state.board_size.max(3) as usize)
+                    .map(|row| row.iter().map(|c| if c.is_empty() { "." } else { c.as_str() }).collect())
+                    .collect();
+                write!(f, "game={} status={} next={} board={} you={}", state.game_id, state.status, state.next_player, board.join("/"), state.your_symbol)?;
+                if !state.error_message.is_empty() {
+                    write!(f, " error={:?}", state.error_message)?;
+                }
+                if !state.info_message.is_empty() {
+                    write!(f, " info={:?}", state.info_message)?;
+                }
+                Ok(())
+            }
+            Event::Error(status) => match end_reason(status) {
+                Some(reason) => write!(f, "error {:?} ({:?}): {}", status.code(), reason, status.message()),
+                None => write!(f, "error {:?}: {}", status.code(), status.message()),
+            },
+            Event::Closed => write!(f, "stream closed"),
+            Event::Note(note) => write!(f, "-- {} --", note),
+        }
+    }
+}
+
+/// 이름 붙인 테스트 클라이언트
+struct Client {
+    name: String,
+    symbol: String,
+    session_token: String,
+    game_id: String,
+    invite_code: String, // private_creator나 room_creator로 만든 게임의 초대 코드
+    connection: Option<Connection>,
+    unread: Option<Connection>, // stop_reading 뒤에도 요청 스트림을 열어 두는 연결
+    events: Vec<Event>,
+    cursor: usize, // 아직 기대 조건 검사에 쓰이지 않은 첫 이벤트
+}
+
+impl Client {
+    fn new(name: String, connection: Option<Connection>) -> Self {
+        Client {
+            name,
+            symbol: String::new(),
+            session_token: String::new(),
+            game_id: String::new(),
+            invite_code: String::new(),
+            connection,
+            unread: None,
+            events: Vec::new(),
+            cursor: 0,
+        }
+    }
+
+    /// 이벤트 하나를 받아 기록 (시간 초과면 false)
+    async fn receive(&mut self, deadline: Instant) -> Result<bool, String> {
+        if matches!(self.events.last(), Some(Event::Closed)) {
+            return Err(format!("`{}` stream already closed", self.name));
+        }
+        let Some(connection) = self.connection.as_mut() else {
+            return Err(format!("`{}` is disconnected", self.name));
+        };
+        let event = match tokio::time::timeout_at(deadline, connection.responses.recv()).await {
+            Err(_) => return Ok(false),
+            Ok(Some(Ok(state))) => {
+                if !state.your_symbol.is_empty() {
+                    self.symbol = state.your_symbol.clone();
+                }
+                if !state.session_token.is_empty() {
+                    self.session_token = state.session_token.clone();
+                }
+                if !state.game_id.is_empty() {
+                    self.game_id = state.game_id.clone();
+                }
+                if !state.invite_code.is_empty() {
+                    self.invite_code = state.invite_code.clone();
+                }
+                Event::Update(Box::new(state))
+            }
+            Ok(None) => Event::Closed,
+            Ok(Some(Err(status))) => Event::Error(status),
+        };
+        self.events.push(event);
+        Ok(true)
+    }
+
+    /// 조건을 만족하는 이벤트가 올 때까지 소비
+    async fn wait_for(&mut self, deadline: Instant, timeout: Duration, what: &str, matches: impl Fn(&Event) -> bool) -> Result<(), String> {
+        loop {
+            while self.cursor < self.events.len() {
+                self.cursor += 1;
+                if matches(&self.events[self.cursor - 1]) {
+                    return Ok(());
+                }
+            }
+            if !self.receive(deadline).await? {
+                return Err(format!("timed out after {:?} waiting for `{}` to see {}", timeout, self.name, what));
+            }
+        }
+    }
+
+    async fn send(&self, request: PlayRequest) -> Result<(), String> {
+        let Some(connection) = self.connection.as_ref() else {
+            return Err(format!("`{}` is disconnected", self.name));
+        };
+        connection.requests.send(request).await.map_err(|_| format!("`{}` request stream closed", self.name))
+    }
+}
+
+/// 클라이언트 한 명의 Play 연결. 응답은 백그라운드 태스크가 읽어 넘기며, 실제 클라이언트처럼
+/// 하트비트에는 바로 응답하고 기록에는 남기지 않습니다. 연결을 버리면 읽기 태스크도 멈춥니다.
+struct Connection {
+    requests: mpsc::Sender<PlayRequest>,
+    responses: mpsc::UnboundedReceiver<Result<GameState, Status>>,
+    reader: AbortHandle,
+}
+
+impl Connection {
+    fn new(requests: mpsc::Sender<PlayRequest>, stream: Streaming<GameState>) -> Self {
+        Self::open(requests, stream, true)
+    }
+
+    /// 하트비트에 응답하지 않는 연결 (자리만 받고 아무것도 보내지 않는 클라이언트)
+    fn silent(requests: mpsc::Sender<PlayRequest>, stream: Streaming<GameState>) -> Self {
+        Self::open(requests, stream, false)
+    }
+
+    fn open(requests: mpsc::Sender<PlayRequest>, mut stream: Streaming<GameState>, answer_heartbeats: bool) -> Self {
+        let (tx, responses) = mpsc::unbounded_channel();
+        // 요청 스트림은 약한 참조로만 잡아, 연결을 버리면 요청 스트림이 바로 닫히게 함
+        let acks = requests.downgrade();
+        let reader = tokio::spawn(async move {
+            while let Some(result) = stream.message().await.transpose() {
+                if matches!(&result, Ok(state) if state.heartbeat) {
+                    if let Some(acks) = acks.upgrade().filter(|_| answer_heartbeats) {
+                        let _ = acks.send(PlayRequest { action: Some(Action::Heartbeat(Heartbeat {})) }).await;
+                    }
+                    continue;
+                }
+                if tx.send(result).is_err() {
+                    break;
+                }
+            }
+        });
+        Connection { requests, responses, reader: reader.abort_handle() }
+    }
+
+    /// 응답 읽기만 멈춤 (하트비트에도 더는 응답하지 않음)
+    fn stop_reading(&self) {
+        self.reader.abort();
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+struct Run {
+    service: TicTacToeService,           // 종료 처리 등 서버 쪽 동작용
+    conn_tx: mpsc::Sender<DuplexStream>, // 새 연결용
+    channel: Channel,                    // 스트리밍 클라이언트가 함께 쓰는 연결
+    clients: Vec<Client>,
+    timeout: Duration,
+}
+
+impl Run {
+    fn client(&mut self, name: &str) -> Result<&mut Client, String> {
+        self.clients
+            .iter_mut()
+            .find(|c| c.name == name)
+            .ok_or_else(|| format!("unknown client `{}`", name))
+    }
+
+    /// Join을 먼저 보낸 뒤 스트림을 엶
+    async fn connect(&self, join: Join) -> Result<Connection, String> {
+        let (tx, rx) = mpsc::channel(16);
+        tx.send(PlayRequest { action: Some(Action::Join(join)) }).await.map_err(|e| e.to_string())?;
+        let response = TicTacToeClient::new(self.channel.clone())
+            .play(ReceiverStream::new(rx))
+            .await
+            .map_err(|status| format!("play failed: {}", status))?;
+        Ok(Connection::new(tx, response.into_inner()))
+    }
+
+    /// 새 클라이언트를 연결하고 첫 응답(초기 상태 또는 거부)을 받아 둠
+    async fn add_client(&mut self, name: String, join: Join) -> Result<(), String> {
+        let connection = self.connect(join).await?;
+        self.add_connection(name, connection).await
+    }
+
+    /// Spectate RPC로 관전 스트림을 열고 첫 응답을 받아 둠 (호출이 거부되면 그 Status를 이벤트로 기록)
+    async fn add_observer(&mut self, name: String, game_id: String) -> Result<(), String> {
+        match TicTacToeClient::new(self.channel.clone()).spectate(SpectateRequest { game_id }).await {
+            // 관전 스트림에는 보낼 요청이 없으므로 요청 채널은 받는 쪽 없이 둠
+            Ok(response) => self.add_connection(name, Connection::new(mpsc::channel(1).0, response.into_inner())).await,
+            Err(status) => {
+                let mut client = Client::new(name, None);
+                client.events.push(Event::Error(status));
+                self.clients.push(client);
+                Ok(())
+            }
+        }
+    }
+
+    async fn add_connection(&mut self, name: String, connection: Connection) -> Result<(), String> {
+        if self.clients.iter().any(|c| c.name == name) {
+            return Err(format!("client `{}` already exists", name));
+        }
+        let mut client = Client::new(name, Some(connection));
+        let received = client.receive(Instant::now() + self.timeout).await;
+        self.clients.push(client);
+        match received? {
+            true => Ok(()),
+            false => Err(format!("no response to join within {:?}", self.timeout)),
+        }
+    }
+
+    async fn apply(&mut self, kind: StepKind) -> Result<(), String> {
+        let deadline = Instant::now() + self.timeout;
+        match kind {
+            StepKind::Join { name, mut join, game_of } => {
+                if let Some(other) = game_of {
+                    join.game_id = self.client(&other)?.game_id.clone();
+                }
+                self.add_client(name, join).await
+            }
+            StepKind::Create { name, request } => {
+                let created = TicTacToeClient::new(self.channel.clone())
+                    .create_game(request)
+                    .await
+                    .map_err(|status| format!("create_game failed: {}", status))?
+                    .into_inner();
+                self.add_client(name, Join { game_id: created.game_id, ..Join::default() }).await
+            }
+            StepKind::Matchmake { names } => {
+                let requests = names.clone().map(|player_name| {
+                    let mut client = TicTacToeClient::new(self.channel.clone());
+                    async move { client.join_matchmaking(MatchmakingRequest { player_name }).await }
+                });
+                let [first, second] = requests;
+                let (first, second) = tokio::join!(first, second);
+                for (name, stream) in names.into_iter().zip([first, second]) {
+                    let mut stream = stream.map_err(|status| format!("join_matchmaking failed: {}", status))?.into_inner();
+                    let game = loop {
+                        match tokio::time::timeout_at(deadline, stream.message()).await {
+                            Err(_) => return Err(format!("`{}` was not matched within {:?}", name, self.timeout)),
+                            Ok(Ok(Some(MatchmakingUpdate { game: Some(game), .. }))) => break game,
+                            Ok(Ok(Some(_))) => continue,
+                            Ok(Ok(None)) => return Err(format!("`{}` matchmaking stream closed before a match", name)),
+                            Ok(Err(status)) => return Err(format!("`{}` matchmaking failed: {}", name, status)),
+                        }
+                    };
+                    let join = Join { session_token: game.session_token, game_id: game.game_id, ..Join::default() };
+                    self.add_client(name, join).await?;
+                }
+                Ok(())
+            }
+            StepKind::CreatePrivate { name } => {
+                let created = TicTacToeClient::new(self.channel.clone())
+                    .create_private_game(CreateGameRequest::default())
+                    .await
+                    .map_err(|status| format!("create_private_game failed: {}", status))?
+                    .into_inner();
+                self.add_client(name.clone(), Join { game_id: created.game_id, ..Join::default() }).await?;
+                self.client(&name)?.invite_code = created.invite_code;
+                Ok(())
+            }
+            StepKind::Invitee { name, creator } => {
+                let invite_code = self.client(&creator)?.invite_code.clone();
+                let game = TicTacToeClient::new(self.channel.clone())
+                    .join_private_game(JoinRequest { invite_code })
+                    .await
+                    .map_err(|status| format!("join_private_game failed: {}", status))?
+                    .into_inner();
+                self.add_client(name, Join { session_token: game.session_token, game_id: game.game_id, ..Join::default() }).await
+            }
+            StepKind::RoomGuest { name, creator } => {
+                let invite_code = self.client(&creator)?.invite_code.clone();
+                self.add_client(name, Join { invite_code, ..Join::default() }).await
+            }
+            StepKind::Spectate { name, target } => {
+                let game_id = self.client(&target)?.game_id.clone();
+                self.add_client(name, Join { spectate: true, game_id, ..Join::default() }).await
+            }
+            StepKind::Observe { name, target } => {
+                let game_id = match target {
+                    Some(target) => self.client(&target)?.game_id.clone(),
+                    None => String::new(),
+                };
+                self.add_observer(name, game_id).await
+            }
+            StepKind::Resume { name, other } => {
+                let other = self.client(&other)?;
+                let join = Join { session_token: other.session_token.clone(), game_id: other.game_id.clone(), ..Join::default() };
+                self.add_client(name, join).await
+            }
+            StepKind::Move { name, position } => {
+                let client = self.client(&name)?;
+                let mv = Move { player_id: client.symbol.clone(), position, ..Move::default() };
+                client.send(PlayRequest { action: Some(Action::Move(mv)) }).await
+            }
+            StepKind::Send { name, request } => self.client(&name)?.send(request).await,
+            StepKind::Expect { name: Some(name), what, predicate } => {
+                let timeout = self.timeout;
+                let matches = |event: &Event| matches!(event, Event::Update(state) if predicate(state));
+                self.client(&name)?.wait_for(deadline, timeout, &what, matches).await
+            }
+            StepKind::Expect { name: None, what, predicate } => {
+                let timeout = self.timeout;
+                for client in self.clients.iter_mut().filter(|c| c.connection.is_some()) {
+                    let matches = |event: &Event| matches!(event, Event::Update(state) if predicate(state));
+                    client.wait_for(deadline, timeout, &what, matches).await?;
+                }
+                Ok(())
+            }
+            StepKind::ExpectError { name, code } => {
+                let timeout = self.timeout;
+                let what = format!("error {:?}", code);
+                let matches = |event: &Event| matches!(event, Event::Error(status) if status.code() == code);
+                self.client(&name)?.wait_for(deadline, timeout, &what, matches).await
+            }
+            StepKind::ExpectEnd { name, end } => {
+                let timeout = self.timeout;
+                let what = match end {
+                    Some((code, reason)) => format!("stream end {:?} ({:?})", code, reason),
+                    None => "stream closed".to_string(),
+                };
+                let matches = |event: &Event| match (event, end) {
+                    (Event::Closed, None) => true,
+                    (Event::Error(status), Some((code, reason))) => status.code() == code && end_reason(status) == Some(reason),
+                    _ => false,
+                };
+                self.client(&name)?.wait_for(deadline, timeout, &what, matches).await
+            }
+            StepKind::Shutdown => {
+                let service = self.service.clone();
+                tokio::spawn(async move { service.shutdown().await });
+                // 종료가 시작되어 새 참가를 막을 때까지 양보
+                tokio::task::yield_now().await;
+                Ok(())
+            }
+            StepKind::Disconnect { name } => {
+                let client = self.client(&name)?;
+                client.connection = None;
+                client.events.push(Event::Note("disconnected"));
+                client.cursor = client.events.len();
+                Ok(())
+            }
+            StepKind::StopReading { name } => {
+                let client = self.client(&name)?;
+                let connection = client.connection.take().ok_or_else(|| format!("`{}` is disconnected", name))?;
+                connection.stop_reading();
+                client.unread = Some(connection);
+                client.events.push(Event::Note("stopped reading"));
+                client.cursor = client.events.len();
+                Ok(())
+            }
+            StepKind::Reconnect { name } => {
+                let client = self.client(&name)?;
+                let join = Join { session_token: client.session_token.clone(), game_id: client.game_id.clone(), ..Join::default() };
+                let connection = self.connect(join).await?;
+                let client = self.client(&name)?;
+                client.connection = Some(connection);
+                client.events.push(Event::Note("reconnected"));
+                client.cursor = client.events.len();
+                match client.receive(deadline).await? {
+                    true => Ok(()),
+                    false => Err(format!("no response to reconnect within {:?}", self.timeout)),
+                }
+            }
+            StepKind::Advance(duration) => {
+                // 멈춘 시계에서 sleep은 런타임이 한가해질 때마다 다음 타이머까지 시간을 당김
+                tokio::time::sleep(duration).await;
+                Ok(())
+            }
+            StepKind::ListGames { request, predicate } => loop {
+                let response = TicTacToeClient::new(self.channel.clone())
+                    .list_games(request.clone())
+                    .await
+                    .map_err(|status| format!("list_games failed: {}", status))?
+                    .into_inner();
+                if predicate(&response) {
+                    return Ok(());
+                }
+                if Instant::now() >= deadline {
+                    return Err(format!("timed out after {:?} waiting for list predicate; last response: {:?}", self.timeout, response));
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            },
+            StepKind::GameState { target, predicate } => {
+                let target = self.client(&target)?;
+                let request = GameStateRequest { game_id: target.game_id.clone(), session_token: target.session_token.clone() };
+                let mut client = TicTacToeClient::new(connect(&self.conn_tx).await);
+                loop {
+                    let state = client
+                        .get_game_state(request.clone())
+                        .await
+                        .map_err(|status| format!("get_game_state failed: {}", status))?
+                        .into_inner();
+                    if predicate(&state) {
+                        return Ok(());
+                    }
+                    if Instant::now() >= deadline {
+                        return Err(format!("timed out after {:?} waiting for game state predicate; last state: {}", self.timeout, Event::Update(Box::new(state))));
+                    }
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            }
+            StepKind::Rating { player_id, predicate } => {
+                let mut client = TicTacToeClient::new(self.channel.clone());
+                loop {
+                    let rating = match client.get_player_rating(PlayerRatingRequest { player_id: player_id.clone() }).await {
+                        Ok(response) => Some(response.into_inner()),
+                        Err(status) if status.code() == Code::NotFound => None,
+                        Err(status) => return Err(format!("get_player_rating failed: {}", status)),
+                    };
+                    if rating.as_ref().is_some_and(&predicate) {
+                        return Ok(());
+                    }
+                    if Instant::now() >= deadline {
+                        return Err(format!("timed out after {:?} waiting for rating predicate; last rating: {:?}", self.timeout, rating));
+                    }
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            }
+            StepKind::Stats { player_id, predicate } => {
+                let mut client = TicTacToeClient::new(self.channel.clone());
+                loop {
+                    let stats = match client.get_player_stats(PlayerStatsRequest { player_id: player_id.clone() }).await {
+                        Ok(response) => Some(response.into_inner()),
+                        Err(status) if status.code() == Code::NotFound => None,
+                        Err(status) => return Err(format!("get_player_stats failed: {}", status)),
+                    };
+                    if stats.as_ref().is_some_and(&predicate) {
+                        return Ok(());
+                    }
+                    if Instant::now() >= deadline {
+                        return Err(format!("timed out after {:?} waiting for stats predicate; last stats: {:?}", self.timeout, stats));
+                    }
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            }
+            StepKind::Leaderboard { request, predicate } => {
+                let mut client = TicTacToeClient::new(self.channel.clone());
+                loop {
+                    let leaders = client
+                        .get_leaderboard(request)
+                        .await
+                        .map_err(|status| format!("get_leaderboard failed: {}", status))?
+                        .into_inner();
+                    if predicate(&leaders) {
+                        return Ok(());
+                    }
+                    if Instant::now() >= deadline {
+                        return Err(format!("timed out after {:?} waiting for leaderboard predicate; last response: {:?}", self.timeout, leaders));
+                    }
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            }
+            StepKind::ForceEnd { target, reason } => {
+                let game_id = self.client(&target)?.game_id.clone();
+                let request = self.admin_request(ForceEndGameRequest { game_id, reason });
+                AdminServiceClient::new(self.channel.clone())
+                    .force_end_game(request)
+                    .await
+                    .map_err(|status| format!("force_end_game failed: {}", status))?;
+                Ok(())
+            }
+            StepKind::Kick { target } => {
+                let target = self.client(&target)?;
+                let kick = KickPlayerRequest { game_id: target.game_id.clone(), symbol: target.symbol.clone() };
+                let request = self.admin_request(kick);
+                AdminServiceClient::new(self.channel.clone())
+                    .kick_player(request)
+                    .await
+                    .map_err(|status| format!("kick_player failed: {}", status))?;
+                Ok(())
+            }
+            StepKind::ResetGame { target, reason } => {
+                let game_id = self.client(&target)?.game_id.clone();
+                let request = self.admin_request(ResetGameRequest { game_id, reason });
+                AdminServiceClient::new(self.channel.clone())
+                    .reset_game(request)
+                    .await
+                    .map_err(|status| format!("reset_game failed: {}", status))?;
+                Ok(())
+            }
+            StepKind::Metrics { predicate } => loop {
+                let body = scrape_metrics(&self.service).await?;
+                if predicate(&body) {
+                    return Ok(());
+                }
+                if Instant::now() >= deadline {
+                    return Err(format!("timed out after {:?} waiting for metrics predicate; last scrape:\n{}", self.timeout, body));
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            },
+        }
+    }
+
+    /// 설정의 관리자 토큰을 메타데이터에 담은 관리자 요청
+    fn admin_request<T>(&self, message: T) -> Request<T> {
+        let mut request = Request::new(message);
+        let token = self.service.config().admin_token.clone().unwrap_or_default();
+        request.metadata_mut().insert("x-admin-token", token.parse().expect("관리자 토큰은 ASCII여야 합니다"));
+        request
+    }
+
+    /// 클라이언트별 전체 기록 (`>`는 아직 검사되지 않은 첫 이벤트)
+    fn transcript(&self) -> String {
+        let mut out = String::new();
+        for client in &self.clients {
+            let symbol = if client.symbol.is_empty() { "-" } else { client.symbol.as_str() };
+            out.push_str(&format!("--- {} ({}) ---\n", client.name, symbol));
+            for (i, event) in client.events.iter().enumerate() {
+                let marker = if i == client.cursor { '>' } else { ' ' };
+                out.push_str(&format!("{}{:>3} {}\n", marker, i, event));
+            }
+        }
+        out
+    }
+}
@@ -0,0 +1,172 @@
+//! 한 줄씩 직접 조작하는 헤드리스 플레이어
+//!
+//! 시나리오 DSL로 적기 어려운 흐름(받은 상태를 보고 다음 수를 고르는 경우 등)은 [`TestPlayer`]로
+//! 직접 씁니다. 서버는 [`TestServer::start`]로 테스트마다 새로 띄우므로 테스트끼리 게임을 나누지
+//! 않습니다. 시나리오와 같은 메모리 연결과 하트비트 처리를 그대로 씁니다.
+
+use std::time::Duration;
+
+use server::config::Config;
+use server::service::TicTacToeService;
+use server::tictactoe::play_request::Action;
+use server::tictactoe::tic_tac_toe_client::TicTacToeClient;
+use server::tictactoe::{GameState, Join, Move, PlayRequest};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::transport::Channel;
+use tonic::{Status, Streaming};
+
+use super::{connect, start_server, Connection, DEFAULT_TIMEOUT};
+
+/// 테스트 하나가 쓰는 메모리 서버
+pub struct TestServer {
+    pub service: TicTacToeService,
+    channel: Channel,
+}
+
+impl TestServer {
+    /// 새 서비스를 만들어 메모리 안에서 띄움
+    pub async fn start(config: Config) -> Self {
+        Self::serve(TicTacToeService::new(config)).await
+    }
+
+    /// 미리 구성한 서비스(통계 파일 등)를 메모리 안에서 띄움
+    pub async fn serve(service: TicTacToeService) -> Self {
+        let channel = connect(&start_server(service.clone())).await;
+        TestServer { service, channel }
+    }
+
+    /// 빈 Join(자동 배정)으로 새 플레이어 참가
+    pub async fn join(&self) -> TestPlayer {
+        self.join_with(Join::default()).await
+    }
+
+    /// 주어진 Join으로 새 플레이어 참가
+    pub async fn join_with(&self, join: Join) -> TestPlayer {
+        TestPlayer::join(self.channel.clone(), join).await
+    }
+
+    /// 참가만 하고 하트비트에도 응답하지 않는 플레이어 (수를 두거나 요청을 보내면 더는 조용하지 않음)
+    pub async fn join_silent(&self) -> TestPlayer {
+        TestPlayer::open(self.channel.clone(), Join::default(), Connection::silent).await
+    }
+
+    /// `other`가 있는 게임에 game_id로 참가
+    pub async fn join_game(&self, other: &TestPlayer) -> TestPlayer {
+        TestPlayer::join(self.channel.clone(), Join { game_id: other.game_id.clone(), ..Join::default() }).await
+    }
+
+    /// `player`의 세션 토큰으로 같은 자리에 다시 참가
+    pub async fn reconnect(&self, player: &TestPlayer) -> TestPlayer {
+        let join = Join { session_token: player.session_token.clone(), game_id: player.game_id.clone(), ..Join::default() };
+        TestPlayer::join(self.channel.clone(), join).await
+    }
+}
+
+/// Play 스트림 하나를 가진 플레이어. 받은 상태에서 기호, 게임 ID, 세션 토큰을 기억합니다.
+pub struct TestPlayer {
+    pub symbol: String,
+    pub game_id: String,
+    pub session_token: String,
+    connection: Option<Connection>,
+    timeout: Duration,
+}
+
+impl TestPlayer {
+    /// Join을 보내고 스트림을 엶 (첫 응답은 [`TestPlayer::next_update`]로 받음)
+    pub async fn join(channel: Channel, join: Join) -> Self {
+        Self::open(channel, join, Connection::new).await
+    }
+
+    async fn open(channel: Channel, join: Join, connection: fn(mpsc::Sender<PlayRequest>, Streaming<GameState>) -> Connection) -> Self {
+        let (tx, rx) = mpsc::channel(16);
+        tx.send(PlayRequest { action: Some(Action::Join(join)) }).await.expect("요청 스트림이 닫힘");
+        let response = TicTacToeClient::new(channel).play(ReceiverStream::new(rx)).await.expect("Play 호출 실패");
+        TestPlayer {
+            symbol: String::new(),
+            game_id: String::new(),
+            session_token: String::new(),
+            connection: Some(connection(tx, response.into_inner())),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// 자기 기호로 `position`에 수를 보냄
+    pub async fn send_move(&self, position: i32) {
+        let mv = Move { player_id: self.symbol.clone(), position, ..Move::default() };
+        self.send(PlayRequest { action: Some(Action::Move(mv)) }).await;
+    }
+
+    /// 요청 하나를 그대로 보냄
+    pub async fn send(&self, request: PlayRequest) {
+        let connection = self.connection.as_ref().expect("연결이 끊긴 플레이어");
+        connection.requests.send(request).await.expect("요청 스트림이 닫힘");
+    }
+
+    /// 다음 응답 (하트비트 제외). 스트림이 끝났으면 None, 시간 안에 아무것도 오지 않으면 패닉
+    pub async fn next_event(&mut self) -> Option<Result<GameState, Status>> {
+        let connection = self.connection.as_mut().expect("연결이 끊긴 플레이어");
+        let event = tokio::time::timeout(self.timeout, connection.responses.recv())
+            .await
+            .unwrap_or_else(|_| panic!("{:?} 동안 응답이 없음", self.timeout));
+        if let Some(Ok(state)) = &event {
+            if !state.your_symbol.is_empty() {
+                self.symbol = state.your_symbol.clone();
+            }
+            if !state.game_id.is_empty() {
+                self.game_id = state.game_id.clone();
+            }
+            if !state.session_token.is_empty() {
+                self.session_token = state.session_token.clone();
+            }
+        }
+        event
+    }
+
+    /// 다음 게임 상태 (오류나 스트림 종료면 패닉)
+    pub async fn next_update(&mut self) -> GameState {
+        match self.next_event().await {
+            Some(Ok(state)) => state,
+            Some(Err(status)) => panic!("상태 대신 오류를 받음: {:?} {}", status.code(), status.message()),
+            None => panic!("상태 대신 스트림 종료를 받음"),
+        }
+    }
+
+    /// 조건을 만족하는 상태가 올 때까지 받음 (중간 상태는 버림)
+    pub async fn update_where(&mut self, matches: impl Fn(&GameState) -> bool) -> GameState {
+        loop {
+            let state = self.next_update().await;
+            if matches(&state) {
+                return state;
+            }
+        }
+    }
+
+    /// 다음 응답으로 오류를 받음 (상태나 스트림 종료면 패닉)
+    pub async fn next_error(&mut self) -> Status {
+        match self.next_event().await {
+            Some(Err(status)) => status,
+            Some(Ok(state)) => panic!("오류 대신 상태를 받음: status={} error={:?}", state.status, state.error_message),
+            None => panic!("오류 대신 스트림 종료를 받음"),
+        }
+    }
+
+    /// 요청 스트림을 닫고 응답 읽기를 멈춤 (세션 토큰은 남아 있어 다시 참가할 수 있음)
+    pub fn disconnect(&mut self) {
+        self.connection = None;
+    }
+}
+
+/// `first`와 `second`가 번갈아 `positions`에 두고 마지막 수가 반영된 상태를 반환
+///
+/// 서로 다른 스트림의 수가 보낸 순서대로 처리되도록, 수를 보낼 때마다 `second`가 그 수를
+/// 받을 때까지 기다린 뒤 다음 수를 보냅니다. 빈 보드에서 시작한다고 가정합니다.
+pub async fn play_alternately(first: &TestPlayer, second: &mut TestPlayer, positions: &[i32]) -> GameState {
+    let mut last = None;
+    for (count, &position) in positions.iter().enumerate() {
+        let player = if count % 2 == 0 { first } else { &*second };
+        player.send_move(position).await;
+        last = Some(second.update_where(|state| state.board.iter().filter(|cell| !cell.is_empty()).count() == count + 1).await);
+    }
+    last.expect("둘 수가 없음")
+}
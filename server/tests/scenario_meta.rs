@@ -0,0 +1,75 @@
+//! 시나리오 DSL 자체의 동작 검사 (시간 초과, 실패 보고)
+mod scenario;
+
+use std::time::{Duration, Instant};
+
+use scenario::{eq, Scenario};
+use server::config::Config;
+
+#[test]
+fn expectation_times_out_in_virtual_time() {
+    let started = Instant::now();
+    let failure = Scenario::new()
+        .timeout(Duration::from_secs(60))
+        .player("alice")
+        .expect_status("alice", eq("ongoing"))
+        .try_run(Config::default())
+        .unwrap_err();
+
+    assert_eq!(failure.index, 1);
+    assert!(failure.reason.contains("timed out after 60s"), "{}", failure);
+    // 멈춘 시계이므로 실제로 60초를 기다리지 않음
+    assert!(started.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn advance_fires_server_timers_before_timeout() {
+    // 빠른 대전 대기 시간보다 짧은 기대 시간으로는 봇이 나타나지 않음
+    let config = Config { quick_play_timeout_secs: 20, ..Config::default() };
+    let failure = Scenario::new()
+        .quick_player("alice")
+        .expect("alice", |s| s.status == "ongoing")
+        .try_run(config.clone())
+        .unwrap_err();
+    assert!(failure.reason.contains("timed out after 5s"), "{}", failure);
+
+    Scenario::new()
+        .quick_player("alice")
+        .advance(Duration::from_secs(20))
+        .expect("alice", |s| s.status == "ongoing")
+        .run(config);
+}
+
+#[test]
+fn failure_report_includes_location_and_every_transcript() {
+    let failure = Scenario::new()
+        .player("alice")
+        .player("bob")
+        .move_("alice", 4)
+        .expect_status("bob", eq("X_win"))
+        .try_run(Config::default())
+        .unwrap_err();
+
+    let report = failure.to_string();
+    assert!(report.contains("step #3 `expect_status(bob, == \"X_win\")`"), "{}", report);
+    assert!(report.contains("scenario_meta.rs"), "{}", report);
+    assert!(report.contains("--- alice (X) ---"), "{}", report);
+    assert!(report.contains("--- bob (O) ---"), "{}", report);
+    assert!(report.contains("board=.../.X./..."), "{}", report);
+}
+
+#[test]
+fn unknown_client_is_reported() {
+    let failure = Scenario::new().move_("nobody", 0).try_run(Config::default()).unwrap_err();
+    assert!(failure.reason.contains("unknown client `nobody`"), "{}", failure);
+}
+
+#[test]
+#[should_panic(expected = "timed out")]
+fn run_panics_with_report() {
+    Scenario::new()
+        .timeout(Duration::from_secs(1))
+        .player("alice")
+        .expect_status("alice", eq("draw"))
+        .run(Config::default());
+}
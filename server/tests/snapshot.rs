@@ -0,0 +1,127 @@
+mod scenario;
+
+use scenario::{play_alternately, TestServer};
+use server::config::{Config, SnapshotFormat};
+use server::game::SharedGame;
+use server::serialization::{self, BincodeSerializer, JsonSerializer, Serializer, BINCODE_MAGIC};
+use server::service::TicTacToeService;
+use server::snapshot::{GameSnapshot, SnapshotWriter};
+use server::tictactoe::{GameState, Join};
+use tokio::sync::mpsc;
+use tonic::Status;
+
+/// X가 윗줄을 채워 이기는 게임 (X: 0, 1, 2 / O: 3, 4)
+async fn finished_game() -> SharedGame {
+    let mut game = SharedGame::new("5".into(), 3, 3);
+    let (tx, _rx) = mpsc::channel::<Result<GameState, Status>>(64);
+    let alice = Join { player_id: "alice".into(), ..Join::default() };
+    game.join_player(&alice, tx.clone(), None).await.unwrap();
+    game.join_player(&Join::default(), tx, None).await.unwrap();
+    for (symbol, position) in [("X", 0), ("O", 3), ("X", 1), ("O", 4), ("X", 2)] {
+        game.apply_move(symbol, position).unwrap();
+    }
+    game
+}
+
+#[tokio::test]
+async fn snapshot_roundtrips_through_the_file() {
+    let dir = scenario::temp_dir("snapshots-roundtrip");
+    let game = finished_game().await;
+    assert_eq!(game.status, "X_win");
+
+    let snapshot = GameSnapshot::of(&game);
+    let json = serde_json::to_string(&snapshot).unwrap();
+    assert_eq!(serde_json::from_str::<GameSnapshot>(&json).unwrap(), snapshot);
+
+    let writer = SnapshotWriter::open(&dir, SnapshotFormat::Json).unwrap();
+    writer.write(&game);
+    writer.flush().await;
+    let loaded = GameSnapshot::load(&writer.path_for("5")).unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert_eq!(loaded.game_id, "5");
+    assert_eq!(loaded.outcome, "X_win");
+    assert_eq!(loaded.board, game.board);
+    assert_eq!(loaded.players[0].player_id, "alice");
+    assert_eq!((loaded.players[1].symbol.as_str(), loaded.players[1].marker.as_str()), ("O", "O"));
+    assert_eq!(loaded.moves.iter().map(|m| m.position).collect::<Vec<_>>(), [0, 3, 1, 4, 2]);
+    assert!(loaded.ended_at_unix >= loaded.created_at_unix);
+
+    // 수를 다시 두면 수마다 보드가 하나씩 나오고 마지막 보드는 끝난 보드와 같음
+    let boards = loaded.boards();
+    assert_eq!(boards.len(), 5);
+    assert_eq!(boards[0], ["X", "", "", "", "", "", "", "", ""]);
+    assert_eq!(boards.last(), Some(&game.board));
+}
+
+#[tokio::test]
+async fn bincode_snapshots_are_smaller_and_load_like_json_ones() {
+    let dir = scenario::temp_dir("snapshots-bincode");
+    let game = finished_game().await;
+    let writer = SnapshotWriter::open(&dir, SnapshotFormat::Bincode).unwrap();
+    writer.write(&game);
+    writer.flush().await;
+    let path = writer.path_for("5");
+    let bytes = std::fs::read(&path).unwrap();
+    let loaded = GameSnapshot::load(&path);
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert_eq!(path.extension().and_then(|e| e.to_str()), Some("bin"));
+    assert!(bytes.starts_with(BINCODE_MAGIC));
+    assert_eq!(serialization::detect(&bytes), SnapshotFormat::Bincode);
+    let loaded = loaded.unwrap();
+    assert_eq!(loaded.moves.iter().map(|m| m.position).collect::<Vec<_>>(), [0, 3, 1, 4, 2]);
+    assert_eq!(loaded.players[0].player_id, "alice");
+
+    // 같은 스냅샷을 JSON으로 쓰면 더 크고, 두 형식 모두 원래 스냅샷으로 되돌아옴
+    let json = JsonSerializer.serialize(&loaded);
+    assert!(bytes.len() < json.len(), "bincode {} bytes, JSON {} bytes", bytes.len(), json.len());
+    assert_eq!(JsonSerializer.deserialize(&json).unwrap(), loaded);
+    assert_eq!(BincodeSerializer.deserialize(&BincodeSerializer.serialize(&loaded)).unwrap(), loaded);
+}
+
+#[tokio::test]
+async fn reading_a_snapshot_with_the_other_format_fails_cleanly() {
+    let snapshot = GameSnapshot::of(&finished_game().await);
+    let json = JsonSerializer.serialize(&snapshot);
+    let bincode = BincodeSerializer.serialize(&snapshot);
+
+    let error = BincodeSerializer.deserialize(&json).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    let error = JsonSerializer.deserialize(&bincode).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+
+    // 매직 바이트는 맞지만 본문이 잘렸거나 뒤에 군더더기가 붙어도 오류
+    assert!(BincodeSerializer.deserialize(&bincode[..bincode.len() - 3]).is_err());
+    assert!(BincodeSerializer.deserialize(&[bincode.as_slice(), b"extra"].concat()).is_err());
+    assert!(BincodeSerializer.deserialize(BINCODE_MAGIC).is_err());
+
+    // 설정을 bincode로 바꾼 뒤에도 예전에 쓴 JSON 스냅샷은 파일 머리로 알아보고 읽음
+    let dir = scenario::temp_dir("snapshots-migration");
+    let (old, new) = (dir.join("5.json"), dir.join("5.bin"));
+    std::fs::write(&old, &json).unwrap();
+    std::fs::write(&new, &bincode).unwrap();
+    let loaded = (GameSnapshot::load(&old), GameSnapshot::load(&new));
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(loaded.0.unwrap(), snapshot);
+    assert_eq!(loaded.1.unwrap(), snapshot);
+}
+
+#[tokio::test]
+async fn finished_game_is_written_as_a_snapshot_file() {
+    let dir = scenario::temp_dir("snapshots-service");
+    let service = TicTacToeService::new(Config::default()).with_snapshots(SnapshotWriter::open(&dir, SnapshotFormat::Json).unwrap());
+    let server = TestServer::serve(service).await;
+    let alice = server.join().await;
+    let mut bob = server.join().await;
+
+    let last = play_alternately(&alice, &mut bob, &[0, 3, 1, 4, 2]).await;
+    assert_eq!(last.status, "X_win");
+
+    server.service.shutdown().await;
+    let snapshot = GameSnapshot::load(&dir.join(format!("{}.json", last.game_id))).unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(snapshot.outcome, "X_win");
+    assert_eq!(snapshot.board, last.board);
+    assert_eq!(snapshot.moves.len(), 5);
+}
@@ -0,0 +1,72 @@
+mod scenario;
+
+use scenario::Scenario;
+use tonic::Code;
+use server::config::Config;
+
+#[test]
+fn spectator_receives_snapshot_and_moves() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .move_("alice", 4)
+        .expect_state(|s| s.board[4] == "X")
+        .spectator("carol", "alice")
+        .expect("carol", |s| s.board[4] == "X" && s.your_symbol.is_empty())
+        .move_("bob", 0)
+        .expect_state(|s| s.board[0] == "O" && s.next_player == "X")
+        .list_games(|r| r.games[0].spectator_count == 1)
+        .run(Config::default());
+}
+
+#[test]
+fn spectator_cannot_move() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .spectator("carol", "alice")
+        .move_("carol", 4)
+        .expect("carol", |s| s.error_message == "Spectators cannot make moves.")
+        .move_("alice", 4)
+        .expect_state(|s| s.board[4] == "X")
+        .run(Config::default());
+}
+
+#[test]
+fn observer_joining_mid_game_gets_the_board_first() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .move_("alice", 4)
+        .expect_state(|s| s.board[4] == "X")
+        .observer("carol", Some("alice"))
+        .expect("carol", |s| s.board[4] == "X" && s.status == "ongoing" && s.your_symbol.is_empty())
+        .move_("bob", 0)
+        .expect("carol", |s| s.board[0] == "O" && s.next_player == "X")
+        .list_games(|r| r.games[0].spectator_count == 1)
+        .disconnect("carol")
+        .list_games(|r| r.games[0].spectator_count == 0)
+        .run(Config::default());
+}
+
+#[test]
+fn observer_without_game_id_watches_the_newest_game() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .player("carol")
+        .player("dave")
+        .move_("carol", 8)
+        .expect_state(|s| s.status == "ongoing")
+        .observer("eve", None)
+        .expect("eve", |s| s.board[8] == "X" && s.board.iter().filter(|c| !c.is_empty()).count() == 1)
+        .run(Config::default());
+}
+
+#[test]
+fn observer_of_unknown_game_is_refused() {
+    Scenario::new()
+        .observer("carol", None)
+        .expect_error("carol", Code::NotFound)
+        .run(Config::default());
+}
@@ -0,0 +1,230 @@
+mod scenario;
+
+use std::time::Duration;
+
+use scenario::{eq, Scenario, TestServer};
+use server::config::Config;
+use server::elo::{EloRating, RatedResult, RatingBook};
+use server::service::TicTacToeService;
+use server::stats::{self, CompletedGame, LeaderboardCache, SeatOutcome, SeatResult, StatsBook};
+use server::tictactoe::tic_tac_toe_server::TicTacToe;
+use server::tictactoe::{Join, LeaderboardRequest, LeaderboardResponse, SortField};
+use tonic::{Code, Request};
+
+fn seat(player_id: &str, outcome: SeatOutcome, move_times_ms: &[u64]) -> SeatResult {
+    let move_times = move_times_ms.iter().map(|&ms| Duration::from_millis(ms)).collect();
+    SeatResult { player_id: player_id.into(), player_name: String::new(), outcome, move_times }
+}
+
+#[test]
+fn stats_book_accumulates_and_persists_results() {
+    let path = std::env::temp_dir().join(format!("tictactoe-stats-{}.toml", std::process::id()));
+    let mut book = StatsBook::load(Some(&path)).unwrap();
+    book.record(&CompletedGame { seats: vec![seat("alice", SeatOutcome::Win, &[100, 300]), seat("bob", SeatOutcome::Loss, &[200])] });
+    book.record(&CompletedGame { seats: vec![seat("alice", SeatOutcome::Forfeit, &[]), seat("bob", SeatOutcome::Win, &[400])] });
+    book.record(&CompletedGame { seats: vec![seat("alice", SeatOutcome::OpponentTimedOut, &[]), seat("bob", SeatOutcome::TimedOut, &[])] });
+
+    let reloaded = StatsBook::load(Some(&path));
+    std::fs::remove_file(&path).unwrap();
+    let reloaded = reloaded.unwrap();
+    let alice = reloaded.get("alice").unwrap();
+    assert_eq!((alice.games_played, alice.wins, alice.losses, alice.forfeits, alice.timeouts), (3, 1, 1, 1, 0));
+    assert_eq!(alice.average_move_time_ms(), 200);
+    let bob = reloaded.get("bob").unwrap();
+    assert_eq!((bob.games_played, bob.wins, bob.losses, bob.forfeits, bob.timeouts), (3, 1, 1, 0, 1));
+    assert_eq!(bob.average_move_time_ms(), 300);
+    assert_eq!(reloaded.get("carol"), None);
+}
+
+#[test]
+fn leaderboard_sorts_by_the_chosen_field_and_breaks_ties_by_player_id() {
+    let mut book = StatsBook::default();
+    book.record(&CompletedGame { seats: vec![seat("carol", SeatOutcome::Win, &[50]), seat("bob", SeatOutcome::Loss, &[900])] });
+    book.record(&CompletedGame { seats: vec![seat("alice", SeatOutcome::Win, &[500]), seat("bob", SeatOutcome::Loss, &[])] });
+    book.record(&CompletedGame { seats: vec![seat("alice", SeatOutcome::Draw, &[]), seat("dave", SeatOutcome::Draw, &[])] });
+
+    let ratings = RatingBook::default();
+    let ids = |sort_by, limit| book.leaderboard(sort_by, &ratings, 0, limit).0.into_iter().map(|(id, _)| id).collect::<Vec<_>>();
+    assert_eq!(ids(SortField::Wins, 10), ["alice", "carol", "bob", "dave"]);
+    assert_eq!(ids(SortField::GamesPlayed, 2), ["alice", "bob"]);
+    assert_eq!(ids(SortField::WinRate, 10), ["carol", "alice", "bob", "dave"]);
+    // 시간을 잰 수가 없는 dave는 빠짐
+    assert_eq!(ids(SortField::AverageMoveTime, 10), ["carol", "alice", "bob"]);
+}
+
+#[test]
+fn leaderboard_pages_continue_the_ranking_and_report_the_total() {
+    let mut book = StatsBook::default();
+    for (winner, loser) in [("alice", "bob"), ("alice", "carol"), ("bob", "carol"), ("alice", "dave"), ("erin", "dave")] {
+        book.record(&CompletedGame { seats: vec![seat(winner, SeatOutcome::Win, &[]), seat(loser, SeatOutcome::Loss, &[])] });
+    }
+    let ratings = RatingBook::default();
+    let page = |offset, limit| {
+        let (leaders, total) = book.leaderboard(SortField::Wins, &ratings, offset, limit);
+        (leaders.into_iter().map(|(id, _)| id).collect::<Vec<_>>(), total)
+    };
+    assert_eq!(page(0, 2), (vec!["alice".to_string(), "bob".to_string()], 5));
+    assert_eq!(page(2, 2), (vec!["erin".to_string(), "carol".to_string()], 5));
+    assert_eq!(page(4, 2), (vec!["dave".to_string()], 5));
+    assert_eq!(page(10, 2), (Vec::new(), 5));
+
+    let response = stats::leaderboard_page(&book, &ratings, SortField::Wins, 2, 2);
+    assert_eq!(response.total_count, 5);
+    let ranks: Vec<(i32, &str, u32, u32)> = response.entries.iter().map(|e| (e.rank, e.player_id.as_str(), e.wins, e.losses)).collect();
+    assert_eq!(ranks, [(3, "erin", 1, 0), (4, "carol", 0, 2)]);
+    assert_eq!(response.players.len(), 2);
+}
+
+#[test]
+fn leaderboard_sorts_by_elo_rating_with_unrated_players_at_the_initial_rating() {
+    let mut book = StatsBook::default();
+    book.record(&CompletedGame { seats: vec![seat("alice", SeatOutcome::Win, &[]), seat("bob", SeatOutcome::Loss, &[])] });
+    book.record(&CompletedGame { seats: vec![seat("carol", SeatOutcome::Draw, &[]), seat("dave", SeatOutcome::Draw, &[])] });
+    let mut ratings = RatingBook::default();
+    ratings.record(&RatedResult::Decisive { winner: "bob".into(), loser: "alice".into() }, &EloRating::new(32.0));
+
+    let response = stats::leaderboard_page(&book, &ratings, SortField::EloRating, 0, 10);
+    let entries: Vec<(&str, i32)> = response.entries.iter().map(|e| (e.player_id.as_str(), e.elo_rating)).collect();
+    assert_eq!(entries, [("bob", 1216), ("carol", 1200), ("dave", 1200), ("alice", 1184)]);
+}
+
+#[test]
+fn leaderboard_entries_carry_the_last_chosen_name() {
+    let mut book = StatsBook::default();
+    let named = |id: &str, name: &str, outcome| SeatResult { player_name: name.into(), ..seat(id, outcome, &[]) };
+    book.record(&CompletedGame { seats: vec![named("alice", "Alice", SeatOutcome::Win), named("bob", "", SeatOutcome::Loss)] });
+    book.record(&CompletedGame { seats: vec![named("alice", "", SeatOutcome::Win), named("bob", "Bobby", SeatOutcome::Loss)] });
+    assert_eq!((book.name("alice"), book.name("bob"), book.name("carol")), (Some("Alice"), Some("Bobby"), None));
+
+    let response = stats::leaderboard_page(&book, &RatingBook::default(), SortField::Wins, 0, 10);
+    let names: Vec<&str> = response.entries.iter().map(|e| e.player_name.as_str()).collect();
+    assert_eq!(names, ["Alice", "Bobby"]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn cached_leaderboard_pages_expire_after_the_ttl() {
+    let ttl = Duration::from_secs(30);
+    let key = (SortField::Wins, 0, 10);
+    let response = LeaderboardResponse { total_count: 3, ..LeaderboardResponse::default() };
+    let mut cache = LeaderboardCache::default();
+    cache.insert(key, response.clone(), ttl);
+    assert_eq!(cache.get(key, ttl), Some(response.clone()));
+    assert_eq!(cache.get((SortField::Wins, 1, 10), ttl), None);
+
+    tokio::time::advance(Duration::from_secs(31)).await;
+    assert_eq!(cache.get(key, ttl), None);
+
+    // 0초면 캐시하지 않음
+    let mut disabled = LeaderboardCache::default();
+    disabled.insert(key, response, Duration::ZERO);
+    assert_eq!(disabled.get(key, Duration::ZERO), None);
+}
+
+#[tokio::test]
+async fn get_leaderboard_pages_and_rejects_negative_pages() {
+    let mut book = StatsBook::default();
+    book.record(&CompletedGame { seats: vec![seat("alice", SeatOutcome::Win, &[]), seat("bob", SeatOutcome::Loss, &[])] });
+    book.record(&CompletedGame { seats: vec![seat("carol", SeatOutcome::Win, &[]), seat("alice", SeatOutcome::Loss, &[])] });
+    let service = TicTacToeService::new(Config::default()).with_stats(book);
+
+    let request = |page, page_size| LeaderboardRequest { sort_by: SortField::GamesPlayed as i32, page, page_size, ..LeaderboardRequest::default() };
+    let second = service.get_leaderboard(Request::new(request(1, 2))).await.unwrap().into_inner();
+    assert_eq!(second.total_count, 3);
+    let entries: Vec<(i32, &str)> = second.entries.iter().map(|e| (e.rank, e.player_id.as_str())).collect();
+    assert_eq!(entries, [(3, "carol")]);
+
+    // page_size가 없으면 limit이 한 페이지
+    let limited = LeaderboardRequest { sort_by: SortField::GamesPlayed as i32, limit: 1, ..LeaderboardRequest::default() };
+    let first = service.get_leaderboard(Request::new(limited)).await.unwrap().into_inner();
+    assert_eq!(first.entries.iter().map(|e| e.player_id.as_str()).collect::<Vec<_>>(), ["alice"]);
+
+    let error = service.get_leaderboard(Request::new(request(-1, 2))).await.unwrap_err();
+    assert_eq!(error.code(), Code::InvalidArgument);
+}
+
+#[test]
+fn finished_games_update_both_players_stats() {
+    Scenario::new()
+        // 1국: alice(X) 승리
+        .rated_player("alice", "alice")
+        .rated_player("bob", "bob")
+        .expect_status("bob", eq("ongoing"))
+        .move_("alice", 0)
+        .move_("bob", 3)
+        .move_("alice", 1)
+        .move_("bob", 4)
+        .move_("alice", 2)
+        .expect_status("bob", eq("X_win"))
+        .player_stats("alice", |s| s.games_played == 1 && s.wins == 1)
+        .player_stats("bob", |s| s.games_played == 1 && s.losses == 1 && s.forfeits == 0)
+        // 2국: alice(X) 기권
+        .rated_player("carol", "alice")
+        .rated_player("dave", "bob")
+        .expect_status("dave", eq("ongoing"))
+        .resign("carol")
+        .expect_status("dave", eq("O_win_by_resignation"))
+        .player_stats("alice", |s| s.games_played == 2 && s.wins == 1 && s.losses == 1 && s.forfeits == 1)
+        .player_stats("bob", |s| s.games_played == 2 && s.wins == 1 && s.losses == 1 && s.forfeits == 0)
+        .leaderboard(SortField::GamesPlayed, 0, |r| {
+            r.players.iter().map(|p| (p.player_id.as_str(), p.games_played)).eq([("alice", 2), ("bob", 2)])
+        })
+        .run(Config::default());
+}
+
+#[test]
+fn idle_timeout_counts_against_the_silent_player_only() {
+    let config = Config::default();
+    let idle = config.idle_timeout().unwrap();
+    Scenario::new()
+        .rated_player("alice", "alice")
+        .rated_player("bob", "bob")
+        .move_("alice", 4)
+        .expect_status("bob", eq("ongoing"))
+        .stop_reading("bob")
+        .advance(idle + Duration::from_secs(5))
+        .expect_status("alice", eq("idle_timeout"))
+        .player_stats("bob", |s| s.games_played == 1 && s.timeouts == 1 && s.losses == 0)
+        .player_stats("alice", |s| s.games_played == 1 && s.timeouts == 0 && s.wins == 0)
+        .run(config);
+}
+
+#[tokio::test]
+async fn stats_file_survives_a_server_restart() {
+    let path = std::env::temp_dir().join(format!("tictactoe-stats-restart-{}.toml", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let started = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64;
+    // 서버를 두 번 띄워 매번 alice(X)가 이김: 두 번째 서버는 첫 서버가 남긴 파일에서 이어 셈
+    for games in 1..=2 {
+        let service = TicTacToeService::new(Config::default()).with_stats(StatsBook::load(Some(&path)).unwrap());
+        let server = TestServer::serve(service).await;
+        let mut x = server.join_with(Join { player_id: "alice".into(), ..Join::default() }).await;
+        x.next_update().await;
+        let mut o = server.join_with(Join { player_id: "bob".into(), ..Join::default() }).await;
+        o.update_where(|s| s.status == "ongoing").await;
+        for (turn, position) in [0, 3, 1, 4, 2].into_iter().enumerate() {
+            let mover = if turn % 2 == 0 { &mut x } else { &mut o };
+            mover.send_move(position).await;
+            let state = mover.update_where(|s| !s.board[position as usize].is_empty()).await;
+            assert_eq!(state.status, if turn == 4 { "X_win" } else { "ongoing" });
+        }
+
+        // 통계 파일은 블로킹 스레드에서 따로 쓰므로 반영될 때까지 기다림
+        let mut attempts = 0;
+        while StatsBook::load(Some(&path)).unwrap().get("alice").map_or(0, |s| s.games_played) < games {
+            attempts += 1;
+            assert!(attempts < 200, "통계 파일에 {}번째 게임이 반영되지 않음", games);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    let reopened = StatsBook::load(Some(&path));
+    std::fs::remove_file(&path).unwrap();
+    let reopened = reopened.unwrap();
+    let alice = reopened.get("alice").unwrap();
+    assert_eq!((alice.games_played, alice.wins), (2, 2));
+    assert!(alice.last_seen >= started);
+    let bob = reopened.get("bob").unwrap();
+    assert_eq!((bob.games_played, bob.losses), (2, 2));
+    let leaders: Vec<String> = reopened.leaderboard(SortField::Wins, &RatingBook::default(), 0, 10).0.into_iter().map(|(id, _)| id).collect();
+    assert_eq!(leaders, ["alice", "bob"]);
+}
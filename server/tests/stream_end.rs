@@ -0,0 +1,154 @@
+mod scenario;
+
+use std::time::Duration;
+
+use scenario::{eq, Scenario};
+use server::config::Config;
+use server::tictactoe::EndReason;
+use tonic::Code;
+
+#[test]
+fn finished_game_closes_every_stream_cleanly() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .spectator("carol", "alice")
+        .move_("alice", 0)
+        .move_("bob", 3)
+        .move_("alice", 1)
+        .move_("bob", 4)
+        .move_("alice", 2)
+        .expect_state(|s| s.status == "X_win")
+        .expect_closed("alice")
+        .expect_closed("bob")
+        .expect_closed("carol")
+        .run(Config::default());
+}
+
+#[test]
+fn resuming_a_seat_ends_the_previous_connection() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .expect_status("bob", eq("ongoing"))
+        .resume("bob-laptop", "bob")
+        .expect_end("bob", Code::Aborted, EndReason::SessionTakenOver)
+        .expect("bob-laptop", |s| s.your_symbol == "O" && s.status == "ongoing")
+        .move_("alice", 4)
+        .expect("bob-laptop", |s| s.board[4] == "X")
+        .run(Config::default());
+}
+
+#[test]
+fn abandoned_game_ends_remaining_streams() {
+    let config = Config::default();
+    let grace = config.reconnect_grace();
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .spectator("carol", "alice")
+        .expect_status("alice", eq("ongoing"))
+        .disconnect("bob")
+        .advance(grace + Duration::from_secs(1))
+        .expect_end("alice", Code::Aborted, EndReason::GameAbandoned)
+        .expect_end("carol", Code::Aborted, EndReason::GameAbandoned)
+        .run(config);
+}
+
+#[test]
+fn server_shutdown_ends_players_and_spectators() {
+    let config = Config { shutdown_drain_timeout_secs: 1, ..Config::default() };
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .spectator("carol", "alice")
+        .player("dave")
+        .expect_status("dave", eq("waiting"))
+        .shutdown_server()
+        .expect_end("dave", Code::Unavailable, EndReason::ServerShutdown)
+        .expect("alice", |s| s.info_message == "The server is shutting down. Finish your game within 1s.")
+        .advance(Duration::from_secs(2))
+        .expect("alice", |s| s.status == "server_shutdown" && s.info_message == "The server shut down before this game finished.")
+        .expect_status("bob", eq("server_shutdown"))
+        .expect_status("carol", eq("server_shutdown"))
+        .expect_closed("alice")
+        .expect_closed("bob")
+        .expect_closed("carol")
+        .run(config);
+}
+
+#[test]
+fn games_finished_during_the_drain_end_normally() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .move_("alice", 0)
+        .move_("bob", 3)
+        .move_("alice", 1)
+        .move_("bob", 4)
+        .shutdown_server()
+        .expect("bob", |s| s.info_message.starts_with("The server is shutting down."))
+        .move_("alice", 2)
+        .expect_state(|s| s.status == "X_win")
+        .expect_closed("alice")
+        .expect_closed("bob")
+        .run(Config::default());
+}
+
+#[test]
+fn new_players_are_turned_away_while_draining() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .expect_status("bob", eq("ongoing"))
+        .shutdown_server()
+        .player("carol")
+        .expect_error("carol", Code::Unavailable)
+        .spectator("dave", "alice")
+        .expect_status("dave", eq("ongoing"))
+        .run(Config::default());
+}
+
+#[test]
+fn lonely_player_is_released_after_the_waiting_timeout() {
+    let config = Config { waiting_timeout_ms: 300, ..Config::default() };
+    let timeout = config.waiting_timeout();
+    Scenario::new()
+        .player("alice")
+        .expect_status("alice", eq("waiting"))
+        .advance(timeout + Duration::from_millis(50))
+        .expect("alice", |s| s.error_message == "No opponent joined in time. The game was closed.")
+        .expect_end("alice", Code::DeadlineExceeded, EndReason::WaitingTimeout)
+        .list_games(|list| list.games.is_empty())
+        .run(config);
+}
+
+#[test]
+fn waiting_timeout_does_not_fire_after_the_game_starts() {
+    let config = Config { waiting_timeout_ms: 300, ..Config::default() };
+    let timeout = config.waiting_timeout();
+    Scenario::new()
+        .player("alice")
+        .advance(timeout / 2)
+        .player("bob")
+        .expect_status("alice", eq("ongoing"))
+        .advance(timeout * 2)
+        .move_("alice", 4)
+        .expect("bob", |s| s.board[4] == "X" && s.error_message.is_empty())
+        .run(config);
+}
+
+#[test]
+fn waiting_timeout_does_not_fire_for_a_player_who_left() {
+    let config = Config { waiting_timeout_ms: 300, ..Config::default() };
+    let timeout = config.waiting_timeout();
+    Scenario::new()
+        .player("alice")
+        .expect_status("alice", eq("waiting"))
+        .disconnect("alice")
+        .advance(timeout * 2)
+        // 재접속 유예 시간 안에는 자리가 그대로 남아 있음
+        .reconnect("alice")
+        .expect("alice", |s| s.status == "waiting" && s.error_message.is_empty())
+        .run(config);
+}
@@ -0,0 +1,113 @@
+mod scenario;
+
+use scenario::Scenario;
+use server::config::Config;
+use server::events;
+use server::game::SharedGame;
+use server::service::TicTacToeService;
+use server::tictactoe::tic_tac_toe_server::TicTacToe;
+use server::tictactoe::{CreateGameRequest, GameMode, Join};
+use tokio::sync::mpsc;
+use tonic::{Code, Request};
+
+fn with_swap_rule() -> CreateGameRequest {
+    CreateGameRequest { swap_rule: true, ..CreateGameRequest::default() }
+}
+
+#[test]
+fn the_second_player_can_take_over_the_first_move() {
+    Scenario::new()
+        .create_player("alice", with_swap_rule())
+        .join_game("bob", "alice")
+        .expect_state(|s| s.status == "ongoing" && !s.swap_available)
+        .move_("alice", 4)
+        .expect_state(|s| s.board[4] == "X" && s.next_player == "O" && s.swap_available)
+        .swap("bob")
+        // 가운데 돌이 O가 되고, 자리는 그대로 X(alice)의 차례
+        .expect("alice", |s| s.board[4] == "O" && s.next_player == "X" && !s.swap_available && s.your_move_count == 0)
+        .expect("bob", |s| s.info_message == "Player O swapped: the first move is now theirs." && s.your_move_count == 1)
+        .move_("alice", 0)
+        .expect_state(|s| s.board[0] == "X" && s.next_player == "O")
+        .run(Config::default());
+}
+
+#[test]
+fn swap_is_rejected_at_any_other_point() {
+    Scenario::new()
+        .create_player("alice", with_swap_rule())
+        .join_game("bob", "alice")
+        .expect_state(|s| s.status == "ongoing")
+        // 첫 수 전
+        .swap("bob")
+        .expect("bob", |s| s.error_message == "You can only swap as your reply to the first move.")
+        .move_("alice", 4)
+        .expect_state(|s| s.swap_available)
+        // 첫 수를 둔 쪽은 스왑할 수 없음
+        .swap("alice")
+        .expect("alice", |s| s.error_message == "You can only swap as your reply to the first move.")
+        // 응수한 뒤에는 늦음
+        .move_("bob", 0)
+        .expect_state(|s| s.board[0] == "O" && !s.swap_available)
+        .move_("alice", 8)
+        .expect_state(|s| s.board[8] == "X")
+        .swap("bob")
+        .expect("bob", |s| s.error_message == "You can only swap as your reply to the first move." && s.board[4] == "X")
+        .run(Config::default());
+}
+
+#[test]
+fn games_without_the_rule_never_offer_a_swap() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .expect_state(|s| s.status == "ongoing")
+        .move_("alice", 4)
+        .expect_state(|s| s.board[4] == "X" && !s.swap_available)
+        .swap("bob")
+        .expect("bob", |s| s.error_message == "The swap rule is not enabled in this game.")
+        .run(Config::default());
+}
+
+#[test]
+fn after_a_swap_the_swapper_owns_the_stone_and_turns_alternate() {
+    Scenario::new()
+        .create_player("alice", with_swap_rule())
+        .join_game("bob", "alice")
+        .move_("alice", 4)
+        .expect_state(|s| s.swap_available)
+        .swap("bob")
+        .expect_state(|s| s.next_player == "X")
+        // O(bob)가 가져온 가운데 돌로 3-4-5 줄을 이음
+        .move_("alice", 0)
+        .move_("bob", 3)
+        .move_("alice", 1)
+        .move_("bob", 5)
+        .expect_state(|s| s.status == "O_win")
+        .run(Config::default());
+}
+
+#[tokio::test]
+async fn swap_is_recorded_in_the_game_events() {
+    let mut game = SharedGame::new("1".into(), 3, 3);
+    game.swap_rule = true;
+    let (tx, _rx) = mpsc::channel(16);
+    game.join_player(&Join::default(), tx.clone(), None).await.unwrap();
+    game.join_player(&Join::default(), tx, None).await.unwrap();
+    game.apply_move("X", 2).unwrap();
+    assert_eq!(game.swap("O"), Ok(2));
+    assert_eq!((game.history[0].symbol.as_str(), game.history[0].mark.as_str()), ("O", "O"));
+
+    let state = events::fold(game.events());
+    assert_eq!((state.board[2].as_str(), state.next_player.as_str()), ("O", "X"));
+    assert_eq!(state.history[0].player_id, "O");
+    assert!(game.swap("X").is_err(), "한 번 스왑한 뒤에는 다시 할 수 없음");
+}
+
+#[tokio::test]
+async fn the_rule_cannot_be_combined_with_wild_mode() {
+    let service = TicTacToeService::new(Config::default());
+    let wild = CreateGameRequest { mode: GameMode::Wild as i32, ..with_swap_rule() };
+    let error = service.create_game(Request::new(wild)).await.unwrap_err();
+    assert_eq!(error.code(), Code::InvalidArgument);
+    assert!(service.create_game(Request::new(with_swap_rule())).await.is_ok());
+}
@@ -0,0 +1,164 @@
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+use rcgen::{BasicConstraints, CertificateParams, CertifiedIssuer, IsCa, KeyPair};
+use server::config::Config;
+use server::service::TicTacToeService;
+use server::tictactoe::play_request::Action;
+use server::tictactoe::tic_tac_toe_client::TicTacToeClient;
+use server::tictactoe::{CreateGameRequest, GameState, Join, Move, PlayRequest};
+use server::tls::TlsFiles;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Identity, Server};
+use tonic::Streaming;
+
+/// 테스트마다 만드는 CA와, 그 CA가 서명한 서버("localhost")와 클라이언트 인증서
+struct Pki {
+    dir: PathBuf,
+    ca: PathBuf,
+    server_cert: PathBuf,
+    server_key: PathBuf,
+    client_cert: PathBuf,
+    client_key: PathBuf,
+}
+
+impl Pki {
+    fn generate(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("ttt-tls-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca = CertifiedIssuer::self_signed(ca_params, KeyPair::generate().unwrap()).unwrap();
+        let write = |file: &str, contents: String| {
+            let path = dir.join(file);
+            std::fs::write(&path, contents).unwrap();
+            path
+        };
+        let leaf = |subject: &str| {
+            let key = KeyPair::generate().unwrap();
+            let cert = CertificateParams::new(vec![subject.to_string()]).unwrap().signed_by(&key, &ca).unwrap();
+            (cert.pem(), key.serialize_pem())
+        };
+        let (server_cert, server_key) = leaf("localhost");
+        let (client_cert, client_key) = leaf("player");
+        Pki {
+            ca: write("ca.pem", ca.pem()),
+            server_cert: write("server.pem", server_cert),
+            server_key: write("server.key", server_key),
+            client_cert: write("client.pem", client_cert),
+            client_key: write("client.key", client_key),
+            dir,
+        }
+    }
+
+    fn server_files(&self, client_auth: bool) -> TlsFiles {
+        TlsFiles {
+            cert: self.server_cert.clone(),
+            key: self.server_key.clone(),
+            client_ca: client_auth.then(|| self.ca.clone()),
+        }
+    }
+
+    fn client_identity(&self) -> Identity {
+        Identity::from_pem(std::fs::read(&self.client_cert).unwrap(), std::fs::read(&self.client_key).unwrap())
+    }
+}
+
+impl Drop for Pki {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+async fn start_tls(files: &TlsFiles) -> SocketAddr {
+    let tls = files.load().unwrap();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = Server::builder()
+        .tls_config(tls)
+        .unwrap()
+        .add_service(TicTacToeService::new(Config::default()).into_server())
+        .serve_with_incoming(TcpListenerStream::new(listener));
+    tokio::spawn(server);
+    addr
+}
+
+/// CA로 서버 인증서를 검증하며 "localhost"로 접속 (identity가 있으면 클라이언트 인증서 제시)
+async fn connect(addr: SocketAddr, ca: &Path, identity: Option<Identity>) -> TicTacToeClient<Channel> {
+    let mut tls = ClientTlsConfig::new().ca_certificate(Certificate::from_pem(std::fs::read(ca).unwrap())).domain_name("localhost");
+    if let Some(identity) = identity {
+        tls = tls.identity(identity);
+    }
+    let channel = Channel::from_shared(format!("https://{}", addr)).unwrap().tls_config(tls).unwrap().connect_lazy();
+    TicTacToeClient::new(channel)
+}
+
+async fn join(client: &mut TicTacToeClient<Channel>) -> (mpsc::Sender<PlayRequest>, Streaming<GameState>) {
+    let (tx, rx) = mpsc::channel(8);
+    tx.send(PlayRequest { action: Some(Action::Join(Join::default())) }).await.unwrap();
+    let updates = client.play(ReceiverStream::new(rx)).await.unwrap().into_inner();
+    (tx, updates)
+}
+
+#[tokio::test]
+async fn plays_a_game_over_tls() {
+    let pki = Pki::generate("game");
+    let addr = start_tls(&pki.server_files(false)).await;
+    let mut client = connect(addr, &pki.ca, None).await;
+    let (alice, _alice_updates) = join(&mut client).await;
+    let (bob, mut bob_updates) = join(&mut client).await;
+
+    // X: 0, 1, 2 / O: 3, 4
+    let moves = [(&alice, 0), (&bob, 3), (&alice, 1), (&bob, 4), (&alice, 2)];
+    let mut last = GameState::default();
+    for (count, (tx, position)) in moves.into_iter().enumerate() {
+        tx.send(PlayRequest { action: Some(Action::Move(Move { position, ..Move::default() })) }).await.unwrap();
+        while last.board.iter().filter(|cell| !cell.is_empty()).count() <= count {
+            last = bob_updates.message().await.unwrap().unwrap();
+        }
+    }
+    assert_eq!(last.status, "X_win");
+}
+
+#[tokio::test]
+async fn plaintext_clients_cannot_talk_to_a_tls_server() {
+    let pki = Pki::generate("plain");
+    let addr = start_tls(&pki.server_files(false)).await;
+    let channel = Channel::from_shared(format!("http://{}", addr)).unwrap().connect_lazy();
+    assert!(TicTacToeClient::new(channel).create_game(CreateGameRequest::default()).await.is_err());
+}
+
+#[tokio::test]
+async fn client_auth_requires_a_certificate_from_the_ca() {
+    let pki = Pki::generate("mtls");
+    let addr = start_tls(&pki.server_files(true)).await;
+
+    let mut anonymous = connect(addr, &pki.ca, None).await;
+    assert!(anonymous.create_game(CreateGameRequest::default()).await.is_err());
+
+    let mut trusted = connect(addr, &pki.ca, Some(pki.client_identity())).await;
+    assert!(trusted.create_game(CreateGameRequest::default()).await.is_ok());
+}
+
+#[test]
+fn misconfigured_files_fail_to_load_with_the_file_named() {
+    let pki = Pki::generate("broken");
+
+    let missing = TlsFiles { cert: pki.dir.join("missing.pem"), ..pki.server_files(false) };
+    let error = missing.load().unwrap_err();
+    assert!(error.contains("서버 인증서 파일을 읽을 수 없습니다") && error.contains("missing.pem"), "{}", error);
+
+    let swapped = TlsFiles { key: pki.server_cert.clone(), ..pki.server_files(false) };
+    let error = swapped.load().unwrap_err();
+    assert!(error.contains("PEM PRIVATE KEY 블록이 없습니다"), "{}", error);
+
+    // 다른 인증서의 키
+    let mismatched = TlsFiles { key: pki.client_key.clone(), ..pki.server_files(false) };
+    let error = mismatched.load().unwrap_err();
+    assert!(error.contains("TLS 설정 오류"), "{}", error);
+
+    let bad_ca = TlsFiles { client_ca: Some(pki.server_key.clone()), ..pki.server_files(false) };
+    let error = bad_ca.load().unwrap_err();
+    assert!(error.contains("클라이언트 인증 CA"), "{}", error);
+}
@@ -0,0 +1,175 @@
+mod scenario;
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use scenario::{connect, start_server, TestPlayer};
+use server::config::Config;
+use server::game::is_finished_status;
+use server::manager::GameManager;
+use server::metrics::Metrics;
+use server::service::TicTacToeService;
+use server::tictactoe::tournament_service_client::TournamentServiceClient;
+use server::tictactoe::{CreateTournamentRequest, Join, TournamentId};
+use server::tournament::{winning_symbol, Bracket, TournamentManager, MAX_TOURNAMENT_PLAYERS};
+use tokio::sync::Mutex;
+use tonic::transport::Channel;
+use tonic::Code;
+
+/// 토너먼트 전체에 주는 시간 (로컬 루프백 기준으로 넉넉하게)
+const BUDGET: Duration = Duration::from_secs(30);
+
+fn players(n: usize) -> Vec<String> {
+    (0..n).map(|i| format!("p{}", i)).collect()
+}
+
+/// 라운드별 (X, O) 짝 (부전승은 O가 빈 문자열)
+fn pairings(bracket: &Bracket, round: usize) -> Vec<(String, String)> {
+    bracket.rounds[round].iter().map(|m| (m.player_x.clone(), m.player_o.clone().unwrap_or_default())).collect()
+}
+
+#[test]
+fn brackets_validate_players_and_give_top_seeds_byes() {
+    assert!(Bracket::new(players(1)).unwrap_err().contains("2~64명"));
+    assert!(Bracket::new(players(MAX_TOURNAMENT_PLAYERS + 1)).is_err());
+    assert!(Bracket::new(vec!["a".into(), "b".into(), "a".into()]).unwrap_err().contains("같은 참가자"));
+    assert!(Bracket::new(vec!["a".into(), " ".into()]).is_err());
+    assert!(Bracket::new(vec!["a".into(), "x".repeat(65)]).is_err());
+
+    let bracket = Bracket::new(players(8)).unwrap();
+    let expected = [("p0", "p7"), ("p1", "p6"), ("p2", "p5"), ("p3", "p4")];
+    assert_eq!(pairings(&bracket, 0), expected.map(|(x, o)| (x.to_string(), o.to_string())));
+    assert!(bracket.rounds[0].iter().all(|m| m.is_pending()));
+
+    // 5명: 8자리 중 3자리가 비어 높은 시드 셋이 부전승
+    let mut bracket = Bracket::new(players(5)).unwrap();
+    let byes: Vec<&str> = bracket.rounds[0].iter().filter(|m| !m.is_pending()).map(|m| m.player_x.as_str()).collect();
+    assert_eq!(byes, ["p0", "p1", "p2"]);
+    assert!(bracket.rounds[0].iter().filter(|m| m.player_o.is_none()).all(|m| m.winner == Some(m.player_x.clone())));
+    assert!(!bracket.round_complete() && !bracket.advance());
+
+    bracket.rounds[0][3].winner = Some("p4".into());
+    assert!(bracket.advance());
+    assert_eq!(pairings(&bracket, 1), [("p0".to_string(), "p1".to_string()), ("p2".to_string(), "p4".to_string())]);
+    bracket.rounds[1][0].winner = Some("p1".into());
+    bracket.rounds[1][1].winner = Some("p4".into());
+    assert!(bracket.advance());
+    assert_eq!(bracket.champion(), None);
+    bracket.rounds[2][0].winner = Some("p4".into());
+    assert_eq!(bracket.champion(), Some("p4"));
+    assert!(!bracket.advance());
+    assert_eq!(bracket.to_proto("t1").champion, "p4");
+}
+
+#[test]
+fn only_decisive_results_have_a_winner() {
+    assert_eq!(winning_symbol("X_win", None), Some("X"));
+    assert_eq!(winning_symbol("O_win_by_resignation", None), Some("O"));
+    assert_eq!(winning_symbol("idle_timeout", Some("X")), Some("O"));
+    for status in ["draw", "draw_agreed", "admin_terminated", "server_shutdown"] {
+        assert_eq!(winning_symbol(status, None), None, "{}", status);
+    }
+}
+
+#[tokio::test]
+async fn drawn_matches_are_replayed_with_sides_swapped() {
+    let games = Arc::new(Mutex::new(GameManager::new(100, Arc::new(Metrics::default()))));
+    let mut tournaments = TournamentManager::new(games.clone());
+    let id = tournaments.create(vec!["alice".into(), "bob".into()]).await.unwrap();
+    let mut updates = tournaments.watch(&id).unwrap();
+    let first = updates.recv().await.unwrap().unwrap();
+    let game_id = first.upcoming[0].game_id.clone();
+    let game = games.lock().await.get(&game_id).unwrap();
+    let reserved = game.with(|game| game.reserved.clone()).await;
+    assert_eq!(reserved, Some(["alice".to_string(), "bob".to_string()]));
+
+    tournaments.game_finished(&game_id, "draw", None).await;
+    let replay = updates.recv().await.unwrap().unwrap();
+    let rematch = &replay.upcoming[0];
+    assert_eq!((rematch.player_x.as_str(), rematch.player_o.as_str(), rematch.replays), ("bob", "alice", 1));
+    assert_ne!(rematch.game_id, game_id);
+    assert!(replay.message.contains("replaying"), "{}", replay.message);
+
+    // 이미 바뀐 게임이나 토너먼트와 상관없는 게임은 무시
+    tournaments.game_finished(&game_id, "X_win", None).await;
+    tournaments.game_finished("999", "X_win", None).await;
+    assert_eq!(tournaments.bracket(&id).unwrap().champion, "");
+
+    tournaments.game_finished(&rematch.game_id, "O_win_by_resignation", None).await;
+    let result = updates.recv().await.unwrap().unwrap();
+    assert!(result.message.contains("alice beat bob"), "{}", result.message);
+    let done = updates.recv().await.unwrap().unwrap();
+    assert_eq!(done.champion, "alice");
+    // 끝나면 스트림을 닫음
+    assert!(updates.recv().await.is_none());
+}
+
+/// 경기 게임에 `player_id`로 앉아 첫 번째 빈 칸에만 두는 봇 (게임이 끝날 때까지)
+async fn play_bot(channel: Channel, game_id: String, player_id: String) {
+    let mut bot = TestPlayer::join(channel, Join { game_id, player_id, ..Join::default() }).await;
+    while let Some(state) = bot.next_event().await {
+        let state = state.unwrap();
+        if is_finished_status(&state.status) {
+            break;
+        }
+        if state.status == "ongoing" && state.next_player == bot.symbol {
+            let position = state.board.iter().position(String::is_empty).unwrap() as i32;
+            bot.send_move(position).await;
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn eight_bots_play_a_tournament_to_the_end() {
+    let channel = connect(&start_server(TicTacToeService::new(Config::default()))).await;
+    let mut tournaments = TournamentServiceClient::new(channel.clone());
+    let id = tournaments.create_tournament(CreateTournamentRequest { players: players(8) }).await.unwrap().into_inner();
+    let mut updates = tournaments.watch_tournament(id.clone()).await.unwrap().into_inner();
+
+    let champion = tokio::time::timeout(BUDGET, async {
+        let mut started = HashSet::new();
+        while let Some(update) = updates.message().await.unwrap() {
+            for game in &update.upcoming {
+                if started.insert(game.game_id.clone()) {
+                    tokio::spawn(play_bot(channel.clone(), game.game_id.clone(), game.player_x.clone()));
+                    tokio::spawn(play_bot(channel.clone(), game.game_id.clone(), game.player_o.clone()));
+                }
+            }
+            if !update.champion.is_empty() {
+                return update.champion;
+            }
+        }
+        panic!("우승자 없이 스트림이 끝남");
+    })
+    .await
+    .expect("tournament did not finish within the time budget");
+
+    // 첫 빈 칸끼리 두면 먼저 두는 X가 이기므로 매 경기 높은 시드가 올라감
+    assert_eq!(champion, "p0");
+    let bracket = tournaments.get_bracket(id).await.unwrap().into_inner();
+    let sizes: Vec<usize> = bracket.rounds.iter().map(|round| round.matches.len()).collect();
+    assert_eq!(sizes, [4, 2, 1]);
+    let winners: Vec<&str> = bracket.rounds.iter().flat_map(|r| &r.matches).map(|m| m.winner.as_str()).collect();
+    assert_eq!(winners, ["p0", "p1", "p2", "p3", "p0", "p2", "p0"]);
+    assert_eq!(bracket.champion, "p0");
+}
+
+#[tokio::test]
+async fn match_games_seat_only_their_players() {
+    let channel = connect(&start_server(TicTacToeService::new(Config::default()))).await;
+    let mut tournaments = TournamentServiceClient::new(channel.clone());
+    let request = CreateTournamentRequest { players: vec!["alice".into(), "bob".into()] };
+    let id = tournaments.create_tournament(request).await.unwrap().into_inner();
+    let game_id = tournaments.get_bracket(id).await.unwrap().into_inner().rounds[0].matches[0].game_id.clone();
+
+    let mut mallory = TestPlayer::join(channel, Join { game_id, player_id: "mallory".into(), ..Join::default() }).await;
+    let code = mallory.next_error().await.code();
+    assert_eq!(code, Code::PermissionDenied);
+
+    let missing = TournamentId { tournament_id: "t999".into() };
+    assert_eq!(tournaments.get_bracket(missing.clone()).await.unwrap_err().code(), Code::NotFound);
+    assert_eq!(tournaments.watch_tournament(missing).await.unwrap_err().code(), Code::NotFound);
+    let solo = CreateTournamentRequest { players: vec!["alice".into()] };
+    assert_eq!(tournaments.create_tournament(solo).await.unwrap_err().code(), Code::InvalidArgument);
+}
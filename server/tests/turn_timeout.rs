@@ -0,0 +1,107 @@
+mod scenario;
+
+use std::time::Duration;
+
+use scenario::{eq, Scenario};
+use server::config::Config;
+use server::game::SharedGame;
+use server::tictactoe::Join;
+use tokio::sync::mpsc;
+
+fn config() -> Config {
+    Config { turn_timeout_secs: 30, ..Config::default() }
+}
+
+#[test]
+fn move_before_the_deadline_cancels_the_timeout() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .expect_status("bob", eq("ongoing"))
+        .advance(Duration::from_secs(20))
+        .move_("alice", 4)
+        .expect_state(|s| s.board[4] == "X")
+        // X의 타이머라면 30초에 끝났겠지만, 수를 둬서 취소되고 O의 차례는 20초부터 새로 셈
+        .advance(Duration::from_secs(25))
+        .game_state("alice", |s| s.status == "ongoing" && s.next_player == "O")
+        .move_("bob", 0)
+        .expect_state(|s| s.board[0] == "O" && s.status == "ongoing")
+        .run(config());
+}
+
+#[test]
+fn no_move_forfeits_exactly_at_the_deadline() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .spectator("carol", "alice")
+        .move_("alice", 4)
+        .expect_state(|s| s.board[4] == "X")
+        .advance(Duration::from_secs(29))
+        .game_state("alice", |s| s.status == "ongoing")
+        .advance(Duration::from_secs(1))
+        .expect("alice", |s| {
+            s.status == "X_win_by_resignation" && s.info_message == "Player O ran out of time. The game is forfeited." && s.lost_on_time == "O"
+        })
+        .expect_status("bob", eq("X_win_by_resignation"))
+        .expect_status("carol", eq("X_win_by_resignation"))
+        .run(config());
+}
+
+#[test]
+fn updates_carry_the_seconds_left_in_the_turn() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .expect_state(|s| s.status == "ongoing" && s.turn_deadline_seconds == 30)
+        .advance(Duration::from_secs(20))
+        .game_state("alice", |s| s.turn_deadline_seconds == 10)
+        // 수를 두면 다음 차례는 제한 시간 전체부터
+        .move_("alice", 4)
+        .expect_state(|s| s.board[4] == "X" && s.turn_deadline_seconds == 30 && s.lost_on_time.is_empty())
+        .advance(Duration::from_millis(24_500))
+        .game_state("bob", |s| s.next_player == "O" && s.turn_deadline_seconds == 6)
+        .resign("bob")
+        // 시간패가 아닌 기권은 lost_on_time이 비어 있고, 끝난 게임에는 남은 시간이 없음
+        .expect_state(|s| s.status == "X_win_by_resignation" && s.turn_deadline_seconds == 0 && s.lost_on_time.is_empty())
+        .run(config());
+}
+
+#[test]
+fn turn_timeout_is_off_by_default() {
+    Scenario::new()
+        .player("alice")
+        .player("bob")
+        .expect_state(|s| s.status == "ongoing" && s.turn_deadline_seconds == 0)
+        .advance(Duration::from_secs(120))
+        .move_("alice", 0)
+        .expect_state(|s| s.status == "ongoing" && s.board[0] == "X")
+        .run(Config::default());
+}
+
+#[tokio::test]
+async fn each_turn_gets_a_fresh_token_that_a_valid_move_cancels() {
+    let mut game = SharedGame::new("1".into(), 3, 3);
+    let (tx, _rx) = mpsc::channel(64);
+    for _ in 0..2 {
+        game.join_player(&Join::default(), tx.clone(), None).await.unwrap();
+    }
+
+    let (symbol, x_turn) = game.start_turn().unwrap();
+    assert_eq!(symbol, "X");
+    assert!(game.start_turn().is_none(), "이미 타이머가 있는 차례");
+
+    // 거부된 수는 차례를 끝내지 않음
+    assert!(game.apply_move("O", 0).is_err());
+    assert!(!x_turn.is_cancelled());
+
+    game.apply_move("X", 0).unwrap();
+    assert!(x_turn.is_cancelled());
+    let (symbol, o_turn) = game.start_turn().unwrap();
+    assert_eq!(symbol, "O");
+    assert!(!o_turn.is_cancelled());
+
+    game.resign("O");
+    assert!(o_turn.is_cancelled());
+    assert!(game.start_turn().is_none(), "끝난 게임");
+}
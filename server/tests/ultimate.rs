@@ -0,0 +1,257 @@
+mod scenario;
+
+use std::time::SystemTime;
+
+use scenario::Scenario;
+use server::config::Config;
+use server::error::MoveError;
+use server::game::{MoveOutcome, RecordedMove, SharedGame};
+use server::service::TicTacToeService;
+use server::tictactoe::tic_tac_toe_server::TicTacToe;
+use server::tictactoe::{CreateGameRequest, GameMode, GameState, Join};
+use server::ultimate::{self, position, split, SUB_BOARD_DRAW};
+use tokio::sync::mpsc;
+use tonic::{Code, Request, Status};
+
+/// 작은 보드 하나를 채우는 칸 (행 우선 9글자, '.'은 빈 칸)
+const X_TOP_ROW: &str = "XXX......";
+const O_TOP_ROW: &str = "OOO......";
+/// 가득 찼지만 줄이 없는 작은 보드
+const FULL_NO_LINE: &str = "XOXXOOOXX";
+
+fn empty_board() -> Vec<String> {
+    vec![String::new(); 81]
+}
+
+/// 작은 보드 `index`를 `cells`로 채움
+fn fill(board: &mut [String], index: usize, cells: &str) {
+    for (cell, c) in cells.chars().enumerate() {
+        board[position(index, cell)] = if c == '.' { String::new() } else { c.to_string() };
+    }
+}
+
+/// 두 자리가 모두 찬 얼티밋 게임
+async fn ultimate_game() -> (SharedGame, mpsc::Receiver<Result<GameState, Status>>) {
+    let mut game = SharedGame::new("1".into(), 9, 3);
+    game.mode = GameMode::Ultimate;
+    let (tx, rx) = mpsc::channel(256);
+    game.join_player(&Join::default(), tx.clone(), None).await.unwrap();
+    game.join_player(&Join::default(), tx, None).await.unwrap();
+    assert_eq!(game.status, "ongoing");
+    (game, rx)
+}
+
+/// 보드를 `board`로 바꾸고 마지막 수를 (심볼, 작은 보드, 칸)으로 정한 게임 (다음 차례는 그 상대)
+async fn arranged_game(board: Vec<String>, last: (&str, usize, usize)) -> SharedGame {
+    let (mut game, _rx) = ultimate_game().await;
+    let (symbol, sub_board, cell) = last;
+    game.board = board;
+    game.board[position(sub_board, cell)] = symbol.to_string();
+    game.history = vec![RecordedMove { symbol: symbol.into(), position: position(sub_board, cell) as u8, played_at: SystemTime::now(), mark: symbol.into() }];
+    game.next_player = if symbol == "X" { "O".into() } else { "X".into() };
+    game
+}
+
+#[test]
+fn positions_and_sub_board_cells_map_both_ways() {
+    for pos in 0..81 {
+        let (sub_board, cell) = split(pos);
+        assert!(sub_board < 9 && cell < 9);
+        assert_eq!(position(sub_board, cell), pos);
+    }
+    assert_eq!(position(0, 0), 0);
+    assert_eq!(position(0, 8), 20);
+    assert_eq!(position(4, 4), 40);
+    assert_eq!(position(8, 8), 80);
+    assert_eq!(split(3), (1, 0));
+    assert_eq!(split(27), (3, 0));
+}
+
+#[test]
+fn sub_board_results_use_the_line_logic() {
+    let mut board = empty_board();
+    fill(&mut board, 0, X_TOP_ROW);
+    fill(&mut board, 4, "O...O...O");
+    fill(&mut board, 8, FULL_NO_LINE);
+    fill(&mut board, 2, "XO.......");
+    let macro_board = ultimate::macro_board(&board);
+    assert_eq!(macro_board, ["X", "", "", "", "O", "", "", "", SUB_BOARD_DRAW]);
+}
+
+#[tokio::test]
+async fn first_move_may_go_anywhere() {
+    for pos in [0, 40, 80, 13] {
+        let (mut game, _rx) = ultimate_game().await;
+        assert_eq!(game.forced_board(), None);
+        assert_eq!(game.apply_move("X", pos), Ok(MoveOutcome::Continue));
+    }
+}
+
+#[tokio::test]
+async fn the_cell_played_picks_the_opponents_sub_board() {
+    let (mut game, _rx) = ultimate_game().await;
+    // X가 작은 보드 4의 칸 2에 두면 O는 작은 보드 2에 둬야 함
+    game.apply_move("X", position(4, 2)).unwrap();
+    assert_eq!(game.forced_board(), Some(2));
+    for wrong in [position(4, 0), position(0, 0), position(8, 8)] {
+        assert_eq!(game.apply_move("O", wrong), Err(MoveError::WrongSubBoard));
+    }
+    // 거부한 수는 아무것도 바꾸지 않음
+    assert_eq!(game.history.len(), 1);
+    assert_eq!(game.next_player, "O");
+
+    assert_eq!(game.apply_move("O", position(2, 7)), Ok(MoveOutcome::Continue));
+    assert_eq!(game.forced_board(), Some(7));
+    assert_eq!(game.apply_move("X", position(2, 0)), Err(MoveError::WrongSubBoard));
+    assert_eq!(game.apply_move("X", position(7, 7)), Ok(MoveOutcome::Continue));
+    // 같은 작은 보드를 가리키면 그 안에서 계속
+    assert_eq!(game.forced_board(), Some(7));
+}
+
+#[tokio::test]
+async fn classic_checks_still_come_first() {
+    let (mut game, _rx) = ultimate_game().await;
+    game.apply_move("X", position(4, 4)).unwrap();
+    assert_eq!(game.apply_move("X", position(4, 0)), Err(MoveError::NotYourTurn));
+    assert_eq!(game.apply_move("O", position(4, 4)), Err(MoveError::CellOccupied));
+    assert_eq!(game.apply_move("O", 81), Err(MoveError::OutOfRange));
+}
+
+#[tokio::test]
+async fn won_target_sub_board_frees_the_choice() {
+    let mut board = empty_board();
+    fill(&mut board, 3, X_TOP_ROW);
+    // O가 작은 보드 5의 칸 3에 둬서 X는 작은 보드 3으로 가야 하지만 이미 X가 가져감
+    let mut game = arranged_game(board, ("O", 5, 3)).await;
+    assert_eq!(game.forced_board(), None);
+    assert_eq!(game.apply_move("X", position(8, 0)), Ok(MoveOutcome::Continue));
+}
+
+#[tokio::test]
+async fn full_target_sub_board_frees_the_choice() {
+    let mut board = empty_board();
+    fill(&mut board, 6, FULL_NO_LINE);
+    let mut game = arranged_game(board, ("X", 1, 6)).await;
+    assert_eq!(game.forced_board(), None);
+    assert_eq!(game.apply_move("O", position(0, 4)), Ok(MoveOutcome::Continue));
+}
+
+#[tokio::test]
+async fn decided_sub_boards_are_closed_even_with_a_free_choice() {
+    let mut board = empty_board();
+    fill(&mut board, 3, X_TOP_ROW);
+    fill(&mut board, 6, FULL_NO_LINE);
+    let mut game = arranged_game(board, ("O", 5, 3)).await;
+    // 작은 보드 3은 빈 칸이 남아 있어도 이미 승부가 났으므로 둘 수 없음
+    assert_eq!(game.apply_move("X", position(3, 8)), Err(MoveError::SubBoardClosed));
+    assert_eq!(game.apply_move("X", position(6, 0)), Err(MoveError::CellOccupied));
+    let legal = ultimate::legal_moves(&game.board, Some(position(5, 3)));
+    assert!(legal.iter().all(|&pos| ![3, 6].contains(&split(pos).0)));
+    assert_eq!(legal.len(), 81 - 18 - 1);
+}
+
+#[tokio::test]
+async fn winning_a_sub_board_marks_the_macro_board() {
+    let mut board = empty_board();
+    fill(&mut board, 2, "XX.......");
+    let mut game = arranged_game(board, ("O", 0, 2)).await;
+    assert_eq!(game.forced_board(), Some(2));
+    // 작은 보드를 가져가도 게임은 계속되고, 가리킨 작은 보드(2)는 이제 닫혔으므로 O는 아무 데나 둠
+    assert_eq!(game.apply_move("X", position(2, 2)), Ok(MoveOutcome::Continue));
+    let update = game.create_update();
+    assert_eq!(update.macro_board[2], "X");
+    assert_eq!(update.forced_board, None);
+    assert_eq!(update.mode, GameMode::Ultimate as i32);
+    assert_eq!(update.board_size, 9);
+}
+
+#[tokio::test]
+async fn three_sub_boards_in_a_row_win_the_game() {
+    let mut board = empty_board();
+    fill(&mut board, 0, X_TOP_ROW);
+    fill(&mut board, 4, X_TOP_ROW);
+    fill(&mut board, 8, "XX.......");
+    fill(&mut board, 1, O_TOP_ROW);
+    let mut game = arranged_game(board, ("O", 7, 8)).await;
+    assert_eq!(game.forced_board(), Some(8));
+    // 작은 보드 8을 가져가 큰 보드의 대각선 (0, 4, 8)을 이음
+    let outcome = game.apply_move("X", position(8, 2));
+    assert_eq!(outcome, Ok(MoveOutcome::Won { winner: "X".into(), line: vec![0, 4, 8] }));
+    assert_eq!(game.status, "X_win");
+    assert_eq!(game.forced_board(), None);
+    assert_eq!(game.apply_move("O", position(2, 0)), Err(MoveError::GameNotOngoing));
+}
+
+#[tokio::test]
+async fn drawn_sub_boards_never_count_toward_a_line() {
+    let mut board = empty_board();
+    fill(&mut board, 0, X_TOP_ROW);
+    fill(&mut board, 1, FULL_NO_LINE);
+    fill(&mut board, 2, "XX.......");
+    let mut game = arranged_game(board, ("O", 5, 2)).await;
+    game.apply_move("X", position(2, 2)).unwrap();
+    assert_eq!(ultimate::winning_line(&game.board), None);
+    assert_eq!(game.status, "ongoing");
+}
+
+#[tokio::test]
+async fn deciding_every_sub_board_without_a_line_is_a_draw() {
+    // 큰 보드 X O X / X O O / O X X 는 줄이 없음 (마지막 작은 보드 8은 X가 이번 수로 가져감)
+    let owners = ["X", "O", "X", "X", "O", "O", "O", "X"];
+    let mut board = empty_board();
+    for (index, owner) in owners.iter().enumerate() {
+        fill(&mut board, index, if *owner == "X" { X_TOP_ROW } else { O_TOP_ROW });
+    }
+    fill(&mut board, 8, "XX.......");
+    let mut game = arranged_game(board, ("O", 6, 8)).await;
+    assert_eq!(game.apply_move("X", position(8, 2)), Ok(MoveOutcome::Draw));
+    assert_eq!(game.status, "draw");
+    assert!(ultimate::all_decided(&game.board));
+}
+
+#[tokio::test]
+async fn classic_games_ignore_the_sub_board_rules() {
+    let mut game = SharedGame::new("1".into(), 9, 3);
+    let (tx, _rx) = mpsc::channel(64);
+    game.join_player(&Join::default(), tx.clone(), None).await.unwrap();
+    game.join_player(&Join::default(), tx, None).await.unwrap();
+    game.apply_move("X", position(4, 2)).unwrap();
+    assert_eq!(game.apply_move("O", position(8, 8)), Ok(MoveOutcome::Continue));
+    let update = game.create_update();
+    assert_eq!(update.mode, GameMode::Classic as i32);
+    assert_eq!(update.forced_board, None);
+    assert!(update.macro_board.is_empty());
+}
+
+#[tokio::test]
+async fn create_game_builds_a_nine_by_nine_ultimate_board() {
+    let service = TicTacToeService::new(Config::default());
+    let request = CreateGameRequest { mode: GameMode::Ultimate as i32, ..CreateGameRequest::default() };
+    let created = service.create_game(Request::new(request)).await.unwrap().into_inner();
+    assert_eq!((created.board_size, created.win_length), (9, 3));
+
+    let sized = CreateGameRequest { mode: GameMode::Ultimate as i32, board_size: 5, ..CreateGameRequest::default() };
+    let error = service.create_game(Request::new(sized)).await.unwrap_err();
+    assert_eq!(error.code(), Code::InvalidArgument);
+
+    let unknown = CreateGameRequest { mode: 7, ..CreateGameRequest::default() };
+    let error = service.create_game(Request::new(unknown)).await.unwrap_err();
+    assert_eq!(error.code(), Code::InvalidArgument);
+}
+
+#[test]
+fn sub_board_rules_are_enforced_over_the_play_stream() {
+    let ultimate = CreateGameRequest { mode: GameMode::Ultimate as i32, ..CreateGameRequest::default() };
+    Scenario::new()
+        .create_player("alice", ultimate)
+        .join_game("bob", "alice")
+        .expect_state(|s| s.board.len() == 81 && s.macro_board.len() == 9 && s.forced_board.is_none())
+        // 가운데 작은 보드의 칸 2에 두면 상대는 작은 보드 2로
+        .move_("alice", position(4, 2) as i32)
+        .expect_state(|s| s.forced_board == Some(2))
+        .move_("bob", position(0, 0) as i32)
+        .expect("bob", |s| s.error_message == "You must play in the highlighted sub-board.")
+        .move_("bob", position(2, 4) as i32)
+        .expect_state(|s| s.board[position(2, 4)] == "O" && s.status == "ongoing" && s.forced_board == Some(4))
+        .run(Config::default());
+}
@@ -0,0 +1,77 @@
+use server::error::MoveError;
+use server::events::fold;
+use server::game::{MoveOutcome, SharedGame};
+use server::tictactoe::{GameMode, GameState, Join};
+use tokio::sync::mpsc;
+use tonic::Status;
+
+/// 두 자리가 모두 찬 3×3 게임
+async fn game(mode: GameMode) -> (SharedGame, mpsc::Receiver<Result<GameState, Status>>) {
+    let mut game = SharedGame::new("1".into(), 3, 3);
+    game.mode = mode;
+    let (tx, rx) = mpsc::channel(64);
+    game.join_player(&Join::default(), tx.clone(), None).await.unwrap();
+    game.join_player(&Join::default(), tx, None).await.unwrap();
+    assert_eq!(game.status, "ongoing");
+    (game, rx)
+}
+
+/// 차례대로 (칸, 놓을 심볼)을 둠 (마지막 수의 결과 반환)
+fn play(game: &mut SharedGame, moves: &[(usize, &str)]) -> MoveOutcome {
+    let mut outcome = MoveOutcome::Continue;
+    for &(pos, mark) in moves {
+        let symbol = game.next_player.clone();
+        outcome = game.apply_move_as(&symbol, pos, mark).unwrap();
+    }
+    outcome
+}
+
+#[tokio::test]
+async fn a_player_wins_with_the_opponents_symbol() {
+    let (mut game, _rx) = game(GameMode::Wild).await;
+    // X 플레이어가 O를 0, 1, 2에 놓아 줄을 완성
+    let outcome = play(&mut game, &[(0, "O"), (8, "X"), (1, "O"), (6, "X"), (2, "O")]);
+    assert_eq!(outcome, MoveOutcome::Won { winner: "X".into(), line: vec![0, 1, 2] });
+    assert_eq!(game.status, "X_win");
+    assert_eq!(game.board[..3], ["O", "O", "O"]);
+}
+
+#[tokio::test]
+async fn leaving_two_in_a_row_hands_the_win_to_the_opponent() {
+    let (mut game, _rx) = game(GameMode::Wild).await;
+    // O 플레이어가 1에 X를 놓아 0-1을 열어 두는 수도 받아들이며, 다음 차례인 X 플레이어가 2에 X를 놓아 이김
+    assert_eq!(play(&mut game, &[(0, "X"), (1, "X")]), MoveOutcome::Continue);
+    assert_eq!(game.next_player, "X");
+    assert_eq!(play(&mut game, &[(2, "X")]), MoveOutcome::Won { winner: "X".into(), line: vec![0, 1, 2] });
+}
+
+#[tokio::test]
+async fn wild_moves_need_x_or_o() {
+    let (mut game, _rx) = game(GameMode::Wild).await;
+    for mark in ["", "Z", "x"] {
+        assert_eq!(game.apply_move_as("X", 4, mark), Err(MoveError::InvalidChosenSymbol));
+    }
+    assert!(game.board.iter().all(String::is_empty) && game.history.is_empty());
+    assert_eq!(game.apply_move_as("X", 4, "O"), Ok(MoveOutcome::Continue));
+    assert_eq!((game.board[4].as_str(), game.history[0].symbol.as_str(), game.history[0].mark.as_str()), ("O", "X", "O"));
+}
+
+#[tokio::test]
+async fn classic_games_ignore_the_chosen_symbol() {
+    let (mut game, _rx) = game(GameMode::Classic).await;
+    assert_eq!(game.apply_move_as("X", 4, "O"), Ok(MoveOutcome::Continue));
+    assert_eq!(game.board[4], "X");
+    assert_eq!(game.apply_move_as("O", 0, ""), Ok(MoveOutcome::Continue));
+    assert_eq!(game.board[0], "O");
+}
+
+#[tokio::test]
+async fn events_fold_to_the_placed_symbols() {
+    let (mut game, _rx) = game(GameMode::Wild).await;
+    play(&mut game, &[(0, "O"), (4, "O"), (8, "X")]);
+    let folded = fold(game.events());
+    assert_eq!(folded.board, game.board);
+    assert_eq!(folded.next_player, "O");
+    let placed: Vec<&str> = folded.history.iter().map(|m| m.chosen_symbol.as_str()).collect();
+    assert_eq!(placed, ["O", "O", "X"]);
+}